@@ -1,6 +1,8 @@
 use oxiri::Iri;
 use oxrdf::NamedNode;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 use reqwest::header;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 use reqwest::header::USER_AGENT;
 use serde::de;
 use serde::de::Visitor;
@@ -9,6 +11,7 @@ use serde::Deserializer;
 use serde::Serialize;
 use serde::Serializer;
 use std::fmt;
+#[cfg(all(feature = "fs", not(target_arch = "wasm32")))]
 use std::fs;
 use std::str::FromStr;
 use url::Url;
@@ -130,44 +133,8 @@ impl IriS {
             })?,
         };
         match url.scheme() {
-            "file" => {
-                let path = url
-                    .to_file_path()
-                    .map_err(|_| IriSError::ConvertingFileUrlToPath { url: url.clone() })?;
-                let path_name = path.to_string_lossy().to_string();
-                let body = fs::read_to_string(path).map_err(|e| IriSError::IOErrorFile {
-                    path: path_name,
-                    url: Box::new(url),
-                    error: format!("{e}"),
-                })?;
-                Ok(body)
-            }
-            _ => {
-                let mut headers = header::HeaderMap::new();
-                /* TODO: Add a parameter with the Accept header ?
-                headers.insert(
-                    ACCEPT,
-                    header::HeaderValue::from_static(""),
-                );*/
-                headers.insert(USER_AGENT, header::HeaderValue::from_static("rudof"));
-                let client = reqwest::blocking::Client::builder()
-                    .default_headers(headers)
-                    .build()
-                    .map_err(|e| IriSError::ReqwestClientCreation {
-                        error: format!("{e}"),
-                    })?;
-                let body = client
-                    .get(url)
-                    .send()
-                    .map_err(|e| IriSError::ReqwestError {
-                        error: format!("{e}"),
-                    })?
-                    .text()
-                    .map_err(|e| IriSError::ReqwestTextError {
-                        error: format!("{e}"),
-                    })?;
-                Ok(body)
-            }
+            "file" => read_file(url),
+            _ => get_url(url),
         }
     }
 
@@ -176,6 +143,62 @@ impl IriS {
     } */
 }
 
+/// Reads the content of a local file from an URL with the `file` scheme
+#[cfg(all(feature = "fs", not(target_arch = "wasm32")))]
+fn read_file(url: Url) -> Result<String, IriSError> {
+    let path = url
+        .to_file_path()
+        .map_err(|_| IriSError::ConvertingFileUrlToPath { url: url.clone() })?;
+    let path_name = path.to_string_lossy().to_string();
+    let body = fs::read_to_string(path).map_err(|e| IriSError::IOErrorFile {
+        path: path_name,
+        url: Box::new(url),
+        error: format!("{e}"),
+    })?;
+    Ok(body)
+}
+
+/// Reading local files needs the `fs` feature and is not available in wasm targets like the browser
+#[cfg(any(not(feature = "fs"), target_arch = "wasm32"))]
+fn read_file(url: Url) -> Result<String, IriSError> {
+    Err(IriSError::FileNotSupported { url })
+}
+
+/// Obtains the content of an URL with an HTTP GET request
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+fn get_url(url: Url) -> Result<String, IriSError> {
+    let mut headers = header::HeaderMap::new();
+    /* TODO: Add a parameter with the Accept header ?
+    headers.insert(
+        ACCEPT,
+        header::HeaderValue::from_static(""),
+    );*/
+    headers.insert(USER_AGENT, header::HeaderValue::from_static("rudof"));
+    let client = reqwest::blocking::Client::builder()
+        .default_headers(headers)
+        .build()
+        .map_err(|e| IriSError::ReqwestClientCreation {
+            error: format!("{e}"),
+        })?;
+    let body = client
+        .get(url)
+        .send()
+        .map_err(|e| IriSError::ReqwestError {
+            error: format!("{e}"),
+        })?
+        .text()
+        .map_err(|e| IriSError::ReqwestTextError {
+            error: format!("{e}"),
+        })?;
+    Ok(body)
+}
+
+/// HTTP requests need the `http` feature and are not available in wasm targets like the browser
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+fn get_url(url: Url) -> Result<String, IriSError> {
+    Err(IriSError::HttpNotSupported { url })
+}
+
 impl fmt::Display for IriS {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.iri.as_str())
@@ -42,6 +42,12 @@ pub enum IriSError {
     #[error("Http request error as String: {error}")]
     ReqwestTextError { error: String },
 
+    #[error("Can't obtain {url}: HTTP requests need the http feature and a non-wasm target")]
+    HttpNotSupported { url: Url },
+
+    #[error("Can't read {url}: reading local files needs the fs feature and a non-wasm target")]
+    FileNotSupported { url: Url },
+
     #[error("trying to obtain a path from file scheme Url: {url}")]
     ConvertingFileUrlToPath { url: Url },
 
@@ -0,0 +1,15 @@
+from pyrudof import rdf, shex
+
+schema = shex.ShExParser.parse("""prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { :name xsd:string ; :knows @:Person * }""")
+
+data = rdf.RdfData.from_str("""prefix : <http://example.org/>
+:alice :name "Alice" ; :knows :bob .
+:bob :name 23 .""")
+
+result = shex.validate(schema, data, ":alice@:Person,:bob@:Person")
+
+print(f"Result:\n{result}")
+for association in result.nonconformant():
+    print(f"{association.node} does not conform to {association.shape}: {association.reason}")
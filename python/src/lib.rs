@@ -2,10 +2,16 @@ use pyo3::prelude::*;
 use pyo3::wrap_pymodule;
 
 use crate::pyconvert::convert;
+use crate::pyerror::add_exceptions;
+use crate::pyrdf::rdf;
 use crate::pyshacl::shacl;
+use crate::pyshex::shex;
 
 mod pyconvert;
+mod pyerror;
+mod pyrdf;
 mod pyshacl;
+mod pyshex;
 
 // Rudof Python bindings
 #[pymodule]
@@ -14,6 +20,10 @@ fn pyrudof(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add("__version__", env!("CARGO_PKG_VERSION"))?;
     module.add("__author__", env!("CARGO_PKG_AUTHORS").replace(':', "\n"))?;
 
+    add_exceptions(module)?;
+
+    module.add_wrapped(wrap_pymodule!(rdf))?;
+    module.add_wrapped(wrap_pymodule!(shex))?;
     module.add_wrapped(wrap_pymodule!(shacl))?;
     module.add_wrapped(wrap_pymodule!(convert))?;
 
@@ -1,34 +1,287 @@
 use dctap::{DCTap, TapConfig};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use shapes_converter::{Tap2ShEx, Tap2ShExConfig};
-use shex_compact::ShExFormatter;
+use shacl_ast::{ShaclParser, ShaclWriter};
+use shapes_converter::{
+    ConverterConfig, ShEx2Html, ShEx2Shacl, ShEx2Sparql, ShEx2Uml, Shacl2ShEx, Tap2ShEx,
+    UmlGenerationMode,
+};
+use shex_ast::Schema as SchemaJson;
+use shex_compact::{ShExFormatter, ShExParser, ShapeMapParser};
+use srdf::{ReaderMode, SRDFGraph};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
+use crate::pyerror::{ConversionError, DCTapError, ShExError, ShaclError};
+use crate::pyrdf::rdf_format;
+
 #[pymodule]
 pub fn convert(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(dctap2shex, module)?)?;
+    module.add_function(wrap_pyfunction!(dctap2uml, module)?)?;
+    module.add_function(wrap_pyfunction!(dctap2html, module)?)?;
+    module.add_function(wrap_pyfunction!(shacl2shex, module)?)?;
+    module.add_function(wrap_pyfunction!(shex2shacl, module)?)?;
+    module.add_function(wrap_pyfunction!(shex2sparql, module)?)?;
+    module.add_function(wrap_pyfunction!(shex2uml, module)?)?;
+    module.add_function(wrap_pyfunction!(shex2html, module)?)?;
     Ok(())
 }
 
 #[pyfunction]
-#[pyo3(signature = (input))]
-pub fn dctap2shex(input: &str, py: Python<'_>) -> PyResult<String> {
+#[pyo3(signature = (input, result_format = "shexc", config = None))]
+pub fn dctap2shex(
+    input: &str,
+    result_format: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let result_format = shex_format(result_format)?;
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let dctap = parse_dctap(input, &config.tap_config())?;
+        let converter = Tap2ShEx::new(&config.tap2shex_config());
+        let schema = converter.convert(&dctap).map_err(|e| {
+            ConversionError::new_err(format!("Error converting DCTAP to ShEx: {e}"))
+        })?;
+        show_shex(&schema, result_format)
+    })
+}
+
+/// Converts a DCTAP in CSV to PlantUML, showing only the neighbours of `shape` if it is given
+#[pyfunction]
+#[pyo3(signature = (input, shape = None, config = None))]
+pub fn dctap2uml(
+    input: &str,
+    shape: Option<&str>,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let dctap = parse_dctap(input, &config.tap_config())?;
+        let schema = Tap2ShEx::new(&config.tap2shex_config())
+            .convert(&dctap)
+            .map_err(|e| {
+                ConversionError::new_err(format!("Error converting DCTAP to ShEx: {e}"))
+            })?;
+        let mut converter = ShEx2Uml::new(&config.shex2uml_config());
+        converter
+            .convert(&schema)
+            .map_err(|e| ConversionError::new_err(format!("Error converting ShEx to UML: {e}")))?;
+        plantuml(&converter, shape)
+    })
+}
+
+/// Generates the HTML pages of a DCTAP in CSV in `target_folder` and returns the path of the landing page
+#[pyfunction]
+#[pyo3(signature = (input, target_folder, config = None))]
+pub fn dctap2html(
+    input: &str,
+    target_folder: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
     py.allow_threads(|| {
-        let reader = input.as_bytes();
-        let dctap = DCTap::from_reader(reader, &TapConfig::default())
-            .map_err(|e| PyValueError::new_err(format!("Error reading DCTAP {e}")))?;
-        let converter = Tap2ShEx::new(&Tap2ShExConfig::default());
-        let schema = converter
+        let config = converter_config(config)?;
+        let dctap = parse_dctap(input, &config.tap_config())?;
+        let schema = Tap2ShEx::new(&config.tap2shex_config())
             .convert(&dctap)
-            .map_err(|e| PyValueError::new_err(format!("Error converting DCTAP to ShEx: {e}")))?;
-        let formatter = ShExFormatter::default().without_colors();
-        let str = formatter.format_schema(&schema);
-        Ok(str)
+            .map_err(|e| {
+                ConversionError::new_err(format!("Error converting DCTAP to ShEx: {e}"))
+            })?;
+        html(&schema, target_folder, &config)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (input, format = "turtle", result_format = "shexc", config = None))]
+pub fn shacl2shex(
+    input: &str,
+    format: &str,
+    result_format: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let format = rdf_format(format)?;
+    let result_format = shex_format(result_format)?;
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let rdf = SRDFGraph::from_str(input, &format, None, &ReaderMode::Strict)
+            .map_err(|e| ShaclError::new_err(format!("Error reading SHACL shapes: {e}")))?;
+        let schema = ShaclParser::new(rdf)
+            .parse()
+            .map_err(|e| ShaclError::new_err(format!("Error parsing SHACL shapes: {e}")))?;
+        let mut converter = Shacl2ShEx::new(&config.shacl2shex_config());
+        converter.convert(&schema).map_err(|e| {
+            ConversionError::new_err(format!("Error converting SHACL to ShEx: {e}"))
+        })?;
+        show_shex(converter.current_shex(), result_format)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (input, format = "shexc", result_format = "turtle", config = None))]
+pub fn shex2shacl(
+    input: &str,
+    format: &str,
+    result_format: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let format = shex_format(format)?;
+    let result_format = rdf_format(result_format)?;
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let schema = parse_shex(input, format)?;
+        let mut converter = ShEx2Shacl::new(&config.shex2shacl_config());
+        converter.convert(&schema).map_err(|e| {
+            ConversionError::new_err(format!("Error converting ShEx to SHACL: {e}"))
+        })?;
+        let mut shacl_writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
+        shacl_writer
+            .write(converter.current_shacl())
+            .map_err(|e| ShaclError::new_err(e.to_string()))?;
+        let mut output = Vec::new();
+        shacl_writer
+            .serialize(result_format, &mut output)
+            .map_err(|e| ShaclError::new_err(e.to_string()))?;
+        String::from_utf8(output).map_err(|e| ShaclError::new_err(e.to_string()))
+    })
+}
+
+/// Generates a SPARQL query that obtains the nodes that match `shape`, or the start shape if it is not given
+#[pyfunction]
+#[pyo3(signature = (input, shape = None, format = "shexc", config = None))]
+pub fn shex2sparql(
+    input: &str,
+    shape: Option<&str>,
+    format: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let format = shex_format(format)?;
+    let shape = match shape {
+        None => None,
+        Some(shape) => Some(
+            ShapeMapParser::parse_iri_ref(shape)
+                .map_err(|e| PyValueError::new_err(format!("Error parsing shape {shape}: {e}")))?,
+        ),
+    };
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let schema = parse_shex(input, format)?;
+        let query = ShEx2Sparql::new(&config.shex2sparql_config())
+            .convert(&schema, shape)
+            .map_err(|e| {
+                ConversionError::new_err(format!("Error converting ShEx to SPARQL: {e}"))
+            })?;
+        Ok(query.to_string())
     })
 }
 
+/// Converts a ShEx schema to PlantUML, showing only the neighbours of `shape` if it is given
+#[pyfunction]
+#[pyo3(signature = (input, shape = None, format = "shexc", config = None))]
+pub fn shex2uml(
+    input: &str,
+    shape: Option<&str>,
+    format: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let format = shex_format(format)?;
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let schema = parse_shex(input, format)?;
+        let mut converter = ShEx2Uml::new(&config.shex2uml_config());
+        converter
+            .convert(&schema)
+            .map_err(|e| ConversionError::new_err(format!("Error converting ShEx to UML: {e}")))?;
+        plantuml(&converter, shape)
+    })
+}
+
+/// Generates the HTML pages of a ShEx schema in `target_folder` and returns the path of the landing page
+#[pyfunction]
+#[pyo3(signature = (input, target_folder, format = "shexc", config = None))]
+pub fn shex2html(
+    input: &str,
+    target_folder: &str,
+    format: &str,
+    config: Option<&str>,
+    py: Python<'_>,
+) -> PyResult<String> {
+    let format = shex_format(format)?;
+    py.allow_threads(|| {
+        let config = converter_config(config)?;
+        let schema = parse_shex(input, format)?;
+        html(&schema, target_folder, &config)
+    })
+}
+
+fn converter_config(path: Option<&str>) -> PyResult<ConverterConfig> {
+    match path {
+        None => Ok(ConverterConfig::default()),
+        Some(path) => ConverterConfig::from_path(path).map_err(|e| {
+            PyValueError::new_err(format!("Error reading converter config {path}: {e}"))
+        }),
+    }
+}
+
+fn parse_dctap(input: &str, config: &TapConfig) -> PyResult<DCTap> {
+    DCTap::from_reader(input.as_bytes(), config)
+        .map_err(|e| DCTapError::new_err(format!("Error reading DCTAP {e}")))
+}
+
+fn parse_shex(input: &str, format: ShExFormat) -> PyResult<SchemaJson> {
+    match format {
+        ShExFormat::ShExC => ShExParser::parse(input, None)
+            .map_err(|e| ShExError::new_err(format!("Error parsing ShExC: {e}"))),
+        ShExFormat::ShExJ => serde_json::from_str::<SchemaJson>(input)
+            .map_err(|e| ShExError::new_err(format!("Error parsing ShExJ: {e}"))),
+    }
+}
+
+fn show_shex(schema: &SchemaJson, format: ShExFormat) -> PyResult<String> {
+    match format {
+        ShExFormat::ShExC => Ok(ShExFormatter::default()
+            .without_colors()
+            .format_schema(schema)),
+        ShExFormat::ShExJ => serde_json::to_string_pretty(schema)
+            .map_err(|e| ShExError::new_err(format!("Error converting schema to ShExJ: {e}"))),
+    }
+}
+
+fn shex_format(str: &str) -> PyResult<ShExFormat> {
+    ShExFormat::from_str(str).map_err(PyValueError::new_err)
+}
+
+fn plantuml(converter: &ShEx2Uml, shape: Option<&str>) -> PyResult<String> {
+    let mode = match shape {
+        None => UmlGenerationMode::all(),
+        Some(shape) => UmlGenerationMode::neighs(shape),
+    };
+    let mut output = Vec::new();
+    converter
+        .as_plantuml(&mut output, &mode)
+        .map_err(|e| ConversionError::new_err(format!("Error generating PlantUML: {e}")))?;
+    String::from_utf8(output).map_err(|e| ConversionError::new_err(e.to_string()))
+}
+
+fn html(schema: &SchemaJson, target_folder: &str, config: &ConverterConfig) -> PyResult<String> {
+    let config = config.shex2html_config().with_target_folder(target_folder);
+    let landing_page = config.landing_page().to_string_lossy().to_string();
+    let mut converter = ShEx2Html::new(config);
+    converter
+        .convert(schema)
+        .map_err(|e| ConversionError::new_err(format!("Error converting ShEx to HTML: {e}")))?;
+    converter
+        .export_schema()
+        .map_err(|e| ConversionError::new_err(format!("Error generating HTML pages: {e}")))?;
+    Ok(landing_page)
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum ShExFormat {
     ShExC,
@@ -0,0 +1,54 @@
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+create_exception!(
+    pyrudof,
+    RudofError,
+    PyException,
+    "Base class of the errors raised by pyrudof"
+);
+create_exception!(
+    pyrudof,
+    RdfDataError,
+    RudofError,
+    "Error reading, querying or serializing RDF data"
+);
+create_exception!(
+    pyrudof,
+    ShExError,
+    RudofError,
+    "Error parsing, compiling or validating ShEx schemas"
+);
+create_exception!(
+    pyrudof,
+    ShapeMapError,
+    RudofError,
+    "Error parsing or reading shape maps"
+);
+create_exception!(
+    pyrudof,
+    ShaclError,
+    RudofError,
+    "Error parsing SHACL shapes or validating against them"
+);
+create_exception!(pyrudof, DCTapError, RudofError, "Error reading DCTAP files");
+create_exception!(
+    pyrudof,
+    ConversionError,
+    RudofError,
+    "Error converting between schema languages"
+);
+
+/// Registers the exception classes so that they can be caught as `pyrudof.ShExError`, etc.
+pub fn add_exceptions(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add("RudofError", py.get_type_bound::<RudofError>())?;
+    module.add("RdfDataError", py.get_type_bound::<RdfDataError>())?;
+    module.add("ShExError", py.get_type_bound::<ShExError>())?;
+    module.add("ShapeMapError", py.get_type_bound::<ShapeMapError>())?;
+    module.add("ShaclError", py.get_type_bound::<ShaclError>())?;
+    module.add("DCTapError", py.get_type_bound::<DCTapError>())?;
+    module.add("ConversionError", py.get_type_bound::<ConversionError>())?;
+    Ok(())
+}
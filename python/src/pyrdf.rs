@@ -0,0 +1,243 @@
+use iri_s::IriS;
+use oxrdf::{NamedNode as OxNamedNode, Subject as OxSubject, Term as OxTerm};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use sparql_service::RdfData;
+use srdf::{QuerySRDF2, RDFFormat, ReaderMode, SRDFBuilder, SRDFGraph, SRDFSparql};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::str::FromStr;
+
+use crate::pyerror::RdfDataError;
+
+#[pymodule]
+pub fn rdf(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyRdfData>()?;
+    Ok(())
+}
+
+/// RDF data that can be loaded from strings, built triple by triple and merged with SPARQL endpoints
+#[pyclass(name = "RdfData", module = "pyrudof.rdf")]
+#[derive(Clone)]
+pub struct PyRdfData {
+    pub(crate) inner: RdfData,
+}
+
+#[pymethods]
+impl PyRdfData {
+    /// Creates an empty in-memory graph
+    #[new]
+    pub fn new() -> Self {
+        PyRdfData {
+            inner: RdfData::empty(),
+        }
+    }
+
+    /// Reads RDF data from a string in the given format
+    #[staticmethod]
+    #[pyo3(signature = (data, format = "turtle", base = None))]
+    pub fn from_str(data: &str, format: &str, base: Option<&str>) -> PyResult<Self> {
+        let mut rdf = PyRdfData::new();
+        rdf.merge_str(data, format, base)?;
+        Ok(rdf)
+    }
+
+    /// Adds the RDF data contained in a string to the in-memory graph
+    #[pyo3(signature = (data, format = "turtle", base = None))]
+    pub fn merge_str(&mut self, data: &str, format: &str, base: Option<&str>) -> PyResult<()> {
+        let format = rdf_format(format)?;
+        let base = match base {
+            None => None,
+            Some(base) => Some(
+                oxiri::Iri::parse(base.to_string())
+                    .map_err(|e| PyValueError::new_err(format!("Invalid base IRI {base}: {e}")))?,
+            ),
+        };
+        self.inner
+            .merge_from_reader(Cursor::new(data), &format, base, &ReaderMode::Strict)
+            .map_err(|e| RdfDataError::new_err(format!("Error reading RDF data: {e}")))
+    }
+
+    /// Adds a SPARQL endpoint, given by its IRI or by a known name like `wikidata`,
+    /// whose data is merged with the rest of the data
+    pub fn add_endpoint(&mut self, endpoint: &str) -> PyResult<()> {
+        let endpoint = match IriS::from_str(endpoint) {
+            Ok(iri) => SRDFSparql::new(&iri),
+            Err(_) => SRDFSparql::from_str(endpoint),
+        }
+        .map_err(|e| RdfDataError::new_err(format!("Error creating endpoint {endpoint}: {e}")))?;
+        self.inner.add_endpoint(endpoint).map_err(cnv_err)
+    }
+
+    pub fn add_prefix(&mut self, alias: &str, iri: &str) -> PyResult<()> {
+        let iri = IriS::from_str(iri)
+            .map_err(|e| PyValueError::new_err(format!("Invalid IRI {iri}: {e}")))?;
+        self.inner.add_prefix(alias, &iri).map_err(cnv_err)
+    }
+
+    /// Declared prefixes as a dictionary from aliases to IRIs
+    pub fn prefixes(&self) -> HashMap<String, String> {
+        self.inner
+            .prefixmap()
+            .iter()
+            .map(|(alias, iri)| (alias.clone(), iri.as_str().to_string()))
+            .collect()
+    }
+
+    /// Adds a triple to the in-memory graph.
+    ///
+    /// Terms are written in N-Triples syntax, like `<http://example.org/alice>` or `"Alice"@en`,
+    /// or as prefixed names like `:alice` that are resolved with the declared prefixes
+    pub fn add_triple(&mut self, subject: &str, predicate: &str, object: &str) -> PyResult<()> {
+        let subject = match self.parse_term(subject)? {
+            OxTerm::NamedNode(iri) => OxSubject::NamedNode(iri),
+            OxTerm::BlankNode(bnode) => OxSubject::BlankNode(bnode),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "{subject} can't be used as subject"
+                )))
+            }
+        };
+        let predicate = match self.parse_term(predicate)? {
+            OxTerm::NamedNode(iri) => iri,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "{predicate} can't be used as predicate"
+                )))
+            }
+        };
+        let object = self.parse_term(object)?;
+        self.inner
+            .add_triple(&subject, &predicate, &object)
+            .map_err(cnv_err)
+    }
+
+    /// Runs a SPARQL SELECT query and returns a list with a dictionary for each solution,
+    /// whose values are the bound terms in N-Triples syntax
+    pub fn query(&self, query: &str, py: Python<'_>) -> PyResult<Vec<PyObject>> {
+        let solutions = py.allow_threads(|| {
+            let results = self.inner.query_select(query).map_err(cnv_err)?;
+            let variables: Vec<String> = results
+                .variables()
+                .map(|v| v.as_str().to_string())
+                .collect();
+            let solutions: Vec<Vec<(String, String)>> = results
+                .iter()
+                .map(|solution| {
+                    variables
+                        .iter()
+                        .filter_map(|v| {
+                            solution
+                                .find_solution(v.as_str())
+                                .map(|term| (v.clone(), term.to_string()))
+                        })
+                        .collect()
+                })
+                .collect();
+            Ok::<_, PyErr>(solutions)
+        })?;
+        let mut result = Vec::new();
+        for solution in solutions {
+            let dict = PyDict::new_bound(py);
+            for (var, term) in solution {
+                dict.set_item(var, term)?;
+            }
+            result.push(dict.into());
+        }
+        Ok(result)
+    }
+
+    /// Runs a SPARQL ASK query
+    pub fn ask(&self, query: &str, py: Python<'_>) -> PyResult<bool> {
+        py.allow_threads(|| self.inner.query_ask(query).map_err(cnv_err))
+    }
+
+    /// Runs a SPARQL CONSTRUCT or DESCRIBE query and serializes the resulting triples
+    #[pyo3(signature = (query, format = "turtle"))]
+    pub fn construct(&self, query: &str, format: &str, py: Python<'_>) -> PyResult<String> {
+        let format = rdf_format(format)?;
+        py.allow_threads(|| {
+            let triples = self.inner.query_construct(query).map_err(cnv_err)?;
+            let mut graph = SRDFGraph::new();
+            graph
+                .add_prefix_map(self.inner.prefixmap())
+                .map_err(|e| RdfDataError::new_err(e.to_string()))?;
+            for triple in triples {
+                graph
+                    .add_triple(&triple.subj(), &triple.pred(), &triple.obj())
+                    .map_err(|e| RdfDataError::new_err(e.to_string()))?;
+            }
+            let mut output = Vec::new();
+            graph
+                .serialize(format, &mut output)
+                .map_err(|e| RdfDataError::new_err(e.to_string()))?;
+            to_string(output)
+        })
+    }
+
+    /// Serializes the in-memory graph
+    #[pyo3(signature = (format = "turtle"))]
+    pub fn serialize(&self, format: &str, py: Python<'_>) -> PyResult<String> {
+        let format = rdf_format(format)?;
+        py.allow_threads(|| {
+            let mut output = Vec::new();
+            self.inner.serialize(format, &mut output).map_err(cnv_err)?;
+            to_string(output)
+        })
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.graph().map(|graph| graph.len()).unwrap_or(0)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "RdfData(triples={}, endpoints={})",
+            self.__len__(),
+            self.inner.endpoints().len()
+        )
+    }
+}
+
+impl PyRdfData {
+    fn parse_term(&self, str: &str) -> PyResult<OxTerm> {
+        let str = str.trim();
+        if str.starts_with('<') || str.starts_with('"') || str.starts_with("_:") {
+            OxTerm::from_str(str)
+                .map_err(|e| PyValueError::new_err(format!("Invalid RDF term {str}: {e}")))
+        } else {
+            let iri = self
+                .inner
+                .prefixmap()
+                .resolve(str)
+                .map_err(|e| PyValueError::new_err(format!("Can't resolve {str}: {e}")))?;
+            Ok(OxNamedNode::new_unchecked(iri.as_str()).into())
+        }
+    }
+}
+
+impl Default for PyRdfData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Obtains an RDF format from its name, like `turtle`, or its usual file extension, like `ttl`
+pub fn rdf_format(str: &str) -> PyResult<RDFFormat> {
+    match str.to_lowercase().as_str() {
+        "turtle" => Ok(RDFFormat::Turtle),
+        "ntriples" | "n-triples" => Ok(RDFFormat::NTriples),
+        "rdfxml" | "rdf/xml" => Ok(RDFFormat::RDFXML),
+        "nquads" | "n-quads" => Ok(RDFFormat::NQuads),
+        other => RDFFormat::from_str(other).map_err(|e| PyValueError::new_err(e.to_string())),
+    }
+}
+
+fn to_string(bytes: Vec<u8>) -> PyResult<String> {
+    String::from_utf8(bytes).map_err(|e| RdfDataError::new_err(e.to_string()))
+}
+
+fn cnv_err(err: sparql_service::RdfDataError) -> PyErr {
+    RdfDataError::new_err(err.to_string())
+}
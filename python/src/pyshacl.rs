@@ -5,18 +5,28 @@ use shacl_validation::shacl_processor::GraphValidation;
 use shacl_validation::shacl_processor::ShaclProcessor;
 use shacl_validation::shacl_processor::ShaclValidationMode;
 use shacl_validation::store::ShaclDataManager;
-use srdf::{RDFFormat, SRDFGraph};
+use shacl_validation::validation_report::report::{ConformanceMode, ValidationReport};
+use shacl_validation::validation_report::result::ValidationResult;
+use sparql_service::RdfData;
+use srdf::{RDFFormat, SRDFBuilder, SRDFGraph};
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::BufReader;
 use std::io::BufWriter;
+use std::io::Cursor;
 use std::path::Path;
 use std::str::FromStr;
 
+use crate::pyerror::ShaclError;
+use crate::pyrdf::{rdf_format, PyRdfData};
+
 #[pymodule]
 pub fn shacl(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(parse, module)?)?;
     module.add_function(wrap_pyfunction!(validate, module)?)?;
+    module.add_function(wrap_pyfunction!(validate_data, module)?)?;
+    module.add_class::<PyValidationReport>()?;
+    module.add_class::<PyValidationResult>()?;
     Ok(())
 }
 
@@ -30,18 +40,18 @@ pub fn parse(input: &str, output: &str, py: Python<'_>) -> PyResult<()> {
         let graph =
             match SRDFGraph::from_path(input, &input_format, None, &srdf::ReaderMode::Strict) {
                 Ok(graph) => graph,
-                Err(error) => return Err(PyValueError::new_err(error.to_string())),
+                Err(error) => return Err(ShaclError::new_err(error.to_string())),
             };
 
         let schema = match ShaclParser::new(graph).parse() {
             Ok(schema) => schema,
-            Err(error) => return Err(PyValueError::new_err(error.to_string())),
+            Err(error) => return Err(ShaclError::new_err(error.to_string())),
         };
 
         let mut shacl_writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
 
         if let Err(error) = shacl_writer.write(&schema) {
-            return Err(PyValueError::new_err(error.to_string()));
+            return Err(ShaclError::new_err(error.to_string()));
         }
 
         let output = Path::new(output);
@@ -53,7 +63,7 @@ pub fn parse(input: &str, output: &str, py: Python<'_>) -> PyResult<()> {
         };
 
         if let Err(error) = shacl_writer.serialize(output_format, &mut writer) {
-            return Err(PyValueError::new_err(error.to_string()));
+            return Err(ShaclError::new_err(error.to_string()));
         }
 
         Ok(())
@@ -62,7 +72,7 @@ pub fn parse(input: &str, output: &str, py: Python<'_>) -> PyResult<()> {
 
 #[pyfunction]
 #[pyo3(signature = (data, shapes))]
-pub fn validate(data: &str, shapes: &str, py: Python<'_>) -> PyResult<()> {
+pub fn validate(data: &str, shapes: &str, py: Python<'_>) -> PyResult<PyValidationReport> {
     py.allow_threads(|| {
         let data = Path::new(data);
         let data_format = obtain_format(data.extension())?;
@@ -71,29 +81,179 @@ pub fn validate(data: &str, shapes: &str, py: Python<'_>) -> PyResult<()> {
 
         // TODO: Consider if it is a good idea to assume a format matches a extension...
         let shapes_format = obtain_format(shapes_path.extension())?;
-        let file = File::open(shapes).unwrap_or_else(|_| panic!("Unable to open file: {shapes}"));
+        let file = match File::open(shapes) {
+            Ok(file) => file,
+            Err(error) => {
+                return Err(PyValueError::new_err(format!(
+                    "Unable to open file {shapes}: {error}"
+                )))
+            }
+        };
         let reader = BufReader::new(file);
 
         let schema = match ShaclDataManager::load(reader, shapes_format, None) {
             Ok(schema) => schema,
-            Err(error) => return Err(PyValueError::new_err(error.to_string())),
+            Err(error) => return Err(ShaclError::new_err(error.to_string())),
         };
 
         let validator =
             match GraphValidation::new(data, data_format, None, ShaclValidationMode::Native) {
                 Ok(validator) => validator,
-                Err(error) => return Err(PyValueError::new_err(error.to_string())),
+                Err(error) => return Err(ShaclError::new_err(error.to_string())),
             };
 
-        let _ = match validator.validate(&schema) {
-            Ok(report) => report,
-            Err(error) => return Err(PyValueError::new_err(error.to_string())),
-        };
+        match validator.validate(&schema) {
+            Ok(report) => Ok(PyValidationReport::new(
+                report.with_nodes_prefixmap(validator.store().prefixmap()),
+            )),
+            Err(error) => Err(ShaclError::new_err(error.to_string())),
+        }
+    })
+}
 
-        Ok(())
+/// Validates RDF data against the SHACL shapes contained in a string.
+///
+/// `mode` selects the engine, `native` or `sparql`, and `conformance` whether any result,
+/// `strict`, or only violations, `violations`, make the data not conform
+#[pyfunction]
+#[pyo3(signature = (data, shapes, shapes_format = "turtle", mode = "native", conformance = "strict"))]
+pub fn validate_data(
+    data: &PyRdfData,
+    shapes: &str,
+    shapes_format: &str,
+    mode: &str,
+    conformance: &str,
+    py: Python<'_>,
+) -> PyResult<PyValidationReport> {
+    let shapes_format = rdf_format(shapes_format)?;
+    let mode = match mode.to_lowercase().as_str() {
+        "native" => ShaclValidationMode::Native,
+        "sparql" => ShaclValidationMode::Sparql,
+        _ => return Err(PyValueError::new_err(format!("Unknown mode: {mode}"))),
+    };
+    let conformance = match conformance.to_lowercase().as_str() {
+        "strict" => ConformanceMode::Strict,
+        "violations" => ConformanceMode::Violations,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown conformance mode: {conformance}"
+            )))
+        }
+    };
+    let data = data.inner.clone();
+    py.allow_threads(|| {
+        let schema = match ShaclDataManager::load(Cursor::new(shapes), shapes_format, None) {
+            Ok(schema) => schema,
+            Err(error) => return Err(ShaclError::new_err(error.to_string())),
+        };
+        let prefixmap = data.prefixmap();
+        let validator = GraphValidation::from_rdf_data(data, mode);
+        match validator.validate(&schema) {
+            Ok(report) => Ok(PyValidationReport::new(
+                report
+                    .with_nodes_prefixmap(prefixmap)
+                    .with_conformance_mode(conformance),
+            )),
+            Err(error) => Err(ShaclError::new_err(error.to_string())),
+        }
     })
 }
 
+/// SHACL validation report
+#[pyclass(name = "ValidationReport", module = "pyrudof.shacl")]
+pub struct PyValidationReport {
+    inner: ValidationReport<RdfData>,
+}
+
+impl PyValidationReport {
+    fn new(report: ValidationReport<RdfData>) -> Self {
+        PyValidationReport {
+            inner: report.without_colors(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyValidationReport {
+    #[getter]
+    pub fn conforms(&self) -> bool {
+        self.inner.conforms()
+    }
+
+    pub fn results(&self) -> Vec<PyValidationResult> {
+        self.inner
+            .results()
+            .iter()
+            .map(PyValidationResult::from)
+            .collect()
+    }
+
+    /// Report in JSON-LD
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.inner.to_json_ld())
+            .map_err(|e| ShaclError::new_err(e.to_string()))
+    }
+
+    /// Report as a `sh:ValidationReport` serialized in an RDF format
+    #[pyo3(signature = (format = "turtle"))]
+    pub fn serialize(&self, format: &str) -> PyResult<String> {
+        let format = rdf_format(format)?;
+        let mut rdf = SRDFGraph::empty();
+        self.inner
+            .to_rdf(&mut rdf)
+            .map_err(|e| ShaclError::new_err(e.to_string()))?;
+        let mut output = Vec::new();
+        rdf.serialize(format, &mut output)
+            .map_err(|e| ShaclError::new_err(e.to_string()))?;
+        String::from_utf8(output).map_err(|e| ShaclError::new_err(e.to_string()))
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.results().len()
+    }
+
+    pub fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+/// Result of a SHACL validation, whose terms are written in N-Triples syntax
+#[pyclass(name = "ValidationResult", module = "pyrudof.shacl", get_all)]
+#[derive(Clone)]
+pub struct PyValidationResult {
+    pub focus_node: String,
+    pub path: Option<String>,
+    pub value: Option<String>,
+    pub source_shape: Option<String>,
+    pub constraint_component: String,
+    pub severity: String,
+    pub messages: Vec<String>,
+}
+
+#[pymethods]
+impl PyValidationResult {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ValidationResult(focus_node={}, constraint_component={}, severity={})",
+            self.focus_node, self.constraint_component, self.severity
+        )
+    }
+}
+
+impl From<&ValidationResult<RdfData>> for PyValidationResult {
+    fn from(result: &ValidationResult<RdfData>) -> Self {
+        PyValidationResult {
+            focus_node: result.focus_node().to_string(),
+            path: result.path().map(|path| path.sparql_path()),
+            value: result.value().map(|value| value.to_string()),
+            source_shape: result.source().map(|source| source.to_string()),
+            constraint_component: result.constraint_component().to_string(),
+            severity: result.severity().to_string(),
+            messages: result.messages().iter().map(|m| m.to_string()).collect(),
+        }
+    }
+}
+
 fn obtain_format(extension: Option<&OsStr>) -> PyResult<RDFFormat> {
     match extension {
         None => Err(PyValueError::new_err("No ouput format is provided")),
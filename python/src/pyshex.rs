@@ -0,0 +1,329 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use shapemap::query_shape_map::QueryShapeMap;
+use shapemap::{ResultAssociation, ResultShapeMap, ValidationStatus};
+use shex_ast::compiled::compiled_schema::CompiledSchema;
+use shex_ast::Schema as SchemaJson;
+use shex_compact::{ShExFormatter, ShExParser, ShapeMapParser, ShapemapFormatter};
+use shex_validation::{Validator, ValidatorConfig};
+
+use crate::pyerror::{ShExError, ShapeMapError};
+use crate::pyrdf::PyRdfData;
+
+#[pymodule]
+pub fn shex(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyShExSchema>()?;
+    module.add_class::<PyShExParser>()?;
+    module.add_class::<PyShExFormatter>()?;
+    module.add_class::<PyQueryShapeMap>()?;
+    module.add_class::<PyResultShapeMap>()?;
+    module.add_class::<PyResultAssociation>()?;
+    module.add_function(wrap_pyfunction!(validate, module)?)?;
+    Ok(())
+}
+
+/// ShEx schema, which can be obtained with `ShExParser`
+#[pyclass(name = "ShExSchema", module = "pyrudof.shex")]
+#[derive(Clone)]
+pub struct PyShExSchema {
+    pub(crate) inner: SchemaJson,
+}
+
+#[pymethods]
+impl PyShExSchema {
+    /// Labels of the shapes declared in the schema
+    pub fn shapes(&self) -> Vec<String> {
+        self.inner
+            .shapes()
+            .unwrap_or_default()
+            .iter()
+            .map(|decl| decl.id.to_string())
+            .collect()
+    }
+
+    pub fn to_shexc(&self) -> String {
+        ShExFormatter::default()
+            .without_colors()
+            .format_schema(&self.inner)
+    }
+
+    pub fn to_shexj(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.inner)
+            .map_err(|e| ShExError::new_err(format!("Error converting schema to ShExJ: {e}")))
+    }
+
+    pub fn __str__(&self) -> String {
+        self.to_shexc()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("ShExSchema(shapes={})", self.shapes().len())
+    }
+}
+
+/// Parser of ShEx schemas in compact syntax (ShExC) or JSON (ShExJ)
+#[pyclass(name = "ShExParser", module = "pyrudof.shex")]
+pub struct PyShExParser;
+
+#[pymethods]
+impl PyShExParser {
+    /// Parses a schema in ShExC, resolving relative IRIs against `base`
+    #[staticmethod]
+    #[pyo3(signature = (input, base = None))]
+    pub fn parse(input: &str, base: Option<&str>) -> PyResult<PyShExSchema> {
+        let base = match base {
+            None => None,
+            Some(base) => Some(
+                base.parse()
+                    .map_err(|e| PyValueError::new_err(format!("Invalid base IRI {base}: {e}")))?,
+            ),
+        };
+        let inner = ShExParser::parse(input, base)
+            .map_err(|e| ShExError::new_err(format!("Error parsing ShExC: {e}")))?;
+        Ok(PyShExSchema { inner })
+    }
+
+    /// Parses a schema in ShExJ
+    #[staticmethod]
+    pub fn parse_json(input: &str) -> PyResult<PyShExSchema> {
+        let inner = serde_json::from_str::<SchemaJson>(input)
+            .map_err(|e| ShExError::new_err(format!("Error parsing ShExJ: {e}")))?;
+        Ok(PyShExSchema { inner })
+    }
+}
+
+/// Formatter that writes ShEx schemas in compact syntax
+#[pyclass(name = "ShExFormatter", module = "pyrudof.shex")]
+pub struct PyShExFormatter {
+    inner: ShExFormatter,
+}
+
+#[pymethods]
+impl PyShExFormatter {
+    /// Colors are intended for terminals, so they are disabled by default
+    #[new]
+    #[pyo3(signature = (colors = false))]
+    pub fn new(colors: bool) -> Self {
+        let inner = if colors {
+            ShExFormatter::default()
+        } else {
+            ShExFormatter::default().without_colors()
+        };
+        PyShExFormatter { inner }
+    }
+
+    pub fn format_schema(&self, schema: &PyShExSchema) -> String {
+        self.inner.format_schema(&schema.inner)
+    }
+}
+
+/// Query shape map that associates node selectors with shape selectors
+#[pyclass(name = "QueryShapeMap", module = "pyrudof.shex")]
+#[derive(Clone)]
+pub struct PyQueryShapeMap {
+    inner: QueryShapeMap,
+}
+
+#[pymethods]
+impl PyQueryShapeMap {
+    #[new]
+    pub fn new() -> Self {
+        PyQueryShapeMap {
+            inner: QueryShapeMap::new(),
+        }
+    }
+
+    /// Parses a shape map in compact syntax, like `:alice@:Person, {FOCUS a :Person}@:Person`
+    #[staticmethod]
+    pub fn parse(input: &str) -> PyResult<Self> {
+        let inner = parse_shapemap(input)?;
+        Ok(PyQueryShapeMap { inner })
+    }
+
+    /// Adds the association of a node selector with a shape selector, both in compact syntax.
+    /// When no shape is given, the node is validated against the start shape
+    #[pyo3(signature = (node, shape = None))]
+    pub fn add(&mut self, node: &str, shape: Option<&str>) -> PyResult<()> {
+        let node_selector = ShapeMapParser::parse_node_selector(node)
+            .map_err(|e| ShapeMapError::new_err(format!("Error parsing node {node}: {e}")))?;
+        let shape_selector = match shape {
+            None => shapemap::ShapeSelector::start(),
+            Some(shape) => ShapeMapParser::parse_shape_selector(shape)
+                .map_err(|e| ShapeMapError::new_err(format!("Error parsing shape {shape}: {e}")))?,
+        };
+        self.inner.add_association(node_selector, shape_selector);
+        Ok(())
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.iter().count()
+    }
+
+    pub fn __str__(&self) -> String {
+        ShapemapFormatter::default()
+            .without_colors()
+            .format_shapemap(&self.inner)
+    }
+}
+
+impl Default for PyQueryShapeMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shape map argument of `validate`, which can be a `QueryShapeMap` or a string in compact syntax
+#[derive(FromPyObject)]
+pub enum ShapeMapArg {
+    ShapeMap(PyQueryShapeMap),
+    Compact(String),
+}
+
+/// Status of a node/shape pair in a result shape map
+#[pyclass(name = "ResultAssociation", module = "pyrudof.shex", get_all)]
+#[derive(Clone)]
+pub struct PyResultAssociation {
+    pub node: String,
+    pub shape: String,
+    /// One of `conformant`, `nonconformant` or `pending`
+    pub status: String,
+    pub reason: Option<String>,
+    /// Reasons or errors of the validation serialized as JSON
+    pub app_info: Option<String>,
+}
+
+#[pymethods]
+impl PyResultAssociation {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ResultAssociation(node={}, shape={}, status={})",
+            self.node, self.shape, self.status
+        )
+    }
+}
+
+impl From<&ResultAssociation> for PyResultAssociation {
+    fn from(assoc: &ResultAssociation) -> Self {
+        PyResultAssociation {
+            node: assoc.node.to_string(),
+            shape: assoc.shape.to_string(),
+            status: assoc.status.to_string(),
+            reason: assoc.reason.clone(),
+            app_info: assoc.app_info.as_ref().map(|info| info.to_string()),
+        }
+    }
+}
+
+/// Result of a ShEx validation, with the status of each node/shape pair
+#[pyclass(name = "ResultShapeMap", module = "pyrudof.shex")]
+#[derive(Clone)]
+pub struct PyResultShapeMap {
+    inner: ResultShapeMap,
+}
+
+#[pymethods]
+impl PyResultShapeMap {
+    /// Reads a result shape map from its JSON representation
+    #[staticmethod]
+    pub fn from_json(input: &str) -> PyResult<Self> {
+        let inner = ResultShapeMap::from_json_str(input)
+            .map_err(|e| ShapeMapError::new_err(format!("Error reading result shape map: {e}")))?;
+        Ok(PyResultShapeMap { inner })
+    }
+
+    pub fn associations(&self) -> Vec<PyResultAssociation> {
+        self.inner.iter().map(PyResultAssociation::from).collect()
+    }
+
+    pub fn conformant(&self) -> Vec<PyResultAssociation> {
+        self.with_status(ValidationStatus::Conformant)
+    }
+
+    pub fn nonconformant(&self) -> Vec<PyResultAssociation> {
+        self.with_status(ValidationStatus::NonConformant)
+    }
+
+    pub fn pending(&self) -> Vec<PyResultAssociation> {
+        self.with_status(ValidationStatus::Pending)
+    }
+
+    /// True if all the node/shape pairs are conformant
+    #[getter]
+    pub fn is_conformant(&self) -> bool {
+        self.inner
+            .iter()
+            .all(|assoc| assoc.status == ValidationStatus::Conformant)
+    }
+
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.inner)
+            .map_err(|e| ShapeMapError::new_err(format!("Error converting result to JSON: {e}")))
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+impl PyResultShapeMap {
+    fn with_status(&self, status: ValidationStatus) -> Vec<PyResultAssociation> {
+        self.inner
+            .iter()
+            .filter(|assoc| assoc.status == status)
+            .map(PyResultAssociation::from)
+            .collect()
+    }
+}
+
+/// Validates the RDF data against a ShEx schema for the node/shape pairs of the shape map
+#[pyfunction]
+#[pyo3(signature = (schema, data, shapemap, max_steps = None))]
+pub fn validate(
+    schema: &PyShExSchema,
+    data: &PyRdfData,
+    shapemap: ShapeMapArg,
+    max_steps: Option<usize>,
+    py: Python<'_>,
+) -> PyResult<PyResultShapeMap> {
+    let shapemap = match shapemap {
+        ShapeMapArg::ShapeMap(shapemap) => shapemap.inner,
+        ShapeMapArg::Compact(str) => parse_shapemap(&str)?,
+    };
+    let schema = &schema.inner;
+    let data = &data.inner;
+    py.allow_threads(|| {
+        let mut compiled = CompiledSchema::new();
+        compiled
+            .from_schema_json(schema)
+            .map_err(|e| ShExError::new_err(format!("Error compiling schema: {e}")))?;
+        let mut config = ValidatorConfig::default();
+        if let Some(max_steps) = max_steps {
+            config.set_max_steps(max_steps)
+        }
+        let mut validator = Validator::new(compiled, &config);
+        validator
+            .validate_shapemap(&shapemap, data)
+            .map_err(|e| ShExError::new_err(format!("Error validating: {e}")))?;
+        let result_map = validator
+            .result_map(Some(data.prefixmap().without_rich_qualifying()))
+            .map_err(|e| ShExError::new_err(format!("Error obtaining results: {e}")))?
+            .with_schema_prefixmap(
+                schema
+                    .prefixmap()
+                    .unwrap_or_default()
+                    .without_rich_qualifying(),
+            );
+        Ok(PyResultShapeMap {
+            inner: result_map.result_shape_map(),
+        })
+    })
+}
+
+fn parse_shapemap(input: &str) -> PyResult<QueryShapeMap> {
+    ShapeMapParser::parse(input, &None, &None)
+        .map_err(|e| ShapeMapError::new_err(format!("Error parsing shape map: {e}")))
+}
@@ -1,9 +1,57 @@
 import sys
 import unittest
 
-from pyrudof import convert
+from pyrudof import convert, ShExError
 
 dctap = """shapeId,shapeLabel,propertyId,Mandatory,Repeatable,valueDatatype,valueShape
 Person,Shape or person,name,true,false,xsd:string, 
 ,,birthdate,false,false,xsd:date"""
 
+shex = """prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { :name xsd:string ; :knows @:Person * }"""
+
+shacl = """@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix : <http://example.org/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+:Person a sh:NodeShape ;
+  sh:property [ sh:path :name ; sh:datatype xsd:string ] ."""
+
+
+class TestConverter(unittest.TestCase):
+
+    def test_dctap2shex(self):
+        result = convert.dctap2shex(dctap)
+        self.assertIn(":Person", result)
+        self.assertIn("xsd:date", result)
+
+    def test_dctap2shex_shexj(self):
+        result = convert.dctap2shex(dctap, result_format="shexj")
+        self.assertIn('"type": "Schema"', result)
+
+    def test_shacl2shex(self):
+        result = convert.shacl2shex(shacl)
+        self.assertIn(":Person", result)
+        self.assertIn(":name xsd:string", result)
+
+    def test_shex2shacl(self):
+        result = convert.shex2shacl(shex)
+        self.assertIn("sh:NodeShape", result)
+        self.assertIn("sh:path :name", result)
+
+    def test_shex2sparql(self):
+        result = convert.shex2sparql(shex, ":Person")
+        self.assertIn("SELECT", result)
+        self.assertIn(":name", result)
+
+    def test_shex2uml(self):
+        result = convert.shex2uml(shex)
+        self.assertTrue(result.startswith("@startuml"))
+
+    def test_invalid_shex(self):
+        with self.assertRaises(ShExError):
+            convert.shex2shacl("not a schema {")
+
+
+if __name__ == "__main__":
+    sys.exit(unittest.main())
@@ -0,0 +1,52 @@
+import sys
+import unittest
+
+from pyrudof import RdfDataError
+from pyrudof import rdf
+
+data_str = """prefix : <http://example.org/>
+:alice :knows :bob ."""
+
+
+class TestRdfData(unittest.TestCase):
+
+    def test_from_str(self):
+        data = rdf.RdfData.from_str(data_str)
+        self.assertEqual(len(data), 1)
+        self.assertEqual(data.prefixes(), {"": "http://example.org/"})
+
+    def test_add_triple(self):
+        data = rdf.RdfData()
+        data.add_prefix("", "http://example.org/")
+        data.add_triple(":alice", ":name", '"Alice"')
+        data.add_triple("<http://example.org/bob>", ":name", '"Bob"@en')
+        self.assertEqual(len(data), 2)
+        self.assertIn('"Bob"@en', data.serialize("ntriples"))
+
+    def test_query(self):
+        data = rdf.RdfData.from_str(data_str)
+        solutions = data.query("SELECT ?x WHERE { ?x ?p ?o }")
+        self.assertEqual(solutions, [{"x": "<http://example.org/alice>"}])
+        self.assertTrue(data.ask("ASK { ?x ?p <http://example.org/bob> }"))
+
+    def test_construct(self):
+        data = rdf.RdfData.from_str(data_str)
+        result = data.construct(
+            "CONSTRUCT { ?y <http://example.org/knownBy> ?x } WHERE { ?x ?p ?y }",
+            format="ntriples",
+        )
+        self.assertIn("<http://example.org/bob> <http://example.org/knownBy>", result)
+
+    def test_merge_str(self):
+        data = rdf.RdfData.from_str(data_str)
+        data.merge_str("<http://example.org/bob> <http://example.org/knows> <http://example.org/carol> .", format="ntriples")
+        self.assertEqual(len(data), 2)
+        self.assertEqual(len(data.query("SELECT * WHERE { ?x ?p ?y }")), 2)
+
+    def test_invalid_data(self):
+        with self.assertRaises(RdfDataError):
+            rdf.RdfData.from_str(":alice :knows")
+
+
+if __name__ == "__main__":
+    sys.exit(unittest.main())
@@ -0,0 +1,45 @@
+import json
+import sys
+import unittest
+
+from pyrudof import ShaclError
+from pyrudof import rdf, shacl
+
+shapes = """@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix : <http://example.org/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+:PersonShape a sh:NodeShape ;
+  sh:targetSubjectsOf :name ;
+  sh:property [ sh:path :name ; sh:datatype xsd:string ] ."""
+
+
+class TestShacl(unittest.TestCase):
+
+    def test_conforms(self):
+        data = rdf.RdfData.from_str('prefix : <http://example.org/>\n:alice :name "Alice" .')
+        report = shacl.validate_data(data, shapes)
+        self.assertTrue(report.conforms)
+        self.assertEqual(len(report), 0)
+
+    def test_does_not_conform(self):
+        data = rdf.RdfData.from_str("prefix : <http://example.org/>\n:bob :name 42 .")
+        report = shacl.validate_data(data, shapes)
+        self.assertFalse(report.conforms)
+        result = report.results()[0]
+        self.assertEqual(result.focus_node, "<http://example.org/bob>")
+        self.assertEqual(result.severity, "<http://www.w3.org/ns/shacl#Violation>")
+        self.assertEqual(json.loads(report.to_json())["sh:conforms"], False)
+        self.assertIn("sh:ValidationReport", report.serialize())
+
+    def test_sparql_mode(self):
+        data = rdf.RdfData.from_str("prefix : <http://example.org/>\n:bob :name 42 .")
+        report = shacl.validate_data(data, shapes, mode="sparql")
+        self.assertFalse(report.conforms)
+
+    def test_invalid_shapes(self):
+        with self.assertRaises(ShaclError):
+            shacl.validate_data(rdf.RdfData(), "not turtle")
+
+
+if __name__ == "__main__":
+    sys.exit(unittest.main())
@@ -0,0 +1,62 @@
+import sys
+import unittest
+
+from pyrudof import ShapeMapError, ShExError
+from pyrudof import rdf, shex
+
+schema_str = """prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { :name xsd:string ; :age xsd:integer ? }"""
+
+data_str = """prefix : <http://example.org/>
+:alice :name "Alice" ; :age 23 .
+:bob :name 42 ."""
+
+
+class TestShEx(unittest.TestCase):
+
+    def setUp(self):
+        self.schema = shex.ShExParser.parse(schema_str)
+        self.data = rdf.RdfData.from_str(data_str)
+
+    def test_parse(self):
+        self.assertEqual(self.schema.shapes(), ["http://example.org/Person"])
+
+    def test_parse_error(self):
+        with self.assertRaises(ShExError):
+            shex.ShExParser.parse(":Person {")
+
+    def test_shexj_roundtrip(self):
+        schema = shex.ShExParser.parse_json(self.schema.to_shexj())
+        self.assertEqual(schema.shapes(), self.schema.shapes())
+
+    def test_formatter(self):
+        result = shex.ShExFormatter().format_schema(self.schema)
+        self.assertIn(":Person", result)
+
+    def test_validate_compact_shapemap(self):
+        result = shex.validate(self.schema, self.data, ":alice@:Person,:bob@:Person")
+        self.assertFalse(result.is_conformant)
+        self.assertEqual([a.node for a in result.conformant()], ["http://example.org/alice"])
+        self.assertEqual([a.node for a in result.nonconformant()], ["http://example.org/bob"])
+        self.assertIsNotNone(result.nonconformant()[0].reason)
+
+    def test_validate_query_shapemap(self):
+        shapemap = shex.QueryShapeMap()
+        shapemap.add(":alice", ":Person")
+        result = shex.validate(self.schema, self.data, shapemap)
+        self.assertTrue(result.is_conformant)
+        self.assertEqual(len(result), 1)
+
+    def test_result_json(self):
+        result = shex.validate(self.schema, self.data, ":alice@:Person")
+        read = shex.ResultShapeMap.from_json(result.to_json())
+        self.assertEqual(read.associations()[0].status, "conformant")
+
+    def test_shapemap_error(self):
+        with self.assertRaises(ShapeMapError):
+            shex.QueryShapeMap.parse(":alice@@")
+
+
+if __name__ == "__main__":
+    sys.exit(unittest.main())
@@ -0,0 +1,62 @@
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use std::fmt::Display;
+
+use crate::Cardinality;
+
+/// Number of values that were assigned to a component of an expression
+/// compared with the cardinality that the component expects
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub struct CardinalityCount<K>
+where
+    K: Display,
+{
+    pub key: K,
+    pub cond: String,
+    pub expected: Cardinality,
+    pub found: usize,
+}
+
+impl<K: Display> CardinalityCount<K> {
+    /// Checks if the number of values found is allowed by the expected cardinality
+    pub fn is_ok(&self) -> bool {
+        self.expected.contains(self.found)
+    }
+}
+
+impl<K: Display> Display for CardinalityCount<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: found {}, expected {}",
+            self.key, self.cond, self.found, self.expected
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Debug)]
+pub struct CardinalityCounts<K>
+where
+    K: Display,
+{
+    counts: Vec<CardinalityCount<K>>,
+}
+
+impl<K: Display> CardinalityCounts<K> {
+    pub fn new(counts: Vec<CardinalityCount<K>>) -> CardinalityCounts<K> {
+        CardinalityCounts { counts }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CardinalityCount<K>> {
+        self.counts.iter()
+    }
+}
+
+impl<K: Display> Display for CardinalityCounts<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for count in &self.counts {
+            write!(f, "{count}|")?;
+        }
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@
 pub mod bag;
 pub mod candidate;
 pub mod cardinality;
+pub mod cardinality_counts;
 pub mod deriv_error;
 pub mod keys;
 pub mod max;
@@ -27,6 +28,7 @@ pub mod rbe_schema;
 pub mod rbe_table;
 
 pub use crate::cardinality::*;
+pub use crate::cardinality_counts::*;
 pub use crate::component::*;
 pub use crate::deriv_n::*;
 pub use crate::failures::*;
@@ -129,6 +129,26 @@ where
         set
     }
 
+    /// Returns the symbols of the expression with their cardinalities
+    pub fn symbol_cards(&self) -> Vec<(A, Cardinality)> {
+        let mut cards = Vec::new();
+        self.symbol_cards_aux(&mut cards);
+        cards
+    }
+
+    fn symbol_cards_aux(&self, cards: &mut Vec<(A, Cardinality)>) {
+        match &self {
+            Rbe::Fail { .. } => (),
+            Rbe::Empty => (),
+            Rbe::Symbol { value, card } => cards.push((value.clone(), card.clone())),
+            Rbe::And { values } => values.iter().for_each(|v| v.symbol_cards_aux(cards)),
+            Rbe::Or { values } => values.iter().for_each(|v| v.symbol_cards_aux(cards)),
+            Rbe::Plus { value } => value.symbol_cards_aux(cards),
+            Rbe::Star { value } => value.symbol_cards_aux(cards),
+            Rbe::Repeat { value, .. } => value.symbol_cards_aux(cards),
+        }
+    }
+
     fn symbols_aux(&self, set: &mut HashSet<A>) {
         match &self {
             Rbe::Fail { .. } => (),
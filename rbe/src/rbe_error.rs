@@ -1,6 +1,7 @@
 use crate::failures::Failures;
 use crate::rbe1::Rbe;
 use crate::Cardinality;
+use crate::CardinalityCounts;
 use crate::Key;
 use crate::Keys;
 use crate::Ref;
@@ -95,4 +96,15 @@ where
 
     #[error("RbeTable: Key {key} has no component associated. Available keys: {available_keys}")]
     RbeTableKeyWithoutComponent { key: K, available_keys: Keys<K> },
+
+    #[error("Value {value} of {key} doesn't match condition {cond}: {error}")]
+    ValueFail {
+        key: K,
+        value: V,
+        cond: String,
+        error: String,
+    },
+
+    #[error("Number of values doesn't match the expected cardinalities: {counts}")]
+    CardinalityMismatch { counts: CardinalityCounts<K> },
 }
@@ -7,6 +7,8 @@ use std::vec::IntoIter;
 use tracing::debug;
 
 use crate::Bag;
+use crate::CardinalityCount;
+use crate::CardinalityCounts;
 use crate::Key;
 use crate::MatchCond;
 use crate::Pending;
@@ -68,10 +70,15 @@ where
         self.rbe = rbe;
     }
 
+    /// Returns the condition that the values assigned to a component must satisfy
+    pub fn component_cond(&self, c: &Component) -> Option<&MatchCond<K, V, R>> {
+        self.component_cond.get(c)
+    }
+
     pub fn matches(
         &self,
         values: Vec<(K, V)>,
-    ) -> Result<MatchTableIter<K, V, R>, RbeError<K, V, R>> {
+    ) -> Result<MatchTableIter<'_, K, V, R>, RbeError<K, V, R>> {
         let mut pairs_found = 0;
         let mut candidates = Vec::new();
         let cs_empty = IndexSet::new();
@@ -88,7 +95,10 @@ where
             candidates.push(pairs);
         }
 
-        if candidates.is_empty() || pairs_found == 0 {
+        if values.is_empty() && self.rbe.match_bag(&Bag::new(), self.open).is_ok() {
+            // An empty neighbourhood matches a nullable expression without pending values
+            Ok(MatchTableIter::Nullable(Some(Pending::new())))
+        } else if candidates.is_empty() || pairs_found == 0 {
             debug!(
                 "No candidates for rbe: {:?}, candidates: {:?}, pairs_found: {pairs_found}",
                 self.rbe, candidates,
@@ -111,12 +121,28 @@ where
             Ok(MatchTableIter::NonEmpty(IterCartesianProduct {
                 is_first: true,
                 state: mp,
-                rbe: self.rbe.clone(),
-                open: self.open,
+                table: self,
+                mismatch: None,
                 // controlled: self.controlled.clone()
             }))
         }
     }
+
+    /// Compares the number of values assigned to each component in `bag` with its cardinality
+    fn cardinality_counts(&self, bag: &Bag<Component>) -> CardinalityCounts<K> {
+        let counts = self
+            .rbe
+            .symbol_cards()
+            .into_iter()
+            .map(|(c, card)| CardinalityCount {
+                key: cnv_key(&c, self),
+                cond: cnv_cond(&c, self).to_string(),
+                expected: card,
+                found: bag.contains(&c),
+            })
+            .collect();
+        CardinalityCounts::new(counts)
+    }
 }
 
 impl<K, V, R> Debug for RbeTable<K, V, R>
@@ -137,43 +163,104 @@ where
     }
 }
 
+/// A successful match of some values with a table.
+///
+/// It contains the pending references of the values and the component that each value was assigned to
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableMatch<K, V, R>
+where
+    K: Key,
+    V: Value,
+    R: Ref,
+{
+    pending: Pending<V, R>,
+    assignment: Vec<(K, V, Component)>,
+}
+
+impl<K, V, R> TableMatch<K, V, R>
+where
+    K: Key,
+    V: Value,
+    R: Ref,
+{
+    pub fn pending(&self) -> &Pending<V, R> {
+        &self.pending
+    }
+
+    /// Key, value and component of each value in the match
+    pub fn assignment(&self) -> &Vec<(K, V, Component)> {
+        &self.assignment
+    }
+
+    pub fn into_pending(self) -> Pending<V, R> {
+        self.pending
+    }
+}
+
 #[derive(Debug)]
-pub enum MatchTableIter<K, V, R>
+pub enum MatchTableIter<'a, K, V, R>
 where
     K: Key,
     V: Value,
     R: Ref,
 {
     Empty(EmptyIter<K, V, R>),
-    NonEmpty(IterCartesianProduct<K, V, R>),
+    NonEmpty(IterCartesianProduct<'a, K, V, R>),
+    Nullable(Option<Pending<V, R>>),
 }
 
-impl<K, V, R> Iterator for MatchTableIter<K, V, R>
+impl<K, V, R> MatchTableIter<'_, K, V, R>
 where
     K: Key,
     V: Value,
     R: Ref,
 {
-    type Item = Result<Pending<V, R>, rbe_error::RbeError<K, V, R>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the next match including the assignment of values to components
+    pub fn next_match(&mut self) -> Option<MatchResult<K, V, R>> {
         match self {
             MatchTableIter::Empty(ref mut e) => {
                 debug!("MatchTableIter::Empty");
-                e.next()
+                e.next().map(|result| {
+                    result.map(|pending| TableMatch {
+                        pending,
+                        assignment: Vec::new(),
+                    })
+                })
             }
             MatchTableIter::NonEmpty(ref mut cp) => {
                 debug!("MatchTableIter::NonEmpty");
-                cp.next()
+                cp.next_match()
             }
+            MatchTableIter::Nullable(ref mut pending) => pending.take().map(|pending| {
+                Ok(TableMatch {
+                    pending,
+                    assignment: Vec::new(),
+                })
+            }),
         }
     }
 }
 
+impl<K, V, R> Iterator for MatchTableIter<'_, K, V, R>
+where
+    K: Key,
+    V: Value,
+    R: Ref,
+{
+    type Item = Result<Pending<V, R>, rbe_error::RbeError<K, V, R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_match()
+            .map(|result| result.map(TableMatch::into_pending))
+    }
+}
+
+type MatchResult<K, V, R> = Result<TableMatch<K, V, R>, RbeError<K, V, R>>;
+
 type State<K, V, R> = MultiProduct<IntoIter<(K, V, Component, MatchCond<K, V, R>)>>;
 
 #[derive(Debug)]
-pub struct IterCartesianProduct<K, V, R>
+pub struct IterCartesianProduct<'a, K, V, R>
 where
     K: Key,
     V: Value,
@@ -181,68 +268,102 @@ where
 {
     is_first: bool,
     state: State<K, V, R>,
-    rbe: Rbe<Component>,
-    open: bool,
+    table: &'a RbeTable<K, V, R>,
+    // Cardinality counts of the first assignment that didn't match the expression
+    mismatch: Option<CardinalityCounts<K>>,
     // controlled: HashSet<K>
 }
 
-impl<K, V, R> Iterator for IterCartesianProduct<K, V, R>
+impl<K, V, R> IterCartesianProduct<'_, K, V, R>
 where
     K: Key,
     V: Value,
     R: Ref,
 {
-    type Item = Result<Pending<V, R>, rbe_error::RbeError<K, V, R>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let next_state = self.state.next();
-        debug!("state in IterCartesianProduct {:?}", self.state);
-        match next_state {
-            None => {
-                if self.is_first {
-                    debug!("Should be internal error? No more candidates");
-                    debug!("RBE: {}", self.rbe);
-                    None
-                } else {
-                    debug!("No more candidates");
-                    None
-                }
-            }
-            Some(vs) => {
-                for (k, v, c, cond) in &vs {
-                    debug!("Next state: ({k} {v}) should match component {c} with cond: {cond})");
+    /// Returns the next assignment of values to components that matches the expression.
+    ///
+    /// If no assignment matches, the cardinality counts of the first one are returned as an error
+    pub fn next_match(&mut self) -> Option<MatchResult<K, V, R>> {
+        loop {
+            let next_state = self.state.next();
+            debug!("state in IterCartesianProduct {:?}", self.state);
+            match next_state {
+                None => {
+                    if self.is_first {
+                        debug!("No more candidates and no match found");
+                        debug!("RBE: {}", self.table.rbe);
+                        return self
+                            .mismatch
+                            .take()
+                            .map(|counts| Err(RbeError::CardinalityMismatch { counts }));
+                    } else {
+                        debug!("No more candidates");
+                        return None;
+                    }
                 }
-                let mut pending: Pending<V, R> = Pending::new();
-                for (_k, v, _, cond) in &vs {
-                    match cond.matches(v) {
-                        Ok(new_pending) => {
-                            debug!("Condition passed: {cond} with value: {v}");
-                            pending.merge(new_pending);
+                Some(vs) => {
+                    for (k, v, c, cond) in &vs {
+                        debug!(
+                            "Next state: ({k} {v}) should match component {c} with cond: {cond})"
+                        );
+                    }
+                    let mut pending: Pending<V, R> = Pending::new();
+                    for (k, v, _, cond) in &vs {
+                        match cond.matches(v) {
+                            Ok(new_pending) => {
+                                debug!("Condition passed: {cond} with value: {v}");
+                                pending.merge(new_pending);
+                            }
+                            Err(err) => {
+                                debug!("Failed condition: {cond} with value: {v}");
+                                return Some(Err(RbeError::ValueFail {
+                                    key: k.clone(),
+                                    value: v.clone(),
+                                    cond: cond.to_string(),
+                                    error: err.to_string(),
+                                }));
+                            }
+                        }
+                    }
+                    debug!("Pending after checking conditions: {pending:?}");
+                    let bag = Bag::from_iter(vs.iter().map(|(_, _, c, _)| *c));
+                    match self.table.rbe.match_bag(&bag, self.table.open) {
+                        Ok(()) => {
+                            debug!("Rbe {} matches bag {}", self.table.rbe, bag);
+                            self.is_first = false;
+                            let assignment = vs.into_iter().map(|(k, v, c, _)| (k, v, c)).collect();
+                            return Some(Ok(TableMatch {
+                                pending,
+                                assignment,
+                            }));
                         }
                         Err(err) => {
-                            debug!("Failed condition: {cond} with value: {v}");
-                            return Some(Err(err));
+                            debug!("### Skipped error: {err}!!!!\n");
+                            if self.mismatch.is_none() {
+                                self.mismatch = Some(self.table.cardinality_counts(&bag));
+                            }
                         }
                     }
                 }
-                debug!("Pending after checking conditions: {pending:?}");
-                let bag = Bag::from_iter(vs.into_iter().map(|(_, _, c, _)| c));
-                match self.rbe.match_bag(&bag, self.open) {
-                    Ok(()) => {
-                        debug!("Rbe {} matches bag {}", self.rbe, bag);
-                        self.is_first = false;
-                        Some(Ok(pending))
-                    }
-                    Err(err) => {
-                        debug!("### Skipped error: {err}!!!!\n");
-                        self.next()
-                    }
-                }
             }
         }
     }
 }
 
+impl<K, V, R> Iterator for IterCartesianProduct<'_, K, V, R>
+where
+    K: Key,
+    V: Value,
+    R: Ref,
+{
+    type Item = Result<Pending<V, R>, rbe_error::RbeError<K, V, R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_match()
+            .map(|result| result.map(TableMatch::into_pending))
+    }
+}
+
 #[derive(Debug)]
 pub struct EmptyIter<K, V, R>
 where
@@ -436,6 +557,14 @@ mod tests {
 
         let mut iter = rbe_table.matches(vs).unwrap();
 
+        match iter.next() {
+            Some(Err(RbeError::CardinalityMismatch { counts })) => {
+                let found: Vec<_> = counts.iter().map(|c| (c.key, c.found)).collect();
+                assert_eq!(found, vec![('p', 1), ('q', 1), ('q', 0)]);
+                assert!(!counts.iter().all(|c| c.is_ok()));
+            }
+            other => panic!("Expected cardinality mismatch, found {other:?}"),
+        }
         assert_eq!(iter.next(), None);
     }
 
@@ -467,7 +596,10 @@ mod tests {
 
         let mut iter = rbe_table.matches(vs).unwrap();
 
-        assert_eq!(iter.next(), Some(Ok(Pending::new())));
+        assert_eq!(
+            iter.next_match().unwrap().unwrap().assignment(),
+            &vec![('p', 'a', c1), ('q', 'a', c2)]
+        );
         assert_eq!(iter.next(), None);
     }
 
@@ -501,9 +633,32 @@ mod tests {
 
         assert_eq!(
             iter.next(),
-            Some(Err(rbe_error::RbeError::MsgError {
-                msg: "Value b!='a'".to_string()
+            Some(Err(rbe_error::RbeError::ValueFail {
+                key: 'q',
+                value: 'b',
+                cond: "is_a".to_string(),
+                error: "Value b!='a'".to_string()
             }))
         );
     }
+
+    #[test]
+    fn test_rbe_table_empty_values() {
+        // { p . * } matches an empty neighbourhood but { p . } doesn't
+        let any: MatchCond<char, char, char> =
+            MatchCond::single(SingleCond::new().with_name("any"));
+        let mut star_table = RbeTable::new();
+        let c = star_table.add_component('p', &any);
+        star_table.with_rbe(Rbe::Star {
+            value: Box::new(Rbe::symbol(c, 1, Max::IntMax(1))),
+        });
+        let results: Vec<_> = star_table.matches(Vec::new()).unwrap().collect();
+        assert_eq!(results, vec![Ok(Pending::new())]);
+
+        let mut one_table = RbeTable::new();
+        let c = one_table.add_component('p', &any);
+        one_table.with_rbe(Rbe::symbol(c, 1, Max::IntMax(1)));
+        let mut iter = one_table.matches(Vec::new()).unwrap();
+        assert!(matches!(iter.next(), Some(Err(_))));
+    }
 }
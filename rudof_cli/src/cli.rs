@@ -1,7 +1,9 @@
 use crate::input_spec::InputSpec;
+use crate::serve::NamedInput;
 use crate::{InputConvertFormat, OutputConvertFormat};
 use clap::{Parser, Subcommand, ValueEnum};
 use shacl_validation::shacl_processor::ShaclValidationMode;
+use shacl_validation::validation_report::report::ConformanceMode;
 use srdf::{RDFFormat, ReaderMode};
 use std::fmt::Display;
 use std::{fmt::Formatter, path::PathBuf};
@@ -209,6 +211,18 @@ pub enum Command {
             default_value_t = false
         )]
         force_overwrite: bool,
+
+        /// Watch the schema, data and shapemap files and validate again when they change
+        #[arg(long = "watch", default_value_t = false)]
+        watch: bool,
+
+        /// Directory of a persistent store created with `rudof data load`, used instead of loading the data
+        #[arg(
+            long = "store",
+            value_name = "Store directory",
+            conflicts_with_all = ["data", "endpoint", "watch"]
+        )]
+        store: Option<PathBuf>,
     },
 
     /// Validate RDF using ShEx schemas
@@ -279,6 +293,14 @@ pub enum Command {
         )]
         output: Option<PathBuf>,
 
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Output result format",
+            default_value_t = ShExResultFormat::Compact
+        )]
+        result_format: ShExResultFormat,
+
         /// Config file path, if unset it assumes default config
         #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
         config: Option<PathBuf>,
@@ -289,6 +311,10 @@ pub enum Command {
             default_value_t = false
         )]
         force_overwrite: bool,
+
+        /// Watch the schema, data and shapemap files and validate again when they change
+        #[arg(long = "watch", default_value_t = false)]
+        watch: bool,
     },
 
     /// Validate RDF data using SHACL shapes
@@ -341,6 +367,95 @@ pub enum Command {
         )]
         mode: ShaclValidationMode,
 
+        /// Results that make the data not conform to the shapes
+        #[arg(
+            long = "conformance-mode",
+            value_name = "Conformance mode",
+            default_value_t = ConformanceMode::Strict,
+            value_enum
+        )]
+        conformance_mode: ConformanceMode,
+
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Output result format",
+            default_value_t = ShaclResultFormat::Compact
+        )]
+        result_format: ShaclResultFormat,
+
+        #[arg(
+            short = 'o',
+            long = "output-file",
+            value_name = "Output file name, default = terminal"
+        )]
+        output: Option<PathBuf>,
+
+        #[arg(
+            long = "force-overwrite",
+            value_name = "Force overwrite mode",
+            default_value_t = false
+        )]
+        force_overwrite: bool,
+
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+
+        /// Watch the shapes and data files and validate again when they change
+        #[arg(long = "watch", default_value_t = false)]
+        watch: bool,
+    },
+
+    /// Infer RDF data from the rules declared in a SHACL shapes graph
+    ShaclInfer {
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        #[arg(
+            short = 's',
+            long = "shapes",
+            value_name = "Shapes graph: file, URI or -"
+        )]
+        shapes: InputSpec,
+
+        #[arg(
+            short = 'f',
+            long = "shapes-format",
+            value_name = "Shapes file format",
+            default_value_t = ShaclFormat::Turtle
+        )]
+        shapes_format: ShaclFormat,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Format of the inferred triples",
+            default_value_t = DataFormat::Turtle
+        )]
+        result_format: DataFormat,
+
+        /// Maximum number of passes over the rules
+        #[arg(long = "max-iterations", value_name = "Maximum number of iterations")]
+        max_iterations: Option<usize>,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -362,6 +477,9 @@ pub enum Command {
 
     /// Show information about RDF data
     Data {
+        #[command(subcommand)]
+        command: Option<DataCommand>,
+
         #[clap(value_parser = clap::value_parser!(InputSpec))]
         data: Vec<InputSpec>,
 
@@ -469,6 +587,14 @@ pub enum Command {
             default_value_t = false
         )]
         force_overwrite: bool,
+
+        /// Directory of a persistent store created with `rudof data load`, used instead of loading the data
+        #[arg(
+            long = "store",
+            value_name = "Store directory",
+            conflicts_with_all = ["data", "endpoint"]
+        )]
+        store: Option<PathBuf>,
     },
 
     /// Show information about SHACL shapes
@@ -734,6 +860,146 @@ pub enum Command {
             default_value_t = false
         )]
         force_overwrite: bool,
+
+        /// Directory of a persistent store created with `rudof data load`, used instead of loading the data
+        #[arg(
+            long = "store",
+            value_name = "Store directory",
+            conflicts_with_all = ["data", "endpoint"]
+        )]
+        store: Option<PathBuf>,
+    },
+
+    /// Start an HTTP server with JSON endpoints for validation, conversion and queries
+    Serve {
+        /// RDF data loaded when the server starts
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
+        endpoint: Option<String>,
+
+        #[arg(long = "host", value_name = "Host", default_value = "127.0.0.1")]
+        host: String,
+
+        #[arg(
+            short = 'p',
+            long = "port",
+            value_name = "Port",
+            default_value_t = 8080
+        )]
+        port: u16,
+
+        /// Number of threads that serve the requests
+        #[arg(
+            long = "threads",
+            value_name = "Number of threads",
+            default_value_t = 4
+        )]
+        threads: usize,
+
+        /// Maximum size in bytes of the body of a request. Larger requests are rejected
+        #[arg(
+            long = "max-body-size",
+            value_name = "Bytes",
+            default_value_t = 16 * 1024 * 1024
+        )]
+        max_body_size: u64,
+
+        /// ShEx schema preloaded with a name, as name=file. It can be repeated
+        #[arg(long = "shex", value_name = "name=file", value_parser = clap::value_parser!(NamedInput))]
+        shex: Vec<NamedInput>,
+
+        #[arg(
+            long = "shex-format",
+            value_name = "Schema format",
+            default_value_t = ShExFormat::ShExC
+        )]
+        shex_format: ShExFormat,
+
+        /// SHACL shapes preloaded with a name, as name=file. It can be repeated
+        #[arg(long = "shacl", value_name = "name=file", value_parser = clap::value_parser!(NamedInput))]
+        shacl: Vec<NamedInput>,
+
+        #[arg(
+            long = "shacl-format",
+            value_name = "Shapes format",
+            default_value_t = ShaclFormat::Turtle
+        )]
+        shacl_format: ShaclFormat,
+
+        /// ShEx validation config file path, if unset it assumes default config
+        #[arg(long = "shex-config", value_name = "Config file name")]
+        shex_config: Option<PathBuf>,
+
+        /// SHACL config file path, if unset it assumes default config
+        #[arg(long = "shacl-config", value_name = "Config file name")]
+        shacl_config: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DataCommand {
+    /// Bulk load RDF data in a persistent store that can be used later with the `--store` option
+    Load {
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        /// Directory of the store, which is created if it doesn't exist
+        #[arg(long = "store", value_name = "Store directory")]
+        store: PathBuf,
+
+        #[arg(
+            short = 'o',
+            long = "output-file",
+            value_name = "Output file name, default = terminal"
+        )]
+        output: Option<PathBuf>,
+
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+
+        #[arg(
+            long = "force-overwrite",
+            value_name = "Force overwrite mode",
+            default_value_t = false
+        )]
+        force_overwrite: bool,
     },
 }
 
@@ -792,6 +1058,7 @@ impl Display for ShExFormat {
 pub enum ShapeMapFormat {
     Compact,
     Internal,
+    Json,
 }
 
 impl Display for ShapeMapFormat {
@@ -799,6 +1066,7 @@ impl Display for ShapeMapFormat {
         match self {
             ShapeMapFormat::Compact => write!(dest, "compact"),
             ShapeMapFormat::Internal => write!(dest, "internal"),
+            ShapeMapFormat::Json => write!(dest, "json"),
         }
     }
 }
@@ -945,6 +1213,7 @@ pub enum OutputConvertMode {
     ShEx,
     UML,
     HTML,
+    SHACL,
 }
 
 impl Display for OutputConvertMode {
@@ -954,6 +1223,7 @@ impl Display for OutputConvertMode {
             OutputConvertMode::ShEx => write!(dest, "shex"),
             OutputConvertMode::UML => write!(dest, "uml"),
             OutputConvertMode::HTML => write!(dest, "html"),
+            OutputConvertMode::SHACL => write!(dest, "shacl"),
         }
     }
 }
@@ -999,16 +1269,106 @@ impl Display for ResultServiceFormat {
     }
 }
 
+/// Form of a SPARQL query, which is obtained from the query itself
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum QueryType {
+    Select,
+    Construct,
+    Ask,
+    Describe,
+}
+
+impl Display for QueryType {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            QueryType::Select => write!(dest, "select"),
+            QueryType::Construct => write!(dest, "construct"),
+            QueryType::Ask => write!(dest, "ask"),
+            QueryType::Describe => write!(dest, "describe"),
+        }
+    }
+}
+
+/// Format of the query results. The SPARQL results formats are used by SELECT and ASK
+/// queries, and the RDF formats by CONSTRUCT and DESCRIBE queries
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 #[clap(rename_all = "lower")]
 pub enum ResultQueryFormat {
     Internal,
+    Json,
+    Xml,
+    Csv,
+    Tsv,
+    Turtle,
+    NTriples,
+    RDFXML,
+    TriG,
+    N3,
+    NQuads,
 }
 
 impl Display for ResultQueryFormat {
     fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             ResultQueryFormat::Internal => write!(dest, "internal"),
+            ResultQueryFormat::Json => write!(dest, "json"),
+            ResultQueryFormat::Xml => write!(dest, "xml"),
+            ResultQueryFormat::Csv => write!(dest, "csv"),
+            ResultQueryFormat::Tsv => write!(dest, "tsv"),
+            ResultQueryFormat::Turtle => write!(dest, "turtle"),
+            ResultQueryFormat::NTriples => write!(dest, "ntriples"),
+            ResultQueryFormat::RDFXML => write!(dest, "rdfxml"),
+            ResultQueryFormat::TriG => write!(dest, "trig"),
+            ResultQueryFormat::N3 => write!(dest, "n3"),
+            ResultQueryFormat::NQuads => write!(dest, "nquads"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[clap(rename_all = "lower")]
+pub enum ShExResultFormat {
+    Compact,
+    Json,
+    ShapeMap,
+}
+
+impl Display for ShExResultFormat {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ShExResultFormat::Compact => write!(dest, "compact"),
+            ShExResultFormat::Json => write!(dest, "json"),
+            ShExResultFormat::ShapeMap => write!(dest, "shapemap"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[clap(rename_all = "lower")]
+pub enum ShaclResultFormat {
+    Compact,
+    Internal,
+    Json,
+    Turtle,
+    NTriples,
+    RDFXML,
+    TriG,
+    N3,
+    NQuads,
+}
+
+impl Display for ShaclResultFormat {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ShaclResultFormat::Compact => write!(dest, "compact"),
+            ShaclResultFormat::Internal => write!(dest, "internal"),
+            ShaclResultFormat::Json => write!(dest, "json"),
+            ShaclResultFormat::Turtle => write!(dest, "turtle"),
+            ShaclResultFormat::NTriples => write!(dest, "ntriples"),
+            ShaclResultFormat::RDFXML => write!(dest, "rdfxml"),
+            ShaclResultFormat::TriG => write!(dest, "trig"),
+            ShaclResultFormat::N3 => write!(dest, "n3"),
+            ShaclResultFormat::NQuads => write!(dest, "nquads"),
         }
     }
 }
@@ -23,26 +23,43 @@ use clap::Parser;
 use dctap::{DCTap, DCTapConfig, TapConfig};
 use iri_s::IriS;
 use oxiri::Iri;
+use oxrdf::{NamedNode as OxNamedNode, Subject as OxSubject, Term as OxTerm, Variable};
 use prefixmap::{IriRef, PrefixMap};
 use shacl_ast::{Schema as ShaclSchema, ShaclParser, ShaclWriter};
+use shacl_validation::rules::RuleExecutor;
 use shacl_validation::shacl_config::ShaclConfig;
-use shacl_validation::shacl_processor::{EndpointValidation, GraphValidation, ShaclValidationMode};
+#[cfg(feature = "rocksdb")]
+use shacl_validation::shacl_processor::RdfStoreValidation;
+use shacl_validation::shacl_processor::{
+    EndpointValidation, GraphValidation, ShaclProcessor, ShaclValidationMode,
+};
 use shacl_validation::store::ShaclDataManager;
-use shapemap::{query_shape_map::QueryShapeMap, NodeSelector, ShapeSelector};
+use shacl_validation::validation_report::report::{ConformanceMode, ValidationReport};
+use shapemap::{
+    query_shape_map::QueryShapeMap, NodeSelect, NodeSelector, ResultShapeMap, ShapeSelector,
+};
 use shapes_converter::{shex_to_sparql::ShEx2SparqlConfig, ShEx2Sparql};
 use shapes_converter::{
-    ConverterConfig, ImageFormat, ShEx2Html, ShEx2HtmlConfig, ShEx2Uml, ShEx2UmlConfig, Shacl2ShEx,
-    Shacl2ShExConfig, Tap2ShEx, UmlGenerationMode,
+    ConverterConfig, ImageFormat, ShEx2Html, ShEx2HtmlConfig, ShEx2Shacl, ShEx2ShaclConfig,
+    ShEx2Uml, ShEx2UmlConfig, Shacl2ShEx, Shacl2ShExConfig, Tap2ShEx, UmlGenerationMode,
 };
-use shex_ast::{object_value::ObjectValue, shexr::shexr_parser::ShExRParser};
+use shex_ast::shexr::shexr_parser::ShExRParser;
+use shex_ast::shexr::shexr_writer::ShExRWriter;
 use shex_ast::{ShapeExprLabel, SimpleReprSchema};
 use shex_compact::{ShExFormatter, ShExParser, ShapeMapParser, ShapemapFormatter};
 use shex_validation::{
     ResolveMethod, SchemaWithoutImports, ShExConfig, ShExConfigMain, Validator, ValidatorConfig,
 };
+use sparesults::{QueryResultsFormat, QueryResultsSerializer};
+use spargebra::Query as SparqlQuery;
+#[cfg(feature = "rocksdb")]
+use sparql_service::RdfStore;
 use sparql_service::{QueryConfig, RdfData, ServiceConfig, ServiceDescription};
 use srdf::srdf_graph::SRDFGraph;
-use srdf::{QuerySolution2, RDFFormat, RdfDataConfig, SRDFBuilder, SRDFSparql, VarName2, SRDF};
+use srdf::{
+    QuerySRDF2, QuerySolution2, QuerySolutions, RDFFormat, RdfDataConfig, SRDFBasic, SRDFBuilder,
+    SRDFSparql, Triple, VarName2, SRDF,
+};
 use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{self, BufWriter, Read, Write};
@@ -57,6 +74,8 @@ pub mod cli;
 pub mod input_convert_format;
 pub mod input_spec;
 pub mod output_convert_format;
+mod serve;
+mod watch;
 
 pub use cli::*;
 pub use input_convert_format::InputConvertFormat;
@@ -152,7 +171,25 @@ fn main() -> Result<()> {
             shacl_validation_mode,
             output,
             force_overwrite,
+            watch,
+            store,
         }) => match validation_mode {
+            ValidationMode::ShEx if *watch => watch::run_watch_shex(
+                schema,
+                schema_format,
+                data,
+                data_format,
+                endpoint,
+                reader_mode,
+                node,
+                shape,
+                shapemap,
+                shapemap_format,
+                cli.debug,
+                output,
+                &ValidatorConfig::default(),
+                *force_overwrite,
+            ),
             ValidationMode::ShEx => run_validate_shex(
                 schema,
                 schema_format,
@@ -164,10 +201,12 @@ fn main() -> Result<()> {
                 shape,
                 shapemap,
                 shapemap_format,
+                &ShExResultFormat::Compact,
                 cli.debug,
                 output,
                 &ValidatorConfig::default(),
                 *force_overwrite,
+                store,
             ),
             ValidationMode::SHACL => {
                 let shacl_format = match schema_format {
@@ -188,6 +227,21 @@ fn main() -> Result<()> {
                     ShExFormat::N3 => Ok(ShaclFormat::N3),
                     ShExFormat::NQuads => Ok(ShaclFormat::NQuads),
                 }?;
+                if *watch {
+                    return watch::run_watch_shacl(
+                        schema,
+                        &shacl_format,
+                        data,
+                        data_format,
+                        endpoint,
+                        reader_mode,
+                        *shacl_validation_mode,
+                        ConformanceMode::default(),
+                        cli.debug,
+                        output,
+                        *force_overwrite,
+                    );
+                }
                 run_validate_shacl(
                     schema,
                     &shacl_format,
@@ -195,9 +249,12 @@ fn main() -> Result<()> {
                     data_format,
                     endpoint,
                     *shacl_validation_mode,
+                    ConformanceMode::default(),
+                    &ShaclResultFormat::Compact,
                     cli.debug,
                     output,
                     *force_overwrite,
+                    store,
                 )
             }
         },
@@ -212,20 +269,31 @@ fn main() -> Result<()> {
             shape,
             shapemap,
             shapemap_format,
+            result_format,
             output,
             config,
             force_overwrite,
+            watch,
         }) => {
-            let config = match config {
-                Some(config_path) => match ValidatorConfig::from_path(config_path) {
-                    Ok(c) => Ok(c),
-                    Err(e) => Err(anyhow!(
-                        "Error obtaining ShEx validation config from {}: {e}",
-                        config_path.display()
-                    )),
-                },
-                None => Ok(ValidatorConfig::default()),
-            }?;
+            let config = get_validator_config(config)?;
+            if *watch {
+                return watch::run_watch_shex(
+                    schema,
+                    schema_format,
+                    data,
+                    data_format,
+                    endpoint,
+                    reader_mode,
+                    node,
+                    shape,
+                    shapemap,
+                    shapemap_format,
+                    cli.debug,
+                    output,
+                    &config,
+                    *force_overwrite,
+                );
+            }
             run_validate_shex(
                 schema,
                 schema_format,
@@ -237,10 +305,12 @@ fn main() -> Result<()> {
                 shape,
                 shapemap,
                 shapemap_format,
+                result_format,
                 cli.debug,
                 output,
                 &config,
                 *force_overwrite,
+                &None,
             )
         }
         Some(Command::ShaclValidate {
@@ -251,11 +321,29 @@ fn main() -> Result<()> {
             reader_mode,
             endpoint,
             mode,
+            conformance_mode,
+            result_format,
             output,
             force_overwrite,
             config,
+            watch,
         }) => {
             let shacl_config = get_shacl_config(config)?;
+            if *watch {
+                return watch::run_watch_shacl(
+                    shapes,
+                    shapes_format,
+                    data,
+                    data_format,
+                    endpoint,
+                    reader_mode,
+                    *mode,
+                    *conformance_mode,
+                    cli.debug,
+                    output,
+                    *force_overwrite,
+                );
+            }
             run_validate_shacl(
                 shapes,
                 shapes_format,
@@ -263,12 +351,67 @@ fn main() -> Result<()> {
                 data_format,
                 endpoint,
                 *mode,
+                *conformance_mode,
+                result_format,
                 cli.debug,
                 output,
                 *force_overwrite,
+                &None,
+            )
+        }
+        Some(Command::ShaclInfer {
+            shapes,
+            shapes_format,
+            data,
+            data_format,
+            reader_mode,
+            result_format,
+            max_iterations,
+            output,
+            force_overwrite,
+            config,
+        }) => {
+            let config = get_shacl_config(config)?;
+            run_infer_shacl(
+                shapes,
+                shapes_format,
+                data,
+                data_format,
+                reader_mode,
+                result_format,
+                max_iterations,
+                cli.debug,
+                output,
+                *force_overwrite,
+                &config,
+            )
+        }
+        Some(Command::Data {
+            command:
+                Some(DataCommand::Load {
+                    data,
+                    data_format,
+                    reader_mode,
+                    store,
+                    output,
+                    config,
+                    force_overwrite,
+                }),
+            ..
+        }) => {
+            let config = get_rdf_data_config(config)?;
+            run_data_load(
+                data,
+                data_format,
+                reader_mode,
+                store,
+                &config,
+                output,
+                *force_overwrite,
             )
         }
         Some(Command::Data {
+            command: None,
             data,
             data_format,
             reader_mode,
@@ -301,6 +444,7 @@ fn main() -> Result<()> {
             output,
             config,
             force_overwrite,
+            store,
         }) => {
             let config = get_rdf_data_config(config)?;
             run_node(
@@ -316,6 +460,7 @@ fn main() -> Result<()> {
                 output,
                 &config,
                 *force_overwrite,
+                store,
             )
         }
         Some(Command::Shapemap {
@@ -401,6 +546,7 @@ fn main() -> Result<()> {
             result_query_format,
             config,
             force_overwrite,
+            store,
         }) => {
             let query_config = get_query_config(config)?;
             run_query(
@@ -414,6 +560,43 @@ fn main() -> Result<()> {
                 &query_config,
                 cli.debug,
                 *force_overwrite,
+                store,
+            )
+        }
+        Some(Command::Serve {
+            data,
+            data_format,
+            reader_mode,
+            endpoint,
+            host,
+            port,
+            threads,
+            max_body_size,
+            shex,
+            shex_format,
+            shacl,
+            shacl_format,
+            shex_config,
+            shacl_config,
+        }) => {
+            let validator_config = get_validator_config(shex_config)?;
+            let shacl_config = get_shacl_config(shacl_config)?;
+            serve::run_serve(
+                data,
+                data_format,
+                endpoint,
+                reader_mode,
+                shex,
+                shex_format,
+                shacl,
+                shacl_format,
+                host,
+                *port,
+                *threads,
+                *max_body_size,
+                validator_config,
+                &shacl_config,
+                cli.debug,
             )
         }
         None => {
@@ -537,9 +720,13 @@ fn show_schema(
             writeln!(writer, "{str}")?;
             Ok(())
         }
-        _ => Err(anyhow!(
-            "Not implemented conversion to {result_schema_format} yet"
-        )),
+        _ => {
+            let data_format = shex_format_to_data_format(result_schema_format)?;
+            let mut shexr_writer: ShExRWriter<SRDFGraph> = ShExRWriter::new();
+            shexr_writer.write(schema)?;
+            shexr_writer.serialize(data_format.into(), &mut writer)?;
+            Ok(())
+        }
     }
 }
 
@@ -555,15 +742,26 @@ fn run_validate_shex(
     maybe_shape: &Option<String>,
     shapemap_path: &Option<PathBuf>,
     shapemap_format: &ShapeMapFormat,
+    result_format: &ShExResultFormat,
     debug: u8,
     output: &Option<PathBuf>,
     config: &ValidatorConfig,
     force_overwrite: bool,
+    store: &Option<PathBuf>,
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
     let schema_json = parse_schema(schema, schema_format, reader_mode, &config.shex_config())?;
     let mut schema: CompiledSchema = CompiledSchema::new();
     schema.from_schema_json(&schema_json)?;
+    let shapemap = get_shapemap(shapemap_path, shapemap_format, maybe_node, maybe_shape)?;
+    let mut validator = Validator::new(schema, config);
+    #[cfg(feature = "rocksdb")]
+    if let Some(store) = store {
+        let data = RdfStore::open_read_only(store)?;
+        return validate_shex_data(&mut validator, &shapemap, &data, result_format, &mut writer);
+    }
+    #[cfg(not(feature = "rocksdb"))]
+    store_not_supported(store)?;
     let rdf_data_config = match &config.data_config {
         None => RdfDataConfig::default(),
         Some(cfg) => cfg.clone(),
@@ -576,6 +774,54 @@ fn run_validate_shex(
         debug,
         &rdf_data_config,
     )?;
+    validate_shex_data(&mut validator, &shapemap, &data, result_format, &mut writer)
+}
+
+fn validate_shex_data<S>(
+    validator: &mut Validator,
+    shapemap: &QueryShapeMap,
+    data: &S,
+    result_format: &ShExResultFormat,
+    writer: &mut Box<dyn Write>,
+) -> Result<()>
+where
+    S: SRDF + QuerySRDF2 + Sync,
+{
+    let result = validator.validate_shapemap_parallel(shapemap, data);
+    match result {
+        Result::Ok(_t) => match validator.result_map(data.prefixmap()) {
+            Result::Ok(result_map) => match result_format {
+                ShExResultFormat::Compact => {
+                    writeln!(writer, "Result:\n{}", result_map)?;
+                    Ok(())
+                }
+                ShExResultFormat::Json => {
+                    let str = serde_json::to_string_pretty(&result_map.result_shape_map())
+                        .context("Error converting result map to JSON")?;
+                    writeln!(writer, "{str}")?;
+                    Ok(())
+                }
+                ShExResultFormat::ShapeMap => {
+                    writeln!(writer, "{}", result_map.result_shape_map())?;
+                    Ok(())
+                }
+            },
+            Err(err) => {
+                bail!("Error generating result_map after validation: {err}");
+            }
+        },
+        Result::Err(err) => {
+            bail!("{err}");
+        }
+    }
+}
+
+fn get_shapemap(
+    shapemap_path: &Option<PathBuf>,
+    shapemap_format: &ShapeMapFormat,
+    maybe_node: &Option<String>,
+    maybe_shape: &Option<String>,
+) -> Result<QueryShapeMap> {
     let mut shapemap = match shapemap_path {
         None => QueryShapeMap::new(),
         Some(shapemap_buf) => parse_shapemap(shapemap_buf, shapemap_format)?,
@@ -599,22 +845,7 @@ fn run_validate_shex(
             )
         }
     };
-    let mut validator = Validator::new(schema, config);
-    let result = validator.validate_shapemap(&shapemap, &data);
-    match result {
-        Result::Ok(_t) => match validator.result_map(Some(data.prefixmap())) {
-            Result::Ok(result_map) => {
-                writeln!(writer, "Result:\n{}", result_map)?;
-                Ok(())
-            }
-            Err(err) => {
-                bail!("Error generating result_map after validation: {err}");
-            }
-        },
-        Result::Err(err) => {
-            bail!("{err}");
-        }
-    }
+    Ok(shapemap)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -625,16 +856,35 @@ fn run_validate_shacl(
     data_format: &DataFormat,
     endpoint: &Option<String>,
     mode: ShaclValidationMode,
+    conformance_mode: ConformanceMode,
+    result_format: &ShaclResultFormat,
     _debug: u8,
     output: &Option<PathBuf>,
     force_overwrite: bool,
+    store: &Option<PathBuf>,
 ) -> Result<()> {
-    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    let (mut writer, color) = get_writer(output, force_overwrite)?;
 
     // TODO: Remove the following cast by refactoring the validate_shex to support more types of data
     let data = cast_to_data_path(data)?;
     let reader = input.open_read()?;
 
+    #[cfg(feature = "rocksdb")]
+    if let Some(store) = store {
+        let data = RdfStore::open_read_only(store)?;
+        let validator = RdfStoreValidation::new(&data, mode);
+        let schema = ShaclDataManager::load(reader, map_shacl_format(shapes_format)?, None)?;
+        let result = match shacl_validation::shacl_processor::ShaclProcessor::validate(
+            &validator, &schema,
+        ) {
+            Ok(result) => result.with_conformance_mode(conformance_mode),
+            Err(e) => bail!("Error validating the store: {e}"),
+        };
+        return write_shacl_report(result, data.prefixmap(), result_format, color, &mut writer);
+    }
+    #[cfg(not(feature = "rocksdb"))]
+    store_not_supported(store)?;
+
     if let Some(data) = data {
         let validator = match GraphValidation::new(&data, map_data_format(data_format)?, None, mode)
         {
@@ -645,11 +895,11 @@ fn run_validate_shacl(
         let result = match shacl_validation::shacl_processor::ShaclProcessor::validate(
             &validator, &schema,
         ) {
-            Ok(result) => result,
+            Ok(result) => result.with_conformance_mode(conformance_mode),
             Err(e) => bail!("Error validating the graph: {e}"),
         };
-        writeln!(writer, "Result:\n{:?}", result)?;
-        Ok(())
+        let prefixmap = validator.store().prefixmap();
+        write_shacl_report(result, prefixmap, result_format, color, &mut writer)
     } else if let Some(endpoint) = endpoint {
         let validator = match EndpointValidation::new(endpoint, mode) {
             Ok(validator) => validator,
@@ -659,16 +909,92 @@ fn run_validate_shacl(
         let result = match shacl_validation::shacl_processor::ShaclProcessor::validate(
             &validator, &schema,
         ) {
-            Ok(result) => result,
+            Ok(result) => result.with_conformance_mode(conformance_mode),
             Err(e) => bail!("Error validating the graph: {e}"),
         };
-        writeln!(writer, "Result:\n{:?}", result)?;
-        Ok(())
+        let prefixmap = validator.store().prefixmap().unwrap_or_default();
+        write_shacl_report(result, prefixmap, result_format, color, &mut writer)
     } else {
         bail!("Please provide either a local data source or an endpoint")
     }
 }
 
+fn write_shacl_report<S: SRDFBasic>(
+    report: ValidationReport<S>,
+    prefixmap: PrefixMap,
+    result_format: &ShaclResultFormat,
+    color: ColorSupport,
+    writer: &mut Box<dyn Write>,
+) -> Result<()> {
+    let report = report.with_nodes_prefixmap(prefixmap);
+    let rdf_format = match result_format {
+        ShaclResultFormat::Compact => {
+            let report = match color {
+                ColorSupport::NoColor => report.without_colors(),
+                ColorSupport::WithColor => report,
+            };
+            write!(writer, "{report}")?;
+            return Ok(());
+        }
+        ShaclResultFormat::Internal => {
+            writeln!(writer, "{report:?}")?;
+            return Ok(());
+        }
+        ShaclResultFormat::Json => {
+            let str = serde_json::to_string_pretty(&report.to_json_ld())?;
+            writeln!(writer, "{str}")?;
+            return Ok(());
+        }
+        ShaclResultFormat::Turtle => RDFFormat::Turtle,
+        ShaclResultFormat::NTriples => RDFFormat::NTriples,
+        ShaclResultFormat::RDFXML => RDFFormat::RDFXML,
+        ShaclResultFormat::TriG => RDFFormat::TriG,
+        ShaclResultFormat::N3 => RDFFormat::N3,
+        ShaclResultFormat::NQuads => RDFFormat::NQuads,
+    };
+    let mut rdf = SRDFGraph::empty();
+    report.to_rdf(&mut rdf)?;
+    rdf.serialize(rdf_format, writer)?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_infer_shacl(
+    input: &InputSpec,
+    shapes_format: &ShaclFormat,
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    reader_mode: &RDFReaderMode,
+    result_format: &DataFormat,
+    max_iterations: &Option<usize>,
+    debug: u8,
+    output: &Option<PathBuf>,
+    force_overwrite: bool,
+    config: &ShaclConfig,
+) -> Result<()> {
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    let data_config = match &config.data {
+        None => RdfDataConfig::default(),
+        Some(cfg) => cfg.clone(),
+    };
+    let shacl_schema = parse_shacl(input, shapes_format, reader_mode, &data_config)?;
+    let mut data = get_data(data, data_format, &None, reader_mode, debug, &data_config)?;
+    let mut executor = RuleExecutor::new(&shacl_schema);
+    if let Some(max_iterations) = max_iterations {
+        executor = executor.with_max_iterations(*max_iterations);
+    }
+    let inferred = executor.infer(&mut data)?;
+
+    // only the inferred triples are written, using the prefixes of the data
+    let mut result = RdfData::empty();
+    result.add_prefix_map(data.prefixmap())?;
+    for triple in inferred {
+        result.add_triple(&triple.subj(), &triple.pred(), &triple.obj())?;
+    }
+    result.serialize(RDFFormat::from(*result_format), &mut writer)?;
+    Ok(())
+}
+
 fn run_shacl(
     input: &InputSpec,
     shapes_format: &ShaclFormat,
@@ -767,6 +1093,9 @@ fn run_convert(
         (InputConvertMode::SHACL, OutputConvertMode::ShEx) => {
             run_shacl2shex(input, format, output, result_format, &converter_config.shacl2shex_config(), force_overwrite, reader_mode)
         }
+        (InputConvertMode::ShEx, OutputConvertMode::SHACL) => {
+            run_shex2shacl(input, format, output, result_format, &converter_config.shex2shacl_config(), force_overwrite, reader_mode)
+        }
         (InputConvertMode::ShEx, OutputConvertMode::HTML) => {
             match target_folder {
                 None => Err(anyhow!(
@@ -820,6 +1149,9 @@ fn run_shacl2shex(
     let schema = parse_shacl(input, &schema_format, reader_mode, &data_config)?;
     let mut converter = Shacl2ShEx::new(config);
     converter.convert(&schema)?;
+    for warning in converter.warnings() {
+        writeln!(io::stderr(), "Warning: {warning}")?;
+    }
     let (writer, color) = get_writer(output, force_overwrite)?;
     let result_schema_format = match &result_format {
         OutputConvertFormat::Default => ShExFormat::ShExC,
@@ -839,6 +1171,42 @@ fn run_shacl2shex(
     Ok(())
 }
 
+fn run_shex2shacl(
+    input: &InputSpec,
+    format: &InputConvertFormat,
+    output: &Option<PathBuf>,
+    result_format: &OutputConvertFormat,
+    config: &ShEx2ShaclConfig,
+    force_overwrite: bool,
+    reader_mode: &RDFReaderMode,
+) -> Result<()> {
+    let schema_format = match format {
+        InputConvertFormat::ShExC => Ok(ShExFormat::ShExC),
+        InputConvertFormat::ShExJ => Ok(ShExFormat::ShExJ),
+        _ => Err(anyhow!("Can't obtain ShEx format from {format}")),
+    }?;
+    let schema = parse_schema(input, &schema_format, reader_mode, &config.shex_config())?;
+    let mut converter = ShEx2Shacl::new(config);
+    converter.convert(&schema)?;
+    for warning in converter.warnings() {
+        writeln!(io::stderr(), "Warning: {warning}")?;
+    }
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    match result_format {
+        OutputConvertFormat::Default | OutputConvertFormat::Turtle => {
+            let mut shacl_writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
+            shacl_writer.write(converter.current_shacl())?;
+            shacl_writer.serialize(RDFFormat::Turtle, &mut writer)?;
+            Ok(())
+        }
+        OutputConvertFormat::Internal => {
+            writeln!(writer, "{}", converter.current_shacl())?;
+            Ok(())
+        }
+        _ => bail!("ShEx2Shacl converter, {result_format} format not supported for SHACL output"),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_shex2uml(
     input: &InputSpec,
@@ -1076,23 +1444,24 @@ fn get_data(
     _debug: u8,
     config: &RdfDataConfig,
 ) -> Result<RdfData> {
-    match (data.is_empty(), endpoint) {
-        (true, None) => {
+    let mut rdf_data = match (data.is_empty(), endpoint) {
+        (true, None) if config.endpoints().is_empty() => {
             bail!("None of `data` or `endpoint` parameters have been specified for validation")
         }
-        (false, None) => {
-            // let data_path = cast_to_data_path(data)?;
+        (true, _) => RdfData::new(),
+        (false, _) => {
             let data = parse_data(data, data_format, reader_mode, config)?;
-            Ok(RdfData::from_graph(data)?)
-        }
-        (true, Some(endpoint)) => {
-            let endpoint = SRDFSparql::from_str(endpoint)?;
-            Ok(RdfData::from_endpoint(endpoint))
-        }
-        (false, Some(_)) => {
-            bail!("Only one of 'data' or 'endpoint' supported at the same time at this moment")
+            RdfData::from_graph(data)?
         }
+    };
+    // The data of the endpoints is merged with the local data
+    if let Some(endpoint) = endpoint {
+        rdf_data.add_endpoint(SRDFSparql::from_str(endpoint)?)?;
+    }
+    for iri in config.endpoints() {
+        rdf_data.add_endpoint(SRDFSparql::new(&iri)?)?;
     }
+    Ok(rdf_data)
 }
 
 fn get_str(input: &InputSpec) -> Result<String> {
@@ -1134,10 +1503,25 @@ fn run_node(
     output: &Option<PathBuf>,
     config: &RdfDataConfig,
     force_overwrite: bool,
+    store: &Option<PathBuf>,
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
-    let data = get_data(data, data_format, endpoint, reader_mode, debug, config)?;
     let node_selector = parse_node_selector(node_str)?;
+    #[cfg(feature = "rocksdb")]
+    if let Some(store) = store {
+        let data = RdfStore::open_read_only(store)?;
+        return show_node_info(
+            node_selector,
+            predicates,
+            &data,
+            show_node_mode,
+            show_hyperlinks,
+            &mut writer,
+        );
+    }
+    #[cfg(not(feature = "rocksdb"))]
+    store_not_supported(store)?;
+    let data = get_data(data, data_format, endpoint, reader_mode, debug, config)?;
     show_node_info(
         node_selector,
         predicates,
@@ -1157,10 +1541,13 @@ fn show_node_info<S, W: Write>(
     writer: &mut W,
 ) -> Result<()>
 where
-    S: SRDF,
+    S: SRDF + QuerySRDF2,
 {
-    for node in node_selector.iter_node(rdf) {
-        let subject = node_to_subject(node, rdf)?;
+    for node in node_selector.select(rdf)? {
+        let subject = match S::term_as_subject(&node) {
+            None => bail!("Node {node} can't be used as subject to show its information"),
+            Some(subject) => subject,
+        };
         writeln!(writer, "Information about node")?;
 
         // Show outgoing arcs
@@ -1263,30 +1650,9 @@ fn run_shapemap(
             writeln!(writer, "{shapemap:?}")?;
             Ok(())
         }
-    }
-}
-
-fn node_to_subject<S>(node: &ObjectValue, rdf: &S) -> Result<S::Subject>
-where
-    S: SRDF,
-{
-    match node {
-        ObjectValue::IriRef(iri_ref) => {
-            let iri = match iri_ref {
-                IriRef::Iri(iri_s) => S::iri_s2iri(iri_s),
-                IriRef::Prefixed { prefix, local } => {
-                    let iri_s = rdf.resolve_prefix_local(prefix, local)?;
-
-                    S::iri_s2iri(&iri_s)
-                }
-            };
-            let term = S::iri_as_term(iri);
-            match S::term_as_subject(&term) {
-                None => bail!("node_to_subject: Can't convert term {term} to subject"),
-                Some(subject) => Ok(subject),
-            }
+        ShapeMapFormat::Json => {
+            bail!("JSON output is only available for result shape maps, use shex-validate --result-format json")
         }
-        ObjectValue::Literal(_lit) => Err(anyhow!("Node must be an IRI")),
     }
 }
 
@@ -1307,6 +1673,64 @@ fn run_data(
     Ok(())
 }
 
+/// Loads the data in the store of a directory, which keeps the data that had already been loaded
+#[cfg(feature = "rocksdb")]
+fn run_data_load(
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    reader_mode: &RDFReaderMode,
+    store: &Path,
+    config: &RdfDataConfig,
+    output: &Option<PathBuf>,
+    force_overwrite: bool,
+) -> Result<()> {
+    if data.is_empty() {
+        bail!("No data to load in the store {}", store.display())
+    }
+    let mut rdf_store = RdfStore::open(store)?;
+    let rdf_format = data_format2rdf_format(data_format);
+    let base = config.base.as_ref().map(|iri| iri.as_str());
+    for d in data {
+        let start = Instant::now();
+        let reader = d.open_read()?;
+        rdf_store.load_reader(reader, &rdf_format, base, &(*reader_mode).into())?;
+        debug!("Loaded {d:?} in {:?}", start.elapsed());
+    }
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    writeln!(
+        writer,
+        "Store {} contains {} quads",
+        store.display(),
+        rdf_store.len()?
+    )?;
+    Ok(())
+}
+
+#[cfg(not(feature = "rocksdb"))]
+fn run_data_load(
+    _data: &Vec<InputSpec>,
+    _data_format: &DataFormat,
+    _reader_mode: &RDFReaderMode,
+    store: &Path,
+    _config: &RdfDataConfig,
+    _output: &Option<PathBuf>,
+    _force_overwrite: bool,
+) -> Result<()> {
+    store_not_supported(&Some(store.to_path_buf()))
+}
+
+/// Persistent stores are only available when rudof is compiled with the `rocksdb` feature
+#[cfg(not(feature = "rocksdb"))]
+fn store_not_supported(store: &Option<PathBuf>) -> Result<()> {
+    match store {
+        None => Ok(()),
+        Some(store) => bail!(
+            "Can't open the store {}: rudof has been compiled without the `rocksdb` feature",
+            store.display()
+        ),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_query(
     data: &Vec<InputSpec>,
@@ -1314,14 +1738,22 @@ fn run_query(
     endpoint: &Option<String>,
     reader_mode: &RDFReaderMode,
     query: &InputSpec,
-    _result_query_format: &ResultQueryFormat,
+    result_query_format: &ResultQueryFormat,
     output: &Option<PathBuf>,
     config: &QueryConfig,
     debug: u8,
     force_overwrite: bool,
+    store: &Option<PathBuf>,
 ) -> Result<()> {
-    use crate::srdf::QuerySRDF2;
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    let query = get_str(query)?;
+    #[cfg(feature = "rocksdb")]
+    if let Some(store) = store {
+        let data = RdfStore::open_read_only(store)?;
+        return query_data(&data, &query, result_query_format, &mut writer);
+    }
+    #[cfg(not(feature = "rocksdb"))]
+    store_not_supported(store)?;
     let data_config = match &config.data_config {
         None => RdfDataConfig::default(),
         Some(dc) => dc.clone(),
@@ -1334,20 +1766,149 @@ fn run_query(
         debug,
         &data_config,
     )?;
-    let query = get_str(query)?;
-    let results = data.query_select(query.as_str())?;
-    let mut results_iter = results.iter().peekable();
-    if let Some(first) = results_iter.peek() {
-        show_variables(&mut writer, first.variables())?;
-        for result in results_iter {
-            show_result(&mut writer, result, &data.prefixmap())?
+    query_data(&data, &query, result_query_format, &mut writer)
+}
+
+/// Form of a SPARQL query, which decides how it is run and how its results are shown
+pub(crate) fn query_type(query: &str) -> Result<QueryType> {
+    Ok(match SparqlQuery::parse(query, None)? {
+        SparqlQuery::Select { .. } => QueryType::Select,
+        SparqlQuery::Construct { .. } => QueryType::Construct,
+        SparqlQuery::Describe { .. } => QueryType::Describe,
+        SparqlQuery::Ask { .. } => QueryType::Ask,
+    })
+}
+
+fn query_data<S, W>(
+    data: &S,
+    query: &str,
+    result_query_format: &ResultQueryFormat,
+    writer: &mut W,
+) -> Result<()>
+where
+    S: QuerySRDF2 + SRDFBasic<IRI = OxNamedNode, Subject = OxSubject, Term = OxTerm>,
+    S::Err: std::error::Error + Send + Sync + 'static,
+    W: Write,
+{
+    let prefixmap = data.prefixmap().unwrap_or_default();
+    let query_type = query_type(query)?;
+    match query_type {
+        QueryType::Select => {
+            let results = data.query_select(query)?;
+            match query_results_format(result_query_format)? {
+                None => {
+                    let mut results_iter = results.iter().peekable();
+                    if let Some(first) = results_iter.peek() {
+                        show_variables(writer, first.variables())?;
+                        for result in results_iter {
+                            show_result(writer, result, &prefixmap)?
+                        }
+                    } else {
+                        write!(writer, "No results")?;
+                    }
+                }
+                Some(format) => serialize_solutions(writer, &results, format)?,
+            }
+        }
+        QueryType::Ask => {
+            let result = data.query_ask(query)?;
+            match query_results_format(result_query_format)? {
+                None => writeln!(writer, "{result}")?,
+                Some(format) => {
+                    QueryResultsSerializer::from_format(format)
+                        .serialize_boolean_to_writer(writer, result)?;
+                }
+            }
+        }
+        QueryType::Construct => {
+            let rdf_format = graph_results_format(result_query_format, query_type)?;
+            let triples = data.query_construct(query)?;
+            serialize_triples(writer, triples, prefixmap, rdf_format)?
+        }
+        QueryType::Describe => {
+            let rdf_format = graph_results_format(result_query_format, query_type)?;
+            let triples = data.query_describe(query)?;
+            serialize_triples(writer, triples, prefixmap, rdf_format)?
+        }
+    }
+    Ok(())
+}
+
+/// RDF format for the triples of CONSTRUCT and DESCRIBE queries, Turtle for the internal format
+fn graph_results_format(format: &ResultQueryFormat, query_type: QueryType) -> Result<RDFFormat> {
+    match format {
+        ResultQueryFormat::Internal | ResultQueryFormat::Turtle => Ok(RDFFormat::Turtle),
+        ResultQueryFormat::NTriples => Ok(RDFFormat::NTriples),
+        ResultQueryFormat::RDFXML => Ok(RDFFormat::RDFXML),
+        ResultQueryFormat::TriG => Ok(RDFFormat::TriG),
+        ResultQueryFormat::N3 => Ok(RDFFormat::N3),
+        ResultQueryFormat::NQuads => Ok(RDFFormat::NQuads),
+        ResultQueryFormat::Json
+        | ResultQueryFormat::Xml
+        | ResultQueryFormat::Csv
+        | ResultQueryFormat::Tsv => {
+            bail!(
+                "Result format {format} not supported for {query_type} queries, use an RDF format"
+            )
         }
-    } else {
-        write!(writer, "No results")?;
     }
+}
+
+/// Writes the triples obtained by a CONSTRUCT or DESCRIBE query in an RDF format
+fn serialize_triples<S, W>(
+    writer: &mut W,
+    triples: Vec<Triple<S>>,
+    prefixmap: PrefixMap,
+    format: RDFFormat,
+) -> Result<()>
+where
+    S: SRDFBasic<IRI = OxNamedNode, Subject = OxSubject, Term = OxTerm>,
+    W: Write,
+{
+    let mut graph = SRDFGraph::new();
+    graph.add_prefix_map(prefixmap)?;
+    for triple in triples {
+        graph.add_triple(&triple.subj(), &triple.pred(), &triple.obj())?;
+    }
+    graph.serialize(format, writer)?;
     Ok(())
 }
 
+/// Writes the solutions of a SELECT query in a SPARQL results format
+fn serialize_solutions<S: SRDFBasic<Term = OxTerm>, W: Write>(
+    writer: W,
+    results: &QuerySolutions<S>,
+    format: QueryResultsFormat,
+) -> Result<()> {
+    let variables: Vec<Variable> = results
+        .variables()
+        .map(|v| Variable::new_unchecked(v.as_str()))
+        .collect();
+    let mut serializer = QueryResultsSerializer::from_format(format)
+        .serialize_solutions_to_writer(writer, variables.clone())?;
+    for result in results.iter() {
+        serializer.serialize(
+            variables
+                .iter()
+                .filter_map(|v| result.find_solution(v.as_str()).map(|t| (v, t))),
+        )?
+    }
+    serializer.finish()?;
+    Ok(())
+}
+
+/// SPARQL results format for the solutions of SELECT and ASK queries, `None` for the internal format
+fn query_results_format(format: &ResultQueryFormat) -> Result<Option<QueryResultsFormat>> {
+    match format {
+        ResultQueryFormat::Internal => Ok(None),
+        ResultQueryFormat::Json => Ok(Some(QueryResultsFormat::Json)),
+        ResultQueryFormat::Xml => Ok(Some(QueryResultsFormat::Xml)),
+        ResultQueryFormat::Csv => Ok(Some(QueryResultsFormat::Csv)),
+        ResultQueryFormat::Tsv => Ok(Some(QueryResultsFormat::Tsv)),
+        _ => bail!("Result format {format} is only supported for CONSTRUCT and DESCRIBE queries"),
+    }
+}
+
 fn show_variables<'a, W: Write>(
     writer: &mut W,
     vars: impl Iterator<Item = &'a VarName2>,
@@ -1360,9 +1921,9 @@ fn show_variables<'a, W: Write>(
     Ok(())
 }
 
-fn show_result<W: Write>(
+fn show_result<S: SRDFBasic<Term = OxTerm>, W: Write>(
     writer: &mut W,
-    result: &QuerySolution2<RdfData>,
+    result: &QuerySolution2<S>,
     prefixmap: &PrefixMap,
 ) -> Result<()> {
     for (idx, _variable) in result.variables().enumerate() {
@@ -1392,6 +1953,12 @@ fn parse_shapemap(shapemap_path: &Path, shapemap_format: &ShapeMapFormat) -> Res
             let shapemap = ShapeMapParser::parse_buf(shapemap_path, &None, &None)?;
             Ok(shapemap)
         }
+        ShapeMapFormat::Json => {
+            let str = std::fs::read_to_string(shapemap_path)?;
+            let result_map = ResultShapeMap::from_json_str(&str)?;
+            let shapemap = result_map.to_query_shape_map()?;
+            Ok(shapemap)
+        }
     }
 }
 
@@ -1416,17 +1983,17 @@ fn parse_schema(
             schema.with_source_iri(&input.as_iri()?);
             Ok(schema)
         }
-        ShExFormat::Turtle => {
+        _ => {
+            let data_format = shex_format_to_data_format(schema_format)?;
             let rdf = parse_data(
                 &vec![input.clone()],
-                &DataFormat::Turtle,
+                &data_format,
                 reader_mode,
                 &config.rdf_config(),
             )?;
             let schema = ShExRParser::new(rdf).parse()?;
             Ok(schema)
         }
-        _ => Err(anyhow!("Not suppported parsing from {schema_format} yet")),
     }
 }
 
@@ -1466,6 +2033,18 @@ fn parse_dctap(input: &InputSpec, format: &DCTapFormat, config: &TapConfig) -> R
     }
 }
 
+fn shex_format_to_data_format(shex_format: &ShExFormat) -> Result<DataFormat> {
+    match shex_format {
+        ShExFormat::Turtle => Ok(DataFormat::Turtle),
+        ShExFormat::RDFXML => Ok(DataFormat::RDFXML),
+        ShExFormat::NTriples => Ok(DataFormat::NTriples),
+        ShExFormat::TriG => Ok(DataFormat::TriG),
+        ShExFormat::N3 => Ok(DataFormat::N3),
+        ShExFormat::NQuads => Ok(DataFormat::NQuads),
+        _ => bail!("Cannot convert ShEx format {shex_format} to RDF data format"),
+    }
+}
+
 fn shacl_format_to_data_format(shacl_format: &ShaclFormat) -> Result<DataFormat> {
     match shacl_format {
         ShaclFormat::Turtle => Ok(DataFormat::Turtle),
@@ -1495,7 +2074,7 @@ fn parse_data(
     reader_mode: &RDFReaderMode,
     config: &RdfDataConfig,
 ) -> Result<SRDFGraph> {
-    let mut graph = SRDFGraph::new();
+    let mut graph = SRDFGraph::new().with_named_graph_mode(config.named_graph_mode());
     let rdf_format = data_format2rdf_format(data_format);
     for d in data {
         let reader = d.open_read()?;
@@ -1556,6 +2135,19 @@ fn get_shex_config(config: &Option<PathBuf>) -> Result<ShExConfigMain> {
     }
 }
 
+fn get_validator_config(config: &Option<PathBuf>) -> Result<ValidatorConfig> {
+    match config {
+        Some(config_path) => match ValidatorConfig::from_path(config_path) {
+            Ok(c) => Ok(c),
+            Err(e) => Err(anyhow!(
+                "Error obtaining ShEx validation config from {}: {e}",
+                config_path.display()
+            )),
+        },
+        None => Ok(ValidatorConfig::default()),
+    }
+}
+
 fn get_shacl_config(config: &Option<PathBuf>) -> Result<ShaclConfig> {
     match config {
         Some(config_path) => match ShaclConfig::from_path(config_path) {
@@ -1626,3 +2218,206 @@ fn show_extends_table<R: Write>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use srdf::ReaderMode;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice :name "Alice" ; :knows :bob .
+:bob :name "Bob" ."#;
+
+    const SELECT: &str = "select ?x ?name where { ?x <http://example.org/name> ?name } order by ?x";
+
+    fn query(query: &str, format: ResultQueryFormat) -> Result<String> {
+        let graph = SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::Strict)?;
+        let data = RdfData::from_graph(graph)?;
+        let mut output = Vec::new();
+        query_data(&data, query, &format, &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[test]
+    fn query_type_is_obtained_from_the_query() {
+        assert_eq!(query_type(SELECT).unwrap(), QueryType::Select);
+        assert_eq!(query_type("ask { ?s ?p ?o }").unwrap(), QueryType::Ask);
+        assert_eq!(
+            query_type("construct { ?s ?p ?o } where { ?s ?p ?o }").unwrap(),
+            QueryType::Construct
+        );
+        assert_eq!(
+            query_type("describe <http://example.org/alice>").unwrap(),
+            QueryType::Describe
+        );
+        assert!(query_type("selec ?x").is_err());
+    }
+
+    #[test]
+    fn select_results_in_json() {
+        let output = query(SELECT, ResultQueryFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["head"]["vars"], serde_json::json!(["x", "name"]));
+        let bindings = json["results"]["bindings"].as_array().unwrap();
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0]["x"]["value"], "http://example.org/alice");
+        assert_eq!(bindings[0]["name"]["value"], "Alice");
+    }
+
+    #[test]
+    fn select_results_in_xml_csv_and_tsv() {
+        let xml = query(SELECT, ResultQueryFormat::Xml).unwrap();
+        assert!(xml.contains("<variable name=\"x\"/>"));
+        assert!(xml.contains("<uri>http://example.org/alice</uri>"));
+        let csv = query(SELECT, ResultQueryFormat::Csv).unwrap();
+        assert_eq!(
+            csv,
+            "x,name\r\nhttp://example.org/alice,Alice\r\nhttp://example.org/bob,Bob\r\n"
+        );
+        let tsv = query(SELECT, ResultQueryFormat::Tsv).unwrap();
+        assert_eq!(
+            tsv,
+            "?x\t?name\n<http://example.org/alice>\t\"Alice\"\n<http://example.org/bob>\t\"Bob\"\n"
+        );
+    }
+
+    #[test]
+    fn ask_results() {
+        let ask = "ask { <http://example.org/alice> <http://example.org/knows> ?x }";
+        assert_eq!(query(ask, ResultQueryFormat::Internal).unwrap(), "true\n");
+        let json: serde_json::Value =
+            serde_json::from_str(&query(ask, ResultQueryFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["boolean"], true);
+    }
+
+    #[test]
+    fn construct_and_describe_results_in_rdf_formats() {
+        let construct = "construct { ?y <http://example.org/knownBy> ?x } where { ?x <http://example.org/knows> ?y }";
+        let ntriples = query(construct, ResultQueryFormat::NTriples).unwrap();
+        assert_eq!(
+            ntriples.trim(),
+            "<http://example.org/bob> <http://example.org/knownBy> <http://example.org/alice> ."
+        );
+        let describe = "describe <http://example.org/bob>";
+        let ntriples = query(describe, ResultQueryFormat::NTriples).unwrap();
+        assert_eq!(
+            ntriples.trim(),
+            "<http://example.org/bob> <http://example.org/name> \"Bob\" ."
+        );
+        let turtle = query(describe, ResultQueryFormat::Internal).unwrap();
+        assert!(turtle.contains(":bob :name \"Bob\""));
+    }
+
+    #[test]
+    fn result_formats_must_match_the_query_type() {
+        let construct = "construct { ?s ?p ?o } where { ?s ?p ?o }";
+        assert!(query(construct, ResultQueryFormat::Json).is_err());
+        assert!(query("describe <http://example.org/bob>", ResultQueryFormat::Csv).is_err());
+        assert!(query(SELECT, ResultQueryFormat::Turtle).is_err());
+    }
+
+    /// Path in the temporary directory which is removed before the test
+    #[cfg(feature = "rocksdb")]
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rudof_cli_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[cfg(feature = "rocksdb")]
+    #[test]
+    fn data_load_and_store_option() {
+        let data = temp_path("data.ttl");
+        std::fs::write(&data, DATA).unwrap();
+        let store = temp_path("store");
+        let summary = temp_path("summary.txt");
+        run_data_load(
+            &vec![InputSpec::path(&data)],
+            &DataFormat::Turtle,
+            &RDFReaderMode::Strict,
+            &store,
+            &RdfDataConfig::default(),
+            &Some(summary.clone()),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&summary).unwrap(),
+            format!("Store {} contains 3 quads\n", store.display())
+        );
+
+        let query = temp_path("query.sparql");
+        std::fs::write(&query, SELECT).unwrap();
+        let results = temp_path("results.csv");
+        run_query(
+            &Vec::new(),
+            &DataFormat::Turtle,
+            &None,
+            &RDFReaderMode::Strict,
+            &InputSpec::path(&query),
+            &ResultQueryFormat::Csv,
+            &Some(results.clone()),
+            &QueryConfig::default(),
+            0,
+            false,
+            &Some(store.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&results).unwrap(),
+            "x,name\r\nhttp://example.org/alice,Alice\r\nhttp://example.org/bob,Bob\r\n"
+        );
+
+        let node_info = temp_path("node.txt");
+        run_node(
+            &Vec::new(),
+            &DataFormat::Turtle,
+            &None,
+            &RDFReaderMode::Strict,
+            "<http://example.org/alice>",
+            &Vec::new(),
+            &ShowNodeMode::Outgoing,
+            &false,
+            0,
+            &Some(node_info.clone()),
+            &RdfDataConfig::default(),
+            false,
+            &Some(store.clone()),
+        )
+        .unwrap();
+        let node = std::fs::read_to_string(&node_info).unwrap();
+        assert!(node.contains(":knows") && node.contains(":bob"));
+
+        for path in [data, summary, query, results, node_info] {
+            std::fs::remove_file(path).unwrap();
+        }
+        std::fs::remove_dir_all(&store).unwrap();
+    }
+
+    #[cfg(feature = "rocksdb")]
+    #[test]
+    fn data_load_stops_at_syntax_errors_in_strict_mode() {
+        let data = temp_path("invalid.ttl");
+        std::fs::write(&data, "prefix : <http://example.org/>\n:alice :knows .").unwrap();
+        let store = temp_path("invalid_store");
+        let summary = temp_path("invalid_summary.txt");
+        assert!(run_data_load(
+            &vec![InputSpec::path(&data)],
+            &DataFormat::Turtle,
+            &RDFReaderMode::Strict,
+            &store,
+            &RdfDataConfig::default(),
+            &Some(summary.clone()),
+            false,
+        )
+        .is_err());
+        assert!(!summary.exists());
+        assert!(RdfStore::open_read_only(&store)
+            .unwrap()
+            .is_empty()
+            .unwrap());
+        std::fs::remove_file(data).unwrap();
+        std::fs::remove_dir_all(&store).unwrap();
+    }
+}
@@ -0,0 +1,769 @@
+//! HTTP server mode.
+//!
+//! The RDF data is loaded once when the server starts and the schemas can be
+//! preloaded from files or registered later through the API. Registered schemas
+//! are kept compiled, so that validation requests only need to parse the shape
+//! map. Every endpoint takes a JSON body and returns a JSON response, with an
+//! `error` field and a 4xx status code when the request can't be processed or
+//! a 500 status code when the server fails to process it. Requests whose body
+//! is larger than the maximum body size are rejected with a 413 status code.
+//!
+//! Endpoints:
+//! - `GET /health`: status and version of the server
+//! - `GET /schemas`: names of the registered ShEx schemas and SHACL shapes graphs
+//! - `POST /shex/schemas`: registers a ShEx schema, `{name, schema, format}`
+//! - `POST /shacl/schemas`: registers a SHACL shapes graph, `{name, shapes, format}`
+//! - `POST /shex/validate`: validates a shape map with a registered or inline ShEx schema
+//! - `POST /shacl/validate`: validates with registered or inline SHACL shapes
+//! - `POST /convert`: converts between DCTAP, ShEx, SHACL, SPARQL and PlantUML
+//! - `POST /query`: runs a SPARQL query over the loaded data
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, Cursor, Read, Write};
+use std::result::Result::Ok;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use anyhow::*;
+use clap::ValueEnum;
+use dctap::{DCTap, TapConfig};
+use oxiri::Iri;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shacl_ast::compiled::schema::CompiledSchema as ShaclSchema;
+use shacl_ast::ShaclWriter;
+use shacl_validation::shacl_config::ShaclConfig;
+use shacl_validation::shacl_processor::{RdfDataValidation, ShaclProcessor, ShaclValidationMode};
+use shacl_validation::store::ShaclDataManager;
+use shacl_validation::validation_report::report::ConformanceMode;
+use shapemap::{query_shape_map::QueryShapeMap, ResultShapeMap, ValidationStatus};
+use shapes_converter::{
+    ConverterConfig, ShEx2Shacl, ShEx2Sparql, ShEx2Uml, Shacl2ShEx, Tap2ShEx, UmlGenerationMode,
+};
+use shex_ast::ast::Schema as SchemaJson;
+use shex_ast::compiled::compiled_schema::CompiledSchema;
+use shex_ast::shexr::shexr_parser::ShExRParser;
+use shex_compact::{ShExFormatter, ShExParser, ShapeMapParser};
+use shex_validation::{Validator, ValidatorConfig};
+use sparesults::{QueryResultsFormat, QueryResultsSerializer};
+use sparql_service::RdfData;
+use srdf::{QuerySRDF2, RDFFormat, RdfDataConfig, SRDFGraph};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::debug;
+
+use crate::{
+    get_data, map_shacl_format, parse_iri_ref, parse_schema, query_type, serialize_solutions,
+    serialize_triples, shex_format_to_data_format, DataFormat, InputSpec, QueryType, RDFReaderMode,
+    ShExFormat, ShaclFormat, ShapeMapFormat,
+};
+
+/// A schema given in the command line as `name=file`
+#[derive(Debug, Clone)]
+pub struct NamedInput {
+    pub name: String,
+    pub input: InputSpec,
+}
+
+impl FromStr for NamedInput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((name, input)) if !name.is_empty() => Ok(NamedInput {
+                name: name.to_string(),
+                input: InputSpec::from_str(input).map_err(|e| e.to_string())?,
+            }),
+            _ => Err(format!("Expected name=file, found {s}")),
+        }
+    }
+}
+
+/// State shared by the threads that serve the requests
+pub struct ServeState {
+    data: RdfData,
+    data_config: RdfDataConfig,
+    reader_mode: RDFReaderMode,
+    validator_config: ValidatorConfig,
+    shex_schemas: RwLock<HashMap<String, Arc<CompiledSchema>>>,
+    shacl_schemas: RwLock<HashMap<String, ShaclSchema<RdfData>>>,
+}
+
+impl ServeState {
+    pub fn new(
+        data: RdfData,
+        validator_config: ValidatorConfig,
+        shacl_config: &ShaclConfig,
+        reader_mode: RDFReaderMode,
+    ) -> ServeState {
+        let data_config = data_config(&validator_config, shacl_config);
+        ServeState {
+            data,
+            data_config,
+            reader_mode,
+            validator_config,
+            shex_schemas: RwLock::new(HashMap::new()),
+            shacl_schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn add_shex_schema(&self, name: &str, schema_json: &SchemaJson) -> Result<()> {
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(schema_json)?;
+        self.shex_schemas
+            .write()
+            .map_err(|_| internal("ShEx schemas lock poisoned"))?
+            .insert(name.to_string(), Arc::new(schema));
+        Ok(())
+    }
+
+    fn add_shacl_schema(&self, name: &str, schema: ShaclSchema<RdfData>) -> Result<()> {
+        self.shacl_schemas
+            .write()
+            .map_err(|_| internal("SHACL schemas lock poisoned"))?
+            .insert(name.to_string(), schema);
+        Ok(())
+    }
+}
+
+/// Error caused by the server instead of by the request, which is answered
+/// with a 500 status code. It is attached as context with [`internal`]
+#[derive(Debug)]
+struct InternalError;
+
+impl Display for InternalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Internal server error")
+    }
+}
+
+fn internal<E: Display>(e: E) -> Error {
+    anyhow!("{e:#}").context(InternalError)
+}
+
+/// The data configuration is taken from the ShEx validation config or, if it doesn't have one, from the SHACL config
+fn data_config(validator_config: &ValidatorConfig, shacl_config: &ShaclConfig) -> RdfDataConfig {
+    validator_config
+        .data_config
+        .clone()
+        .or_else(|| shacl_config.data.clone())
+        .unwrap_or_default()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_serve(
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    endpoint: &Option<String>,
+    reader_mode: &RDFReaderMode,
+    shex: &Vec<NamedInput>,
+    shex_format: &ShExFormat,
+    shacl: &Vec<NamedInput>,
+    shacl_format: &ShaclFormat,
+    host: &str,
+    port: u16,
+    threads: usize,
+    max_body_size: u64,
+    validator_config: ValidatorConfig,
+    shacl_config: &ShaclConfig,
+    debug: u8,
+) -> Result<()> {
+    let config = data_config(&validator_config, shacl_config);
+    let rdf_data = if data.is_empty() && endpoint.is_none() && config.endpoints().is_empty() {
+        RdfData::new()
+    } else {
+        get_data(data, data_format, endpoint, reader_mode, debug, &config)?
+    };
+    let state = ServeState::new(rdf_data, validator_config, shacl_config, *reader_mode);
+    for NamedInput { name, input } in shex {
+        let schema = parse_schema(
+            input,
+            shex_format,
+            reader_mode,
+            &state.validator_config.shex_config(),
+        )?;
+        state
+            .add_shex_schema(name, &schema)
+            .with_context(|| format!("Error compiling ShEx schema {name}"))?;
+    }
+    for NamedInput { name, input } in shacl {
+        let schema =
+            ShaclDataManager::load(input.open_read()?, map_shacl_format(shacl_format)?, None)
+                .with_context(|| format!("Error compiling SHACL shapes {name}"))?;
+        state.add_shacl_schema(name, schema)?;
+    }
+    let server = Server::http((host, port)).map_err(|e| anyhow!("Error starting server: {e}"))?;
+    writeln!(io::stderr(), "rudof serving at http://{host}:{port}")?;
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| serve_requests(&server, &state, max_body_size));
+        }
+    });
+    Ok(())
+}
+
+fn serve_requests(server: &Server, state: &ServeState, max_body_size: u64) {
+    for mut request in server.incoming_requests() {
+        let (status, value) = match read_body(&mut request, max_body_size) {
+            Ok(body) => handle(state, request.method(), request.url(), &body),
+            Err(response) => response,
+        };
+        debug!("{} {} -> {status}", request.method(), request.url());
+        if let Err(e) = respond(request, status, &value) {
+            debug!("Error sending response: {e}");
+        }
+    }
+}
+
+/// Reads the body of a request, which is rejected if it is larger than
+/// `max_body_size`. The size is checked while reading, so that requests
+/// without a `Content-Length` header can't send a larger body either
+fn read_body(request: &mut Request, max_body_size: u64) -> Result<String, (u16, Value)> {
+    let too_large = || {
+        (
+            413,
+            json!({ "error": format!("Request body larger than {max_body_size} bytes") }),
+        )
+    };
+    if request
+        .body_length()
+        .is_some_and(|length| length as u64 > max_body_size)
+    {
+        return Err(too_large());
+    }
+    let mut body = String::new();
+    match request
+        .as_reader()
+        .take(max_body_size + 1)
+        .read_to_string(&mut body)
+    {
+        Ok(length) if length as u64 > max_body_size => Err(too_large()),
+        Ok(_) => Ok(body),
+        Err(e) => Err((
+            400,
+            json!({ "error": format!("Error reading request body: {e}") }),
+        )),
+    }
+}
+
+fn respond(request: Request, status: u16, value: &Value) -> std::io::Result<()> {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("Valid content type header");
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    request.respond(response)
+}
+
+/// Dispatches a request to its endpoint and returns the status code and the JSON response
+pub fn handle(state: &ServeState, method: &Method, url: &str, body: &str) -> (u16, Value) {
+    let path = url.split('?').next().unwrap_or(url);
+    let result = match (method, path) {
+        (Method::Get, "/health") => Ok(json!({
+            "status": "ok",
+            "version": env!("CARGO_PKG_VERSION")
+        })),
+        (Method::Get, "/schemas") => list_schemas(state),
+        (Method::Post, "/shex/schemas") => parse_body(body).and_then(|r| add_shex(state, r)),
+        (Method::Post, "/shacl/schemas") => parse_body(body).and_then(|r| add_shacl(state, r)),
+        (Method::Post, "/shex/validate") => parse_body(body).and_then(|r| validate_shex(state, r)),
+        (Method::Post, "/shacl/validate") => {
+            parse_body(body).and_then(|r| validate_shacl(state, r))
+        }
+        (Method::Post, "/convert") => parse_body(body).and_then(convert),
+        (Method::Post, "/query") => parse_body(body).and_then(|r| query(state, r)),
+        (
+            _,
+            "/health" | "/schemas" | "/shex/schemas" | "/shacl/schemas" | "/shex/validate"
+            | "/shacl/validate" | "/convert" | "/query",
+        ) => {
+            return (
+                405,
+                json!({ "error": format!("Method {method} not allowed for {path}") }),
+            )
+        }
+        _ => return (404, json!({ "error": format!("Unknown endpoint {path}") })),
+    };
+    match result {
+        Ok(value) => (200, value),
+        Err(e) if e.downcast_ref::<InternalError>().is_some() => {
+            (500, json!({ "error": format!("{e:#}") }))
+        }
+        Err(e) => (400, json!({ "error": format!("{e:#}") })),
+    }
+}
+
+fn parse_body<'a, A: Deserialize<'a>>(body: &'a str) -> Result<A> {
+    serde_json::from_str(body).context("Error parsing request body")
+}
+
+/// Parses the name of a format using the same names as the command line options
+fn parse_format<A: ValueEnum>(format: &Option<String>, default: A) -> Result<A> {
+    match format {
+        None => Ok(default),
+        Some(str) => A::from_str(str, true).map_err(|e| anyhow!("Unknown format {str}: {e}")),
+    }
+}
+
+fn list_schemas(state: &ServeState) -> Result<Value> {
+    let mut shex: Vec<String> = read_lock(&state.shex_schemas)?.keys().cloned().collect();
+    let mut shacl: Vec<String> = read_lock(&state.shacl_schemas)?.keys().cloned().collect();
+    shex.sort();
+    shacl.sort();
+    Ok(json!({ "shex": shex, "shacl": shacl }))
+}
+
+fn read_lock<A>(lock: &RwLock<A>) -> Result<std::sync::RwLockReadGuard<'_, A>> {
+    lock.read().map_err(|_| internal("Schemas lock poisoned"))
+}
+
+#[derive(Deserialize)]
+struct AddShExRequest {
+    name: String,
+    schema: String,
+    format: Option<String>,
+}
+
+fn add_shex(state: &ServeState, request: AddShExRequest) -> Result<Value> {
+    let schema_format = parse_format(&request.format, ShExFormat::ShExC)?;
+    let schema = parse_shex_str(&request.schema, &schema_format, state)?;
+    state.add_shex_schema(&request.name, &schema)?;
+    Ok(json!({ "name": request.name }))
+}
+
+#[derive(Deserialize)]
+struct AddShaclRequest {
+    name: String,
+    shapes: String,
+    format: Option<String>,
+}
+
+fn add_shacl(state: &ServeState, request: AddShaclRequest) -> Result<Value> {
+    let shapes_format = parse_format(&request.format, ShaclFormat::Turtle)?;
+    let schema = parse_shacl_str(&request.shapes, &shapes_format)?;
+    state.add_shacl_schema(&request.name, schema)?;
+    Ok(json!({ "name": request.name }))
+}
+
+#[derive(Deserialize)]
+struct ShExValidateRequest {
+    schema: Option<String>,
+    schema_name: Option<String>,
+    schema_format: Option<String>,
+    data: Option<String>,
+    data_format: Option<String>,
+    shapemap: String,
+    shapemap_format: Option<String>,
+}
+
+fn validate_shex(state: &ServeState, request: ShExValidateRequest) -> Result<Value> {
+    let schema = match (&request.schema, &request.schema_name) {
+        (Some(schema), None) => {
+            let schema_format = parse_format(&request.schema_format, ShExFormat::ShExC)?;
+            let schema_json = parse_shex_str(schema, &schema_format, state)?;
+            let mut schema = CompiledSchema::new();
+            schema.from_schema_json(&schema_json)?;
+            Arc::new(schema)
+        }
+        (None, Some(name)) => match read_lock(&state.shex_schemas)?.get(name) {
+            Some(schema) => Arc::clone(schema),
+            None => bail!("Unknown ShEx schema {name}"),
+        },
+        _ => bail!("One of `schema` or `schema_name` must be provided"),
+    };
+    let request_data = parse_request_data(state, &request.data, &request.data_format)?;
+    let data = request_data.as_ref().unwrap_or(&state.data);
+    let nodes_prefixmap = data.prefixmap();
+    let shapes_prefixmap = schema.prefixmap();
+    let shapemap = match parse_format(&request.shapemap_format, ShapeMapFormat::Compact)? {
+        ShapeMapFormat::Compact => ShapeMapParser::parse(
+            &request.shapemap,
+            &Some(nodes_prefixmap.clone()),
+            &Some(shapes_prefixmap.clone()),
+        )?,
+        ShapeMapFormat::Json => {
+            ResultShapeMap::from_json_str(&request.shapemap)?.to_query_shape_map()?
+        }
+        ShapeMapFormat::Internal => bail!("Cannot read internal ShapeMap format yet"),
+    };
+    let result = validate_shapemap(state, schema, &shapemap, data)?;
+    let conformant = result
+        .iter()
+        .all(|association| association.status == ValidationStatus::Conformant);
+    Ok(json!({ "conformant": conformant, "result": result }))
+}
+
+fn validate_shapemap(
+    state: &ServeState,
+    schema: Arc<CompiledSchema>,
+    shapemap: &QueryShapeMap,
+    data: &RdfData,
+) -> Result<ResultShapeMap> {
+    let shapes_prefixmap = schema.prefixmap();
+    let mut validator = Validator::new(schema, &state.validator_config);
+    validator.validate_shapemap_parallel(shapemap, data)?;
+    let result = validator
+        .result_map(Some(data.prefixmap().without_rich_qualifying()))?
+        .with_schema_prefixmap(shapes_prefixmap.without_rich_qualifying());
+    Ok(result.result_shape_map())
+}
+
+#[derive(Deserialize)]
+struct ShaclValidateRequest {
+    shapes: Option<String>,
+    shapes_name: Option<String>,
+    shapes_format: Option<String>,
+    data: Option<String>,
+    data_format: Option<String>,
+    mode: Option<String>,
+    conformance: Option<String>,
+}
+
+fn validate_shacl(state: &ServeState, request: ShaclValidateRequest) -> Result<Value> {
+    let mode = parse_format(&request.mode, ShaclValidationMode::Native)?;
+    let conformance = parse_format(&request.conformance, ConformanceMode::Strict)?;
+    let request_data = parse_request_data(state, &request.data, &request.data_format)?;
+    let data = request_data.as_ref().unwrap_or(&state.data);
+    let validator = RdfDataValidation::new(data, mode);
+    let report = match (&request.shapes, &request.shapes_name) {
+        (Some(shapes), None) => {
+            let shapes_format = parse_format(&request.shapes_format, ShaclFormat::Turtle)?;
+            let schema = parse_shacl_str(shapes, &shapes_format)?;
+            validator.validate(&schema)?
+        }
+        (None, Some(name)) => match read_lock(&state.shacl_schemas)?.get(name) {
+            Some(schema) => validator.validate(schema)?,
+            None => bail!("Unknown SHACL shapes {name}"),
+        },
+        _ => bail!("One of `shapes` or `shapes_name` must be provided"),
+    };
+    Ok(report
+        .with_nodes_prefixmap(data.prefixmap())
+        .with_conformance_mode(conformance)
+        .without_colors()
+        .to_json_ld())
+}
+
+#[derive(Deserialize)]
+struct ConvertRequest {
+    input: String,
+    from: String,
+    to: String,
+    format: Option<String>,
+    result_format: Option<String>,
+    shape: Option<String>,
+}
+
+fn convert(request: ConvertRequest) -> Result<Value> {
+    let config = ConverterConfig::default();
+    let from = request.from.to_lowercase();
+    let to = request.to.to_lowercase();
+    let result = match (from.as_str(), to.as_str()) {
+        ("dctap", "shex") => {
+            let dctap = DCTap::from_reader(request.input.as_bytes(), &TapConfig::default())?;
+            let schema = Tap2ShEx::new(&config.tap2shex_config()).convert(&dctap)?;
+            show_shex(&schema, &request.result_format)?
+        }
+        ("shacl", "shex") => {
+            let format = parse_format(&request.format, ShaclFormat::Turtle)?;
+            let rdf = SRDFGraph::from_str(
+                &request.input,
+                &map_shacl_format(&format)?,
+                None,
+                &srdf::ReaderMode::Strict,
+            )?;
+            let shacl = shacl_ast::ShaclParser::new(rdf).parse()?;
+            let mut converter = Shacl2ShEx::new(&config.shacl2shex_config());
+            converter.convert(&shacl)?;
+            show_shex(converter.current_shex(), &request.result_format)?
+        }
+        ("shex", "shex") => show_shex(&shex_input(&request)?, &request.result_format)?,
+        ("shex", "shacl") => {
+            let schema = shex_input(&request)?;
+            let result_format = parse_format(&request.result_format, ShaclFormat::Turtle)?;
+            let mut converter = ShEx2Shacl::new(&config.shex2shacl_config());
+            converter.convert(&schema)?;
+            let mut shacl_writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
+            shacl_writer.write(converter.current_shacl())?;
+            let mut output = Vec::new();
+            shacl_writer.serialize(map_shacl_format(&result_format)?, &mut output)?;
+            String::from_utf8(output)?
+        }
+        ("shex", "sparql") => {
+            let schema = shex_input(&request)?;
+            let shape = match &request.shape {
+                None => None,
+                Some(shape) => Some(parse_iri_ref(shape)?),
+            };
+            ShEx2Sparql::new(&config.shex2sparql_config())
+                .convert(&schema, shape)?
+                .to_string()
+        }
+        ("shex", "uml") => {
+            let schema = shex_input(&request)?;
+            let mut converter = ShEx2Uml::new(&config.shex2uml_config());
+            converter.convert(&schema)?;
+            let mode = match &request.shape {
+                None => UmlGenerationMode::all(),
+                Some(shape) => UmlGenerationMode::neighs(shape),
+            };
+            let mut output = Vec::new();
+            converter.as_plantuml(&mut output, &mode)?;
+            String::from_utf8(output)?
+        }
+        _ => bail!("Conversion from {from} to {to} is not supported"),
+    };
+    Ok(json!({ "result": result }))
+}
+
+fn shex_input(request: &ConvertRequest) -> Result<SchemaJson> {
+    match parse_format(&request.format, ShExFormat::ShExC)? {
+        ShExFormat::ShExC => Ok(ShExParser::parse(&request.input, None)?),
+        ShExFormat::ShExJ => Ok(serde_json::from_str(&request.input)?),
+        other => bail!("Conversions from ShEx in {other} format are not supported"),
+    }
+}
+
+fn show_shex(schema: &SchemaJson, result_format: &Option<String>) -> Result<String> {
+    match parse_format(result_format, ShExFormat::ShExC)? {
+        ShExFormat::ShExC => Ok(ShExFormatter::default()
+            .without_colors()
+            .format_schema(schema)),
+        ShExFormat::ShExJ => Ok(serde_json::to_string_pretty(schema)?),
+        other => bail!("ShEx result format {other} is not supported"),
+    }
+}
+
+#[derive(Deserialize)]
+struct QueryRequest {
+    query: String,
+    data: Option<String>,
+    data_format: Option<String>,
+}
+
+fn query(state: &ServeState, request: QueryRequest) -> Result<Value> {
+    let request_data = parse_request_data(state, &request.data, &request.data_format)?;
+    let data = request_data.as_ref().unwrap_or(&state.data);
+    let mut output = Vec::new();
+    match query_type(&request.query)? {
+        QueryType::Select => {
+            let results = data.query_select(&request.query)?;
+            serialize_solutions(&mut output, &results, QueryResultsFormat::Json)
+                .map_err(internal)?;
+            serde_json::from_slice(&output).map_err(internal)
+        }
+        QueryType::Ask => {
+            let result = data.query_ask(&request.query)?;
+            QueryResultsSerializer::from_format(QueryResultsFormat::Json)
+                .serialize_boolean_to_writer(&mut output, result)
+                .map_err(internal)?;
+            serde_json::from_slice(&output).map_err(internal)
+        }
+        QueryType::Construct => {
+            let triples = data.query_construct(&request.query)?;
+            serialize_triples(&mut output, triples, data.prefixmap(), RDFFormat::Turtle)
+                .map_err(internal)?;
+            Ok(json!({ "result": String::from_utf8(output).map_err(internal)? }))
+        }
+        QueryType::Describe => {
+            let triples = data.query_describe(&request.query)?;
+            serialize_triples(&mut output, triples, data.prefixmap(), RDFFormat::Turtle)
+                .map_err(internal)?;
+            Ok(json!({ "result": String::from_utf8(output).map_err(internal)? }))
+        }
+    }
+}
+
+/// Parses the data sent in a request, if any, with the data configuration of the server
+fn parse_request_data(
+    state: &ServeState,
+    data: &Option<String>,
+    data_format: &Option<String>,
+) -> Result<Option<RdfData>> {
+    match data {
+        None => Ok(None),
+        Some(data) => {
+            let data_format = parse_format(data_format, DataFormat::Turtle)?;
+            let graph = parse_rdf_str(data, &data_format, &state.reader_mode, &state.data_config)?;
+            Ok(Some(RdfData::from_graph(graph)?))
+        }
+    }
+}
+
+fn parse_rdf_str(
+    data: &str,
+    data_format: &DataFormat,
+    reader_mode: &RDFReaderMode,
+    config: &RdfDataConfig,
+) -> Result<SRDFGraph> {
+    let mut graph = SRDFGraph::new().with_named_graph_mode(config.named_graph_mode());
+    let base = config
+        .base
+        .as_ref()
+        .map(|iri_s| Iri::parse_unchecked(iri_s.as_str().to_string()));
+    graph.merge_from_reader(
+        Cursor::new(data),
+        &(*data_format).into(),
+        base,
+        &(*reader_mode).into(),
+    )?;
+    Ok(graph)
+}
+
+fn parse_shex_str(
+    schema: &str,
+    schema_format: &ShExFormat,
+    state: &ServeState,
+) -> Result<SchemaJson> {
+    match schema_format {
+        ShExFormat::Internal | ShExFormat::Simple => {
+            bail!("Cannot read {schema_format} ShEx format")
+        }
+        ShExFormat::ShExC => Ok(ShExParser::parse(schema, None)?),
+        ShExFormat::ShExJ => Ok(serde_json::from_str(schema)?),
+        _ => {
+            let data_format = shex_format_to_data_format(schema_format)?;
+            let rdf = parse_rdf_str(
+                schema,
+                &data_format,
+                &state.reader_mode,
+                &state.validator_config.shex_config().rdf_config(),
+            )?;
+            Ok(ShExRParser::new(rdf).parse()?)
+        }
+    }
+}
+
+fn parse_shacl_str(shapes: &str, shapes_format: &ShaclFormat) -> Result<ShaclSchema<RdfData>> {
+    Ok(ShaclDataManager::load(
+        Cursor::new(shapes),
+        map_shacl_format(shapes_format)?,
+        None,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person { :name xsd:string }"#;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+
+:alice a :Person ; :name "Alice" .
+:bob a :Person ; :name 23 ."#;
+
+    fn state() -> ServeState {
+        let graph =
+            SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &srdf::ReaderMode::Strict).unwrap();
+        ServeState::new(
+            RdfData::from_graph(graph).unwrap(),
+            ValidatorConfig::default(),
+            &ShaclConfig::default(),
+            RDFReaderMode::Strict,
+        )
+    }
+
+    fn post(state: &ServeState, path: &str, body: Value) -> (u16, Value) {
+        handle(state, &Method::Post, path, &body.to_string())
+    }
+
+    #[test]
+    fn validates_with_registered_shex_schema() {
+        let state = state();
+        let (status, _) = post(
+            &state,
+            "/shex/schemas",
+            json!({ "name": "person", "schema": SCHEMA }),
+        );
+        assert_eq!(status, 200);
+        let (status, response) = post(
+            &state,
+            "/shex/validate",
+            json!({ "schema_name": "person", "shapemap": ":alice@:Person" }),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(response["conformant"], true);
+        let (_, response) = post(
+            &state,
+            "/shex/validate",
+            json!({ "schema_name": "person", "shapemap": ":bob@:Person" }),
+        );
+        assert_eq!(response["conformant"], false);
+    }
+
+    #[test]
+    fn validates_shacl_and_queries_loaded_data() {
+        let state = state();
+        let shapes = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:PersonShape a sh:NodeShape ;
+  sh:targetClass :Person ;
+  sh:property [ sh:path :name ; sh:datatype xsd:string ] ."#;
+        let (status, report) = post(&state, "/shacl/validate", json!({ "shapes": shapes }));
+        assert_eq!(status, 200);
+        assert_eq!(report["sh:conforms"], false);
+        let (status, response) = post(
+            &state,
+            "/query",
+            json!({ "query": "ASK { <http://example.org/alice> a <http://example.org/Person> }" }),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(response["boolean"], true);
+    }
+
+    #[test]
+    fn reports_errors_as_json() {
+        let state = state();
+        let (status, response) = post(
+            &state,
+            "/shex/validate",
+            json!({ "schema_name": "unknown", "shapemap": ":alice@:Person" }),
+        );
+        assert_eq!(status, 400);
+        assert!(response["error"].as_str().unwrap().contains("unknown"));
+        let (status, _) = handle(&state, &Method::Get, "/nothing", "");
+        assert_eq!(status, 404);
+        let (status, _) = handle(&state, &Method::Get, "/query", "");
+        assert_eq!(status, 405);
+    }
+
+    #[test]
+    fn reports_internal_errors_with_500() {
+        let state = state();
+        let poisoned = std::panic::catch_unwind(|| {
+            let _guard = state.shex_schemas.write().unwrap();
+            panic!("poisoning the schemas lock");
+        });
+        assert!(poisoned.is_err());
+        let (status, response) = handle(&state, &Method::Get, "/schemas", "");
+        assert_eq!(status, 500);
+        assert!(response["error"]
+            .as_str()
+            .unwrap()
+            .contains("lock poisoned"));
+    }
+
+    #[test]
+    fn rejects_bodies_larger_than_the_limit() {
+        let mut request: Request = tiny_http::TestRequest::new()
+            .with_method(Method::Post)
+            .with_body("{\"query\": \"ASK {}\"}")
+            .into();
+        let (status, _) = read_body(&mut request, 10).unwrap_err();
+        assert_eq!(status, 413);
+        let mut request: Request = tiny_http::TestRequest::new()
+            .with_method(Method::Post)
+            .with_body("{\"query\": \"ASK {}\"}")
+            .into();
+        assert_eq!(
+            read_body(&mut request, 100).unwrap(),
+            "{\"query\": \"ASK {}\"}"
+        );
+    }
+}
@@ -0,0 +1,567 @@
+//! Watch mode of the validation commands.
+//!
+//! The schema, data and shape map files are polled for changes. When some of them
+//! change, only those files are parsed again and only the node/shape pairs that
+//! can be affected by the changes are validated again. The differences with
+//! respect to the previous results are shown after each change.
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use std::result::Result::Ok;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::*;
+use prefixmap::Deref;
+use shacl_ast::compiled::schema::CompiledSchema as ShaclSchema;
+use shacl_validation::shacl_processor::{GraphValidation, ShaclProcessor, ShaclValidationMode};
+use shacl_validation::store::ShaclDataManager;
+use shacl_validation::validation_report::report::{ConformanceMode, ValidationReport};
+use shapemap::query_shape_map::QueryShapeMap;
+use shapemap::ResultShapeMap;
+use shex_ast::ast::Schema as SchemaJson;
+use shex_ast::compiled::compiled_schema::CompiledSchema;
+use shex_ast::{Node, ShapeExprLabel};
+use shex_validation::{Validator, ValidatorConfig};
+use sparql_service::RdfData;
+use srdf::srdf_graph::SRDFGraph;
+use srdf::{Object, RdfDataConfig, SRDFBasic};
+
+use crate::{
+    get_data, get_shapemap, get_writer, map_shacl_format, parse_schema, ColorSupport, DataFormat,
+    InputSpec, RDFReaderMode, ShExFormat, ShaclFormat, ShapeMapFormat,
+};
+
+/// Time between two consecutive checks of the files
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time to wait after a change is detected, so that editors that save a file
+/// in several steps have finished before it is read
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Polls the modification times of a set of files
+struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+    fn new(paths: Vec<PathBuf>) -> Result<FileWatcher> {
+        if paths.is_empty() {
+            bail!("Watch mode requires some schema, data or shape map file to watch")
+        }
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+        Ok(FileWatcher { files })
+    }
+
+    /// Blocks until some of the files change and returns the files that changed
+    fn wait_changes(&mut self) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if self
+                .files
+                .iter()
+                .any(|(path, last)| modified(path) != *last)
+            {
+                thread::sleep(DEBOUNCE);
+                let mut changed = Vec::new();
+                for (path, last) in self.files.iter_mut() {
+                    let current = modified(path);
+                    if current != *last {
+                        *last = current;
+                        changed.push(path.clone());
+                    }
+                }
+                return changed;
+            }
+        }
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn watched_path(input: &InputSpec) -> Option<PathBuf> {
+    match input {
+        InputSpec::Path(path) => Some(path.clone()),
+        InputSpec::Stdin | InputSpec::Url(_) => None,
+    }
+}
+
+/// State kept by the ShEx watch mode between two validations
+struct ShExState {
+    schema: SchemaJson,
+    data: RdfData,
+    shapemap: QueryShapeMap,
+    pairs: HashSet<(Object, ShapeExprLabel)>,
+    result: ResultShapeMap,
+}
+
+/// Outcome of the validation after some files change, with the files that
+/// have been parsed again
+struct ShExRound {
+    schema: Option<SchemaJson>,
+    data: Option<RdfData>,
+    shapemap: Option<QueryShapeMap>,
+    pairs: HashSet<(Object, ShapeExprLabel)>,
+    result: ResultShapeMap,
+    revalidated: usize,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_watch_shex(
+    schema: &InputSpec,
+    schema_format: &ShExFormat,
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    endpoint: &Option<String>,
+    reader_mode: &RDFReaderMode,
+    maybe_node: &Option<String>,
+    maybe_shape: &Option<String>,
+    shapemap_path: &Option<PathBuf>,
+    shapemap_format: &ShapeMapFormat,
+    debug: u8,
+    output: &Option<PathBuf>,
+    config: &ValidatorConfig,
+    force_overwrite: bool,
+) -> Result<()> {
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    let rdf_data_config = match &config.data_config {
+        None => RdfDataConfig::default(),
+        Some(cfg) => cfg.clone(),
+    };
+    let schema_path = watched_path(schema);
+    let data_paths: Vec<PathBuf> = data.iter().filter_map(watched_path).collect();
+    let mut paths: Vec<PathBuf> = schema_path
+        .iter()
+        .chain(data_paths.iter())
+        .cloned()
+        .collect();
+    paths.extend(shapemap_path.iter().cloned());
+    let mut watcher = FileWatcher::new(paths)?;
+
+    let schema_json = parse_schema(schema, schema_format, reader_mode, &config.shex_config())?;
+    let rdf_data = get_data(
+        data,
+        data_format,
+        endpoint,
+        reader_mode,
+        debug,
+        &rdf_data_config,
+    )?;
+    let shapemap = get_shapemap(shapemap_path, shapemap_format, maybe_node, maybe_shape)?;
+    let pairs = shapemap_pairs(&shapemap, &rdf_data, &schema_json)?;
+    let mut compiled = CompiledSchema::new();
+    compiled.from_schema_json(&schema_json)?;
+    let result = validate_pairs(compiled, &pairs, &rdf_data, config)?;
+    writeln!(writer, "Result:\n{result}")?;
+    writer.flush()?;
+    let mut state = ShExState {
+        schema: schema_json,
+        data: rdf_data,
+        shapemap,
+        pairs,
+        result,
+    };
+
+    loop {
+        let changed = watcher.wait_changes();
+        let schema_changed = schema_path.iter().any(|path| changed.contains(path));
+        let data_changed = data_paths.iter().any(|path| changed.contains(path));
+        let shapemap_changed = shapemap_path.iter().any(|path| changed.contains(path));
+        let round = || -> Result<ShExRound> {
+            let new_schema = match schema_changed {
+                true => Some(parse_schema(
+                    schema,
+                    schema_format,
+                    reader_mode,
+                    &config.shex_config(),
+                )?),
+                false => None,
+            };
+            let new_data = match data_changed {
+                true => Some(get_data(
+                    data,
+                    data_format,
+                    endpoint,
+                    reader_mode,
+                    debug,
+                    &rdf_data_config,
+                )?),
+                false => None,
+            };
+            let new_shapemap = match shapemap_changed {
+                true => Some(get_shapemap(
+                    shapemap_path,
+                    shapemap_format,
+                    maybe_node,
+                    maybe_shape,
+                )?),
+                false => None,
+            };
+            revalidate(&state, new_schema, new_data, new_shapemap, config)
+        };
+        match round() {
+            Ok(round) => {
+                writeln!(
+                    writer,
+                    "Revalidated {} of {} node/shape pairs",
+                    round.revalidated,
+                    round.pairs.len()
+                )?;
+                let diff = round.result.diff(&state.result);
+                if diff.is_empty() {
+                    writeln!(writer, "No changes")?;
+                } else {
+                    writeln!(writer, "{diff}")?;
+                }
+                if let Some(schema) = round.schema {
+                    state.schema = schema
+                }
+                if let Some(data) = round.data {
+                    state.data = data
+                }
+                if let Some(shapemap) = round.shapemap {
+                    state.shapemap = shapemap
+                }
+                state.pairs = round.pairs;
+                state.result = round.result;
+            }
+            Err(err) => writeln!(writer, "Error: {err}")?,
+        }
+        writer.flush()?;
+    }
+}
+
+/// Validates again the node/shape pairs that can be affected by the files that
+/// have been parsed again, keeping the previous results of the other pairs
+fn revalidate(
+    state: &ShExState,
+    new_schema: Option<SchemaJson>,
+    new_data: Option<RdfData>,
+    new_shapemap: Option<QueryShapeMap>,
+    config: &ValidatorConfig,
+) -> Result<ShExRound> {
+    let schema_json = new_schema.as_ref().unwrap_or(&state.schema);
+    let rdf_data = new_data.as_ref().unwrap_or(&state.data);
+    let shapemap = new_shapemap.as_ref().unwrap_or(&state.shapemap);
+    let changed_shapes = match &new_schema {
+        Some(new_schema) => new_schema.changed_shapes(&state.schema),
+        None => HashSet::new(),
+    };
+    let affected = match (
+        state.data.graph(),
+        new_data.as_ref().and_then(|d| d.graph()),
+    ) {
+        (Some(before), Some(after)) => {
+            affected_nodes(before, after, schema_json.has_inverse_triple_constraints())
+        }
+        _ => HashSet::new(),
+    };
+    let pairs = shapemap_pairs(shapemap, rdf_data, schema_json)?;
+    let pending: HashSet<(Object, ShapeExprLabel)> = pairs
+        .iter()
+        .filter(|(node, shape)| {
+            !state.pairs.contains(&(node.clone(), shape.clone()))
+                || changed_shapes.contains(shape)
+                || affected.contains(node)
+        })
+        .cloned()
+        .collect();
+    let mut compiled = CompiledSchema::new();
+    compiled.from_schema_json(schema_json)?;
+    let mut result = validate_pairs(compiled, &pending, rdf_data, config)?;
+    let revalidated: HashSet<(Object, ShapeExprLabel)> = result
+        .iter()
+        .map(|a| (a.node.clone(), a.shape.clone()))
+        .collect();
+    // The previous results that can not be affected by the changes are kept
+    for association in state.result.iter() {
+        let pair = (association.node.clone(), association.shape.clone());
+        let removed = state.pairs.contains(&pair) && !pairs.contains(&pair);
+        if !revalidated.contains(&pair)
+            && !removed
+            && !changed_shapes.contains(&association.shape)
+            && !affected.contains(&association.node)
+        {
+            result.add_association(association.clone())
+        }
+    }
+    Ok(ShExRound {
+        schema: new_schema,
+        data: new_data,
+        shapemap: new_shapemap,
+        pairs,
+        result,
+        revalidated: pending.len(),
+    })
+}
+
+/// Node/shape pairs selected by a shape map. The shape labels are resolved with
+/// the prefixes of the schema, so they can be compared with its changed shapes
+fn shapemap_pairs(
+    shapemap: &QueryShapeMap,
+    data: &RdfData,
+    schema: &SchemaJson,
+) -> Result<HashSet<(Object, ShapeExprLabel)>> {
+    let pairs = shapemap.fixed_shape_map(data)?;
+    let (base, prefixmap) = (schema.base(), schema.prefixmap());
+    pairs
+        .into_iter()
+        .map(|(term, label)| {
+            let label = label.deref(&base, &prefixmap)?;
+            Ok((RdfData::term_as_object(&term), label))
+        })
+        .collect()
+}
+
+/// Nodes whose neighbourhood changed between both versions of the data, and the
+/// nodes that reach them, whose shapes may depend on them
+fn affected_nodes(before: &SRDFGraph, after: &SRDFGraph, inverse: bool) -> HashSet<Object> {
+    let changed = after.changed_nodes(before);
+    before
+        .dependent_nodes(&changed, inverse)
+        .union(&after.dependent_nodes(&changed, inverse))
+        .map(SRDFGraph::term_as_object)
+        .collect()
+}
+
+fn validate_pairs(
+    schema: CompiledSchema,
+    pairs: &HashSet<(Object, ShapeExprLabel)>,
+    data: &RdfData,
+    config: &ValidatorConfig,
+) -> Result<ResultShapeMap> {
+    let mut labels = Vec::new();
+    for (node, shape) in pairs {
+        let idx = schema.find_ref(shape)?;
+        let Some((label, _)) = schema.find_shape_idx(&idx) else {
+            bail!("Shape {shape} not found in schema")
+        };
+        labels.push((Node::from(node.clone()), label.clone()));
+    }
+    let mut validator = Validator::new(schema, config);
+    for (node, label) in labels {
+        validator.validate_node_shape(&node, &label, data)?;
+    }
+    let result_map = validator.result_map(Some(data.prefixmap()))?;
+    Ok(result_map.result_shape_map())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_watch_shacl(
+    shapes: &InputSpec,
+    shapes_format: &ShaclFormat,
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    endpoint: &Option<String>,
+    reader_mode: &RDFReaderMode,
+    mode: ShaclValidationMode,
+    conformance_mode: ConformanceMode,
+    debug: u8,
+    output: &Option<PathBuf>,
+    force_overwrite: bool,
+) -> Result<()> {
+    if endpoint.is_some() {
+        bail!("Watch mode is not supported for data in SPARQL endpoints")
+    }
+    if data.is_empty() {
+        bail!("Please provide some local data files to watch")
+    }
+    let (mut writer, color) = get_writer(output, force_overwrite)?;
+    let shapes_path = watched_path(shapes);
+    let data_paths: Vec<PathBuf> = data.iter().filter_map(watched_path).collect();
+    let paths: Vec<PathBuf> = shapes_path
+        .iter()
+        .chain(data_paths.iter())
+        .cloned()
+        .collect();
+    let mut watcher = FileWatcher::new(paths)?;
+
+    let load_data = || -> Result<GraphValidation> {
+        let rdf_data = get_data(
+            data,
+            data_format,
+            endpoint,
+            reader_mode,
+            debug,
+            &RdfDataConfig::default(),
+        )?;
+        Ok(GraphValidation::from_rdf_data(rdf_data, mode))
+    };
+    let load_shapes = || -> Result<ShaclSchema<RdfData>> {
+        let reader = shapes.open_read()?;
+        Ok(ShaclDataManager::load(
+            reader,
+            map_shacl_format(shapes_format)?,
+            None,
+        )?)
+    };
+    let prepare = |validator: &GraphValidation, report: ValidationReport<RdfData>| {
+        let report = report
+            .with_conformance_mode(conformance_mode)
+            .with_nodes_prefixmap(validator.store().prefixmap());
+        match color {
+            ColorSupport::NoColor => report.without_colors(),
+            ColorSupport::WithColor => report,
+        }
+    };
+
+    let mut validator = load_data()?;
+    let mut schema = load_shapes()?;
+    let mut report = prepare(&validator, validator.validate(&schema)?);
+    write!(writer, "{report}")?;
+    writer.flush()?;
+
+    loop {
+        let changed = watcher.wait_changes();
+        let shapes_changed = shapes_path.iter().any(|path| changed.contains(path));
+        let data_changed = data_paths.iter().any(|path| changed.contains(path));
+        let round = || -> Result<ShaclRound> {
+            let new_schema = match shapes_changed {
+                true => Some(load_shapes()?),
+                false => None,
+            };
+            let new_validator = match data_changed {
+                true => Some(load_data()?),
+                false => None,
+            };
+            let current_validator = new_validator.as_ref().unwrap_or(&validator);
+            let current_schema = new_schema.as_ref().unwrap_or(&schema);
+            let before = validator.store().graph();
+            let after = current_validator.store().graph();
+            let (new_report, revalidated) = match (&new_schema, before, after) {
+                // Any shape can change the targets, so all the nodes are validated again
+                (Some(_), _, _) | (None, None, _) | (None, _, None) => {
+                    let new_report = current_validator.validate(current_schema)?;
+                    (prepare(current_validator, new_report), None)
+                }
+                (None, Some(before), Some(after)) => {
+                    let changed = after.changed_nodes(before);
+                    let inverse = current_schema.has_inverse_paths();
+                    let mut nodes = before.dependent_nodes(&changed, inverse);
+                    nodes.extend(after.dependent_nodes(&changed, inverse));
+                    // Changes in other nodes, like the types of a node or the
+                    // subjects and objects of some predicate, can add or remove
+                    // nodes from the targets of the shapes
+                    let targets_before = validator.targets(current_schema);
+                    let targets_after = current_validator.targets(current_schema);
+                    nodes.extend(
+                        targets_before
+                            .symmetric_difference(&targets_after)
+                            .map(|(_, node)| node.clone()),
+                    );
+                    let partial = current_validator.validate_nodes(current_schema, &nodes)?;
+                    let mut new_report = report.clone();
+                    new_report.update(&nodes, partial);
+                    (new_report, Some(nodes.len()))
+                }
+            };
+            Ok(ShaclRound {
+                schema: new_schema,
+                validator: new_validator,
+                report: new_report,
+                revalidated,
+            })
+        };
+        match round() {
+            Ok(round) => {
+                match round.revalidated {
+                    Some(n) => writeln!(writer, "Revalidated {n} nodes")?,
+                    None => writeln!(writer, "Revalidated all nodes")?,
+                }
+                let diff = round.report.show_diff(&report);
+                if diff.is_empty() {
+                    writeln!(writer, "No changes")?;
+                } else {
+                    writeln!(writer, "{diff}")?;
+                }
+                if let Some(new_schema) = round.schema {
+                    schema = new_schema
+                }
+                if let Some(new_validator) = round.validator {
+                    validator = new_validator
+                }
+                report = round.report;
+            }
+            Err(err) => writeln!(writer, "Error: {err}")?,
+        }
+        writer.flush()?;
+    }
+}
+
+/// Outcome of the SHACL validation after some files change, with the files
+/// that have been loaded again
+struct ShaclRound {
+    schema: Option<ShaclSchema<RdfData>>,
+    validator: Option<GraphValidation>,
+    report: ValidationReport<RdfData>,
+    revalidated: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shapemap::ValidationStatus;
+    use shex_compact::{ShExParser, ShapeMapParser};
+    use srdf::{RDFFormat, ReaderMode};
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice :name "Alice" ."#;
+
+    fn schema(employee: &str) -> SchemaJson {
+        let str = format!(
+            r#"prefix : <http://example.org/>
+ABSTRACT :Person {{ :name . }}
+:Employee extends @:Person {{ {employee} }}"#
+        );
+        ShExParser::parse(&str, None).unwrap()
+    }
+
+    fn state(schema: SchemaJson, config: &ValidatorConfig) -> ShExState {
+        let graph =
+            SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let data = RdfData::from_graph(graph).unwrap();
+        let shapemap = ShapeMapParser::parse(
+            ":alice@:Person",
+            &Some(data.prefixmap()),
+            &schema.prefixmap(),
+        )
+        .unwrap();
+        let pairs = shapemap_pairs(&shapemap, &data, &schema).unwrap();
+        let mut compiled = CompiledSchema::new();
+        compiled.from_schema_json(&schema).unwrap();
+        let result = validate_pairs(compiled, &pairs, &data, config).unwrap();
+        ShExState {
+            schema,
+            data,
+            shapemap,
+            pairs,
+            result,
+        }
+    }
+
+    #[test]
+    fn shapes_are_revalidated_when_the_shapes_that_extend_them_change() {
+        let config = ValidatorConfig::default();
+        let state = state(schema(":salary ."), &config);
+        let alice = Object::iri(iri_s::IriS::new_unchecked("http://example.org/alice"));
+        let person = ShapeExprLabel::iri_unchecked("http://example.org/Person");
+        assert_eq!(
+            state.result.get_status(&alice, &person),
+            Some(&ValidationStatus::NonConformant)
+        );
+        let round = revalidate(&state, Some(schema(":salary . ?")), None, None, &config).unwrap();
+        assert_eq!(round.revalidated, 1);
+        assert_eq!(
+            round.result.get_status(&alice, &person),
+            Some(&ValidationStatus::Conformant)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use dctap::{DCTap, TapConfig};
+use shacl_ast::ShaclWriter;
+use shapes_converter::{
+    ShEx2Shacl, ShEx2Sparql, ShEx2Uml, Shacl2ShEx, Tap2ShEx, Tap2ShExConfig, UmlGenerationMode,
+};
+use shex_ast::Schema as SchemaJson;
+use shex_compact::{ShExFormatter, ShapeMapParser};
+use shex_validation::ShExFormat;
+use srdf::SRDFGraph;
+
+use crate::data::rdf_format;
+use crate::shacl::parse_shacl_schema;
+use crate::shex::{parse_shex_schema, shex_format, to_json};
+use crate::RudofWasmError;
+
+type Result<A> = std::result::Result<A, RudofWasmError>;
+
+/// Converts a DCTAP in CSV to a ShEx schema in `result_format`, `shexc` or `shexj`
+pub fn dctap2shex(input: &str, result_format: &str) -> Result<String> {
+    let dctap = DCTap::from_reader(input.as_bytes(), &TapConfig::default()).map_err(|e| {
+        RudofWasmError::DCTap {
+            error: e.to_string(),
+        }
+    })?;
+    let schema = Tap2ShEx::new(&Tap2ShExConfig::default())
+        .convert(&dctap)
+        .map_err(|e| conversion_error("DCTAP", "ShEx", e))?;
+    show_shex(&schema, result_format)
+}
+
+/// Converts SHACL shapes to a ShEx schema in `result_format`, `shexc` or `shexj`
+pub fn shacl2shex(input: &str, format: &str, result_format: &str) -> Result<String> {
+    let shacl = parse_shacl_schema(input, format)?;
+    let mut converter = Shacl2ShEx::new(&Default::default());
+    converter
+        .convert(&shacl)
+        .map_err(|e| conversion_error("SHACL", "ShEx", e))?;
+    show_shex(converter.current_shex(), result_format)
+}
+
+/// Converts a ShEx schema to SHACL shapes serialized in `result_format`
+pub fn shex2shacl(input: &str, format: &str, result_format: &str) -> Result<String> {
+    let schema = parse_shex_schema(input, format)?;
+    let result_format = rdf_format(result_format)?;
+    let mut converter = ShEx2Shacl::new(&Default::default());
+    converter
+        .convert(&schema)
+        .map_err(|e| conversion_error("ShEx", "SHACL", e))?;
+    let mut shacl_writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
+    shacl_writer
+        .write(converter.current_shacl())
+        .map_err(|e| conversion_error("ShEx", "SHACL", e))?;
+    let mut output = Vec::new();
+    shacl_writer
+        .serialize(result_format, &mut output)
+        .map_err(|e| conversion_error("ShEx", "SHACL", e))?;
+    to_string(output)
+}
+
+/// Generates a SPARQL query that obtains the nodes that match `shape`, or the start shape if it is empty
+pub fn shex2sparql(input: &str, format: &str, shape: &str) -> Result<String> {
+    let schema = parse_shex_schema(input, format)?;
+    let shape = if shape.is_empty() {
+        None
+    } else {
+        Some(
+            ShapeMapParser::parse_iri_ref(shape).map_err(|e| RudofWasmError::ShapeMap {
+                error: e.to_string(),
+            })?,
+        )
+    };
+    let query = ShEx2Sparql::new(&Default::default())
+        .convert(&schema, shape)
+        .map_err(|e| conversion_error("ShEx", "SPARQL", e))?;
+    Ok(query.to_string())
+}
+
+/// Converts a ShEx schema to PlantUML, showing only the neighbours of `shape` unless it is empty
+pub fn shex2uml(input: &str, format: &str, shape: &str) -> Result<String> {
+    let schema = parse_shex_schema(input, format)?;
+    let mut converter = ShEx2Uml::new(&Default::default());
+    converter
+        .convert(&schema)
+        .map_err(|e| conversion_error("ShEx", "UML", e))?;
+    let mode = if shape.is_empty() {
+        UmlGenerationMode::all()
+    } else {
+        UmlGenerationMode::neighs(shape)
+    };
+    let mut output = Vec::new();
+    converter
+        .as_plantuml(&mut output, &mode)
+        .map_err(|e| conversion_error("ShEx", "UML", e))?;
+    to_string(output)
+}
+
+fn show_shex(schema: &SchemaJson, format: &str) -> Result<String> {
+    match shex_format(format)? {
+        ShExFormat::ShExC => Ok(ShExFormatter::default()
+            .without_colors()
+            .format_schema(schema)),
+        ShExFormat::ShExJ => to_json(schema),
+    }
+}
+
+fn to_string(bytes: Vec<u8>) -> Result<String> {
+    String::from_utf8(bytes).map_err(|e| RudofWasmError::Serializing {
+        error: e.to_string(),
+    })
+}
+
+fn conversion_error(from: &str, to: &str, error: impl std::fmt::Display) -> RudofWasmError {
+    RudofWasmError::Conversion {
+        from: from.to_string(),
+        to: to.to_string(),
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person { :name xsd:string }"#;
+
+    #[test]
+    fn shex_to_shacl_and_back() {
+        let shacl = shex2shacl(SCHEMA, "shexc", "turtle").unwrap();
+        assert!(shacl.contains("NodeShape"));
+        let shex = shacl2shex(&shacl, "turtle", "shexc").unwrap();
+        assert!(shex.contains(":Person"));
+    }
+
+    #[test]
+    fn dctap_to_shex() {
+        let dctap = "shapeID,propertyID\nPerson,name\n";
+        let shex = dctap2shex(dctap, "shexc").unwrap();
+        assert!(shex.contains("Person"));
+    }
+
+    #[test]
+    fn shex_to_uml() {
+        let uml = shex2uml(SCHEMA, "shexc", "").unwrap();
+        assert!(uml.starts_with("@startuml"));
+    }
+}
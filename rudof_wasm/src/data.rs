@@ -0,0 +1,31 @@
+use sparql_service::RdfData;
+use srdf::{RDFFormat, ReaderMode, SRDFGraph};
+use std::str::FromStr;
+
+use crate::RudofWasmError;
+
+/// Reads RDF data contained in a string into an in-memory graph
+pub fn read_data(data: &str, format: &str) -> Result<RdfData, RudofWasmError> {
+    let format = rdf_format(format)?;
+    let graph = SRDFGraph::from_str(data, &format, None, &ReaderMode::Strict).map_err(|e| {
+        RudofWasmError::RdfData {
+            error: e.to_string(),
+        }
+    })?;
+    RdfData::from_graph(graph).map_err(|e| RudofWasmError::RdfData {
+        error: e.to_string(),
+    })
+}
+
+/// Obtains an RDF format from its name, like `turtle`, or its usual file extension, like `ttl`
+pub fn rdf_format(format: &str) -> Result<RDFFormat, RudofWasmError> {
+    match format.to_lowercase().as_str() {
+        "turtle" => Ok(RDFFormat::Turtle),
+        "ntriples" | "n-triples" => Ok(RDFFormat::NTriples),
+        "rdfxml" | "rdf/xml" => Ok(RDFFormat::RDFXML),
+        "nquads" | "n-quads" => Ok(RDFFormat::NQuads),
+        other => RDFFormat::from_str(other).map_err(|_| RudofWasmError::UnknownRdfFormat {
+            format: format.to_string(),
+        }),
+    }
+}
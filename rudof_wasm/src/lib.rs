@@ -0,0 +1,101 @@
+//! WebAssembly bindings of rudof
+//!
+//! The functions take the schemas and data as strings and return strings, which are JSON for structured results,
+//! so they can be called from JavaScript without a server. Reading from files or URLs and querying SPARQL endpoints
+//! are not available in the browser, so the contents must be obtained by the caller.
+pub mod convert;
+pub mod data;
+pub mod rudof_wasm_error;
+pub mod shacl;
+pub mod shex;
+
+pub use crate::rudof_wasm_error::*;
+
+use wasm_bindgen::prelude::*;
+
+/// Version of rudof
+#[wasm_bindgen]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// Parses a ShEx schema in `shexc` or `shexj` and returns it in ShExJ
+#[wasm_bindgen(js_name = parseShEx)]
+pub fn parse_shex(input: &str, format: &str) -> Result<String, JsError> {
+    Ok(shex::parse_shex(input, format)?)
+}
+
+/// Parses a ShEx schema in `shexc` or `shexj` and returns it in ShExC
+#[wasm_bindgen(js_name = formatShEx)]
+pub fn format_shex(input: &str, format: &str) -> Result<String, JsError> {
+    Ok(shex::format_shex(input, format)?)
+}
+
+/// Validates RDF data against a ShEx schema with a shape map in compact syntax and returns the result shape map in JSON
+#[wasm_bindgen(js_name = validateShEx)]
+pub fn validate_shex(
+    schema: &str,
+    schema_format: &str,
+    data: &str,
+    data_format: &str,
+    shapemap: &str,
+) -> Result<String, JsError> {
+    Ok(shex::validate_shex(
+        schema,
+        schema_format,
+        data,
+        data_format,
+        shapemap,
+    )?)
+}
+
+/// Parses SHACL shapes and returns their prefixes and shape identifiers in JSON
+#[wasm_bindgen(js_name = parseShacl)]
+pub fn parse_shacl(input: &str, format: &str) -> Result<String, JsError> {
+    Ok(shacl::parse_shacl(input, format)?)
+}
+
+/// Validates RDF data against SHACL shapes and returns the validation report in JSON-LD
+#[wasm_bindgen(js_name = validateShacl)]
+pub fn validate_shacl(
+    data: &str,
+    data_format: &str,
+    shapes: &str,
+    shapes_format: &str,
+    mode: &str,
+    conformance: &str,
+) -> Result<String, JsError> {
+    Ok(shacl::validate_shacl(
+        data,
+        data_format,
+        shapes,
+        shapes_format,
+        mode,
+        conformance,
+    )?)
+}
+
+#[wasm_bindgen(js_name = dctap2shex)]
+pub fn dctap2shex(input: &str, result_format: &str) -> Result<String, JsError> {
+    Ok(convert::dctap2shex(input, result_format)?)
+}
+
+#[wasm_bindgen(js_name = shacl2shex)]
+pub fn shacl2shex(input: &str, format: &str, result_format: &str) -> Result<String, JsError> {
+    Ok(convert::shacl2shex(input, format, result_format)?)
+}
+
+#[wasm_bindgen(js_name = shex2shacl)]
+pub fn shex2shacl(input: &str, format: &str, result_format: &str) -> Result<String, JsError> {
+    Ok(convert::shex2shacl(input, format, result_format)?)
+}
+
+#[wasm_bindgen(js_name = shex2sparql)]
+pub fn shex2sparql(input: &str, format: &str, shape: &str) -> Result<String, JsError> {
+    Ok(convert::shex2sparql(input, format, shape)?)
+}
+
+#[wasm_bindgen(js_name = shex2uml)]
+pub fn shex2uml(input: &str, format: &str, shape: &str) -> Result<String, JsError> {
+    Ok(convert::shex2uml(input, format, shape)?)
+}
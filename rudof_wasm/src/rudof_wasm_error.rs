@@ -0,0 +1,53 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RudofWasmError {
+    #[error("Unknown RDF format: {format}")]
+    UnknownRdfFormat { format: String },
+
+    #[error("Unknown ShEx format: {format}")]
+    UnknownShExFormat { format: String },
+
+    #[error("Unknown SHACL validation mode: {mode}")]
+    UnknownShaclMode { mode: String },
+
+    #[error("Unknown conformance mode: {mode}")]
+    UnknownConformanceMode { mode: String },
+
+    #[error("Error reading RDF data: {error}")]
+    RdfData { error: String },
+
+    #[error("Error parsing ShExC: {error}")]
+    ShExC { error: String },
+
+    #[error("Error parsing ShExJ: {error}")]
+    ShExJ { error: String },
+
+    #[error("Error compiling ShEx schema: {error}")]
+    CompilingSchema { error: String },
+
+    #[error("Error parsing shape map: {error}")]
+    ShapeMap { error: String },
+
+    #[error("Error validating with ShEx: {error}")]
+    ShExValidation { error: String },
+
+    #[error("Error reading SHACL shapes: {error}")]
+    Shacl { error: String },
+
+    #[error("Error validating with SHACL: {error}")]
+    ShaclValidation { error: String },
+
+    #[error("Error reading DCTAP: {error}")]
+    DCTap { error: String },
+
+    #[error("Error converting {from} to {to}: {error}")]
+    Conversion {
+        from: String,
+        to: String,
+        error: String,
+    },
+
+    #[error("Error serializing result: {error}")]
+    Serializing { error: String },
+}
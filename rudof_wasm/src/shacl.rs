@@ -0,0 +1,132 @@
+use serde_json::json;
+use shacl_ast::{Schema, ShaclParser};
+use shacl_validation::shacl_processor::{GraphValidation, ShaclProcessor, ShaclValidationMode};
+use shacl_validation::store::ShaclDataManager;
+use shacl_validation::validation_report::report::ConformanceMode;
+use srdf::{Object, ReaderMode, SRDFGraph};
+use std::io::Cursor;
+
+use crate::data::{rdf_format, read_data};
+use crate::shex::to_json;
+use crate::RudofWasmError;
+
+type Result<A> = std::result::Result<A, RudofWasmError>;
+
+/// Parses SHACL shapes and returns a JSON object with the declared prefixes and the identifiers of the shapes
+pub fn parse_shacl(input: &str, format: &str) -> Result<String> {
+    let schema = parse_shacl_schema(input, format)?;
+    let prefixmap = schema.prefix_map();
+    let mut shapes: Vec<String> = schema
+        .iter()
+        .map(|(node, _)| match node {
+            Object::Iri(iri) => prefixmap.qualify(iri),
+            other => other.to_string(),
+        })
+        .collect();
+    shapes.sort();
+    let prefixes: serde_json::Map<String, serde_json::Value> = prefixmap
+        .iter()
+        .map(|(alias, iri)| (alias.clone(), json!(iri.as_str())))
+        .collect();
+    to_json(&json!({ "prefixes": prefixes, "shapes": shapes }))
+}
+
+/// Validates RDF data against SHACL shapes and returns the validation report in JSON-LD.
+///
+/// `mode` selects the engine, `native` or `sparql`, and `conformance` whether any result,
+/// `strict`, or only violations, `violations`, make the data not conform
+pub fn validate_shacl(
+    data: &str,
+    data_format: &str,
+    shapes: &str,
+    shapes_format: &str,
+    mode: &str,
+    conformance: &str,
+) -> Result<String> {
+    let mode = match mode.to_lowercase().as_str() {
+        "native" => ShaclValidationMode::Native,
+        "sparql" => ShaclValidationMode::Sparql,
+        _ => {
+            return Err(RudofWasmError::UnknownShaclMode {
+                mode: mode.to_string(),
+            })
+        }
+    };
+    let conformance = match conformance.to_lowercase().as_str() {
+        "strict" => ConformanceMode::Strict,
+        "violations" => ConformanceMode::Violations,
+        _ => {
+            return Err(RudofWasmError::UnknownConformanceMode {
+                mode: conformance.to_string(),
+            })
+        }
+    };
+    let data = read_data(data, data_format)?;
+    let schema = ShaclDataManager::load(Cursor::new(shapes), rdf_format(shapes_format)?, None)
+        .map_err(|e| RudofWasmError::Shacl {
+            error: e.to_string(),
+        })?;
+    let prefixmap = data.prefixmap();
+    let validator = GraphValidation::from_rdf_data(data, mode);
+    let report = validator
+        .validate(&schema)
+        .map_err(|e| RudofWasmError::ShaclValidation {
+            error: e.to_string(),
+        })?
+        .with_nodes_prefixmap(prefixmap)
+        .with_conformance_mode(conformance)
+        .without_colors();
+    to_json(&report.to_json_ld())
+}
+
+pub(crate) fn parse_shacl_schema(input: &str, format: &str) -> Result<Schema> {
+    let format = rdf_format(format)?;
+    let rdf = SRDFGraph::from_str(input, &format, None, &ReaderMode::Strict).map_err(|e| {
+        RudofWasmError::Shacl {
+            error: e.to_string(),
+        }
+    })?;
+    ShaclParser::new(rdf)
+        .parse()
+        .map_err(|e| RudofWasmError::Shacl {
+            error: e.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHAPES: &str = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:PersonShape a sh:NodeShape ;
+  sh:targetClass :Person ;
+  sh:property [
+    sh:path :name ;
+    sh:datatype xsd:string ;
+    sh:minCount 1
+  ] ."#;
+
+    #[test]
+    fn lists_shapes() {
+        let result: serde_json::Value =
+            serde_json::from_str(&parse_shacl(SHAPES, "turtle").unwrap()).unwrap();
+        assert!(result["shapes"]
+            .as_array()
+            .unwrap()
+            .contains(&json!(":PersonShape")));
+        assert_eq!(result["prefixes"]["sh"], "http://www.w3.org/ns/shacl#");
+    }
+
+    #[test]
+    fn validates_data() {
+        let data = r#"prefix : <http://example.org/>
+:alice a :Person ; :name "Alice" .
+:bob a :Person ."#;
+        let report = validate_shacl(data, "turtle", SHAPES, "turtle", "native", "strict").unwrap();
+        let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(report["sh:conforms"], false);
+    }
+}
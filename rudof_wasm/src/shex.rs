@@ -0,0 +1,161 @@
+use shex_ast::compiled::compiled_schema::CompiledSchema;
+use shex_ast::Schema as SchemaJson;
+use shex_compact::{ShExFormatter, ShExParser, ShapeMapParser};
+use shex_validation::{ShExFormat, Validator, ValidatorConfig};
+
+use crate::data::read_data;
+use crate::RudofWasmError;
+
+type Result<A> = std::result::Result<A, RudofWasmError>;
+
+/// Parses a ShEx schema and returns it in ShExJ
+pub fn parse_shex(input: &str, format: &str) -> Result<String> {
+    let schema = parse_shex_schema(input, format)?;
+    to_json(&schema)
+}
+
+/// Parses a ShEx schema and returns it in compact syntax
+pub fn format_shex(input: &str, format: &str) -> Result<String> {
+    let schema = parse_shex_schema(input, format)?;
+    Ok(ShExFormatter::default()
+        .without_colors()
+        .format_schema(&schema))
+}
+
+/// Validates RDF data against a ShEx schema for the node/shape pairs of a shape map in compact syntax.
+///
+/// Prefixed names in the shape map are resolved with the prefixes of the data for the nodes
+/// and with the prefixes of the schema for the shapes. The result shape map is returned in JSON
+pub fn validate_shex(
+    schema: &str,
+    schema_format: &str,
+    data: &str,
+    data_format: &str,
+    shapemap: &str,
+) -> Result<String> {
+    let schema = parse_shex_schema(schema, schema_format)?;
+    let data = read_data(data, data_format)?;
+    let nodes_prefixmap = data.prefixmap();
+    let shapes_prefixmap = schema.prefixmap().unwrap_or_default();
+    let shapemap = ShapeMapParser::parse(
+        shapemap,
+        &Some(nodes_prefixmap.clone()),
+        &Some(shapes_prefixmap.clone()),
+    )
+    .map_err(|e| RudofWasmError::ShapeMap {
+        error: e.to_string(),
+    })?;
+    let mut compiled = CompiledSchema::new();
+    compiled
+        .from_schema_json(&schema)
+        .map_err(|e| RudofWasmError::CompilingSchema {
+            error: e.to_string(),
+        })?;
+    let mut validator = Validator::new(compiled, &ValidatorConfig::default());
+    validator
+        .validate_shapemap(&shapemap, &data)
+        .map_err(|e| RudofWasmError::ShExValidation {
+            error: e.to_string(),
+        })?;
+    let result = validator
+        .result_map(Some(nodes_prefixmap.without_rich_qualifying()))
+        .map_err(|e| RudofWasmError::ShExValidation {
+            error: e.to_string(),
+        })?
+        .with_schema_prefixmap(shapes_prefixmap.without_rich_qualifying());
+    to_json(&result.result_shape_map())
+}
+
+pub(crate) fn parse_shex_schema(input: &str, format: &str) -> Result<SchemaJson> {
+    match shex_format(format)? {
+        ShExFormat::ShExC => ShExParser::parse(input, None).map_err(|e| RudofWasmError::ShExC {
+            error: e.to_string(),
+        }),
+        ShExFormat::ShExJ => {
+            serde_json::from_str::<SchemaJson>(input).map_err(|e| RudofWasmError::ShExJ {
+                error: e.to_string(),
+            })
+        }
+    }
+}
+
+pub(crate) fn shex_format(format: &str) -> Result<ShExFormat> {
+    match format.to_lowercase().as_str() {
+        "shexc" => Ok(ShExFormat::ShExC),
+        "shexj" | "json" => Ok(ShExFormat::ShExJ),
+        _ => Err(RudofWasmError::UnknownShExFormat {
+            format: format.to_string(),
+        }),
+    }
+}
+
+pub(crate) fn to_json<A: serde::Serialize>(value: &A) -> Result<String> {
+    serde_json::to_string_pretty(value).map_err(|e| RudofWasmError::Serializing {
+        error: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person {
+  :name xsd:string ;
+  :age xsd:integer ?
+}"#;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+
+:alice :name "Alice" ; :age 23 .
+:bob :name 23 ."#;
+
+    #[test]
+    fn validates_conformant_and_nonconformant_nodes() {
+        let result = validate_shex(
+            SCHEMA,
+            "shexc",
+            DATA,
+            "turtle",
+            ":alice@:Person,:bob@:Person",
+        )
+        .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let statuses: Vec<(String, String)> = result
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|assoc| {
+                (
+                    assoc["node"].as_str().unwrap().to_string(),
+                    assoc["status"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert!(statuses.contains(&(
+            "http://example.org/alice".to_string(),
+            "conformant".to_string()
+        )));
+        assert!(statuses.contains(&(
+            "http://example.org/bob".to_string(),
+            "nonconformant".to_string()
+        )));
+    }
+
+    #[test]
+    fn shexj_round_trip() {
+        let json = parse_shex(SCHEMA, "shexc").unwrap();
+        let compact = format_shex(&json, "shexj").unwrap();
+        assert!(compact.contains(":Person"));
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        assert!(matches!(
+            parse_shex(":Person { :name", "shexc"),
+            Err(RudofWasmError::ShExC { .. })
+        ));
+    }
+}
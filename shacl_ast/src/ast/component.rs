@@ -1,11 +1,14 @@
 use crate::{
-    node_kind::NodeKind, value::Value, SH_AND_STR, SH_CLASS_STR, SH_CLOSED_STR, SH_DATATYPE_STR,
-    SH_DISJOINT_STR, SH_EQUALS_STR, SH_FLAGS_STR, SH_HAS_VALUE_STR, SH_IGNORED_PROPERTIES_STR,
-    SH_IN_STR, SH_IRI_STR, SH_LANGUAGE_IN_STR, SH_LESS_THAN_OR_EQUALS_STR, SH_LESS_THAN_STR,
-    SH_MAX_COUNT_STR, SH_MAX_EXCLUSIVE_STR, SH_MAX_INCLUSIVE_STR, SH_MAX_LENGTH_STR,
-    SH_MIN_COUNT_STR, SH_MIN_EXCLUSIVE_STR, SH_MIN_INCLUSIVE_STR, SH_MIN_LENGTH_STR, SH_NODE_STR,
+    node_kind::NodeKind, sparql_constraint::SparqlConstraint, value::Value, SH_AND_STR,
+    SH_BLANKNODE_STR, SH_BLANK_NODE_OR_IRI_STR, SH_BLANK_NODE_OR_LITERAL_STR, SH_CLASS_STR,
+    SH_CLOSED_STR, SH_DATATYPE_STR, SH_DISJOINT_STR, SH_EQUALS_STR, SH_FLAGS_STR, SH_HAS_VALUE_STR,
+    SH_IGNORED_PROPERTIES_STR, SH_IN_STR, SH_IRI_OR_LITERAL_STR, SH_IRI_STR, SH_LANGUAGE_IN_STR,
+    SH_LESS_THAN_OR_EQUALS_STR, SH_LESS_THAN_STR, SH_LITERAL_STR, SH_MAX_COUNT_STR,
+    SH_MAX_EXCLUSIVE_STR, SH_MAX_INCLUSIVE_STR, SH_MAX_LENGTH_STR, SH_MIN_COUNT_STR,
+    SH_MIN_EXCLUSIVE_STR, SH_MIN_INCLUSIVE_STR, SH_MIN_LENGTH_STR, SH_NODE_KIND_STR, SH_NODE_STR,
     SH_NOT_STR, SH_OR_STR, SH_PATTERN_STR, SH_QUALIFIED_MAX_COUNT_STR, SH_QUALIFIED_MIN_COUNT_STR,
-    SH_QUALIFIED_VALUE_SHAPE_STR, SH_UNIQUE_LANG_STR, SH_XONE_STR,
+    SH_QUALIFIED_VALUE_SHAPES_DISJOINT_STR, SH_QUALIFIED_VALUE_SHAPE_STR,
+    SH_SPARQL_CONSTRAINT_COMPONENT_STR, SH_SPARQL_STR, SH_UNIQUE_LANG_STR, SH_XONE_STR,
 };
 use iri_s::{iri, IriS};
 use itertools::Itertools;
@@ -70,6 +73,13 @@ pub enum Component {
         qualified_max_count: Option<isize>,
         qualified_value_shapes_disjoint: Option<bool>,
     },
+    Sparql(SparqlConstraint),
+    /// Use of a SPARQL-based constraint component, with the values that the
+    /// shape declares for each of the parameters of the component
+    SparqlComponent {
+        component: IriS,
+        parameters: Vec<(IriS, RDFNode)>,
+    },
 }
 
 impl Component {
@@ -87,11 +97,14 @@ impl Component {
             Self::NodeKind(node_kind) => {
                 let iri = match &node_kind {
                     NodeKind::Iri => SH_IRI_STR,
-
-                    _ => unimplemented!(),
+                    NodeKind::Literal => SH_LITERAL_STR,
+                    NodeKind::BlankNode => SH_BLANKNODE_STR,
+                    NodeKind::BlankNodeOrIri => SH_BLANK_NODE_OR_IRI_STR,
+                    NodeKind::BlankNodeOrLiteral => SH_BLANK_NODE_OR_LITERAL_STR,
+                    NodeKind::IRIOrLiteral => SH_IRI_OR_LITERAL_STR,
                 };
 
-                Self::write_iri(&IriRef::Iri(iri!(iri)), SH_DATATYPE_STR, rdf_node, rdf)?;
+                Self::write_iri(&IriRef::Iri(iri!(iri)), SH_NODE_KIND_STR, rdf_node, rdf)?;
             }
             Self::MinCount(value) => {
                 Self::write_integer(*value, SH_MIN_COUNT_STR, rdf_node, rdf)?;
@@ -127,14 +140,11 @@ impl Component {
                 Self::write_boolean(*value, SH_UNIQUE_LANG_STR, rdf_node, rdf)?;
             }
             Self::LanguageIn { langs } => {
-                langs.iter().try_for_each(|lang| {
-                    Self::write_literal(
-                        &Literal::str(&lang.value()),
-                        SH_LANGUAGE_IN_STR,
-                        rdf_node,
-                        rdf,
-                    )
-                })?;
+                let values = langs
+                    .iter()
+                    .map(|lang| Self::literal_term::<RDF>(&Literal::str(&lang.value())))
+                    .collect::<Vec<_>>();
+                Self::write_list(&values, SH_LANGUAGE_IN_STR, rdf_node, rdf)?;
             }
             Self::Equals(iri) => {
                 Self::write_iri(iri, SH_EQUALS_STR, rdf_node, rdf)?;
@@ -149,22 +159,19 @@ impl Component {
                 Self::write_iri(iri, SH_LESS_THAN_OR_EQUALS_STR, rdf_node, rdf)?;
             }
             Self::Or { shapes } => {
-                shapes.iter().try_for_each(|shape| {
-                    Self::write_term(&RDF::object_as_term(shape), SH_OR_STR, rdf_node, rdf)
-                })?;
+                let values = shapes.iter().map(RDF::object_as_term).collect::<Vec<_>>();
+                Self::write_list(&values, SH_OR_STR, rdf_node, rdf)?;
             }
             Self::And { shapes } => {
-                shapes.iter().try_for_each(|shape| {
-                    Self::write_term(&RDF::object_as_term(shape), SH_AND_STR, rdf_node, rdf)
-                })?;
+                let values = shapes.iter().map(RDF::object_as_term).collect::<Vec<_>>();
+                Self::write_list(&values, SH_AND_STR, rdf_node, rdf)?;
             }
             Self::Not { shape } => {
-                Self::write_term(&RDF::object_as_term(shape), SH_PATTERN_STR, rdf_node, rdf)?;
+                Self::write_term(&RDF::object_as_term(shape), SH_NOT_STR, rdf_node, rdf)?;
             }
             Self::Xone { shapes } => {
-                shapes.iter().try_for_each(|shape| {
-                    Self::write_term(&RDF::object_as_term(shape), SH_XONE_STR, rdf_node, rdf)
-                })?;
+                let values = shapes.iter().map(RDF::object_as_term).collect::<Vec<_>>();
+                Self::write_list(&values, SH_XONE_STR, rdf_node, rdf)?;
             }
             Self::Closed {
                 is_closed,
@@ -172,36 +179,31 @@ impl Component {
             } => {
                 Self::write_boolean(*is_closed, SH_CLOSED_STR, rdf_node, rdf)?;
 
-                ignored_properties.iter().try_for_each(|iri| {
-                    Self::write_iri(iri, SH_IGNORED_PROPERTIES_STR, rdf_node, rdf)
-                })?;
+                if !ignored_properties.is_empty() {
+                    let values = ignored_properties
+                        .iter()
+                        .map(|iri| Ok(RDF::iri_s2term(&rdf.resolve_iri_ref(iri)?)))
+                        .collect::<Result<Vec<_>, RDF::Err>>()?;
+                    Self::write_list(&values, SH_IGNORED_PROPERTIES_STR, rdf_node, rdf)?;
+                }
             }
             Self::Node { shape } => {
                 Self::write_term(&RDF::object_as_term(shape), SH_NODE_STR, rdf_node, rdf)?;
             }
-            Self::HasValue { value } => match value {
-                Value::Iri(iri) => {
-                    Self::write_iri(iri, SH_HAS_VALUE_STR, rdf_node, rdf)?;
-                }
-                Value::Literal(literal) => {
-                    Self::write_literal(
-                        &Literal::str(&literal.to_string()),
-                        SH_HAS_VALUE_STR,
-                        rdf_node,
-                        rdf,
-                    )?;
-                }
-            },
+            Self::HasValue { value } => {
+                Self::write_term(
+                    &Self::value_term(value, rdf)?,
+                    SH_HAS_VALUE_STR,
+                    rdf_node,
+                    rdf,
+                )?;
+            }
             Self::In { values } => {
-                values.iter().try_for_each(|value| match value {
-                    Value::Iri(iri) => Self::write_iri(iri, SH_HAS_VALUE_STR, rdf_node, rdf),
-                    Value::Literal(literal) => Self::write_literal(
-                        &Literal::str(&literal.to_string()),
-                        SH_HAS_VALUE_STR,
-                        rdf_node,
-                        rdf,
-                    ),
-                })?;
+                let values = values
+                    .iter()
+                    .map(|value| Self::value_term(value, rdf))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Self::write_list(&values, SH_IN_STR, rdf_node, rdf)?;
             }
             Self::QualifiedValueShape {
                 shape,
@@ -225,9 +227,28 @@ impl Component {
                 }
 
                 if let Some(value) = qualified_value_shapes_disjoint {
-                    Self::write_boolean(*value, SH_QUALIFIED_MAX_COUNT_STR, rdf_node, rdf)?;
+                    Self::write_boolean(
+                        *value,
+                        SH_QUALIFIED_VALUE_SHAPES_DISJOINT_STR,
+                        rdf_node,
+                        rdf,
+                    )?;
                 }
             }
+            Self::Sparql(constraint) => {
+                Self::write_term(
+                    &RDF::object_as_term(constraint.id()),
+                    SH_SPARQL_STR,
+                    rdf_node,
+                    rdf,
+                )?;
+                constraint.write(rdf)?;
+            }
+            Self::SparqlComponent { parameters, .. } => {
+                parameters.iter().try_for_each(|(path, value)| {
+                    Self::write_term(&RDF::object_as_term(value), path.as_str(), rdf_node, rdf)
+                })?;
+            }
         }
         Ok(())
     }
@@ -274,9 +295,39 @@ impl Component {
     where
         RDF: SRDFBuilder,
     {
-        let term = OxTerm::Literal(OxLiteral::new_simple_literal(value.lexical_form()));
+        Self::write_term(&Self::literal_term::<RDF>(value), predicate, rdf_node, rdf)
+    }
 
-        Self::write_term(&RDF::term_s2term(&term), predicate, rdf_node, rdf)
+    /// Writes the values as an RDF list, like the ones of `sh:in` or `sh:or`
+    fn write_list<RDF>(
+        values: &[RDF::Term],
+        predicate: &str,
+        rdf_node: &RDFNode,
+        rdf: &mut RDF,
+    ) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        let list = rdf.add_list(values)?;
+        Self::write_term(&list, predicate, rdf_node, rdf)
+    }
+
+    fn literal_term<RDF>(literal: &Literal) -> RDF::Term
+    where
+        RDF: SRDFBuilder,
+    {
+        RDF::object_as_term(&RDFNode::literal(literal.clone()))
+    }
+
+    /// Prefixed IRIs are resolved with the prefixes of the RDF graph
+    fn value_term<RDF>(value: &Value, rdf: &RDF) -> Result<RDF::Term, RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        match value {
+            Value::Iri(iri) => Ok(RDF::iri_s2term(&rdf.resolve_iri_ref(iri)?)),
+            Value::Literal(literal) => Ok(Self::literal_term::<RDF>(literal)),
+        }
     }
 
     fn write_iri<RDF>(
@@ -288,12 +339,8 @@ impl Component {
     where
         RDF: SRDFBuilder,
     {
-        Self::write_term(
-            &RDF::iri_s2term(&value.get_iri().unwrap()),
-            predicate,
-            rdf_node,
-            rdf,
-        )
+        let iri = rdf.resolve_iri_ref(value)?;
+        Self::write_term(&RDF::iri_s2term(&iri), predicate, rdf_node, rdf)
     }
 
     fn write_term<RDF>(
@@ -332,11 +379,14 @@ impl Display for Component {
                 None => write!(f, "pattern({pattern})"),
             },
             Component::UniqueLang(ul) => write!(f, "uniqueLang({ul})"),
-            Component::LanguageIn { .. } => todo!(), // write!(f, "languageIn({langs})"),
+            Component::LanguageIn { langs } => {
+                let str = langs.iter().map(|l| l.value()).join(" ");
+                write!(f, "languageIn([{str}])")
+            }
             Component::Equals(e) => write!(f, "equals({e})"),
             Component::Disjoint(d) => write!(f, "disjoint({d})"),
-            Component::LessThan(lt) => write!(f, "uniqueLang({lt})"),
-            Component::LessThanOrEquals(lte) => write!(f, "uniqueLang({lte})"),
+            Component::LessThan(lt) => write!(f, "lessThan({lt})"),
+            Component::LessThanOrEquals(lte) => write!(f, "lessThanOrEquals({lte})"),
             Component::Or { shapes } => {
                 let str = shapes.iter().map(|s| s.to_string()).join(" ");
                 write!(f, "or [{str}]")
@@ -352,14 +402,40 @@ impl Display for Component {
                 let str = shapes.iter().map(|s| s.to_string()).join(" ");
                 write!(f, "xone [{str}]")
             }
-            Component::Closed { .. } => todo!(),
+            Component::Closed {
+                is_closed,
+                ignored_properties,
+            } => {
+                let str = ignored_properties.iter().map(|p| p.to_string()).join(" ");
+                write!(f, "closed({is_closed}, [{str}])")
+            }
             Component::Node { shape } => write!(f, "node({shape})"),
             Component::HasValue { value } => write!(f, "hasValue({value})"),
             Component::In { values } => {
                 let str = values.iter().map(|v| v.to_string()).join(" ");
                 write!(f, "In [{str}]")
             }
-            Component::QualifiedValueShape { .. } => todo!(),
+            Component::QualifiedValueShape {
+                shape,
+                qualified_min_count,
+                qualified_max_count,
+                ..
+            } => {
+                let min = qualified_min_count.map_or("_".to_string(), |n| n.to_string());
+                let max = qualified_max_count.map_or("_".to_string(), |n| n.to_string());
+                write!(f, "qualifiedValueShape({shape}, {min}, {max})")
+            }
+            Component::Sparql(constraint) => write!(f, "{constraint}"),
+            Component::SparqlComponent {
+                component,
+                parameters,
+            } => {
+                let str = parameters
+                    .iter()
+                    .map(|(path, value)| format!("{path}={value}"))
+                    .join(" ");
+                write!(f, "{component} [{str}]")
+            }
         }
     }
 }
@@ -396,6 +472,8 @@ impl From<Component> for IriS {
             Component::QualifiedValueShape { .. } => {
                 IriS::new_unchecked(SH_QUALIFIED_VALUE_SHAPE_STR)
             }
+            Component::Sparql(_) => IriS::new_unchecked(SH_SPARQL_CONSTRAINT_COMPONENT_STR),
+            Component::SparqlComponent { component, .. } => component,
         }
     }
 }
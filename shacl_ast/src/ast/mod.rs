@@ -1,12 +1,15 @@
 pub mod component;
 pub mod message_map;
+pub mod node_expr;
 pub mod node_kind;
 pub mod node_shape;
 pub mod property_shape;
+pub mod rule;
 pub mod schema;
 pub mod severity;
 pub mod shacl_error;
 pub mod shape;
+pub mod sparql_constraint;
 pub mod target;
 pub mod value;
 
@@ -0,0 +1,62 @@
+use itertools::Itertools;
+use srdf::{RDFNode, SHACLPath};
+use std::fmt::Display;
+
+/// Node expressions are used by rules to compute sets of nodes starting from
+/// a focus node.
+///
+/// https://www.w3.org/TR/shacl-af/#node-expressions
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeExpr {
+    /// `sh:this`, evaluates to the focus node
+    FocusNode,
+
+    /// IRIs and literals evaluate to themselves
+    Constant(RDFNode),
+
+    /// The values of the path starting from the nodes of `nodes`, which
+    /// defaults to the focus node
+    Path {
+        path: SHACLPath,
+        nodes: Option<Box<NodeExpr>>,
+    },
+
+    /// The nodes of `nodes` that conform to the shape
+    FilterShape {
+        shape: RDFNode,
+        nodes: Box<NodeExpr>,
+    },
+
+    Intersection(Vec<NodeExpr>),
+
+    Union(Vec<NodeExpr>),
+
+    /// The distinct nodes of the inner expression
+    Distinct(Box<NodeExpr>),
+
+    /// A literal with the number of nodes of the inner expression
+    Count(Box<NodeExpr>),
+
+    /// `true` if the inner expression has at least one node, `false` otherwise
+    Exists(Box<NodeExpr>),
+}
+
+impl Display for NodeExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeExpr::FocusNode => write!(f, "this"),
+            NodeExpr::Constant(node) => write!(f, "{node}"),
+            NodeExpr::Path { path, nodes: None } => write!(f, "path({path})"),
+            NodeExpr::Path {
+                path,
+                nodes: Some(nodes),
+            } => write!(f, "path({path}, {nodes})"),
+            NodeExpr::FilterShape { shape, nodes } => write!(f, "filter({shape}, {nodes})"),
+            NodeExpr::Intersection(exprs) => write!(f, "intersection({})", exprs.iter().join(", ")),
+            NodeExpr::Union(exprs) => write!(f, "union({})", exprs.iter().join(", ")),
+            NodeExpr::Distinct(expr) => write!(f, "distinct({expr})"),
+            NodeExpr::Count(expr) => write!(f, "count({expr})"),
+            NodeExpr::Exists(expr) => write!(f, "exists({expr})"),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
-    component::Component, message_map::MessageMap, severity::Severity, target::Target,
-    SH_CLOSED_STR, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_NAME_STR,
-    SH_NODE_SHAPE, SH_PROPERTY_STR, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR,
+    component::Component, message_map::MessageMap, rule::Rule, severity::Severity, target::Target,
+    SH_CLOSED_STR, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR,
+    SH_MESSAGE_STR, SH_NAME_STR, SH_NODE_SHAPE, SH_PROPERTY_STR, SH_SEVERITY_STR, SH_VIOLATION_STR,
+    SH_WARNING_STR,
 };
 use iri_s::iri;
 use oxrdf::{Literal as OxLiteral, Term as OxTerm};
@@ -14,10 +15,11 @@ pub struct NodeShape {
     components: Vec<Component>,
     targets: Vec<Target>,
     property_shapes: Vec<RDFNode>,
+    rules: Vec<Rule>,
     closed: bool,
     // ignored_properties: Vec<IriRef>,
     deactivated: bool,
-    // message: MessageMap,
+    message: MessageMap,
     severity: Option<Severity>,
     name: MessageMap,
     description: MessageMap,
@@ -32,10 +34,11 @@ impl NodeShape {
             components: Vec::new(),
             targets: Vec::new(),
             property_shapes: Vec::new(),
+            rules: Vec::new(),
             closed: false,
             // ignored_properties: Vec::new(),
             deactivated: false,
-            // message: MessageMap::new(),
+            message: MessageMap::new(),
             severity: None,
             name: MessageMap::new(),
             description: MessageMap::new(),
@@ -63,11 +66,26 @@ impl NodeShape {
         self
     }
 
+    pub fn with_rules(mut self, rules: Vec<Rule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
     pub fn with_closed(mut self, closed: bool) -> Self {
         self.closed = closed;
         self
     }
 
+    pub fn with_message(mut self, message: MessageMap) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Option<Severity>) -> Self {
+        self.severity = severity;
+        self
+    }
+
     pub fn id(&self) -> &RDFNode {
         &self.id
     }
@@ -80,6 +98,10 @@ impl NodeShape {
         &self.deactivated
     }
 
+    pub fn message(&self) -> &MessageMap {
+        &self.message
+    }
+
     pub fn severity(&self) -> Option<Severity> {
         self.severity.to_owned()
     }
@@ -96,6 +118,10 @@ impl NodeShape {
         &self.property_shapes
     }
 
+    pub fn rules(&self) -> &Vec<Rule> {
+        &self.rules
+    }
+
     pub fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
     where
         RDF: SRDFBuilder,
@@ -118,6 +144,14 @@ impl NodeShape {
             )
         })?;
 
+        self.message.to_term_iter().try_for_each(|term| {
+            rdf.add_triple(
+                &RDF::object_as_subject(&self.id).unwrap(),
+                &RDF::iri_s2iri(&iri!(SH_MESSAGE_STR)),
+                &RDF::term_s2term(&term),
+            )
+        })?;
+
         self.components
             .iter()
             .try_for_each(|component| component.write(&self.id, rdf))?;
@@ -196,6 +230,9 @@ impl Display for NodeShape {
         for component in self.components.iter() {
             writeln!(f, "       {component}")?
         }
+        for rule in self.rules.iter() {
+            writeln!(f, "       Rule {rule}")?
+        }
         write!(f, "}}")?;
         Ok(())
     }
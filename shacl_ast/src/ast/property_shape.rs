@@ -8,8 +8,10 @@ use std::{collections::HashSet, fmt::Display};
 
 use crate::{
     component::Component, message_map::MessageMap, severity::Severity, target::Target,
-    SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_NAME_STR, SH_ORDER_STR,
+    SH_ALTERNATIVE_PATH_STR, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR,
+    SH_INVERSE_PATH_STR, SH_MESSAGE_STR, SH_NAME_STR, SH_ONE_OR_MORE_PATH_STR, SH_ORDER_STR,
     SH_PATH_STR, SH_PROPERTY_SHAPE, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR,
+    SH_ZERO_OR_MORE_PATH_STR, SH_ZERO_OR_ONE_PATH_STR,
 };
 
 #[derive(Debug, Clone)]
@@ -22,7 +24,7 @@ pub struct PropertyShape {
     closed: bool,
     // ignored_properties: Vec<IriRef>,
     deactivated: bool,
-    // message: MessageMap,
+    message: MessageMap,
     severity: Option<Severity>,
     name: MessageMap,
     description: MessageMap,
@@ -43,7 +45,7 @@ impl PropertyShape {
             closed: false,
             // ignored_properties: Vec::new(),
             deactivated: false,
-            // message: MessageMap::new(),
+            message: MessageMap::new(),
             severity: None,
             name: MessageMap::new(),
             description: MessageMap::new(),
@@ -93,6 +95,11 @@ impl PropertyShape {
         self
     }
 
+    pub fn with_message(mut self, message: MessageMap) -> Self {
+        self.message = message;
+        self
+    }
+
     pub fn with_severity(mut self, severity: Option<Severity>) -> Self {
         self.severity = severity;
         self
@@ -122,6 +129,10 @@ impl PropertyShape {
         &self.deactivated
     }
 
+    pub fn message(&self) -> &MessageMap {
+        &self.message
+    }
+
     pub fn severity(&self) -> Option<Severity> {
         self.severity.to_owned()
     }
@@ -196,6 +207,14 @@ impl PropertyShape {
             )
         })?;
 
+        self.message.to_term_iter().try_for_each(|term| {
+            rdf.add_triple(
+                &RDF::object_as_subject(&self.id).unwrap(),
+                &RDF::iri_s2iri(&iri!(SH_MESSAGE_STR)),
+                &RDF::term_s2term(&term),
+            )
+        })?;
+
         if let Some(order) = &self.order {
             let decimal_type = NamedNode::new(XSD_DECIMAL_STR).unwrap();
 
@@ -219,15 +238,12 @@ impl PropertyShape {
             )?;
         }
 
-        if let SHACLPath::Predicate { pred } = &self.path {
-            rdf.add_triple(
-                &RDF::object_as_subject(&self.id).unwrap(),
-                &RDF::iri_s2iri(&iri!(SH_PATH_STR)),
-                &RDF::iri_s2term(pred),
-            )?;
-        } else {
-            unimplemented!()
-        }
+        let path = write_path(&self.path, rdf)?;
+        rdf.add_triple(
+            &RDF::object_as_subject(&self.id).unwrap(),
+            &RDF::iri_s2iri(&iri!(SH_PATH_STR)),
+            &path,
+        )?;
 
         self.components
             .iter()
@@ -266,6 +282,39 @@ impl PropertyShape {
     }
 }
 
+/// Writes a property path and returns the node that represents it
+///
+/// https://www.w3.org/TR/shacl/#property-paths
+pub fn write_path<RDF>(path: &SHACLPath, rdf: &mut RDF) -> Result<RDF::Term, RDF::Err>
+where
+    RDF: SRDFBuilder,
+{
+    let (predicate, path) = match path {
+        SHACLPath::Predicate { pred } => return Ok(RDF::iri_s2term(pred)),
+        SHACLPath::Sequence { paths } => {
+            let paths = paths
+                .iter()
+                .map(|path| write_path(path, rdf))
+                .collect::<Result<Vec<_>, _>>()?;
+            return rdf.add_list(&paths);
+        }
+        SHACLPath::Alternative { paths } => {
+            let paths = paths
+                .iter()
+                .map(|path| write_path(path, rdf))
+                .collect::<Result<Vec<_>, _>>()?;
+            (SH_ALTERNATIVE_PATH_STR, rdf.add_list(&paths)?)
+        }
+        SHACLPath::Inverse { path } => (SH_INVERSE_PATH_STR, write_path(path, rdf)?),
+        SHACLPath::ZeroOrMore { path } => (SH_ZERO_OR_MORE_PATH_STR, write_path(path, rdf)?),
+        SHACLPath::OneOrMore { path } => (SH_ONE_OR_MORE_PATH_STR, write_path(path, rdf)?),
+        SHACLPath::ZeroOrOne { path } => (SH_ZERO_OR_ONE_PATH_STR, write_path(path, rdf)?),
+    };
+    let node = RDF::bnode_as_subject(rdf.add_bnode()?);
+    rdf.add_triple(&node, &RDF::iri_s2iri(&iri!(predicate)), &path)?;
+    Ok(RDF::subject_as_term(&node))
+}
+
 impl Display for PropertyShape {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "{{")?;
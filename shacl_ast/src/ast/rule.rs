@@ -0,0 +1,123 @@
+use crate::{
+    node_expr::NodeExpr,
+    sparql_constraint::{with_prologue, PrefixDeclaration},
+};
+use iri_s::IriS;
+use srdf::RDFNode;
+use std::fmt::Display;
+
+/// SHACL rule, declared in a shape through `sh:rule`. Rules are applied to
+/// the focus nodes of the shape to infer new triples.
+///
+/// https://www.w3.org/TR/shacl-af/#rules
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    id: RDFNode,
+    kind: RuleKind,
+    order: Option<f64>,
+    conditions: Vec<RDFNode>,
+    deactivated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleKind {
+    /// `sh:TripleRule`, infers a triple for each combination of the nodes of
+    /// the subject and object expressions
+    ///
+    /// https://www.w3.org/TR/shacl-af/#TripleRule
+    Triple {
+        subject: NodeExpr,
+        predicate: IriS,
+        object: NodeExpr,
+    },
+
+    /// `sh:SPARQLRule`, infers the triples produced by the CONSTRUCT query
+    /// after pre-binding `$this` to the focus node
+    ///
+    /// https://www.w3.org/TR/shacl-af/#SPARQLRule
+    Sparql {
+        construct: String,
+        prefixes: Vec<PrefixDeclaration>,
+    },
+}
+
+impl Rule {
+    pub fn new(id: RDFNode, kind: RuleKind) -> Self {
+        Rule {
+            id,
+            kind,
+            order: None,
+            conditions: Vec::new(),
+            deactivated: false,
+        }
+    }
+
+    pub fn with_order(mut self, order: Option<f64>) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn with_conditions(mut self, conditions: Vec<RDFNode>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    pub fn with_deactivated(mut self, deactivated: bool) -> Self {
+        self.deactivated = deactivated;
+        self
+    }
+
+    pub fn id(&self) -> &RDFNode {
+        &self.id
+    }
+
+    pub fn kind(&self) -> &RuleKind {
+        &self.kind
+    }
+
+    /// Rules are executed in ascending order, rules without `sh:order` are
+    /// executed as if they had order 0
+    pub fn order(&self) -> f64 {
+        self.order.unwrap_or_default()
+    }
+
+    /// Shapes that the focus nodes have to conform to for the rule to be
+    /// applied
+    pub fn conditions(&self) -> &Vec<RDFNode> {
+        &self.conditions
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated
+    }
+}
+
+impl RuleKind {
+    /// The CONSTRUCT query including the declared prefixes
+    pub fn construct_query(&self) -> Option<String> {
+        match self {
+            RuleKind::Triple { .. } => None,
+            RuleKind::Sparql {
+                construct,
+                prefixes,
+            } => Some(with_prologue(construct, prefixes)),
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            RuleKind::Triple {
+                subject,
+                predicate,
+                object,
+            } => write!(f, "TripleRule({subject} {predicate} {object})")?,
+            RuleKind::Sparql { .. } => write!(f, "SPARQLRule({})", self.id)?,
+        }
+        if let Some(order) = self.order {
+            write!(f, " order {order}")?;
+        }
+        Ok(())
+    }
+}
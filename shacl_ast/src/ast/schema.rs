@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fmt::Display};
 
 use crate::shape::Shape;
+use crate::sparql_constraint::ConstraintComponent;
 use iri_s::IriS;
 use prefixmap::PrefixMap;
 use srdf::RDFNode;
@@ -10,6 +11,7 @@ pub struct Schema {
     // imports: Vec<IriS>,
     // entailments: Vec<IriS>,
     shapes: HashMap<RDFNode, Shape>,
+    components: HashMap<IriS, ConstraintComponent>,
     prefixmap: PrefixMap,
     base: Option<IriS>,
 }
@@ -29,6 +31,11 @@ impl Schema {
         self
     }
 
+    pub fn with_components(mut self, components: HashMap<IriS, ConstraintComponent>) -> Self {
+        self.components = components;
+        self
+    }
+
     pub fn prefix_map(&self) -> PrefixMap {
         self.prefixmap.clone()
     }
@@ -44,6 +51,14 @@ impl Schema {
     pub fn get_shape(&self, sref: &RDFNode) -> Option<&Shape> {
         self.shapes.get(sref)
     }
+
+    pub fn components(&self) -> impl Iterator<Item = (&IriS, &ConstraintComponent)> {
+        self.components.iter()
+    }
+
+    pub fn get_component(&self, iri: &IriS) -> Option<&ConstraintComponent> {
+        self.components.get(iri)
+    }
 }
 
 impl Display for Schema {
@@ -51,6 +66,9 @@ impl Display for Schema {
         for (id, shape) in self.shapes.iter() {
             writeln!(f, "{id} -> {shape}")?;
         }
+        for component in self.components.values() {
+            writeln!(f, "{component}")?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,411 @@
+use crate::{
+    SH_ASK_STR, SH_CONSTRAINT_COMPONENT, SH_DEACTIVATED_STR, SH_MESSAGE_STR, SH_NODE_VALIDATOR_STR,
+    SH_OPTIONAL_STR, SH_PARAMETER_STR, SH_PATH_STR, SH_PROPERTY_VALIDATOR_STR, SH_SELECT_STR,
+    SH_SPARQL_ASK_VALIDATOR, SH_SPARQL_CONSTRAINT, SH_SPARQL_SELECT_VALIDATOR, SH_VALIDATOR_STR,
+};
+use iri_s::{iri, IriS};
+use itertools::Itertools;
+use srdf::{literal::Literal, RDFNode, SRDFBuilder};
+use std::fmt::Display;
+
+/// Prefix declared through `sh:declare`, which is added to the prologue of the
+/// SPARQL queries that reference it with `sh:prefixes`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PrefixDeclaration {
+    prefix: String,
+    namespace: IriS,
+}
+
+impl PrefixDeclaration {
+    pub fn new(prefix: &str, namespace: IriS) -> Self {
+        PrefixDeclaration {
+            prefix: prefix.to_string(),
+            namespace,
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn namespace(&self) -> &IriS {
+        &self.namespace
+    }
+}
+
+impl Display for PrefixDeclaration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PREFIX {}: <{}>", self.prefix, self.namespace.as_str())
+    }
+}
+
+/// Returns the query preceded by the PREFIX declarations it depends on
+pub(crate) fn with_prologue(query: &str, prefixes: &[PrefixDeclaration]) -> String {
+    prefixes
+        .iter()
+        .map(|prefix| prefix.to_string())
+        .chain(std::iter::once(query.to_string()))
+        .join("\n")
+}
+
+/// SPARQL-based constraint, declared in a shape through `sh:sparql`.
+///
+/// - IRI: https://www.w3.org/TR/shacl/#SPARQLConstraintComponent
+/// - DEF: There is a validation result for each solution of the SELECT query,
+///   after pre-binding `$this` to each focus node.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SparqlConstraint {
+    id: RDFNode,
+    select: String,
+    prefixes: Vec<PrefixDeclaration>,
+    messages: Vec<Literal>,
+    deactivated: bool,
+}
+
+impl SparqlConstraint {
+    pub fn new(id: RDFNode, select: String) -> Self {
+        SparqlConstraint {
+            id,
+            select,
+            prefixes: Vec::new(),
+            messages: Vec::new(),
+            deactivated: false,
+        }
+    }
+
+    pub fn with_prefixes(mut self, prefixes: Vec<PrefixDeclaration>) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    pub fn with_messages(mut self, messages: Vec<Literal>) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub fn with_deactivated(mut self, deactivated: bool) -> Self {
+        self.deactivated = deactivated;
+        self
+    }
+
+    pub fn id(&self) -> &RDFNode {
+        &self.id
+    }
+
+    pub fn select(&self) -> &String {
+        &self.select
+    }
+
+    pub fn prefixes(&self) -> &Vec<PrefixDeclaration> {
+        &self.prefixes
+    }
+
+    pub fn messages(&self) -> &Vec<Literal> {
+        &self.messages
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated
+    }
+
+    /// The SELECT query including the declared prefixes
+    pub fn query(&self) -> String {
+        with_prologue(&self.select, &self.prefixes)
+    }
+
+    pub fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        rdf.add_type(&self.id, RDF::iri_s2term(&SH_SPARQL_CONSTRAINT))?;
+
+        // the prefixes are written as part of the query, which avoids having
+        // to generate new nodes for the sh:declare values
+        write_literal(&self.id, SH_SELECT_STR, &Literal::str(&self.query()), rdf)?;
+
+        self.messages
+            .iter()
+            .try_for_each(|message| write_literal(&self.id, SH_MESSAGE_STR, message, rdf))?;
+
+        if self.deactivated {
+            write_literal(&self.id, SH_DEACTIVATED_STR, &Literal::boolean(true), rdf)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SparqlConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sparql({})", self.id)
+    }
+}
+
+/// Declaration of a SPARQL-based constraint component in the shapes graph,
+/// that is, an instance of `sh:ConstraintComponent`.
+///
+/// - IRI: https://www.w3.org/TR/shacl/#sparql-constraint-components
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ConstraintComponent {
+    id: IriS,
+    parameters: Vec<Parameter>,
+    validator: Option<ComponentValidator>,
+    node_validator: Option<ComponentValidator>,
+    property_validator: Option<ComponentValidator>,
+}
+
+impl ConstraintComponent {
+    pub fn new(id: IriS) -> Self {
+        ConstraintComponent {
+            id,
+            parameters: Vec::new(),
+            validator: None,
+            node_validator: None,
+            property_validator: None,
+        }
+    }
+
+    pub fn with_parameters(mut self, parameters: Vec<Parameter>) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    pub fn with_validator(mut self, validator: Option<ComponentValidator>) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    pub fn with_node_validator(mut self, validator: Option<ComponentValidator>) -> Self {
+        self.node_validator = validator;
+        self
+    }
+
+    pub fn with_property_validator(mut self, validator: Option<ComponentValidator>) -> Self {
+        self.property_validator = validator;
+        self
+    }
+
+    pub fn id(&self) -> &IriS {
+        &self.id
+    }
+
+    pub fn parameters(&self) -> &Vec<Parameter> {
+        &self.parameters
+    }
+
+    /// Parameters that a shape must declare for the component to be applied
+    pub fn mandatory_parameters(&self) -> impl Iterator<Item = &Parameter> {
+        self.parameters.iter().filter(|p| !p.is_optional())
+    }
+
+    pub fn validator(&self) -> &Option<ComponentValidator> {
+        &self.validator
+    }
+
+    pub fn node_validator(&self) -> &Option<ComponentValidator> {
+        &self.node_validator
+    }
+
+    pub fn property_validator(&self) -> &Option<ComponentValidator> {
+        &self.property_validator
+    }
+
+    pub fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        let id = RDFNode::iri(self.id.clone());
+        rdf.add_type(&id, RDF::iri_s2term(&SH_CONSTRAINT_COMPONENT))?;
+
+        self.parameters.iter().try_for_each(|parameter| {
+            write_node(&id, SH_PARAMETER_STR, parameter.id(), rdf)?;
+            parameter.write(rdf)
+        })?;
+
+        let validators = [
+            (SH_VALIDATOR_STR, &self.validator),
+            (SH_NODE_VALIDATOR_STR, &self.node_validator),
+            (SH_PROPERTY_VALIDATOR_STR, &self.property_validator),
+        ];
+
+        for (predicate, validator) in validators {
+            if let Some(validator) = validator {
+                write_node(&id, predicate, validator.id(), rdf)?;
+                validator.write(rdf)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ConstraintComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parameters = self.parameters.iter().map(|p| p.to_string()).join(" ");
+        write!(f, "component({} [{parameters}])", self.id)
+    }
+}
+
+/// Parameter of a constraint component. Its local name is the name of the
+/// variable that is pre-bound in the validators of the component.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Parameter {
+    id: RDFNode,
+    path: IriS,
+    optional: bool,
+}
+
+impl Parameter {
+    pub fn new(id: RDFNode, path: IriS, optional: bool) -> Self {
+        Parameter { id, path, optional }
+    }
+
+    pub fn id(&self) -> &RDFNode {
+        &self.id
+    }
+
+    pub fn path(&self) -> &IriS {
+        &self.path
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Name of the variable that represents the parameter in the validators
+    pub fn var_name(&self) -> String {
+        let iri = self.path.as_str();
+        match iri.rfind(['#', '/', ':']) {
+            Some(idx) => iri[idx + 1..].to_string(),
+            None => iri.to_string(),
+        }
+    }
+
+    fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        write_node(&self.id, SH_PATH_STR, &RDFNode::iri(self.path.clone()), rdf)?;
+        if self.optional {
+            write_literal(&self.id, SH_OPTIONAL_STR, &Literal::boolean(true), rdf)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.optional {
+            true => write!(f, "{}?", self.path),
+            false => write!(f, "{}", self.path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ValidatorKind {
+    /// `sh:SPARQLAskValidator`, there is a validation result for each value
+    /// node for which the ASK query returns false
+    Ask,
+    /// `sh:SPARQLSelectValidator`, there is a validation result for each
+    /// solution of the SELECT query
+    Select,
+}
+
+/// SPARQL-based validator of a constraint component
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ComponentValidator {
+    id: RDFNode,
+    kind: ValidatorKind,
+    query: String,
+    prefixes: Vec<PrefixDeclaration>,
+    messages: Vec<Literal>,
+}
+
+impl ComponentValidator {
+    pub fn new(id: RDFNode, kind: ValidatorKind, query: String) -> Self {
+        ComponentValidator {
+            id,
+            kind,
+            query,
+            prefixes: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn with_prefixes(mut self, prefixes: Vec<PrefixDeclaration>) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    pub fn with_messages(mut self, messages: Vec<Literal>) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub fn id(&self) -> &RDFNode {
+        &self.id
+    }
+
+    pub fn kind(&self) -> ValidatorKind {
+        self.kind
+    }
+
+    pub fn prefixes(&self) -> &Vec<PrefixDeclaration> {
+        &self.prefixes
+    }
+
+    pub fn messages(&self) -> &Vec<Literal> {
+        &self.messages
+    }
+
+    /// The ASK or SELECT query including the declared prefixes
+    pub fn query(&self) -> String {
+        with_prologue(&self.query, &self.prefixes)
+    }
+
+    fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
+    where
+        RDF: SRDFBuilder,
+    {
+        let (class, predicate) = match self.kind {
+            ValidatorKind::Ask => (&*SH_SPARQL_ASK_VALIDATOR, SH_ASK_STR),
+            ValidatorKind::Select => (&*SH_SPARQL_SELECT_VALIDATOR, SH_SELECT_STR),
+        };
+
+        rdf.add_type(&self.id, RDF::iri_s2term(class))?;
+        write_literal(&self.id, predicate, &Literal::str(&self.query()), rdf)?;
+
+        self.messages
+            .iter()
+            .try_for_each(|message| write_literal(&self.id, SH_MESSAGE_STR, message, rdf))
+    }
+}
+
+fn write_literal<RDF>(
+    subject: &RDFNode,
+    predicate: &str,
+    value: &Literal,
+    rdf: &mut RDF,
+) -> Result<(), RDF::Err>
+where
+    RDF: SRDFBuilder,
+{
+    write_node(subject, predicate, &RDFNode::literal(value.clone()), rdf)
+}
+
+fn write_node<RDF>(
+    subject: &RDFNode,
+    predicate: &str,
+    value: &RDFNode,
+    rdf: &mut RDF,
+) -> Result<(), RDF::Err>
+where
+    RDF: SRDFBuilder,
+{
+    rdf.add_node_triple(
+        subject,
+        &RDF::iri_s2iri(&iri!(predicate)),
+        &RDF::object_as_term(value),
+    )
+}
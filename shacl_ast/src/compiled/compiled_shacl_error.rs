@@ -1,3 +1,4 @@
+use iri_s::IriS;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,4 +9,6 @@ pub enum CompiledShaclError {
     ShapeNotFound,
     #[error("Could not convert to Literal")]
     LiteralConversion,
+    #[error("Could not find the definition of the constraint component {0}")]
+    ComponentNotFound(IriS),
 }
@@ -12,6 +12,8 @@ use super::shape::CompiledShape;
 use iri_s::iri;
 use iri_s::IriS;
 use node_kind::NodeKind;
+use sparql_constraint::ComponentValidator;
+use srdf::literal::Literal;
 use srdf::RDFNode;
 use srdf::SRDFBasic;
 
@@ -43,6 +45,8 @@ pub enum CompiledComponent<S: SRDFBasic> {
     HasValue(HasValue<S>),
     In(In<S>),
     QualifiedValueShape(QualifiedValueShape<S>),
+    Sparql(Sparql),
+    SparqlComponent(SparqlComponent<S>),
 }
 
 impl<S: SRDFBasic> CompiledComponent<S> {
@@ -156,10 +160,112 @@ impl<S: SRDFBasic> CompiledComponent<S> {
                     qualified_value_shapes_disjoint,
                 ))
             }
+            Component::Sparql(constraint) => CompiledComponent::Sparql(Sparql::new(
+                constraint.query(),
+                constraint.messages().clone(),
+                constraint.is_deactivated(),
+            )),
+            Component::SparqlComponent {
+                component,
+                parameters,
+            } => {
+                let definition = match schema.get_component(&component) {
+                    Some(definition) => definition,
+                    None => return Err(CompiledShaclError::ComponentNotFound(component)),
+                };
+                let parameters = parameters
+                    .into_iter()
+                    .flat_map(|(path, value)| {
+                        definition
+                            .parameters()
+                            .iter()
+                            .find(|parameter| *parameter.path() == path)
+                            .map(|parameter| (parameter.var_name(), S::object_as_term(&value)))
+                    })
+                    .collect();
+                CompiledComponent::SparqlComponent(SparqlComponent::new(
+                    component,
+                    parameters,
+                    definition.validator().clone(),
+                    definition.node_validator().clone(),
+                    definition.property_validator().clone(),
+                ))
+            }
         };
 
         Ok(component)
     }
+
+    /// The parameters of the component with a single value, by their local
+    /// name, that can be referenced as `{$name}` in the messages
+    pub fn parameters(&self) -> Vec<(String, S::Term)> {
+        let integer = |n: isize| S::object_as_term(&RDFNode::literal(Literal::integer(n)));
+        let parameters = match self {
+            CompiledComponent::Class(c) => vec![("class", c.class_rule().clone())],
+            CompiledComponent::Datatype(d) => {
+                vec![("datatype", S::iri_as_term(d.datatype().clone()))]
+            }
+            CompiledComponent::NodeKind(n) => {
+                let node_kind = match n.node_kind() {
+                    NodeKind::Iri => iri!(SH_IRI_STR),
+                    NodeKind::Literal => iri!(SH_LITERAL_STR),
+                    NodeKind::BlankNode => iri!(SH_BLANKNODE_STR),
+                    NodeKind::BlankNodeOrIri => iri!(SH_BLANK_NODE_OR_IRI_STR),
+                    NodeKind::BlankNodeOrLiteral => iri!(SH_BLANK_NODE_OR_LITERAL_STR),
+                    NodeKind::IRIOrLiteral => iri!(SH_IRI_OR_LITERAL_STR),
+                };
+                vec![("nodeKind", S::iri_s2term(&node_kind))]
+            }
+            CompiledComponent::MinCount(c) => {
+                vec![("minCount", integer(c.min_count() as isize))]
+            }
+            CompiledComponent::MaxCount(c) => {
+                vec![("maxCount", integer(c.max_count() as isize))]
+            }
+            CompiledComponent::MinExclusive(c) => {
+                vec![("minExclusive", c.min_exclusive().clone())]
+            }
+            CompiledComponent::MaxExclusive(c) => {
+                vec![("maxExclusive", c.max_exclusive().clone())]
+            }
+            CompiledComponent::MinInclusive(c) => {
+                vec![("minInclusive", c.min_inclusive().clone())]
+            }
+            CompiledComponent::MaxInclusive(c) => {
+                vec![("maxInclusive", c.max_inclusive().clone())]
+            }
+            CompiledComponent::MinLength(c) => vec![("minLength", integer(c.min_length()))],
+            CompiledComponent::MaxLength(c) => vec![("maxLength", integer(c.max_length()))],
+            CompiledComponent::Pattern(p) => {
+                let mut parameters = vec![(
+                    "pattern",
+                    S::object_as_term(&RDFNode::literal(Literal::str(p.pattern()))),
+                )];
+                if let Some(flags) = p.flags() {
+                    let flags = S::object_as_term(&RDFNode::literal(Literal::str(flags)));
+                    parameters.push(("flags", flags));
+                }
+                parameters
+            }
+            CompiledComponent::Equals(c) => vec![("equals", S::iri_as_term(c.iri_ref().clone()))],
+            CompiledComponent::Disjoint(c) => {
+                vec![("disjoint", S::iri_as_term(c.iri_ref().clone()))]
+            }
+            CompiledComponent::LessThan(c) => {
+                vec![("lessThan", S::iri_as_term(c.iri_ref().clone()))]
+            }
+            CompiledComponent::LessThanOrEquals(c) => {
+                vec![("lessThanOrEquals", S::iri_as_term(c.iri_ref().clone()))]
+            }
+            CompiledComponent::HasValue(c) => vec![("hasValue", c.value().clone())],
+            CompiledComponent::SparqlComponent(c) => return c.parameters().clone(),
+            _ => Vec::new(),
+        };
+        parameters
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
 }
 
 /// sh:maxCount specifies the maximum number of value nodes that satisfy the
@@ -459,6 +565,7 @@ pub struct QualifiedValueShape<S: SRDFBasic> {
     qualified_min_count: Option<isize>,
     qualified_max_count: Option<isize>,
     qualified_value_shapes_disjoint: Option<bool>,
+    siblings: Vec<CompiledShape<S>>,
 }
 
 impl<S: SRDFBasic> QualifiedValueShape<S> {
@@ -473,13 +580,29 @@ impl<S: SRDFBasic> QualifiedValueShape<S> {
             qualified_min_count,
             qualified_max_count,
             qualified_value_shapes_disjoint,
+            siblings: Vec::new(),
         }
     }
 
+    /// Sets the sibling shapes, which are only checked when
+    /// sh:qualifiedValueShapesDisjoint is true
+    pub fn with_siblings(mut self, siblings: Vec<CompiledShape<S>>) -> Self {
+        self.siblings = siblings;
+        self
+    }
+
     pub fn shape(&self) -> &CompiledShape<S> {
         &self.shape
     }
 
+    /// The qualified value shapes of the other property shapes of the shapes
+    /// that have this property shape as a value of sh:property
+    ///
+    /// https://www.w3.org/TR/shacl/#dfn-sibling-shapes
+    pub fn siblings(&self) -> &Vec<CompiledShape<S>> {
+        &self.siblings
+    }
+
     pub fn qualified_min_count(&self) -> Option<isize> {
         self.qualified_min_count
     }
@@ -493,6 +616,92 @@ impl<S: SRDFBasic> QualifiedValueShape<S> {
     }
 }
 
+/// SPARQL-based constraint, declared through sh:sparql.
+///
+/// - IRI: https://www.w3.org/TR/shacl/#SPARQLConstraintComponent
+/// - DEF: There is a validation result for each solution of the SELECT query,
+///   after pre-binding $this to each focus node and replacing $PATH with the
+///   path of the property shape.
+pub struct Sparql {
+    query: String,
+    messages: Vec<Literal>,
+    deactivated: bool,
+}
+
+impl Sparql {
+    pub fn new(query: String, messages: Vec<Literal>, deactivated: bool) -> Self {
+        Sparql {
+            query,
+            messages,
+            deactivated,
+        }
+    }
+
+    pub fn query(&self) -> &String {
+        &self.query
+    }
+
+    pub fn messages(&self) -> &Vec<Literal> {
+        &self.messages
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated
+    }
+}
+
+/// Use of a SPARQL-based constraint component in a shape, with the values of
+/// the parameters indexed by the name of the variable they pre-bind.
+///
+/// https://www.w3.org/TR/shacl/#sparql-constraint-components
+pub struct SparqlComponent<S: SRDFBasic> {
+    component: IriS,
+    parameters: Vec<(String, S::Term)>,
+    validator: Option<Box<ComponentValidator>>,
+    node_validator: Option<Box<ComponentValidator>>,
+    property_validator: Option<Box<ComponentValidator>>,
+}
+
+impl<S: SRDFBasic> SparqlComponent<S> {
+    pub fn new(
+        component: IriS,
+        parameters: Vec<(String, S::Term)>,
+        validator: Option<ComponentValidator>,
+        node_validator: Option<ComponentValidator>,
+        property_validator: Option<ComponentValidator>,
+    ) -> Self {
+        SparqlComponent {
+            component,
+            parameters,
+            validator: validator.map(Box::new),
+            node_validator: node_validator.map(Box::new),
+            property_validator: property_validator.map(Box::new),
+        }
+    }
+
+    pub fn component(&self) -> &IriS {
+        &self.component
+    }
+
+    pub fn parameters(&self) -> &Vec<(String, S::Term)> {
+        &self.parameters
+    }
+
+    /// The validator to be used for node shapes. sh:nodeValidator takes
+    /// precedence over sh:validator
+    pub fn node_validator(&self) -> Option<&ComponentValidator> {
+        self.node_validator.as_deref().or(self.validator.as_deref())
+    }
+
+    /// The validator to be used for property shapes. sh:propertyValidator
+    /// takes precedence over sh:validator
+    pub fn property_validator(&self) -> Option<&ComponentValidator> {
+        self.property_validator
+            .as_deref()
+            .or(self.validator.as_deref())
+    }
+}
+
 /// The condition specified by sh:languageIn is that the allowed language tags
 /// for each value node are limited by a given list of language tags.
 ///
@@ -746,6 +955,8 @@ impl<S: SRDFBasic> From<&CompiledComponent<S>> for IriS {
             CompiledComponent::QualifiedValueShape { .. } => {
                 iri!(SH_QUALIFIED_VALUE_SHAPE_STR)
             }
+            CompiledComponent::Sparql(_) => iri!(SH_SPARQL_CONSTRAINT_COMPONENT_STR),
+            CompiledComponent::SparqlComponent(component) => component.component().clone(),
         }
     }
 }
@@ -7,6 +7,7 @@ use srdf::Object;
 use srdf::RDFNode;
 use srdf::SRDFBasic;
 
+use crate::message_map::MessageMap;
 use crate::value::Value;
 use crate::Schema;
 
@@ -32,6 +33,24 @@ fn convert_lang<S: SRDFBasic>(lang: Lang) -> Result<S::Literal, CompiledShaclErr
     S::term_as_literal(&term).ok_or(CompiledShaclError::LiteralConversion)
 }
 
+/// The messages as literals, sorted by their language tag so that the results
+/// are reported in a stable order
+fn convert_messages(messages: &MessageMap) -> Vec<Literal> {
+    let mut messages = messages
+        .messages()
+        .iter()
+        .map(|(lang, message)| match lang {
+            Some(lang) => Literal::lang_str(message, lang.clone()),
+            None => Literal::str(message),
+        })
+        .collect::<Vec<_>>();
+    messages.sort_by_key(|literal| match literal {
+        Literal::StringLiteral { lang, .. } => lang.as_ref().map(|lang| lang.value()),
+        _ => None,
+    });
+    messages
+}
+
 fn compile_shape<S: SRDFBasic>(
     shape: Object,
     schema: &Schema,
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use srdf::literal::Literal;
 use srdf::SRDFBasic;
 
 use crate::node_shape::NodeShape;
@@ -8,6 +9,7 @@ use crate::Schema;
 use super::compile_shape;
 use super::compiled_shacl_error::CompiledShaclError;
 use super::component::CompiledComponent;
+use super::convert_messages;
 use super::severity::CompiledSeverity;
 use super::shape::CompiledShape;
 use super::target::CompiledTarget;
@@ -20,7 +22,7 @@ pub struct CompiledNodeShape<S: SRDFBasic> {
     closed: bool,
     // ignored_properties: Vec<S::IRI>,
     deactivated: bool,
-    // message: MessageMap,
+    messages: Vec<Literal>,
     severity: Option<CompiledSeverity<S>>,
     // name: MessageMap,
     // description: MessageMap,
@@ -29,6 +31,7 @@ pub struct CompiledNodeShape<S: SRDFBasic> {
 }
 
 impl<S: SRDFBasic> CompiledNodeShape<S> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: S::Term,
         components: Vec<CompiledComponent<S>>,
@@ -36,6 +39,7 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
         property_shapes: Vec<CompiledShape<S>>,
         closed: bool,
         deactivated: bool,
+        messages: Vec<Literal>,
         severity: Option<CompiledSeverity<S>>,
     ) -> Self {
         CompiledNodeShape {
@@ -45,6 +49,7 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
             property_shapes,
             closed,
             deactivated,
+            messages,
             severity,
         }
     }
@@ -57,6 +62,10 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
         &self.deactivated
     }
 
+    pub fn messages(&self) -> &Vec<Literal> {
+        &self.messages
+    }
+
     pub fn severity(&self) -> &CompiledSeverity<S> {
         match &self.severity {
             Some(severity) => severity,
@@ -86,6 +95,7 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
         let id = S::object_as_term(shape.id());
         let closed = shape.is_closed().to_owned();
         let deactivated = shape.is_deactivated().to_owned();
+        let messages = convert_messages(shape.message());
         let severity = CompiledSeverity::compile(shape.severity())?;
 
         let components = shape.components().iter().collect::<HashSet<_>>();
@@ -114,6 +124,7 @@ impl<S: SRDFBasic> CompiledNodeShape<S> {
             property_shapes,
             closed,
             deactivated,
+            messages,
             severity,
         );
 
@@ -1,14 +1,20 @@
 use std::collections::HashSet;
 
+use srdf::literal::Literal;
+use srdf::RDFNode;
 use srdf::SHACLPath;
 use srdf::SRDFBasic;
 
+use crate::component::Component;
 use crate::property_shape::PropertyShape;
+use crate::shape::Shape;
 use crate::Schema;
 
 use super::compile_shape;
+use super::compile_shapes;
 use super::compiled_shacl_error::CompiledShaclError;
 use super::component::CompiledComponent;
+use super::convert_messages;
 use super::severity::CompiledSeverity;
 use super::shape::CompiledShape;
 use super::target::CompiledTarget;
@@ -22,7 +28,7 @@ pub struct CompiledPropertyShape<S: SRDFBasic> {
     closed: bool,
     // ignored_properties: Vec<S::IRI>,
     deactivated: bool,
-    // message: MessageMap,
+    messages: Vec<Literal>,
     severity: Option<CompiledSeverity<S>>,
     // name: MessageMap,
     // description: MessageMap,
@@ -42,6 +48,7 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
         property_shapes: Vec<CompiledShape<S>>,
         closed: bool,
         deactivated: bool,
+        messages: Vec<Literal>,
         severity: Option<CompiledSeverity<S>>,
     ) -> Self {
         CompiledPropertyShape {
@@ -52,6 +59,7 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
             property_shapes,
             closed,
             deactivated,
+            messages,
             severity,
         }
     }
@@ -72,6 +80,10 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
         &self.deactivated
     }
 
+    pub fn messages(&self) -> &Vec<Literal> {
+        &self.messages
+    }
+
     pub fn severity(&self) -> &CompiledSeverity<S> {
         match &self.severity {
             Some(severity) => severity,
@@ -98,12 +110,21 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
         let path = shape.path().to_owned();
         let closed = shape.is_closed().to_owned();
         let deactivated = shape.is_deactivated().to_owned();
+        let messages = convert_messages(shape.message());
         let severity = CompiledSeverity::compile(shape.severity())?;
 
         let components = shape.components().iter().collect::<HashSet<_>>();
         let mut compiled_components = Vec::new();
         for component in components {
-            let component = CompiledComponent::compile(component.to_owned(), schema)?;
+            let component = match CompiledComponent::compile(component.to_owned(), schema)? {
+                CompiledComponent::QualifiedValueShape(qvs)
+                    if qvs.qualified_value_shapes_disjoint() == Some(true) =>
+                {
+                    let siblings = compile_shapes::<S>(sibling_shapes(shape.id(), schema), schema)?;
+                    CompiledComponent::QualifiedValueShape(qvs.with_siblings(siblings))
+                }
+                component => component,
+            };
             compiled_components.push(component);
         }
 
@@ -127,9 +148,40 @@ impl<S: SRDFBasic> CompiledPropertyShape<S> {
             property_shapes,
             closed,
             deactivated,
+            messages,
             severity,
         );
 
         Ok(compiled_property_shape)
     }
 }
+
+/// The qualified value shapes of the sibling property shapes of `id`, that is,
+/// of the other property shapes of the shapes that have `id` as a value of
+/// sh:property
+///
+/// https://www.w3.org/TR/shacl/#dfn-sibling-shapes
+fn sibling_shapes(id: &RDFNode, schema: &Schema) -> Vec<RDFNode> {
+    let mut siblings = Vec::new();
+    for (_, parent) in schema.iter() {
+        let property_shapes = match parent {
+            Shape::NodeShape(ns) => ns.property_shapes(),
+            Shape::PropertyShape(ps) => ps.property_shapes(),
+        };
+        if !property_shapes.contains(id) {
+            continue;
+        }
+        for sibling in property_shapes.iter().filter(|sibling| *sibling != id) {
+            if let Some(Shape::PropertyShape(ps)) = schema.get_shape(sibling) {
+                for component in ps.components() {
+                    if let Component::QualifiedValueShape { shape, .. } = component {
+                        if !siblings.contains(shape) {
+                            siblings.push(shape.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    siblings
+}
@@ -44,6 +44,19 @@ impl<S: SRDFBasic> CompiledSchema<S> {
     pub fn get_shape(&self, sref: &S::Term) -> Option<&CompiledShape<S>> {
         self.shapes.get(sref)
     }
+
+    /// Checks if some property shape has a path that traverses triples in the
+    /// inverse direction
+    pub fn has_inverse_paths(&self) -> bool {
+        fn has_inverse<S: SRDFBasic>(shape: &CompiledShape<S>) -> bool {
+            let inverse = match shape {
+                CompiledShape::NodeShape(_) => false,
+                CompiledShape::PropertyShape(ps) => ps.path().has_inverse(),
+            };
+            inverse || shape.property_shapes().iter().any(has_inverse)
+        }
+        self.shapes.values().any(has_inverse)
+    }
 }
 
 impl<S: SRDFBasic> TryFrom<Schema> for CompiledSchema<S> {
@@ -1,3 +1,5 @@
+use srdf::literal::Literal;
+use srdf::SHACLPath;
 use srdf::SRDFBasic;
 
 use crate::shape::Shape;
@@ -50,10 +52,17 @@ impl<S: SRDFBasic> CompiledShape<S> {
         }
     }
 
-    pub fn path(&self) -> Option<S::Term> {
+    pub fn path(&self) -> Option<&SHACLPath> {
         match self {
             CompiledShape::NodeShape(_) => None,
-            CompiledShape::PropertyShape(_ps) => todo!(),
+            CompiledShape::PropertyShape(ps) => Some(ps.path()),
+        }
+    }
+
+    pub fn messages(&self) -> &Vec<Literal> {
+        match self {
+            CompiledShape::NodeShape(ns) => ns.messages(),
+            CompiledShape::PropertyShape(ps) => ps.messages(),
         }
     }
 
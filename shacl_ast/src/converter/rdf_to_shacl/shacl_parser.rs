@@ -1,17 +1,35 @@
+use iri_s::IriS;
 use prefixmap::{IriRef, PrefixMap};
 use srdf::{
-    combine_parsers, combine_vec, has_type, not, ok, optional, parse_nodes, property_bool,
-    property_value, property_values, property_values_int, property_values_iri,
-    property_values_non_empty, rdf_list, term, FocusRDF, Object, PResult, RDFNode, RDFNodeParse,
-    RDFParseError, RDFParser, SHACLPath, SRDFBasic, Triple, RDF_TYPE,
+    apply_rdf, combine_parsers, combine_vec, has_type, lang::Lang, literal::Literal, not, ok,
+    optional, parse_nodes, property_bool, property_integer, property_string, property_value,
+    property_values, property_values_int, property_values_iri, property_values_non_empty, rdf_list,
+    term, FocusRDF, Object, PResult, RDFNode, RDFNodeParse, RDFParseError, RDFParser, SHACLPath,
+    SRDFBasic, Triple, RDF_FIRST, RDF_TYPE,
 };
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    component::Component, node_kind::NodeKind, node_shape::NodeShape,
-    property_shape::PropertyShape, schema::Schema, shape::Shape, target::Target, value::Value, *,
+    component::Component,
+    message_map::MessageMap,
+    node_expr::NodeExpr,
+    node_kind::NodeKind,
+    node_shape::NodeShape,
+    property_shape::PropertyShape,
+    rule::{Rule, RuleKind},
+    schema::Schema,
+    severity::Severity,
+    shape::Shape,
+    sparql_constraint::{
+        ComponentValidator, ConstraintComponent, Parameter, PrefixDeclaration, SparqlConstraint,
+        ValidatorKind,
+    },
+    target::Target,
+    value::Value,
+    *,
 };
 use std::fmt::Debug;
+use std::str::FromStr;
 
 use super::shacl_parser_error::ShaclParserError;
 
@@ -52,6 +70,7 @@ where
 
     pub fn parse(&mut self) -> Result<Schema> {
         let prefixmap: PrefixMap = self.rdf_parser.prefixmap().unwrap_or_default();
+        let components = parse_constraint_components(&self.rdf_parser.rdf)?;
 
         let mut state = State::from(self.shapes_candidates()?);
         while let Some(node) = state.pop_pending() {
@@ -61,13 +80,15 @@ where
                 let shape = Self::shape(&mut state)
                     .parse_impl(&mut self.rdf_parser.rdf)
                     .map_err(|e| ShaclParserError::RDFParseError { err: e })?;
-                e.insert(shape);
+                let uses = sparql_component_uses(&self.rdf_parser.rdf, &node, &components)?;
+                e.insert(with_component_uses(shape, uses));
             }
         }
 
         Ok(Schema::new()
             .with_prefixmap(prefixmap)
-            .with_shapes(self.shapes.clone()))
+            .with_shapes(self.shapes.clone())
+            .with_components(components))
     }
 
     fn shapes_candidates(&mut self) -> Result<Vec<RDFNode>> {
@@ -98,6 +119,10 @@ where
         // elements of `sh:not` list
         let sh_node_values = self.get_sh_node_values()?;
 
+        // values of `sh:qualifiedValueShape`
+        let sh_qualified_value_shape_values =
+            self.objects_with_predicate(Self::sh_qualified_value_shape())?;
+
         // TODO: subjects with type `sh:PropertyShape`
         let property_shapes_instances = HashSet::new();
 
@@ -114,6 +139,7 @@ where
         candidates.extend(sh_and_values);
         candidates.extend(sh_not_values);
         candidates.extend(sh_node_values);
+        candidates.extend(sh_qualified_value_shape_values);
         candidates.extend(property_shapes_instances);
         candidates.extend(shape_instances);
 
@@ -249,6 +275,10 @@ where
         RDF::iri_s2iri(&SH_NODE)
     }
 
+    fn sh_qualified_value_shape() -> RDF::IRI {
+        RDF::iri_s2iri(&SH_QUALIFIED_VALUE_SHAPE)
+    }
+
     fn triple_object_as_subject(triple: &Triple<RDF>) -> Result<RDF::Subject> {
         let subj = RDF::term_as_subject(&triple.obj()).ok_or_else(|| ShaclParserError::Custom {
             msg: format!("Expected triple object value to act as a subject: {triple}"),
@@ -270,25 +300,109 @@ where
     }
 }
 
+fn with_component_uses(shape: Shape, uses: Vec<Component>) -> Shape {
+    if uses.is_empty() {
+        return shape;
+    }
+    match shape {
+        Shape::NodeShape(ns) => {
+            let components = ns.components().iter().cloned().chain(uses).collect();
+            Shape::NodeShape(Box::new(ns.with_components(components)))
+        }
+        Shape::PropertyShape(ps) => {
+            let components = ps.components().iter().cloned().chain(uses).collect();
+            Shape::PropertyShape(ps.with_components(components))
+        }
+    }
+}
+
 fn components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    // components are grouped following the sections of the SHACL spec, which
+    // keeps the types of the combined parsers manageable for the compiler
+    combine_parsers!(
+        cardinality_components(),
+        value_components(),
+        value_range_components(),
+        string_based_components(),
+        property_pair_components(),
+        logical_components(),
+        shape_based_components(),
+        other_components()
+    )
+}
+
+fn cardinality_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(min_count(), max_count())
+}
+
+fn value_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(class(), datatype(), node_kind())
+}
+
+fn value_range_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(
+        min_exclusive(),
+        max_exclusive(),
+        min_inclusive(),
+        max_inclusive()
+    )
+}
+
+fn string_based_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
 where
     RDF: FocusRDF,
 {
     combine_parsers!(
-        min_count(),
-        max_count(),
-        in_component(),
-        datatype(),
-        node_kind(),
-        class(),
-        or(),
-        xone(),
-        and(),
-        not_parser(),
-        node(),
         min_length(),
         max_length(),
-        has_value()
+        pattern(),
+        language_in(),
+        unique_lang()
+    )
+}
+
+fn property_pair_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(equals(), disjoint(), less_than(), less_than_or_equals())
+}
+
+fn logical_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(not_parser(), and(), or(), xone())
+}
+
+fn shape_based_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(node(), qualified_value_shape())
+}
+
+fn other_components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    combine_parsers!(
+        closed_component(),
+        has_value(),
+        in_component(),
+        sparql_constraints()
     )
 }
 
@@ -317,6 +431,8 @@ where
             property_shapes()
                 .flat_map(move |prop_shapes| Ok(ps.clone().with_property_shapes(prop_shapes)))
         })
+        .then(|ps| message().flat_map(move |m| Ok(ps.clone().with_message(m))))
+        .then(|ps| severity().flat_map(move |s| Ok(ps.clone().with_severity(s))))
         .then(move |ps| property_shape_components(ps))
 }
 
@@ -352,7 +468,10 @@ where
             .then(|ns| {
                 property_shapes().flat_map(move |ps| Ok(ns.clone().with_property_shapes(ps)))
             })
-            .then(|ns| components().flat_map(move |cs| Ok(ns.clone().with_components(cs)))),
+            .then(|ns| message().flat_map(move |m| Ok(ns.clone().with_message(m))))
+            .then(|ns| severity().flat_map(move |s| Ok(ns.clone().with_severity(s))))
+            .then(|ns| components().flat_map(move |cs| Ok(ns.clone().with_components(cs))))
+            .then(|ns| rules().flat_map(move |rs| Ok(ns.clone().with_rules(rs)))),
     )
 }
 
@@ -446,19 +565,103 @@ fn shacl_path<RDF>(term: RDF::Term) -> impl RDFNodeParse<RDF, Output = SHACLPath
 where
     RDF: FocusRDF,
 {
-    let obj = RDF::term_as_object(&term);
-    match obj {
-        Object::Iri(iri) => ok(&SHACLPath::iri(iri)),
-        Object::BlankNode(_) => todo!(),
-        Object::Literal(_) => todo!(),
+    apply_rdf(move |rdf: &mut RDF| parse_shacl_path(rdf, &term))
+}
+
+/// Parses a term as a SHACL path following the [syntax rules](https://www.w3.org/TR/shacl/#property-paths)
+/// of SHACL property paths
+fn parse_shacl_path<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<SHACLPath>
+where
+    RDF: FocusRDF,
+{
+    let subject = match RDF::term_as_object(term) {
+        Object::Iri(iri) => return Ok(SHACLPath::iri(iri)),
+        Object::Literal(lit) => {
+            return Err(RDFParseError::Custom {
+                msg: format!("Literal {lit} is not a valid SHACL path"),
+            })
+        }
+        Object::BlankNode(_) => {
+            RDF::term_as_subject(term).ok_or_else(|| RDFParseError::ExpectedSubject {
+                node: term.to_string(),
+            })?
+        }
+    };
+
+    // a sequence path is a well-formed SHACL list of two or more paths
+    if !path_objects(rdf, &subject, &RDF_FIRST)?.is_empty() {
+        let paths = parse_path_list(rdf, term)?;
+        return Ok(SHACLPath::Sequence { paths });
+    }
+
+    if let Some(list) = path_objects(rdf, &subject, &SH_ALTERNATIVE_PATH)?.pop() {
+        let paths = parse_path_list(rdf, &list)?;
+        return Ok(SHACLPath::Alternative { paths });
+    }
+
+    type UnaryPath = fn(Box<SHACLPath>) -> SHACLPath;
+    let unary_paths: [(&IriS, UnaryPath); 4] = [
+        (&SH_INVERSE_PATH, |path| SHACLPath::Inverse { path }),
+        (&SH_ZERO_OR_MORE_PATH, |path| SHACLPath::ZeroOrMore { path }),
+        (&SH_ONE_OR_MORE_PATH, |path| SHACLPath::OneOrMore { path }),
+        (&SH_ZERO_OR_ONE_PATH, |path| SHACLPath::ZeroOrOne { path }),
+    ];
+
+    for (property, build) in unary_paths {
+        if let Some(value) = path_objects(rdf, &subject, property)?.pop() {
+            let path = parse_shacl_path(rdf, &value)?;
+            return Ok(build(Box::new(path)));
+        }
+    }
+
+    Err(RDFParseError::Custom {
+        msg: format!("Blank node {term} is not a valid SHACL path"),
+    })
+}
+
+fn path_objects<RDF>(rdf: &RDF, subject: &RDF::Subject, property: &IriS) -> PResult<Vec<RDF::Term>>
+where
+    RDF: FocusRDF,
+{
+    rdf.objects_for_subject_predicate(subject, &RDF::iri_s2iri(property))
+        .map(|objects| objects.into_iter().collect())
+        .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })
+}
+
+fn parse_path_list<RDF>(rdf: &mut RDF, list: &RDF::Term) -> PResult<Vec<SHACLPath>>
+where
+    RDF: FocusRDF,
+{
+    parse_list(rdf, list)?
+        .iter()
+        .map(|member| parse_shacl_path(rdf, member))
+        .collect()
+}
+
+fn parse_list<RDF>(rdf: &mut RDF, list: &RDF::Term) -> PResult<Vec<RDF::Term>>
+where
+    RDF: FocusRDF,
+{
+    // the list parser works over the focus node, which has to be restored afterwards
+    let focus = rdf.get_focus().clone();
+    rdf.set_focus(list);
+    let members = rdf_list().parse_impl(rdf);
+    if let Some(focus) = focus {
+        rdf.set_focus(&focus);
     }
+    members
 }
 
 fn targets<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
 where
     RDF: FocusRDF,
 {
-    combine_vec(targets_class(), targets_node())
+    combine_parsers!(
+        targets_class(),
+        targets_node(),
+        targets_subjects_of(),
+        targets_objects_of()
+    )
 }
 
 fn closed<RDF>() -> impl RDFNodeParse<RDF, Output = bool>
@@ -468,6 +671,40 @@ where
     property_bool(&SH_CLOSED)
 }
 
+/// The values of sh:message, at most one for each language tag
+fn message<RDF>() -> impl RDFNodeParse<RDF, Output = MessageMap>
+where
+    RDF: FocusRDF,
+{
+    property_literals(&SH_MESSAGE).map(|ls| {
+        ls.into_iter()
+            .fold(MessageMap::new(), |messages, literal| match literal {
+                Literal::StringLiteral { lexical_form, lang } => {
+                    messages.with_message(lang, lexical_form)
+                }
+                other => messages.with_message(None, other.lexical_form()),
+            })
+    })
+}
+
+fn severity<RDF>() -> impl RDFNodeParse<RDF, Output = Option<Severity>>
+where
+    RDF: FocusRDF,
+{
+    optional(property_value(&SH_SEVERITY).flat_map(|term| {
+        let iri = RDF::term_as_iri(&term).ok_or_else(|| RDFParseError::ExpectedIRI {
+            term: term.to_string(),
+        })?;
+        let severity = match RDF::iri2iri_s(&iri) {
+            iri if iri == *SH_VIOLATION => Severity::Violation,
+            iri if iri == *SH_WARNING => Severity::Warning,
+            iri if iri == *SH_INFO => Severity::Info,
+            iri => Severity::Generic(IriRef::iri(iri)),
+        };
+        Ok(severity)
+    }))
+}
+
 fn min_count<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
 where
     RDF: FocusRDF,
@@ -538,6 +775,217 @@ where
     })
 }
 
+fn min_exclusive<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_literals(&SH_MIN_EXCLUSIVE)
+        .map(|ls| ls.into_iter().map(Component::MinExclusive).collect())
+}
+
+fn max_exclusive<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_literals(&SH_MAX_EXCLUSIVE)
+        .map(|ls| ls.into_iter().map(Component::MaxExclusive).collect())
+}
+
+fn min_inclusive<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_literals(&SH_MIN_INCLUSIVE)
+        .map(|ls| ls.into_iter().map(Component::MinInclusive).collect())
+}
+
+fn max_inclusive<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_literals(&SH_MAX_INCLUSIVE)
+        .map(|ls| ls.into_iter().map(Component::MaxInclusive).collect())
+}
+
+fn property_literals<RDF>(property: &IriS) -> impl RDFNodeParse<RDF, Output = Vec<Literal>>
+where
+    RDF: FocusRDF,
+{
+    property_values(property).flat_map(|ts| ts.iter().map(term_to_literal::<RDF>).collect())
+}
+
+fn term_to_literal<RDF>(term: &RDF::Term) -> std::result::Result<Literal, RDFParseError>
+where
+    RDF: SRDFBasic,
+{
+    match RDF::term_as_object(term) {
+        Object::Literal(lit) => Ok(lit),
+        _ => Err(RDFParseError::ExpectedLiteral {
+            term: term.to_string(),
+        }),
+    }
+}
+
+fn pattern<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_literals(&SH_PATTERN)
+        .and(optional(property_string(&SH_FLAGS)))
+        .map(|(patterns, flags)| {
+            patterns
+                .iter()
+                .map(|pattern| Component::Pattern {
+                    pattern: pattern.lexical_form(),
+                    flags: flags.clone(),
+                })
+                .collect()
+        })
+}
+
+fn language_in<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values(&SH_LANGUAGE_IN).then(move |terms_set| {
+        let terms: Vec<_> = terms_set.into_iter().collect();
+        parse_nodes(terms, rdf_list().flat_map(cnv_language_in_list::<RDF>))
+    })
+}
+
+fn cnv_language_in_list<RDF>(ls: Vec<RDF::Term>) -> PResult<Component>
+where
+    RDF: SRDFBasic,
+{
+    let langs = ls
+        .iter()
+        .map(|t| term_to_literal::<RDF>(t).map(|lit| Lang::new(&lit.lexical_form())))
+        .collect::<PResult<Vec<_>>>()?;
+    Ok(Component::LanguageIn { langs })
+}
+
+fn unique_lang<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values(&SH_UNIQUE_LANG).flat_map(|ts| {
+        ts.iter()
+            .map(|t| match RDF::term_as_boolean(t) {
+                Some(b) => Ok(Component::UniqueLang(b)),
+                None => Err(RDFParseError::ExpectedBoolean {
+                    term: t.to_string(),
+                }),
+            })
+            .collect()
+    })
+}
+
+fn equals<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_EQUALS).map(|ns| {
+        ns.iter()
+            .map(|iri| Component::Equals(IriRef::iri(iri.clone())))
+            .collect()
+    })
+}
+
+fn disjoint<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_DISJOINT).map(|ns| {
+        ns.iter()
+            .map(|iri| Component::Disjoint(IriRef::iri(iri.clone())))
+            .collect()
+    })
+}
+
+fn less_than<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_LESS_THAN).map(|ns| {
+        ns.iter()
+            .map(|iri| Component::LessThan(IriRef::iri(iri.clone())))
+            .collect()
+    })
+}
+
+fn less_than_or_equals<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_LESS_THAN_OR_EQUALS).map(|ns| {
+        ns.iter()
+            .map(|iri| Component::LessThanOrEquals(IriRef::iri(iri.clone())))
+            .collect()
+    })
+}
+
+/// Closed shapes are also represented as a component, so that the validators
+/// can check them together with the ignored properties
+fn closed_component<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    optional(closed())
+        .and(ignored_properties())
+        .map(|(closed, ignored_properties)| match closed {
+            Some(true) => vec![Component::Closed {
+                is_closed: true,
+                ignored_properties,
+            }],
+            _ => Vec::new(),
+        })
+}
+
+fn ignored_properties<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<IriRef>>
+where
+    RDF: FocusRDF,
+{
+    property_values(&SH_IGNORED_PROPERTIES).then(move |terms_set| {
+        let terms: Vec<_> = terms_set.into_iter().collect();
+        parse_nodes(terms, rdf_list().flat_map(cnv_iri_list::<RDF>)).map(|ls| ls.concat())
+    })
+}
+
+fn cnv_iri_list<RDF>(ls: Vec<RDF::Term>) -> PResult<Vec<IriRef>>
+where
+    RDF: SRDFBasic,
+{
+    ls.iter()
+        .map(|t| match RDF::term_as_iri(t) {
+            Some(iri) => Ok(IriRef::iri(RDF::iri2iri_s(&iri))),
+            None => Err(RDFParseError::ExpectedIRI {
+                term: t.to_string(),
+            }),
+        })
+        .collect()
+}
+
+fn qualified_value_shape<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    property_values(&SH_QUALIFIED_VALUE_SHAPE)
+        .and(optional(property_integer(&SH_QUALIFIED_MIN_COUNT)))
+        .and(optional(property_integer(&SH_QUALIFIED_MAX_COUNT)))
+        .and(optional(property_bool(&SH_QUALIFIED_VALUE_SHAPES_DISJOINT)))
+        .map(|(((shapes, min_count), max_count), disjoint)| {
+            shapes
+                .iter()
+                .map(|shape| Component::QualifiedValueShape {
+                    shape: RDF::term_as_object(shape),
+                    qualified_min_count: min_count,
+                    qualified_max_count: max_count,
+                    qualified_value_shapes_disjoint: disjoint,
+                })
+                .collect()
+        })
+}
+
 fn has_value<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
 where
     RDF: FocusRDF,
@@ -692,6 +1140,28 @@ where
     })
 }
 
+fn targets_subjects_of<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_TARGET_SUBJECTS_OF).map(|ns| {
+        ns.iter()
+            .map(|iri| Target::TargetSubjectsOf(IriRef::iri(iri.clone())))
+            .collect()
+    })
+}
+
+fn targets_objects_of<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_TARGET_OBJECTS_OF).map(|ns| {
+        ns.iter()
+            .map(|iri| Target::TargetObjectsOf(IriRef::iri(iri.clone())))
+            .collect()
+    })
+}
+
 fn targets_node<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
 where
     RDF: FocusRDF,
@@ -707,3 +1177,402 @@ where
         Ok(result)
     })
 }
+
+fn sparql_constraints<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    apply_rdf(|rdf: &mut RDF| {
+        let focus = rdf.get_focus_as_subject()?;
+        path_objects(rdf, &focus, &SH_SPARQL)?
+            .iter()
+            .map(|term| parse_sparql_constraint(rdf, term).map(Component::Sparql))
+            .collect()
+    })
+}
+
+fn parse_sparql_constraint<RDF>(rdf: &RDF, term: &RDF::Term) -> PResult<SparqlConstraint>
+where
+    RDF: FocusRDF,
+{
+    let subject = term_to_subject::<RDF>(term)?;
+    let select = match path_string(rdf, &subject, &SH_SELECT)? {
+        Some(select) => select,
+        None => {
+            return Err(RDFParseError::Custom {
+                msg: format!("SPARQL constraint {term} without sh:select query"),
+            })
+        }
+    };
+
+    let deactivated = path_objects(rdf, &subject, &SH_DEACTIVATED)?
+        .iter()
+        .any(|t| RDF::term_as_boolean(t) == Some(true));
+
+    Ok(SparqlConstraint::new(RDF::term_as_object(term), select)
+        .with_prefixes(parse_prefixes(rdf, &subject)?)
+        .with_messages(path_literals(rdf, &subject, &SH_MESSAGE)?)
+        .with_deactivated(deactivated))
+}
+
+/// Parses the instances of `sh:ConstraintComponent` of the shapes graph
+fn parse_constraint_components<RDF>(rdf: &RDF) -> PResult<HashMap<IriS, ConstraintComponent>>
+where
+    RDF: FocusRDF,
+{
+    let subjects = rdf
+        .subjects_with_predicate_object(
+            &RDF::iri_s2iri(&RDF_TYPE),
+            &RDF::iri_s2term(&SH_CONSTRAINT_COMPONENT),
+        )
+        .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })?;
+
+    let mut components = HashMap::new();
+    for subject in subjects {
+        let term = RDF::subject_as_term(&subject);
+        let id = match RDF::term_as_iri(&term) {
+            Some(iri) => RDF::iri2iri_s(&iri),
+            None => {
+                return Err(RDFParseError::Custom {
+                    msg: format!("Constraint component {term} must be an IRI"),
+                })
+            }
+        };
+
+        let parameters = path_objects(rdf, &subject, &SH_PARAMETER)?
+            .iter()
+            .map(|parameter| parse_parameter(rdf, parameter))
+            .collect::<PResult<Vec<_>>>()?;
+
+        let component = ConstraintComponent::new(id.clone())
+            .with_parameters(parameters)
+            .with_validator(parse_validator(rdf, &subject, &SH_VALIDATOR)?)
+            .with_node_validator(parse_validator(rdf, &subject, &SH_NODE_VALIDATOR)?)
+            .with_property_validator(parse_validator(rdf, &subject, &SH_PROPERTY_VALIDATOR)?);
+
+        components.insert(id, component);
+    }
+
+    Ok(components)
+}
+
+fn parse_parameter<RDF>(rdf: &RDF, term: &RDF::Term) -> PResult<Parameter>
+where
+    RDF: FocusRDF,
+{
+    let subject = term_to_subject::<RDF>(term)?;
+    let path = path_objects(rdf, &subject, &SH_PATH)?
+        .iter()
+        .find_map(|t| RDF::term_as_iri(t))
+        .map(|iri| RDF::iri2iri_s(&iri))
+        .ok_or_else(|| RDFParseError::Custom {
+            msg: format!("Parameter {term} must have an IRI as sh:path"),
+        })?;
+
+    let optional = path_objects(rdf, &subject, &SH_OPTIONAL)?
+        .iter()
+        .any(|t| RDF::term_as_boolean(t) == Some(true));
+
+    Ok(Parameter::new(RDF::term_as_object(term), path, optional))
+}
+
+fn parse_validator<RDF>(
+    rdf: &RDF,
+    component: &RDF::Subject,
+    property: &IriS,
+) -> PResult<Option<ComponentValidator>>
+where
+    RDF: FocusRDF,
+{
+    let term = match path_objects(rdf, component, property)?.into_iter().next() {
+        Some(term) => term,
+        None => return Ok(None),
+    };
+    let subject = term_to_subject::<RDF>(&term)?;
+
+    let (kind, query) = match (
+        path_string(rdf, &subject, &SH_ASK)?,
+        path_string(rdf, &subject, &SH_SELECT)?,
+    ) {
+        (Some(ask), None) => (ValidatorKind::Ask, ask),
+        (None, Some(select)) => (ValidatorKind::Select, select),
+        _ => {
+            return Err(RDFParseError::Custom {
+                msg: format!("Validator {term} must have either a sh:ask or a sh:select query"),
+            })
+        }
+    };
+
+    let validator = ComponentValidator::new(RDF::term_as_object(&term), kind, query)
+        .with_prefixes(parse_prefixes(rdf, &subject)?)
+        .with_messages(path_literals(rdf, &subject, &SH_MESSAGE)?);
+
+    Ok(Some(validator))
+}
+
+/// Obtains the prefixes declared with `sh:declare` by the values of
+/// `sh:prefixes` of a SPARQL-based constraint or validator
+fn parse_prefixes<RDF>(rdf: &RDF, subject: &RDF::Subject) -> PResult<Vec<PrefixDeclaration>>
+where
+    RDF: FocusRDF,
+{
+    let mut prefixes = Vec::new();
+    for owner in path_objects(rdf, subject, &SH_PREFIXES)? {
+        let owner = term_to_subject::<RDF>(&owner)?;
+        for declaration in path_objects(rdf, &owner, &SH_DECLARE)? {
+            let declaration = term_to_subject::<RDF>(&declaration)?;
+            let prefix = path_string(rdf, &declaration, &SH_PREFIX)?;
+            let namespace = path_objects(rdf, &declaration, &SH_NAMESPACE)?
+                .into_iter()
+                .next()
+                .and_then(|t| match RDF::term_as_iri(&t) {
+                    Some(iri) => Some(RDF::iri2iri_s(&iri).as_str().to_string()),
+                    None => RDF::term_as_string(&t),
+                });
+            match (prefix, namespace) {
+                (Some(prefix), Some(namespace)) => {
+                    let namespace =
+                        IriS::from_str(&namespace).map_err(|e| RDFParseError::Custom {
+                            msg: format!("Wrong namespace {namespace} for prefix {prefix}: {e}"),
+                        })?;
+                    prefixes.push(PrefixDeclaration::new(&prefix, namespace))
+                }
+                _ => {
+                    return Err(RDFParseError::Custom {
+                        msg: "Prefix declarations require sh:prefix and sh:namespace".to_string(),
+                    })
+                }
+            }
+        }
+    }
+    Ok(prefixes)
+}
+
+/// Obtains the uses of the SPARQL-based constraint components in a shape. A
+/// component is used when the shape has values for all its mandatory
+/// parameters, and there is one use for each combination of those values.
+fn sparql_component_uses<RDF>(
+    rdf: &RDF,
+    shape: &RDFNode,
+    components: &HashMap<IriS, ConstraintComponent>,
+) -> PResult<Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    let subject = match RDF::object_as_subject(shape) {
+        Some(subject) => subject,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut uses = Vec::new();
+    'components: for component in components.values() {
+        let mut bindings: Vec<Vec<(IriS, RDFNode)>> = vec![Vec::new()];
+        for parameter in component.parameters() {
+            let values = path_objects(rdf, &subject, parameter.path())?;
+            if values.is_empty() {
+                if parameter.is_optional() {
+                    continue;
+                }
+                continue 'components;
+            }
+            bindings = bindings
+                .into_iter()
+                .flat_map(|binding| {
+                    values.iter().map(move |value| {
+                        let mut binding = binding.clone();
+                        binding.push((parameter.path().clone(), RDF::term_as_object(value)));
+                        binding
+                    })
+                })
+                .collect();
+        }
+
+        uses.extend(
+            bindings
+                .into_iter()
+                .map(|parameters| Component::SparqlComponent {
+                    component: component.id().clone(),
+                    parameters,
+                }),
+        );
+    }
+
+    Ok(uses)
+}
+
+fn term_to_subject<RDF>(term: &RDF::Term) -> PResult<RDF::Subject>
+where
+    RDF: SRDFBasic,
+{
+    RDF::term_as_subject(term).ok_or_else(|| RDFParseError::ExpectedSubject {
+        node: term.to_string(),
+    })
+}
+
+fn path_string<RDF>(rdf: &RDF, subject: &RDF::Subject, property: &IriS) -> PResult<Option<String>>
+where
+    RDF: FocusRDF,
+{
+    Ok(path_objects(rdf, subject, property)?
+        .iter()
+        .find_map(|t| RDF::term_as_string(t)))
+}
+
+fn path_literals<RDF>(rdf: &RDF, subject: &RDF::Subject, property: &IriS) -> PResult<Vec<Literal>>
+where
+    RDF: FocusRDF,
+{
+    path_objects(rdf, subject, property)?
+        .iter()
+        .map(term_to_literal::<RDF>)
+        .collect()
+}
+
+fn rules<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Rule>>
+where
+    RDF: FocusRDF,
+{
+    apply_rdf(|rdf: &mut RDF| {
+        let focus = rdf.get_focus_as_subject()?;
+        path_objects(rdf, &focus, &SH_RULE)?
+            .iter()
+            .map(|term| parse_rule(rdf, term))
+            .collect()
+    })
+}
+
+fn parse_rule<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<Rule>
+where
+    RDF: FocusRDF,
+{
+    let subject = term_to_subject::<RDF>(term)?;
+
+    let kind = match path_string(rdf, &subject, &SH_CONSTRUCT)? {
+        Some(construct) => RuleKind::Sparql {
+            construct,
+            prefixes: parse_prefixes(rdf, &subject)?,
+        },
+        None => {
+            let predicate = path_objects(rdf, &subject, &SH_PREDICATE)?
+                .iter()
+                .find_map(|t| RDF::term_as_iri(t))
+                .map(|iri| RDF::iri2iri_s(&iri))
+                .ok_or_else(|| RDFParseError::Custom {
+                    msg: format!(
+                        "Rule {term} must be a SPARQL rule or have an IRI as sh:predicate"
+                    ),
+                })?;
+            RuleKind::Triple {
+                subject: parse_node_expr_property(rdf, &subject, &SH_SUBJECT)?,
+                predicate,
+                object: parse_node_expr_property(rdf, &subject, &SH_OBJECT)?,
+            }
+        }
+    };
+
+    let order = match path_string(rdf, &subject, &SH_ORDER)? {
+        Some(order) => Some(order.parse().map_err(|_| RDFParseError::Custom {
+            msg: format!("Rule {term} has a non numeric sh:order: {order}"),
+        })?),
+        None => None,
+    };
+
+    let conditions = path_objects(rdf, &subject, &SH_CONDITION)?
+        .iter()
+        .map(|t| RDF::term_as_object(t))
+        .collect();
+
+    let deactivated = path_objects(rdf, &subject, &SH_DEACTIVATED)?
+        .iter()
+        .any(|t| RDF::term_as_boolean(t) == Some(true));
+
+    Ok(Rule::new(RDF::term_as_object(term), kind)
+        .with_order(order)
+        .with_conditions(conditions)
+        .with_deactivated(deactivated))
+}
+
+/// Parses the node expression which is the value of `property`
+fn parse_node_expr_property<RDF>(
+    rdf: &mut RDF,
+    subject: &RDF::Subject,
+    property: &IriS,
+) -> PResult<NodeExpr>
+where
+    RDF: FocusRDF,
+{
+    match path_objects(rdf, subject, property)?.into_iter().next() {
+        Some(term) => parse_node_expr(rdf, &term),
+        None => Err(RDFParseError::Custom {
+            msg: format!("Expected a node expression as value of {property} for {subject}"),
+        }),
+    }
+}
+
+fn parse_node_expr<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<NodeExpr>
+where
+    RDF: FocusRDF,
+{
+    let subject = match RDF::term_as_subject(term) {
+        Some(subject) if RDF::subject_is_bnode(&subject) => subject,
+        _ => {
+            return match RDF::term_as_object(term) {
+                Object::Iri(iri) if iri == *SH_THIS => Ok(NodeExpr::FocusNode),
+                node => Ok(NodeExpr::Constant(node)),
+            }
+        }
+    };
+
+    if let Some(path) = path_objects(rdf, &subject, &SH_PATH)?.into_iter().next() {
+        let path = parse_shacl_path(rdf, &path)?;
+        let nodes = match path_objects(rdf, &subject, &SH_NODES)?.into_iter().next() {
+            Some(nodes) => Some(Box::new(parse_node_expr(rdf, &nodes)?)),
+            None => None,
+        };
+        return Ok(NodeExpr::Path { path, nodes });
+    }
+
+    if let Some(shape) = path_objects(rdf, &subject, &SH_FILTER_SHAPE)?
+        .into_iter()
+        .next()
+    {
+        let nodes = parse_node_expr_property(rdf, &subject, &SH_NODES)?;
+        return Ok(NodeExpr::FilterShape {
+            shape: RDF::term_as_object(&shape),
+            nodes: Box::new(nodes),
+        });
+    }
+
+    for (property, cnv) in [
+        (
+            &*SH_INTERSECTION,
+            NodeExpr::Intersection as fn(Vec<NodeExpr>) -> NodeExpr,
+        ),
+        (&*SH_UNION, NodeExpr::Union),
+    ] {
+        if let Some(list) = path_objects(rdf, &subject, property)?.into_iter().next() {
+            let exprs = parse_list(rdf, &list)?
+                .iter()
+                .map(|member| parse_node_expr(rdf, member))
+                .collect::<PResult<Vec<_>>>()?;
+            return Ok(cnv(exprs));
+        }
+    }
+
+    for (property, cnv) in [
+        (
+            &*SH_DISTINCT,
+            NodeExpr::Distinct as fn(Box<NodeExpr>) -> NodeExpr,
+        ),
+        (&*SH_COUNT, NodeExpr::Count),
+        (&*SH_EXISTS, NodeExpr::Exists),
+    ] {
+        if let Some(inner) = path_objects(rdf, &subject, property)?.into_iter().next() {
+            return Ok(cnv(Box::new(parse_node_expr(rdf, &inner)?)));
+        }
+    }
+
+    Err(RDFParseError::Custom {
+        msg: format!("Unsupported node expression: {term}"),
+    })
+}
@@ -32,6 +32,10 @@ where
             .iter()
             .try_for_each(|(_, shape)| shape.write(&mut self.rdf))?;
 
+        schema
+            .components()
+            .try_for_each(|(_, component)| component.write(&mut self.rdf))?;
+
         Ok(())
     }
 
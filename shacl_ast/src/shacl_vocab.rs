@@ -18,12 +18,21 @@ pub const SH_VALIDATION_REPORT_STR: &str = concatcp!(SH_STR, "ValidationReport")
 pub const SH_VALIDATION_RESULT_STR: &str = concatcp!(SH_STR, "ValidationResult");
 pub const SH_VIOLATION_STR: &str = concatcp!(SH_STR, "Violation");
 pub const SH_WARNING_STR: &str = concatcp!(SH_STR, "Warning");
+pub const SH_CONSTRAINT_COMPONENT_STR: &str = concatcp!(SH_STR, "ConstraintComponent");
+pub const SH_SPARQL_ASK_VALIDATOR_STR: &str = concatcp!(SH_STR, "SPARQLAskValidator");
+pub const SH_SPARQL_CONSTRAINT_STR: &str = concatcp!(SH_STR, "SPARQLConstraint");
+pub const SH_SPARQL_CONSTRAINT_COMPONENT_STR: &str = concatcp!(SH_STR, "SPARQLConstraintComponent");
+pub const SH_SPARQL_SELECT_VALIDATOR_STR: &str = concatcp!(SH_STR, "SPARQLSelectValidator");
+pub const SH_SPARQL_RULE_STR: &str = concatcp!(SH_STR, "SPARQLRule");
+pub const SH_TRIPLE_RULE_STR: &str = concatcp!(SH_STR, "TripleRule");
 pub const SH_AND_STR: &str = concatcp!(SH_STR, "and");
+pub const SH_ALTERNATIVE_PATH_STR: &str = concatcp!(SH_STR, "alternativePath");
 pub const SH_CLASS_STR: &str = concatcp!(SH_STR, "class");
 pub const SH_CLOSED_STR: &str = concatcp!(SH_STR, "closed");
 pub const SH_CONFORMS_STR: &str = concatcp!(SH_STR, "conforms");
 pub const SH_DATATYPE_STR: &str = concatcp!(SH_STR, "datatype");
 pub const SH_DEACTIVATED_STR: &str = concatcp!(SH_STR, "deactivated");
+pub const SH_DETAIL_STR: &str = concatcp!(SH_STR, "detail");
 pub const SH_DESCRIPTION_STR: &str = concatcp!(SH_STR, "description");
 pub const SH_DISJOINT_STR: &str = concatcp!(SH_STR, "disjoint");
 pub const SH_EQUALS_STR: &str = concatcp!(SH_STR, "equals");
@@ -34,6 +43,7 @@ pub const SH_GROUP_STR: &str = concatcp!(SH_STR, "group");
 pub const SH_HAS_VALUE_STR: &str = concatcp!(SH_STR, "hasValue");
 pub const SH_IGNORED_PROPERTIES_STR: &str = concatcp!(SH_STR, "ignoredProperties");
 pub const SH_IN_STR: &str = concatcp!(SH_STR, "in");
+pub const SH_INVERSE_PATH_STR: &str = concatcp!(SH_STR, "inversePath");
 pub const SH_LANGUAGE_IN_STR: &str = concatcp!(SH_STR, "languageIn");
 pub const SH_LESS_THAN_STR: &str = concatcp!(SH_STR, "lessThan");
 pub const SH_LESS_THAN_OR_EQUALS_STR: &str = concatcp!(SH_STR, "lessThanOrEquals");
@@ -50,6 +60,7 @@ pub const SH_NAME_STR: &str = concatcp!(SH_STR, "name");
 pub const SH_NODE_KIND_STR: &str = concatcp!(SH_STR, "nodeKind");
 pub const SH_NODE_STR: &str = concatcp!(SH_STR, "node");
 pub const SH_NOT_STR: &str = concatcp!(SH_STR, "not");
+pub const SH_ONE_OR_MORE_PATH_STR: &str = concatcp!(SH_STR, "oneOrMorePath");
 pub const SH_OR_STR: &str = concatcp!(SH_STR, "or");
 pub const SH_ORDER_STR: &str = concatcp!(SH_STR, "order");
 pub const SH_PATH_STR: &str = concatcp!(SH_STR, "path");
@@ -76,6 +87,34 @@ pub const SH_TARGET_OBJECTS_OF_STR: &str = concatcp!(SH_STR, "targetObjectsOf");
 pub const SH_TEXT_STR: &str = concatcp!(SH_STR, "text");
 pub const SH_UNIQUE_LANG_STR: &str = concatcp!(SH_STR, "uniqueLang");
 pub const SH_XONE_STR: &str = concatcp!(SH_STR, "xone");
+pub const SH_ZERO_OR_MORE_PATH_STR: &str = concatcp!(SH_STR, "zeroOrMorePath");
+pub const SH_ZERO_OR_ONE_PATH_STR: &str = concatcp!(SH_STR, "zeroOrOnePath");
+pub const SH_ASK_STR: &str = concatcp!(SH_STR, "ask");
+pub const SH_DECLARE_STR: &str = concatcp!(SH_STR, "declare");
+pub const SH_NAMESPACE_STR: &str = concatcp!(SH_STR, "namespace");
+pub const SH_NODE_VALIDATOR_STR: &str = concatcp!(SH_STR, "nodeValidator");
+pub const SH_OPTIONAL_STR: &str = concatcp!(SH_STR, "optional");
+pub const SH_PARAMETER_STR: &str = concatcp!(SH_STR, "parameter");
+pub const SH_PREFIX_STR: &str = concatcp!(SH_STR, "prefix");
+pub const SH_PREFIXES_STR: &str = concatcp!(SH_STR, "prefixes");
+pub const SH_PROPERTY_VALIDATOR_STR: &str = concatcp!(SH_STR, "propertyValidator");
+pub const SH_SELECT_STR: &str = concatcp!(SH_STR, "select");
+pub const SH_SPARQL_STR: &str = concatcp!(SH_STR, "sparql");
+pub const SH_VALIDATOR_STR: &str = concatcp!(SH_STR, "validator");
+pub const SH_CONDITION_STR: &str = concatcp!(SH_STR, "condition");
+pub const SH_CONSTRUCT_STR: &str = concatcp!(SH_STR, "construct");
+pub const SH_COUNT_STR: &str = concatcp!(SH_STR, "count");
+pub const SH_DISTINCT_STR: &str = concatcp!(SH_STR, "distinct");
+pub const SH_EXISTS_STR: &str = concatcp!(SH_STR, "exists");
+pub const SH_FILTER_SHAPE_STR: &str = concatcp!(SH_STR, "filterShape");
+pub const SH_INTERSECTION_STR: &str = concatcp!(SH_STR, "intersection");
+pub const SH_NODES_STR: &str = concatcp!(SH_STR, "nodes");
+pub const SH_OBJECT_STR: &str = concatcp!(SH_STR, "object");
+pub const SH_PREDICATE_STR: &str = concatcp!(SH_STR, "predicate");
+pub const SH_RULE_STR: &str = concatcp!(SH_STR, "rule");
+pub const SH_SUBJECT_STR: &str = concatcp!(SH_STR, "subject");
+pub const SH_THIS_STR: &str = concatcp!(SH_STR, "this");
+pub const SH_UNION_STR: &str = concatcp!(SH_STR, "union");
 pub const SH_SOURCE_CONSTRAINT_STR: &str = concatcp!(SH_STR, "sourceConstraint");
 
 lazy_static! {
@@ -96,12 +135,23 @@ lazy_static! {
     pub static ref SH_VALIDATION_RESULT: IriS = IriS::new_unchecked(SH_VALIDATION_RESULT_STR);
     pub static ref SH_VIOLATION: IriS = IriS::new_unchecked(SH_VIOLATION_STR);
     pub static ref SH_WARNING: IriS = IriS::new_unchecked(SH_WARNING_STR);
+    pub static ref SH_CONSTRAINT_COMPONENT: IriS = IriS::new_unchecked(SH_CONSTRAINT_COMPONENT_STR);
+    pub static ref SH_SPARQL_ASK_VALIDATOR: IriS = IriS::new_unchecked(SH_SPARQL_ASK_VALIDATOR_STR);
+    pub static ref SH_SPARQL_CONSTRAINT: IriS = IriS::new_unchecked(SH_SPARQL_CONSTRAINT_STR);
+    pub static ref SH_SPARQL_CONSTRAINT_COMPONENT: IriS =
+        IriS::new_unchecked(SH_SPARQL_CONSTRAINT_COMPONENT_STR);
+    pub static ref SH_SPARQL_SELECT_VALIDATOR: IriS =
+        IriS::new_unchecked(SH_SPARQL_SELECT_VALIDATOR_STR);
+    pub static ref SH_SPARQL_RULE: IriS = IriS::new_unchecked(SH_SPARQL_RULE_STR);
+    pub static ref SH_TRIPLE_RULE: IriS = IriS::new_unchecked(SH_TRIPLE_RULE_STR);
     pub static ref SH_AND: IriS = IriS::new_unchecked(SH_AND_STR);
+    pub static ref SH_ALTERNATIVE_PATH: IriS = IriS::new_unchecked(SH_ALTERNATIVE_PATH_STR);
     pub static ref SH_CLASS: IriS = IriS::new_unchecked(SH_CLASS_STR);
     pub static ref SH_CLOSED: IriS = IriS::new_unchecked(SH_CLOSED_STR);
     pub static ref SH_CONFORMS: IriS = IriS::new_unchecked(SH_CONFORMS_STR);
     pub static ref SH_DATATYPE: IriS = IriS::new_unchecked(SH_DATATYPE_STR);
     pub static ref SH_DEACTIVATED: IriS = IriS::new_unchecked(SH_DEACTIVATED_STR);
+    pub static ref SH_DETAIL: IriS = IriS::new_unchecked(SH_DETAIL_STR);
     pub static ref SH_DESCRIPTION: IriS = IriS::new_unchecked(SH_DESCRIPTION_STR);
     pub static ref SH_DISJOINT: IriS = IriS::new_unchecked(SH_DISJOINT_STR);
     pub static ref SH_EQUALS: IriS = IriS::new_unchecked(SH_EQUALS_STR);
@@ -112,6 +162,7 @@ lazy_static! {
     pub static ref SH_HAS_VALUE: IriS = IriS::new_unchecked(SH_HAS_VALUE_STR);
     pub static ref SH_IGNORED_PROPERTIES: IriS = IriS::new_unchecked(SH_IGNORED_PROPERTIES_STR);
     pub static ref SH_IN: IriS = IriS::new_unchecked(SH_IN_STR);
+    pub static ref SH_INVERSE_PATH: IriS = IriS::new_unchecked(SH_INVERSE_PATH_STR);
     pub static ref SH_LANGUAGE_IN: IriS = IriS::new_unchecked(SH_LANGUAGE_IN_STR);
     pub static ref SH_LESS_THAN: IriS = IriS::new_unchecked(SH_LESS_THAN_STR);
     pub static ref SH_LESS_THAN_OR_EQUALS: IriS = IriS::new_unchecked(SH_LESS_THAN_OR_EQUALS_STR);
@@ -128,6 +179,7 @@ lazy_static! {
     pub static ref SH_NODE_KIND: IriS = IriS::new_unchecked(SH_NODE_KIND_STR);
     pub static ref SH_NODE: IriS = IriS::new_unchecked(SH_NODE_STR);
     pub static ref SH_NOT: IriS = IriS::new_unchecked(SH_NOT_STR);
+    pub static ref SH_ONE_OR_MORE_PATH: IriS = IriS::new_unchecked(SH_ONE_OR_MORE_PATH_STR);
     pub static ref SH_OR: IriS = IriS::new_unchecked(SH_OR_STR);
     pub static ref SH_ORDER: IriS = IriS::new_unchecked(SH_ORDER_STR);
     pub static ref SH_PATH: IriS = IriS::new_unchecked(SH_PATH_STR);
@@ -156,5 +208,33 @@ lazy_static! {
     pub static ref SH_TEXT: IriS = IriS::new_unchecked(SH_TEXT_STR);
     pub static ref SH_UNIQUE_LANG: IriS = IriS::new_unchecked(SH_UNIQUE_LANG_STR);
     pub static ref SH_XONE: IriS = IriS::new_unchecked(SH_XONE_STR);
+    pub static ref SH_ZERO_OR_MORE_PATH: IriS = IriS::new_unchecked(SH_ZERO_OR_MORE_PATH_STR);
+    pub static ref SH_ZERO_OR_ONE_PATH: IriS = IriS::new_unchecked(SH_ZERO_OR_ONE_PATH_STR);
+    pub static ref SH_ASK: IriS = IriS::new_unchecked(SH_ASK_STR);
+    pub static ref SH_DECLARE: IriS = IriS::new_unchecked(SH_DECLARE_STR);
+    pub static ref SH_NAMESPACE: IriS = IriS::new_unchecked(SH_NAMESPACE_STR);
+    pub static ref SH_NODE_VALIDATOR: IriS = IriS::new_unchecked(SH_NODE_VALIDATOR_STR);
+    pub static ref SH_OPTIONAL: IriS = IriS::new_unchecked(SH_OPTIONAL_STR);
+    pub static ref SH_PARAMETER: IriS = IriS::new_unchecked(SH_PARAMETER_STR);
+    pub static ref SH_PREFIX: IriS = IriS::new_unchecked(SH_PREFIX_STR);
+    pub static ref SH_PREFIXES: IriS = IriS::new_unchecked(SH_PREFIXES_STR);
+    pub static ref SH_PROPERTY_VALIDATOR: IriS = IriS::new_unchecked(SH_PROPERTY_VALIDATOR_STR);
+    pub static ref SH_SELECT: IriS = IriS::new_unchecked(SH_SELECT_STR);
+    pub static ref SH_SPARQL: IriS = IriS::new_unchecked(SH_SPARQL_STR);
+    pub static ref SH_VALIDATOR: IriS = IriS::new_unchecked(SH_VALIDATOR_STR);
+    pub static ref SH_CONDITION: IriS = IriS::new_unchecked(SH_CONDITION_STR);
+    pub static ref SH_CONSTRUCT: IriS = IriS::new_unchecked(SH_CONSTRUCT_STR);
+    pub static ref SH_COUNT: IriS = IriS::new_unchecked(SH_COUNT_STR);
+    pub static ref SH_DISTINCT: IriS = IriS::new_unchecked(SH_DISTINCT_STR);
+    pub static ref SH_EXISTS: IriS = IriS::new_unchecked(SH_EXISTS_STR);
+    pub static ref SH_FILTER_SHAPE: IriS = IriS::new_unchecked(SH_FILTER_SHAPE_STR);
+    pub static ref SH_INTERSECTION: IriS = IriS::new_unchecked(SH_INTERSECTION_STR);
+    pub static ref SH_NODES: IriS = IriS::new_unchecked(SH_NODES_STR);
+    pub static ref SH_OBJECT: IriS = IriS::new_unchecked(SH_OBJECT_STR);
+    pub static ref SH_PREDICATE: IriS = IriS::new_unchecked(SH_PREDICATE_STR);
+    pub static ref SH_RULE: IriS = IriS::new_unchecked(SH_RULE_STR);
+    pub static ref SH_SUBJECT: IriS = IriS::new_unchecked(SH_SUBJECT_STR);
+    pub static ref SH_THIS: IriS = IriS::new_unchecked(SH_THIS_STR);
+    pub static ref SH_UNION: IriS = IriS::new_unchecked(SH_UNION_STR);
     pub static ref SH_SOURCE_CONSTRAINT: IriS = IriS::new_unchecked(SH_SOURCE_CONSTRAINT_STR);
 }
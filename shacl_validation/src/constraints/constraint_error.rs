@@ -1,9 +1,13 @@
 use thiserror::Error;
 
+use crate::helpers::helper_error::SPARQLError;
+
 #[derive(Error, Debug)]
 pub enum ConstraintError {
     #[error("{} constraint not yet implemented", ._0)]
     NotImplemented(String),
     #[error("{}", ._0)]
     Query(String),
+    #[error(transparent)]
+    Sparql(#[from] SPARQLError),
 }
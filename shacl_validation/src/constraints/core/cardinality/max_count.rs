@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::MaxCount;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -32,7 +32,7 @@ impl<S: SRDFBasic> Validator<S> for MaxCount {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for MaxCount {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for MaxCount {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -44,7 +44,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for MaxCount {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for MaxCount {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for MaxCount {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -14,7 +14,7 @@ use crate::value_nodes::ValueNodes;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::MinCount;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -36,7 +36,7 @@ impl<S: SRDFBasic> Validator<S> for MinCount {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for MinCount {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for MinCount {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -48,7 +48,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for MinCount {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for MinCount {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for MinCount {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
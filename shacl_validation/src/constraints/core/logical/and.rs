@@ -3,7 +3,7 @@ use std::ops::Not;
 use shacl_ast::compiled::component::And;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -47,7 +47,7 @@ impl<S: SRDFBasic> Validator<S> for And<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for And<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for And<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -59,7 +59,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for And<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for And<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for And<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Not;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -38,7 +38,7 @@ impl<S: SRDFBasic> Validator<S> for Not<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for Not<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Not<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -50,7 +50,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Not<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Not<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Not<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
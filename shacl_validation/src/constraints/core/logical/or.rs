@@ -3,7 +3,7 @@ use std::ops::Not;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Or;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -50,7 +50,7 @@ impl<S: SRDFBasic> Validator<S> for Or<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for Or<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Or<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -62,7 +62,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Or<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Or<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Or<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
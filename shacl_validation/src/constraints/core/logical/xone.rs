@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Xone;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -46,7 +46,7 @@ impl<S: SRDFBasic> Validator<S> for Xone<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for Xone<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Xone<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -58,7 +58,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Xone<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Xone<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Xone<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -6,3 +6,284 @@ pub mod shape_based;
 pub mod string_based;
 pub mod value;
 pub mod value_range;
+
+#[cfg(test)]
+mod tests {
+    use crate::shacl_processor::ShaclValidationMode;
+    use crate::test_utils::results;
+    use crate::test_utils::validate;
+
+    const A: &str = "<http://example.org/a>";
+    const B: &str = "<http://example.org/b>";
+
+    /// Shapes for the property :p of the nodes :a and :b
+    fn property(constraints: &str) -> String {
+        format!(":S a sh:NodeShape ; sh:targetNode :a, :b ; sh:property [ sh:path :p ; {constraints} ] .")
+    }
+
+    fn node(constraints: &str) -> String {
+        format!(":S a sh:NodeShape ; sh:targetNode :a, :b ; {constraints} .")
+    }
+
+    fn assert_results(shapes: &str, data: &str, expected: &[(&str, &str)]) {
+        let report = validate(shapes, data, ShaclValidationMode::Sparql);
+        let expected = expected
+            .iter()
+            .map(|(node, component)| (node.to_string(), component.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(results(&report), expected, "{shapes}\n{data}");
+    }
+
+    #[test]
+    fn value_type_components() {
+        assert_results(&node("sh:class :C"), ":a a :C . :b a :D .", &[(B, "class")]);
+        assert_results(
+            &property("sh:datatype xsd:integer"),
+            r#":a :p 1 . :b :p "x" ."#,
+            &[(B, "datatype")],
+        );
+        let report = validate(
+            &property("sh:nodeKind sh:IRI"),
+            r#":a :p :x . :b :p "x" ."#,
+            ShaclValidationMode::Sparql,
+        );
+        let focus_nodes = results(&report)
+            .into_iter()
+            .map(|(node, _)| node)
+            .collect::<Vec<_>>();
+        assert_eq!(focus_nodes, vec![B]);
+    }
+
+    #[test]
+    fn cardinality_components() {
+        assert_results(
+            &property("sh:minCount 1"),
+            ":a :p 1 . :b :q 1 .",
+            &[(B, "minCount")],
+        );
+        assert_results(
+            &property("sh:maxCount 1"),
+            ":a :p 1 . :b :p 1, 2 .",
+            &[(B, "maxCount")],
+        );
+    }
+
+    #[test]
+    fn value_range_components() {
+        assert_results(
+            &property("sh:minExclusive 5"),
+            ":a :p 6 . :b :p 5 .",
+            &[(B, "minExclusive")],
+        );
+        assert_results(
+            &property("sh:minInclusive 5"),
+            ":a :p 5 . :b :p 4 .",
+            &[(B, "minInclusive")],
+        );
+        assert_results(
+            &property("sh:maxExclusive 5"),
+            ":a :p 4 . :b :p 5 .",
+            &[(B, "maxExclusive")],
+        );
+        assert_results(
+            &property("sh:maxInclusive 5"),
+            ":a :p 5 . :b :p 6 .",
+            &[(B, "maxInclusive")],
+        );
+    }
+
+    #[test]
+    fn string_based_components() {
+        assert_results(
+            &property("sh:minLength 3"),
+            r#":a :p "abc" . :b :p "ab" ."#,
+            &[(B, "minLength")],
+        );
+        assert_results(
+            &property("sh:maxLength 2"),
+            r#":a :p "ab" . :b :p "abc" ."#,
+            &[(B, "maxLength")],
+        );
+        assert_results(
+            &property(r#"sh:pattern "^a""#),
+            r#":a :p "abc" . :b :p "bcd" ."#,
+            &[(B, "pattern")],
+        );
+        assert_results(
+            &property(r#"sh:languageIn ( "en" )"#),
+            r#":a :p "x"@en . :b :p "x"@fr ."#,
+            &[(B, "languageIn")],
+        );
+        assert_results(
+            &property("sh:uniqueLang true"),
+            r#":a :p "x"@en, "y"@es . :b :p "x"@en, "y"@en ."#,
+            &[(B, "uniqueLang")],
+        );
+    }
+
+    #[test]
+    fn property_pair_components() {
+        assert_results(
+            &property("sh:equals :q"),
+            ":a :p 1 ; :q 1 . :b :p 1 ; :q 2 .",
+            &[(B, "equals")],
+        );
+        assert_results(
+            &property("sh:disjoint :q"),
+            ":a :p 1 ; :q 2 . :b :p 1 ; :q 1 .",
+            &[(B, "disjoint")],
+        );
+        assert_results(
+            &property("sh:lessThan :q"),
+            ":a :p 1 ; :q 2 . :b :p 3 ; :q 2 .",
+            &[(B, "lessThan")],
+        );
+        assert_results(
+            &property("sh:lessThanOrEquals :q"),
+            ":a :p 2 ; :q 2 . :b :p 3 ; :q 2 .",
+            &[(B, "lessThanOrEquals")],
+        );
+    }
+
+    #[test]
+    fn logical_components() {
+        assert_results(
+            &node("sh:or ( [ sh:class :C ] [ sh:class :D ] )"),
+            ":a a :D . :b a :E .",
+            &[(B, "or")],
+        );
+        assert_results(
+            &node("sh:and ( [ sh:class :C ] [ sh:class :D ] )"),
+            ":a a :C, :D . :b a :C .",
+            &[(B, "and")],
+        );
+        assert_results(
+            &node("sh:not [ sh:class :C ]"),
+            ":a a :D . :b a :C .",
+            &[(B, "not")],
+        );
+        assert_results(
+            &node("sh:xone ( [ sh:class :C ] [ sh:class :D ] )"),
+            ":a a :C . :b a :C, :D .",
+            &[(B, "xone")],
+        );
+    }
+
+    #[test]
+    fn shape_based_components() {
+        assert_results(
+            &format!("{}\n:T sh:class :C .", property("sh:node :T")),
+            ":a :p :x . :x a :C . :b :p :y .",
+            &[(B, "node")],
+        );
+        assert_results(
+            &property("sh:qualifiedValueShape [ sh:class :C ] ; sh:qualifiedMinCount 1"),
+            ":a :p :x, :y . :x a :C . :b :p :y .",
+            &[(B, "qualifiedValueShape")],
+        );
+    }
+
+    #[test]
+    fn qualified_value_shapes_disjoint() {
+        let shapes = |disjoint: bool| {
+            format!(
+                ":S a sh:NodeShape ; sh:targetNode :a, :b ; sh:property :P1, :P2 .
+                :P1 sh:path :p ; sh:qualifiedValueShape [ sh:class :C ] ;
+                  sh:qualifiedMinCount 1 ; sh:qualifiedValueShapesDisjoint {disjoint} .
+                :P2 sh:path :p ; sh:qualifiedValueShape [ sh:class :D ] ;
+                  sh:qualifiedMinCount 1 ; sh:qualifiedValueShapesDisjoint {disjoint} ."
+            )
+        };
+        let data = ":a :p :x, :y . :x a :C . :y a :D . :b :p :z . :z a :C, :D .";
+        assert_results(&shapes(false), data, &[]);
+        assert_results(
+            &shapes(true),
+            data,
+            &[(B, "qualifiedValueShape"), (B, "qualifiedValueShape")],
+        );
+    }
+
+    #[test]
+    fn other_components() {
+        assert_results(
+            &format!(
+                "{} ; sh:closed true ; sh:ignoredProperties ( rdf:type ) .",
+                property("sh:minCount 0").trim_end_matches(" .")
+            ),
+            ":a a :C ; :p 1 . :b :p 1 ; :q 2 .",
+            &[(B, "closed")],
+        );
+        assert_results(
+            &property("sh:hasValue 1"),
+            ":a :p 1 . :b :p 2 .",
+            &[(B, "hasValue")],
+        );
+        assert_results(
+            &property("sh:in ( 1 2 )"),
+            ":a :p 1 . :b :p 3 .",
+            &[(B, "in")],
+        );
+    }
+
+    #[test]
+    fn blank_focus_nodes_are_skipped_by_sparql_engine() {
+        let shapes = ":S a sh:NodeShape ; sh:targetObjectsOf :r ; sh:closed true ;
+            sh:property [ sh:path :p ; sh:minCount 1 ] .";
+        let data = ":a :r _:x . _:x :p 1 ; :q 2 .";
+
+        // blank nodes can't be referenced from the queries, so the SPARQL engine
+        // skips them instead of reporting violations, as _:x has a value for :p
+        let report = validate(shapes, data, ShaclValidationMode::Sparql);
+        assert!(results(&report).is_empty());
+    }
+
+    #[test]
+    fn blank_focus_nodes_in_native_engine() {
+        let shapes =
+            property("sh:minCount 1").replace("sh:targetNode :a, :b", "sh:targetObjectsOf :r");
+        let data = ":a :r _:x, _:y . _:x :p 1 .";
+        let report = validate(&shapes, data, ShaclValidationMode::Native);
+        let results = results(&report);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, "minCount");
+        assert_ne!(results[0].0, A);
+    }
+
+    #[test]
+    fn complex_paths_in_both_engines() {
+        let data = ":a :p :c . :c :p :d . :x :q :a . :b :p :e .";
+        let cases = [
+            (
+                "[ sh:alternativePath ( :p [ sh:inversePath :q ] ) ]",
+                "sh:minCount 2",
+                B,
+            ),
+            ("[ sh:inversePath :q ]", "sh:minCount 1", B),
+            ("( :p :p )", "sh:minCount 1", B),
+            ("[ sh:oneOrMorePath :p ]", "sh:maxCount 1", A),
+            ("[ sh:zeroOrMorePath :p ]", "sh:maxCount 2", A),
+            (
+                "[ sh:zeroOrOnePath [ sh:inversePath :q ] ]",
+                "sh:minCount 2",
+                B,
+            ),
+        ];
+        for (path, constraint, focus_node) in cases {
+            let shapes = format!(
+                ":S a sh:NodeShape ; sh:targetNode :a, :b ; sh:property [ sh:path {path} ; {constraint} ] ."
+            );
+            for mode in [ShaclValidationMode::Native, ShaclValidationMode::Sparql] {
+                let report = validate(&shapes, data, mode);
+                let focus_nodes = results(&report)
+                    .into_iter()
+                    .map(|(node, _)| node)
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    focus_nodes,
+                    vec![focus_node],
+                    "{path} {constraint} {mode:?}"
+                );
+            }
+        }
+    }
+}
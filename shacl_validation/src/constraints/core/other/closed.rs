@@ -1,7 +1,9 @@
+use indoc::formatdoc;
 use shacl_ast::compiled::component::Closed;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
+use srdf::SHACLPath;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -10,9 +12,12 @@ use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
 use crate::constraints::Validator;
 use crate::engine::native::NativeEngine;
-use crate::engine::sparql::SparqlEngine;
 use crate::engine::Engine;
+use crate::helpers::constraint::validate_with_focus;
+use crate::helpers::helper_error::SPARQLError;
+use crate::helpers::sparql::ask;
 use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
 
 impl<S: SRDFBasic> Validator<S> for Closed<S> {
@@ -28,7 +33,7 @@ impl<S: SRDFBasic> Validator<S> for Closed<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for Closed<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Closed<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -40,7 +45,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Closed<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Closed<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Closed<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -48,6 +53,40 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for Closed<S> {
         store: &S,
         value_nodes: &ValueNodes<S>,
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
-        self.validate(component, shape, store, SparqlEngine, value_nodes)
+        if !self.is_closed() {
+            return Ok(Vec::default());
+        }
+
+        let allowed = shape
+            .property_shapes()
+            .iter()
+            .filter_map(|property_shape| match property_shape {
+                CompiledShape::PropertyShape(ps) => match ps.path() {
+                    SHACLPath::Predicate { pred } => Some(pred.to_owned()),
+                    _ => None,
+                },
+                CompiledShape::NodeShape(_) => None,
+            })
+            .chain(self.ignored_properties().iter().map(S::iri2iri_s))
+            .map(|iri| format!("<{}>", iri.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let closed = |_: &S::Term, value_node: &S::Term| {
+            if S::term_is_bnode(value_node) {
+                return Err(SPARQLError::BlankNode(value_node.to_string()).into());
+            }
+
+            let query = formatdoc! {"
+                ASK {{
+                    {} ?predicate ?object .
+                    FILTER (?predicate NOT IN ({}))
+                }}
+            ", value_node, allowed};
+
+            Ok(ask(store, query)?)
+        };
+
+        validate_with_focus(component, shape, value_nodes, ValueNodeIteration, closed)
     }
 }
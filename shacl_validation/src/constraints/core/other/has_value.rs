@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::HasValue;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -33,7 +33,7 @@ impl<S: SRDFBasic> Validator<S> for HasValue<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for HasValue<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for HasValue<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -45,7 +45,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for HasValue<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for HasValue<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for HasValue<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
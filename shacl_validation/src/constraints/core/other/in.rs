@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::In;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -30,7 +30,7 @@ impl<S: SRDFBasic> Validator<S> for In<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for In<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for In<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -42,7 +42,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for In<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for In<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for In<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -1,16 +1,19 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Disjoint;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDF;
 
 use crate::constraints::constraint_error::ConstraintError;
 use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
+use crate::helpers::constraint::validate_with_focus;
+use crate::helpers::sparql::objects_for;
 use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
 
-impl<S: SRDF + 'static> NativeValidator<S> for Disjoint<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Disjoint<S> {
     fn validate_native(
         &self,
         _component: &CompiledComponent<S>,
@@ -22,14 +25,19 @@ impl<S: SRDF + 'static> NativeValidator<S> for Disjoint<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Disjoint<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Disjoint<S> {
     fn validate_sparql(
         &self,
-        _component: &CompiledComponent<S>,
-        _shape: &CompiledShape<S>,
-        _store: &S,
-        _value_nodes: &ValueNodes<S>,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
-        Err(ConstraintError::NotImplemented("Disjoint".to_string()))
+        let disjoint = |focus_node: &S::Term, value_node: &S::Term| {
+            let objects = objects_for(store, focus_node, self.iri_ref())?;
+            Ok(objects.contains(value_node))
+        };
+
+        validate_with_focus(component, shape, value_nodes, ValueNodeIteration, disjoint)
     }
 }
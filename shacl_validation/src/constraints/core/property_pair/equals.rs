@@ -1,7 +1,9 @@
+use std::collections::HashSet;
+
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Equals;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -10,9 +12,12 @@ use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
 use crate::constraints::Validator;
 use crate::engine::native::NativeEngine;
-use crate::engine::sparql::SparqlEngine;
 use crate::engine::Engine;
+use crate::focus_nodes::FocusNodes;
+use crate::helpers::constraint::validate_with_focus;
+use crate::helpers::sparql::objects_for;
 use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::FocusNodeIteration;
 use crate::value_nodes::ValueNodes;
 
 impl<S: SRDFBasic> Validator<S> for Equals<S> {
@@ -28,7 +33,7 @@ impl<S: SRDFBasic> Validator<S> for Equals<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for Equals<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Equals<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -40,7 +45,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Equals<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Equals<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Equals<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -48,6 +53,12 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for Equals<S> {
         store: &S,
         value_nodes: &ValueNodes<S>,
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
-        self.validate(component, shape, store, SparqlEngine, value_nodes)
+        let equals = |focus_node: &S::Term, targets: &FocusNodes<S>| {
+            let objects = objects_for(store, focus_node, self.iri_ref())?;
+            let values = targets.iter().cloned().collect::<HashSet<_>>();
+            Ok(objects != values)
+        };
+
+        validate_with_focus(component, shape, value_nodes, FocusNodeIteration, equals)
     }
 }
@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::QualifiedValueShape;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -12,25 +12,65 @@ use crate::constraints::Validator;
 use crate::engine::native::NativeEngine;
 use crate::engine::sparql::SparqlEngine;
 use crate::engine::Engine;
+use crate::focus_nodes::FocusNodes;
+use crate::helpers::constraint::validate_with;
+use crate::shape::Validate;
 use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::FocusNodeIteration;
 use crate::value_nodes::ValueNodes;
 
 impl<S: SRDFBasic> Validator<S> for QualifiedValueShape<S> {
     fn validate(
         &self,
-        _component: &CompiledComponent<S>,
-        _shape: &CompiledShape<S>,
-        _store: &S,
-        _engine: impl Engine<S>,
-        _value_nodes: &ValueNodes<S>,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        engine: impl Engine<S>,
+        value_nodes: &ValueNodes<S>,
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
-        Err(ConstraintError::NotImplemented(
-            "QualifiedValueShape".to_string(),
-        ))
+        let conforms = |shape: &CompiledShape<S>, value_node: &S::Term| {
+            let focus_nodes = FocusNodes::new(std::iter::once(value_node.clone()));
+            match shape.validate(store, &engine, Some(&focus_nodes)) {
+                Ok(results) => results.is_empty(),
+                Err(_) => false,
+            }
+        };
+
+        // with sh:qualifiedValueShapesDisjoint, the value nodes that conform
+        // to some sibling shape are not counted
+        let qualified_value_shape = |targets: &FocusNodes<S>| {
+            let conforming = targets
+                .iter()
+                .filter(|value_node| {
+                    conforms(self.shape(), value_node)
+                        && !self
+                            .siblings()
+                            .iter()
+                            .any(|sibling| conforms(sibling, value_node))
+                })
+                .count() as isize;
+
+            let below_min = self
+                .qualified_min_count()
+                .is_some_and(|min| conforming < min);
+            let above_max = self
+                .qualified_max_count()
+                .is_some_and(|max| conforming > max);
+
+            below_min || above_max
+        };
+
+        validate_with(
+            component,
+            shape,
+            value_nodes,
+            FocusNodeIteration,
+            qualified_value_shape,
+        )
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for QualifiedValueShape<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for QualifiedValueShape<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -42,7 +82,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for QualifiedValueShape<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for QualifiedValueShape<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for QualifiedValueShape<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -1,7 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::LanguageIn;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -27,12 +27,14 @@ impl<S: SRDFBasic> Validator<S> for LanguageIn<S> {
         value_nodes: &ValueNodes<S>,
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
         let language_in = |value_node: &S::Term| {
-            if let Some(lang) = S::term_as_literal(value_node) {
-                if self.langs().contains(&lang) {
-                    return false;
-                }
+            let lang = S::term_as_literal(value_node).and_then(|literal| S::lang(&literal));
+            match lang {
+                Some(lang) => !self
+                    .langs()
+                    .iter()
+                    .any(|range| lang_matches(&lang, S::lexical_form(range))),
+                None => true,
             }
-            true
         };
 
         validate_with(
@@ -45,7 +47,7 @@ impl<S: SRDFBasic> Validator<S> for LanguageIn<S> {
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for LanguageIn<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for LanguageIn<S> {
     fn validate_native<'a>(
         &self,
         component: &CompiledComponent<S>,
@@ -57,7 +59,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for LanguageIn<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for LanguageIn<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for LanguageIn<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -68,3 +70,11 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for LanguageIn<S> {
         self.validate(component, shape, store, SparqlEngine, value_nodes)
     }
 }
+
+/// Basic filtering of language tags, as done by the SPARQL `langMatches`
+/// function, which is the matching used by sh:languageIn
+fn lang_matches(lang: &str, range: &str) -> bool {
+    let lang = lang.to_lowercase();
+    let range = range.to_lowercase();
+    lang == range || lang.starts_with(&format!("{range}-"))
+}
@@ -2,19 +2,19 @@ use indoc::formatdoc;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::MaxLength;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDF;
 
 use crate::constraints::constraint_error::ConstraintError;
 use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
-use crate::helpers::constraint::validate_ask_with;
+use crate::helpers::constraint::validate_non_blank_ask_with;
 use crate::helpers::constraint::validate_with;
 use crate::validation_report::result::ValidationResult;
 use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
 
-impl<S: SRDF + 'static> NativeValidator<S> for MaxLength {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for MaxLength {
     fn validate_native<'a>(
         &self,
         component: &CompiledComponent<S>,
@@ -44,7 +44,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for MaxLength {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for MaxLength {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for MaxLength {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -61,6 +61,6 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for MaxLength {
             }
         };
 
-        validate_ask_with(component, shape, store, value_nodes, query)
+        validate_non_blank_ask_with(component, shape, store, value_nodes, query)
     }
 }
@@ -2,19 +2,20 @@ use indoc::formatdoc;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Pattern;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDF;
 
 use crate::constraints::constraint_error::ConstraintError;
 use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
-use crate::helpers::constraint::validate_ask_with;
+use crate::helpers::constraint::validate_non_blank_ask_with;
 use crate::helpers::constraint::validate_with;
+use crate::helpers::sparql::string_literal;
 use crate::validation_report::result::ValidationResult;
 use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
 
-impl<S: SRDF + 'static> NativeValidator<S> for Pattern {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Pattern {
     fn validate_native<'a>(
         &self,
         component: &CompiledComponent<S>,
@@ -33,7 +34,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Pattern {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Pattern {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Pattern {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -41,8 +42,8 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for Pattern {
         store: &S,
         value_nodes: &ValueNodes<S>,
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
-        let flags = self.flags().clone();
-        let pattern = self.pattern().clone();
+        let flags = self.flags().as_deref().map(string_literal);
+        let pattern = string_literal(self.pattern());
 
         let query = |value_node: &S::Term| match &flags {
             Some(flags) => formatdoc! {
@@ -55,6 +56,6 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for Pattern {
             },
         };
 
-        validate_ask_with(component, shape, store, value_nodes, query)
+        validate_non_blank_ask_with(component, shape, store, value_nodes, query)
     }
 }
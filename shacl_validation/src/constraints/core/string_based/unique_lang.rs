@@ -1,10 +1,9 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+use std::collections::HashSet;
 
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::UniqueLang;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -15,9 +14,10 @@ use crate::constraints::Validator;
 use crate::engine::native::NativeEngine;
 use crate::engine::sparql::SparqlEngine;
 use crate::engine::Engine;
+use crate::focus_nodes::FocusNodes;
 use crate::helpers::constraint::validate_with;
 use crate::validation_report::result::ValidationResult;
-use crate::value_nodes::ValueNodeIteration;
+use crate::value_nodes::FocusNodeIteration;
 use crate::value_nodes::ValueNodes;
 
 impl<S: SRDFBasic> Validator<S> for UniqueLang {
@@ -33,34 +33,27 @@ impl<S: SRDFBasic> Validator<S> for UniqueLang {
             return Ok(Default::default());
         }
 
-        let langs: Rc<RefCell<Vec<_>>> = Rc::new(RefCell::new(Vec::new()));
-
-        let unique_lang = |value_node: &S::Term| {
-            if let Some(literal) = S::term_as_literal(value_node) {
-                if let Some(lang) = S::lang(&literal) {
-                    let mut langs_borrowed = langs.borrow_mut();
-
-                    if langs_borrowed.contains(&lang) {
-                        return true;
-                    } else {
-                        langs_borrowed.push(lang);
-                    }
-                }
-            }
-            false
+        // the language tags are compared among the value nodes of each focus node
+        let unique_lang = |targets: &FocusNodes<S>| {
+            let mut langs = HashSet::new();
+            targets
+                .iter()
+                .filter_map(|value_node| S::term_as_literal(value_node))
+                .filter_map(|literal| S::lang(&literal))
+                .any(|lang| !langs.insert(lang.to_lowercase()))
         };
 
         validate_with(
             component,
             shape,
             value_nodes,
-            ValueNodeIteration,
+            FocusNodeIteration,
             unique_lang,
         )
     }
 }
 
-impl<S: SRDF + 'static> NativeValidator<S> for UniqueLang {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for UniqueLang {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -72,7 +65,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for UniqueLang {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for UniqueLang {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for UniqueLang {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -2,7 +2,7 @@ use indoc::formatdoc;
 use shacl_ast::compiled::component::Class;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::RDFS_SUBCLASS_OF;
 use srdf::RDF_TYPE;
 use srdf::SRDF;
@@ -17,7 +17,7 @@ use crate::validation_report::result::ValidationResult;
 use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
 
-impl<S: SRDF + 'static> NativeValidator<S> for Class<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Class<S> {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -47,7 +47,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Class<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Class<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Class<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -5,19 +5,20 @@ use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::Nodekind;
 use shacl_ast::compiled::shape::CompiledShape;
 use shacl_ast::node_kind::NodeKind;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDF;
 
 use crate::constraints::constraint_error::ConstraintError;
 use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
-use crate::helpers::constraint::validate_ask_with;
+use crate::helpers::constraint::ask_violation;
 use crate::helpers::constraint::validate_with;
+use crate::helpers::constraint::validate_with_focus;
 use crate::validation_report::result::ValidationResult;
 use crate::value_nodes::ValueNodeIteration;
 use crate::value_nodes::ValueNodes;
 
-impl<S: SRDF + 'static> NativeValidator<S> for Nodekind {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Nodekind {
     fn validate_native(
         &self,
         component: &CompiledComponent<S>,
@@ -52,7 +53,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for Nodekind {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for Nodekind {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Nodekind {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -72,18 +73,26 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for Nodekind {
             } else if S::term_is_bnode(value_node) {
                 formatdoc! {"
                         PREFIX sh: <http://www.w3.org/ns/shacl#>
-                        ASK {{ FILTER ({} IN ( sh:Literal, sh:BlankNodeOrLiteral, sh:IRIOrLiteral ) ) }}
+                        ASK {{ FILTER ({} IN ( sh:BlankNode, sh:BlankNodeOrIRI, sh:BlankNodeOrLiteral ) ) }}
                     ", node_kind
                 }
             } else {
                 formatdoc! {"
                         PREFIX sh: <http://www.w3.org/ns/shacl#>
-                        ASK {{ FILTER ({} IN ( sh:BlankNode, sh:BlankNodeOrIRI, sh:BlankNodeOrLiteral ) ) }}
+                        ASK {{ FILTER ({} IN ( sh:Literal, sh:BlankNodeOrLiteral, sh:IRIOrLiteral ) ) }}
                     ", node_kind
                 }
             }
         };
 
-        validate_ask_with(component, shape, store, value_nodes, query)
+        // the query only depends on the kind of the value node, so blank nodes
+        // can be checked too
+        validate_with_focus(
+            component,
+            shape,
+            value_nodes,
+            ValueNodeIteration,
+            |_, value_node| ask_violation(store, &query(value_node)),
+        )
     }
 }
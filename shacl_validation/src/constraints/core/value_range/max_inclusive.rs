@@ -2,17 +2,17 @@ use indoc::formatdoc;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::component::MaxInclusive;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDF;
 
 use crate::constraints::constraint_error::ConstraintError;
 use crate::constraints::NativeValidator;
 use crate::constraints::SparqlValidator;
-use crate::helpers::constraint::validate_ask_with;
+use crate::helpers::constraint::validate_non_blank_ask_with;
 use crate::validation_report::result::ValidationResult;
 use crate::value_nodes::ValueNodes;
 
-impl<S: SRDF + 'static> NativeValidator<S> for MaxInclusive<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for MaxInclusive<S> {
     fn validate_native(
         &self,
         _component: &CompiledComponent<S>,
@@ -24,7 +24,7 @@ impl<S: SRDF + 'static> NativeValidator<S> for MaxInclusive<S> {
     }
 }
 
-impl<S: QuerySRDF + 'static> SparqlValidator<S> for MaxInclusive<S> {
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for MaxInclusive<S> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -36,11 +36,11 @@ impl<S: QuerySRDF + 'static> SparqlValidator<S> for MaxInclusive<S> {
 
         let query = |value_node: &S::Term| {
             formatdoc! {
-                " ASK {{ FILTER ({} >= {}) }} ",
+                " ASK {{ FILTER ({} <= {}) }} ",
                 value_node, max_inclusive_value
             }
         };
 
-        validate_ask_with(component, shape, store, value_nodes, query)
+        validate_non_blank_ask_with(component, shape, store, value_nodes, query)
     }
 }
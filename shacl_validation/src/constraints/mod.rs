@@ -1,7 +1,7 @@
 use constraint_error::ConstraintError;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
@@ -11,6 +11,7 @@ use crate::value_nodes::ValueNodes;
 
 pub mod constraint_error;
 pub mod core;
+pub mod sparql;
 
 pub trait Validator<S: SRDFBasic> {
     fn validate(
@@ -33,7 +34,7 @@ pub trait NativeValidator<S: SRDF> {
     ) -> Result<Vec<ValidationResult<S>>, ConstraintError>;
 }
 
-pub trait SparqlValidator<S: QuerySRDF> {
+pub trait SparqlValidator<S: QuerySRDF2> {
     fn validate_sparql(
         &self,
         component: &CompiledComponent<S>,
@@ -59,7 +60,7 @@ pub trait NativeDeref {
     fn deref(&self) -> &Self::Target;
 }
 
-impl<S: SRDF + 'static> NativeDeref for CompiledComponent<S> {
+impl<S: SRDF + QuerySRDF2 + 'static> NativeDeref for CompiledComponent<S> {
     type Target = dyn NativeValidator<S>;
 
     generate_deref_fn!(
@@ -90,7 +91,9 @@ impl<S: SRDF + 'static> NativeDeref for CompiledComponent<S> {
         Node,
         HasValue,
         In,
-        QualifiedValueShape
+        QualifiedValueShape,
+        Sparql,
+        SparqlComponent
     );
 }
 
@@ -100,7 +103,7 @@ pub trait SparqlDeref {
     fn deref(&self) -> &Self::Target;
 }
 
-impl<S: QuerySRDF + 'static> SparqlDeref for CompiledComponent<S> {
+impl<S: QuerySRDF2 + 'static> SparqlDeref for CompiledComponent<S> {
     type Target = dyn SparqlValidator<S>;
 
     generate_deref_fn!(
@@ -131,6 +134,8 @@ impl<S: QuerySRDF + 'static> SparqlDeref for CompiledComponent<S> {
         Node,
         HasValue,
         In,
-        QualifiedValueShape
+        QualifiedValueShape,
+        Sparql,
+        SparqlComponent
     );
 }
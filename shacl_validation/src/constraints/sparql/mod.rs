@@ -0,0 +1,255 @@
+use iri_s::IriS;
+use shacl_ast::compiled::component::CompiledComponent;
+use shacl_ast::compiled::shape::CompiledShape;
+use srdf::literal::Literal;
+use srdf::QuerySRDF2;
+use srdf::SHACLPath;
+use srdf::SRDFBasic;
+use tracing::warn;
+
+use crate::constraints::constraint_error::ConstraintError;
+use crate::helpers::helper_error::SPARQLError;
+use crate::helpers::message;
+use crate::helpers::sparql::prebind;
+use crate::validation_report::result::ValidationResult;
+
+pub(crate) mod sparql_component;
+pub(crate) mod sparql_constraint;
+
+/// Variables that are pre-bound for every focus node: `$this` and
+/// `$currentShape`. The shapes graph is not a graph of the data, so
+/// `$shapesGraph` is left unbound
+fn focus_bindings<'a, S: SRDFBasic>(
+    shape: &'a CompiledShape<S>,
+    focus_node: &'a S::Term,
+) -> Vec<(&'a str, &'a S::Term)> {
+    vec![("this", focus_node), ("currentShape", shape.id())]
+}
+
+/// The messages of the constraint, or those of the shape if it has none
+fn result_messages<S: SRDFBasic>(
+    shape: &CompiledShape<S>,
+    messages: &[Literal],
+    binding: impl Fn(&str) -> Option<S::Term>,
+) -> Vec<S::Term> {
+    match messages.is_empty() {
+        true => message::messages::<S>(shape.messages(), binding),
+        false => message::messages::<S>(messages, binding),
+    }
+}
+
+/// Evaluates a SELECT query for a focus node, there is a validation result
+/// for each of its solutions. Blank focus nodes can't be pre-bound, so they
+/// are skipped with a warning.
+///
+/// https://www.w3.org/TR/shacl/#sparql-constraints-validation
+fn select_results<S: QuerySRDF2>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    query: &str,
+    bindings: &[(&str, &S::Term)],
+    messages: &[Literal],
+) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+    let path = match shape {
+        CompiledShape::NodeShape(_) => None,
+        CompiledShape::PropertyShape(ps) => Some(ps.path()),
+    };
+    let focus_node = bindings[0].1;
+
+    let query = match prebind::<S>(query, bindings, path) {
+        Ok(query) => query,
+        Err(error @ SPARQLError::BlankNode(_)) => {
+            warn!(
+                "Constraint {} of shape {} not checked for focus node {focus_node}: {error}",
+                IriS::from(component),
+                shape.id()
+            );
+            return Ok(Vec::new());
+        }
+        Err(error) => return Err(error.into()),
+    };
+    let solutions = store
+        .query_select(&query)
+        .map_err(|err| ConstraintError::Query(format!("SELECT query failed: {}", err)))?;
+
+    let mut results = Vec::new();
+    for solution in solutions.iter() {
+        if let Some(true) = solution
+            .find_solution("failure")
+            .and_then(S::term_as_boolean)
+        {
+            return Err(ConstraintError::Query(format!(
+                "Failure reported validating {focus_node}"
+            )));
+        }
+
+        let binding = |name: &str| {
+            solution
+                .find_solution(name)
+                .or_else(|| bindings.iter().find(|(var, _)| *var == name).map(|b| b.1))
+                .cloned()
+        };
+
+        // for node shapes, the focus node is the value node if the query does
+        // not bind $value
+        let value = match (binding("value"), shape) {
+            (None, CompiledShape::NodeShape(_)) => Some(focus_node.clone()),
+            (value, _) => value,
+        };
+
+        results.push(ValidationResult::new(
+            binding("this").unwrap_or(focus_node.clone()),
+            // the path of the solution has to be an IRI
+            binding("path")
+                .and_then(|path| S::term_as_iri(&path))
+                .map(|iri| SHACLPath::iri(S::iri2iri_s(&iri)))
+                .or(shape.path().cloned()),
+            value,
+            Some(shape.id().to_owned()),
+            S::iri_s2term(&component.into()),
+            None,
+            match binding("message") {
+                Some(message) => vec![message],
+                None => result_messages(shape, messages, binding),
+            },
+            shape.severity(),
+        ));
+    }
+
+    Ok(results)
+}
+
+/// Evaluates an ASK query for a value node, there is a validation result if
+/// it returns false. Blank nodes can't be pre-bound, so they are skipped with
+/// a warning.
+///
+/// https://www.w3.org/TR/shacl/#ASKValidator
+fn ask_result<S: QuerySRDF2>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    query: &str,
+    bindings: &[(&str, &S::Term)],
+    messages: &[Literal],
+) -> Result<Option<ValidationResult<S>>, ConstraintError> {
+    let binding = |name: &str| {
+        bindings
+            .iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, term)| (*term).clone())
+    };
+
+    let query = match prebind::<S>(query, bindings, None) {
+        Ok(query) => query,
+        Err(error @ SPARQLError::BlankNode(_)) => {
+            warn!(
+                "Constraint {} of shape {} not checked for focus node {}: {error}",
+                IriS::from(component),
+                shape.id(),
+                bindings[0].1
+            );
+            return Ok(None);
+        }
+        Err(error) => return Err(error.into()),
+    };
+    let conforms = store
+        .query_ask(&query)
+        .map_err(|err| ConstraintError::Query(format!("ASK query failed: {}", err)))?;
+
+    if conforms {
+        return Ok(None);
+    }
+
+    Ok(Some(ValidationResult::new(
+        bindings[0].1.to_owned(),
+        shape.path().cloned(),
+        binding("value"),
+        Some(shape.id().to_owned()),
+        S::iri_s2term(&component.into()),
+        None,
+        result_messages(shape, messages, binding),
+        shape.severity(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shacl_processor::ShaclValidationMode;
+    use crate::test_utils::results;
+    use crate::test_utils::validate;
+
+    const A: &str = "<http://example.org/a>";
+    const B: &str = "<http://example.org/b>";
+    const SPARQL: &str = "SPARQLConstraintComponent";
+
+    fn assert_results(shapes: &str, data: &str, expected: &[(&str, &str)]) {
+        let expected = expected
+            .iter()
+            .map(|(node, component)| (node.to_string(), component.to_string()))
+            .collect::<Vec<_>>();
+        for mode in [ShaclValidationMode::Native, ShaclValidationMode::Sparql] {
+            let report = validate(shapes, data, mode);
+            assert_eq!(results(&report), expected, "{shapes}\n{data}");
+        }
+    }
+
+    #[test]
+    fn sparql_constraint_prebinds_this() {
+        // the comment, the string literal and the subquery must not be
+        // affected by the pre-binding
+        let shapes = r#":S a sh:NodeShape ; sh:targetNode :a, :b ;
+            sh:sparql [ sh:select """
+                # a comment with { and $this
+                PREFIX : <http://example.org/>
+                SELECT $this WHERE {
+                    { SELECT $this (COUNT(?v) AS ?n) WHERE { $this :p ?v } GROUP BY $this }
+                    FILTER (?n > 1 && STR($this) != "$this")
+                }""" ] ."#;
+        assert_results(shapes, ":a :p 1, 2 . :b :p 1 .", &[(A, SPARQL)]);
+    }
+
+    #[test]
+    fn sparql_constraint_skips_blank_focus_nodes() {
+        // _:x would be a variable in the pre-bound query, so it is skipped
+        // instead of being reported
+        let shapes = r#":S a sh:NodeShape ; sh:targetSubjectsOf :p ;
+            sh:sparql [ sh:select """
+                PREFIX : <http://example.org/>
+                SELECT $this WHERE { $this :p 2 }""" ] ."#;
+        assert_results(shapes, ":a :p 2 . [] :p 2 .", &[(A, SPARQL)]);
+    }
+
+    #[test]
+    fn select_validator_prebinds_path_and_parameters() {
+        let shapes = r#":MaxValuesComponent a sh:ConstraintComponent ;
+                sh:parameter [ sh:path :maxValues ] ;
+                sh:propertyValidator [ a sh:SPARQLSelectValidator ; sh:select """
+                    SELECT $this WHERE { $this $PATH ?v }
+                    GROUP BY $this HAVING (COUNT(?v) > $maxValues)""" ] .
+            :S a sh:NodeShape ; sh:targetNode :a, :b ;
+                sh:property [ sh:path [ sh:alternativePath ( :p [ sh:inversePath :q ] ) ] ;
+                    :maxValues 1 ] ."#;
+        assert_results(
+            shapes,
+            ":a :p 1 . :x :q :a . :b :p 1 .",
+            &[(A, "http://example.org/MaxValuesComponent")],
+        );
+    }
+
+    #[test]
+    fn ask_validator_prebinds_value_and_leaves_shapes_graph_unbound() {
+        let shapes = r#":ForbiddenComponent a sh:ConstraintComponent ;
+                sh:parameter [ sh:path :forbidden ] ;
+                sh:validator [ a sh:SPARQLAskValidator ; sh:ask """
+                    ASK { FILTER ($value != $forbidden
+                        && $currentShape = <http://example.org/S>
+                        && !BOUND($shapesGraph)) }""" ] .
+            :S a sh:NodeShape ; sh:targetNode :a, :b ; :forbidden :b ."#;
+        assert_results(
+            shapes,
+            ":a :p 1 . :b :p 1 .",
+            &[(B, "http://example.org/ForbiddenComponent")],
+        );
+    }
+}
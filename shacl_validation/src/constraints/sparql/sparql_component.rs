@@ -0,0 +1,107 @@
+use shacl_ast::compiled::component::CompiledComponent;
+use shacl_ast::compiled::component::SparqlComponent;
+use shacl_ast::compiled::shape::CompiledShape;
+use shacl_ast::sparql_constraint::ValidatorKind;
+use srdf::QuerySRDF2;
+use srdf::SRDF;
+
+use crate::constraints::constraint_error::ConstraintError;
+use crate::constraints::NativeValidator;
+use crate::constraints::SparqlValidator;
+use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::FocusNodeIteration;
+use crate::value_nodes::IterationStrategy;
+use crate::value_nodes::ValueNodeIteration;
+use crate::value_nodes::ValueNodes;
+
+use super::ask_result;
+use super::focus_bindings;
+use super::select_results;
+
+/// ASK validators are evaluated for each value node, with `$value` pre-bound,
+/// while SELECT validators are evaluated once for each focus node. In both
+/// cases the parameters of the component are pre-bound to the values given
+/// by the shape.
+fn validate_component<S: QuerySRDF2>(
+    sparql_component: &SparqlComponent<S>,
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    value_nodes: &ValueNodes<S>,
+) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+    let validator = match shape {
+        CompiledShape::NodeShape(_) => sparql_component.node_validator(),
+        CompiledShape::PropertyShape(_) => sparql_component.property_validator(),
+    };
+
+    // components without a suitable validator are ignored
+    let validator = match validator {
+        Some(validator) => validator,
+        None => return Ok(Vec::new()),
+    };
+
+    let query = validator.query();
+    let parameters = sparql_component
+        .parameters()
+        .iter()
+        .map(|(name, value)| (name.as_str(), value));
+
+    let mut results = Vec::new();
+    match validator.kind() {
+        ValidatorKind::Ask => {
+            for (focus_node, value_node) in ValueNodeIteration.iterate(value_nodes) {
+                let mut bindings = focus_bindings(shape, focus_node);
+                bindings.push(("value", value_node));
+                bindings.extend(parameters.clone());
+                results.extend(ask_result(
+                    component,
+                    shape,
+                    store,
+                    &query,
+                    &bindings,
+                    validator.messages(),
+                )?);
+            }
+        }
+        ValidatorKind::Select => {
+            for (focus_node, _) in FocusNodeIteration.iterate(value_nodes) {
+                let mut bindings = focus_bindings(shape, focus_node);
+                bindings.extend(parameters.clone());
+                results.extend(select_results(
+                    component,
+                    shape,
+                    store,
+                    &query,
+                    &bindings,
+                    validator.messages(),
+                )?);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for SparqlComponent<S> {
+    fn validate_native(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+        validate_component(self, component, shape, store, value_nodes)
+    }
+}
+
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for SparqlComponent<S> {
+    fn validate_sparql(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+        validate_component(self, component, shape, store, value_nodes)
+    }
+}
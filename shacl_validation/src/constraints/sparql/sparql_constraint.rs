@@ -0,0 +1,68 @@
+use shacl_ast::compiled::component::CompiledComponent;
+use shacl_ast::compiled::component::Sparql;
+use shacl_ast::compiled::shape::CompiledShape;
+use srdf::QuerySRDF2;
+use srdf::SRDF;
+
+use crate::constraints::constraint_error::ConstraintError;
+use crate::constraints::NativeValidator;
+use crate::constraints::SparqlValidator;
+use crate::validation_report::result::ValidationResult;
+use crate::value_nodes::FocusNodeIteration;
+use crate::value_nodes::IterationStrategy;
+use crate::value_nodes::ValueNodes;
+
+use super::focus_bindings;
+use super::select_results;
+
+/// The SELECT query is evaluated over the store in both engines, as SPARQL is
+/// the language in which the constraint is defined.
+fn validate_sparql<S: QuerySRDF2>(
+    sparql: &Sparql,
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    value_nodes: &ValueNodes<S>,
+) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+    if sparql.is_deactivated() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+    for (focus_node, _) in FocusNodeIteration.iterate(value_nodes) {
+        results.extend(select_results(
+            component,
+            shape,
+            store,
+            sparql.query(),
+            &focus_bindings(shape, focus_node),
+            sparql.messages(),
+        )?);
+    }
+
+    Ok(results)
+}
+
+impl<S: SRDF + QuerySRDF2 + 'static> NativeValidator<S> for Sparql {
+    fn validate_native(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+        validate_sparql(self, component, shape, store, value_nodes)
+    }
+}
+
+impl<S: QuerySRDF2 + 'static> SparqlValidator<S> for Sparql {
+    fn validate_sparql(
+        &self,
+        component: &CompiledComponent<S>,
+        shape: &CompiledShape<S>,
+        store: &S,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+        validate_sparql(self, component, shape, store, value_nodes)
+    }
+}
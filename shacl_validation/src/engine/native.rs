@@ -1,6 +1,7 @@
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::property_shape::CompiledPropertyShape;
 use shacl_ast::compiled::shape::CompiledShape;
+use srdf::QuerySRDF2;
 use srdf::SHACLPath;
 use srdf::RDFS_CLASS;
 use srdf::RDFS_SUBCLASS_OF;
@@ -10,6 +11,7 @@ use srdf::SRDF;
 use crate::constraints::NativeDeref;
 use crate::focus_nodes::FocusNodes;
 use crate::helpers::srdf::get_objects_for;
+use crate::helpers::srdf::get_path_values;
 use crate::helpers::srdf::get_subjects_for;
 use crate::validate_error::ValidateError;
 use crate::validation_report::result::ValidationResult;
@@ -19,7 +21,7 @@ use super::Engine;
 
 pub struct NativeEngine;
 
-impl<S: SRDF + 'static> Engine<S> for NativeEngine {
+impl<S: SRDF + QuerySRDF2 + 'static> Engine<S> for NativeEngine {
     fn evaluate(
         &self,
         store: &S,
@@ -93,6 +95,12 @@ impl<S: SRDF + 'static> Engine<S> for NativeEngine {
         store: &S,
         shape: &CompiledShape<S>,
     ) -> Result<FocusNodes<S>, ValidateError> {
+        // the blank nodes of the shapes graph are not nodes of the data graph,
+        // so only shapes identified by an IRI can act as implicit classes
+        if !S::term_is_iri(shape.id()) {
+            return Ok(FocusNodes::default());
+        }
+
         let ctypes = get_objects_for(store, shape.id(), &S::iri_s2iri(&RDF_TYPE))?;
 
         let mut subclasses = get_subjects_for(
@@ -137,61 +145,91 @@ impl<S: SRDF + 'static> Engine<S> for NativeEngine {
 
     fn alternative(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Alternative {
+            paths: paths.to_vec(),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn sequence(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Sequence {
+            paths: paths.to_vec(),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn inverse(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Inverse {
+            path: Box::new(path.to_owned()),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn zero_or_more(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrMore {
+            path: Box::new(path.to_owned()),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn one_or_more(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::OneOrMore {
+            path: Box::new(path.to_owned()),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn zero_or_one(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrOne {
+            path: Box::new(path.to_owned()),
+        };
+        path_values(store, &path, focus_node)
     }
 }
+
+/// Obtains the value nodes reachable from the focus node through the given
+/// path by traversing the data graph
+fn path_values<S: SRDF>(
+    store: &S,
+    path: &SHACLPath,
+    focus_node: &S::Term,
+) -> Result<FocusNodes<S>, ValidateError> {
+    Ok(FocusNodes::new(
+        get_path_values(store, focus_node, path, false)?.into_iter(),
+    ))
+}
@@ -2,7 +2,7 @@ use indoc::formatdoc;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::property_shape::CompiledPropertyShape;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SHACLPath;
 
 use crate::constraints::SparqlDeref;
@@ -16,7 +16,7 @@ use super::Engine;
 
 pub struct SparqlEngine;
 
-impl<S: QuerySRDF + 'static> Engine<S> for SparqlEngine {
+impl<S: QuerySRDF2 + 'static> Engine<S> for SparqlEngine {
     fn evaluate(
         &self,
         store: &S,
@@ -42,9 +42,7 @@ impl<S: QuerySRDF + 'static> Engine<S> for SparqlEngine {
             }}
         ", node};
 
-        select(store, query, "this")?;
-
-        Err(ValidateError::NotImplemented)
+        Ok(FocusNodes::new(select(store, query, "this")?.into_iter()))
     }
 
     fn target_class(&self, store: &S, class: &S::Term) -> Result<FocusNodes<S>, ValidateError> {
@@ -62,9 +60,7 @@ impl<S: QuerySRDF + 'static> Engine<S> for SparqlEngine {
             }}
         ", class};
 
-        select(store, query, "this")?;
-
-        Err(ValidateError::NotImplemented)
+        Ok(FocusNodes::new(select(store, query, "this")?.into_iter()))
     }
 
     fn target_subject_of(
@@ -79,9 +75,7 @@ impl<S: QuerySRDF + 'static> Engine<S> for SparqlEngine {
             }}
         ", predicate};
 
-        select(store, query, "this")?;
-
-        Err(ValidateError::NotImplemented)
+        Ok(FocusNodes::new(select(store, query, "this")?.into_iter()))
     }
 
     fn target_object_of(
@@ -96,86 +90,146 @@ impl<S: QuerySRDF + 'static> Engine<S> for SparqlEngine {
             }}
         ", predicate};
 
-        select(store, query, "this")?;
-
-        Err(ValidateError::NotImplemented)
+        Ok(FocusNodes::new(select(store, query, "this")?.into_iter()))
     }
 
+    /// If s is a SHACL instance of rdfs:Class in a graph then the SHACL
+    /// instances of s are implicit targets of s.
     fn implicit_target_class(
         &self,
-        _store: &S,
-        _shape: &CompiledShape<S>,
+        store: &S,
+        shape: &CompiledShape<S>,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        // blank nodes cannot be referenced from a SPARQL query, so only shapes
+        // identified by an IRI can act as implicit classes
+        if !S::term_is_iri(shape.id()) {
+            return Ok(FocusNodes::default());
+        }
+
+        let query = formatdoc! {"
+            PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+            PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+
+            SELECT DISTINCT ?this
+            WHERE {{
+                {} rdf:type/rdfs:subClassOf* rdfs:Class .
+                ?this rdf:type/rdfs:subClassOf* {} .
+            }}
+        ", shape.id(), shape.id()};
+
+        Ok(FocusNodes::new(select(store, query, "this")?.into_iter()))
     }
 
     fn predicate(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _predicate: &S::IRI,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        predicate: &S::IRI,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::iri(S::iri2iri_s(predicate));
+        path_query(store, &path, focus_node)
     }
 
     fn alternative(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Alternative {
+            paths: paths.to_vec(),
+        };
+        path_query(store, &path, focus_node)
     }
 
     fn sequence(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Sequence {
+            paths: paths.to_vec(),
+        };
+        path_query(store, &path, focus_node)
     }
 
     fn inverse(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Inverse {
+            path: Box::new(path.to_owned()),
+        };
+        path_query(store, &path, focus_node)
     }
 
     fn zero_or_more(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrMore {
+            path: Box::new(path.to_owned()),
+        };
+        path_query(store, &path, focus_node)
     }
 
     fn one_or_more(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::OneOrMore {
+            path: Box::new(path.to_owned()),
+        };
+        path_query(store, &path, focus_node)
     }
 
     fn zero_or_one(
         &self,
-        _store: &S,
-        _shape: &CompiledPropertyShape<S>,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        store: &S,
+        _: &CompiledPropertyShape<S>,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<FocusNodes<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrOne {
+            path: Box::new(path.to_owned()),
+        };
+        path_query(store, &path, focus_node)
     }
 }
+
+/// Obtains the value nodes reachable from the focus node through the given
+/// path, which is translated into a SPARQL property path.
+///
+/// Blank nodes act as variables in SPARQL queries, so it is not possible to
+/// start a path from a blank focus node.
+fn path_query<S: QuerySRDF2>(
+    store: &S,
+    path: &SHACLPath,
+    focus_node: &S::Term,
+) -> Result<FocusNodes<S>, ValidateError> {
+    if S::term_is_bnode(focus_node) {
+        return Err(ValidateError::FocusNodeBlankNode(focus_node.to_string()));
+    }
+
+    let query = formatdoc! {"
+        SELECT DISTINCT ?value
+        WHERE {{
+            {} {} ?value .
+        }}
+    ", focus_node, path.sparql_path()};
+
+    Ok(FocusNodes::new(select(store, query, "value")?.into_iter()))
+}
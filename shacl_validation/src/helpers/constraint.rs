@@ -1,9 +1,14 @@
+use iri_s::IriS;
 use shacl_ast::compiled::component::CompiledComponent;
 use shacl_ast::compiled::shape::CompiledShape;
-use srdf::QuerySRDF;
+use srdf::QuerySRDF2;
 use srdf::SRDFBasic;
+use tracing::warn;
 
 use crate::constraints::constraint_error::ConstraintError;
+use crate::helpers::helper_error::SPARQLError;
+use crate::helpers::message::messages;
+use crate::helpers::message::path_term;
 use crate::validation_report::result::ValidationResult;
 use crate::value_nodes::IterationStrategy;
 use crate::value_nodes::ValueNodeIteration;
@@ -14,28 +19,48 @@ fn apply<S: SRDFBasic, I: IterationStrategy<S>>(
     shape: &CompiledShape<S>,
     value_nodes: &ValueNodes<S>,
     iteration_strategy: I,
-    evaluator: impl Fn(&I::Item) -> Result<bool, ConstraintError>,
+    evaluator: impl Fn(&S::Term, &I::Item) -> Result<bool, ConstraintError>,
 ) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
-    let results = iteration_strategy
-        .iterate(value_nodes)
-        .flat_map(|(focus_node, item)| {
-            if let Ok(condition) = evaluator(item) {
-                if condition {
-                    return Some(ValidationResult::new(
-                        focus_node.to_owned(),
-                        None, // TODO: path
-                        None, // TODO: item
-                        Some(shape.id().to_owned()),
-                        S::iri_s2term(&component.into()),
-                        None, // TODO: details
-                        None, // TODO: message
-                        shape.severity(),
-                    ));
-                }
+    let path = shape.path();
+    let parameters = component.parameters();
+
+    let mut results = Vec::new();
+    for (focus_node, item) in iteration_strategy.iterate(value_nodes) {
+        let value = iteration_strategy.to_value(item);
+        match evaluator(focus_node, item) {
+            Ok(false) => {}
+            Ok(true) => {
+                let binding = |name: &str| match name {
+                    "this" => Some(focus_node.to_owned()),
+                    "value" => value.clone(),
+                    "path" => path.map(path_term::<S>),
+                    "currentShape" => Some(shape.id().to_owned()),
+                    _ => parameters
+                        .iter()
+                        .find(|(parameter, _)| parameter == name)
+                        .map(|(_, term)| term.to_owned()),
+                };
+                results.push(ValidationResult::new(
+                    focus_node.to_owned(),
+                    path.cloned(),
+                    value.clone(),
+                    Some(shape.id().to_owned()),
+                    S::iri_s2term(&component.into()),
+                    None, // TODO: details
+                    messages::<S>(shape.messages(), binding),
+                    shape.severity(),
+                ));
+            }
+            Err(ConstraintError::Sparql(error @ SPARQLError::BlankNode(_))) => {
+                warn!(
+                    "Constraint {} of shape {} not checked for focus node {focus_node}: {error}",
+                    IriS::from(component),
+                    shape.id()
+                )
             }
-            None
-        })
-        .collect();
+            Err(error) => return Err(error),
+        }
+    }
 
     Ok(results)
 }
@@ -52,11 +77,27 @@ pub fn validate_with<S: SRDFBasic, I: IterationStrategy<S>>(
         shape,
         value_nodes,
         iteration_strategy,
-        |item: &I::Item| Ok(evaluator(item)),
+        |_: &S::Term, item: &I::Item| Ok(evaluator(item)),
     )
 }
 
-pub fn validate_ask_with<S: QuerySRDF>(
+/// Same as `validate_with` but the evaluator also receives the focus node
+/// from which the item was obtained. Items with blank nodes that the evaluator
+/// cannot query are skipped with a warning, other errors are propagated
+pub fn validate_with_focus<S: SRDFBasic, I: IterationStrategy<S>>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    value_nodes: &ValueNodes<S>,
+    iteration_strategy: I,
+    evaluator: impl Fn(&S::Term, &I::Item) -> Result<bool, ConstraintError>,
+) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+    apply(component, shape, value_nodes, iteration_strategy, evaluator)
+}
+
+/// Checks each value node with the ASK query returned by `eval_query`. Blank
+/// value nodes would act as variables in the query, so they are skipped with a
+/// warning
+pub fn validate_ask_with<S: QuerySRDF2>(
     component: &CompiledComponent<S>,
     shape: &CompiledShape<S>,
     store: &S,
@@ -68,9 +109,47 @@ pub fn validate_ask_with<S: QuerySRDF>(
         shape,
         value_nodes,
         ValueNodeIteration,
-        |value_node| match store.query_ask(&eval_query(value_node)) {
-            Ok(ask) => Ok(!ask),
-            Err(err) => Err(ConstraintError::Query(format!("ASK query failed: {}", err))),
+        |_, value_node| {
+            if S::term_is_bnode(value_node) {
+                return Err(SPARQLError::BlankNode(value_node.to_string()).into());
+            }
+            ask_violation(store, &eval_query(value_node))
         },
     )
 }
+
+/// Same as `validate_ask_with` for the components that don't accept blank
+/// nodes as values, like the string based and value range ones, so blank
+/// value nodes are violations without querying
+pub fn validate_non_blank_ask_with<S: QuerySRDF2>(
+    component: &CompiledComponent<S>,
+    shape: &CompiledShape<S>,
+    store: &S,
+    value_nodes: &ValueNodes<S>,
+    eval_query: impl Fn(&S::Term) -> String,
+) -> Result<Vec<ValidationResult<S>>, ConstraintError> {
+    apply(
+        component,
+        shape,
+        value_nodes,
+        ValueNodeIteration,
+        |_, value_node| {
+            if S::term_is_bnode(value_node) {
+                return Ok(true);
+            }
+            ask_violation(store, &eval_query(value_node))
+        },
+    )
+}
+
+/// Runs an ASK query which is true when the value node conforms, so the result
+/// is negated to tell if it is a violation
+pub(crate) fn ask_violation<S: QuerySRDF2>(
+    store: &S,
+    query: &str,
+) -> Result<bool, ConstraintError> {
+    match store.query_ask(query) {
+        Ok(ask) => Ok(!ask),
+        Err(err) => Err(ConstraintError::Query(format!("ASK query failed: {}", err))),
+    }
+}
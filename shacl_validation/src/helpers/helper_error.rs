@@ -6,12 +6,18 @@ use thiserror::Error;
 pub enum SPARQLError {
     #[error("Query could not be performed")]
     Query,
+    #[error("Blank node {} cannot be referenced from a SPARQL query", ._0)]
+    BlankNode(String),
+    #[error("Syntax error in the SPARQL query: {}", ._0)]
+    Syntax(String),
 }
 
 #[derive(Error, Debug)]
 pub enum SRDFError {
     #[error("Error during the SRDF operation")]
     Srdf,
+    #[error("Node {} is not a well-formed SHACL path", ._0)]
+    Path(String),
     #[error("Error parsing the IRI")]
     IriParse(#[from] IriParseError),
     #[error("Error during the creation of the SRDFGraph")]
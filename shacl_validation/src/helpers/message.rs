@@ -0,0 +1,63 @@
+use srdf::literal::Literal;
+use srdf::RDFNode;
+use srdf::SHACLPath;
+use srdf::SRDFBasic;
+
+/// Obtains the messages of a validation result from the `sh:message` values,
+/// one for each of them, replacing the `{$var}` and `{?var}` templates by the
+/// value of the variables. The language tag of each template is kept.
+///
+/// https://www.w3.org/TR/shacl/#message
+pub(crate) fn messages<S: SRDFBasic>(
+    templates: &[Literal],
+    binding: impl Fn(&str) -> Option<S::Term>,
+) -> Vec<S::Term> {
+    templates
+        .iter()
+        .map(|template| {
+            let text = substitute::<S>(&template.lexical_form(), &binding);
+            let literal = match template {
+                Literal::StringLiteral {
+                    lang: Some(lang), ..
+                } => Literal::lang_str(&text, lang.clone()),
+                _ => Literal::str(&text),
+            };
+            S::object_as_term(&RDFNode::literal(literal))
+        })
+        .collect()
+}
+
+fn substitute<S: SRDFBasic>(template: &str, binding: impl Fn(&str) -> Option<S::Term>) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest
+        .match_indices('{')
+        .map(|(idx, _)| idx)
+        .find(|idx| rest[idx + 1..].starts_with(['$', '?']))
+    {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        text.push_str(&rest[..start]);
+        if let Some(term) = binding(&rest[start + 2..end]) {
+            match (S::term_as_literal(&term), S::term_as_iri(&term)) {
+                (Some(literal), _) => text.push_str(S::lexical_form(&literal)),
+                (_, Some(iri)) => text.push_str(S::iri2iri_s(&iri).as_str()),
+                _ => text.push_str(&term.to_string()),
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+/// The value of `{$path}` in the messages, the predicate of a predicate path
+/// or the SPARQL property path of the others
+pub(crate) fn path_term<S: SRDFBasic>(path: &SHACLPath) -> S::Term {
+    match path {
+        SHACLPath::Predicate { pred } => S::iri_s2term(pred),
+        _ => S::object_as_term(&RDFNode::literal(Literal::str(&path.sparql_path()))),
+    }
+}
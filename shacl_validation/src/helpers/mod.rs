@@ -1,4 +1,5 @@
 pub(crate) mod constraint;
 pub(crate) mod helper_error;
+pub(crate) mod message;
 pub(crate) mod sparql;
 pub(crate) mod srdf;
@@ -1,10 +1,28 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 
-use srdf::QuerySRDF;
+use indoc::formatdoc;
+use spargebra::algebra::AggregateExpression;
+use spargebra::algebra::Expression;
+use spargebra::algebra::GraphPattern;
+use spargebra::algebra::OrderExpression;
+use spargebra::algebra::PropertyPathExpression;
+use spargebra::term::Literal;
+use spargebra::term::NamedNode;
+use spargebra::term::NamedNodePattern;
+use spargebra::term::Term;
+use spargebra::term::TermPattern;
+use spargebra::term::TriplePattern;
+use spargebra::term::Variable;
+use spargebra::Query;
+use srdf::QuerySRDF2;
+use srdf::SHACLPath;
+use srdf::SRDFBasic;
+use srdf::SRDFGraph;
 
 use super::helper_error::SPARQLError;
 
-pub fn select<S: QuerySRDF>(
+pub fn select<S: QuerySRDF2>(
     store: &S,
     query: String,
     index: &str,
@@ -14,14 +32,380 @@ pub fn select<S: QuerySRDF>(
         Ok(ans) => ans,
         Err(_) => return Err(SPARQLError::Query),
     };
-    for solution in query.into_iter() {
-        let solution = match solution {
-            Ok(ans) => ans,
-            Err(_) => return Err(SPARQLError::Query),
-        };
+    for solution in query.iter() {
         if let Some(solution) = solution.find_solution(index) {
             ans.insert(solution.to_owned());
         }
     }
     Ok(ans)
 }
+
+pub fn ask<S: QuerySRDF2>(store: &S, query: String) -> Result<bool, SPARQLError> {
+    match store.query_ask(&query) {
+        Ok(ans) => Ok(ans),
+        Err(_) => Err(SPARQLError::Query),
+    }
+}
+
+/// Writes a string as a SPARQL string literal, escaping the characters that
+/// cannot appear between quotes
+pub fn string_literal(str: &str) -> String {
+    let mut literal = String::with_capacity(str.len() + 2);
+    literal.push('"');
+    for c in str.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Obtains the objects of the triples with the given subject and predicate.
+///
+/// Blank nodes act as variables in SPARQL queries, so they cannot be used as
+/// the subject of the query.
+pub fn objects_for<S: QuerySRDF2>(
+    store: &S,
+    subject: &S::Term,
+    predicate: &S::IRI,
+) -> Result<HashSet<S::Term>, SPARQLError> {
+    if S::term_is_bnode(subject) {
+        return Err(SPARQLError::BlankNode(subject.to_string()));
+    }
+
+    let query = formatdoc! {"
+        SELECT DISTINCT ?object
+        WHERE {{
+            {} {} ?object .
+        }}
+    ", subject, predicate};
+
+    select(store, query, "object")
+}
+
+/// Pre-binds the variables of a SPARQL query, replacing their occurrences in
+/// the algebra of the query by the given terms, and the predicates `$PATH` by
+/// the SPARQL property path. Pre-bound variables that are projected keep
+/// being returned in the solutions with their values.
+///
+/// Blank nodes cannot be referenced from a query, so pre-binding a variable
+/// that appears in the query to a blank node is an error.
+///
+/// https://www.w3.org/TR/shacl/#pre-binding
+pub fn prebind<S: QuerySRDF2>(
+    query: &str,
+    bindings: &[(&str, &S::Term)],
+    path: Option<&SHACLPath>,
+) -> Result<String, SPARQLError> {
+    let mut query =
+        Query::parse(query, None).map_err(|err| SPARQLError::Syntax(err.to_string()))?;
+
+    let mut prebinding = Prebinding {
+        values: bindings
+            .iter()
+            .map(|(name, term)| {
+                let value = match S::term_is_bnode(term) {
+                    true => Err(term.to_string()),
+                    false => Ok(SRDFGraph::object_as_term(&S::term_as_object(term))),
+                };
+                (Variable::new_unchecked(*name), value)
+            })
+            .collect(),
+        path: path.map(property_path),
+        blank_node: None,
+    };
+
+    match &mut query {
+        Query::Select { pattern, .. }
+        | Query::Ask { pattern, .. }
+        | Query::Describe { pattern, .. } => prebinding.graph_pattern(pattern),
+        Query::Construct {
+            template, pattern, ..
+        } => {
+            template
+                .iter_mut()
+                .for_each(|triple| prebinding.triple_pattern(triple));
+            prebinding.graph_pattern(pattern);
+        }
+    }
+
+    match prebinding.blank_node {
+        Some(blank_node) => Err(SPARQLError::BlankNode(blank_node)),
+        None => Ok(query.to_string()),
+    }
+}
+
+/// Substitution of the pre-bound variables in the algebra of a query, blank
+/// nodes are kept as errors that are only reported if they are used.
+struct Prebinding {
+    values: HashMap<Variable, Result<Term, String>>,
+    path: Option<PropertyPathExpression>,
+    blank_node: Option<String>,
+}
+
+impl Prebinding {
+    fn value(&mut self, variable: &Variable) -> Option<Term> {
+        match self.values.get(variable)? {
+            Ok(term) => Some(term.clone()),
+            Err(blank_node) => {
+                self.blank_node.get_or_insert_with(|| blank_node.clone());
+                None
+            }
+        }
+    }
+
+    fn graph_pattern(&mut self, pattern: &mut GraphPattern) {
+        match pattern {
+            GraphPattern::Bgp { patterns } => {
+                let paths = self.bgp(patterns);
+                if !paths.is_empty() {
+                    let bgp = GraphPattern::Bgp {
+                        patterns: std::mem::take(patterns),
+                    };
+                    *pattern = paths
+                        .into_iter()
+                        .fold(bgp, |left, right| GraphPattern::Join {
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        });
+                }
+            }
+            GraphPattern::Path {
+                subject, object, ..
+            } => {
+                self.term_pattern(subject);
+                self.term_pattern(object);
+            }
+            GraphPattern::Join { left, right }
+            | GraphPattern::Union { left, right }
+            | GraphPattern::Minus { left, right }
+            | GraphPattern::Lateral { left, right } => {
+                self.graph_pattern(left);
+                self.graph_pattern(right);
+            }
+            GraphPattern::LeftJoin {
+                left,
+                right,
+                expression,
+            } => {
+                self.graph_pattern(left);
+                self.graph_pattern(right);
+                if let Some(expression) = expression {
+                    self.expression(expression);
+                }
+            }
+            GraphPattern::Filter { expr, inner } => {
+                self.expression(expr);
+                self.graph_pattern(inner);
+            }
+            GraphPattern::Graph { name, inner } | GraphPattern::Service { name, inner, .. } => {
+                self.named_node_pattern(name);
+                self.graph_pattern(inner);
+            }
+            GraphPattern::Extend {
+                inner, expression, ..
+            } => {
+                self.graph_pattern(inner);
+                self.expression(expression);
+            }
+            GraphPattern::OrderBy { inner, expression } => {
+                self.graph_pattern(inner);
+                for order in expression {
+                    match order {
+                        OrderExpression::Asc(expression) | OrderExpression::Desc(expression) => {
+                            self.expression(expression)
+                        }
+                    }
+                }
+            }
+            GraphPattern::Project { inner, variables } => {
+                self.graph_pattern(inner);
+                self.bind(inner, variables);
+            }
+            GraphPattern::Group {
+                inner,
+                variables,
+                aggregates,
+            } => {
+                self.graph_pattern(inner);
+                self.bind(inner, variables);
+                for (_, aggregate) in aggregates {
+                    if let AggregateExpression::FunctionCall { expr, .. } = aggregate {
+                        self.expression(expr);
+                    }
+                }
+            }
+            GraphPattern::Distinct { inner }
+            | GraphPattern::Reduced { inner }
+            | GraphPattern::Slice { inner, .. } => self.graph_pattern(inner),
+            // inline values are given by the query itself
+            GraphPattern::Values { .. } => {}
+        }
+    }
+
+    /// Substitutes the variables of a basic graph pattern, returning the path
+    /// patterns of the triples whose predicate is `$PATH`
+    fn bgp(&mut self, patterns: &mut Vec<TriplePattern>) -> Vec<GraphPattern> {
+        let mut paths = Vec::new();
+        let mut triples = Vec::with_capacity(patterns.len());
+        for mut triple in patterns.drain(..) {
+            self.term_pattern(&mut triple.subject);
+            self.term_pattern(&mut triple.object);
+            match (&triple.predicate, &self.path) {
+                (NamedNodePattern::Variable(variable), Some(path))
+                    if variable.as_str() == "PATH" =>
+                {
+                    paths.push(GraphPattern::Path {
+                        subject: triple.subject,
+                        path: path.clone(),
+                        object: triple.object,
+                    })
+                }
+                _ => {
+                    self.named_node_pattern(&mut triple.predicate);
+                    triples.push(triple);
+                }
+            }
+        }
+        *patterns = triples;
+        paths
+    }
+
+    /// Projected variables that are pre-bound are added to the solutions of
+    /// the inner pattern, unless they are still in scope after the
+    /// substitution, like those bound by a grouping
+    fn bind(&mut self, inner: &mut Box<GraphPattern>, variables: &[Variable]) {
+        let mut in_scope = HashSet::new();
+        inner.on_in_scope_variable(|variable| {
+            in_scope.insert(variable.clone());
+        });
+        for variable in variables.iter().filter(|v| !in_scope.contains(*v)) {
+            if let Some(expression) = self.value(variable).and_then(term_expression) {
+                let pattern = std::mem::take(inner.as_mut());
+                **inner = GraphPattern::Extend {
+                    inner: Box::new(pattern),
+                    variable: variable.clone(),
+                    expression,
+                };
+            }
+        }
+    }
+
+    fn triple_pattern(&mut self, triple: &mut TriplePattern) {
+        self.term_pattern(&mut triple.subject);
+        self.named_node_pattern(&mut triple.predicate);
+        self.term_pattern(&mut triple.object);
+    }
+
+    fn term_pattern(&mut self, pattern: &mut TermPattern) {
+        match pattern {
+            TermPattern::Variable(variable) => {
+                if let Some(term) = self.value(variable) {
+                    *pattern = term.into();
+                }
+            }
+            TermPattern::Triple(triple) => self.triple_pattern(triple),
+            _ => {}
+        }
+    }
+
+    fn named_node_pattern(&mut self, pattern: &mut NamedNodePattern) {
+        if let NamedNodePattern::Variable(variable) = pattern {
+            if let Some(Term::NamedNode(node)) = self.value(variable) {
+                *pattern = node.into();
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::Variable(variable) => {
+                if let Some(value) = self.value(variable).and_then(term_expression) {
+                    *expression = value;
+                }
+            }
+            Expression::Bound(variable) => {
+                if self.value(variable).is_some() {
+                    *expression = Expression::Literal(Literal::from(true));
+                }
+            }
+            Expression::Or(a, b)
+            | Expression::And(a, b)
+            | Expression::Equal(a, b)
+            | Expression::SameTerm(a, b)
+            | Expression::Greater(a, b)
+            | Expression::GreaterOrEqual(a, b)
+            | Expression::Less(a, b)
+            | Expression::LessOrEqual(a, b)
+            | Expression::Add(a, b)
+            | Expression::Subtract(a, b)
+            | Expression::Multiply(a, b)
+            | Expression::Divide(a, b) => {
+                self.expression(a);
+                self.expression(b);
+            }
+            Expression::In(a, list) => {
+                self.expression(a);
+                list.iter_mut().for_each(|b| self.expression(b));
+            }
+            Expression::UnaryPlus(a) | Expression::UnaryMinus(a) | Expression::Not(a) => {
+                self.expression(a)
+            }
+            Expression::Exists(pattern) => self.graph_pattern(pattern),
+            Expression::If(a, b, c) => {
+                self.expression(a);
+                self.expression(b);
+                self.expression(c);
+            }
+            Expression::Coalesce(list) | Expression::FunctionCall(_, list) => {
+                list.iter_mut().for_each(|a| self.expression(a))
+            }
+            Expression::NamedNode(_) | Expression::Literal(_) => {}
+        }
+    }
+}
+
+/// The expression of a constant term, quoted triples cannot be written as
+/// constants in expressions
+fn term_expression(term: Term) -> Option<Expression> {
+    match term {
+        Term::NamedNode(node) => Some(Expression::NamedNode(node)),
+        Term::Literal(literal) => Some(Expression::Literal(literal)),
+        _ => None,
+    }
+}
+
+fn property_path(path: &SHACLPath) -> PropertyPathExpression {
+    let fold = |paths: &[SHACLPath], f: fn(Box<_>, Box<_>) -> PropertyPathExpression| {
+        paths
+            .iter()
+            .map(property_path)
+            .reduce(|a, b| f(Box::new(a), Box::new(b)))
+            // empty lists of paths cannot be parsed from the shapes graph
+            .unwrap_or(PropertyPathExpression::NegatedPropertySet(Vec::new()))
+    };
+    match path {
+        SHACLPath::Predicate { pred } => {
+            PropertyPathExpression::NamedNode(NamedNode::new_unchecked(pred.as_str()))
+        }
+        SHACLPath::Alternative { paths } => fold(paths, PropertyPathExpression::Alternative),
+        SHACLPath::Sequence { paths } => fold(paths, PropertyPathExpression::Sequence),
+        SHACLPath::Inverse { path } => {
+            PropertyPathExpression::Reverse(Box::new(property_path(path)))
+        }
+        SHACLPath::ZeroOrMore { path } => {
+            PropertyPathExpression::ZeroOrMore(Box::new(property_path(path)))
+        }
+        SHACLPath::OneOrMore { path } => {
+            PropertyPathExpression::OneOrMore(Box::new(property_path(path)))
+        }
+        SHACLPath::ZeroOrOne { path } => {
+            PropertyPathExpression::ZeroOrOne(Box::new(property_path(path)))
+        }
+    }
+}
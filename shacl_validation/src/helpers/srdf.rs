@@ -1,5 +1,15 @@
 use std::collections::HashSet;
 
+use iri_s::IriS;
+use shacl_ast::SH_ALTERNATIVE_PATH;
+use shacl_ast::SH_INVERSE_PATH;
+use shacl_ast::SH_ONE_OR_MORE_PATH;
+use shacl_ast::SH_ZERO_OR_MORE_PATH;
+use shacl_ast::SH_ZERO_OR_ONE_PATH;
+use srdf::SHACLPath;
+use srdf::RDF_FIRST;
+use srdf::RDF_NIL;
+use srdf::RDF_REST;
 use srdf::SRDF;
 
 use super::helper_error::SRDFError;
@@ -47,3 +57,138 @@ pub(crate) fn get_subjects_for<S: SRDF>(
         Err(_) => Err(SRDFError::Srdf),
     }
 }
+
+/// Parses the SHACL path represented by a node, which is either an IRI or a
+/// blank node following the syntax rules of SHACL property paths
+///
+/// https://www.w3.org/TR/shacl/#property-paths
+pub(crate) fn get_path<S: SRDF>(store: &S, node: &S::Term) -> Result<SHACLPath, SRDFError> {
+    if let Some(iri) = S::term_as_iri(node) {
+        return Ok(SHACLPath::iri(S::iri2iri_s(&iri)));
+    }
+
+    // a sequence path is a well-formed SHACL list of two or more paths
+    if get_object_for(store, node, &S::iri_s2iri(&RDF_FIRST))?.is_some() {
+        let paths = get_path_list(store, node)?;
+        return Ok(SHACLPath::Sequence { paths });
+    }
+
+    if let Some(list) = get_object_for(store, node, &S::iri_s2iri(&SH_ALTERNATIVE_PATH))? {
+        let paths = get_path_list(store, &list)?;
+        return Ok(SHACLPath::Alternative { paths });
+    }
+
+    type UnaryPath = fn(Box<SHACLPath>) -> SHACLPath;
+    let unary_paths: [(&IriS, UnaryPath); 4] = [
+        (&SH_INVERSE_PATH, |path| SHACLPath::Inverse { path }),
+        (&SH_ZERO_OR_MORE_PATH, |path| SHACLPath::ZeroOrMore { path }),
+        (&SH_ONE_OR_MORE_PATH, |path| SHACLPath::OneOrMore { path }),
+        (&SH_ZERO_OR_ONE_PATH, |path| SHACLPath::ZeroOrOne { path }),
+    ];
+
+    for (property, build) in unary_paths {
+        if let Some(value) = get_object_for(store, node, &S::iri_s2iri(property))? {
+            return Ok(build(Box::new(get_path(store, &value)?)));
+        }
+    }
+
+    Err(SRDFError::Path(node.to_string()))
+}
+
+fn get_path_list<S: SRDF>(store: &S, list: &S::Term) -> Result<Vec<SHACLPath>, SRDFError> {
+    let nil = S::iri_s2term(&RDF_NIL);
+    let mut paths = Vec::new();
+    let mut visited = HashSet::new();
+    let mut node = list.clone();
+    while node != nil {
+        // cyclic lists are not well-formed
+        if !visited.insert(node.clone()) {
+            return Err(SRDFError::Path(list.to_string()));
+        }
+        let (first, rest) = match (
+            get_object_for(store, &node, &S::iri_s2iri(&RDF_FIRST))?,
+            get_object_for(store, &node, &S::iri_s2iri(&RDF_REST))?,
+        ) {
+            (Some(first), Some(rest)) => (first, rest),
+            _ => return Err(SRDFError::Path(list.to_string())),
+        };
+        paths.push(get_path(store, &first)?);
+        node = rest;
+    }
+    Ok(paths)
+}
+
+/// Obtains the nodes that can be reached from `node` following the path. When
+/// `inverse` is true, the path is followed backwards.
+pub(crate) fn get_path_values<S: SRDF>(
+    store: &S,
+    node: &S::Term,
+    path: &SHACLPath,
+    inverse: bool,
+) -> Result<HashSet<S::Term>, SRDFError> {
+    match path {
+        SHACLPath::Predicate { pred } => {
+            let predicate = S::iri_s2iri(pred);
+            match (inverse, S::term_is_literal(node)) {
+                (true, _) => get_subjects_for(store, &predicate, node),
+                // literals do not have outgoing arcs
+                (false, true) => Ok(HashSet::new()),
+                (false, false) => get_objects_for(store, node, &predicate),
+            }
+        }
+        SHACLPath::Inverse { path } => get_path_values(store, node, path, !inverse),
+        SHACLPath::Alternative { paths } => {
+            let mut values = HashSet::new();
+            for path in paths {
+                values.extend(get_path_values(store, node, path, inverse)?);
+            }
+            Ok(values)
+        }
+        SHACLPath::Sequence { paths } => {
+            let mut current = HashSet::from([node.clone()]);
+            // an inverse sequence is the sequence of the inverses in reverse order
+            let ordered: Vec<_> = match inverse {
+                true => paths.iter().rev().collect(),
+                false => paths.iter().collect(),
+            };
+            for path in ordered {
+                let mut next = HashSet::new();
+                for node in current.iter() {
+                    next.extend(get_path_values(store, node, path, inverse)?);
+                }
+                current = next;
+            }
+            Ok(current)
+        }
+        SHACLPath::ZeroOrOne { path } => {
+            let mut values = get_path_values(store, node, path, inverse)?;
+            values.insert(node.clone());
+            Ok(values)
+        }
+        SHACLPath::ZeroOrMore { path } => {
+            let mut values = get_closure_values(store, node, path, inverse)?;
+            values.insert(node.clone());
+            Ok(values)
+        }
+        SHACLPath::OneOrMore { path } => get_closure_values(store, node, path, inverse),
+    }
+}
+
+/// Nodes reachable following the path one or more times
+fn get_closure_values<S: SRDF>(
+    store: &S,
+    node: &S::Term,
+    path: &SHACLPath,
+    inverse: bool,
+) -> Result<HashSet<S::Term>, SRDFError> {
+    let mut values = HashSet::new();
+    let mut pending: Vec<_> = get_path_values(store, node, path, inverse)?
+        .into_iter()
+        .collect();
+    while let Some(value) = pending.pop() {
+        if values.insert(value.clone()) {
+            pending.extend(get_path_values(store, &value, path, inverse)?);
+        }
+    }
+    Ok(values)
+}
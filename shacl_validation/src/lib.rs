@@ -2,6 +2,7 @@ pub(crate) mod constraints;
 pub(crate) mod engine;
 pub(crate) mod focus_nodes;
 pub(crate) mod helpers;
+pub mod rules;
 pub mod shacl_config;
 pub mod shacl_processor;
 pub mod shacl_validation_vocab;
@@ -10,3 +11,6 @@ pub mod store;
 pub mod validate_error;
 pub mod validation_report;
 pub(crate) mod value_nodes;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,305 @@
+use shacl_ast::compiled::schema::CompiledSchema;
+use shacl_ast::rule::Rule;
+use shacl_ast::rule::RuleKind;
+use shacl_ast::shape::Shape;
+use shacl_ast::Schema;
+use srdf::QuerySRDF2;
+use srdf::RDFNode;
+use srdf::SRDFBuilder;
+use srdf::Triple;
+use srdf::SRDF;
+
+use crate::engine::native::NativeEngine;
+use crate::engine::Engine;
+use crate::focus_nodes::FocusNodes;
+use crate::helpers::helper_error::SPARQLError;
+use crate::helpers::sparql::prebind;
+use crate::shape::Validate;
+
+use self::node_expr::Evaluate;
+use self::rules_error::RulesError;
+
+pub(crate) mod node_expr;
+pub mod rules_error;
+
+/// Maximum number of passes over the rules when no limit is given
+const MAX_ITERATIONS: usize = 100;
+
+/// Executes the SHACL rules declared in a shapes graph over a data graph.
+///
+/// Rules are applied in ascending `sh:order` to the focus nodes of the shape
+/// that declares them, and the inferred triples are added to the data graph.
+/// The process is repeated until no new triples are inferred, so that rules
+/// can build on the triples inferred by other rules.
+///
+/// Blank nodes cannot be referenced from a SPARQL query, so SPARQL rules
+/// whose query uses `$this` infer nothing for blank focus nodes.
+///
+/// https://www.w3.org/TR/shacl-af/#rules-execution
+pub struct RuleExecutor {
+    schema: Schema,
+    max_iterations: usize,
+}
+
+impl RuleExecutor {
+    pub fn new(schema: &Schema) -> Self {
+        RuleExecutor {
+            schema: schema.clone(),
+            max_iterations: MAX_ITERATIONS,
+        }
+    }
+
+    /// Limits the number of passes, which prevents rules that always infer
+    /// new nodes (e.g. blank nodes in a CONSTRUCT) from running forever
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Active rules, together with the shape that declares them, in the order
+    /// in which they have to be executed
+    fn rules(&self) -> Vec<(&RDFNode, &Rule)> {
+        let mut rules: Vec<_> = self
+            .schema
+            .iter()
+            .flat_map(|(id, shape)| match shape {
+                Shape::NodeShape(ns) if !*ns.is_deactivated() => ns
+                    .rules()
+                    .iter()
+                    .filter(|rule| !rule.is_deactivated())
+                    .map(|rule| (id, rule))
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        rules.sort_by(|(_, a), (_, b)| a.order().total_cmp(&b.order()));
+        rules
+    }
+
+    /// Adds the inferred triples to the store, returning them.
+    ///
+    /// It fails if the rules still infer new triples after the maximum
+    /// number of passes, keeping the triples inferred until then in the store
+    pub fn infer<S: SRDF + SRDFBuilder + QuerySRDF2 + 'static>(
+        &self,
+        store: &mut S,
+    ) -> Result<Vec<Triple<S>>, RulesError> {
+        let schema = CompiledSchema::<S>::try_from(self.schema.clone())?;
+        let rules = self.rules();
+        let mut inferred = Vec::new();
+
+        for _ in 0..self.max_iterations {
+            let mut changed = false;
+            for (shape_id, rule) in rules.iter() {
+                for triple in apply(store, &schema, shape_id, rule)? {
+                    if contains(store, &triple)? {
+                        continue;
+                    }
+                    store
+                        .add_triple(&triple.subj(), &triple.pred(), &triple.obj())
+                        .map_err(|e| RulesError::AddTriple(triple.to_string(), e.to_string()))?;
+                    inferred.push(triple);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(inferred);
+            }
+        }
+
+        Err(RulesError::NoFixpoint(self.max_iterations))
+    }
+}
+
+/// Triples inferred by a rule for all the focus nodes of its shape
+fn apply<S: SRDF + QuerySRDF2 + 'static>(
+    store: &S,
+    schema: &CompiledSchema<S>,
+    shape_id: &RDFNode,
+    rule: &Rule,
+) -> Result<Vec<Triple<S>>, RulesError> {
+    let shape_term = S::object_as_term(shape_id);
+    let shape = match schema.get_shape(&shape_term) {
+        Some(shape) => shape,
+        None => return Err(RulesError::ShapeNotFound(shape_id.to_string())),
+    };
+
+    let mut triples = Vec::new();
+    for focus_node in NativeEngine.focus_nodes(store, shape, shape.targets())? {
+        let mut applies = true;
+        for condition in rule.conditions() {
+            if !conforms(store, schema, condition, &focus_node)? {
+                applies = false;
+                break;
+            }
+        }
+        if !applies {
+            continue;
+        }
+
+        match rule.kind() {
+            RuleKind::Triple {
+                subject,
+                predicate,
+                object,
+            } => {
+                let objects = object.evaluate(store, schema, &focus_node)?;
+                for subject in subject.evaluate(store, schema, &focus_node)? {
+                    // literals cannot be the subject of a triple
+                    let subject = match S::term_as_subject(&subject) {
+                        Some(subject) => subject,
+                        None => continue,
+                    };
+                    for object in objects.iter() {
+                        triples.push(Triple::new(
+                            subject.clone(),
+                            S::iri_s2iri(predicate),
+                            object.clone(),
+                        ));
+                    }
+                }
+            }
+            RuleKind::Sparql { .. } => {
+                let query = rule.kind().construct_query().unwrap_or_default();
+                let query = match prebind::<S>(
+                    &query,
+                    &[("this", &focus_node), ("currentShape", &shape_term)],
+                    None,
+                ) {
+                    Ok(query) => query,
+                    Err(SPARQLError::BlankNode(_)) => continue,
+                    Err(error) => return Err(error.into()),
+                };
+                match store.query_construct(&query) {
+                    Ok(result) => triples.extend(result),
+                    Err(error) => return Err(RulesError::Construct(error.to_string())),
+                }
+            }
+        }
+    }
+
+    Ok(triples)
+}
+
+/// Checks whether a node conforms to a shape of the schema
+pub(crate) fn conforms<S: SRDF + QuerySRDF2 + 'static>(
+    store: &S,
+    schema: &CompiledSchema<S>,
+    shape: &RDFNode,
+    node: &S::Term,
+) -> Result<bool, RulesError> {
+    let shape = match schema.get_shape(&S::object_as_term(shape)) {
+        Some(shape) => shape,
+        None => return Err(RulesError::ShapeNotFound(shape.to_string())),
+    };
+    let focus_nodes = FocusNodes::new(std::iter::once(node.clone()));
+    let results = shape.validate(store, &NativeEngine, Some(&focus_nodes))?;
+    Ok(results.is_empty())
+}
+
+fn contains<S: SRDF>(store: &S, triple: &Triple<S>) -> Result<bool, RulesError> {
+    match store.objects_for_subject_predicate(&triple.subj(), &triple.pred()) {
+        Ok(objects) => Ok(objects.contains(&triple.obj())),
+        Err(error) => Err(RulesError::FindTriple(
+            triple.to_string(),
+            error.to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shacl_ast::ShaclParser;
+    use sparql_service::RdfData;
+    use srdf::RDFFormat;
+    use srdf::ReaderMode;
+    use srdf::SRDFGraph;
+
+    use crate::test_utils::PREFIXES;
+
+    use super::RuleExecutor;
+    use super::RulesError;
+
+    fn parse(str: &str) -> SRDFGraph {
+        let str = format!("{PREFIXES}{str}");
+        SRDFGraph::from_str(&str, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    /// The triples inferred from the data by the rules of the shapes, with
+    /// the prefix `:` in place of the example namespace, sorted
+    fn infer(shapes: &str, data: &str) -> Vec<String> {
+        let schema = ShaclParser::new(parse(shapes)).parse().unwrap();
+        let mut data = RdfData::from_graph(parse(data)).unwrap();
+        let mut triples = RuleExecutor::new(&schema)
+            .infer(&mut data)
+            .unwrap()
+            .iter()
+            .map(|t| format!("{} {} {}", t.subj(), t.pred(), t.obj()))
+            .map(|t| t.replace("<http://example.org/", ":").replace('>', ""))
+            .collect::<Vec<_>>();
+        triples.sort();
+        triples
+    }
+
+    #[test]
+    fn triple_rules_are_applied_until_fixpoint() {
+        // the rule with the condition runs first, so :bob only becomes a
+        // contact in the second pass
+        let shapes = r#":Person a sh:NodeShape ; sh:targetClass :Person ;
+            sh:rule [ a sh:TripleRule ; sh:order 1 ;
+                sh:subject sh:this ; sh:predicate :knows ;
+                sh:object [ sh:path [ sh:inversePath :knows ] ] ] ;
+            sh:rule [ a sh:TripleRule ; sh:condition :Knows ;
+                sh:subject sh:this ; sh:predicate :type ; sh:object :Contact ] .
+            :Knows a sh:NodeShape ; sh:property [ sh:path :knows ; sh:minCount 1 ] ."#;
+        let data = ":alice a :Person ; :knows :bob . :bob a :Person .";
+        assert_eq!(
+            infer(shapes, data),
+            vec![
+                ":alice :type :Contact",
+                ":bob :knows :alice",
+                ":bob :type :Contact",
+            ]
+        );
+    }
+
+    #[test]
+    fn triple_rules_skip_literal_subjects() {
+        let shapes = r#":Person a sh:NodeShape ; sh:targetClass :Person ;
+            sh:rule [ a sh:TripleRule ;
+                sh:subject [ sh:path :name ] ; sh:predicate :of ; sh:object sh:this ] ."#;
+        let data = r#":alice a :Person ; :name "Alice" ."#;
+        assert!(infer(shapes, data).is_empty());
+    }
+
+    #[test]
+    fn sparql_rules_skip_blank_focus_nodes() {
+        let shapes = r#":Person a sh:NodeShape ; sh:targetClass :Person ;
+            sh:rule [ a sh:SPARQLRule ; sh:construct """
+                PREFIX : <http://example.org/>
+                CONSTRUCT { $this :friends ?count }
+                WHERE { { SELECT (COUNT(?friend) AS ?count) WHERE { $this :knows ?friend } } }
+            """ ] ."#;
+        let data = ":alice a :Person ; :knows :bob . [] a :Person ; :knows :alice .";
+        assert_eq!(
+            infer(shapes, data),
+            vec![r#":alice :friends "1"^^<http://www.w3.org/2001/XMLSchema#integer"#]
+        );
+    }
+
+    #[test]
+    fn rules_without_fixpoint_fail() {
+        // a new blank node is inferred in each pass
+        let shapes = r#":Person a sh:NodeShape ; sh:targetClass :Person ;
+            sh:rule [ a sh:SPARQLRule ; sh:construct """
+                PREFIX : <http://example.org/>
+                CONSTRUCT { $this :knows [] } WHERE { }
+            """ ] ."#;
+        let schema = ShaclParser::new(parse(shapes)).parse().unwrap();
+        let mut data = RdfData::from_graph(parse(":alice a :Person .")).unwrap();
+        let result = RuleExecutor::new(&schema)
+            .with_max_iterations(3)
+            .infer(&mut data);
+        assert!(matches!(result, Err(RulesError::NoFixpoint(3))));
+    }
+}
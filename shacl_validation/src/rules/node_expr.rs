@@ -0,0 +1,95 @@
+use shacl_ast::compiled::schema::CompiledSchema;
+use shacl_ast::node_expr::NodeExpr;
+use srdf::literal::Literal;
+use srdf::QuerySRDF2;
+use srdf::RDFNode;
+use srdf::SRDF;
+
+use crate::helpers::srdf::get_path_values;
+
+use super::conforms;
+use super::rules_error::RulesError;
+
+/// Evaluation of node expressions for a given focus node.
+///
+/// https://www.w3.org/TR/shacl-af/#node-expressions
+pub(crate) trait Evaluate<S: SRDF> {
+    fn evaluate(
+        &self,
+        store: &S,
+        schema: &CompiledSchema<S>,
+        focus_node: &S::Term,
+    ) -> Result<Vec<S::Term>, RulesError>;
+}
+
+impl<S: SRDF + QuerySRDF2 + 'static> Evaluate<S> for NodeExpr {
+    fn evaluate(
+        &self,
+        store: &S,
+        schema: &CompiledSchema<S>,
+        focus_node: &S::Term,
+    ) -> Result<Vec<S::Term>, RulesError> {
+        match self {
+            NodeExpr::FocusNode => Ok(vec![focus_node.clone()]),
+            NodeExpr::Constant(node) => Ok(vec![S::object_as_term(node)]),
+            NodeExpr::Path { path, nodes } => {
+                let nodes = match nodes {
+                    Some(nodes) => nodes.evaluate(store, schema, focus_node)?,
+                    None => vec![focus_node.clone()],
+                };
+                let mut values = Vec::new();
+                for node in nodes.iter() {
+                    values.extend(get_path_values(store, node, path, false)?);
+                }
+                Ok(values)
+            }
+            NodeExpr::FilterShape { shape, nodes } => {
+                let mut values = Vec::new();
+                for node in nodes.evaluate(store, schema, focus_node)? {
+                    if conforms(store, schema, shape, &node)? {
+                        values.push(node);
+                    }
+                }
+                Ok(values)
+            }
+            NodeExpr::Intersection(exprs) => {
+                let mut exprs = exprs.iter();
+                let mut values = match exprs.next() {
+                    Some(expr) => expr.evaluate(store, schema, focus_node)?,
+                    None => return Ok(Vec::new()),
+                };
+                for expr in exprs {
+                    let other = expr.evaluate(store, schema, focus_node)?;
+                    values.retain(|value| other.contains(value));
+                }
+                Ok(values)
+            }
+            NodeExpr::Union(exprs) => {
+                let mut values = Vec::new();
+                for expr in exprs {
+                    values.extend(expr.evaluate(store, schema, focus_node)?);
+                }
+                Ok(values)
+            }
+            NodeExpr::Distinct(expr) => {
+                let mut values = Vec::new();
+                for value in expr.evaluate(store, schema, focus_node)? {
+                    if !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+                Ok(values)
+            }
+            NodeExpr::Count(expr) => {
+                let count = expr.evaluate(store, schema, focus_node)?.len();
+                let literal = Literal::integer(count as isize);
+                Ok(vec![S::object_as_term(&RDFNode::literal(literal))])
+            }
+            NodeExpr::Exists(expr) => {
+                let exists = !expr.evaluate(store, schema, focus_node)?.is_empty();
+                let literal = Literal::boolean(exists);
+                Ok(vec![S::object_as_term(&RDFNode::literal(literal))])
+            }
+        }
+    }
+}
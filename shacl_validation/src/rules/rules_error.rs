@@ -0,0 +1,35 @@
+use shacl_ast::compiled::compiled_shacl_error::CompiledShaclError;
+use thiserror::Error;
+
+use crate::helpers::helper_error::SPARQLError;
+use crate::helpers::helper_error::SRDFError;
+use crate::validate_error::ValidateError;
+
+#[derive(Error, Debug)]
+pub enum RulesError {
+    #[error("Error during the compilation of the Schema, {}", ._0)]
+    CompiledShacl(#[from] CompiledShaclError),
+    #[error("Error checking the conditions of a rule, {}", ._0)]
+    Validate(Box<ValidateError>),
+    #[error("Error computing the focus nodes of the rule")]
+    Srdf(#[from] SRDFError),
+    #[error("Error preparing the query of a SPARQL rule, {}", ._0)]
+    Sparql(#[from] SPARQLError),
+    #[error("Error running the CONSTRUCT query of a SPARQL rule, {}", ._0)]
+    Construct(String),
+    #[error("Error adding the inferred triple {} to the data graph, {}", ._0, ._1)]
+    AddTriple(String, String),
+    #[error("Error looking for the inferred triple {} in the data graph, {}", ._0, ._1)]
+    FindTriple(String, String),
+    #[error("The rules still infer new triples after {} passes", ._0)]
+    NoFixpoint(usize),
+    #[error("Shape {} referenced from a rule was not found", ._0)]
+    ShapeNotFound(String),
+}
+
+// the validation errors are boxed, as they are much larger than the others
+impl From<ValidateError> for RulesError {
+    fn from(error: ValidateError) -> Self {
+        RulesError::Validate(Box::new(error))
+    }
+}
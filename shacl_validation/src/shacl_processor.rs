@@ -1,15 +1,23 @@
+use std::collections::HashSet;
 use std::path::Path;
 
 use clap::ValueEnum;
 use shacl_ast::compiled::schema::CompiledSchema;
+use sparql_service::RdfData;
+#[cfg(feature = "rocksdb")]
+use sparql_service::RdfStore;
+use srdf::FetchConfig;
+use srdf::FetchableSRDF;
+use srdf::PrefetchedSRDF;
 use srdf::RDFFormat;
 use srdf::SRDFBasic;
-use srdf::SRDFGraph;
 use srdf::SRDFSparql;
 
 use crate::engine::native::NativeEngine;
 use crate::engine::sparql::SparqlEngine;
 use crate::engine::Engine;
+use crate::focus_nodes::FocusNodes;
+use crate::shape::FocusNodesOps;
 use crate::shape::Validate;
 use crate::store::graph::Graph;
 use crate::store::sparql::Endpoint;
@@ -52,6 +60,40 @@ pub trait ShaclProcessor<S: SRDFBasic> {
 
         Ok(ValidationReport::new(validation_results)) // return the possibly empty validation report
     }
+
+    /// Validates only the focus nodes of each shape that are contained in
+    /// `nodes`, which allows to revalidate the nodes whose neighbourhood has
+    /// changed without validating the whole data graph again
+    fn validate_nodes(
+        &self,
+        schema: &CompiledSchema<S>,
+        nodes: &HashSet<S::Term>,
+    ) -> Result<ValidationReport<S>, ValidateError> {
+        let mut validation_results = Vec::new();
+        for (_, shape) in schema.iter() {
+            let targets = shape.focus_nodes(self.store(), self.runner());
+            let targets = FocusNodes::new(targets.into_iter().filter(|node| nodes.contains(node)));
+            let results = shape.validate(self.store(), self.runner(), Some(&targets))?;
+            validation_results.extend(results);
+        }
+        Ok(ValidationReport::new(validation_results))
+    }
+
+    /// The pairs of shape and focus node selected by the targets of the
+    /// shapes. Comparing the targets before and after a change of the data
+    /// gives the nodes that entered or left the target of some shape, which
+    /// must be validated again even if their neighbourhood did not change
+    fn targets(&self, schema: &CompiledSchema<S>) -> HashSet<(S::Term, S::Term)> {
+        schema
+            .iter()
+            .flat_map(|(id, shape)| {
+                shape
+                    .focus_nodes(self.store(), self.runner())
+                    .into_iter()
+                    .map(move |node| (id.clone(), node))
+            })
+            .collect()
+    }
 }
 
 pub struct GraphValidation {
@@ -66,26 +108,85 @@ impl GraphValidation {
         base: Option<&str>,
         mode: ShaclValidationMode,
     ) -> Result<Self, ValidateError> {
-        if mode == ShaclValidationMode::Sparql {
-            return Err(ValidateError::UnsupportedMode("Graph".to_string()));
-        }
-
         Ok(GraphValidation {
             store: Graph::new(data, data_format, base)?,
             mode,
         })
     }
+
+    /// Validates data that has already been loaded instead of reading it from a file
+    pub fn from_rdf_data(data: RdfData, mode: ShaclValidationMode) -> Self {
+        GraphValidation {
+            store: Graph::from_rdf_data(data),
+            mode,
+        }
+    }
 }
 
-impl ShaclProcessor<SRDFGraph> for GraphValidation {
-    fn store(&self) -> &SRDFGraph {
+impl ShaclProcessor<RdfData> for GraphValidation {
+    fn store(&self) -> &RdfData {
         self.store.store()
     }
 
-    fn runner(&self) -> &dyn Engine<SRDFGraph> {
+    fn runner(&self) -> &dyn Engine<RdfData> {
         match self.mode {
             ShaclValidationMode::Native => &NativeEngine,
-            ShaclValidationMode::Sparql => todo!(),
+            ShaclValidationMode::Sparql => &SparqlEngine,
+        }
+    }
+}
+
+/// Validation of data that is shared with other components, like the data
+/// loaded by a long-running service, without taking ownership of it
+pub struct RdfDataValidation<'a> {
+    store: &'a RdfData,
+    mode: ShaclValidationMode,
+}
+
+impl<'a> RdfDataValidation<'a> {
+    pub fn new(data: &'a RdfData, mode: ShaclValidationMode) -> Self {
+        RdfDataValidation { store: data, mode }
+    }
+}
+
+impl ShaclProcessor<RdfData> for RdfDataValidation<'_> {
+    fn store(&self) -> &RdfData {
+        self.store
+    }
+
+    fn runner(&self) -> &dyn Engine<RdfData> {
+        match self.mode {
+            ShaclValidationMode::Native => &NativeEngine,
+            ShaclValidationMode::Sparql => &SparqlEngine,
+        }
+    }
+}
+
+/// Validation of data kept in a persistent store, which is opened instead of
+/// loading the data again for each validation
+#[cfg(feature = "rocksdb")]
+pub struct RdfStoreValidation<'a> {
+    store: &'a RdfStore,
+    mode: ShaclValidationMode,
+}
+
+#[cfg(feature = "rocksdb")]
+impl<'a> RdfStoreValidation<'a> {
+    pub fn new(data: &'a RdfStore, mode: ShaclValidationMode) -> Self {
+        RdfStoreValidation { store: data, mode }
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+impl ShaclProcessor<RdfStore> for RdfStoreValidation<'_> {
+    fn store(&self) -> &RdfStore {
+        self.store
+    }
+
+    fn runner(&self) -> &dyn Engine<RdfStore> {
+        match self.mode {
+            ShaclValidationMode::Native => &NativeEngine,
+            ShaclValidationMode::Sparql => &SparqlEngine,
         }
     }
 }
@@ -116,3 +217,141 @@ impl ShaclProcessor<SRDFSparql> for EndpointValidation {
         }
     }
 }
+
+/// Validation of data that is fetched asynchronously, like remote SPARQL
+/// endpoints, which sends the queries in batches and concurrently instead of
+/// one blocking query for each node. See [`PrefetchedSRDF`]
+pub struct AsyncValidation<S: FetchableSRDF> {
+    store: PrefetchedSRDF<S>,
+    mode: ShaclValidationMode,
+}
+
+impl<S: FetchableSRDF + 'static> AsyncValidation<S> {
+    pub fn new(data: S, config: &FetchConfig, mode: ShaclValidationMode) -> Self {
+        AsyncValidation {
+            store: PrefetchedSRDF::new(data, config),
+            mode,
+        }
+    }
+
+    /// Validates the data fetching it in rounds. Each round validates the
+    /// data fetched so far and then fetches all the data that it missed, until
+    /// a round does not miss anything
+    pub async fn validate_async(
+        &self,
+        schema: &CompiledSchema<PrefetchedSRDF<S>>,
+    ) -> Result<ValidationReport<PrefetchedSRDF<S>>, ValidateError> {
+        loop {
+            let missed = self.store.missed_lookups();
+            let report = self.validate(schema);
+            if self.store.missed_lookups() == missed {
+                return report;
+            }
+            self.store.fetch_misses().await?;
+        }
+    }
+}
+
+impl<S: FetchableSRDF + 'static> ShaclProcessor<PrefetchedSRDF<S>> for AsyncValidation<S> {
+    fn store(&self) -> &PrefetchedSRDF<S> {
+        &self.store
+    }
+
+    fn runner(&self) -> &dyn Engine<PrefetchedSRDF<S>> {
+        match self.mode {
+            ShaclValidationMode::Native => &NativeEngine,
+            ShaclValidationMode::Sparql => &SparqlEngine,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ShaclDataManager;
+    use sparql_service::LocalEndpoint;
+    use srdf::{ReaderMode, SRDFGraph};
+
+    const SHAPES: &str = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:PersonShape a sh:NodeShape ;
+  sh:targetClass :Person ;
+  sh:property [ sh:path :name ; sh:minCount 1 ; sh:datatype xsd:string ] ;
+  sh:property [ sh:path :knows ; sh:class :Person ] ."#;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice a :Person ; :name "Alice" ; :knows :bob .
+:bob a :Person .
+:carol a :Person ; :name "Carol" ; :knows :dave .
+:dave :name "Dave" ."#;
+
+    fn focus_nodes<S: SRDFBasic>(report: &ValidationReport<S>) -> Vec<String> {
+        let mut nodes: Vec<String> = report
+            .results()
+            .iter()
+            .map(|result| result.focus_node().to_string())
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[tokio::test]
+    async fn async_validation_with_endpoint() {
+        let graph =
+            SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::default()).unwrap();
+        let local = GraphValidation::from_rdf_data(
+            RdfData::from_graph(graph.clone()).unwrap(),
+            ShaclValidationMode::Native,
+        );
+        let schema = ShaclDataManager::load(SHAPES.as_bytes(), RDFFormat::Turtle, None).unwrap();
+        let expected = local.validate(&schema).unwrap();
+
+        let endpoint = LocalEndpoint::new(&graph).unwrap();
+        for mode in [ShaclValidationMode::Native, ShaclValidationMode::Sparql] {
+            let validation = AsyncValidation::new(
+                SRDFSparql::new(endpoint.iri()).unwrap(),
+                &FetchConfig::new().with_parallelism(2),
+                mode,
+            );
+            let schema =
+                ShaclDataManager::load(SHAPES.as_bytes(), RDFFormat::Turtle, None).unwrap();
+            let report = validation.validate_async(&schema).await.unwrap();
+            assert!(!report.conforms());
+            assert_eq!(focus_nodes(&report), focus_nodes(&expected), "{mode:?}");
+        }
+    }
+
+    #[cfg(feature = "rocksdb")]
+    #[test]
+    fn store_validation_matches_graph_validation() {
+        use srdf::SRDFBuilder;
+
+        let graph =
+            SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::default()).unwrap();
+        let local = GraphValidation::from_rdf_data(
+            RdfData::from_graph(graph).unwrap(),
+            ShaclValidationMode::Native,
+        );
+        let schema = ShaclDataManager::load(SHAPES.as_bytes(), RDFFormat::Turtle, None).unwrap();
+        let expected = local.validate(&schema).unwrap();
+
+        let mut store = RdfStore::empty();
+        store
+            .load_reader(
+                DATA.as_bytes(),
+                &RDFFormat::Turtle,
+                None,
+                &ReaderMode::default(),
+            )
+            .unwrap();
+        for mode in [ShaclValidationMode::Native, ShaclValidationMode::Sparql] {
+            let validation = RdfStoreValidation::new(&store, mode);
+            let schema =
+                ShaclDataManager::load(SHAPES.as_bytes(), RDFFormat::Turtle, None).unwrap();
+            let report = validation.validate(&schema).unwrap();
+            assert_eq!(focus_nodes(&report), focus_nodes(&expected), "{mode:?}");
+        }
+    }
+}
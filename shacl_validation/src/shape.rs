@@ -2,6 +2,7 @@ use shacl_ast::compiled::node_shape::CompiledNodeShape;
 use shacl_ast::compiled::property_shape::CompiledPropertyShape;
 use shacl_ast::compiled::shape::CompiledShape;
 use srdf::SRDFBasic;
+use tracing::warn;
 
 use crate::engine::Engine;
 use crate::focus_nodes::FocusNodes;
@@ -40,9 +41,16 @@ impl<S: SRDFBasic> Validate<S> for CompiledShape<S> {
         // 2. Second we compute the ValueNodes; that is, the set of nodes that
         //    are going to be used during the validation stages. This set of
         //    nodes is obtained from the set of focus nodes
-        let value_nodes = self.value_nodes(store, &focus_nodes, runner);
+        let (value_nodes, unreachable) = self.value_nodes(store, &focus_nodes, runner);
 
-        // 3.
+        // 3. The focus nodes whose value nodes could not be computed can't be
+        //    checked, so they are skipped with a warning
+        for (focus_node, error) in &unreachable {
+            warn!(
+                "Shape {} not checked for focus node {focus_node}: {error}",
+                self.id()
+            );
+        }
         let component_validation_results = self
             .components()
             .iter()
@@ -80,13 +88,17 @@ impl<S: SRDFBasic> FocusNodesOps<S> for CompiledShape<S> {
     }
 }
 
+/// The value nodes of each focus node, and the focus nodes for which they
+/// could not be computed with the reason
+type ValueNodesResult<S> = (ValueNodes<S>, Vec<(<S as SRDFBasic>::Term, ValidateError)>);
+
 pub trait ValueNodesOps<S: SRDFBasic> {
     fn value_nodes(
         &self,
         store: &S,
         focus_nodes: &FocusNodes<S>,
         runner: &dyn Engine<S>,
-    ) -> ValueNodes<S>;
+    ) -> ValueNodesResult<S>;
 }
 
 impl<S: SRDFBasic> ValueNodesOps<S> for CompiledShape<S> {
@@ -95,7 +107,7 @@ impl<S: SRDFBasic> ValueNodesOps<S> for CompiledShape<S> {
         store: &S,
         focus_nodes: &FocusNodes<S>,
         runner: &dyn Engine<S>,
-    ) -> ValueNodes<S> {
+    ) -> ValueNodesResult<S> {
         match self {
             CompiledShape::NodeShape(ns) => ns.value_nodes(store, focus_nodes, runner),
             CompiledShape::PropertyShape(ps) => ps.value_nodes(store, focus_nodes, runner),
@@ -104,14 +116,19 @@ impl<S: SRDFBasic> ValueNodesOps<S> for CompiledShape<S> {
 }
 
 impl<S: SRDFBasic> ValueNodesOps<S> for CompiledNodeShape<S> {
-    fn value_nodes(&self, _: &S, focus_nodes: &FocusNodes<S>, _: &dyn Engine<S>) -> ValueNodes<S> {
+    fn value_nodes(
+        &self,
+        _: &S,
+        focus_nodes: &FocusNodes<S>,
+        _: &dyn Engine<S>,
+    ) -> ValueNodesResult<S> {
         let value_nodes = focus_nodes.iter().map(|focus_node| {
             (
                 focus_node.clone(),
                 FocusNodes::new(std::iter::once(focus_node.clone())),
             )
         });
-        ValueNodes::new(value_nodes)
+        (ValueNodes::new(value_nodes), Vec::new())
     }
 }
 
@@ -121,13 +138,19 @@ impl<S: SRDFBasic> ValueNodesOps<S> for CompiledPropertyShape<S> {
         store: &S,
         focus_nodes: &FocusNodes<S>,
         runner: &dyn Engine<S>,
-    ) -> ValueNodes<S> {
-        let value_nodes = focus_nodes.iter().filter_map(|focus_node| {
-            runner
-                .path(store, self, focus_node)
-                .ok()
-                .map(|targets| (focus_node.clone(), targets))
-        });
-        ValueNodes::new(value_nodes)
+    ) -> ValueNodesResult<S> {
+        let mut value_nodes = Vec::new();
+        let mut unreachable = Vec::new();
+        for focus_node in focus_nodes.iter() {
+            match runner.path(store, self, focus_node) {
+                Ok(targets) => value_nodes.push((focus_node.clone(), targets)),
+                Err(error @ ValidateError::FocusNodeBlankNode(_)) => {
+                    unreachable.push((focus_node.clone(), error))
+                }
+                // TODO: other errors are still skipped
+                Err(_) => {}
+            }
+        }
+        (ValueNodes::new(value_nodes.into_iter()), unreachable)
     }
 }
@@ -1,14 +1,17 @@
 use std::{path::Path, str::FromStr};
 
 use oxiri::Iri;
+use sparql_service::RdfData;
 use srdf::{RDFFormat, ReaderMode, SRDFGraph};
 
 use crate::validate_error::ValidateError;
 
 use super::Store;
 
+/// In-memory data graph, which is also loaded into an oxigraph store so that
+/// it can be queried through SPARQL
 pub struct Graph {
-    store: SRDFGraph,
+    store: RdfData,
 }
 
 impl Graph {
@@ -29,14 +32,21 @@ impl Graph {
             },
             &ReaderMode::default(), // TODO: this should be revisited
         ) {
-            Ok(store) => Ok(Self { store }),
+            Ok(graph) => Ok(Self {
+                store: RdfData::from_graph(graph)?,
+            }),
             Err(error) => Err(ValidateError::Graph(error)),
         }
     }
+
+    /// Data that has already been loaded, like the one built in memory
+    pub fn from_rdf_data(data: RdfData) -> Self {
+        Self { store: data }
+    }
 }
 
-impl Store<SRDFGraph> for Graph {
-    fn store(&self) -> &SRDFGraph {
+impl Store<RdfData> for Graph {
+    fn store(&self) -> &RdfData {
         &self.store
     }
 }
@@ -0,0 +1,53 @@
+use shacl_ast::SH_STR;
+use sparql_service::RdfData;
+use srdf::RDFFormat;
+use srdf::ReaderMode;
+use srdf::SRDFBasic;
+use srdf::SRDFGraph;
+
+use crate::shacl_processor::GraphValidation;
+use crate::shacl_processor::ShaclProcessor;
+use crate::shacl_processor::ShaclValidationMode;
+use crate::store::ShaclDataManager;
+use crate::validation_report::report::ValidationReport;
+
+pub(crate) const PREFIXES: &str = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+"#;
+
+/// Validates the Turtle data against the Turtle shapes, both without prefix
+/// declarations, which are taken from [`PREFIXES`]
+pub(crate) fn validate(
+    shapes: &str,
+    data: &str,
+    mode: ShaclValidationMode,
+) -> ValidationReport<RdfData> {
+    let data = format!("{PREFIXES}{data}");
+    let graph = SRDFGraph::from_str(&data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+    let validation = GraphValidation::from_rdf_data(RdfData::from_graph(graph).unwrap(), mode);
+    let shapes = format!("{PREFIXES}{shapes}");
+    let schema = ShaclDataManager::load(shapes.as_bytes(), RDFFormat::Turtle, None).unwrap();
+    validation.validate(&schema).unwrap()
+}
+
+/// The focus nodes and the local names of the constraint components of the
+/// results, sorted
+pub(crate) fn results<S: SRDFBasic>(report: &ValidationReport<S>) -> Vec<(String, String)> {
+    let mut results: Vec<(String, String)> = report
+        .results()
+        .iter()
+        .map(|result| {
+            let component = result.constraint_component().to_string();
+            let component = component
+                .trim_start_matches('<')
+                .trim_start_matches(SH_STR)
+                .trim_end_matches('>')
+                .to_string();
+            (result.focus_node().to_string(), component)
+        })
+        .collect();
+    results.sort();
+    results
+}
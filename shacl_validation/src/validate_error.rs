@@ -1,6 +1,8 @@
 use oxiri::IriParseError;
 use shacl_ast::compiled::compiled_shacl_error::CompiledShaclError;
 use shacl_ast::shacl_parser_error::ShaclParserError;
+use sparql_service::RdfDataError;
+use srdf::FetchError;
 use srdf::RDFParseError;
 use srdf::SRDFGraphError;
 use thiserror::Error;
@@ -15,10 +17,14 @@ pub enum ValidateError {
     SRDF,
     #[error("TargetNode cannot be a Blank Node")]
     TargetNodeBlankNode,
+    #[error("The path of a blank focus node {} cannot be computed using SPARQL", ._0)]
+    FocusNodeBlankNode(String),
     #[error("TargetClass should be an IRI")]
     TargetClassNotIri,
     #[error("Error when working with the SRDFGraph, {}", ._0)] // TODO: move to store
     Graph(#[from] SRDFGraphError),
+    #[error("Error when loading the data into the RDF store, {}", ._0)] // TODO: move to store
+    RdfData(#[from] RdfDataError),
     #[error("Error when parsing the SHACL Graph, {}", ._0)] // TODO: move to store
     ShaclParser(#[from] ShaclParserError),
     #[error("Error during the constraint evaluation")]
@@ -41,6 +47,8 @@ pub enum ValidateError {
     SrdfHelper(#[from] SRDFError),
     #[error("Error during the compilation of the Schema, {}", ._0)] // TODO: move to store
     CompiledShacl(#[from] CompiledShaclError),
+    #[error("Error fetching the data, {}", ._0)]
+    Fetch(#[from] FetchError),
     #[error("Not yet implemented")]
     NotImplemented,
 }
@@ -1,37 +1,328 @@
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::fmt::Display;
 
+use clap::ValueEnum;
+use colored::*;
+use const_format::concatcp;
+use prefixmap::PrefixMap;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+use shacl_ast::SH;
+use shacl_ast::SH_CONFORMS;
+use shacl_ast::SH_INFO;
+use shacl_ast::SH_RESULT;
+use shacl_ast::SH_STR;
+use shacl_ast::SH_VALIDATION_REPORT;
+use shacl_ast::SH_VIOLATION;
+use shacl_ast::SH_WARNING;
+use srdf::literal::Literal;
+use srdf::RDFNode;
+use srdf::SHACLPath;
 use srdf::SRDFBasic;
+use srdf::SRDFBuilder;
+use srdf::RDF_TYPE;
 use srdf::SRDF;
+use srdf::XSD;
 
 use crate::helpers::srdf::get_objects_for;
 
 use super::result::ValidationResult;
 use super::validation_report_error::ReportError;
 
+const XSD_STRING: &str = concatcp!(XSD, "string");
+
+#[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq)]
+/// Results that make the data graph not conform to the shapes graph
+pub enum ConformanceMode {
+    /// Any result, whatever its severity, as stated in the SHACL Recommendation
+    #[default]
+    Strict,
+    /// Only the results with `sh:Violation` severity, the `sh:Warning` and
+    /// `sh:Info` ones are reported but tolerated
+    Violations,
+}
+
 pub struct ValidationReport<S: SRDFBasic> {
     results: Vec<ValidationResult<S>>,
+    nodes_prefixmap: PrefixMap,
+    display_with_colors: bool,
+    conformance_mode: ConformanceMode,
+}
+
+impl<S: SRDFBasic> Clone for ValidationReport<S> {
+    fn clone(&self) -> Self {
+        Self {
+            results: self.results.clone(),
+            nodes_prefixmap: self.nodes_prefixmap.clone(),
+            display_with_colors: self.display_with_colors,
+            conformance_mode: self.conformance_mode,
+        }
+    }
 }
 
 impl<S: SRDFBasic> ValidationReport<S> {
     pub fn new(results: Vec<ValidationResult<S>>) -> Self {
-        Self { results }
+        Self {
+            results,
+            nodes_prefixmap: PrefixMap::new(),
+            display_with_colors: true,
+            conformance_mode: ConformanceMode::default(),
+        }
+    }
+
+    /// Prefix map used to qualify the IRIs when the report is displayed
+    pub fn with_nodes_prefixmap(mut self, prefixmap: PrefixMap) -> Self {
+        self.nodes_prefixmap = prefixmap;
+        self
+    }
+
+    pub fn without_colors(mut self) -> Self {
+        self.display_with_colors = false;
+        self.nodes_prefixmap = self.nodes_prefixmap.without_rich_qualifying();
+        self
+    }
+
+    pub fn with_conformance_mode(mut self, conformance_mode: ConformanceMode) -> Self {
+        self.conformance_mode = conformance_mode;
+        self
     }
 
     pub fn results(&self) -> &Vec<ValidationResult<S>> {
         &self.results
     }
+
+    /// The data graph conforms to the shapes graph if there are no results,
+    /// or no violations when the conformance mode only takes them into account
+    ///
+    /// https://www.w3.org/TR/shacl/#conforms
+    pub fn conforms(&self) -> bool {
+        match self.conformance_mode {
+            ConformanceMode::Strict => self.results.is_empty(),
+            ConformanceMode::Violations => {
+                let violation = S::iri_s2term(&SH_VIOLATION);
+                !self
+                    .results
+                    .iter()
+                    .any(|result| *result.severity() == violation)
+            }
+        }
+    }
+
+    /// Writes the report as a `sh:ValidationReport` in the given RDF graph
+    ///
+    /// https://www.w3.org/TR/shacl/#validation-report
+    pub fn to_rdf<RDF: SRDFBuilder>(&self, rdf: &mut RDF) -> Result<(), RDF::Err> {
+        let mut prefixmap = self.nodes_prefixmap.clone().without_rich_qualifying();
+        let _ = prefixmap.insert("sh", &SH);
+        rdf.add_prefix_map(prefixmap)?;
+
+        // fresh blank nodes do not collide with the ones of the graph
+        let report = RDF::bnode_as_subject(rdf.add_bnode()?);
+        rdf.add_triple(
+            &report,
+            &RDF::iri_s2iri(&RDF_TYPE),
+            &RDF::iri_s2term(&SH_VALIDATION_REPORT),
+        )?;
+        rdf.add_triple(
+            &report,
+            &RDF::iri_s2iri(&SH_CONFORMS),
+            &RDF::object_as_term(&RDFNode::literal(Literal::boolean(self.conforms()))),
+        )?;
+
+        for result in self.results.iter() {
+            let node = RDF::bnode_as_subject(rdf.add_bnode()?);
+            rdf.add_triple(
+                &report,
+                &RDF::iri_s2iri(&SH_RESULT),
+                &RDF::subject_as_term(&node),
+            )?;
+            result.to_rdf(rdf, &node)?;
+        }
+
+        Ok(())
+    }
+
+    /// The report as a JSON-LD document, using the `sh` prefix for the SHACL
+    /// vocabulary
+    pub fn to_json_ld(&self) -> Value {
+        let results: Vec<Value> = self
+            .results
+            .iter()
+            .map(|result| result.to_json_ld())
+            .collect();
+        json!({
+            "@context": { "sh": SH_STR },
+            "@type": "sh:ValidationReport",
+            "sh:conforms": self.conforms(),
+            "sh:result": results,
+        })
+    }
+
+    /// Replaces the results about the focus nodes in `nodes` by the results
+    /// of `report`, which has been obtained validating only those nodes
+    pub fn update(&mut self, nodes: &HashSet<S::Term>, report: ValidationReport<S>) {
+        self.results
+            .retain(|result| !nodes.contains(result.focus_node()));
+        self.results.extend(report.results);
+    }
+
+    /// Shows the results that have been added to this report with respect to
+    /// `previous`, prefixed by `+`, and the ones that have been removed,
+    /// prefixed by `-`, one per line
+    pub fn show_diff(&self, previous: &ValidationReport<S>) -> String {
+        let added = self
+            .results
+            .iter()
+            .filter(|result| !previous.results.contains(result))
+            .map(|result| format!("+ {}", self.show_result(result)));
+        let removed = previous
+            .results
+            .iter()
+            .filter(|result| !self.results.contains(result))
+            .map(|result| format!("- {}", self.show_result(result)));
+        added.chain(removed).collect::<Vec<_>>().join("\n")
+    }
+
+    fn show_result(&self, result: &ValidationResult<S>) -> String {
+        let mut str = format!(
+            "{} {} {}",
+            self.show_term(result.severity()),
+            self.show_term(result.focus_node()),
+            self.show_term(result.constraint_component())
+        );
+        if let Some(path) = result.path() {
+            str.push_str(&format!(" path: {}", self.show_path(path)));
+        }
+        if let Some(value) = result.value() {
+            str.push_str(&format!(" value: {}", self.show_term(value)));
+        }
+        str
+    }
+
+    fn show_term(&self, term: &S::Term) -> String {
+        match S::term_as_iri(term) {
+            Some(iri) => self.nodes_prefixmap.qualify(&S::iri2iri_s(&iri)),
+            None => term.to_string(),
+        }
+    }
+
+    /// Shows a path with the syntax of SPARQL property paths, qualifying the
+    /// IRIs of its predicates
+    fn show_path(&self, path: &SHACLPath) -> String {
+        let join = |paths: &[SHACLPath], separator| {
+            let paths: Vec<_> = paths.iter().map(|path| self.show_path(path)).collect();
+            format!("({})", paths.join(separator))
+        };
+        match path {
+            SHACLPath::Predicate { pred } => self.nodes_prefixmap.qualify(pred),
+            SHACLPath::Alternative { paths } => join(paths, " | "),
+            SHACLPath::Sequence { paths } => join(paths, " / "),
+            SHACLPath::Inverse { path } => format!("^{}", self.show_path(path)),
+            SHACLPath::ZeroOrMore { path } => format!("{}*", self.show_path(path)),
+            SHACLPath::OneOrMore { path } => format!("{}+", self.show_path(path)),
+            SHACLPath::ZeroOrOne { path } => format!("{}?", self.show_path(path)),
+        }
+    }
+
+    fn colored(&self, str: String, color: Color) -> String {
+        match self.display_with_colors {
+            true => str.color(color).to_string(),
+            false => str,
+        }
+    }
 }
 
 impl<S: SRDF> ValidationReport<S> {
     pub fn parse(store: &S, subject: S::Term) -> Result<Self, ReportError> {
         let mut results = Vec::new();
-        for result in get_objects_for(store, &subject, &S::iri_s2iri(&shacl_ast::SH_RESULT))? {
+        for result in get_objects_for(store, &subject, &S::iri_s2iri(&SH_RESULT))? {
             results.push(ValidationResult::parse(store, &result)?);
         }
         Ok(ValidationReport::new(results))
     }
 }
 
+/// Converts a term to its JSON-LD representation, IRIs and blank nodes as node
+/// objects and literals as value objects
+pub(crate) fn term_to_json_ld<S: SRDFBasic>(term: &S::Term) -> Value {
+    if let Some(iri) = S::term_as_iri(term) {
+        return json!({ "@id": S::iri2iri_s(&iri).as_str() });
+    }
+    match S::term_as_literal(term) {
+        Some(literal) => {
+            let mut value = Map::new();
+            value.insert("@value".to_string(), json!(S::lexical_form(&literal)));
+            let datatype = S::datatype_str(&literal);
+            match S::lang(&literal) {
+                Some(lang) => {
+                    value.insert("@language".to_string(), json!(lang));
+                }
+                // plain strings do not need a datatype
+                None if datatype == XSD_STRING => {}
+                None => {
+                    value.insert("@type".to_string(), json!(datatype));
+                }
+            }
+            Value::Object(value)
+        }
+        None => json!({ "@id": term.to_string() }),
+    }
+}
+
+impl<S: SRDFBasic> Display for ValidationReport<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.results.is_empty() {
+            return writeln!(f, "{}", self.colored("Conforms".to_string(), Color::Green));
+        }
+
+        let (header, color) = match self.conforms() {
+            true => ("Conforms", Color::Green),
+            false => ("Does not conform", Color::Red),
+        };
+        let header = format!("{header}, {} result(s)", self.results.len());
+        writeln!(f, "{}", self.colored(header, color))?;
+        for result in self.results.iter() {
+            let color = if *result.severity() == S::iri_s2term(&SH_WARNING) {
+                Color::Yellow
+            } else if *result.severity() == S::iri_s2term(&SH_INFO) {
+                Color::Blue
+            } else {
+                Color::Red
+            };
+            writeln!(
+                f,
+                "{} {} {}",
+                self.colored(self.show_term(result.severity()), color),
+                self.show_term(result.focus_node()),
+                self.show_term(result.constraint_component())
+            )?;
+            if let Some(source) = result.source() {
+                writeln!(f, "  shape: {}", self.show_term(source))?;
+            }
+            if let Some(path) = result.path() {
+                writeln!(f, "  path: {}", self.show_path(path))?;
+            }
+            if let Some(value) = result.value() {
+                writeln!(f, "  value: {}", self.show_term(value))?;
+            }
+            for message in result.messages() {
+                match S::term_as_literal(message) {
+                    Some(literal) => match S::lang(&literal) {
+                        Some(lang) => {
+                            writeln!(f, "  message ({lang}): {}", S::lexical_form(&literal))?
+                        }
+                        None => writeln!(f, "  message: {}", S::lexical_form(&literal))?,
+                    },
+                    None => writeln!(f, "  message: {message}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<S: SRDFBasic> Debug for ValidationReport<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ValidationReport")
@@ -42,17 +333,229 @@ impl<S: SRDFBasic> Debug for ValidationReport<S> {
 
 impl<S: SRDFBasic> Default for ValidationReport<S> {
     fn default() -> Self {
-        ValidationReport {
-            results: Vec::new(),
-        }
+        ValidationReport::new(Vec::new())
     }
 }
 
+/// Reports are compared as sets of results, regardless of their order
 impl<S: SRDFBasic> PartialEq for ValidationReport<S> {
     fn eq(&self, other: &Self) -> bool {
         if self.results.len() != other.results.len() {
             return false;
         }
+        let mut pending: Vec<&ValidationResult<S>> = other.results.iter().collect();
+        for result in self.results.iter() {
+            match pending.iter().position(|other| result == *other) {
+                Some(index) => {
+                    pending.swap_remove(index);
+                }
+                None => return false,
+            }
+        }
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use iri_s::iri;
+    use oxrdfio::JsonLdProfileSet;
+    use oxrdfio::RdfFormat;
+    use oxrdfio::RdfParser;
+    use prefixmap::PrefixMap;
+    use shacl_ast::compiled::schema::CompiledSchema;
+    use shacl_ast::SH_VALIDATION_REPORT;
+    use sparql_service::RdfData;
+    use srdf::RDFFormat;
+    use srdf::ReaderMode;
+    use srdf::SHACLPath;
+    use srdf::SRDFBasic;
+    use srdf::SRDFBuilder;
+    use srdf::SRDFGraph;
+    use srdf::RDF_TYPE;
+    use srdf::SRDF;
+
+    use crate::shacl_processor::GraphValidation;
+    use crate::shacl_processor::ShaclProcessor;
+    use crate::shacl_processor::ShaclValidationMode;
+    use crate::store::ShaclDataManager;
+    use crate::test_utils::results;
+    use crate::test_utils::validate;
+    use crate::test_utils::PREFIXES;
+
+    use super::ValidationReport;
+
+    fn report() -> ValidationReport<RdfData> {
+        let shapes = r#":S a sh:NodeShape ; sh:targetNode :a, :b, :c ;
+            sh:property [ sh:path :p ; sh:datatype xsd:integer ; sh:maxCount 1 ;
+                sh:message "Wrong value"@en ] ."#;
+        let data = r#":a :p "x", 2 . :b :p 1 . :c :p [] ."#;
+        validate(shapes, data, ShaclValidationMode::Native)
+    }
+
+    /// The reports of the graph, as nodes of type `sh:ValidationReport`
+    fn reports(graph: &SRDFGraph) -> Vec<<SRDFGraph as SRDFBasic>::Subject> {
+        graph
+            .subjects_with_predicate_object(
+                &SRDFGraph::iri_s2iri(&RDF_TYPE),
+                &SRDFGraph::iri_s2term(&SH_VALIDATION_REPORT),
+            )
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    /// Parses the only report of the graph
+    fn parse(graph: SRDFGraph) -> ValidationReport<RdfData> {
+        let reports = reports(&graph);
+        assert_eq!(reports.len(), 1);
+        let report = SRDFGraph::subject_as_term(&reports[0]);
+        ValidationReport::parse(&RdfData::from_graph(graph).unwrap(), report).unwrap()
+    }
+
+    /// A report whose result has an alternative path with an inverse path
+    fn complex_path_report() -> ValidationReport<RdfData> {
+        let shapes = r#":S a sh:NodeShape ; sh:targetNode :a ;
+            sh:property [ sh:path [ sh:alternativePath ( :p [ sh:inversePath :q ] ) ] ;
+                sh:maxCount 1 ; sh:message "Too many values of {$path}" ] ."#;
+        validate(shapes, ":a :p 1 . :x :q :a .", ShaclValidationMode::Sparql)
+    }
+
+    fn json_ld_graph(report: &ValidationReport<RdfData>) -> SRDFGraph {
+        let json_ld = report.to_json_ld().to_string();
+        let mut graph = SRDFGraph::empty();
+        let format = RdfFormat::JsonLd {
+            profile: JsonLdProfileSet::empty(),
+        };
+        for quad in RdfParser::from_format(format).for_slice(json_ld.as_bytes()) {
+            let quad = quad.unwrap();
+            graph
+                .add_triple(&quad.subject, &quad.predicate, &quad.object)
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn rdf_round_trip() {
+        for report in [report(), complex_path_report()] {
+            let mut graph = SRDFGraph::empty();
+            report.to_rdf(&mut graph).unwrap();
+            assert_eq!(parse(graph), report);
+        }
+    }
+
+    #[test]
+    fn complex_paths_are_kept_in_results() {
+        let report = complex_path_report().without_colors();
+        let path = SHACLPath::Alternative {
+            paths: vec![
+                SHACLPath::iri(iri!("http://example.org/p")),
+                SHACLPath::Inverse {
+                    path: Box::new(SHACLPath::iri(iri!("http://example.org/q"))),
+                },
+            ],
+        };
+        assert_eq!(report.results().len(), 1);
+        assert_eq!(report.results()[0].path(), Some(&path));
+        let mut prefixmap = PrefixMap::new();
+        prefixmap.insert("", &iri!("http://example.org/")).unwrap();
+        let report = report.with_nodes_prefixmap(prefixmap);
+        let shown = report.to_string();
+        assert!(shown.contains("path: (:p | ^:q)"));
+        assert!(shown.contains(
+            "message: Too many values of (<http://example.org/p> | ^(<http://example.org/q>))"
+        ));
+    }
+
+    #[test]
+    fn reports_in_the_same_graph_do_not_collide() {
+        let mut graph = SRDFGraph::empty();
+        report().to_rdf(&mut graph).unwrap();
+        report().to_rdf(&mut graph).unwrap();
+        assert_eq!(reports(&graph).len(), 2);
+    }
+
+    #[test]
+    fn json_ld_round_trip() {
+        for report in [report(), complex_path_report()] {
+            assert_eq!(parse(json_ld_graph(&report)), report);
+        }
+    }
+
+    const PERSON_SHAPES: &str = r#":S a sh:NodeShape ; sh:targetClass :Person ;
+        sh:property [ sh:path :name ; sh:minCount 1 ] ."#;
+
+    fn graph_validation(data: &str) -> GraphValidation {
+        let data = format!("{PREFIXES}{data}");
+        let graph =
+            SRDFGraph::from_str(&data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        GraphValidation::from_rdf_data(
+            RdfData::from_graph(graph).unwrap(),
+            ShaclValidationMode::Native,
+        )
+    }
+
+    fn person_schema() -> CompiledSchema<RdfData> {
+        let shapes = format!("{PREFIXES}{PERSON_SHAPES}");
+        ShaclDataManager::load(shapes.as_bytes(), RDFFormat::Turtle, None).unwrap()
+    }
+
+    fn example_prefixmap() -> PrefixMap {
+        let mut prefixmap = PrefixMap::new();
+        prefixmap.insert("", &iri!("http://example.org/")).unwrap();
+        prefixmap
+    }
+
+    #[test]
+    fn update_replaces_the_results_of_the_revalidated_nodes() {
+        let schema = person_schema();
+        let before = graph_validation(":a a :Person . :b a :Person ; :name \"b\" . :c a :Person .");
+        let after = graph_validation(
+            ":a a :Person ; :name \"a\" . :b a :Person ; :name \"b\" . :d a :Person .",
+        );
+        let mut report = before.validate(&schema).unwrap();
+        // :a changed, :c left the target of the shape and :d entered it
+        let mut nodes: HashSet<_> = before
+            .targets(&schema)
+            .symmetric_difference(&after.targets(&schema))
+            .map(|(_, node)| node.clone())
+            .collect();
+        nodes.insert(RdfData::iri_s2term(&iri!("http://example.org/a")));
+        let partial = after.validate_nodes(&schema, &nodes).unwrap();
+        report.update(&nodes, partial);
+        assert_eq!(results(&report), results(&after.validate(&schema).unwrap()));
+    }
+
+    #[test]
+    fn update_keeps_the_results_of_other_nodes() {
+        let schema = person_schema();
+        let validation = graph_validation(":a a :Person . :b a :Person .");
+        let mut report = validation.validate(&schema).unwrap();
+        let nodes = HashSet::from([RdfData::iri_s2term(&iri!("http://example.org/a"))]);
+        report.update(&nodes, ValidationReport::new(Vec::new()));
+        let nodes: Vec<_> = results(&report).into_iter().map(|(node, _)| node).collect();
+        assert_eq!(nodes, vec!["<http://example.org/b>".to_string()]);
+    }
+
+    #[test]
+    fn show_diff_shows_added_and_removed_results() {
+        let schema = person_schema();
+        let before = graph_validation(":a a :Person . :b a :Person .")
+            .validate(&schema)
+            .unwrap();
+        let after = graph_validation(":b a :Person . :c a :Person .")
+            .validate(&schema)
+            .unwrap()
+            .without_colors()
+            .with_nodes_prefixmap(example_prefixmap());
+        let diff = after.show_diff(&before);
+        let lines: Vec<&str> = diff.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("+ ") && lines[0].contains(" :c "));
+        assert!(lines[1].starts_with("- ") && lines[1].contains(" :a "));
+        assert!(after.show_diff(&after.clone()).is_empty());
+    }
+}
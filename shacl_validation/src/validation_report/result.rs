@@ -1,21 +1,33 @@
 use std::fmt::Debug;
 
+use iri_s::IriS;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+use shacl_ast::property_shape::write_path;
 use shacl_ast::*;
+use srdf::SHACLPath;
 use srdf::SRDFBasic;
+use srdf::SRDFBuilder;
+use srdf::RDF_TYPE;
 use srdf::SRDF;
 
 use crate::helpers::srdf::get_object_for;
+use crate::helpers::srdf::get_objects_for;
+use crate::helpers::srdf::get_path;
+
+use super::report::term_to_json_ld;
 
 use super::validation_report_error::ResultError;
 
 pub struct ValidationResult<S: SRDFBasic> {
     focus_node: S::Term,           // required
-    path: Option<S::Term>,         // optional
+    path: Option<SHACLPath>,       // optional
     value: Option<S::Term>,        // optional
     source: Option<S::Term>,       // optional
     constraint_component: S::Term, // required
     details: Option<Vec<S::Term>>, // optional
-    message: Option<S::Term>,      // optional
+    messages: Vec<S::Term>,        // optional
     severity: S::Term,             // required
 }
 
@@ -23,12 +35,12 @@ pub struct ValidationResult<S: SRDFBasic> {
 impl<S: SRDFBasic> ValidationResult<S> {
     pub fn new(
         focus_node: S::Term,
-        path: Option<S::Term>,
+        path: Option<SHACLPath>,
         value: Option<S::Term>,
         source: Option<S::Term>,
         constraint_component: S::Term,
         details: Option<Vec<S::Term>>,
-        message: Option<S::Term>,
+        messages: Vec<S::Term>,
         severity: S::Term,
     ) -> Self {
         Self {
@@ -38,10 +50,169 @@ impl<S: SRDFBasic> ValidationResult<S> {
             source,
             constraint_component,
             details,
-            message,
+            messages,
             severity,
         }
     }
+
+    pub fn focus_node(&self) -> &S::Term {
+        &self.focus_node
+    }
+
+    pub fn path(&self) -> Option<&SHACLPath> {
+        self.path.as_ref()
+    }
+
+    pub fn value(&self) -> Option<&S::Term> {
+        self.value.as_ref()
+    }
+
+    pub fn source(&self) -> Option<&S::Term> {
+        self.source.as_ref()
+    }
+
+    pub fn constraint_component(&self) -> &S::Term {
+        &self.constraint_component
+    }
+
+    pub fn details(&self) -> Option<&Vec<S::Term>> {
+        self.details.as_ref()
+    }
+
+    /// The values of `sh:resultMessage`, at most one for each language
+    pub fn messages(&self) -> &Vec<S::Term> {
+        &self.messages
+    }
+
+    pub fn severity(&self) -> &S::Term {
+        &self.severity
+    }
+
+    /// Writes the result as a `sh:ValidationResult` with the given subject
+    pub(crate) fn to_rdf<RDF: SRDFBuilder>(
+        &self,
+        rdf: &mut RDF,
+        subject: &RDF::Subject,
+    ) -> Result<(), RDF::Err> {
+        rdf.add_triple(
+            subject,
+            &RDF::iri_s2iri(&RDF_TYPE),
+            &RDF::iri_s2term(&SH_VALIDATION_RESULT),
+        )?;
+        for (predicate, term) in self.fields() {
+            let object = RDF::object_as_term(&S::term_as_object(term));
+            rdf.add_triple(subject, &RDF::iri_s2iri(predicate), &object)?;
+        }
+        if let Some(path) = &self.path {
+            let path = write_path(path, rdf)?;
+            rdf.add_triple(subject, &RDF::iri_s2iri(&SH_RESULT_PATH), &path)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn to_json_ld(&self) -> Value {
+        let mut result = Map::new();
+        result.insert("@type".to_string(), json!("sh:ValidationResult"));
+        for (predicate, term) in self.fields() {
+            let key = predicate.as_str().replacen(SH_STR, "sh:", 1);
+            let value = term_to_json_ld::<S>(term);
+            match result.get_mut(&key) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(previous) => *previous = json!([previous.take(), value]),
+                None => {
+                    let value = match predicate == &*SH_DETAIL {
+                        true => json!([value]),
+                        false => value,
+                    };
+                    result.insert(key, value);
+                }
+            }
+        }
+        if let Some(path) = &self.path {
+            result.insert("sh:resultPath".to_string(), path_to_json_ld(path));
+        }
+        Value::Object(result)
+    }
+
+    /// The SHACL properties of the result with their values
+    fn fields(&self) -> Vec<(&IriS, &S::Term)> {
+        let mut fields = vec![
+            (&*SH_FOCUS_NODE, &self.focus_node),
+            (&*SH_SOURCE_CONSTRAINT_COMPONENT, &self.constraint_component),
+            (&*SH_RESULT_SEVERITY, &self.severity),
+        ];
+        let optional = [(&*SH_VALUE, &self.value), (&*SH_SOURCE_SHAPE, &self.source)];
+        for (predicate, term) in optional {
+            if let Some(term) = term {
+                fields.push((predicate, term));
+            }
+        }
+        fields.extend(
+            self.messages
+                .iter()
+                .map(|message| (&*SH_RESULT_MESSAGE, message)),
+        );
+        if let Some(details) = &self.details {
+            fields.extend(details.iter().map(|detail| (&*SH_DETAIL, detail)));
+        }
+        fields
+    }
+}
+
+/// Converts a path to its JSON-LD representation, following the syntax rules
+/// of SHACL property paths, where sequence paths are lists
+fn path_to_json_ld(path: &SHACLPath) -> Value {
+    let list = |paths: &[SHACLPath]| json!({ "@list": paths.iter().map(path_to_json_ld).collect::<Vec<_>>() });
+    match path {
+        SHACLPath::Predicate { pred } => json!({ "@id": pred.as_str() }),
+        SHACLPath::Sequence { paths } => list(paths),
+        SHACLPath::Alternative { paths } => json!({ "sh:alternativePath": list(paths) }),
+        SHACLPath::Inverse { path } => json!({ "sh:inversePath": path_to_json_ld(path) }),
+        SHACLPath::ZeroOrMore { path } => json!({ "sh:zeroOrMorePath": path_to_json_ld(path) }),
+        SHACLPath::OneOrMore { path } => json!({ "sh:oneOrMorePath": path_to_json_ld(path) }),
+        SHACLPath::ZeroOrOne { path } => json!({ "sh:zeroOrOnePath": path_to_json_ld(path) }),
+    }
+}
+
+fn same_term<S: SRDFBasic>(a: &S::Term, b: &S::Term) -> bool {
+    (S::term_is_bnode(a) && S::term_is_bnode(b)) || a == b
+}
+
+fn same_optional_term<S: SRDFBasic>(a: &Option<S::Term>, b: &Option<S::Term>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => same_term::<S>(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Two results are equal if they have the same values for all the fields but
+/// the details and messages, which are implementation specific. Blank nodes
+/// are local to each graph, so any two of them are considered equal
+impl<S: SRDFBasic> PartialEq for ValidationResult<S> {
+    fn eq(&self, other: &Self) -> bool {
+        same_term::<S>(&self.focus_node, &other.focus_node)
+            && self.path == other.path
+            && same_optional_term::<S>(&self.value, &other.value)
+            && same_optional_term::<S>(&self.source, &other.source)
+            && same_term::<S>(&self.constraint_component, &other.constraint_component)
+            && same_term::<S>(&self.severity, &other.severity)
+    }
+}
+
+impl<S: SRDFBasic> Clone for ValidationResult<S> {
+    fn clone(&self) -> Self {
+        Self {
+            focus_node: self.focus_node.clone(),
+            path: self.path.clone(),
+            value: self.value.clone(),
+            source: self.source.clone(),
+            constraint_component: self.constraint_component.clone(),
+            details: self.details.clone(),
+            messages: self.messages.clone(),
+            severity: self.severity.clone(),
+        }
+    }
 }
 
 impl<S: SRDFBasic> Debug for ValidationResult<S> {
@@ -53,7 +224,7 @@ impl<S: SRDFBasic> Debug for ValidationResult<S> {
             .field("source", &self.source)
             .field("constraint_component", &self.constraint_component)
             .field("details", &self.details)
-            .field("message", &self.message)
+            .field("messages", &self.messages)
             .field("severity", &self.severity)
             .finish()
     }
@@ -87,9 +258,16 @@ impl<S: SRDF> ValidationResult<S> {
         };
 
         // 2. Second, we must process the optional fields
-        let path = get_object_for(store, validation_result, &S::iri_s2iri(&SH_RESULT_PATH))?;
+        let path = match get_object_for(store, validation_result, &S::iri_s2iri(&SH_RESULT_PATH))? {
+            Some(path) => Some(get_path(store, &path)?),
+            None => None,
+        };
         let source = get_object_for(store, validation_result, &S::iri_s2iri(&SH_SOURCE_SHAPE))?;
         let value = get_object_for(store, validation_result, &S::iri_s2iri(&SH_VALUE))?;
+        let messages =
+            get_objects_for(store, validation_result, &S::iri_s2iri(&SH_RESULT_MESSAGE))?
+                .into_iter()
+                .collect();
 
         // 3. Lastly we build the ValidationResult
         Ok(ValidationResult {
@@ -99,7 +277,7 @@ impl<S: SRDF> ValidationResult<S> {
             source,
             constraint_component,
             details: None,
-            message: None,
+            messages,
             severity,
         })
     }
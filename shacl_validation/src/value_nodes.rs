@@ -19,6 +19,9 @@ pub trait IterationStrategy<S: SRDFBasic> {
         &'a self,
         value_nodes: &'a ValueNodes<S>,
     ) -> Box<dyn Iterator<Item = (&'a S::Term, &'a Self::Item)> + 'a>;
+
+    /// The value node reported in the validation result of an item, if any
+    fn to_value(&self, item: &Self::Item) -> Option<S::Term>;
 }
 
 pub struct FocusNodeIteration;
@@ -32,6 +35,10 @@ impl<S: SRDFBasic> IterationStrategy<S> for FocusNodeIteration {
     ) -> Box<dyn Iterator<Item = (&'a S::Term, &'a Self::Item)> + 'a> {
         Box::new(value_nodes.0.iter())
     }
+
+    fn to_value(&self, _: &Self::Item) -> Option<S::Term> {
+        None
+    }
 }
 
 pub struct ValueNodeIteration;
@@ -49,4 +56,8 @@ impl<S: SRDFBasic> IterationStrategy<S> for ValueNodeIteration {
                 .map(move |value_node| (focus_node, value_node))
         }))
     }
+
+    fn to_value(&self, item: &Self::Item) -> Option<S::Term> {
+        Some(item.clone())
+    }
 }
@@ -1,12 +1,10 @@
-use std::iter::once;
+use shex_ast::ShapeExprLabel;
+use srdf::{QuerySRDF2, SRDF};
 
-use shex_ast::{object_value::ObjectValue, ShapeExprLabel};
-use srdf::SRDF;
-
-use crate::{NodeSelector, ShapeSelector};
+use crate::{NodeSelect, NodeSelector, NodeSelectorError, ShapeSelector};
 
 /// Combines a [`NodeSelector`] with a [`ShapeExprLabel`]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Association {
     pub node_selector: NodeSelector,
     pub shape_selector: ShapeSelector,
@@ -20,17 +18,21 @@ impl Association {
         }
     }
 
-    pub fn iter_node_shape<S>(
+    /// Returns the pairs of nodes and shape labels selected by this association in `rdf`
+    pub fn node_shape_pairs<S>(
         &self,
         rdf: &S,
-    ) -> impl Iterator<Item = (&ObjectValue, &ShapeExprLabel)>
+    ) -> Result<Vec<(S::Term, &ShapeExprLabel)>, NodeSelectorError>
     where
-        S: SRDF,
+        S: SRDF + QuerySRDF2,
     {
-        self.node_selector.iter_node(rdf).flat_map(move |node| {
-            self.shape_selector
-                .iter_shape()
-                .flat_map(move |label| once((node, label)))
-        })
+        let nodes = self.node_selector.select(rdf)?;
+        let mut result = Vec::new();
+        for node in nodes {
+            for label in self.shape_selector.iter_shape() {
+                result.push((node.clone(), label))
+            }
+        }
+        Ok(result)
     }
 }
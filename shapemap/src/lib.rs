@@ -9,11 +9,13 @@ pub mod node_selector;
 pub mod shape_selector;
 
 pub mod query_shape_map;
+pub mod result_shape_map;
 pub mod shapemap;
 pub mod shapemap_state;
 
 pub use crate::association::*;
 pub use crate::node_selector::*;
+pub use crate::result_shape_map::*;
 pub use crate::shape_selector::*;
 pub use crate::shapemap::*;
 pub use crate::shapemap_state::*;
@@ -1,19 +1,24 @@
+use indexmap::IndexSet;
 use iri_s::IriS;
-use prefixmap::IriRef;
-use shex_ast::{object_value::ObjectValue, Node};
+use prefixmap::{IriRef, PrefixMapError};
+use shex_ast::object_value::ObjectValue;
 use srdf::literal::Literal;
 use srdf::shacl_path::SHACLPath;
-use srdf::SRDF;
+use srdf::{Object, QuerySRDF2, SRDF};
+use std::hash::Hash;
 use thiserror::Error;
 
+/// IRI used by generic node selectors whose parameter is a SPARQL query
+pub const SPARQL_SELECTOR_STR: &str = "http://www.w3.org/ns/shex#Sparql";
+
 /// A NodeSelector following [ShapeMap spec](https://shexspec.github.io/shape-map/#shapemap-structure) can be used to select RDF Nodes
 ///
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum NodeSelector {
     Node(ObjectValue),
     TriplePattern {
         subject: Pattern,
-        pred: IriS,
+        pred: IriRef,
         object: Pattern,
     },
     TriplePatternPath {
@@ -25,7 +30,7 @@ pub enum NodeSelector {
         query: String,
     },
     Generic {
-        iri: IriS,
+        iri: IriRef,
         param: String,
     },
 }
@@ -47,57 +52,378 @@ impl NodeSelector {
         NodeSelector::Node(ObjectValue::prefixed(alias, local))
     }
 
-    pub fn iter_node<S>(&self, _rdf: &S) -> impl Iterator<Item = &ObjectValue>
-    where
-        S: SRDF,
-    {
-        match self {
-            NodeSelector::Node(value) => std::iter::once(value),
-            _ => todo!(),
+    pub fn triple_pattern(subject: Pattern, pred: IriRef, object: Pattern) -> NodeSelector {
+        NodeSelector::TriplePattern {
+            subject,
+            pred,
+            object,
+        }
+    }
+
+    pub fn triple_pattern_path(subject: Pattern, pred: SHACLPath, object: Pattern) -> NodeSelector {
+        NodeSelector::TriplePatternPath {
+            subject,
+            pred,
+            object,
+        }
+    }
+
+    pub fn sparql(query: &str) -> NodeSelector {
+        NodeSelector::Sparql {
+            query: query.to_string(),
+        }
+    }
+
+    pub fn generic(iri: IriRef, param: &str) -> NodeSelector {
+        NodeSelector::Generic {
+            iri,
+            param: param.to_string(),
         }
     }
 }
 
 #[derive(Debug, Error)]
-pub enum NodeSelectorError {}
+pub enum NodeSelectorError {
+    #[error("Error obtaining nodes from RDF data: {error}")]
+    SRDFError { error: String },
+
+    #[error(transparent)]
+    PrefixMapError(#[from] PrefixMapError),
+
+    #[error("Node {node} can't be used as subject of a triple pattern")]
+    NodeNotSubject { node: String },
+
+    #[error("Triple pattern {pattern} must contain exactly one FOCUS and it must be the subject or the object")]
+    InvalidTriplePattern { pattern: String },
+
+    #[error("Error running SPARQL query for node selector: {error}\nQuery:\n{query}")]
+    SparqlError { query: String, error: String },
+
+    #[error("Unknown generic node selector {iri} with parameter {param}")]
+    UnknownGenericSelector { iri: IriS, param: String },
+}
 
 impl NodeSelect for NodeSelector {
-    fn select<S>(&self, _rdf: S) -> Result<Vec<S::Term>, NodeSelectorError>
+    fn select<S>(&self, rdf: &S) -> Result<Vec<S::Term>, NodeSelectorError>
     where
-        S: SRDF,
+        S: SRDF + QuerySRDF2,
     {
         match self {
-            NodeSelector::Node(_node) => {
-                todo!()
+            NodeSelector::Node(node) => {
+                let term = object_value2term(node, rdf)?;
+                Ok(vec![term])
             }
             NodeSelector::TriplePattern {
                 subject,
                 pred,
                 object,
-            } => match (subject, pred, object) {
-                (Pattern::Focus, _pred, Pattern::Wildcard) => todo!(),
-                (Pattern::Focus, _pred, Pattern::Node(_node)) => todo!(),
-                (Pattern::Wildcard, _pred, Pattern::Focus) => todo!(),
-                (Pattern::Node(_n), _pred, Pattern::Focus) => todo!(),
-                (_, _, _) => todo!(),
+            } => {
+                let pred = S::iri_s2iri(&resolve_iri_ref(pred, rdf)?);
+                match (subject, object) {
+                    (Pattern::Focus, Pattern::Wildcard) => {
+                        let triples = rdf.triples_with_predicate(&pred).map_err(cnv_err::<S>)?;
+                        Ok(unique(
+                            triples.iter().map(|t| S::subject_as_term(&t.subj())),
+                        ))
+                    }
+                    (Pattern::Focus, Pattern::Node(node)) => {
+                        let object = object_value2term(node, rdf)?;
+                        let subjects = rdf
+                            .subjects_with_predicate_object(&pred, &object)
+                            .map_err(cnv_err::<S>)?;
+                        Ok(unique(subjects.iter().map(S::subject_as_term)))
+                    }
+                    (Pattern::Wildcard, Pattern::Focus) => {
+                        let triples = rdf.triples_with_predicate(&pred).map_err(cnv_err::<S>)?;
+                        Ok(unique(triples.iter().map(|t| t.obj())))
+                    }
+                    (Pattern::Node(node), Pattern::Focus) => {
+                        let term = object_value2term(node, rdf)?;
+                        let subject = S::term_as_subject(&term).ok_or_else(|| {
+                            NodeSelectorError::NodeNotSubject {
+                                node: node.to_string(),
+                            }
+                        })?;
+                        let objects = rdf
+                            .objects_for_subject_predicate(&subject, &pred)
+                            .map_err(cnv_err::<S>)?;
+                        Ok(unique(objects.into_iter()))
+                    }
+                    (_, _) => Err(NodeSelectorError::InvalidTriplePattern {
+                        pattern: self.to_string(),
+                    }),
+                }
+            }
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => {
+                let path = pred.sparql_path();
+                let pattern = match (subject, object) {
+                    (Pattern::Focus, Pattern::Wildcard) => format!("?focus {path} ?object"),
+                    (Pattern::Focus, Pattern::Node(node)) => {
+                        format!("?focus {path} {}", sparql_term(node, rdf)?)
+                    }
+                    (Pattern::Wildcard, Pattern::Focus) => format!("?subject {path} ?focus"),
+                    (Pattern::Node(node), Pattern::Focus) => {
+                        format!("{} {path} ?focus", sparql_term(node, rdf)?)
+                    }
+                    (_, _) => {
+                        return Err(NodeSelectorError::InvalidTriplePattern {
+                            pattern: self.to_string(),
+                        })
+                    }
+                };
+                let query = format!("SELECT DISTINCT ?focus WHERE {{ {pattern} }}");
+                select_sparql(&query, rdf)
+            }
+            NodeSelector::Sparql { query } => select_sparql(query, rdf),
+            NodeSelector::Generic { iri, param } => {
+                let iri = resolve_iri_ref(iri, rdf)?;
+                if iri.as_str() == SPARQL_SELECTOR_STR {
+                    select_sparql(param, rdf)
+                } else {
+                    Err(NodeSelectorError::UnknownGenericSelector {
+                        iri,
+                        param: param.clone(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Selects the values of the first variable of the solutions of a SPARQL query
+fn select_sparql<S>(query: &str, rdf: &S) -> Result<Vec<S::Term>, NodeSelectorError>
+where
+    S: SRDF + QuerySRDF2,
+{
+    let solutions = rdf
+        .query_select(query)
+        .map_err(|e| NodeSelectorError::SparqlError {
+            query: query.to_string(),
+            error: e.to_string(),
+        })?;
+    Ok(unique(
+        solutions
+            .iter()
+            .filter_map(|solution| solution.find_solution(0).cloned()),
+    ))
+}
+
+/// Removes duplicated terms keeping the order in which they were found
+fn unique<T>(terms: impl Iterator<Item = T>) -> Vec<T>
+where
+    T: Hash + Eq,
+{
+    terms.collect::<IndexSet<_>>().into_iter().collect()
+}
+
+fn cnv_err<S>(err: S::Err) -> NodeSelectorError
+where
+    S: SRDF,
+{
+    NodeSelectorError::SRDFError {
+        error: err.to_string(),
+    }
+}
+
+fn resolve_iri_ref<S>(iri_ref: &IriRef, rdf: &S) -> Result<IriS, NodeSelectorError>
+where
+    S: SRDF,
+{
+    match iri_ref {
+        IriRef::Iri(iri) => Ok(iri.clone()),
+        IriRef::Prefixed { prefix, local } => {
+            let iri = rdf.resolve_prefix_local(prefix, local)?;
+            Ok(iri)
+        }
+    }
+}
+
+fn object_value2term<S>(value: &ObjectValue, rdf: &S) -> Result<S::Term, NodeSelectorError>
+where
+    S: SRDF,
+{
+    match value {
+        ObjectValue::IriRef(iri_ref) => {
+            let iri = resolve_iri_ref(iri_ref, rdf)?;
+            Ok(S::iri_s2term(&iri))
+        }
+        ObjectValue::Literal(lit) => Ok(S::object_as_term(&Object::Literal(lit.clone()))),
+    }
+}
+
+fn sparql_term<S>(value: &ObjectValue, rdf: &S) -> Result<String, NodeSelectorError>
+where
+    S: SRDF,
+{
+    match value {
+        ObjectValue::IriRef(iri_ref) => {
+            let iri = resolve_iri_ref(iri_ref, rdf)?;
+            Ok(format!("<{}>", iri.as_str()))
+        }
+        ObjectValue::Literal(lit) => Ok(lit.to_string()),
+    }
+}
+
+impl std::fmt::Display for NodeSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeSelector::Node(node) => write!(f, "{node}"),
+            NodeSelector::TriplePattern {
+                subject,
+                pred,
+                object,
+            } => write!(f, "{{{subject} {pred} {object}}}"),
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => write!(f, "{{{subject} {pred} {object}}}"),
+            NodeSelector::Sparql { query } => write!(f, "SPARQL \"\"\"{query}\"\"\""),
+            NodeSelector::Generic { iri, param } => match iri {
+                IriRef::Iri(iri) => write!(f, "<{iri}> \"\"\"{param}\"\"\""),
+                IriRef::Prefixed { .. } => write!(f, "{iri} \"\"\"{param}\"\"\""),
             },
-            NodeSelector::TriplePatternPath { .. } => todo!(),
-            NodeSelector::Sparql { .. } => todo!(),
-            NodeSelector::Generic { .. } => todo!(),
         }
     }
 }
-#[derive(Debug, PartialEq)]
 
+#[derive(Debug, PartialEq, Clone)]
 pub enum Pattern {
-    Node(Node),
+    Node(ObjectValue),
     Wildcard,
     Focus,
 }
 
-#[allow(dead_code)]
-trait NodeSelect {
-    fn select<S>(&self, rdf: S) -> Result<Vec<S::Term>, NodeSelectorError>
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Node(node) => write!(f, "{node}"),
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Focus => write!(f, "FOCUS"),
+        }
+    }
+}
+
+/// Types that implement this trait can select RDF nodes from some RDF data
+pub trait NodeSelect {
+    fn select<S>(&self, rdf: &S) -> Result<Vec<S::Term>, NodeSelectorError>
     where
-        S: SRDF;
+        S: SRDF + QuerySRDF2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sparql_service::RdfData;
+    use srdf::{RDFFormat, ReaderMode, SRDFGraph};
+
+    fn rdf_data(data: &str) -> RdfData {
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        RdfData::from_graph(graph).unwrap()
+    }
+
+    fn selected_iris(selector: &NodeSelector, rdf: &RdfData) -> Vec<String> {
+        let mut iris: Vec<String> = selector
+            .select(rdf)
+            .unwrap()
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        iris.sort();
+        iris
+    }
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+        :alice a :Person ; :knows :bob .
+        :bob a :Person ; :knows :carol .
+        :carol :name "Carol" ."#;
+
+    #[test]
+    fn select_focus_type() {
+        let rdf = rdf_data(DATA);
+        let selector = NodeSelector::triple_pattern(
+            Pattern::Focus,
+            IriRef::iri(IriS::new_unchecked(
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+            )),
+            Pattern::Node(ObjectValue::prefixed("", "Person")),
+        );
+        assert_eq!(
+            selected_iris(&selector, &rdf),
+            vec!["<http://example.org/alice>", "<http://example.org/bob>"]
+        );
+    }
+
+    #[test]
+    fn select_wildcard_focus() {
+        let rdf = rdf_data(DATA);
+        let selector = NodeSelector::triple_pattern(
+            Pattern::Wildcard,
+            IriRef::prefixed("", "knows"),
+            Pattern::Focus,
+        );
+        assert_eq!(
+            selected_iris(&selector, &rdf),
+            vec!["<http://example.org/bob>", "<http://example.org/carol>"]
+        );
+    }
+
+    #[test]
+    fn select_path() {
+        let rdf = rdf_data(DATA);
+        let knows = SHACLPath::iri(IriS::new_unchecked("http://example.org/knows"));
+        let selector = NodeSelector::triple_pattern_path(
+            Pattern::Node(ObjectValue::prefixed("", "alice")),
+            SHACLPath::OneOrMore {
+                path: Box::new(knows),
+            },
+            Pattern::Focus,
+        );
+        assert_eq!(
+            selected_iris(&selector, &rdf),
+            vec!["<http://example.org/bob>", "<http://example.org/carol>"]
+        );
+    }
+
+    #[test]
+    fn select_sparql() {
+        let rdf = rdf_data(DATA);
+        let selector =
+            NodeSelector::sparql("SELECT ?x WHERE { ?x <http://example.org/name> ?name }");
+        assert_eq!(
+            selected_iris(&selector, &rdf),
+            vec!["<http://example.org/carol>"]
+        );
+    }
+
+    #[test]
+    fn select_prefixed_generic() {
+        let rdf = rdf_data(&format!(
+            "prefix shex: <http://www.w3.org/ns/shex#>\n{DATA}"
+        ));
+        let selector = NodeSelector::generic(
+            IriRef::prefixed("shex", "Sparql"),
+            "SELECT ?x WHERE { ?x <http://example.org/name> ?name }",
+        );
+        assert_eq!(
+            selected_iris(&selector, &rdf),
+            vec!["<http://example.org/carol>"]
+        );
+    }
+
+    #[test]
+    fn select_two_focus_fails() {
+        let rdf = rdf_data(DATA);
+        let selector = NodeSelector::triple_pattern(
+            Pattern::Focus,
+            IriRef::prefixed("", "knows"),
+            Pattern::Focus,
+        );
+        assert!(selector.select(&rdf).is_err());
+    }
 }
@@ -1,9 +1,9 @@
-use crate::{Association, NodeSelector, ShapeSelector};
+use crate::{Association, NodeSelector, NodeSelectorError, ShapeSelector};
 use prefixmap::PrefixMap;
-use shex_ast::{object_value::ObjectValue, ShapeExprLabel};
-use srdf::SRDF;
+use shex_ast::ShapeExprLabel;
+use srdf::{QuerySRDF2, SRDF};
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, PartialEq, Clone)]
 pub struct QueryShapeMap {
     associations: Vec<Association>,
     nodes_prefixmap: PrefixMap,
@@ -42,13 +42,19 @@ impl QueryShapeMap {
         self.associations.iter()
     }
 
-    pub fn iter_node_shape<'a, S>(
-        &'a self,
-        rdf: &'a S,
-    ) -> impl Iterator<Item = (&ObjectValue, &ShapeExprLabel)> + 'a
+    /// Expands the node selectors of this query shape map against `rdf`,
+    /// obtaining the node/shape pairs of the corresponding fixed shape map
+    pub fn fixed_shape_map<S>(
+        &self,
+        rdf: &S,
+    ) -> Result<Vec<(S::Term, &ShapeExprLabel)>, NodeSelectorError>
     where
-        S: SRDF,
+        S: SRDF + QuerySRDF2,
     {
-        self.iter().flat_map(|assoc| assoc.iter_node_shape(rdf))
+        let mut result = Vec::new();
+        for assoc in self.iter() {
+            result.extend(assoc.node_shape_pairs(rdf)?)
+        }
+        Ok(result)
     }
 }
@@ -0,0 +1,564 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use iri_s::IriS;
+use prefixmap::PrefixMap;
+use serde::ser::SerializeSeq;
+use serde::{Deserializer, Serializer};
+use serde_derive::{Deserialize, Serialize};
+use shex_ast::object_value::ObjectValue;
+use shex_ast::ShapeExprLabel;
+use srdf::Object;
+use thiserror::Error;
+
+use crate::query_shape_map::QueryShapeMap;
+use crate::{NodeSelector, ShapeSelector};
+
+/// Status of a node/shape association in a [result shape map](https://shexspec.github.io/shape-map/#results)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationStatus {
+    Conformant,
+    NonConformant,
+    Pending,
+}
+
+impl ValidationStatus {
+    /// Marker that follows `@` in the compact syntax: `@` for conformant, `@!` for nonconformant and `@?` for pending
+    pub fn compact_marker(&self) -> &str {
+        match self {
+            ValidationStatus::Conformant => "@",
+            ValidationStatus::NonConformant => "@!",
+            ValidationStatus::Pending => "@?",
+        }
+    }
+}
+
+impl Display for ValidationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationStatus::Conformant => write!(f, "conformant"),
+            ValidationStatus::NonConformant => write!(f, "nonconformant"),
+            ValidationStatus::Pending => write!(f, "pending"),
+        }
+    }
+}
+
+/// Association between an RDF node and a shape label in a fixed shape map, with the status of the validation,
+/// a human readable reason and some application specific information
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(try_from = "JsonAssociation", into = "JsonAssociation")]
+pub struct ResultAssociation {
+    pub node: Object,
+    pub shape: ShapeExprLabel,
+    pub status: ValidationStatus,
+    pub reason: Option<String>,
+    pub app_info: Option<serde_json::Value>,
+}
+
+impl ResultAssociation {
+    pub fn new(node: Object, shape: ShapeExprLabel, status: ValidationStatus) -> Self {
+        ResultAssociation {
+            node,
+            shape,
+            status,
+            reason: None,
+            app_info: None,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: Option<String>) -> Self {
+        self.reason = reason;
+        self
+    }
+
+    pub fn with_app_info(mut self, app_info: Option<serde_json::Value>) -> Self {
+        self.app_info = app_info;
+        self
+    }
+}
+
+/// A fixed shape map that contains the results of a validation.
+///
+/// It is serialized as the JSON representation of the [ShapeMap spec](https://shexspec.github.io/shape-map/#json-representation)
+/// and displayed in compact syntax, where nonconformant associations are written as `node@!shape`
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct ResultShapeMap {
+    associations: Vec<ResultAssociation>,
+    // Position of the first association of each node/shape pair
+    index: HashMap<(Object, ShapeExprLabel), usize>,
+    nodes_prefixmap: PrefixMap,
+    shapes_prefixmap: PrefixMap,
+}
+
+impl ResultShapeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn nodes_prefixmap(&self) -> PrefixMap {
+        self.nodes_prefixmap.clone()
+    }
+
+    pub fn shapes_prefixmap(&self) -> PrefixMap {
+        self.shapes_prefixmap.clone()
+    }
+
+    pub fn with_nodes_prefixmap(mut self, prefixmap: &PrefixMap) -> Self {
+        self.nodes_prefixmap = prefixmap.clone();
+        self
+    }
+
+    pub fn with_shapes_prefixmap(mut self, prefixmap: &PrefixMap) -> Self {
+        self.shapes_prefixmap = prefixmap.clone();
+        self
+    }
+
+    pub fn add_association(&mut self, association: ResultAssociation) {
+        self.index
+            .entry((association.node.clone(), association.shape.clone()))
+            .or_insert(self.associations.len());
+        self.associations.push(association)
+    }
+
+    /// Keeps only the associations for which `f` returns true
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&ResultAssociation) -> bool,
+    {
+        self.associations.retain(f);
+        self.reindex()
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (position, assoc) in self.associations.iter().enumerate() {
+            self.index
+                .entry((assoc.node.clone(), assoc.shape.clone()))
+                .or_insert(position);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ResultAssociation> + '_ {
+        self.associations.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.associations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.associations.is_empty()
+    }
+
+    /// Returns the status of the first association between `node` and `shape`
+    pub fn get_status(&self, node: &Object, shape: &ShapeExprLabel) -> Option<&ValidationStatus> {
+        self.index
+            .get(&(node.clone(), shape.clone()))
+            .map(|position| &self.associations[*position].status)
+    }
+
+    /// Obtains the changes of status of the node/shape pairs from `previous` to this result shape map
+    pub fn diff(&self, previous: &ResultShapeMap) -> ResultShapeMapDiff {
+        let mut changes = Vec::new();
+        for assoc in &self.associations {
+            let before = previous.get_status(&assoc.node, &assoc.shape);
+            if before != Some(&assoc.status) {
+                changes.push(StatusChange {
+                    node: assoc.node.clone(),
+                    shape: assoc.shape.clone(),
+                    before: before.cloned(),
+                    after: Some(assoc.status),
+                })
+            }
+        }
+        for assoc in &previous.associations {
+            if self.get_status(&assoc.node, &assoc.shape).is_none() {
+                changes.push(StatusChange {
+                    node: assoc.node.clone(),
+                    shape: assoc.shape.clone(),
+                    before: Some(assoc.status),
+                    after: None,
+                })
+            }
+        }
+        ResultShapeMapDiff {
+            changes,
+            nodes_prefixmap: self.nodes_prefixmap.clone(),
+            shapes_prefixmap: self.shapes_prefixmap.clone(),
+        }
+    }
+
+    /// Parses a result shape map in JSON
+    pub fn from_json_str(str: &str) -> Result<ResultShapeMap, ResultShapeMapError> {
+        serde_json::from_str(str).map_err(|e| ResultShapeMapError::JsonError {
+            error: e.to_string(),
+        })
+    }
+
+    /// Obtains a query shape map with the same node/shape pairs, so they can be validated again
+    pub fn to_query_shape_map(&self) -> Result<QueryShapeMap, ResultShapeMapError> {
+        let mut query_shape_map = QueryShapeMap::new()
+            .with_nodes_prefixmap(&self.nodes_prefixmap)
+            .with_shapes_prefixmap(&self.shapes_prefixmap);
+        for assoc in &self.associations {
+            let node = match &assoc.node {
+                Object::Iri(iri) => ObjectValue::iri(iri.clone()),
+                Object::Literal(lit) => ObjectValue::literal(lit.clone()),
+                Object::BlankNode(_) => {
+                    return Err(ResultShapeMapError::BlankNodeSelector {
+                        node: show_node(&assoc.node, &self.nodes_prefixmap),
+                    })
+                }
+            };
+            let shape = match &assoc.shape {
+                ShapeExprLabel::Start => ShapeSelector::Start,
+                label => ShapeSelector::Label(label.clone()),
+            };
+            query_shape_map.add_association(NodeSelector::Node(node), shape)
+        }
+        Ok(query_shape_map)
+    }
+}
+
+impl serde::Serialize for ResultShapeMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.associations.len()))?;
+        for assoc in &self.associations {
+            seq.serialize_element(assoc)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ResultShapeMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let associations: Vec<ResultAssociation> = serde::Deserialize::deserialize(deserializer)?;
+        let mut result = ResultShapeMap {
+            associations,
+            ..Default::default()
+        };
+        result.reindex();
+        Ok(result)
+    }
+}
+
+impl Display for ResultShapeMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let assocs: Vec<String> = self
+            .associations
+            .iter()
+            .map(|a| {
+                format!(
+                    "{}{}{}",
+                    show_node(&a.node, &self.nodes_prefixmap),
+                    a.status.compact_marker(),
+                    show_label(&a.shape, &self.shapes_prefixmap)
+                )
+            })
+            .collect();
+        write!(f, "{}", assocs.join(",\n"))
+    }
+}
+
+/// Change of the status of a node/shape pair between two result shape maps.
+///
+/// `before` is `None` for pairs that were not in the previous result shape map and `after` is `None` for pairs that were removed
+#[derive(Debug, PartialEq, Clone)]
+pub struct StatusChange {
+    pub node: Object,
+    pub shape: ShapeExprLabel,
+    pub before: Option<ValidationStatus>,
+    pub after: Option<ValidationStatus>,
+}
+
+/// Status changes between two result shape maps
+#[derive(Debug, PartialEq, Clone)]
+pub struct ResultShapeMapDiff {
+    changes: Vec<StatusChange>,
+    nodes_prefixmap: PrefixMap,
+    shapes_prefixmap: PrefixMap,
+}
+
+impl ResultShapeMapDiff {
+    pub fn iter(&self) -> impl Iterator<Item = &StatusChange> + '_ {
+        self.changes.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Shows one line per change: `+ node@shape` for new pairs, `- node@shape` for removed ones
+/// and `node@shape: before -> after` for pairs whose status changed
+impl Display for ResultShapeMapDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let changes: Vec<String> = self
+            .changes
+            .iter()
+            .map(|c| {
+                let node = show_node(&c.node, &self.nodes_prefixmap);
+                let shape = show_label(&c.shape, &self.shapes_prefixmap);
+                match (c.before, c.after) {
+                    (None, Some(after)) => format!("+ {node}{}{shape}", after.compact_marker()),
+                    (Some(before), None) => format!("- {node}{}{shape}", before.compact_marker()),
+                    (Some(before), Some(after)) => format!("{node}@{shape}: {before} -> {after}"),
+                    (None, None) => format!("{node}@{shape}"),
+                }
+            })
+            .collect();
+        write!(f, "{}", changes.join("\n"))
+    }
+}
+
+fn show_node(node: &Object, prefixmap: &PrefixMap) -> String {
+    match node {
+        Object::Iri(iri) => prefixmap.qualify(iri),
+        Object::BlankNode(id) => format!("_:{id}"),
+        Object::Literal(lit) => lit.to_string(),
+    }
+}
+
+fn show_label(label: &ShapeExprLabel, prefixmap: &PrefixMap) -> String {
+    match label {
+        ShapeExprLabel::IriRef { value } => match value.get_iri() {
+            Ok(iri) => prefixmap.qualify(&iri),
+            Err(_) => value.to_string(),
+        },
+        ShapeExprLabel::BNode { value } => value.to_string(),
+        ShapeExprLabel::Start => "START".to_string(),
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ResultShapeMapError {
+    #[error("Error parsing result shape map in JSON: {error}")]
+    JsonError { error: String },
+
+    #[error("Bad node {node} in result shape map: {error}")]
+    BadNode { node: String, error: String },
+
+    #[error("Bad shape label {shape} in result shape map: {error}")]
+    BadShape { shape: String, error: String },
+
+    #[error("Blank node {node} cannot be used as node selector of a query shape map")]
+    BlankNodeSelector { node: String },
+}
+
+/// Representation of an association in the ShapeMap JSON format
+#[derive(Serialize, Deserialize, Clone)]
+struct JsonAssociation {
+    node: JsonNode,
+    shape: String,
+    status: ValidationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(default, rename = "appinfo", skip_serializing_if = "Option::is_none")]
+    app_info: Option<serde_json::Value>,
+}
+
+/// IRIs and blank nodes are represented by strings and literals by ShExJ object values
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum JsonNode {
+    Str(String),
+    Value(ObjectValue),
+}
+
+impl From<ResultAssociation> for JsonAssociation {
+    fn from(assoc: ResultAssociation) -> Self {
+        let node = match assoc.node {
+            Object::Iri(iri) => JsonNode::Str(iri.as_str().to_string()),
+            Object::BlankNode(id) => JsonNode::Str(format!("_:{id}")),
+            Object::Literal(lit) => JsonNode::Value(ObjectValue::literal(lit)),
+        };
+        let shape = assoc.shape.to_string();
+        JsonAssociation {
+            node,
+            shape,
+            status: assoc.status,
+            reason: assoc.reason,
+            app_info: assoc.app_info,
+        }
+    }
+}
+
+impl TryFrom<JsonAssociation> for ResultAssociation {
+    type Error = ResultShapeMapError;
+
+    fn try_from(json: JsonAssociation) -> Result<Self, Self::Error> {
+        let node =
+            match json.node {
+                JsonNode::Str(str) => match str.strip_prefix("_:") {
+                    Some(id) => Object::bnode(id.to_string()),
+                    None => Object::iri(IriS::from_str(&str).map_err(|e| {
+                        ResultShapeMapError::BadNode {
+                            node: str.clone(),
+                            error: e.to_string(),
+                        }
+                    })?),
+                },
+                JsonNode::Value(ObjectValue::Literal(lit)) => Object::literal(lit),
+                JsonNode::Value(ObjectValue::IriRef(iri)) => {
+                    Object::iri(iri.get_iri().map_err(|e| ResultShapeMapError::BadNode {
+                        node: iri.to_string(),
+                        error: e.to_string(),
+                    })?)
+                }
+            };
+        let shape = if json.shape == "START" {
+            ShapeExprLabel::Start
+        } else {
+            ShapeExprLabel::from_str(&json.shape).map_err(|e| ResultShapeMapError::BadShape {
+                shape: json.shape.clone(),
+                error: e.to_string(),
+            })?
+        };
+        Ok(ResultAssociation {
+            node,
+            shape,
+            status: json.status,
+            reason: json.reason,
+            app_info: json.app_info,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use srdf::literal::Literal;
+
+    const JSON: &str = r#"[
+        { "node": "http://example.org/alice", "shape": "http://example.org/S", "status": "conformant",
+          "appinfo": { "reasons": [] } },
+        { "node": "_:b1", "shape": "START", "status": "nonconformant", "reason": "missing :name" },
+        { "node": { "value": "1", "type": "http://www.w3.org/2001/XMLSchema#integer" },
+          "shape": "http://example.org/T", "status": "pending" },
+        { "node": "http://example.org/bob", "shape": "_:s1", "status": "conformant" }
+    ]"#;
+
+    #[test]
+    fn parse_result_shape_map_json() {
+        let result_map = ResultShapeMap::from_json_str(JSON).unwrap();
+        assert_eq!(result_map.len(), 4);
+        let alice = Object::iri(IriS::new_unchecked("http://example.org/alice"));
+        let s = ShapeExprLabel::iri_unchecked("http://example.org/S");
+        assert_eq!(
+            result_map.get_status(&alice, &s),
+            Some(&ValidationStatus::Conformant)
+        );
+        assert_eq!(
+            result_map.get_status(&Object::bnode("b1".to_string()), &ShapeExprLabel::Start),
+            Some(&ValidationStatus::NonConformant)
+        );
+        let third = result_map.iter().nth(2).unwrap();
+        assert!(matches!(
+            third.node,
+            Object::Literal(Literal::NumericLiteral(_))
+        ));
+        assert_eq!(third.status, ValidationStatus::Pending);
+    }
+
+    #[test]
+    fn result_shape_map_json_round_trip() {
+        let result_map = ResultShapeMap::from_json_str(JSON).unwrap();
+        let json = serde_json::to_string(&result_map).unwrap();
+        assert_eq!(ResultShapeMap::from_json_str(&json).unwrap(), result_map);
+    }
+
+    #[test]
+    fn result_shape_map_compact() {
+        let mut pm = PrefixMap::new();
+        pm.insert("", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        let result_map = ResultShapeMap::from_json_str(JSON)
+            .unwrap()
+            .with_nodes_prefixmap(&pm)
+            .with_shapes_prefixmap(&pm);
+        assert_eq!(
+            result_map.to_string(),
+            ":alice@:S,\n_:b1@!START,\n1@?:T,\n:bob@_:s1"
+        );
+    }
+
+    #[test]
+    fn result_shape_map_diff() {
+        let previous = ResultShapeMap::from_json_str(JSON).unwrap();
+        let json = r#"[
+            { "node": "http://example.org/alice", "shape": "http://example.org/S", "status": "nonconformant" },
+            { "node": "_:b1", "shape": "START", "status": "nonconformant" },
+            { "node": { "value": "1", "type": "http://www.w3.org/2001/XMLSchema#integer" },
+              "shape": "http://example.org/T", "status": "pending" },
+            { "node": "http://example.org/carol", "shape": "http://example.org/S", "status": "conformant" }
+        ]"#;
+        let mut pm = PrefixMap::new();
+        pm.insert("", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        let current = ResultShapeMap::from_json_str(json)
+            .unwrap()
+            .with_nodes_prefixmap(&pm)
+            .with_shapes_prefixmap(&pm);
+        let diff = current.diff(&previous);
+        assert_eq!(diff.iter().count(), 3);
+        assert_eq!(
+            diff.to_string(),
+            ":alice@:S: conformant -> nonconformant\n+ :carol@:S\n- :bob@_:s1"
+        );
+        assert!(current.diff(&current).is_empty());
+    }
+
+    #[test]
+    fn status_of_first_association_after_retain() {
+        let s = ShapeExprLabel::iri_unchecked("http://example.org/S");
+        let node = |n: usize| Object::iri(IriS::new_unchecked(&format!("http://example.org/n{n}")));
+        let mut result_map = ResultShapeMap::new();
+        for n in 0..1000 {
+            let status = if n % 2 == 0 {
+                ValidationStatus::Conformant
+            } else {
+                ValidationStatus::NonConformant
+            };
+            result_map.add_association(ResultAssociation::new(node(n), s.clone(), status));
+        }
+        // A repeated pair keeps the status of its first association
+        result_map.add_association(ResultAssociation::new(
+            node(0),
+            s.clone(),
+            ValidationStatus::Pending,
+        ));
+        assert_eq!(
+            result_map.get_status(&node(0), &s),
+            Some(&ValidationStatus::Conformant)
+        );
+        assert_eq!(
+            result_map.get_status(&node(999), &s),
+            Some(&ValidationStatus::NonConformant)
+        );
+        result_map.retain(|a| a.status != ValidationStatus::Conformant);
+        assert_eq!(result_map.len(), 501);
+        assert_eq!(
+            result_map.get_status(&node(0), &s),
+            Some(&ValidationStatus::Pending)
+        );
+        assert_eq!(result_map.get_status(&node(2), &s), None);
+        assert_eq!(
+            result_map.get_status(&node(999), &s),
+            Some(&ValidationStatus::NonConformant)
+        );
+    }
+
+    #[test]
+    fn bad_status() {
+        let json = r#"[{ "node": "http://example.org/a", "shape": "http://example.org/S", "status": "ok" }]"#;
+        assert!(ResultShapeMap::from_json_str(json).is_err())
+    }
+}
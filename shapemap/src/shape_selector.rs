@@ -3,7 +3,7 @@ use shex_ast::ShapeExprLabel;
 
 /// A ShapeSelector following [ShapeMap spec](https://shexspec.github.io/shape-map/#shapemap-structure) can be used to select shape expressions to validate
 ///
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ShapeSelector {
     Label(ShapeExprLabel),
     Start,
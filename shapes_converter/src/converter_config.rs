@@ -4,8 +4,8 @@ use dctap::TapConfig;
 use serde_derive::{Deserialize, Serialize};
 
 use crate::{
-    ConverterError, ShEx2HtmlConfig, ShEx2SparqlConfig, ShEx2UmlConfig, Shacl2ShExConfig,
-    Tap2ShExConfig,
+    ConverterError, ShEx2HtmlConfig, ShEx2ShaclConfig, ShEx2SparqlConfig, ShEx2UmlConfig,
+    Shacl2ShExConfig, Tap2ShExConfig,
 };
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
@@ -15,6 +15,7 @@ pub struct ConverterConfig {
     tap2shex: Option<Tap2ShExConfig>,
     shex2sparql: Option<ShEx2SparqlConfig>,
     shacl2shex: Option<Shacl2ShExConfig>,
+    shex2shacl: Option<ShEx2ShaclConfig>,
     shex2uml: Option<ShEx2UmlConfig>,
 }
 
@@ -71,6 +72,13 @@ impl ConverterConfig {
         }
     }
 
+    pub fn shex2shacl_config(&self) -> ShEx2ShaclConfig {
+        match &self.shex2shacl {
+            Some(c) => c.clone(),
+            None => ShEx2ShaclConfig::default(),
+        }
+    }
+
     pub fn shex2sparql_config(&self) -> ShEx2SparqlConfig {
         match &self.shex2sparql {
             Some(c) => c.clone(),
@@ -6,6 +6,7 @@ pub mod converter_error;
 pub mod landing_html_template;
 pub mod shacl_to_shex;
 pub mod shex_to_html;
+pub mod shex_to_shacl;
 pub mod shex_to_sparql;
 pub mod shex_to_uml;
 pub mod tap_to_shex;
@@ -24,6 +25,9 @@ pub use crate::shacl_to_shex::shacl2shex_error::*;
 pub use crate::shex_to_html::shex2html::*;
 pub use crate::shex_to_html::shex2html_config::*;
 pub use crate::shex_to_html::shex2html_error::*;
+pub use crate::shex_to_shacl::shex2shacl::*;
+pub use crate::shex_to_shacl::shex2shacl_config::*;
+pub use crate::shex_to_shacl::shex2shacl_error::*;
 pub use crate::shex_to_sparql::shex2sparql::*;
 pub use crate::shex_to_sparql::shex2sparql_config::*;
 pub use crate::shex_to_sparql::shex2sparql_error::*;
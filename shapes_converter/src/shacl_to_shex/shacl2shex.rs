@@ -1,21 +1,27 @@
 use super::{Shacl2ShExConfig, Shacl2ShExError};
 use iri_s::IriS;
 use prefixmap::IriRef;
+use rust_decimal::Decimal;
 use shacl_ast::{
-    component::Component, node_shape::NodeShape, property_shape::PropertyShape,
-    shape::Shape as ShaclShape, target::Target, Schema as ShaclSchema,
+    component::Component, node_kind::NodeKind as ShaclNodeKind, node_shape::NodeShape,
+    property_shape::PropertyShape, shape::Shape as ShaclShape, target::Target, value::Value,
+    Schema as ShaclSchema,
 };
 use shex_ast::{
-    BNode, NodeConstraint, Schema as ShExSchema, Shape as ShExShape, ShapeExpr, ShapeExprLabel,
-    TripleExpr, TripleExprWrapper, ValueSetValue,
+    BNode, NodeConstraint, NodeKind, ObjectValue, Schema as ShExSchema, Shape as ShExShape,
+    ShapeExpr, ShapeExprLabel, TripleExpr, ValueSetValue, XsFacet,
+};
+use srdf::{
+    literal::Literal, numeric_literal::NumericLiteral, Object, RDFNode, SHACLPath, XSD_DECIMAL,
+    XSD_DOUBLE, XSD_INTEGER,
 };
-use srdf::{Object, RDFNode, SHACLPath};
 use tracing::debug;
 
 #[allow(dead_code)] // TODO: only for config...
 pub struct Shacl2ShEx {
     config: Shacl2ShExConfig,
     current_shex: ShExSchema,
+    warnings: Vec<String>,
 }
 
 impl Shacl2ShEx {
@@ -23,6 +29,7 @@ impl Shacl2ShEx {
         Shacl2ShEx {
             config: config.clone(),
             current_shex: ShExSchema::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -30,9 +37,16 @@ impl Shacl2ShEx {
         &self.current_shex
     }
 
+    /// Constructs of the last converted schema that have no equivalent in
+    /// ShEx and have been ignored or approximated
+    pub fn warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
     pub fn convert(&mut self, schema: &ShaclSchema) -> Result<(), Shacl2ShExError> {
         let prefixmap = schema.prefix_map().without_rich_qualifying();
         self.current_shex = ShExSchema::new().with_prefixmap(Some(prefixmap));
+        self.warnings = Vec::new();
         for (_, shape) in schema.iter() {
             match &shape {
                 shacl_ast::shape::Shape::NodeShape(ns) => {
@@ -48,7 +62,7 @@ impl Shacl2ShEx {
     }
 
     pub fn convert_shape(
-        &self,
+        &mut self,
         shape: &NodeShape,
         schema: &ShaclSchema,
     ) -> Result<(ShapeExprLabel, ShapeExpr, bool), Shacl2ShExError> {
@@ -68,50 +82,109 @@ impl Shacl2ShEx {
         }
     }
 
+    /// A node shape is converted to a ShEx shape with a triple constraint for
+    /// each property shape. The components that constrain the focus node
+    /// itself and the qualified value shapes of its properties are added as a
+    /// conjunction with that shape
     pub fn node_shape2shape_expr(
-        &self,
+        &mut self,
         shape: &NodeShape,
         schema: &ShaclSchema,
     ) -> Result<ShapeExpr, Shacl2ShExError> {
+        let (mut exprs, qualified) =
+            self.property_shapes2triple_exprs(shape.property_shapes(), schema)?;
+        exprs.extend(self.convert_target_decls(shape.targets(), schema)?);
+
+        let mut is_closed = None;
+        let mut components = Vec::new();
+        for component in shape.components() {
+            match component {
+                Component::Closed {
+                    is_closed: true,
+                    ignored_properties,
+                } => {
+                    is_closed = Some(true);
+                    // ignored properties can have any value in a closed shape
+                    exprs.extend(ignored_properties.iter().map(|property| {
+                        TripleExpr::triple_constraint(
+                            None,
+                            None,
+                            property.clone(),
+                            None,
+                            Some(0),
+                            Some(-1),
+                        )
+                    }));
+                }
+                Component::Closed { .. } => {}
+                Component::MinCount(_) | Component::MaxCount(_) => self.warning(format!(
+                    "{component} in node shape {} is ignored, it only applies to property shapes",
+                    shape.id()
+                )),
+                _ => components.push(component.clone()),
+            }
+        }
+        if is_closed.is_none() && *shape.is_closed() {
+            is_closed = Some(true)
+        }
+
+        let extra = None; // TODO: Check if we could find a way to obtain extras in SHACL ?
+        let te = match exprs.len() {
+            0 => None,
+            1 => exprs.pop(),
+            _ => Some(TripleExpr::each_of(exprs)),
+        };
+        let shex_shape = ShapeExpr::shape(ShExShape::new(is_closed, extra, te));
+        let mut ses: Vec<_> = self
+            .components2shape_expr(&components, schema)?
+            .into_iter()
+            .collect();
+        if ses.is_empty() || shex_shape != ShapeExpr::empty_shape() {
+            ses.push(shex_shape)
+        }
+        ses.extend(qualified);
+        Ok(match ses.len() {
+            1 => ses.pop().unwrap(),
+            _ => ShapeExpr::and(ses),
+        })
+    }
+
+    /// Converts the property shapes of a shape to their triple expressions and
+    /// the shape expressions of their qualified value shapes
+    fn property_shapes2triple_exprs(
+        &mut self,
+        property_shapes: &Vec<RDFNode>,
+        schema: &ShaclSchema,
+    ) -> Result<(Vec<TripleExpr>, Vec<ShapeExpr>), Shacl2ShExError> {
         let mut exprs = Vec::new();
-        for node in shape.property_shapes() {
+        let mut qualified = Vec::new();
+        for node in property_shapes {
             match schema.get_shape(node) {
-                None => todo!(),
-                Some(shape) => match shape {
-                    ShaclShape::PropertyShape(ps) => {
-                        let tc = self.property_shape2triple_constraint(ps)?;
-                        exprs.push(tc);
-                        Ok(())
-                    }
-                    ShaclShape::NodeShape(ns) => Err(Shacl2ShExError::NotExpectedNodeShape {
+                None => {
+                    return Err(Shacl2ShExError::ShapeNotFound {
+                        node: node.to_string(),
+                    })
+                }
+                Some(ShaclShape::PropertyShape(ps)) => {
+                    let (te, ses) = self.property_shape2triple_exprs(ps, schema)?;
+                    exprs.extend(te);
+                    qualified.extend(ses)
+                }
+                Some(ShaclShape::NodeShape(ns)) => {
+                    return Err(Shacl2ShExError::NotExpectedNodeShape {
                         node_shape: ns.clone(),
-                    }),
-                },
-            }?
+                    })
+                }
+            }
         }
-        let is_closed = None; // TODO: Check real value
-        let extra = None; // TODO: Check if we could find a way to obtain extras in SHACL ?
-        let te = if exprs.is_empty() {
-            None
-        } else {
-            Some(TripleExpr::each_of(exprs))
-        };
-        let target_class_expr = self.convert_target_decls(shape.targets(), schema)?;
-        let ter = match (te, target_class_expr) {
-            (None, None) => None,
-            (None, Some(t)) => Some(t),
-            (Some(t), None) => Some(t),
-            (Some(t1), Some(t2)) => Some(self.merge_triple_exprs(&t1, &t2)),
-        };
-        let shape = ShExShape::new(is_closed, extra, ter);
-        Ok(ShapeExpr::shape(shape))
+        Ok((exprs, qualified))
     }
 
     pub fn convert_target_decls(
         &self,
         targets: &Vec<Target>,
         schema: &ShaclSchema,
-    ) -> Result<Option<TripleExpr>, Shacl2ShExError> {
+    ) -> Result<Vec<TripleExpr>, Shacl2ShExError> {
         let mut tes = Vec::new();
         for target in targets {
             let te = self.target2triple_constraint(target, schema)?;
@@ -120,11 +193,7 @@ impl Shacl2ShEx {
                 Some(te) => tes.push(te),
             }
         }
-        if tes.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(TripleExpr::each_of(tes)))
-        }
+        Ok(tes)
     }
 
     pub fn target2triple_constraint(
@@ -164,245 +233,575 @@ impl Shacl2ShEx {
         }
     }
 
-    pub fn merge_triple_exprs(&self, te1: &TripleExpr, te2: &TripleExpr) -> TripleExpr {
-        match te1 {
-            TripleExpr::EachOf {
-                id,
-                expressions,
-                min,
-                max,
-                sem_acts,
-                annotations,
-            } => match te2 {
-                TripleExpr::EachOf {
-                    id: _,
-                    expressions: exprs,
-                    min: _,
-                    max: _,
-                    sem_acts: _,
-                    annotations: _,
-                } => TripleExpr::EachOf {
-                    id: id.clone(),
-                    expressions: Self::merge_expressions(expressions, exprs),
-                    min: *min,
-                    max: *max,
-                    sem_acts: sem_acts.clone(),
-                    annotations: annotations.clone(),
-                },
-                tc @ TripleExpr::TripleConstraint {
-                    id,
-                    negated: _,
-                    inverse: _,
-                    predicate: _,
-                    value_expr: _,
-                    min,
-                    max,
-                    sem_acts,
-                    annotations,
-                } => TripleExpr::EachOf {
-                    id: id.clone(),
-                    expressions: Self::merge_expressions(
-                        expressions,
-                        &vec![TripleExprWrapper { te: tc.clone() }],
-                    ),
-                    min: *min,
-                    max: *max,
-                    sem_acts: sem_acts.clone(),
-                    annotations: annotations.clone(),
-                },
-                _ => todo!(),
-            },
-            tc @ TripleExpr::TripleConstraint {
-                id,
-                negated: _,
-                inverse: _,
-                predicate: _,
-                value_expr: _,
-                min,
-                max,
-                sem_acts,
-                annotations,
-            } => match te2 {
-                TripleExpr::EachOf {
-                    id: _,
-                    expressions: exprs,
-                    min: _,
-                    max: _,
-                    sem_acts: _,
-                    annotations: _,
-                } => TripleExpr::EachOf {
-                    id: id.clone(),
-                    expressions: Self::merge_expressions(
-                        &vec![TripleExprWrapper { te: tc.clone() }],
-                        exprs,
-                    ),
-                    min: *min,
-                    max: *max,
-                    sem_acts: sem_acts.clone(),
-                    annotations: annotations.clone(),
-                },
-                tc2 @ TripleExpr::TripleConstraint {
-                    id,
-                    negated: _,
-                    inverse: _,
-                    predicate: _,
-                    value_expr: _,
-                    min,
-                    max,
-                    sem_acts,
-                    annotations,
-                } => TripleExpr::EachOf {
-                    id: id.clone(),
-                    expressions: vec![
-                        TripleExprWrapper { te: tc.clone() },
-                        TripleExprWrapper { te: tc2.clone() },
-                    ],
-                    min: *min,
-                    max: *max,
-                    sem_acts: sem_acts.clone(),
-                    annotations: annotations.clone(),
-                },
-                _ => todo!(),
-            },
-            _ => todo!(),
-        }
-    }
+    /// A property shape is converted to a triple constraint whose cardinality
+    /// is taken from `sh:minCount` and `sh:maxCount` (`*` by default) and
+    /// whose value expression is the conjunction of the other components.
+    ///
+    /// A triple constraint with the same predicate in the same shape would
+    /// take some of the values, so each `sh:qualifiedValueShape` is converted
+    /// to a separate shape `EXTRA p { p @S {min,max} }`, which counts only
+    /// the values that conform to `S`, to be added as a conjunction
+    pub fn property_shape2triple_exprs(
+        &mut self,
+        shape: &PropertyShape,
+        schema: &ShaclSchema,
+    ) -> Result<(Vec<TripleExpr>, Vec<ShapeExpr>), Shacl2ShExError> {
+        let (predicate, inverse) = match self.shacl_path2predicate(shape.path())? {
+            Some(predicate) => predicate,
+            None => {
+                self.warning(format!(
+                    "Property shape {} is ignored, path {} can't be represented in ShEx",
+                    shape.id(),
+                    shape.path()
+                ));
+                return Ok((Vec::new(), Vec::new()));
+            }
+        };
+        let inverse = if inverse { Some(true) } else { None };
 
-    pub fn merge_expressions(
-        e1: &Vec<TripleExprWrapper>,
-        e2: &Vec<TripleExprWrapper>,
-    ) -> Vec<TripleExprWrapper> {
-        let mut es = Vec::new();
-        for e in e1 {
-            es.push(e.clone())
+        let mut min = Some(0);
+        let mut max = Some(-1);
+        let mut qualified = Vec::new();
+        let mut components = Vec::new();
+        for component in shape.components() {
+            match component {
+                Component::MinCount(n) => min = Some(*n as i32),
+                Component::MaxCount(n) => max = Some(*n as i32),
+                Component::QualifiedValueShape {
+                    shape: qualified_shape,
+                    qualified_min_count,
+                    qualified_max_count,
+                    qualified_value_shapes_disjoint,
+                } => {
+                    if inverse.is_some() {
+                        // EXTRA only applies to outgoing arcs
+                        self.warning(format!(
+                            "{component} in property shape {} is ignored, it can't be represented for an inverse path",
+                            shape.id()
+                        ));
+                        continue;
+                    }
+                    if let Some(true) = qualified_value_shapes_disjoint {
+                        self.warning(format!(
+                            "sh:qualifiedValueShapesDisjoint in property shape {} is ignored",
+                            shape.id()
+                        ))
+                    }
+                    let se = self.shape_ref2shape_expr(qualified_shape, schema)?;
+                    let te = TripleExpr::triple_constraint(
+                        None,
+                        None,
+                        predicate.clone(),
+                        Some(se),
+                        qualified_min_count.map(|n| n as i32).or(Some(0)),
+                        qualified_max_count.map(|n| n as i32).or(Some(-1)),
+                    );
+                    qualified.push(ShapeExpr::shape(ShExShape::new(
+                        None,
+                        Some(vec![predicate.clone()]),
+                        Some(te),
+                    )))
+                }
+                Component::HasValue { .. } => {
+                    self.warning(format!(
+                        "sh:hasValue in property shape {} is converted to a value set, so the other values are not allowed",
+                        shape.id()
+                    ));
+                    components.push(component.clone())
+                }
+                _ => components.push(component.clone()),
+            }
         }
-        for e in e2 {
-            es.push(e.clone())
+
+        let mut se = self.components2shape_expr(&components, schema)?;
+        let (nested, nested_qualified) =
+            self.property_shapes2triple_exprs(shape.property_shapes(), schema)?;
+        if !nested.is_empty() || !nested_qualified.is_empty() {
+            let te = match nested.len() {
+                0 => None,
+                1 => nested.into_iter().next(),
+                _ => Some(TripleExpr::each_of(nested)),
+            };
+            let mut ses: Vec<_> = se.into_iter().collect();
+            if te.is_some() {
+                ses.push(ShapeExpr::shape(ShExShape::new(None, None, te)))
+            }
+            ses.extend(nested_qualified);
+            se = Some(match ses.len() {
+                1 => ses.pop().unwrap(),
+                _ => ShapeExpr::and(ses),
+            })
         }
-        es
-    }
 
-    pub fn property_shape2triple_constraint(
-        &self,
-        shape: &PropertyShape,
-    ) -> Result<TripleExpr, Shacl2ShExError> {
-        let predicate = self.shacl_path2predicate(shape.path())?;
-        let negated = None;
-        let inverse = None;
-        let se = self.components2shape_expr(shape.components())?;
-        let min = None;
-        let max = None;
-        Ok(TripleExpr::triple_constraint(
-            negated, inverse, predicate, se, min, max,
-        ))
+        let te = TripleExpr::triple_constraint(None, inverse, predicate, se, min, max);
+        Ok((vec![te], qualified))
     }
 
+    /// Converts the components that constrain a value node to a shape
+    /// expression. Node constraints are merged when possible, and the
+    /// expressions are combined as a conjunction
     pub fn components2shape_expr(
-        &self,
+        &mut self,
         components: &Vec<Component>,
+        schema: &ShaclSchema,
     ) -> Result<Option<ShapeExpr>, Shacl2ShExError> {
-        let mut ses = Vec::new();
+        let mut ses: Vec<ShapeExpr> = Vec::new();
         for c in components {
-            let se = self.component2shape_expr(c)?;
-            ses.push(se);
-        }
-        if ses.is_empty() {
-            Ok(None)
-        } else {
-            match ses.len() {
-                1 => {
-                    let se = &ses[0];
-                    Ok(Some(se.clone()))
+            let se = match self.component2shape_expr(c, schema)? {
+                Some(se) => se,
+                None => continue,
+            };
+            let merged = match (&se, ses.last()) {
+                (ShapeExpr::NodeConstraint(nc), Some(ShapeExpr::NodeConstraint(last))) => {
+                    merge_node_constraints(last, nc)
                 }
-                _ => {
-                    // Err(Shacl2ShExError::not_implemented("Conversion of shapes with multiple components is not implemented yet: {components:?}"))}
-                    debug!("More than one component: {components:?}, taking only the first one");
-                    let se = &ses[0];
-                    Ok(Some(se.clone()))
+                _ => None,
+            };
+            match merged {
+                Some(nc) => {
+                    ses.pop();
+                    ses.push(ShapeExpr::node_constraint(nc))
                 }
+                None => ses.push(se),
             }
         }
+        match ses.len() {
+            0 => Ok(None),
+            1 => Ok(ses.pop()),
+            _ => Ok(Some(ShapeExpr::and(ses))),
+        }
     }
 
-    pub fn create_class_constraint(&self, cls: &RDFNode) -> Result<ShapeExpr, Shacl2ShExError> {
+    pub fn create_class_constraint(
+        &self,
+        cls: &RDFNode,
+    ) -> Result<Option<ShapeExpr>, Shacl2ShExError> {
         let rdf_type = IriRef::iri(IriS::rdf_type());
         let value = match cls {
             Object::Iri(iri) => ValueSetValue::iri(IriRef::iri(iri.clone())),
-            Object::BlankNode(_) => todo!(),
-            Object::Literal(_) => todo!(),
+            Object::BlankNode(_) | Object::Literal(_) => return Ok(None),
         };
         let cls = NodeConstraint::new().with_values(vec![value]);
         let te = TripleExpr::triple_constraint(
             None,
             None,
-            rdf_type,
+            rdf_type.clone(),
             Some(ShapeExpr::node_constraint(cls)),
-            None,
-            None,
+            Some(1),
+            Some(-1),
         );
-        let se = ShapeExpr::shape(ShExShape::new(None, None, Some(te)));
-        Ok(se)
+        // EXTRA allows other types, as the node can be an instance of several classes
+        let se = ShapeExpr::shape(ShExShape::new(None, Some(vec![rdf_type]), Some(te)));
+        Ok(Some(se))
     }
 
+    /// Converts a component to a shape expression that a value node must
+    /// conform to. Components that can't be represented in ShEx are added
+    /// to the warnings and return `None`
     pub fn component2shape_expr(
-        &self,
+        &mut self,
         component: &Component,
-    ) -> Result<ShapeExpr, Shacl2ShExError> {
-        match component {
+        schema: &ShaclSchema,
+    ) -> Result<Option<ShapeExpr>, Shacl2ShExError> {
+        let nc = match component {
             Component::Class(cls) => {
                 debug!("TODO: Converting Class components for {cls:?} doesn't match rdfs:subClassOf semantics of SHACL yet");
                 let se = self.create_class_constraint(cls)?;
-                Ok(se)
+                if se.is_none() {
+                    self.warning(format!("{component} is ignored, the class must be an IRI"))
+                }
+                return Ok(se);
+            }
+            Component::Datatype(dt) => NodeConstraint::new().with_datatype(dt.clone()),
+            Component::NodeKind(node_kind) => {
+                let node_kind = match node_kind {
+                    ShaclNodeKind::Iri => NodeKind::Iri,
+                    ShaclNodeKind::Literal => NodeKind::Literal,
+                    ShaclNodeKind::BlankNode => NodeKind::BNode,
+                    ShaclNodeKind::BlankNodeOrIri => NodeKind::NonLiteral,
+                    ShaclNodeKind::BlankNodeOrLiteral => {
+                        return Ok(Some(node_kinds(NodeKind::BNode, NodeKind::Literal)))
+                    }
+                    ShaclNodeKind::IRIOrLiteral => {
+                        return Ok(Some(node_kinds(NodeKind::Iri, NodeKind::Literal)))
+                    }
+                };
+                NodeConstraint::new().with_node_kind(node_kind)
+            }
+            Component::MinExclusive(literal)
+            | Component::MaxExclusive(literal)
+            | Component::MinInclusive(literal)
+            | Component::MaxInclusive(literal) => {
+                let n = match numeric_value(literal) {
+                    Some(n) => n,
+                    None => {
+                        self.warning(format!(
+                            "{component} is ignored, ShEx only supports numeric ranges"
+                        ));
+                        return Ok(None);
+                    }
+                };
+                let facet = match component {
+                    Component::MinExclusive(_) => XsFacet::min_exclusive(n),
+                    Component::MaxExclusive(_) => XsFacet::max_exclusive(n),
+                    Component::MinInclusive(_) => XsFacet::min_inclusive(n),
+                    _ => XsFacet::max_inclusive(n),
+                };
+                NodeConstraint::new().add_facet(facet)
+            }
+            Component::MinLength(n) => NodeConstraint::new().with_minlength(*n as usize),
+            Component::MaxLength(n) => NodeConstraint::new().with_maxlength(*n as usize),
+            Component::Pattern { pattern, flags } => match flags {
+                Some(flags) => NodeConstraint::new().with_pattern_flags(pattern, flags),
+                None => NodeConstraint::new().with_pattern(pattern),
+            },
+            Component::LanguageIn { langs } => NodeConstraint::new().with_values(
+                langs
+                    .iter()
+                    .map(|lang| ValueSetValue::language_stem(lang.clone()))
+                    .collect(),
+            ),
+            Component::HasValue { value } => {
+                NodeConstraint::new().with_values(vec![value2value_set_value(value)])
             }
-            Component::Datatype(dt) => Ok(ShapeExpr::node_constraint(
-                NodeConstraint::new().with_datatype(dt.clone()),
-            )),
-            Component::NodeKind(_) => todo!(),
-            Component::MinCount(_) => todo!(),
-            Component::MaxCount(_) => todo!(),
-            Component::MinExclusive(_) => todo!(),
-            Component::MaxExclusive(_) => todo!(),
-            Component::MinInclusive(_) => todo!(),
-            Component::MaxInclusive(_) => todo!(),
-            Component::MinLength(_) => todo!(),
-            Component::MaxLength(_) => todo!(),
-            Component::Pattern {
-                pattern: _,
-                flags: _,
-            } => todo!(),
-            Component::UniqueLang(_) => todo!(),
-            Component::LanguageIn { langs: _ } => todo!(),
-            Component::Equals(_) => todo!(),
-            Component::Disjoint(_) => todo!(),
-            Component::LessThan(_) => todo!(),
-            Component::LessThanOrEquals(_) => todo!(),
-            Component::Or { shapes: _ } => {
-                debug!("Not implemented OR Shapes");
-                Ok(ShapeExpr::empty_shape())
+            Component::In { values } => NodeConstraint::new()
+                .with_values(values.iter().map(value2value_set_value).collect()),
+            Component::Or { shapes } => {
+                let ses = self.shape_refs2shape_exprs(shapes, schema)?;
+                return Ok(Some(ShapeExpr::or(ses)));
+            }
+            Component::And { shapes } => {
+                let ses = self.shape_refs2shape_exprs(shapes, schema)?;
+                return Ok(Some(ShapeExpr::and(ses)));
+            }
+            Component::Not { shape } => {
+                let se = self.shape_ref2shape_expr(shape, schema)?;
+                return Ok(Some(ShapeExpr::shape_not(se)));
+            }
+            Component::Xone { shapes } => {
+                // exactly one of the shapes: each of them and none of the others
+                let ses = self.shape_refs2shape_exprs(shapes, schema)?;
+                let alternatives = (0..ses.len())
+                    .map(|i| {
+                        let mut conjuncts = vec![ses[i].clone()];
+                        conjuncts.extend(
+                            ses.iter()
+                                .enumerate()
+                                .filter(|(j, _)| *j != i)
+                                .map(|(_, se)| ShapeExpr::shape_not(se.clone())),
+                        );
+                        ShapeExpr::and(conjuncts)
+                    })
+                    .collect();
+                return Ok(Some(ShapeExpr::or(alternatives)));
+            }
+            Component::Node { shape } => {
+                let se = self.shape_ref2shape_expr(shape, schema)?;
+                return Ok(Some(se));
+            }
+            Component::UniqueLang(_)
+            | Component::Equals(_)
+            | Component::Disjoint(_)
+            | Component::LessThan(_)
+            | Component::LessThanOrEquals(_) => {
+                self.warning(format!(
+                    "{component} can't be represented in ShEx, ignoring it"
+                ));
+                return Ok(None);
+            }
+            Component::Closed { .. }
+            | Component::MinCount(_)
+            | Component::MaxCount(_)
+            | Component::QualifiedValueShape { .. } => {
+                self.warning(format!(
+                    "{component} is ignored, it is only supported directly in node or property shapes"
+                ));
+                return Ok(None);
+            }
+            Component::Sparql(_) | Component::SparqlComponent { .. } => {
+                self.warning(format!(
+                    "SPARQL-based constraints can't be represented in ShEx, ignoring {component}"
+                ));
+                return Ok(None);
+            }
+        };
+        Ok(Some(ShapeExpr::node_constraint(nc)))
+    }
+
+    /// A reference to a node shape is converted to a shape reference, while
+    /// property shapes, which have no label in ShEx, are converted inline
+    fn shape_ref2shape_expr(
+        &mut self,
+        node: &RDFNode,
+        schema: &ShaclSchema,
+    ) -> Result<ShapeExpr, Shacl2ShExError> {
+        match schema.get_shape(node) {
+            None => Err(Shacl2ShExError::ShapeNotFound {
+                node: node.to_string(),
+            }),
+            Some(ShaclShape::NodeShape(ns)) => {
+                Ok(ShapeExpr::shape_ref(self.rdfnode2label(ns.id())?))
+            }
+            Some(ShaclShape::PropertyShape(ps)) => {
+                let (tes, qualified) = self.property_shape2triple_exprs(ps, schema)?;
+                let mut ses = vec![ShapeExpr::shape(ShExShape::new(
+                    None,
+                    None,
+                    tes.into_iter().next(),
+                ))];
+                ses.extend(qualified);
+                Ok(match ses.len() {
+                    1 => ses.pop().unwrap(),
+                    _ => ShapeExpr::and(ses),
+                })
             }
-            Component::And { shapes: _ } => todo!(),
-            Component::Not { shape: _ } => todo!(),
-            Component::Xone { shapes: _ } => todo!(),
-            Component::Closed {
-                is_closed: _,
-                ignored_properties: _,
-            } => todo!(),
-            Component::Node { shape: _ } => todo!(),
-            Component::HasValue { value: _ } => todo!(),
-            Component::In { values: _ } => todo!(),
-            Component::QualifiedValueShape {
-                shape: _,
-                qualified_min_count: _,
-                qualified_max_count: _,
-                qualified_value_shapes_disjoint: _,
-            } => todo!(),
         }
     }
 
-    pub fn shacl_path2predicate(&self, path: &SHACLPath) -> Result<IriRef, Shacl2ShExError> {
+    fn shape_refs2shape_exprs(
+        &mut self,
+        nodes: &[RDFNode],
+        schema: &ShaclSchema,
+    ) -> Result<Vec<ShapeExpr>, Shacl2ShExError> {
+        nodes
+            .iter()
+            .map(|node| self.shape_ref2shape_expr(node, schema))
+            .collect()
+    }
+
+    /// Obtains the predicate of a path and whether it is inverse. Only
+    /// predicates and inverse predicates can be represented in ShEx
+    pub fn shacl_path2predicate(
+        &self,
+        path: &SHACLPath,
+    ) -> Result<Option<(IriRef, bool)>, Shacl2ShExError> {
         match path {
-            SHACLPath::Predicate { pred } => Ok(IriRef::iri(pred.clone())),
-            _ => todo!(),
+            SHACLPath::Predicate { pred } => Ok(Some((IriRef::iri(pred.clone()), false))),
+            SHACLPath::Inverse { path } => match path.as_ref() {
+                SHACLPath::Predicate { pred } => Ok(Some((IriRef::iri(pred.clone()), true))),
+                _ => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn warning(&mut self, msg: String) {
+        debug!("Shacl2ShEx warning: {msg}");
+        self.warnings.push(msg)
+    }
+}
+
+fn node_kinds(nk1: NodeKind, nk2: NodeKind) -> ShapeExpr {
+    ShapeExpr::or(vec![
+        ShapeExpr::node_constraint(NodeConstraint::new().with_node_kind(nk1)),
+        ShapeExpr::node_constraint(NodeConstraint::new().with_node_kind(nk2)),
+    ])
+}
+
+fn value2value_set_value(value: &Value) -> ValueSetValue {
+    match value {
+        Value::Iri(iri) => ValueSetValue::iri(iri.clone()),
+        Value::Literal(Literal::StringLiteral { lexical_form, lang }) => {
+            ValueSetValue::string_literal(lexical_form, lang.clone())
+        }
+        Value::Literal(literal) => {
+            ValueSetValue::object_value(ObjectValue::literal(literal.clone()))
+        }
+    }
+}
+
+/// Numeric value of a literal, which the RDF parsers may keep as a typed
+/// literal with its lexical form, like `"5"^^xsd:integer`
+fn numeric_value(literal: &Literal) -> Option<NumericLiteral> {
+    match literal {
+        Literal::DatatypeLiteral {
+            lexical_form,
+            datatype,
+        } => {
+            let datatype = datatype.get_iri().ok()?;
+            if datatype == *XSD_INTEGER {
+                lexical_form.parse().ok().map(NumericLiteral::integer)
+            } else if datatype == *XSD_DECIMAL {
+                Decimal::from_str_exact(lexical_form)
+                    .ok()
+                    .map(NumericLiteral::decimal)
+            } else if datatype == *XSD_DOUBLE {
+                lexical_form.parse().ok().map(NumericLiteral::double)
+            } else {
+                None
+            }
+        }
+        _ => literal.numeric_value(),
+    }
+}
+
+/// Merges two node constraints if at most one of them has a node kind, a
+/// datatype or a value set, which can be combined with any facets
+fn merge_node_constraints(nc1: &NodeConstraint, nc2: &NodeConstraint) -> Option<NodeConstraint> {
+    let has_base = |nc: &NodeConstraint| {
+        nc.node_kind().is_some() || nc.datatype().is_some() || nc.values().is_some()
+    };
+    let (base, other) = match (has_base(nc1), has_base(nc2)) {
+        (true, true) => return None,
+        (false, _) => (nc2, nc1),
+        (true, false) => (nc1, nc2),
+    };
+    let facets = other.xs_facet().unwrap_or_default();
+    Some(
+        facets
+            .into_iter()
+            .fold(base.clone(), |nc, facet| nc.add_facet(facet)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prefixmap::Deref;
+    use shacl_ast::ShaclParser;
+    use shex_ast::compiled::compiled_schema::CompiledSchema;
+    use shex_ast::compiled::shape_label::ShapeLabel;
+    use shex_ast::Node;
+    use shex_compact::{ShExFormatter, ShExParser};
+    use shex_validation::{ResultValue, Validator, ValidatorConfig};
+    use srdf::{RDFFormat, ReaderMode, SRDFGraph};
+
+    const SHACL_PREFIXES: &str = "\
+prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+";
+
+    const SHEX_PREFIXES: &str = "\
+prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+";
+
+    /// Converts a SHACL schema and checks that it has the shapes of a ShEx
+    /// schema, in any order, and the number of warnings
+    fn assert_converts(shacl_str: &str, shex_str: &str, warnings: usize) {
+        let shacl_str = format!("{SHACL_PREFIXES}{shacl_str}");
+        let rdf = SRDFGraph::from_str(&shacl_str, &RDFFormat::Turtle, None, &ReaderMode::default())
+            .unwrap();
+        let shacl = ShaclParser::new(rdf).parse().unwrap();
+        let expected = ShExParser::parse(&format!("{SHEX_PREFIXES}{shex_str}"), None).unwrap();
+        let expected_shapes: Vec<_> = expected
+            .shapes()
+            .unwrap()
+            .iter()
+            .map(|decl| decl.deref(&None, &expected.prefixmap()).unwrap())
+            .collect();
+        let mut converter = Shacl2ShEx::new(&Shacl2ShExConfig::default());
+        converter.convert(&shacl).unwrap();
+        let shapes = converter.current_shex().shapes().unwrap();
+        let shown = ShExFormatter::default()
+            .without_colors()
+            .format_schema(converter.current_shex());
+        assert_eq!(shapes.len(), expected_shapes.len(), "{shown}");
+        for shape in expected_shapes.iter() {
+            assert!(shapes.contains(shape), "{} not in {shown}", shape.id);
+        }
+        assert_eq!(
+            converter.warnings().len(),
+            warnings,
+            "{:?}",
+            converter.warnings()
+        );
+    }
+
+    #[test]
+    fn test_core_components() {
+        assert_converts(
+            ":Person a sh:NodeShape ;
+  sh:nodeKind sh:IRI ;
+  sh:property [
+    sh:path :name ;
+    sh:datatype xsd:string ;
+    sh:minLength 1 ;
+    sh:maxCount 1 ;
+    sh:lessThan :surname
+  ] .",
+            ":Person IRI AND { :name xsd:string MINLENGTH 1 ? }",
+            1,
+        );
+    }
+
+    #[test]
+    fn test_logical_components() {
+        assert_converts(
+            ":A a sh:NodeShape ; sh:nodeKind sh:IRI .
+:B a sh:NodeShape ; sh:nodeKind sh:BlankNode .
+:Or a sh:NodeShape ; sh:property [ sh:path :p ; sh:or ( :A :B ) ] .
+:Xone a sh:NodeShape ; sh:property [ sh:path :p ; sh:xone ( :A :B ) ] .",
+            ":A IRI
+:B BNODE
+:Or { :p @:A OR @:B * }
+:Xone { :p (@:A AND NOT @:B) OR (@:B AND NOT @:A) * }",
+            0,
+        );
+    }
+
+    #[test]
+    fn test_closed_and_in() {
+        assert_converts(
+            ":S a sh:NodeShape ;
+  sh:closed true ;
+  sh:ignoredProperties ( :q ) ;
+  sh:property [ sh:path :p ; sh:in ( :a 1 \"x\" ) ; sh:minCount 1 ] .",
+            ":S CLOSED { :p [ :a \"1\"^^xsd:integer \"x\" ] + ; :q . * }",
+            0,
+        );
+    }
+
+    #[test]
+    fn test_inverse_path() {
+        assert_converts(
+            ":S a sh:NodeShape ;
+  sh:property [ sh:path [ sh:inversePath :p ] ; sh:nodeKind sh:IRI ; sh:maxCount 1 ] ;
+  sh:property [ sh:path ( :p :q ) ; sh:minCount 1 ] .",
+            ":S { ^:p IRI ? }",
+            1,
+        );
+    }
+
+    #[test]
+    fn test_qualified_value_shape() {
+        assert_converts(
+            ":A a sh:NodeShape ; sh:nodeKind sh:IRI .
+:S a sh:NodeShape ;
+  sh:property [ sh:path :p ; sh:maxCount 3 ;
+    sh:qualifiedValueShape :A ; sh:qualifiedMinCount 1 ; sh:qualifiedMaxCount 2 ] .",
+            ":A IRI
+:S { :p . {0,3} } AND EXTRA :p { :p @:A {1,2} }",
+            0,
+        );
+    }
+
+    #[test]
+    fn test_class_allows_other_types() {
+        let shacl_str = format!("{SHACL_PREFIXES}:S a sh:NodeShape ; sh:class :Person .");
+        let rdf = SRDFGraph::from_str(&shacl_str, &RDFFormat::Turtle, None, &ReaderMode::default())
+            .unwrap();
+        let shacl = ShaclParser::new(rdf).parse().unwrap();
+        let mut converter = Shacl2ShEx::new(&Shacl2ShExConfig::default());
+        converter.convert(&shacl).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(converter.current_shex()).unwrap();
+        let data = SRDFGraph::from_str(
+            "prefix : <http://example.org/>
+:alice a :Person, :Student .
+:bob a :Student .",
+            &RDFFormat::Turtle,
+            None,
+            &ReaderMode::default(),
+        )
+        .unwrap();
+        let shape = ShapeLabel::iri(IriS::new_unchecked("http://example.org/S"));
+        let mut validator = Validator::new(schema, &ValidatorConfig::default());
+        for (node, expected) in [("alice", ResultValue::Ok), ("bob", ResultValue::Failed)] {
+            let node = Node::iri(IriS::new_unchecked(&format!("http://example.org/{node}")));
+            validator.validate_node_shape(&node, &shape, &data).unwrap();
+            assert_eq!(validator.get_result(&node, &shape).unwrap(), expected);
         }
     }
 }
@@ -10,6 +10,9 @@ pub enum Shacl2ShExError {
     #[error("Shacl2ShEx error: Feature not implemented: {literal}")]
     RDFNode2LabelLiteral { literal: Literal },
 
+    #[error("Shape {node} not found in the SHACL schema")]
+    ShapeNotFound { node: String },
+
     #[error("Not expected node shape: {node_shape:?}")]
     NotExpectedNodeShape { node_shape: Box<NodeShape> },
 
@@ -0,0 +1,10 @@
+//! ShEx to SHACL converter
+//!
+//!
+pub mod shex2shacl;
+pub mod shex2shacl_config;
+pub mod shex2shacl_error;
+
+pub use shex2shacl::*;
+pub use shex2shacl_config::*;
+pub use shex2shacl_error::*;
@@ -0,0 +1,727 @@
+use std::collections::HashMap;
+
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use shacl_ast::{
+    component::Component, node_kind::NodeKind as ShaclNodeKind, node_shape::NodeShape,
+    property_shape::PropertyShape, shape::Shape as ShaclShape, value::Value, Schema as ShaclSchema,
+};
+use shex_ast::{
+    NodeConstraint, NodeKind, NumericFacet, ObjectValue, Schema as ShExSchema, Shape, ShapeExpr,
+    ShapeExprLabel, StringFacet, TripleExpr, ValueSetValue, XsFacet,
+};
+use srdf::{lang::Lang, literal::Literal, RDFNode, SHACLPath};
+use tracing::debug;
+
+use super::{ShEx2ShaclConfig, ShEx2ShaclError};
+
+#[allow(dead_code)] // TODO: only for config...
+pub struct ShEx2Shacl {
+    config: ShEx2ShaclConfig,
+    current_shacl: ShaclSchema,
+    current_prefixmap: PrefixMap,
+    shapes: HashMap<RDFNode, ShaclShape>,
+    start: Option<RDFNode>,
+    bnode_counter: usize,
+    warnings: Vec<String>,
+}
+
+/// Triple constraint of a shape with its cardinality resolved
+struct Constraint {
+    predicate: IriS,
+    inverse: bool,
+    value_expr: Option<ShapeExpr>,
+    min: i32,
+    max: i32,
+}
+
+impl ShEx2Shacl {
+    pub fn new(config: &ShEx2ShaclConfig) -> ShEx2Shacl {
+        ShEx2Shacl {
+            config: config.clone(),
+            current_shacl: ShaclSchema::new(),
+            current_prefixmap: PrefixMap::new(),
+            shapes: HashMap::new(),
+            start: None,
+            bnode_counter: 0,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn current_shacl(&self) -> &ShaclSchema {
+        &self.current_shacl
+    }
+
+    /// Constructs of the last converted schema that have no equivalent in
+    /// SHACL and have been ignored or approximated
+    pub fn warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
+    pub fn convert(&mut self, schema: &ShExSchema) -> Result<(), ShEx2ShaclError> {
+        self.current_prefixmap = schema.prefixmap().unwrap_or_default();
+        self.shapes = HashMap::new();
+        self.bnode_counter = 0;
+        self.warnings = Vec::new();
+        self.start = None;
+        if let Some(start) = schema.start() {
+            self.warning(
+                "The start shape expression is converted to a node shape without targets, SHACL has no start shape".to_string(),
+            );
+            let id = self.fresh_bnode("start");
+            self.start = Some(id.clone());
+            self.shape_expr2node_shape(id, &start)?;
+        }
+        for decl in schema.shapes().unwrap_or_default() {
+            if decl.is_abstract {
+                self.warning(format!(
+                    "Shape {} is declared abstract, which has no equivalent in SHACL",
+                    decl.id
+                ))
+            }
+            let id = self.label2node(&decl.id)?;
+            self.shape_expr2node_shape(id, &decl.shape_expr)?;
+        }
+        self.current_shacl = ShaclSchema::new()
+            .with_prefixmap(self.current_prefixmap.clone())
+            .with_shapes(std::mem::take(&mut self.shapes));
+        Ok(())
+    }
+
+    fn label2node(&mut self, label: &ShapeExprLabel) -> Result<RDFNode, ShEx2ShaclError> {
+        match label {
+            ShapeExprLabel::IriRef { value } => Ok(RDFNode::iri(self.resolve(value)?)),
+            ShapeExprLabel::BNode { value } => Ok(RDFNode::bnode(value.value().to_string())),
+            ShapeExprLabel::Start => self.start.clone().ok_or(ShEx2ShaclError::UndefinedStart),
+        }
+    }
+
+    /// Adds a node shape with the given id whose focus nodes must conform to
+    /// the shape expression
+    fn shape_expr2node_shape(
+        &mut self,
+        id: RDFNode,
+        shape_expr: &ShapeExpr,
+    ) -> Result<(), ShEx2ShaclError> {
+        let mut components = Vec::new();
+        let mut property_shapes = Vec::new();
+        self.add_shape_expr(shape_expr, &mut components, &mut property_shapes)?;
+        let node_shape = NodeShape::new(id.clone())
+            .with_components(components)
+            .with_property_shapes(property_shapes);
+        self.shapes
+            .insert(id, ShaclShape::NodeShape(Box::new(node_shape)));
+        Ok(())
+    }
+
+    /// Node that represents a shape expression, which is the label of the
+    /// shape for references and a new node shape otherwise
+    fn shape_expr2shape_node(
+        &mut self,
+        shape_expr: &ShapeExpr,
+    ) -> Result<RDFNode, ShEx2ShaclError> {
+        match shape_expr {
+            ShapeExpr::Ref(label) => self.label2node(label),
+            _ => {
+                let id = self.fresh_bnode("shape");
+                self.shape_expr2node_shape(id.clone(), shape_expr)?;
+                Ok(id)
+            }
+        }
+    }
+
+    /// Conjunctions and shapes are added directly to the node shape, the
+    /// remaining shape expressions are converted to components
+    fn add_shape_expr(
+        &mut self,
+        shape_expr: &ShapeExpr,
+        components: &mut Vec<Component>,
+        property_shapes: &mut Vec<RDFNode>,
+    ) -> Result<(), ShEx2ShaclError> {
+        match shape_expr {
+            ShapeExpr::ShapeAnd { shape_exprs } => {
+                for sew in shape_exprs {
+                    self.add_shape_expr(&sew.se, components, property_shapes)?;
+                }
+            }
+            ShapeExpr::Shape(shape) => self.add_shape(shape, components, property_shapes)?,
+            _ => components.extend(self.shape_expr2components(shape_expr)?),
+        }
+        Ok(())
+    }
+
+    /// Each triple constraint of a shape is converted to a property shape
+    /// with `sh:minCount` and `sh:maxCount`. When the predicate is repeated
+    /// or declared as `EXTRA`, the triple constraints are converted to
+    /// qualified value shapes, as only some values need to conform
+    fn add_shape(
+        &mut self,
+        shape: &Shape,
+        components: &mut Vec<Component>,
+        property_shapes: &mut Vec<RDFNode>,
+    ) -> Result<(), ShEx2ShaclError> {
+        if shape.extends.is_some() {
+            self.warning("EXTENDS can't be represented in SHACL, ignoring it".to_string())
+        }
+        if shape.sem_acts.is_some() {
+            self.warning("Semantic actions are ignored".to_string())
+        }
+        let mut extra = Vec::new();
+        for iri_ref in shape.extra.iter().flatten() {
+            extra.push(self.resolve(iri_ref)?)
+        }
+
+        let mut constraints = Vec::new();
+        let mut alternatives = Vec::new();
+        if let Some(te) = shape.triple_expr() {
+            self.triple_expr2constraints(&te, &mut constraints, &mut alternatives)?;
+        }
+
+        let mut repeated: Vec<(IriS, bool)> = Vec::new();
+        for (i, c) in constraints.iter().enumerate() {
+            let key = (c.predicate.clone(), c.inverse);
+            let is_repeated = constraints[i + 1..]
+                .iter()
+                .any(|other| other.predicate == c.predicate && other.inverse == c.inverse);
+            if is_repeated && !repeated.contains(&key) {
+                repeated.push(key)
+            }
+        }
+
+        for c in constraints.iter() {
+            let qualified = repeated.contains(&(c.predicate.clone(), c.inverse))
+                || (!c.inverse && extra.contains(&c.predicate));
+            let id = self.constraint2property_shape(c, qualified)?;
+            property_shapes.push(id);
+        }
+        if !repeated.is_empty() {
+            self.warning(format!(
+                "Triple constraints with repeated predicates {} are converted to qualified value shapes, without partitioning their values",
+                repeated
+                    .iter()
+                    .map(|(predicate, _)| self.current_prefixmap.qualify(predicate))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+
+        let mut alternative_predicates = Vec::new();
+        for alternative in alternatives {
+            collect_predicates(&alternative, &mut alternative_predicates);
+            let shapes = match alternative {
+                TripleExpr::OneOf { expressions, .. } => expressions
+                    .iter()
+                    .map(|tew| {
+                        let shape = Shape::new(None, None, Some(tew.te.clone()));
+                        self.shape_expr2shape_node(&ShapeExpr::shape(shape))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                _ => Vec::new(),
+            };
+            self.warning(
+                "OneOf is converted to sh:xone, which doesn't partition the values among the alternatives".to_string(),
+            );
+            components.push(Component::Xone { shapes })
+        }
+
+        if shape.is_closed() {
+            let mut ignored_properties: Vec<IriRef> =
+                extra.iter().cloned().map(IriRef::iri).collect();
+            // the predicates of the alternatives are not direct properties of the shape
+            for predicate in alternative_predicates {
+                let predicate = IriRef::iri(self.resolve(&predicate)?);
+                if !ignored_properties.contains(&predicate) {
+                    ignored_properties.push(predicate)
+                }
+            }
+            components.push(Component::Closed {
+                is_closed: true,
+                ignored_properties,
+            })
+        }
+        Ok(())
+    }
+
+    /// Flattens the `EachOf` groups of a triple expression into triple
+    /// constraints, while the `OneOf` groups are returned as alternatives
+    fn triple_expr2constraints(
+        &mut self,
+        te: &TripleExpr,
+        constraints: &mut Vec<Constraint>,
+        alternatives: &mut Vec<TripleExpr>,
+    ) -> Result<(), ShEx2ShaclError> {
+        match te {
+            TripleExpr::TripleConstraint {
+                negated,
+                inverse,
+                predicate,
+                value_expr,
+                min,
+                max,
+                ..
+            } => {
+                let predicate = self.resolve(predicate)?;
+                if let Some(true) = negated {
+                    self.warning(format!(
+                        "Negated triple constraint for {} can't be represented in SHACL, ignoring it",
+                        self.current_prefixmap.qualify(&predicate)
+                    ));
+                    return Ok(());
+                }
+                constraints.push(Constraint {
+                    predicate,
+                    inverse: inverse.unwrap_or(false),
+                    value_expr: value_expr.as_ref().map(|se| se.as_ref().clone()),
+                    min: min.unwrap_or(1),
+                    max: max.unwrap_or(1),
+                })
+            }
+            TripleExpr::EachOf {
+                expressions,
+                min,
+                max,
+                ..
+            } => {
+                if min.unwrap_or(1) != 1 || max.unwrap_or(1) != 1 {
+                    self.warning(
+                        "The cardinality of groups of triple expressions is ignored".to_string(),
+                    )
+                }
+                for tew in expressions {
+                    self.triple_expr2constraints(&tew.te, constraints, alternatives)?;
+                }
+            }
+            TripleExpr::OneOf { min, max, .. } => {
+                if min.unwrap_or(1) != 1 || max.unwrap_or(1) != 1 {
+                    self.warning(
+                        "The cardinality of groups of triple expressions is ignored".to_string(),
+                    )
+                }
+                alternatives.push(te.clone())
+            }
+            TripleExpr::TripleExprRef(label) => self.warning(format!(
+                "Reference to triple expression {label} can't be represented in SHACL, ignoring it"
+            )),
+        }
+        Ok(())
+    }
+
+    fn constraint2property_shape(
+        &mut self,
+        constraint: &Constraint,
+        qualified: bool,
+    ) -> Result<RDFNode, ShEx2ShaclError> {
+        let pred = SHACLPath::iri(constraint.predicate.clone());
+        let path = if constraint.inverse {
+            SHACLPath::Inverse {
+                path: Box::new(pred),
+            }
+        } else {
+            pred
+        };
+        let min = match constraint.min {
+            0 => None,
+            n => Some(n as isize),
+        };
+        let max = match constraint.max {
+            -1 => None,
+            n => Some(n as isize),
+        };
+
+        let mut components = Vec::new();
+        if qualified {
+            let shape_expr = constraint
+                .value_expr
+                .clone()
+                .unwrap_or_else(ShapeExpr::empty_shape);
+            components.push(Component::QualifiedValueShape {
+                shape: self.shape_expr2shape_node(&shape_expr)?,
+                qualified_min_count: Some(min.unwrap_or(0)),
+                qualified_max_count: max,
+                qualified_value_shapes_disjoint: None,
+            })
+        } else {
+            components.extend(min.map(Component::MinCount));
+            components.extend(max.map(Component::MaxCount));
+            if let Some(se) = &constraint.value_expr {
+                components.extend(self.shape_expr2components(se)?)
+            }
+        }
+
+        let id = self.fresh_bnode("property");
+        let property_shape = PropertyShape::new(id.clone(), path).with_components(components);
+        self.shapes
+            .insert(id.clone(), ShaclShape::PropertyShape(property_shape));
+        Ok(id)
+    }
+
+    /// Converts a shape expression to the components that a value node must
+    /// conform to
+    fn shape_expr2components(
+        &mut self,
+        shape_expr: &ShapeExpr,
+    ) -> Result<Vec<Component>, ShEx2ShaclError> {
+        match shape_expr {
+            ShapeExpr::NodeConstraint(nc) => self.node_constraint2components(nc),
+            ShapeExpr::Ref(label) => Ok(vec![Component::Node {
+                shape: self.label2node(label)?,
+            }]),
+            ShapeExpr::ShapeAnd { shape_exprs } => {
+                let mut components = Vec::new();
+                for sew in shape_exprs {
+                    components.extend(self.shape_expr2components(&sew.se)?)
+                }
+                Ok(components)
+            }
+            ShapeExpr::ShapeOr { shape_exprs } => {
+                let shapes = shape_exprs
+                    .iter()
+                    .map(|sew| self.shape_expr2shape_node(&sew.se))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(vec![Component::Or { shapes }])
+            }
+            ShapeExpr::ShapeNot { shape_expr } => Ok(vec![Component::Not {
+                shape: self.shape_expr2shape_node(&shape_expr.se)?,
+            }]),
+            ShapeExpr::Shape(_) => Ok(vec![Component::Node {
+                shape: self.shape_expr2shape_node(shape_expr)?,
+            }]),
+            ShapeExpr::External => {
+                self.warning(
+                    "EXTERNAL shapes can't be represented in SHACL, ignoring them".to_string(),
+                );
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn node_constraint2components(
+        &mut self,
+        nc: &NodeConstraint,
+    ) -> Result<Vec<Component>, ShEx2ShaclError> {
+        let mut components = Vec::new();
+        if let Some(node_kind) = nc.node_kind() {
+            let node_kind = match node_kind {
+                NodeKind::Iri => ShaclNodeKind::Iri,
+                NodeKind::BNode => ShaclNodeKind::BlankNode,
+                NodeKind::NonLiteral => ShaclNodeKind::BlankNodeOrIri,
+                NodeKind::Literal => ShaclNodeKind::Literal,
+            };
+            components.push(Component::NodeKind(node_kind))
+        }
+        if let Some(datatype) = nc.datatype() {
+            components.push(Component::Datatype(IriRef::iri(self.resolve(&datatype)?)))
+        }
+        for facet in nc.xs_facet().unwrap_or_default() {
+            match facet {
+                XsFacet::StringFacet(StringFacet::Length(n)) => {
+                    components.push(Component::MinLength(n as isize));
+                    components.push(Component::MaxLength(n as isize))
+                }
+                XsFacet::StringFacet(StringFacet::MinLength(n)) => {
+                    components.push(Component::MinLength(n as isize))
+                }
+                XsFacet::StringFacet(StringFacet::MaxLength(n)) => {
+                    components.push(Component::MaxLength(n as isize))
+                }
+                XsFacet::StringFacet(StringFacet::Pattern(pattern)) => {
+                    components.push(Component::Pattern {
+                        pattern: pattern.str,
+                        flags: pattern.flags,
+                    })
+                }
+                XsFacet::NumericFacet(NumericFacet::MinInclusive(n)) => {
+                    components.push(Component::MinInclusive(Literal::NumericLiteral(n)))
+                }
+                XsFacet::NumericFacet(NumericFacet::MinExclusive(n)) => {
+                    components.push(Component::MinExclusive(Literal::NumericLiteral(n)))
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxInclusive(n)) => {
+                    components.push(Component::MaxInclusive(Literal::NumericLiteral(n)))
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxExclusive(n)) => {
+                    components.push(Component::MaxExclusive(Literal::NumericLiteral(n)))
+                }
+                XsFacet::NumericFacet(NumericFacet::TotalDigits(_))
+                | XsFacet::NumericFacet(NumericFacet::FractionDigits(_)) => self.warning(format!(
+                    "{facet:?} can't be represented in SHACL, ignoring it"
+                )),
+            }
+        }
+        if let Some(values) = nc.values() {
+            components.extend(self.values2components(&values)?)
+        }
+        Ok(components)
+    }
+
+    /// Values are converted to `sh:in` and language tags to `sh:languageIn`,
+    /// which are combined with `sh:or` when a value set has both of them
+    fn values2components(
+        &mut self,
+        values: &[ValueSetValue],
+    ) -> Result<Vec<Component>, ShEx2ShaclError> {
+        let mut objects = Vec::new();
+        let mut langs: Vec<Lang> = Vec::new();
+        for value in values {
+            match value {
+                ValueSetValue::ObjectValue(ObjectValue::IriRef(iri_ref)) => {
+                    objects.push(Value::iri(self.resolve(iri_ref)?))
+                }
+                ValueSetValue::ObjectValue(ObjectValue::Literal(literal)) => {
+                    objects.push(Value::literal(self.resolve_literal(literal)?))
+                }
+                ValueSetValue::LanguageStem { stem } => langs.push(stem.clone()),
+                ValueSetValue::Language { language_tag } => {
+                    self.warning(format!(
+                        "Language tag @{} is converted to sh:languageIn, which also accepts its subtags",
+                        language_tag.value()
+                    ));
+                    langs.push(language_tag.clone())
+                }
+                _ => self.warning(format!(
+                    "Value {value:?} can't be represented in SHACL, ignoring it"
+                )),
+            }
+        }
+        let mut components = Vec::new();
+        if !objects.is_empty() {
+            components.push(Component::In { values: objects })
+        }
+        if !langs.is_empty() {
+            components.push(Component::LanguageIn { langs })
+        }
+        if components.len() > 1 {
+            let shapes = components
+                .into_iter()
+                .map(|component| {
+                    let id = self.fresh_bnode("shape");
+                    let node_shape = NodeShape::new(id.clone()).with_components(vec![component]);
+                    self.shapes
+                        .insert(id.clone(), ShaclShape::NodeShape(Box::new(node_shape)));
+                    id
+                })
+                .collect();
+            components = vec![Component::Or { shapes }]
+        }
+        Ok(components)
+    }
+
+    fn resolve(&self, iri_ref: &IriRef) -> Result<IriS, ShEx2ShaclError> {
+        Ok(self.current_prefixmap.resolve_iriref(iri_ref)?)
+    }
+
+    fn resolve_literal(&self, literal: &Literal) -> Result<Literal, ShEx2ShaclError> {
+        match literal {
+            Literal::DatatypeLiteral {
+                lexical_form,
+                datatype,
+            } => Ok(Literal::DatatypeLiteral {
+                lexical_form: lexical_form.clone(),
+                datatype: IriRef::iri(self.resolve(datatype)?),
+            }),
+            _ => Ok(literal.clone()),
+        }
+    }
+
+    fn fresh_bnode(&mut self, prefix: &str) -> RDFNode {
+        self.bnode_counter += 1;
+        RDFNode::bnode(format!("{prefix}{}", self.bnode_counter))
+    }
+
+    fn warning(&mut self, msg: String) {
+        debug!("ShEx2Shacl warning: {msg}");
+        self.warnings.push(msg)
+    }
+}
+
+fn collect_predicates(te: &TripleExpr, predicates: &mut Vec<IriRef>) {
+    match te {
+        TripleExpr::TripleConstraint {
+            predicate, inverse, ..
+        } => {
+            if !inverse.unwrap_or(false) {
+                predicates.push(predicate.clone())
+            }
+        }
+        TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+            for tew in expressions {
+                collect_predicates(&tew.te, predicates)
+            }
+        }
+        TripleExpr::TripleExprRef(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::{ShaclParser, ShaclWriter};
+    use shex_compact::ShExParser;
+    use srdf::{RDFFormat, ReaderMode, SRDFGraph};
+
+    #[test]
+    fn test_triple_constraints() {
+        let shex_str = "\
+prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person CLOSED { :name xsd:string MINLENGTH 1 {1,3} }";
+        let schema = ShExParser::parse(shex_str, None).unwrap();
+        let mut converter = ShEx2Shacl::new(&ShEx2ShaclConfig::default());
+        converter.convert(&schema).unwrap();
+        let shacl = converter.current_shacl();
+
+        let person = RDFNode::iri(IriS::new_unchecked("http://example.org/Person"));
+        let node_shape = match shacl.get_shape(&person) {
+            Some(ShaclShape::NodeShape(ns)) => ns,
+            _ => panic!("Expected a node shape for :Person"),
+        };
+        assert_eq!(
+            node_shape.components(),
+            &vec![Component::Closed {
+                is_closed: true,
+                ignored_properties: Vec::new()
+            }]
+        );
+        let property_shape = match shacl.get_shape(&node_shape.property_shapes()[0]) {
+            Some(ShaclShape::PropertyShape(ps)) => ps,
+            _ => panic!("Expected a property shape for :name"),
+        };
+        assert_eq!(
+            property_shape.path(),
+            &SHACLPath::iri(IriS::new_unchecked("http://example.org/name"))
+        );
+        assert_eq!(
+            property_shape.components(),
+            &vec![
+                Component::MinCount(1),
+                Component::MaxCount(3),
+                Component::Datatype(IriRef::iri(IriS::new_unchecked(
+                    "http://www.w3.org/2001/XMLSchema#string"
+                ))),
+                Component::MinLength(1),
+            ]
+        );
+        assert!(converter.warnings().is_empty());
+    }
+
+    /// Paths and components of the property shapes of a shape, sorted by path
+    fn property_shapes(schema: &ShaclSchema, node: &RDFNode) -> Vec<(String, Vec<Component>)> {
+        let shape = match schema.get_shape(node) {
+            Some(ShaclShape::NodeShape(ns)) => ns,
+            _ => panic!("Expected a node shape for {node}"),
+        };
+        let mut property_shapes: Vec<_> = shape
+            .property_shapes()
+            .iter()
+            .map(|ps| match schema.get_shape(ps) {
+                Some(ShaclShape::PropertyShape(ps)) => {
+                    (ps.path().to_string(), ps.components().clone())
+                }
+                _ => panic!("Expected a property shape for {ps}"),
+            })
+            .collect();
+        property_shapes.sort_by(|(p1, _), (p2, _)| p1.cmp(p2));
+        property_shapes
+    }
+
+    #[test]
+    fn test_lists_paths_and_node_kinds_round_trip() {
+        let shex_str = r#"
+prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:S { :p [:a "1"^^xsd:integer "x"] ; ^:q @:T OR @:U ? ; :r IRI * }
+:U BNODE"#;
+        let schema = ShExParser::parse(shex_str, None).unwrap();
+        let mut converter = ShEx2Shacl::new(&ShEx2ShaclConfig::default());
+        converter.convert(&schema).unwrap();
+        let shacl = converter.current_shacl();
+
+        let s = RDFNode::iri(IriS::new_unchecked("http://example.org/S"));
+        let u = RDFNode::iri(IriS::new_unchecked("http://example.org/U"));
+        let converted = property_shapes(shacl, &s);
+        let paths: Vec<_> = converted.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "^http://example.org/q",
+                "http://example.org/p",
+                "http://example.org/r"
+            ]
+        );
+        assert!(converted[0].1.contains(&Component::Or {
+            shapes: vec![
+                RDFNode::iri(IriS::new_unchecked("http://example.org/T")),
+                u.clone()
+            ]
+        }));
+
+        let mut writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
+        writer.write(shacl).unwrap();
+        let mut turtle = Vec::new();
+        writer.serialize(RDFFormat::Turtle, &mut turtle).unwrap();
+        let turtle = String::from_utf8(turtle).unwrap();
+        let rdf =
+            SRDFGraph::from_str(&turtle, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let parsed = ShaclParser::new(rdf).parse().unwrap();
+
+        assert_eq!(property_shapes(&parsed, &s), converted, "{turtle}");
+        let node_kind = |schema: &ShaclSchema| match schema.get_shape(&u) {
+            Some(ShaclShape::NodeShape(ns)) => ns.components().clone(),
+            _ => panic!("Expected a node shape for :U"),
+        };
+        assert_eq!(node_kind(&parsed), node_kind(shacl));
+        assert_eq!(
+            node_kind(shacl),
+            vec![Component::NodeKind(ShaclNodeKind::BlankNode)]
+        );
+    }
+
+    #[test]
+    fn test_start_shape() {
+        // :S { :p @START ? }
+        let s_label = ShapeExprLabel::iri(IriS::new_unchecked("http://example.org/S"));
+        let shape = ShapeExpr::shape(Shape::new(
+            None,
+            None,
+            Some(TripleExpr::triple_constraint(
+                None,
+                None,
+                IriRef::iri(IriS::new_unchecked("http://example.org/p")),
+                Some(ShapeExpr::shape_ref(ShapeExprLabel::Start)),
+                Some(0),
+                Some(1),
+            )),
+        ));
+        let mut schema = ShExSchema::new();
+        schema.add_shape(s_label.clone(), shape, false);
+        let mut converter = ShEx2Shacl::new(&ShEx2ShaclConfig::default());
+        assert!(matches!(
+            converter.convert(&schema),
+            Err(ShEx2ShaclError::UndefinedStart)
+        ));
+
+        let schema = schema.with_start(Some(ShapeExpr::shape_ref(s_label)));
+        converter.convert(&schema).unwrap();
+        let shacl = converter.current_shacl();
+        let s = RDFNode::iri(IriS::new_unchecked("http://example.org/S"));
+        let start = property_shapes(shacl, &s)[0]
+            .1
+            .iter()
+            .find_map(|component| match component {
+                Component::Node { shape } => Some(shape.clone()),
+                _ => None,
+            })
+            .unwrap();
+        // the start shape is a node shape that refers to :S
+        let start_shape = match shacl.get_shape(&start) {
+            Some(ShaclShape::NodeShape(ns)) => ns,
+            _ => panic!("Expected a node shape for the start shape"),
+        };
+        assert_eq!(
+            start_shape.components(),
+            &vec![Component::Node { shape: s.clone() }]
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use shex_validation::ShExConfig;
+
+/// Defines the configuration of the converter
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct ShEx2ShaclConfig {
+    pub shex: Option<ShExConfig>,
+}
+
+impl ShEx2ShaclConfig {
+    /// Get the ShExConfig if it has been declared or the default one
+    pub fn shex_config(&self) -> ShExConfig {
+        match &self.shex {
+            None => ShExConfig::default(),
+            Some(sc) => sc.clone(),
+        }
+    }
+}
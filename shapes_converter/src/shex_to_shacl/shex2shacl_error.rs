@@ -0,0 +1,27 @@
+use prefixmap::PrefixMapError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ShEx2ShaclError {
+    #[error("ShEx2Shacl error: Feature not implemented: {msg}")]
+    NotImplemented { msg: String },
+
+    #[error(
+        "ShEx2Shacl error: Reference to the start shape in a schema without start shape expression"
+    )]
+    UndefinedStart,
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+}
+
+impl ShEx2ShaclError {
+    pub fn not_implemented(msg: &str) -> ShEx2ShaclError {
+        ShEx2ShaclError::NotImplemented {
+            msg: msg.to_string(),
+        }
+    }
+}
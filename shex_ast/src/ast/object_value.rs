@@ -70,6 +70,15 @@ impl ObjectValue {
     }
 }
 
+impl fmt::Display for ObjectValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectValue::IriRef(iri) => write!(f, "{iri}"),
+            ObjectValue::Literal(lit) => write!(f, "{lit}"),
+        }
+    }
+}
+
 impl Deref for ObjectValue {
     fn deref(
         &self,
@@ -3,6 +3,7 @@ use crate::ShapeExprLabel;
 use iri_s::IriS;
 use prefixmap::{IriRef, PrefixMap, PrefixMapError};
 use serde_derive::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::BufRead;
 use std::path::{Path, PathBuf};
@@ -272,6 +273,79 @@ impl Schema {
     }
 }
 
+impl Schema {
+    /// Labels of the shapes declared differently in `self` and `other`, together with the labels of the
+    /// shapes that depend on them through references or extensions. A shape also depends on the shapes
+    /// that extend it, as a node can conform to it through them.
+    ///
+    /// `START` is included when the start shape expression changes or depends on a changed shape.
+    /// If the prefixes, base or start actions change, all shapes are considered changed
+    pub fn changed_shapes(&self, other: &Schema) -> HashSet<ShapeExprLabel> {
+        let decls = |schema: &Schema| -> HashMap<ShapeExprLabel, ShapeDecl> {
+            schema
+                .shapes
+                .iter()
+                .flatten()
+                .map(|decl| (decl.id.clone(), decl.clone()))
+                .collect()
+        };
+        let (old, new) = (decls(self), decls(other));
+        if self.prefixmap != other.prefixmap
+            || self.base != other.base
+            || self.start_acts != other.start_acts
+        {
+            let mut all: HashSet<ShapeExprLabel> = old.keys().chain(new.keys()).cloned().collect();
+            all.insert(ShapeExprLabel::Start);
+            return all;
+        }
+        let mut changed: HashSet<ShapeExprLabel> = old
+            .keys()
+            .chain(new.keys())
+            .filter(|label| old.get(*label) != new.get(*label))
+            .cloned()
+            .collect();
+        if self.start != other.start {
+            changed.insert(ShapeExprLabel::Start);
+        }
+        let mut references: Vec<(ShapeExprLabel, Vec<ShapeExprLabel>)> = old
+            .values()
+            .chain(new.values())
+            .map(|decl| (decl.id.clone(), decl.shape_expr.references()))
+            .collect();
+        for start in self.start.iter().chain(other.start.iter()) {
+            references.push((ShapeExprLabel::Start, start.references()))
+        }
+        for decl in old.values().chain(new.values()) {
+            for parent in decl.shape_expr.extends() {
+                references.push((parent, vec![decl.id.clone()]))
+            }
+        }
+        loop {
+            let dependents: Vec<ShapeExprLabel> = references
+                .iter()
+                .filter(|(label, refs)| {
+                    !changed.contains(label) && refs.iter().any(|r| changed.contains(r))
+                })
+                .map(|(label, _)| label.clone())
+                .collect();
+            if dependents.is_empty() {
+                break;
+            }
+            changed.extend(dependents)
+        }
+        changed
+    }
+
+    /// Checks if some shape of the schema contains an inverse triple constraint
+    pub fn has_inverse_triple_constraints(&self) -> bool {
+        self.shapes
+            .iter()
+            .flatten()
+            .any(|decl| decl.shape_expr.has_inverse())
+            || self.start.as_ref().is_some_and(|se| se.has_inverse())
+    }
+}
+
 impl Default for Schema {
     fn default() -> Self {
         Self::new()
@@ -315,4 +389,39 @@ mod tests {
         let schema_after_serialization = serde_json::from_str(&serialized).unwrap();
         assert_eq!(schema, schema_after_serialization);
     }
+
+    #[test]
+    fn test_changed_shapes() {
+        let schema = |person_name: &str| -> Schema {
+            let str = format!(
+                r#"{{
+                "type": "Schema",
+                "shapes": [
+                  {{ "type": "ShapeDecl", "id": "http://example.org/Person",
+                     "shapeExpr": {{ "type": "Shape", "expression": {{
+                        "type": "TripleConstraint", "predicate": "{person_name}" }} }} }},
+                  {{ "type": "ShapeDecl", "id": "http://example.org/Company",
+                     "shapeExpr": {{ "type": "Shape", "expression": {{
+                        "type": "TripleConstraint", "predicate": "http://example.org/employee",
+                        "valueExpr": "http://example.org/Person" }} }} }},
+                  {{ "type": "ShapeDecl", "id": "http://example.org/Product",
+                     "shapeExpr": {{ "type": "Shape", "expression": {{
+                        "type": "TripleConstraint", "predicate": "http://example.org/price" }} }} }}
+                ],
+                "@context": "http://www.w3.org/ns/shex.jsonld"
+              }}"#
+            );
+            serde_json::from_str(&str).unwrap()
+        };
+        let before = schema("http://schema.org/name");
+        let after = schema("http://schema.org/givenName");
+        let label =
+            |name: &str| ShapeExprLabel::iri_unchecked(&format!("http://example.org/{name}"));
+        assert!(before.changed_shapes(&before).is_empty());
+        assert_eq!(
+            before.changed_shapes(&after),
+            HashSet::from([label("Person"), label("Company")])
+        );
+        assert!(!after.has_inverse_triple_constraints());
+    }
 }
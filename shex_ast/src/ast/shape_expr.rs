@@ -164,6 +164,52 @@ impl ShapeExpr {
             _ => todo!(),
         }
     }
+
+    /// Labels of the shape expressions referenced by this shape expression, including the shapes that it extends
+    pub fn references(&self) -> Vec<ShapeExprLabel> {
+        match self {
+            ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => {
+                shape_exprs.iter().flat_map(|e| e.se.references()).collect()
+            }
+            ShapeExpr::ShapeNot { shape_expr } => shape_expr.se.references(),
+            ShapeExpr::Shape(shape) => {
+                let mut refs = shape.extends.clone().unwrap_or_default();
+                if let Some(expr) = &shape.expression {
+                    refs.extend(expr.te.references())
+                }
+                refs
+            }
+            ShapeExpr::Ref(label) => vec![label.clone()],
+            ShapeExpr::NodeConstraint(_) | ShapeExpr::External => Vec::new(),
+        }
+    }
+
+    /// Labels of the shapes extended by the shapes of this shape expression
+    pub fn extends(&self) -> Vec<ShapeExprLabel> {
+        match self {
+            ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => {
+                shape_exprs.iter().flat_map(|e| e.se.extends()).collect()
+            }
+            ShapeExpr::ShapeNot { shape_expr } => shape_expr.se.extends(),
+            ShapeExpr::Shape(shape) => shape.extends.clone().unwrap_or_default(),
+            ShapeExpr::Ref(_) | ShapeExpr::NodeConstraint(_) | ShapeExpr::External => Vec::new(),
+        }
+    }
+
+    /// Checks if this shape expression contains some inverse triple constraint
+    pub fn has_inverse(&self) -> bool {
+        match self {
+            ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => {
+                shape_exprs.iter().any(|e| e.se.has_inverse())
+            }
+            ShapeExpr::ShapeNot { shape_expr } => shape_expr.se.has_inverse(),
+            ShapeExpr::Shape(shape) => shape
+                .expression
+                .as_ref()
+                .is_some_and(|expr| expr.te.has_inverse()),
+            ShapeExpr::Ref(_) | ShapeExpr::NodeConstraint(_) | ShapeExpr::External => false,
+        }
+    }
 }
 
 impl Default for ShapeExpr {
@@ -12,6 +12,7 @@ use super::{
     annotation::Annotation, sem_act::SemAct, shape_expr::ShapeExpr,
     triple_expr_label::TripleExprLabel,
 };
+use crate::ShapeExprLabel;
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(tag = "type")]
@@ -112,6 +113,35 @@ impl TripleExpr {
         }
     }
 
+    /// Labels of the shape expressions referenced by the value expressions of this triple expression
+    pub fn references(&self) -> Vec<ShapeExprLabel> {
+        match self {
+            TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+                expressions.iter().flat_map(|e| e.te.references()).collect()
+            }
+            TripleExpr::TripleConstraint { value_expr, .. } => value_expr
+                .as_ref()
+                .map(|se| se.references())
+                .unwrap_or_default(),
+            TripleExpr::TripleExprRef(_) => Vec::new(),
+        }
+    }
+
+    /// Checks if this triple expression contains some inverse triple constraint
+    pub fn has_inverse(&self) -> bool {
+        match self {
+            TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+                expressions.iter().any(|e| e.te.has_inverse())
+            }
+            TripleExpr::TripleConstraint {
+                inverse,
+                value_expr,
+                ..
+            } => inverse.unwrap_or(false) || value_expr.as_ref().is_some_and(|se| se.has_inverse()),
+            TripleExpr::TripleExprRef(_) => false,
+        }
+    }
+
     pub fn each_of(tes: Vec<TripleExpr>) -> TripleExpr {
         let mut tews = Vec::new();
         for te in tes {
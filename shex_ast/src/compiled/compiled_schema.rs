@@ -4,19 +4,23 @@ use crate::{
 };
 use iri_s::IriS;
 use prefixmap::{IriRef, PrefixMap};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
+use super::sem_act::SemAct;
 use super::shape_expr::ShapeExpr;
 use super::shape_label::ShapeLabel;
 
 type Result<A> = std::result::Result<A, CompiledSchemaError>;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CompiledSchema {
     shape_labels_map: HashMap<ShapeLabel, ShapeLabelIdx>,
     shapes: HashMap<ShapeLabelIdx, (ShapeLabel, ShapeExpr)>,
     shape_label_counter: ShapeLabelIdx,
+    abstract_shapes: HashSet<ShapeLabelIdx>,
+    descendants: HashMap<ShapeLabelIdx, Vec<ShapeLabelIdx>>,
+    start_acts: Vec<SemAct>,
     prefixmap: PrefixMap,
 }
 
@@ -26,6 +30,9 @@ impl CompiledSchema {
             shape_labels_map: HashMap::new(),
             shape_label_counter: ShapeLabelIdx::default(),
             shapes: HashMap::new(),
+            abstract_shapes: HashSet::new(),
+            descendants: HashMap::new(),
+            start_acts: Vec::new(),
             prefixmap: PrefixMap::new(),
         }
     }
@@ -34,6 +41,11 @@ impl CompiledSchema {
         self.prefixmap = prefixmap.clone().unwrap_or_default();
     }
 
+    /// Prefix map of the schema from which it was compiled
+    pub fn prefixmap(&self) -> PrefixMap {
+        self.prefixmap.clone()
+    }
+
     pub fn add_shape(&mut self, shape_label: ShapeLabel, se: ShapeExpr) {
         let idx = self.shape_label_counter;
         self.shape_labels_map.insert(shape_label.clone(), idx);
@@ -41,6 +53,107 @@ impl CompiledSchema {
         self.shape_label_counter.incr()
     }
 
+    pub fn set_start_acts(&mut self, start_acts: Vec<SemAct>) {
+        self.start_acts = start_acts;
+    }
+
+    /// Semantic actions that are executed when the validation starts
+    pub fn start_acts(&self) -> &Vec<SemAct> {
+        &self.start_acts
+    }
+
+    pub fn set_abstract(&mut self, idx: &ShapeLabelIdx) {
+        self.abstract_shapes.insert(*idx);
+    }
+
+    pub fn is_abstract(&self, idx: &ShapeLabelIdx) -> bool {
+        self.abstract_shapes.contains(idx)
+    }
+
+    /// Shapes directly extended by the shape declaration `idx`
+    pub fn parents(&self, idx: &ShapeLabelIdx) -> Vec<ShapeLabelIdx> {
+        match self.shapes.get(idx) {
+            Some((_label, se)) => se.extends(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Shapes that extend the shape declaration `idx`, directly or transitively, sorted by index.
+    ///
+    /// They are obtained by [`CompiledSchema::compute_descendants`] once the schema has been compiled
+    pub fn descendants(&self, idx: &ShapeLabelIdx) -> &[ShapeLabelIdx] {
+        self.descendants
+            .get(idx)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Computes the descendants of every shape declaration from the `EXTENDS` declarations
+    pub fn compute_descendants(&mut self) {
+        let mut descendants: HashMap<ShapeLabelIdx, Vec<ShapeLabelIdx>> = HashMap::new();
+        for idx in self.shapes.keys() {
+            for ancestor in self.ancestors(idx) {
+                descendants.entry(ancestor).or_default().push(*idx)
+            }
+        }
+        for idxs in descendants.values_mut() {
+            idxs.sort()
+        }
+        self.descendants = descendants
+    }
+
+    /// Shapes extended by the shape declaration `idx`, directly or transitively
+    pub fn ancestors(&self, idx: &ShapeLabelIdx) -> Vec<ShapeLabelIdx> {
+        let mut ancestors = Vec::new();
+        let mut pending = self.parents(idx);
+        while let Some(parent) = pending.pop() {
+            if !ancestors.contains(&parent) {
+                pending.extend(self.parents(&parent));
+                ancestors.push(parent);
+            }
+        }
+        ancestors
+    }
+
+    /// Checks that no shape declaration extends itself
+    pub fn check_extends_cycles(&self) -> Result<()> {
+        let mut visited = HashSet::new();
+        let mut idxs: Vec<&ShapeLabelIdx> = self.shapes.keys().collect();
+        idxs.sort();
+        for idx in idxs {
+            self.check_extends_cycle_from(idx, &mut Vec::new(), &mut visited)?;
+        }
+        Ok(())
+    }
+
+    fn check_extends_cycle_from(
+        &self,
+        idx: &ShapeLabelIdx,
+        path: &mut Vec<ShapeLabelIdx>,
+        visited: &mut HashSet<ShapeLabelIdx>,
+    ) -> Result<()> {
+        if path.contains(idx) {
+            return match self.shapes.get(idx) {
+                Some((label, _se)) => Err(CompiledSchemaError::CyclicExtends {
+                    shape_label: label.clone(),
+                }),
+                None => Err(CompiledSchemaError::Internal {
+                    msg: format!("Unknown shape label index {idx} in EXTENDS"),
+                }),
+            };
+        }
+        if visited.contains(idx) {
+            return Ok(());
+        }
+        path.push(*idx);
+        for parent in self.parents(idx) {
+            self.check_extends_cycle_from(&parent, path, visited)?;
+        }
+        path.pop();
+        visited.insert(*idx);
+        Ok(())
+    }
+
     pub fn get_shape_expr(&self, shape_label: &ShapeLabel) -> Option<&ShapeExpr> {
         if let Some(idx) = self.find_shape_label_idx(shape_label) {
             self.shapes.get(idx).map(|(_label, se)| se)
@@ -55,7 +168,7 @@ impl CompiledSchema {
         Ok(())
     }
 
-    pub fn find_ref(&mut self, se_ref: &ShapeExprLabel) -> CResult<ShapeLabelIdx> {
+    pub fn find_ref(&self, se_ref: &ShapeExprLabel) -> CResult<ShapeLabelIdx> {
         let shape_label = match se_ref {
             ShapeExprLabel::IriRef { value } => match value {
                 IriRef::Iri(iri) => {
@@ -178,6 +291,7 @@ impl Display for CompiledSchema {
 mod tests {
     use super::CompiledSchema;
     use crate::ast::Schema as SchemaJson;
+    use crate::CompiledSchemaError;
 
     #[test]
     fn test_find_component() {
@@ -204,6 +318,33 @@ mod tests {
         //        let shape = compiled_schema.get
     }
 
+    #[test]
+    fn test_cyclic_extends() {
+        let str = r#"{
+            "@context": "http://www.w3.org/ns/shex.jsonld",
+            "type": "Schema",
+            "shapes": [
+                {
+                    "type": "ShapeDecl",
+                    "id": "http://a.example/S1",
+                    "shapeExpr": { "type": "Shape", "extends": ["http://a.example/S2"] }
+                },
+                {
+                    "type": "ShapeDecl",
+                    "id": "http://a.example/S2",
+                    "shapeExpr": { "type": "Shape", "extends": ["http://a.example/S1"] }
+                }
+            ]
+        }"#;
+        let schema_json: SchemaJson = serde_json::from_str::<SchemaJson>(str).unwrap();
+        let mut compiled_schema = CompiledSchema::new();
+        let result = compiled_schema.from_schema_json(&schema_json);
+        assert!(matches!(
+            result,
+            Err(CompiledSchemaError::CyclicExtends { .. })
+        ));
+    }
+
     /*#[test]
     fn validation_convert() {
         let str = r#"{
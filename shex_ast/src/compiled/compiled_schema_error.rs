@@ -113,6 +113,9 @@ pub enum CompiledSchemaError {
     #[error("Label not found: {shape_label}")]
     LabelNotFound { shape_label: ShapeLabel },
 
+    #[error("Shape {shape_label} extends itself through a cycle of EXTENDS declarations")]
+    CyclicExtends { shape_label: ShapeLabel },
+
     #[error("Internal: {msg}")]
     Internal { msg: String },
 }
@@ -1,7 +1,7 @@
 use crate::compiled::annotation::Annotation;
 use crate::compiled::compiled_schema::CompiledSchema;
 use crate::compiled::object_value::ObjectValue;
-use crate::compiled::sem_act::SemAct;
+use crate::compiled::sem_act::{SemAct, TripleSemActs};
 use crate::compiled::shape::Shape;
 use crate::compiled::shape_expr::ShapeExpr;
 use crate::compiled::shape_label::ShapeLabel;
@@ -47,8 +47,11 @@ impl SchemaJsonCompiler {
     ) -> CResult<()> {
         debug!("Compiling schema_json: {compiled_schema:?}");
         compiled_schema.set_prefixmap(schema_json.prefixmap());
+        compiled_schema.set_start_acts(Self::cnv_sem_acts(&schema_json.start_actions())?);
         self.collect_shape_labels(schema_json, compiled_schema)?;
         self.collect_shape_exprs(schema_json, compiled_schema)?;
+        compiled_schema.check_extends_cycles()?;
+        compiled_schema.compute_descendants();
         Ok(())
     }
 
@@ -62,7 +65,11 @@ impl SchemaJsonCompiler {
             Some(sds) => {
                 for sd in sds {
                     let label = self.shape_expr_label_to_shape_label(&sd.id)?;
-                    compiled_schema.add_shape(label, ShapeExpr::Empty);
+                    compiled_schema.add_shape(label.clone(), ShapeExpr::Empty);
+                    if sd.is_abstract {
+                        let idx = compiled_schema.get_shape_label_idx(&label)?;
+                        compiled_schema.set_abstract(&idx);
+                    }
                     self.shape_decls_counter += 1;
                 }
                 Ok(())
@@ -181,16 +188,30 @@ impl SchemaJsonCompiler {
             }
             ast::ShapeExpr::Shape(shape) => {
                 let new_extra = self.cnv_extra(&shape.extra)?;
+                let mut triple_sem_acts = Vec::new();
                 let rbe_table = match &shape.expression {
                     None => RbeTable::new(),
                     Some(tew) => {
                         let mut table = RbeTable::new();
-                        let rbe = self.triple_expr2rbe(&tew.te, compiled_schema, &mut table)?;
+                        let rbe = self.triple_expr2rbe(
+                            &tew.te,
+                            compiled_schema,
+                            &mut table,
+                            &mut triple_sem_acts,
+                        )?;
                         table.with_rbe(rbe);
                         table
                     }
                 };
                 let preds = Self::get_preds_shape(shape);
+                let mut sem_acts = Self::cnv_sem_acts(&shape.sem_acts)?;
+                if let Some(tew) = &shape.expression {
+                    Self::collect_group_sem_acts(&tew.te, &mut sem_acts)?;
+                }
+                let mut extends = Vec::new();
+                for sref in shape.extends.iter().flatten() {
+                    extends.push(self.ref2idx(sref, compiled_schema)?);
+                }
 
                 let display = match compiled_schema.find_shape_idx(idx) {
                     None => "internal".to_string(),
@@ -201,11 +222,13 @@ impl SchemaJsonCompiler {
                     Self::cnv_closed(&shape.closed),
                     new_extra,
                     rbe_table,
-                    Self::cnv_sem_acts(&shape.sem_acts),
+                    sem_acts,
                     Self::cnv_annotations(&shape.annotations),
                     preds,
                     display,
-                );
+                )
+                .with_extends(extends)
+                .with_triple_sem_acts(triple_sem_acts);
                 Ok(ShapeExpr::Shape(shape))
             }
             ast::ShapeExpr::NodeConstraint(nc) => {
@@ -268,12 +291,40 @@ impl SchemaJsonCompiler {
         }
     }
 
-    fn cnv_sem_acts(sem_acts: &Option<Vec<ast::SemAct>>) -> Vec<SemAct> {
-        if let Some(_vs) = sem_acts {
-            // TODO
-            Vec::new()
-        } else {
-            Vec::new()
+    fn cnv_sem_acts(sem_acts: &Option<Vec<ast::SemAct>>) -> CResult<Vec<SemAct>> {
+        let mut vs = Vec::new();
+        for sem_act in sem_acts.iter().flatten() {
+            let name = cnv_iri_ref(&sem_act.name())?;
+            vs.push(SemAct::new(name, sem_act.code()));
+        }
+        Ok(vs)
+    }
+
+    /// Collects the semantic actions of the `EachOf` and `OneOf` groups of a triple expression.
+    ///
+    /// The actions of triple constraints are attached to their components in [`Self::triple_expr2rbe`]
+    fn collect_group_sem_acts(
+        triple_expr: &ast::TripleExpr,
+        group_sem_acts: &mut Vec<SemAct>,
+    ) -> CResult<()> {
+        match triple_expr {
+            ast::TripleExpr::EachOf {
+                expressions,
+                sem_acts,
+                ..
+            }
+            | ast::TripleExpr::OneOf {
+                expressions,
+                sem_acts,
+                ..
+            } => {
+                group_sem_acts.extend(Self::cnv_sem_acts(sem_acts)?);
+                for e in expressions {
+                    Self::collect_group_sem_acts(&e.te, group_sem_acts)?;
+                }
+                Ok(())
+            }
+            ast::TripleExpr::TripleConstraint { .. } | ast::TripleExpr::TripleExprRef(_) => Ok(()),
         }
     }
 
@@ -291,6 +342,7 @@ impl SchemaJsonCompiler {
         triple_expr: &ast::TripleExpr,
         compiled_schema: &mut CompiledSchema,
         current_table: &mut RbeTable<Pred, Node, ShapeLabelIdx>,
+        triple_sem_acts: &mut Vec<TripleSemActs>,
     ) -> CResult<Rbe<Component>> {
         match triple_expr {
             ast::TripleExpr::EachOf {
@@ -303,7 +355,12 @@ impl SchemaJsonCompiler {
             } => {
                 let mut cs = Vec::new();
                 for e in expressions {
-                    let c = self.triple_expr2rbe(&e.te, compiled_schema, current_table)?;
+                    let c = self.triple_expr2rbe(
+                        &e.te,
+                        compiled_schema,
+                        current_table,
+                        triple_sem_acts,
+                    )?;
                     cs.push(c)
                 }
                 let card = self.cnv_min_max(min, max)?;
@@ -319,7 +376,12 @@ impl SchemaJsonCompiler {
             } => {
                 let mut cs = Vec::new();
                 for e in expressions {
-                    let c = self.triple_expr2rbe(&e.te, compiled_schema, current_table)?;
+                    let c = self.triple_expr2rbe(
+                        &e.te,
+                        compiled_schema,
+                        current_table,
+                        triple_sem_acts,
+                    )?;
                     cs.push(c)
                 }
                 let card = self.cnv_min_max(min, max)?;
@@ -333,7 +395,7 @@ impl SchemaJsonCompiler {
                 value_expr,
                 min,
                 max,
-                sem_acts: _,
+                sem_acts,
                 annotations: _,
             } => {
                 let min = self.cnv_min(min)?;
@@ -341,6 +403,10 @@ impl SchemaJsonCompiler {
                 let iri = Self::cnv_predicate(predicate)?;
                 let cond = self.value_expr2match_cond(value_expr, compiled_schema)?;
                 let c = current_table.add_component(iri, &cond);
+                let sem_acts = Self::cnv_sem_acts(sem_acts)?;
+                if !sem_acts.is_empty() {
+                    triple_sem_acts.push(TripleSemActs::new(c, sem_acts));
+                }
                 Ok(Rbe::symbol(c, min.value, max))
             }
             ast::TripleExpr::TripleExprRef(r) => Err(CompiledSchemaError::Todo {
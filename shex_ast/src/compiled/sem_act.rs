@@ -1,7 +1,49 @@
 use iri_s::IriS;
+use rbe::Component;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SemAct {
     name: IriS,
     code: Option<String>,
 }
+
+impl SemAct {
+    pub fn new(name: IriS, code: Option<String>) -> SemAct {
+        SemAct { name, code }
+    }
+
+    pub fn name(&self) -> &IriS {
+        &self.name
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+}
+
+/// Semantic actions attached to a triple constraint.
+///
+/// They are executed for the triples that a match of the neighbourhood assigns to `component`,
+/// the component of the triple constraint in the table of its shape.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TripleSemActs {
+    component: Component,
+    sem_acts: Vec<SemAct>,
+}
+
+impl TripleSemActs {
+    pub fn new(component: Component, sem_acts: Vec<SemAct>) -> TripleSemActs {
+        TripleSemActs {
+            component,
+            sem_acts,
+        }
+    }
+
+    pub fn component(&self) -> &Component {
+        &self.component
+    }
+
+    pub fn sem_acts(&self) -> &Vec<SemAct> {
+        &self.sem_acts
+    }
+}
@@ -4,7 +4,10 @@ use std::fmt::Display;
 
 use crate::{Node, Pred, ShapeLabelIdx};
 
-use super::{annotation::Annotation, sem_act::SemAct};
+use super::{
+    annotation::Annotation,
+    sem_act::{SemAct, TripleSemActs},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Shape {
@@ -14,6 +17,8 @@ pub struct Shape {
     sem_acts: Vec<SemAct>,
     annotations: Vec<Annotation>,
     preds: Vec<IriS>,
+    extends: Vec<ShapeLabelIdx>,
+    triple_sem_acts: Vec<TripleSemActs>,
     display: String,
 }
 
@@ -34,10 +39,42 @@ impl Shape {
             sem_acts,
             annotations,
             preds,
+            extends: Vec::new(),
+            triple_sem_acts: Vec::new(),
             display,
         }
     }
 
+    pub fn with_extends(mut self, extends: Vec<ShapeLabelIdx>) -> Self {
+        self.extends = extends;
+        self
+    }
+
+    pub fn with_triple_sem_acts(mut self, triple_sem_acts: Vec<TripleSemActs>) -> Self {
+        self.triple_sem_acts = triple_sem_acts;
+        self
+    }
+
+    /// Semantic actions of the shape and of the groups (`EachOf`, `OneOf`) in its triple expression
+    pub fn sem_acts(&self) -> &Vec<SemAct> {
+        &self.sem_acts
+    }
+
+    /// Semantic actions of the triple constraints in the triple expression of the shape
+    pub fn triple_sem_acts(&self) -> &Vec<TripleSemActs> {
+        &self.triple_sem_acts
+    }
+
+    /// Shapes that are extended by this shape (`EXTENDS` declarations)
+    pub fn extends(&self) -> &Vec<ShapeLabelIdx> {
+        &self.extends
+    }
+
+    /// Predicates declared with `EXTRA`, whose triples don't have to match the triple expression
+    pub fn extra(&self) -> &Vec<IriS> {
+        &self.extra
+    }
+
     pub fn preds(&self) -> Vec<IriS> {
         self.preds.clone()
     }
@@ -29,6 +29,15 @@ impl ShapeExpr {
     pub fn mk_ref(idx: ShapeLabelIdx) -> ShapeExpr {
         ShapeExpr::Ref { idx }
     }
+
+    /// Shapes extended by this shape expression, i.e. the `EXTENDS` of a shape or of the shapes in a conjunction
+    pub fn extends(&self) -> Vec<ShapeLabelIdx> {
+        match self {
+            ShapeExpr::Shape(shape) => shape.extends().clone(),
+            ShapeExpr::ShapeAnd { exprs, .. } => exprs.iter().flat_map(|e| e.extends()).collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl Display for ShapeExpr {
@@ -40,7 +49,7 @@ impl Display for ShapeExpr {
             ShapeExpr::NodeConstraint(nc) => write!(f, "{nc}"),
             ShapeExpr::Shape(shape) => write!(f, "{shape}"),
             ShapeExpr::External {} => write!(f, "External"),
-            ShapeExpr::Ref { .. } => todo!(),
+            ShapeExpr::Ref { idx } => write!(f, "@{idx}"),
             ShapeExpr::Empty => write!(f, "<Empty>"),
         }
     }
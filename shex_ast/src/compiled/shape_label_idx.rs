@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use rbe::Ref;
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
 pub struct ShapeLabelIdx(usize);
 
 impl Default for ShapeLabelIdx {
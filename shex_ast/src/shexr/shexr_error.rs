@@ -1,4 +1,5 @@
 use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMapError};
 use srdf::{literal::Literal, RDFParseError};
 use std::fmt::Display;
 use thiserror::Error;
@@ -24,6 +25,18 @@ pub enum ShExRError {
 
     #[error("Unexpected value for nodeKind: {iri}")]
     UnexpectedNodeKind { iri: IriS },
+
+    #[error("Resolving IRI {iri_ref}: {err}")]
+    IriRefError {
+        iri_ref: IriRef,
+        err: Box<PrefixMapError>,
+    },
+
+    #[error("The START label can't be represented in ShExR")]
+    StartLabel,
+
+    #[error("Error building ShExR triples: {err}")]
+    SRDFBuilderError { err: String },
 }
 
 #[derive(Debug)]
@@ -1,16 +1,24 @@
-use super::shexr_error::ShExRError;
+use super::shexr_error::{Nodes, ShExRError};
 use super::*;
 use crate::{
-    BNode, NodeConstraint, NodeKind, ObjectValue, Schema, Shape, ShapeDecl, ShapeExpr,
-    ShapeExprLabel, ValueSetValue,
+    Annotation, BNode, IriExclusion, IriOrStr, IriRefOrWildcard, LangOrWildcard, LanguageExclusion,
+    LiteralExclusion, NodeConstraint, NodeKind, ObjectValue, Pattern, Schema, SemAct, Shape,
+    ShapeDecl, ShapeExpr, ShapeExprLabel, StringFacet, StringOrWildcard, TripleExpr,
+    TripleExprLabel, ValueSetValue, XsFacet,
 };
 use iri_s::IriS;
 use prefixmap::IriRef;
-use srdf::rdf_parser;
+use rust_decimal::Decimal;
+use srdf::lang::Lang;
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
 use srdf::srdf_parser::*;
-use srdf::FocusRDF;
-use srdf::RDFParseError;
-use srdf::{Object, RDFParser};
+use srdf::{
+    FocusRDF, Object, RDFParseError, RDFParser, RDF_FIRST, RDF_NIL, RDF_REST, RDF_TYPE,
+    XSD_BOOLEAN_STR, XSD_DECIMAL_STR, XSD_DOUBLE_STR, XSD_INTEGER_STR,
+};
+use std::collections::HashSet;
+use std::str::FromStr;
 
 type Result<A> = std::result::Result<A, ShExRError>;
 
@@ -32,263 +40,651 @@ where
     }
 
     pub fn parse(&mut self) -> Result<Schema> {
-        let schema = Self::schema_parser()
-            .parse_impl(&mut self.rdf_parser.rdf)
-            .map_err(|e| ShExRError::RDFParseError { err: e })?;
+        let rdf = &mut self.rdf_parser.rdf;
+        let mut nodes = instances_of(&ShExRVocab::sx_schema()).parse_impl(rdf)?;
+        let node = match nodes.len() {
+            0 => return Err(ShExRError::NoSchemaNodes),
+            1 => nodes.pop().unwrap(),
+            _ => {
+                let nodes = nodes
+                    .iter()
+                    .map(|node| RDF::subject_as_object(node).into())
+                    .collect();
+                return Err(ShExRError::MoreThanOneSchema {
+                    nodes: Nodes::new(nodes),
+                });
+            }
+        };
+        let schema = parse_schema(rdf, &RDF::subject_as_term(&node))?;
         let prefixmap = self.rdf_parser.prefixmap();
         Ok(schema.with_prefixmap(prefixmap))
     }
 
     pub fn schema_parser() -> impl RDFNodeParse<RDF, Output = Schema> {
-        instance_of(&ShExRVocab::sx_schema())
-            .then(|node| set_focus_subject(node).then(|_| Self::schema()))
-    }
-
-    fn schema() -> impl RDFNodeParse<RDF, Output = Schema> {
-        property_value(&sx_shapes()).then(|ref node| {
-            set_focus(node)
-                .and(parse_rdf_list::<RDF, _>(Self::shape_decl()))
-                .map(|(_, vs)| Schema::new().with_shapes(Some(vs)))
+        instance_of(&ShExRVocab::sx_schema()).then(|node| {
+            apply_rdf(move |rdf: &mut RDF| parse_schema(rdf, &RDF::subject_as_term(&node)))
         })
     }
+}
 
-    fn term_to_shape_label(term: &RDF::Term) -> Result<ShapeExprLabel> {
-        let object = RDF::term_as_object(term);
-        match object {
-            Object::Iri(iri) => Ok(ShapeExprLabel::iri(iri)),
-            Object::BlankNode(bnode) => Ok(ShapeExprLabel::bnode(BNode::new(bnode.as_str()))),
-            Object::Literal(lit) => Err(ShExRError::ShapeExprLabelLiteral { lit }),
+/// Parses the node of type `sx:Schema`
+fn parse_schema<RDF>(rdf: &RDF, node: &RDF::Term) -> PResult<Schema>
+where
+    RDF: FocusRDF,
+{
+    let mut reader = ShExRReader {
+        rdf,
+        triple_exprs: HashSet::new(),
+    };
+    let mut schema = Schema::new();
+    if let Some(imports) = reader.object(node, SX_IMPORTS)? {
+        for import in reader.list(&imports)? {
+            let iri = reader.iri(&import)?;
+            schema = schema.with_import(IriOrStr::new(iri.as_str()));
         }
     }
-
-    fn shape_decl() -> impl RDFNodeParse<RDF, Output = ShapeDecl> {
-        (term().flat_map(move |ref t| {
-            let label = Self::term_to_shape_label(t).map_err(|e| RDFParseError::Custom {
-                msg: format!("Expected term to be a label: {t}: {e}"),
-            })?;
-            Ok(label)
-        }))
-        .and(Self::parse_shape_expr())
-        .map(|(label, se)| ShapeDecl::new(label, se, false))
+    if let Some(sem_acts) = reader.object(node, SX_STARTACTS)? {
+        schema = schema.with_start_actions(Some(reader.sem_acts(&sem_acts)?));
     }
-
-    fn parse_shape_expr() -> impl RDFNodeParse<RDF, Output = ShapeExpr> {
-        property_value(&sx_shape_expr()).then(|ref node| set_focus(node).then(|_| shape_expr()))
+    if let Some(start) = reader.object(node, SX_START)? {
+        schema = schema.with_start(Some(reader.shape_expr(&start)?));
+    }
+    // sx:shapes is multi-valued, but previous versions of ShExR represented it as an RDF list
+    let mut labels = reader.objects(node, SX_SHAPES)?;
+    if let [list] = labels.as_slice() {
+        if reader.is_list(list)? {
+            labels = reader.list(list)?;
+        }
     }
+    let shapes = labels
+        .iter()
+        .map(|label| reader.shape_decl(label))
+        .collect::<PResult<Vec<_>>>()?;
+    if !shapes.is_empty() {
+        schema = schema.with_shapes(Some(shapes));
+    }
+    Ok(schema)
 }
 
-fn shape_expr_<RDF>() -> impl RDFNodeParse<RDF, Output = ShapeExpr>
+struct ShExRReader<'a, RDF>
 where
     RDF: FocusRDF,
 {
-    // I would like the following code to work...but it doesn't yet...
-    /*parse_by_type(vec![
-     (sx_shape_and(), shape_and()),
-     . . .
-     (sx_node_constraint(), node_constraint())
-    ], default) */
-
-    shape_and().or(shape_or()).or(shape()).or(node_constraint())
-}
+    rdf: &'a RDF,
 
-fn shape<RDF>() -> impl RDFNodeParse<RDF, Output = ShapeExpr>
-where
-    RDF: FocusRDF,
-{
-    has_type(sx_shape()).with({
-        closed().then(|maybe_closed| {
-            let extra = None; // TODO
-            let expression = None; // TODO
-            ok(&ShapeExpr::shape(Shape::new(
-                maybe_closed,
-                extra,
-                expression,
-            )))
-        })
-    })
+    // Triple expressions that have already been parsed, which appear as references afterwards
+    triple_exprs: HashSet<RDF::Term>,
 }
 
-fn closed<RDF>() -> impl RDFNodeParse<RDF, Output = Option<bool>>
+impl<'a, RDF> ShExRReader<'a, RDF>
 where
     RDF: FocusRDF,
 {
-    optional(property_bool(&sx_closed()))
-}
+    fn shape_decl(&mut self, label: &RDF::Term) -> PResult<ShapeDecl> {
+        let id = self.shape_label(label)?;
+        let is_abstract = self.boolean(label, SX_ABSTRACT)?.unwrap_or(false);
+        let se = self.required(label, SX_SHAPE_EXPR)?;
+        let shape_expr = self.shape_expr(&se)?;
+        Ok(ShapeDecl::new(id, shape_expr, is_abstract))
+    }
 
-rdf_parser! {
-    pub fn shape_and[RDF]()(RDF) -> ShapeExpr where [] {
-        has_type(sx_shape_and()).with(
-        property_value(&sx_shape_exprs()).then(|ref node| {
-            set_focus(node).and(
-                   parse_rdf_list::<RDF, _>(shape_expr())
-                 ).map(|(_,vs)| { ShapeExpr::and(vs) })
-           }))
+    fn shape_expr(&mut self, term: &RDF::Term) -> PResult<ShapeExpr> {
+        // Shape expressions with a label are represented by their `sx:ShapeDecl` node
+        if RDF::term_is_iri(term) || self.has_type(term, SX_SHAPE_DECL)? {
+            return Ok(ShapeExpr::Ref(self.shape_label(term)?));
+        }
+        if self.has_type(term, SX_SHAPE_OR)? || self.has_type(term, SX_SHAPE_AND)? {
+            let list = self.required(term, SX_SHAPE_EXPRS)?;
+            let ses = self
+                .list(&list)?
+                .iter()
+                .map(|se| self.shape_expr(se))
+                .collect::<PResult<Vec<_>>>()?;
+            if self.has_type(term, SX_SHAPE_OR)? {
+                Ok(ShapeExpr::or(ses))
+            } else {
+                Ok(ShapeExpr::and(ses))
+            }
+        } else if self.has_type(term, SX_SHAPE_NOT)? {
+            let se = self.required(term, SX_SHAPE_EXPR)?;
+            Ok(ShapeExpr::shape_not(self.shape_expr(&se)?))
+        } else if self.has_type(term, SX_NODECONSTRAINT)? {
+            Ok(ShapeExpr::node_constraint(self.node_constraint(term)?))
+        } else if self.has_type(term, SX_SHAPE)? {
+            Ok(ShapeExpr::shape(self.shape(term)?))
+        } else if self.has_type(term, SX_SHAPE_EXTERNAL)? {
+            Ok(ShapeExpr::external())
+        } else {
+            Err(RDFParseError::Custom {
+                msg: format!("Node {term} is not a shape expression"),
+            })
+        }
     }
-}
 
-rdf_parser! {
-    pub fn shape_or[RDF]()(RDF) -> ShapeExpr where [] {
-        has_type(sx_shape_or()).with(
-        property_value(&sx_shape_exprs()).then(|ref node| {
-            set_focus(node).and(
-                   parse_rdf_list::<RDF, _>(shape_expr())
-                 ).map(|(_,vs)| { ShapeExpr::or(vs) })
-           }))
+    fn node_constraint(&mut self, term: &RDF::Term) -> PResult<NodeConstraint> {
+        let mut nc = NodeConstraint::new();
+        if let Some(node_kind) = self.object(term, SX_NODEKIND)? {
+            let node_kind = match self.iri(&node_kind)?.as_str() {
+                SX_IRI => NodeKind::Iri,
+                SX_BNODE => NodeKind::BNode,
+                SX_NONLITERAL => NodeKind::NonLiteral,
+                SX_LITERAL => NodeKind::Literal,
+                _ => {
+                    return Err(RDFParseError::Custom {
+                        msg: format!("Unexpected value for nodeKind: {node_kind}"),
+                    })
+                }
+            };
+            nc = nc.with_node_kind(node_kind);
+        }
+        if let Some(datatype) = self.object(term, SX_DATATYPE)? {
+            nc = nc.with_datatype(IriRef::iri(self.iri(&datatype)?));
+        }
+        // Facets are added in the same order as in ShExJ
+        let mut facets = Vec::new();
+        if let Some(pattern) = self.string(term, SX_PATTERN)? {
+            let pattern = match self.string(term, SX_FLAGS)? {
+                Some(flags) => Pattern::new_flags(&pattern, &flags),
+                None => Pattern::new(&pattern),
+            };
+            facets.push(XsFacet::StringFacet(StringFacet::Pattern(pattern)));
+        }
+        type IntegerFacet = fn(usize) -> XsFacet;
+        let length_facets: [(&str, IntegerFacet); 3] = [
+            (SX_LENGTH, XsFacet::length),
+            (SX_MINLENGTH, XsFacet::min_length),
+            (SX_MAXLENGTH, XsFacet::max_length),
+        ];
+        for (property, facet) in length_facets {
+            if let Some(n) = self.integer(term, property)? {
+                facets.push(facet(n as usize));
+            }
+        }
+        type NumericFacetFn = fn(NumericLiteral) -> XsFacet;
+        let numeric_facets: [(&str, NumericFacetFn); 4] = [
+            (SX_MININCLUSIVE, XsFacet::min_inclusive),
+            (SX_MAXINCLUSIVE, XsFacet::max_inclusive),
+            (SX_MINEXCLUSIVE, XsFacet::min_exclusive),
+            (SX_MAXEXCLUSIVE, XsFacet::max_exclusive),
+        ];
+        for (property, facet) in numeric_facets {
+            if let Some(value) = self.object(term, property)? {
+                match cnv_literal(RDF::term_as_object(&value)) {
+                    Object::Literal(Literal::NumericLiteral(n)) => facets.push(facet(n)),
+                    _ => {
+                        return Err(RDFParseError::Custom {
+                            msg: format!("Expected numeric literal for {property}: {value}"),
+                        })
+                    }
+                }
+            }
+        }
+        let digits_facets: [(&str, IntegerFacet); 2] = [
+            (SX_TOTALDIGITS, XsFacet::totaldigits),
+            (SX_FRACTIONDIGITS, XsFacet::fractiondigits),
+        ];
+        for (property, facet) in digits_facets {
+            if let Some(n) = self.integer(term, property)? {
+                facets.push(facet(n as usize));
+            }
+        }
+        nc = nc.with_xsfacets(facets);
+        if let Some(values) = self.object(term, SX_VALUES)? {
+            let values = self
+                .list(&values)?
+                .iter()
+                .map(|value| self.value_set_value(value))
+                .collect::<PResult<Vec<_>>>()?;
+            nc = nc.with_values(values);
+        }
+        Ok(nc)
     }
-}
 
-rdf_parser! {
-    pub fn shape_expr[RDF]()(RDF) -> ShapeExpr where [] {
-       shape_expr_()
+    fn shape(&mut self, term: &RDF::Term) -> PResult<Shape> {
+        let closed = self.boolean(term, SX_CLOSED)?;
+        let extra = self
+            .objects(term, SX_EXTRA)?
+            .iter()
+            .map(|extra| Ok(IriRef::iri(self.iri(extra)?)))
+            .collect::<PResult<Vec<_>>>()?;
+        let extends = self
+            .objects(term, SX_EXTENDS)?
+            .iter()
+            .map(|extend| self.shape_label(extend))
+            .collect::<PResult<Vec<_>>>()?;
+        let expression = match self.object(term, SX_EXPRESSION)? {
+            Some(te) => Some(self.triple_expr(&te)?),
+            None => None,
+        };
+        let shape = Shape::new(closed, none_if_empty(extra), expression)
+            .with_extends(none_if_empty(extends))
+            .with_sem_acts(self.optional_sem_acts(term)?)
+            .with_annotations(self.annotations(term)?);
+        Ok(shape)
     }
-}
 
-/*
-#[inline]
-fn sx_schema<RDF>() -> RDF::Term
-where
-    RDF: FocusRDF,
-{
-    RDFParser::<RDF>::term_iri_unchecked(SX_SCHEMA)
-}
-*/
+    fn triple_expr(&mut self, term: &RDF::Term) -> PResult<TripleExpr> {
+        // A triple expression that has already been parsed or that is declared elsewhere is a reference
+        if self.triple_exprs.contains(term) || self.types(term)?.is_empty() {
+            return Ok(TripleExpr::TripleExprRef(self.triple_expr_label(term)?));
+        }
+        self.triple_exprs.insert(term.clone());
+        let te = if self.has_type(term, SX_EACHOF)? || self.has_type(term, SX_ONEOF)? {
+            let list = self.required(term, SX_EXPRESSIONS)?;
+            let tes = self
+                .list(&list)?
+                .iter()
+                .map(|te| self.triple_expr(te))
+                .collect::<PResult<Vec<_>>>()?;
+            if self.has_type(term, SX_EACHOF)? {
+                TripleExpr::each_of(tes)
+            } else {
+                TripleExpr::one_of(tes)
+            }
+        } else if self.has_type(term, SX_TRIPLECONSTRAINT)? {
+            let predicate = self.required(term, SX_PREDICATE)?;
+            let value_expr = match self.object(term, SX_VALUEEXPR)? {
+                Some(se) => Some(self.shape_expr(&se)?),
+                None => None,
+            };
+            TripleExpr::triple_constraint(
+                self.boolean(term, SX_NEGATED)?,
+                self.boolean(term, SX_INVERSE)?,
+                IriRef::iri(self.iri(&predicate)?),
+                value_expr,
+                None,
+                None,
+            )
+        } else {
+            return Err(RDFParseError::Custom {
+                msg: format!("Node {term} is not a triple expression"),
+            });
+        };
+        let id = if RDF::term_is_iri(term) || self.is_shared(term)? {
+            Some(self.triple_expr_label(term)?)
+        } else {
+            None
+        };
+        let te = te
+            .with_id(id)
+            .with_min(self.integer(term, SX_MIN)?.map(|n| n as i32))
+            .with_max(self.max(term)?)
+            .with_sem_acts(self.optional_sem_acts(term)?)
+            .with_annotations(self.annotations(term)?);
+        Ok(te)
+    }
 
-#[inline]
-fn sx_shapes() -> IriS {
-    IriS::new_unchecked(SX_SHAPES)
-}
+    fn max(&self, term: &RDF::Term) -> PResult<Option<i32>> {
+        match self.object(term, SX_MAX)? {
+            Some(max) if RDF::term_is_iri(&max) && self.iri(&max)?.as_str() == SX_INF => {
+                Ok(Some(-1))
+            }
+            Some(max) => match RDF::term_as_integer(&max) {
+                Some(n) => Ok(Some(n as i32)),
+                None => Err(RDFParseError::Custom {
+                    msg: format!("Expected integer value for sx:max: {max}"),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
 
-fn sx_shape_expr() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_EXPR)
-}
+    fn optional_sem_acts(&self, term: &RDF::Term) -> PResult<Option<Vec<SemAct>>> {
+        match self.object(term, SX_SEMACTS)? {
+            Some(list) => Ok(Some(self.sem_acts(&list)?)),
+            None => Ok(None),
+        }
+    }
 
-fn sx_closed() -> IriS {
-    IriS::new_unchecked(SX_CLOSED)
-}
+    fn sem_acts(&self, list: &RDF::Term) -> PResult<Vec<SemAct>> {
+        self.list(list)?
+            .iter()
+            .map(|sem_act| {
+                let name = self.required(sem_act, SX_NAME)?;
+                let code = self.string(sem_act, SX_CODE)?;
+                Ok(SemAct::new(IriRef::iri(self.iri(&name)?), code))
+            })
+            .collect()
+    }
 
-#[inline]
-fn sx_values() -> IriS {
-    IriS::new_unchecked(SX_VALUES)
-}
+    fn annotations(&self, term: &RDF::Term) -> PResult<Option<Vec<Annotation>>> {
+        match self.object(term, SX_ANNOTATION_PROP)? {
+            Some(list) => {
+                let annotations = self
+                    .list(&list)?
+                    .iter()
+                    .map(|annotation| {
+                        let predicate = self.required(annotation, SX_PREDICATE)?;
+                        let object = self.required(annotation, SX_OBJECT)?;
+                        Ok(Annotation::new(
+                            IriRef::iri(self.iri(&predicate)?),
+                            object_value::<RDF>(&object)?,
+                        ))
+                    })
+                    .collect::<PResult<Vec<_>>>()?;
+                Ok(Some(annotations))
+            }
+            None => Ok(None),
+        }
+    }
 
-#[inline]
-fn sx_shape() -> IriS {
-    IriS::new_unchecked(SX_SHAPE)
-}
+    fn value_set_value(&self, term: &RDF::Term) -> PResult<ValueSetValue> {
+        if RDF::term_is_iri(term) || RDF::term_is_literal(term) {
+            return Ok(ValueSetValue::ObjectValue(object_value::<RDF>(term)?));
+        }
+        if self.has_type(term, SX_IRISTEM)? {
+            Ok(ValueSetValue::IriStem {
+                stem: self.iri_stem(term)?,
+            })
+        } else if self.has_type(term, SX_IRISTEMRANGE)? {
+            let stem = match self.stem(term)? {
+                None => IriRefOrWildcard::Wildcard,
+                Some(_) => IriRefOrWildcard::IriRef(self.iri_stem(term)?),
+            };
+            let exclusions = self.exclusions(term, |exclusion| {
+                if RDF::term_is_iri(exclusion) {
+                    Ok(IriExclusion::Iri(IriRef::iri(self.iri(exclusion)?)))
+                } else {
+                    Ok(IriExclusion::IriStem(self.iri_stem(exclusion)?))
+                }
+            })?;
+            Ok(ValueSetValue::IriStemRange { stem, exclusions })
+        } else if self.has_type(term, SX_LITERALSTEM)? {
+            Ok(ValueSetValue::LiteralStem {
+                stem: self.string_stem(term)?,
+            })
+        } else if self.has_type(term, SX_LITERALSTEMRANGE)? {
+            let stem = match self.stem(term)? {
+                None => StringOrWildcard::Wildcard,
+                Some(stem) => StringOrWildcard::String(stem),
+            };
+            let exclusions = self.exclusions(term, |exclusion| match string::<RDF>(exclusion) {
+                Some(str) => Ok(LiteralExclusion::Literal(str)),
+                None => Ok(LiteralExclusion::LiteralStem(self.string_stem(exclusion)?)),
+            })?;
+            Ok(ValueSetValue::LiteralStemRange { stem, exclusions })
+        } else if self.has_type(term, SX_LANGUAGE)? {
+            let language_tag =
+                self.string(term, SX_LANGUAGE_TAG)?
+                    .ok_or_else(|| RDFParseError::Custom {
+                        msg: format!("Language {term} without sx:languageTag"),
+                    })?;
+            Ok(ValueSetValue::Language {
+                language_tag: Lang::new(&language_tag),
+            })
+        } else if self.has_type(term, SX_LANGUAGESTEM)? {
+            Ok(ValueSetValue::LanguageStem {
+                stem: Lang::new(&self.string_stem(term)?),
+            })
+        } else if self.has_type(term, SX_LANGUAGESTEMRANGE)? {
+            let stem = match self.stem(term)? {
+                None => LangOrWildcard::Wildcard,
+                Some(stem) => LangOrWildcard::Lang(Lang::new(&stem)),
+            };
+            let exclusions = self.exclusions(term, |exclusion| match string::<RDF>(exclusion) {
+                Some(str) => Ok(LanguageExclusion::Language(Lang::new(&str))),
+                None => Ok(LanguageExclusion::LanguageStem(Lang::new(
+                    &self.string_stem(exclusion)?,
+                ))),
+            })?;
+            Ok(ValueSetValue::LanguageStemRange { stem, exclusions })
+        } else {
+            Err(RDFParseError::Custom {
+                msg: format!("Node {term} is not a value set value"),
+            })
+        }
+    }
 
-#[inline]
-fn sx_shape_exprs() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_EXPRS)
-}
+    /// Returns the value of `sx:stem` or `None` if it is a `sx:Wildcard`
+    fn stem(&self, term: &RDF::Term) -> PResult<Option<String>> {
+        let stem = self.required(term, SX_STEM)?;
+        if self.has_type(&stem, SX_WILDCARD)? {
+            Ok(None)
+        } else {
+            string::<RDF>(&stem)
+                .map(Some)
+                .ok_or_else(|| RDFParseError::Custom {
+                    msg: format!("Expected literal as sx:stem of {term}: {stem}"),
+                })
+        }
+    }
 
-#[inline]
-fn sx_node_kind() -> IriS {
-    IriS::new_unchecked(SX_NODEKIND)
-}
+    fn string_stem(&self, term: &RDF::Term) -> PResult<String> {
+        self.stem(term)?.ok_or_else(|| RDFParseError::Custom {
+            msg: format!("Unexpected wildcard as sx:stem of {term}"),
+        })
+    }
 
-fn sx_shape_and() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_AND)
-}
+    fn iri_stem(&self, term: &RDF::Term) -> PResult<IriRef> {
+        let stem = self.string_stem(term)?;
+        let iri = IriS::from_str(&stem).map_err(|e| RDFParseError::Custom {
+            msg: format!("Expected IRI as sx:stem of {term}: {e}"),
+        })?;
+        Ok(IriRef::iri(iri))
+    }
 
-fn sx_shape_or() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_OR)
-}
+    fn exclusions<E>(
+        &self,
+        term: &RDF::Term,
+        parse: impl Fn(&RDF::Term) -> PResult<E>,
+    ) -> PResult<Option<Vec<E>>> {
+        match self.object(term, SX_EXCLUSION)? {
+            Some(list) => {
+                let exclusions = self
+                    .list(&list)?
+                    .iter()
+                    .map(parse)
+                    .collect::<PResult<_>>()?;
+                Ok(Some(exclusions))
+            }
+            None => Ok(None),
+        }
+    }
 
-/*
-fn sx_node_constraint() -> IriS {
-    IriS::new_unchecked(SX_NODECONSTRAINT)
-}
-*/
-
-rdf_parser! {
- fn node_constraint[RDF]()(RDF) -> ShapeExpr
- where [] {
-    parse_nodekind().then(|maybe_nodekind|
-        parse_value_set().then(move |maybe_valueset| {
-           let mut nc = NodeConstraint::new();
-           if let Some(nk) = &maybe_nodekind {
-              nc = nc.with_node_kind(nk.clone());
-           }
-           if let Some(vs) = &maybe_valueset {
-              nc = nc.with_values(vs.clone())
-           }
-           ok(&ShapeExpr::node_constraint(nc))
-         }
-        )
-    )
-  }
-}
+    fn shape_label(&self, term: &RDF::Term) -> PResult<ShapeExprLabel> {
+        match RDF::term_as_object(term) {
+            Object::Iri(iri) => Ok(ShapeExprLabel::iri(iri)),
+            Object::BlankNode(bnode) => Ok(ShapeExprLabel::bnode(BNode::new(bnode.as_str()))),
+            Object::Literal(lit) => Err(RDFParseError::Custom {
+                msg: format!("{}", ShExRError::ShapeExprLabelLiteral { lit }),
+            }),
+        }
+    }
 
-fn parse_nodekind<RDF>() -> impl RDFNodeParse<RDF, Output = Option<NodeKind>>
-where
-    RDF: FocusRDF,
-{
-    optional(
-        property_value(&sx_node_kind())
-            .then(|ref node| set_focus(node).and(nodekind()).map(|(_, vs)| vs)),
-    )
-}
+    fn triple_expr_label(&self, term: &RDF::Term) -> PResult<TripleExprLabel> {
+        match RDF::term_as_object(term) {
+            Object::Iri(iri) => Ok(TripleExprLabel::IriRef {
+                value: IriRef::iri(iri),
+            }),
+            Object::BlankNode(bnode) => Ok(TripleExprLabel::BNode {
+                value: BNode::new(bnode.as_str()),
+            }),
+            Object::Literal(lit) => Err(RDFParseError::Custom {
+                msg: format!("Triple expression label can not be a literal {lit}"),
+            }),
+        }
+    }
 
-fn nodekind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    iri_kind()
-        .or(literal_kind())
-        .or(bnode_kind())
-        .or(nonliteral_kind())
-}
+    /// Checks if a node is referenced more than once, which requires a label
+    fn is_shared(&self, term: &RDF::Term) -> PResult<bool> {
+        let arcs = self
+            .rdf
+            .incoming_arcs(term)
+            .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })?;
+        Ok(arcs.values().map(|subjects| subjects.len()).sum::<usize>() > 1)
+    }
 
-fn iri_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_iri()).map(|_| NodeKind::Iri)
-}
+    fn is_list(&self, term: &RDF::Term) -> PResult<bool> {
+        Ok(*term == RDF::iri_s2term(&RDF_NIL) || !self.objects_iri(term, &RDF_FIRST)?.is_empty())
+    }
 
-fn literal_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_literal()).map(|_| NodeKind::Literal)
-}
+    fn list(&self, term: &RDF::Term) -> PResult<Vec<RDF::Term>> {
+        let nil = RDF::iri_s2term(&RDF_NIL);
+        let mut members = Vec::new();
+        let mut current = term.clone();
+        while current != nil {
+            let first = self.objects_iri(&current, &RDF_FIRST)?.pop();
+            let rest = self.objects_iri(&current, &RDF_REST)?.pop();
+            match (first, rest) {
+                (Some(first), Some(rest)) => {
+                    members.push(first);
+                    current = rest;
+                }
+                _ => {
+                    return Err(RDFParseError::Custom {
+                        msg: format!("Node {current} is not a well formed RDF list"),
+                    })
+                }
+            }
+        }
+        Ok(members)
+    }
 
-fn bnode_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_bnode()).map(|_| NodeKind::BNode)
+    fn has_type(&self, term: &RDF::Term, type_: &str) -> PResult<bool> {
+        Ok(self
+            .types(term)?
+            .contains(&RDF::iri_s2term(&IriS::new_unchecked(type_))))
+    }
+
+    fn types(&self, term: &RDF::Term) -> PResult<Vec<RDF::Term>> {
+        self.objects_iri(term, &RDF_TYPE)
+    }
+
+    fn iri(&self, term: &RDF::Term) -> PResult<IriS> {
+        match RDF::term_as_object(term) {
+            Object::Iri(iri) => Ok(iri),
+            _ => Err(RDFParseError::Custom {
+                msg: format!("Expected IRI, found {term}"),
+            }),
+        }
+    }
+
+    fn boolean(&self, term: &RDF::Term, property: &str) -> PResult<Option<bool>> {
+        match self.object(term, property)? {
+            Some(value) => match RDF::term_as_boolean(&value) {
+                Some(b) => Ok(Some(b)),
+                None => Err(RDFParseError::Custom {
+                    msg: format!("Expected boolean value for {property}: {value}"),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn integer(&self, term: &RDF::Term, property: &str) -> PResult<Option<isize>> {
+        match self.object(term, property)? {
+            Some(value) => match RDF::term_as_integer(&value) {
+                Some(n) => Ok(Some(n)),
+                None => Err(RDFParseError::Custom {
+                    msg: format!("Expected integer value for {property}: {value}"),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn string(&self, term: &RDF::Term, property: &str) -> PResult<Option<String>> {
+        match self.object(term, property)? {
+            Some(value) => match string::<RDF>(&value) {
+                Some(str) => Ok(Some(str)),
+                None => Err(RDFParseError::Custom {
+                    msg: format!("Expected string value for {property}: {value}"),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn required(&self, term: &RDF::Term, property: &str) -> PResult<RDF::Term> {
+        self.object(term, property)?
+            .ok_or_else(|| RDFParseError::Custom {
+                msg: format!("Node {term} has no value for {property}"),
+            })
+    }
+
+    fn object(&self, term: &RDF::Term, property: &str) -> PResult<Option<RDF::Term>> {
+        let mut values = self.objects(term, property)?;
+        match values.len() {
+            0 | 1 => Ok(values.pop()),
+            _ => Err(RDFParseError::Custom {
+                msg: format!("Node {term} has more than one value for {property}"),
+            }),
+        }
+    }
+
+    /// Values of a multi-valued property, sorted to obtain a deterministic order
+    fn objects(&self, term: &RDF::Term, property: &str) -> PResult<Vec<RDF::Term>> {
+        let mut values = self.objects_iri(term, &IriS::new_unchecked(property))?;
+        values.sort_by_key(|value| value.to_string());
+        Ok(values)
+    }
+
+    fn objects_iri(&self, term: &RDF::Term, property: &IriS) -> PResult<Vec<RDF::Term>> {
+        if RDF::term_is_literal(term) {
+            return Ok(Vec::new());
+        }
+        let subject = self.subject(term)?;
+        self.rdf
+            .objects_for_subject_predicate(&subject, &RDF::iri_s2iri(property))
+            .map(|objects| objects.into_iter().collect())
+            .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })
+    }
+
+    fn subject(&self, term: &RDF::Term) -> PResult<RDF::Subject> {
+        RDF::term_as_subject(term).ok_or_else(|| RDFParseError::ExpectedSubject {
+            node: term.to_string(),
+        })
+    }
 }
 
-fn nonliteral_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
+fn object_value<RDF>(term: &RDF::Term) -> PResult<ObjectValue>
 where
     RDF: FocusRDF,
 {
-    is_iri(ShExRVocab::sx_nonliteral()).map(|_| NodeKind::NonLiteral)
+    match cnv_literal(RDF::term_as_object(term)) {
+        Object::Iri(iri) => Ok(ObjectValue::iri(iri)),
+        Object::Literal(lit) => Ok(ObjectValue::Literal(lit)),
+        Object::BlankNode(_) => Err(RDFParseError::Custom {
+            msg: format!("Expected IRI or literal, found {term}"),
+        }),
+    }
 }
 
-fn parse_value_set<RDF>() -> impl RDFNodeParse<RDF, Output = Option<Vec<ValueSetValue>>>
+fn string<RDF>(term: &RDF::Term) -> Option<String>
 where
     RDF: FocusRDF,
 {
-    optional(property_value(&sx_values()).then(|ref node| {
-        set_focus(node)
-            .and(parse_rdf_list::<RDF, _>(parse_value()))
-            .map(|(_, vs)| vs)
-    }))
+    match RDF::term_as_object(term) {
+        Object::Literal(lit) => Some(lit.lexical_form()),
+        _ => None,
+    }
 }
 
-fn parse_value<RDF>() -> impl RDFNodeParse<RDF, Output = ValueSetValue>
-where
-    RDF: FocusRDF,
-{
-    //firstOf(objectValue, )
-    object_value().map(ValueSetValue::ObjectValue)
+/// Converts literals with a boolean or numeric datatype to the representation used by ShExJ
+fn cnv_literal(object: Object) -> Object {
+    if let Object::Literal(Literal::DatatypeLiteral {
+        lexical_form,
+        datatype: IriRef::Iri(datatype),
+    }) = &object
+    {
+        let literal = match datatype.as_str() {
+            XSD_BOOLEAN_STR => bool::from_str(lexical_form).ok().map(Literal::boolean),
+            XSD_INTEGER_STR => isize::from_str(lexical_form).ok().map(Literal::integer),
+            XSD_DECIMAL_STR => Decimal::from_str(lexical_form).ok().map(Literal::decimal),
+            XSD_DOUBLE_STR => f64::from_str(lexical_form).ok().map(Literal::double),
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            return Object::Literal(literal);
+        }
+    }
+    object
 }
 
-fn object_value<RDF>() -> impl RDFNodeParse<RDF, Output = ObjectValue>
-where
-    RDF: FocusRDF,
-{
-    iri().map(|ref iri| ObjectValue::IriRef(IriRef::Iri(iri.clone())))
+fn none_if_empty<A>(values: Vec<A>) -> Option<Vec<A>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
 }
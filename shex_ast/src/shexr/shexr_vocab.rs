@@ -8,6 +8,7 @@ pub const SX_ANNOTATION: &str = concatcp!(SX, "Annotation");
 pub const SX_EACHOF: &str = concatcp!(SX, "EachOf");
 pub const SX_IRISTEM: &str = concatcp!(SX, "IriStem");
 pub const SX_IRISTEMRANGE: &str = concatcp!(SX, "IriStemRange");
+pub const SX_LANGUAGE: &str = concatcp!(SX, "Language");
 pub const SX_LANGUAGESTEM: &str = concatcp!(SX, "LanguageStem");
 pub const SX_LANGUAGESTEMRANGE: &str = concatcp!(SX, "LanguageStemRange");
 pub const SX_LITERALSTEM: &str = concatcp!(SX, "LiteralStem");
@@ -35,12 +36,15 @@ pub const SX_CLOSED: &str = concatcp!(SX, "closed");
 pub const SX_DATATYPE: &str = concatcp!(SX, "datatype");
 pub const SX_EXTRA: &str = concatcp!(SX, "extra");
 pub const SX_EXCLUSION: &str = concatcp!(SX, "exclusion");
-pub const SX_EXPRESION: &str = concatcp!(SX, "expresion");
-pub const SX_EXPRESIONS: &str = concatcp!(SX, "expresions");
+pub const SX_EXPRESSION: &str = concatcp!(SX, "expression");
+pub const SX_EXPRESSIONS: &str = concatcp!(SX, "expressions");
+pub const SX_EXTENDS: &str = concatcp!(SX, "extends");
 pub const SX_FRACTIONDIGITS: &str = concatcp!(SX, "fractiondigits");
 pub const SX_FLAGS: &str = concatcp!(SX, "flags");
 pub const SX_IRI: &str = concatcp!(SX, "iri");
+pub const SX_IMPORTS: &str = concatcp!(SX, "imports");
 pub const SX_INVERSE: &str = concatcp!(SX, "inverse");
+pub const SX_LANGUAGE_TAG: &str = concatcp!(SX, "languageTag");
 pub const SX_LENGTH: &str = concatcp!(SX, "length");
 pub const SX_LITERAL: &str = concatcp!(SX, "literal");
 pub const SX_MIN: &str = concatcp!(SX, "min");
@@ -54,7 +58,7 @@ pub const SX_MAXLENGTH: &str = concatcp!(SX, "maxlength");
 pub const SX_NAME: &str = concatcp!(SX, "name");
 pub const SX_NEGATED: &str = concatcp!(SX, "negated");
 pub const SX_NODEKIND: &str = concatcp!(SX, "nodeKind");
-pub const SX_NONLITERAL: &str = concatcp!(SX, "nonLiteral");
+pub const SX_NONLITERAL: &str = concatcp!(SX, "nonliteral");
 pub const SX_OBJECT: &str = concatcp!(SX, "object");
 pub const SX_PATTERN: &str = concatcp!(SX, "pattern");
 pub const SX_PREDICATE: &str = concatcp!(SX, "predicate");
@@ -105,7 +109,7 @@ impl ShExRVocab {
 
     #[inline]
     pub fn sx_shape_exprs() -> IriS {
-        IriS::new_unchecked(SX_SHAPE_EXPR)
+        IriS::new_unchecked(SX_SHAPE_EXPRS)
     }
 
     #[inline]
@@ -0,0 +1,677 @@
+use super::shexr_error::ShExRError;
+use super::*;
+use crate::{
+    Annotation, IriExclusion, IriOrStr, IriRefOrWildcard, LangOrWildcard, LanguageExclusion,
+    LiteralExclusion, NodeConstraint, NodeKind, NumericFacet, ObjectValue, Schema, SemAct, Shape,
+    ShapeDecl, ShapeExpr, ShapeExprLabel, StringFacet, StringOrWildcard, TripleExpr,
+    TripleExprLabel, ValueSetValue, XsFacet,
+};
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
+use srdf::{Object, RDFFormat, SRDFBuilder, RDF, RDF_TYPE_STR, XSD};
+use std::io::Write;
+use std::str::FromStr;
+
+type Result<A> = std::result::Result<A, ShExRError>;
+
+/// Serializes ShEx schemas as RDF following the [ShExR](https://shex.io/shex-semantics/#shexr) representation
+pub struct ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    rdf: RDF,
+    prefixmap: PrefixMap,
+}
+
+impl<RDF> ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    pub fn new() -> Self {
+        Self {
+            rdf: RDF::empty(),
+            prefixmap: PrefixMap::new(),
+        }
+    }
+
+    pub fn write(&mut self, schema: &Schema) -> Result<()> {
+        self.prefixmap = schema.prefixmap().unwrap_or_default();
+        let mut prefix_map = self.prefixmap.clone();
+        let _ = prefix_map.insert("sx", &IriS::from_str(SX).unwrap());
+        let _ = prefix_map.insert("rdf", &IriS::from_str(RDF).unwrap());
+        let _ = prefix_map.insert("xsd", &IriS::from_str(XSD).unwrap());
+        self.rdf.add_prefix_map(prefix_map).map_err(cnv_err)?;
+        self.rdf.add_base(&schema.base()).map_err(cnv_err)?;
+
+        let node = self.node_with_type(SX_SCHEMA)?;
+        let imports = schema.imports();
+        if !imports.is_empty() {
+            let values: Vec<_> = imports.iter().map(import_term::<RDF>).collect();
+            let list = self.list(&values)?;
+            self.add(&node, SX_IMPORTS, &list)?;
+        }
+        if let Some(sem_acts) = schema.start_actions() {
+            let list = self.sem_acts(&sem_acts)?;
+            self.add(&node, SX_STARTACTS, &list)?;
+        }
+        if let Some(start) = schema.start() {
+            let start = self.shape_expr(&start)?;
+            self.add(&node, SX_START, &start)?;
+        }
+        for decl in schema.shapes().unwrap_or_default() {
+            let label = self.shape_decl(&decl)?;
+            self.add(&node, SX_SHAPES, &label)?;
+        }
+        Ok(())
+    }
+
+    pub fn serialize<W: Write>(&self, format: RDFFormat, writer: &mut W) -> Result<()> {
+        self.rdf.serialize(format, writer).map_err(cnv_err)
+    }
+
+    /// Returns the RDF graph that contains the triples that have been written
+    pub fn into_rdf(self) -> RDF {
+        self.rdf
+    }
+
+    fn shape_decl(&mut self, decl: &ShapeDecl) -> Result<RDF::Term> {
+        let label = self.shape_label(&decl.id)?;
+        self.add_type(&label, SX_SHAPE_DECL)?;
+        if decl.is_abstract {
+            self.add(&label, SX_ABSTRACT, &boolean::<RDF>(true))?;
+        }
+        let se = self.shape_expr(&decl.shape_expr)?;
+        self.add(&label, SX_SHAPE_EXPR, &se)?;
+        Ok(label)
+    }
+
+    fn shape_expr(&mut self, se: &ShapeExpr) -> Result<RDF::Term> {
+        match se {
+            ShapeExpr::Ref(label) => self.shape_label(label),
+            ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => {
+                let type_ = if matches!(se, ShapeExpr::ShapeOr { .. }) {
+                    SX_SHAPE_OR
+                } else {
+                    SX_SHAPE_AND
+                };
+                let node = self.node_with_type(type_)?;
+                let values = shape_exprs
+                    .iter()
+                    .map(|sew| self.shape_expr(&sew.se))
+                    .collect::<Result<Vec<_>>>()?;
+                let list = self.list(&values)?;
+                self.add(&node, SX_SHAPE_EXPRS, &list)?;
+                Ok(node)
+            }
+            ShapeExpr::ShapeNot { shape_expr } => {
+                let node = self.node_with_type(SX_SHAPE_NOT)?;
+                let value = self.shape_expr(&shape_expr.se)?;
+                self.add(&node, SX_SHAPE_EXPR, &value)?;
+                Ok(node)
+            }
+            ShapeExpr::NodeConstraint(nc) => self.node_constraint(nc),
+            ShapeExpr::Shape(shape) => self.shape(shape),
+            ShapeExpr::External => self.node_with_type(SX_SHAPE_EXTERNAL),
+        }
+    }
+
+    fn node_constraint(&mut self, nc: &NodeConstraint) -> Result<RDF::Term> {
+        let node = self.node_with_type(SX_NODECONSTRAINT)?;
+        if let Some(node_kind) = nc.node_kind() {
+            let kind = match node_kind {
+                NodeKind::Iri => SX_IRI,
+                NodeKind::BNode => SX_BNODE,
+                NodeKind::NonLiteral => SX_NONLITERAL,
+                NodeKind::Literal => SX_LITERAL,
+            };
+            self.add(&node, SX_NODEKIND, &iri_term::<RDF>(kind))?;
+        }
+        if let Some(datatype) = nc.datatype() {
+            let datatype = self.iri(&datatype)?;
+            self.add(&node, SX_DATATYPE, &RDF::iri_s2term(&datatype))?;
+        }
+        for facet in nc.xs_facet().unwrap_or_default() {
+            let (property, value) = match facet {
+                XsFacet::StringFacet(StringFacet::Length(n)) => (SX_LENGTH, integer::<RDF>(n)),
+                XsFacet::StringFacet(StringFacet::MinLength(n)) => {
+                    (SX_MINLENGTH, integer::<RDF>(n))
+                }
+                XsFacet::StringFacet(StringFacet::MaxLength(n)) => {
+                    (SX_MAXLENGTH, integer::<RDF>(n))
+                }
+                XsFacet::StringFacet(StringFacet::Pattern(pattern)) => {
+                    if let Some(flags) = &pattern.flags {
+                        self.add(&node, SX_FLAGS, &string::<RDF>(flags))?;
+                    }
+                    (SX_PATTERN, string::<RDF>(&pattern.str))
+                }
+                XsFacet::NumericFacet(NumericFacet::MinInclusive(n)) => {
+                    (SX_MININCLUSIVE, numeric::<RDF>(n))
+                }
+                XsFacet::NumericFacet(NumericFacet::MinExclusive(n)) => {
+                    (SX_MINEXCLUSIVE, numeric::<RDF>(n))
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxInclusive(n)) => {
+                    (SX_MAXINCLUSIVE, numeric::<RDF>(n))
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxExclusive(n)) => {
+                    (SX_MAXEXCLUSIVE, numeric::<RDF>(n))
+                }
+                XsFacet::NumericFacet(NumericFacet::TotalDigits(n)) => {
+                    (SX_TOTALDIGITS, integer::<RDF>(n))
+                }
+                XsFacet::NumericFacet(NumericFacet::FractionDigits(n)) => {
+                    (SX_FRACTIONDIGITS, integer::<RDF>(n))
+                }
+            };
+            self.add(&node, property, &value)?;
+        }
+        if let Some(values) = nc.values() {
+            let values = values
+                .iter()
+                .map(|value| self.value_set_value(value))
+                .collect::<Result<Vec<_>>>()?;
+            let list = self.list(&values)?;
+            self.add(&node, SX_VALUES, &list)?;
+        }
+        Ok(node)
+    }
+
+    fn shape(&mut self, shape: &Shape) -> Result<RDF::Term> {
+        let node = self.node_with_type(SX_SHAPE)?;
+        if let Some(closed) = shape.closed {
+            self.add(&node, SX_CLOSED, &boolean::<RDF>(closed))?;
+        }
+        for extra in shape.extra.iter().flatten() {
+            let extra = self.iri(extra)?;
+            self.add(&node, SX_EXTRA, &RDF::iri_s2term(&extra))?;
+        }
+        for extend in shape.extends.iter().flatten() {
+            let label = self.shape_label(extend)?;
+            self.add(&node, SX_EXTENDS, &label)?;
+        }
+        if let Some(expression) = &shape.expression {
+            let te = self.triple_expr(&expression.te)?;
+            self.add(&node, SX_EXPRESSION, &te)?;
+        }
+        self.sem_acts_annotations(&node, &shape.sem_acts, &shape.annotations)?;
+        Ok(node)
+    }
+
+    fn triple_expr(&mut self, te: &TripleExpr) -> Result<RDF::Term> {
+        match te {
+            TripleExpr::EachOf {
+                id,
+                expressions,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            }
+            | TripleExpr::OneOf {
+                id,
+                expressions,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            } => {
+                let node = self.triple_expr_node(id)?;
+                let type_ = if matches!(te, TripleExpr::EachOf { .. }) {
+                    SX_EACHOF
+                } else {
+                    SX_ONEOF
+                };
+                self.add_type(&node, type_)?;
+                let values = expressions
+                    .iter()
+                    .map(|tew| self.triple_expr(&tew.te))
+                    .collect::<Result<Vec<_>>>()?;
+                let list = self.list(&values)?;
+                self.add(&node, SX_EXPRESSIONS, &list)?;
+                self.cardinality(&node, min, max)?;
+                self.sem_acts_annotations(&node, sem_acts, annotations)?;
+                Ok(node)
+            }
+            TripleExpr::TripleConstraint {
+                id,
+                negated,
+                inverse,
+                predicate,
+                value_expr,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            } => {
+                let node = self.triple_expr_node(id)?;
+                self.add_type(&node, SX_TRIPLECONSTRAINT)?;
+                if let Some(negated) = negated {
+                    self.add(&node, SX_NEGATED, &boolean::<RDF>(*negated))?;
+                }
+                if let Some(inverse) = inverse {
+                    self.add(&node, SX_INVERSE, &boolean::<RDF>(*inverse))?;
+                }
+                let predicate = self.iri(predicate)?;
+                self.add(&node, SX_PREDICATE, &RDF::iri_s2term(&predicate))?;
+                if let Some(value_expr) = value_expr {
+                    let value = self.shape_expr(value_expr)?;
+                    self.add(&node, SX_VALUEEXPR, &value)?;
+                }
+                self.cardinality(&node, min, max)?;
+                self.sem_acts_annotations(&node, sem_acts, annotations)?;
+                Ok(node)
+            }
+            TripleExpr::TripleExprRef(label) => self.triple_expr_label(label),
+        }
+    }
+
+    fn triple_expr_node(&mut self, id: &Option<TripleExprLabel>) -> Result<RDF::Term> {
+        match id {
+            Some(label) => self.triple_expr_label(label),
+            None => self.bnode(),
+        }
+    }
+
+    fn cardinality(
+        &mut self,
+        node: &RDF::Term,
+        min: &Option<i32>,
+        max: &Option<i32>,
+    ) -> Result<()> {
+        if let Some(min) = min {
+            self.add(node, SX_MIN, &integer::<RDF>(*min as usize))?;
+        }
+        if let Some(max) = max {
+            let max = RDF::object_as_term(&Object::Literal(Literal::integer(*max as isize)));
+            self.add(node, SX_MAX, &max)?;
+        }
+        Ok(())
+    }
+
+    fn sem_acts_annotations(
+        &mut self,
+        node: &RDF::Term,
+        sem_acts: &Option<Vec<SemAct>>,
+        annotations: &Option<Vec<Annotation>>,
+    ) -> Result<()> {
+        if let Some(sem_acts) = sem_acts {
+            let list = self.sem_acts(sem_acts)?;
+            self.add(node, SX_SEMACTS, &list)?;
+        }
+        if let Some(annotations) = annotations {
+            let values = annotations
+                .iter()
+                .map(|annotation| {
+                    let node = self.node_with_type(SX_ANNOTATION)?;
+                    let predicate = self.iri(&annotation.predicate())?;
+                    self.add(&node, SX_PREDICATE, &RDF::iri_s2term(&predicate))?;
+                    let object = self.object_value(&annotation.object())?;
+                    self.add(&node, SX_OBJECT, &object)?;
+                    Ok(node)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let list = self.list(&values)?;
+            self.add(node, SX_ANNOTATION_PROP, &list)?;
+        }
+        Ok(())
+    }
+
+    fn sem_acts(&mut self, sem_acts: &[SemAct]) -> Result<RDF::Term> {
+        let values = sem_acts
+            .iter()
+            .map(|sem_act| {
+                let node = self.node_with_type(SX_SEMACT)?;
+                let name = self.iri(&sem_act.name())?;
+                self.add(&node, SX_NAME, &RDF::iri_s2term(&name))?;
+                if let Some(code) = sem_act.code() {
+                    self.add(&node, SX_CODE, &string::<RDF>(&code))?;
+                }
+                Ok(node)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.list(&values)
+    }
+
+    fn value_set_value(&mut self, value: &ValueSetValue) -> Result<RDF::Term> {
+        match value {
+            ValueSetValue::ObjectValue(ov) => self.object_value(ov),
+            ValueSetValue::IriStem { stem } => self.iri_stem(stem),
+            ValueSetValue::IriStemRange { stem, exclusions } => {
+                let node = self.node_with_type(SX_IRISTEMRANGE)?;
+                let stem = match stem {
+                    IriRefOrWildcard::IriRef(iri) => any_uri::<RDF>(&self.iri(iri)?),
+                    IriRefOrWildcard::Wildcard => self.node_with_type(SX_WILDCARD)?,
+                };
+                self.add(&node, SX_STEM, &stem)?;
+                if let Some(exclusions) = exclusions {
+                    let values = exclusions
+                        .iter()
+                        .map(|exclusion| match exclusion {
+                            IriExclusion::Iri(iri) => Ok(RDF::iri_s2term(&self.iri(iri)?)),
+                            IriExclusion::IriStem(stem) => self.iri_stem(stem),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let list = self.list(&values)?;
+                    self.add(&node, SX_EXCLUSION, &list)?;
+                }
+                Ok(node)
+            }
+            ValueSetValue::LiteralStem { stem } => self.stem(SX_LITERALSTEM, stem),
+            ValueSetValue::LiteralStemRange { stem, exclusions } => {
+                let node = self.node_with_type(SX_LITERALSTEMRANGE)?;
+                let stem = match stem {
+                    StringOrWildcard::String(str) => string::<RDF>(str),
+                    StringOrWildcard::Wildcard => self.node_with_type(SX_WILDCARD)?,
+                };
+                self.add(&node, SX_STEM, &stem)?;
+                if let Some(exclusions) = exclusions {
+                    let values = exclusions
+                        .iter()
+                        .map(|exclusion| match exclusion {
+                            LiteralExclusion::Literal(str) => Ok(string::<RDF>(str)),
+                            LiteralExclusion::LiteralStem(stem) => self.stem(SX_LITERALSTEM, stem),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let list = self.list(&values)?;
+                    self.add(&node, SX_EXCLUSION, &list)?;
+                }
+                Ok(node)
+            }
+            ValueSetValue::Language { language_tag } => {
+                let node = self.node_with_type(SX_LANGUAGE)?;
+                self.add(
+                    &node,
+                    SX_LANGUAGE_TAG,
+                    &string::<RDF>(&language_tag.value()),
+                )?;
+                Ok(node)
+            }
+            ValueSetValue::LanguageStem { stem } => self.stem(SX_LANGUAGESTEM, &stem.value()),
+            ValueSetValue::LanguageStemRange { stem, exclusions } => {
+                let node = self.node_with_type(SX_LANGUAGESTEMRANGE)?;
+                let stem = match stem {
+                    LangOrWildcard::Lang(lang) => string::<RDF>(&lang.value()),
+                    LangOrWildcard::Wildcard => self.node_with_type(SX_WILDCARD)?,
+                };
+                self.add(&node, SX_STEM, &stem)?;
+                if let Some(exclusions) = exclusions {
+                    let values = exclusions
+                        .iter()
+                        .map(|exclusion| match exclusion {
+                            LanguageExclusion::Language(lang) => Ok(string::<RDF>(&lang.value())),
+                            LanguageExclusion::LanguageStem(stem) => {
+                                self.stem(SX_LANGUAGESTEM, &stem.value())
+                            }
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let list = self.list(&values)?;
+                    self.add(&node, SX_EXCLUSION, &list)?;
+                }
+                Ok(node)
+            }
+        }
+    }
+
+    fn iri_stem(&mut self, stem: &IriRef) -> Result<RDF::Term> {
+        let node = self.node_with_type(SX_IRISTEM)?;
+        let stem = any_uri::<RDF>(&self.iri(stem)?);
+        self.add(&node, SX_STEM, &stem)?;
+        Ok(node)
+    }
+
+    fn stem(&mut self, type_: &str, stem: &str) -> Result<RDF::Term> {
+        let node = self.node_with_type(type_)?;
+        self.add(&node, SX_STEM, &string::<RDF>(stem))?;
+        Ok(node)
+    }
+
+    fn object_value(&self, value: &ObjectValue) -> Result<RDF::Term> {
+        match value {
+            ObjectValue::IriRef(iri) => Ok(RDF::iri_s2term(&self.iri(iri)?)),
+            ObjectValue::Literal(Literal::DatatypeLiteral {
+                lexical_form,
+                datatype,
+            }) => {
+                let datatype = IriRef::iri(self.iri(datatype)?);
+                let literal = Literal::datatype(lexical_form, &datatype);
+                Ok(RDF::object_as_term(&Object::Literal(literal)))
+            }
+            ObjectValue::Literal(literal) => {
+                Ok(RDF::object_as_term(&Object::Literal(literal.clone())))
+            }
+        }
+    }
+
+    fn shape_label(&self, label: &ShapeExprLabel) -> Result<RDF::Term> {
+        match label {
+            ShapeExprLabel::IriRef { value } => Ok(RDF::iri_s2term(&self.iri(value)?)),
+            ShapeExprLabel::BNode { value } => Ok(RDF::bnode_id2term(value.value())),
+            ShapeExprLabel::Start => Err(ShExRError::StartLabel),
+        }
+    }
+
+    fn triple_expr_label(&self, label: &TripleExprLabel) -> Result<RDF::Term> {
+        match label {
+            TripleExprLabel::IriRef { value } => Ok(RDF::iri_s2term(&self.iri(value)?)),
+            TripleExprLabel::BNode { value } => Ok(RDF::bnode_id2term(value.value())),
+        }
+    }
+
+    fn iri(&self, iri_ref: &IriRef) -> Result<IriS> {
+        self.prefixmap
+            .resolve_iriref(iri_ref)
+            .map_err(|err| ShExRError::IriRefError {
+                iri_ref: iri_ref.clone(),
+                err: Box::new(err),
+            })
+    }
+
+    fn list(&mut self, values: &[RDF::Term]) -> Result<RDF::Term> {
+        self.rdf.add_list(values).map_err(cnv_err)
+    }
+
+    fn bnode(&mut self) -> Result<RDF::Term> {
+        let bnode = self.rdf.add_bnode().map_err(cnv_err)?;
+        Ok(RDF::bnode_as_term(bnode))
+    }
+
+    fn node_with_type(&mut self, type_: &str) -> Result<RDF::Term> {
+        let node = self.bnode()?;
+        self.add_type(&node, type_)?;
+        Ok(node)
+    }
+
+    fn add_type(&mut self, node: &RDF::Term, type_: &str) -> Result<()> {
+        self.add(node, RDF_TYPE_STR, &iri_term::<RDF>(type_))
+    }
+
+    fn add(&mut self, subject: &RDF::Term, property: &str, value: &RDF::Term) -> Result<()> {
+        let subject =
+            RDF::term_as_subject(subject).ok_or_else(|| ShExRError::SRDFBuilderError {
+                err: format!("{subject} can't be used as subject"),
+            })?;
+        let property = RDF::iri_s2iri(&IriS::new_unchecked(property));
+        self.rdf
+            .add_triple(&subject, &property, value)
+            .map_err(cnv_err)
+    }
+}
+
+impl<RDF> Default for ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn cnv_err<E: std::fmt::Display>(err: E) -> ShExRError {
+    ShExRError::SRDFBuilderError {
+        err: err.to_string(),
+    }
+}
+
+fn import_term<RDF: SRDFBuilder>(import: &IriOrStr) -> RDF::Term {
+    match import {
+        IriOrStr::IriS(iri) => RDF::iri_s2term(iri),
+        IriOrStr::String(str) => RDF::iri_s2term(&IriS::new_unchecked(str)),
+    }
+}
+
+fn iri_term<RDF: SRDFBuilder>(iri: &str) -> RDF::Term {
+    RDF::iri_s2term(&IriS::new_unchecked(iri))
+}
+
+fn any_uri<RDF: SRDFBuilder>(iri: &IriS) -> RDF::Term {
+    let datatype = IriRef::iri(IriS::new_unchecked(&format!("{XSD}anyURI")));
+    RDF::object_as_term(&Object::Literal(Literal::datatype(iri.as_str(), &datatype)))
+}
+
+fn string<RDF: SRDFBuilder>(str: &str) -> RDF::Term {
+    RDF::object_as_term(&Object::Literal(Literal::str(str)))
+}
+
+fn boolean<RDF: SRDFBuilder>(value: bool) -> RDF::Term {
+    RDF::object_as_term(&Object::Literal(Literal::boolean(value)))
+}
+
+fn integer<RDF: SRDFBuilder>(n: usize) -> RDF::Term {
+    RDF::object_as_term(&Object::Literal(Literal::integer(n as isize)))
+}
+
+fn numeric<RDF: SRDFBuilder>(n: NumericLiteral) -> RDF::Term {
+    RDF::object_as_term(&Object::Literal(Literal::NumericLiteral(n)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexr::shexr_parser::ShExRParser;
+    use srdf::{ReaderMode, SRDFGraph};
+
+    #[test]
+    fn test_round_trip() {
+        let str = r#"
+        {
+            "type": "Schema",
+            "shapes": [
+              {
+                "type": "ShapeDecl",
+                "id": "http://example.org/Person",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "closed": true,
+                  "extra": [ "http://example.org/knows" ],
+                  "expression": {
+                    "type": "EachOf",
+                    "expressions": [
+                      {
+                        "type": "TripleConstraint",
+                        "predicate": "http://example.org/name",
+                        "valueExpr": {
+                          "type": "NodeConstraint",
+                          "datatype": "http://www.w3.org/2001/XMLSchema#string",
+                          "minlength": 1,
+                          "pattern": "^[A-Z]",
+                          "flags": "i"
+                        },
+                        "min": 1,
+                        "max": -1
+                      },
+                      {
+                        "type": "TripleConstraint",
+                        "inverse": true,
+                        "predicate": "http://example.org/knows",
+                        "valueExpr": "http://example.org/Status",
+                        "annotations": [
+                          {
+                            "type": "Annotation",
+                            "predicate": "http://www.w3.org/2000/01/rdf-schema#label",
+                            "object": { "value": "known by" }
+                          }
+                        ]
+                      }
+                    ]
+                  }
+                }
+              },
+              {
+                "type": "ShapeDecl",
+                "id": "http://example.org/Status",
+                "shapeExpr": {
+                  "type": "ShapeOr",
+                  "shapeExprs": [
+                    { "type": "NodeConstraint", "nodeKind": "iri" },
+                    {
+                      "type": "NodeConstraint",
+                      "values": [
+                        "http://example.org/active",
+                        { "value": "1", "type": "http://www.w3.org/2001/XMLSchema#integer" },
+                        { "value": "inactive", "language": "en" },
+                        { "type": "IriStem", "stem": "http://example.org/" },
+                        { "type": "LanguageStem", "stem": "es" }
+                      ]
+                    }
+                  ]
+                }
+              }
+            ],
+            "@context": "http://www.w3.org/ns/shex.jsonld"
+          }
+        "#;
+        let schema: Schema = serde_json::from_str(str).unwrap();
+        let mut writer = ShExRWriter::<SRDFGraph>::new();
+        writer.write(&schema).unwrap();
+        let mut turtle = Vec::new();
+        writer.serialize(RDFFormat::Turtle, &mut turtle).unwrap();
+        let turtle = String::from_utf8(turtle).unwrap();
+        let rdf =
+            SRDFGraph::from_str(&turtle, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let parsed = ShExRParser::new(rdf).parse().unwrap();
+        assert_eq!(parsed.with_prefixmap(None).shapes(), schema.shapes());
+    }
+
+    #[test]
+    fn test_round_trip_schema_declarations() {
+        // the same comparison as the schemas of the ShEx test suite
+        let str = r#"
+        {
+            "type": "Schema",
+            "imports": [ "http://example.org/other" ],
+            "startActs": [
+              { "type": "SemAct", "name": "http://example.org/Test", "code": "print" }
+            ],
+            "start": "http://example.org/Person",
+            "shapes": [
+              {
+                "type": "ShapeDecl",
+                "id": "http://example.org/Person",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": {
+                    "type": "TripleConstraint",
+                    "predicate": "http://example.org/knows",
+                    "valueExpr": "http://example.org/Person",
+                    "min": 0,
+                    "max": -1
+                  }
+                }
+              }
+            ],
+            "@context": "http://www.w3.org/ns/shex.jsonld"
+          }
+        "#;
+        let schema: Schema = serde_json::from_str(str).unwrap();
+        let mut writer = ShExRWriter::<SRDFGraph>::new();
+        writer.write(&schema).unwrap();
+        let parsed = ShExRParser::new(writer.into_rdf()).parse().unwrap();
+        assert_eq!(parsed.imports(), schema.imports());
+        assert_eq!(parsed.start(), schema.start());
+        assert_eq!(parsed.start_actions(), schema.start_actions());
+        assert_eq!(parsed.shapes(), schema.shapes());
+    }
+}
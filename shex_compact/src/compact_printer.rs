@@ -13,12 +13,8 @@ pub(crate) fn pp_object_value<'a, A>(
 ) -> DocBuilder<'a, Arena<'a, A>, A> {
     match v {
         ObjectValue::IriRef(i) => pp_iri_ref(i, doc, prefixmap),
-        ObjectValue::Literal(Literal::BooleanLiteral(_value)) => {
-            todo!()
-        }
         ObjectValue::Literal(Literal::NumericLiteral(num)) => pp_numeric_literal(num, doc),
-        ObjectValue::Literal(Literal::DatatypeLiteral { .. }) => todo!(),
-        ObjectValue::Literal(Literal::StringLiteral { .. }) => todo!(),
+        ObjectValue::Literal(lit) => doc.text(lit.to_string()),
     }
 }
 
@@ -53,7 +49,7 @@ fn pp_numeric_literal<'a, A>(
     }
 }
 
-fn pp_iri_ref<'a, A>(
+pub(crate) fn pp_iri_ref<'a, A>(
     value: &IriRef,
     doc: &'a Arena<'a, A>,
     prefixmap: &PrefixMap,
@@ -1,8 +1,8 @@
-use crate::{keyword, pp_label, pp_object_value};
+use crate::{keyword, pp_iri_ref, pp_label, pp_object_value};
 use colored::*;
 use prefixmap::PrefixMap;
 use pretty::{Arena, DocAllocator, DocBuilder};
-use shapemap::{query_shape_map::QueryShapeMap, Association, NodeSelector, ShapeSelector};
+use shapemap::{query_shape_map::QueryShapeMap, Association, NodeSelector, Pattern, ShapeSelector};
 use std::marker::PhantomData;
 
 /// Struct that can be used to pretty print Shapemaps
@@ -155,10 +155,48 @@ where
     fn pp_node_selector(&self, ns: &NodeSelector) -> DocBuilder<'a, Arena<'a, A>, A> {
         match ns {
             NodeSelector::Node(v) => pp_object_value(v, self.doc, &self.nodes_prefixmap),
-            NodeSelector::TriplePattern { .. } => todo!(),
-            NodeSelector::TriplePatternPath { .. } => todo!(),
-            NodeSelector::Sparql { .. } => todo!(),
-            NodeSelector::Generic { .. } => todo!(),
+            NodeSelector::TriplePattern {
+                subject,
+                pred,
+                object,
+            } => self
+                .doc
+                .text("{")
+                .append(self.pp_pattern(subject))
+                .append(self.doc.space())
+                .append(pp_iri_ref(pred, self.doc, &self.nodes_prefixmap))
+                .append(self.doc.space())
+                .append(self.pp_pattern(object))
+                .append(self.doc.text("}")),
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => self
+                .doc
+                .text("{")
+                .append(self.pp_pattern(subject))
+                .append(self.doc.space())
+                .append(self.doc.text(pred.sparql_path()))
+                .append(self.doc.space())
+                .append(self.pp_pattern(object))
+                .append(self.doc.text("}")),
+            NodeSelector::Sparql { query } => keyword("SPARQL", self.doc, self.keyword_color)
+                .append(self.doc.space())
+                .append(self.doc.text(format!("\"\"\"{query}\"\"\""))),
+            NodeSelector::Generic { iri, param } => {
+                pp_iri_ref(iri, self.doc, &self.nodes_prefixmap)
+                    .append(self.doc.space())
+                    .append(self.doc.text(format!("\"\"\"{param}\"\"\"")))
+            }
+        }
+    }
+
+    fn pp_pattern(&self, pattern: &Pattern) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match pattern {
+            Pattern::Node(v) => pp_object_value(v, self.doc, &self.nodes_prefixmap),
+            Pattern::Wildcard => self.doc.text("_"),
+            Pattern::Focus => keyword("FOCUS", self.doc, self.keyword_color),
         }
     }
 
@@ -1,7 +1,7 @@
 use crate::{
     grammar::{map_error, tag_no_case_tws, token_tws, traced, tws0},
     iri, literal,
-    shex_grammar::shape_expr_label,
+    shex_grammar::{predicate, shape_expr_label, string},
     IRes, ParseError, Span,
 };
 use nom::{
@@ -11,13 +11,15 @@ use nom::{
     multi::many0,
     sequence::tuple,
 };
-use shapemap::{NodeSelector, ShapeSelector};
+use shapemap::{NodeSelector, Pattern, ShapeSelector, ValidationStatus};
+use shex_ast::object_value::ObjectValue;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ShapeMapStatement {
     Association {
         node_selector: NodeSelector,
         shape_selector: ShapeSelector,
+        status: ValidationStatus,
     },
 }
 
@@ -44,16 +46,27 @@ pub(crate) fn shapemap_statement<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, Vec<S
     )
 }
 
-/// `association ::= node_spec @ shape_spec`
+/// `association ::= node_spec ('@' | '@!' | '@?') shape_spec`
 fn association(i: Span) -> IRes<ShapeMapStatement> {
-    let (i, (ns, _, sl)) = tuple((node_selector(), token_tws("@"), shape_spec()))(i)?;
+    let (i, (ns, _, status, _, sl)) =
+        tuple((node_selector(), token_tws("@"), status, tws0, shape_spec()))(i)?;
     let s = ShapeMapStatement::Association {
         node_selector: ns,
         shape_selector: sl,
+        status,
     };
     Ok((i, s))
 }
 
+/// Status of an association in a result shape map: `!` is nonconformant, `?` is pending and no mark is conformant
+fn status(i: Span) -> IRes<ValidationStatus> {
+    map(opt(alt((char('!'), char('?')))), |c| match c {
+        Some('!') => ValidationStatus::NonConformant,
+        Some(_) => ValidationStatus::Pending,
+        None => ValidationStatus::Conformant,
+    })(i)
+}
+
 fn rest_associations(i: Span) -> IRes<Vec<ShapeMapStatement>> {
     let (i, ass) = many0(tuple((token_tws(","), tws0, association)))(i)?;
     let r = ass.into_iter().map(|(_, _, a)| a).collect();
@@ -76,10 +89,15 @@ pub(crate) fn shape_spec<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, ShapeSelector
 }
 
 /// nodeSelector     : objectTerm | triplePattern | extended ;
+///
+/// `extended` is tried first because its IRI would otherwise be taken as an `objectTerm`
 pub(crate) fn node_selector<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, NodeSelector> {
     traced(
         "node_selector",
-        map_error(move |i| object_term(i), || ParseError::ExpectedNodeSpec),
+        map_error(
+            move |i| alt((extended, object_term, triple_pattern))(i),
+            || ParseError::ExpectedNodeSpec,
+        ),
     )
 }
 
@@ -87,6 +105,73 @@ fn object_term(i: Span) -> IRes<NodeSelector> {
     alt((subject_term, literal_selector))(i)
 }
 
+/// triplePattern : '{' KW_FOCUS predicate (objectTerm | KW_WILDCARD) '}'
+///               | '{' (subjectTerm | KW_WILDCARD) predicate KW_FOCUS '}' ;
+fn triple_pattern(i: Span) -> IRes<NodeSelector> {
+    alt((focus_subject_pattern, focus_object_pattern))(i)
+}
+
+fn focus_subject_pattern(i: Span) -> IRes<NodeSelector> {
+    let (i, (_, _, pred, _, object, _)) = tuple((
+        token_tws("{"),
+        tag_no_case_tws("FOCUS"),
+        predicate,
+        tws0,
+        alt((object_pattern, wildcard)),
+        token_tws("}"),
+    ))(i)?;
+    Ok((
+        i,
+        NodeSelector::triple_pattern(Pattern::Focus, pred, object),
+    ))
+}
+
+fn focus_object_pattern(i: Span) -> IRes<NodeSelector> {
+    let (i, (_, subject, _, pred, _, _)) = tuple((
+        token_tws("{"),
+        alt((subject_pattern, wildcard)),
+        tws0,
+        predicate,
+        tag_no_case_tws("FOCUS"),
+        token_tws("}"),
+    ))(i)?;
+    Ok((
+        i,
+        NodeSelector::triple_pattern(subject, pred, Pattern::Focus),
+    ))
+}
+
+fn object_pattern(i: Span) -> IRes<Pattern> {
+    alt((
+        subject_pattern,
+        map(literal(), |lit| Pattern::Node(ObjectValue::literal(lit))),
+    ))(i)
+}
+
+fn subject_pattern(i: Span) -> IRes<Pattern> {
+    map(iri, |iri| Pattern::Node(ObjectValue::iri_ref(iri)))(i)
+}
+
+/// KW_WILDCARD : '_' ;
+fn wildcard(i: Span) -> IRes<Pattern> {
+    map(token_tws("_"), |_| Pattern::Wildcard)(i)
+}
+
+/// extended : KW_SPARQL string | nodeIri string ;
+fn extended(i: Span) -> IRes<NodeSelector> {
+    alt((sparql_selector, generic_selector))(i)
+}
+
+fn sparql_selector(i: Span) -> IRes<NodeSelector> {
+    let (i, (_, query)) = tuple((tag_no_case_tws("SPARQL"), string()))(i)?;
+    Ok((i, NodeSelector::Sparql { query }))
+}
+
+fn generic_selector(i: Span) -> IRes<NodeSelector> {
+    let (i, (iri, _, param)) = tuple((iri, tws0, string()))(i)?;
+    Ok((i, NodeSelector::Generic { iri, param }))
+}
+
 fn subject_term(i: Span) -> IRes<NodeSelector> {
     let (i, iri) = iri(i)?;
     Ok((i, NodeSelector::iri_ref(iri)))
@@ -99,6 +184,8 @@ fn literal_selector(i: Span) -> IRes<NodeSelector> {
 
 #[cfg(test)]
 mod tests {
+    use iri_s::IriS;
+    use prefixmap::IriRef;
     use shapemap::ShapeSelector;
 
     use super::*;
@@ -110,6 +197,92 @@ mod tests {
         let expected = ShapeMapStatement::Association {
             node_selector: NodeSelector::prefixed("", "a"),
             shape_selector: ShapeSelector::prefixed("", "label"),
+            status: ValidationStatus::Conformant,
+        };
+        assert_eq!(shape_map, expected);
+    }
+
+    #[test]
+    fn example_triple_pattern() {
+        let input = Span::new("{ FOCUS a :Person }@:PersonShape");
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::triple_pattern(
+                Pattern::Focus,
+                IriRef::iri(IriS::new_unchecked(
+                    "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+                )),
+                Pattern::Node(ObjectValue::prefixed("", "Person")),
+            ),
+            shape_selector: ShapeSelector::prefixed("", "PersonShape"),
+            status: ValidationStatus::Conformant,
+        };
+        assert_eq!(shape_map, expected);
+    }
+
+    #[test]
+    fn example_triple_pattern_wildcard() {
+        let input = Span::new("{_ :knows FOCUS}@:S");
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::triple_pattern(
+                Pattern::Wildcard,
+                IriRef::prefixed("", "knows"),
+                Pattern::Focus,
+            ),
+            shape_selector: ShapeSelector::prefixed("", "S"),
+            status: ValidationStatus::Conformant,
+        };
+        assert_eq!(shape_map, expected);
+    }
+
+    #[test]
+    fn example_sparql_selector() {
+        let input = Span::new(r#"SPARQL "select ?x where { ?x a ?t }"@:S"#);
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::sparql("select ?x where { ?x a ?t }"),
+            shape_selector: ShapeSelector::prefixed("", "S"),
+            status: ValidationStatus::Conformant,
+        };
+        assert_eq!(shape_map, expected);
+    }
+
+    #[test]
+    fn example_prefixed_generic_selector() {
+        let input = Span::new(r#"shex:Sparql "select ?x where { ?x a ?t }"@:S"#);
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::generic(
+                IriRef::prefixed("shex", "Sparql"),
+                "select ?x where { ?x a ?t }",
+            ),
+            shape_selector: ShapeSelector::prefixed("", "S"),
+            status: ValidationStatus::Conformant,
+        };
+        assert_eq!(shape_map, expected);
+    }
+
+    #[test]
+    fn example_nonconformant() {
+        let input = Span::new(":a@!:S");
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::prefixed("", "a"),
+            shape_selector: ShapeSelector::prefixed("", "S"),
+            status: ValidationStatus::NonConformant,
+        };
+        assert_eq!(shape_map, expected);
+    }
+
+    #[test]
+    fn example_pending_start() {
+        let input = Span::new(":a @? START");
+        let (_, shape_map) = association(input).unwrap();
+        let expected = ShapeMapStatement::Association {
+            node_selector: NodeSelector::prefixed("", "a"),
+            shape_selector: ShapeSelector::Start,
+            status: ValidationStatus::Pending,
         };
         assert_eq!(shape_map, expected);
     }
@@ -6,11 +6,15 @@ use crate::tws0;
 use crate::ParseError;
 use crate::Span;
 use nom::Err;
+use prefixmap::Deref;
 use prefixmap::IriRef;
 use prefixmap::PrefixMap;
 use shapemap::query_shape_map::QueryShapeMap;
 use shapemap::NodeSelector;
 use shapemap::ShapeSelector;
+use shapemap::{ResultAssociation, ResultShapeMap};
+use shex_ast::object_value::ObjectValue;
+use srdf::Object;
 use std::fs;
 use std::path::Path;
 use tracing::debug;
@@ -43,9 +47,11 @@ impl<'a> ShapeMapParser<'a> {
             let statements = ss?;
             for s in statements {
                 match s {
+                    // The status of result shape maps is ignored so they can be validated again
                     ShapeMapStatement::Association {
                         node_selector,
                         shape_selector,
+                        status: _,
                     } => {
                         tracing::debug!("Association {node_selector:?}@{shape_selector:?}");
                         query_shapemap.add_association(node_selector, shape_selector);
@@ -56,6 +62,61 @@ impl<'a> ShapeMapParser<'a> {
         Ok(query_shapemap)
     }
 
+    /// Parse a result ShapeMap in compact syntax, where `node@!shape` marks nonconformant associations
+    /// and `node@?shape` pending ones.
+    ///
+    /// Node selectors must be RDF terms. Prefixed names are resolved against the prefix maps
+    pub fn parse_result_map(
+        src: &str,
+        nodes_prefixmap: &Option<PrefixMap>,
+        shapes_prefixmap: &Option<PrefixMap>,
+    ) -> Result<ResultShapeMap> {
+        let mut result_map = ResultShapeMap::new();
+        if let Some(pm) = nodes_prefixmap {
+            result_map = result_map.with_nodes_prefixmap(pm)
+        };
+        if let Some(pm) = shapes_prefixmap {
+            result_map = result_map.with_shapes_prefixmap(pm)
+        };
+        let mut parser = ShapeMapParser {
+            shapemap_statement_iterator: ShapeMapStatementIterator::new(Span::new(src))?,
+        };
+        for ss in parser.shapemap_statement_iterator.by_ref() {
+            let statements = ss?;
+            for s in statements {
+                match s {
+                    ShapeMapStatement::Association {
+                        node_selector,
+                        shape_selector,
+                        status,
+                    } => {
+                        let node = result_node(&node_selector, nodes_prefixmap)?;
+                        for label in shape_selector.iter_shape() {
+                            let label = label.deref(&None, shapes_prefixmap)?;
+                            result_map.add_association(ResultAssociation::new(
+                                node.clone(),
+                                label,
+                                status,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(result_map)
+    }
+
+    pub fn parse_result_map_buf(
+        path: &Path,
+        nodes_prefixmap: &Option<PrefixMap>,
+        shapes_prefixmap: &Option<PrefixMap>,
+    ) -> Result<ResultShapeMap> {
+        let data = fs::read_to_string(path)?;
+        let result_map =
+            ShapeMapParser::parse_result_map(&data, nodes_prefixmap, shapes_prefixmap)?;
+        Ok(result_map)
+    }
+
     pub fn parse_buf(
         path: &Path,
         nodes_prefixmap: &Option<PrefixMap>,
@@ -103,6 +164,23 @@ impl<'a> ShapeMapParser<'a> {
     }
 }
 
+fn result_node(node_selector: &NodeSelector, prefixmap: &Option<PrefixMap>) -> Result<Object> {
+    match node_selector {
+        NodeSelector::Node(value) => match value.deref(&None, prefixmap)? {
+            ObjectValue::IriRef(iri_ref) => {
+                let iri = iri_ref.get_iri().map_err(|e| ParseError::Custom {
+                    msg: format!("Cannot resolve node {iri_ref} in result shape map: {e}"),
+                })?;
+                Ok(Object::iri(iri))
+            }
+            ObjectValue::Literal(lit) => Ok(Object::literal(lit)),
+        },
+        _ => Err(ParseError::Custom {
+            msg: format!("Result shape maps can only contain RDF nodes, found {node_selector:?}"),
+        }),
+    }
+}
+
 struct ShapeMapStatementIterator<'a> {
     src: Span<'a>,
     done: bool,
@@ -166,7 +244,8 @@ impl<'a> Iterator for ShapeMapStatementIterator<'a> {
 mod tests {
 
     use iri_s::IriS;
-    use shapemap::{NodeSelector, ShapeSelector};
+    use shapemap::{NodeSelector, ShapeSelector, ValidationStatus};
+    use shex_ast::ShapeExprLabel;
 
     use super::*;
 
@@ -200,4 +279,35 @@ mod tests {
         );
         assert_eq!(parsed_shapemap, expected)
     }
+
+    #[test]
+    fn test_result_map() {
+        let str = r#":a@:S, :b@!:S, :c@?START"#;
+        let mut prefixmap = PrefixMap::new();
+        prefixmap
+            .insert("", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        let pm = Some(prefixmap.clone());
+        let result_map = ShapeMapParser::parse_result_map(str, &pm, &pm).unwrap();
+        let node =
+            |name: &str| Object::iri(IriS::new_unchecked(&format!("http://example.org/{name}")));
+        let s = ShapeExprLabel::iri_unchecked("http://example.org/S");
+        assert_eq!(
+            result_map.get_status(&node("a"), &s),
+            Some(&ValidationStatus::Conformant)
+        );
+        assert_eq!(
+            result_map.get_status(&node("b"), &s),
+            Some(&ValidationStatus::NonConformant)
+        );
+        assert_eq!(
+            result_map.get_status(&node("c"), &ShapeExprLabel::Start),
+            Some(&ValidationStatus::Pending)
+        );
+        assert_eq!(result_map.to_string(), ":a@:S,\n:b@!:S,\n:c@?START");
+
+        // Result maps can be parsed again as query shape maps
+        let query_map = ShapeMapParser::parse(str, &pm, &pm).unwrap();
+        assert_eq!(query_map.iter().count(), 3);
+    }
 }
@@ -263,7 +263,7 @@ where
             ShapeExpr::ShapeAnd { shape_exprs } => {
                 let mut docs = Vec::new();
                 for sew in shape_exprs {
-                    docs.push(self.pp_shape_expr(&sew.se))
+                    docs.push(self.pp_shape_expr_operand(&sew.se, false))
                 }
                 self.doc
                     .intersperse(docs, self.keyword(" AND "))
@@ -284,7 +284,29 @@ where
                 .doc
                 .nil()
                 .append(self.keyword("NOT "))
-                .append(self.pp_shape_expr(&shape_expr.se)),
+                .append(self.pp_shape_expr_operand(&shape_expr.se, true)),
+        }
+    }
+
+    /// Operands of AND and NOT are enclosed in parenthesis when they have a
+    /// lower precedence, i.e. OR inside AND, and AND or OR inside NOT
+    fn pp_shape_expr_operand(
+        &self,
+        se: &ShapeExpr,
+        in_not: bool,
+    ) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match se {
+            ShapeExpr::ShapeOr { .. } => self
+                .doc
+                .text("(")
+                .append(self.pp_shape_expr(se))
+                .append(")"),
+            ShapeExpr::ShapeAnd { .. } if in_not => self
+                .doc
+                .text("(")
+                .append(self.pp_shape_expr(se))
+                .append(")"),
+            _ => self.pp_shape_expr(se),
         }
     }
 
@@ -490,11 +512,14 @@ where
                 self.pp_string_literal(lexical_form, lang)
             }
             Literal::DatatypeLiteral {
-                lexical_form: _,
-                datatype: _,
-            } => todo!(),
+                lexical_form,
+                datatype,
+            } => self
+                .pp_string(lexical_form)
+                .append("^^")
+                .append(self.pp_iri_ref(datatype)),
             Literal::NumericLiteral(lit) => self.pp_numeric_literal(lit),
-            Literal::BooleanLiteral(_) => todo!(),
+            Literal::BooleanLiteral(b) => self.doc.text(b.to_string()),
         }
     }
 
@@ -504,7 +529,9 @@ where
         lang: &Option<Lang>,
     ) -> DocBuilder<'a, Arena<'a, A>, A> {
         match lang {
-            Some(_) => todo!(),
+            Some(lang) => self
+                .pp_string(lexical_form)
+                .append(self.doc.text(format!("@{}", lang.value()))),
             None => self.pp_string(lexical_form),
         }
     }
@@ -578,27 +605,27 @@ where
     fn pp_numericfacet(&self, nf: &NumericFacet) -> DocBuilder<'a, Arena<'a, A>, A> {
         match nf {
             NumericFacet::FractionDigits(fd) => self
-                .keyword("FractionDigits")
+                .keyword("FRACTIONDIGITS")
                 .append(self.space())
                 .append(self.pp_usize(fd)),
             NumericFacet::TotalDigits(td) => self
-                .keyword("TotalDigits")
+                .keyword("TOTALDIGITS")
                 .append(self.space())
                 .append(self.pp_usize(td)),
             NumericFacet::MinInclusive(m) => self
-                .keyword("MinInclusive")
+                .keyword("MININCLUSIVE")
                 .append(self.space())
                 .append(self.pp_numeric_literal(m)),
             NumericFacet::MaxInclusive(m) => self
-                .keyword("MaxInclusive")
+                .keyword("MAXINCLUSIVE")
                 .append(self.space())
                 .append(self.pp_numeric_literal(m)),
             NumericFacet::MinExclusive(m) => self
-                .keyword("MinExclusive")
+                .keyword("MINEXCLUSIVE")
                 .append(self.space())
                 .append(self.pp_numeric_literal(m)),
             NumericFacet::MaxExclusive(m) => self
-                .keyword("MaxExclusive")
+                .keyword("MAXEXCLUSIVE")
                 .append(self.space())
                 .append(self.pp_numeric_literal(m)),
         }
@@ -607,15 +634,15 @@ where
     fn pp_stringfacet(&self, sf: &StringFacet) -> DocBuilder<'a, Arena<'a, A>, A> {
         match sf {
             StringFacet::Length(l) => self
-                .keyword("Length")
+                .keyword("LENGTH")
                 .append(self.space())
                 .append(self.pp_usize(l)),
             StringFacet::MinLength(l) => self
-                .keyword("MinLength")
+                .keyword("MINLENGTH")
                 .append(self.space())
                 .append(self.pp_usize(l)),
             StringFacet::MaxLength(l) => self
-                .keyword("MaxLength")
+                .keyword("MAXLENGTH")
                 .append(self.space())
                 .append(self.pp_usize(l)),
             StringFacet::Pattern(pat) => self.pp_pattern(pat),
@@ -643,14 +670,18 @@ where
 
     fn pp_value_set_value(&self, v: &ValueSetValue) -> DocBuilder<'a, Arena<'a, A>, A> {
         match v {
-            ValueSetValue::LanguageStem { .. } => todo!(),
+            ValueSetValue::LanguageStem { stem } => {
+                self.doc.text(format!("@{}", stem.value())).append("~")
+            }
             ValueSetValue::LanguageStemRange { .. } => todo!(),
             ValueSetValue::ObjectValue(ov) => pp_object_value(ov, self.doc, &self.prefixmap),
-            ValueSetValue::IriStem { .. } => todo!(),
+            ValueSetValue::IriStem { stem } => self.pp_iri_ref(stem).append("~"),
             ValueSetValue::IriStemRange { .. } => todo!(),
-            ValueSetValue::LiteralStem { .. } => todo!(),
+            ValueSetValue::LiteralStem { stem } => self.pp_string(stem).append("~"),
             ValueSetValue::LiteralStemRange { .. } => todo!(),
-            ValueSetValue::Language { .. } => todo!(),
+            ValueSetValue::Language { language_tag } => {
+                self.doc.text(format!("@{}", language_tag.value()))
+            }
         }
     }
 
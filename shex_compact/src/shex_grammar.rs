@@ -579,10 +579,14 @@ fn xs_facet<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, XsFacet> {
 
 /// `[28] stringFacet ::= stringLength INTEGER | REGEXP`
 fn string_facet(i: Span) -> IRes<XsFacet> {
-    alt((
-        string_length,
-        map(regexp, |p| XsFacet::StringFacet(StringFacet::Pattern(p))),
-    ))(i)
+    delimited(
+        tws0,
+        alt((
+            string_length,
+            map(regexp, |p| XsFacet::StringFacet(StringFacet::Pattern(p))),
+        )),
+        tws0,
+    )(i)
 }
 
 // `[29]   	stringLength	   ::=   	"LENGTH" | "MINLENGTH" | "MAXLENGTH"`
@@ -1536,7 +1540,7 @@ fn datatype_iri(i: Span) -> IRes<IriRef> {
 
 /// `[135s] string ::= STRING_LITERAL1 | STRING_LITERAL_LONG1`
 /// `                  | STRING_LITERAL2 | STRING_LITERAL_LONG2`
-fn string<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, String> {
+pub(crate) fn string<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, String> {
     traced(
         "string",
         map_error(
@@ -1686,7 +1690,7 @@ fn lang_tag(i: Span) -> IRes<Lang> {
 }
 
 /// `[61] predicate ::= iri | RDF_TYPE`
-fn predicate(i: Span) -> IRes<IriRef> {
+pub(crate) fn predicate(i: Span) -> IRes<IriRef> {
     alt((iri, rdf_type))(i)
 }
 
@@ -1,5 +1,5 @@
 use iri_s::IriSError;
-use shex_ast::{ast::SchemaJsonError, CompiledSchemaError, Schema};
+use shex_ast::{ast::SchemaJsonError, shexr::shexr_error::ShExRError, CompiledSchemaError, Schema};
 use shex_compact::ParseError;
 use shex_validation::{ResultValue, ValidatorError};
 use srdf::srdf_graph::SRDFGraphError;
@@ -50,6 +50,9 @@ pub enum ManifestError {
     #[error(transparent)]
     IriError(#[from] IriSError),
 
+    #[error(transparent)]
+    ShExRError(#[from] ShExRError),
+
     #[error(transparent)]
     ValidationError(#[from] ValidatorError),
 
@@ -79,6 +82,14 @@ pub enum ManifestError {
         shexc_schema_parsed: Schema,
     },
 
+    #[error(
+        "Error |ShExRSchemaDifferent| ShEx Schema after a round trip through ShExR is different from schema parsed\nSchema JSON Parsed:\n{json_schema_parsed:?}\nSchema parsed from ShExR:\n{shexr_schema_parsed:?}"
+    )]
+    ShExRSchemaDifferent {
+        json_schema_parsed: Schema,
+        shexr_schema_parsed: Schema,
+    },
+
     #[error("Schema parsed could not be serialized\n{schema_parsed:?}\n{error:?}")]
     SchemaSerializationError {
         schema_parsed: Schema,
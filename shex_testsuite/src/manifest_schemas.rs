@@ -10,7 +10,10 @@ use serde::de::{self};
 use serde::{Deserialize, Deserializer, Serialize};
 // use serde_derive::{Serialize};
 use shex_ast::ast::Schema as SchemaJson;
+use shex_ast::shexr::shexr_parser::ShExRParser;
+use shex_ast::shexr::shexr_writer::ShExRWriter;
 use shex_compact::ShExParser;
+use srdf::SRDFGraph;
 use tracing::debug;
 use url::Url;
 
@@ -209,7 +212,7 @@ impl SchemasEntry {
             shexc_schema_parsed = shexc_schema_parsed.with_base(None).with_prefixmap(None);
             if schema_parsed == shexc_schema_parsed {
                 debug!("Schema JSON parsed == Schema ShExC parsed");
-                Ok(())
+                self.check_shexr(&schema_parsed)
             } else {
                 Err(ManifestError::ShExSchemaDifferent {
                     json_schema_parsed: schema_parsed,
@@ -227,6 +230,33 @@ impl SchemasEntry {
             })
         }
     }
+
+    /// Checks that the schema parsed from its ShExR representation is the
+    /// same as the schema parsed from JSON. The base and prefixes are not
+    /// compared, as they are not kept in JSON
+    fn check_shexr(&self, schema: &SchemaJson) -> Result<(), ManifestError> {
+        let shexr = shexr_graph(schema)?;
+        let shexr_schema_parsed = ShExRParser::new(shexr).parse()?;
+        if shexr_schema_parsed.imports() == schema.imports()
+            && shexr_schema_parsed.start() == schema.start()
+            && shexr_schema_parsed.start_actions() == schema.start_actions()
+            && shexr_schema_parsed.shapes() == schema.shapes()
+        {
+            debug!("Schema JSON parsed == Schema parsed from its ShExR");
+            Ok(())
+        } else {
+            Err(ManifestError::ShExRSchemaDifferent {
+                json_schema_parsed: schema.clone(),
+                shexr_schema_parsed,
+            })
+        }
+    }
+}
+
+fn shexr_graph(schema: &SchemaJson) -> Result<SRDFGraph, ManifestError> {
+    let mut writer = ShExRWriter::<SRDFGraph>::new();
+    writer.write(schema)?;
+    Ok(writer.into_rdf())
 }
 
 impl Manifest for ManifestSchemas {
@@ -1,16 +1,55 @@
 use std::fmt::Display;
 
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
 use shex_ast::{
     compiled::{node_constraint::NodeConstraint, shape::Shape, shape_expr::ShapeExpr},
-    Node,
+    Node, Pred, ShapeLabelIdx,
 };
 
+use crate::ValidatorErrors;
+
 /// Reason represents justifications about why a node conforms to some shape
 #[derive(Debug, Clone)]
 pub enum Reason {
-    NodeConstraintPassed { node: Node, nc: NodeConstraint },
-    ShapeAndPassed { node: Node, se: ShapeExpr },
-    ShapePassed { node: Node, shape: Shape },
+    NodeConstraintPassed {
+        node: Node,
+        nc: NodeConstraint,
+    },
+    ShapeAndPassed {
+        node: Node,
+        se: ShapeExpr,
+    },
+    ShapeOrPassed {
+        node: Node,
+        shape_expr: ShapeExpr,
+        reasons: Reasons,
+    },
+    ShapeNotPassed {
+        node: Node,
+        shape_expr: ShapeExpr,
+        errors_evidences: ValidatorErrors,
+    },
+    ShapePassed {
+        node: Node,
+        shape: Shape,
+        matches: Vec<TripleMatch>,
+    },
+    ShapeRefPassed {
+        node: Node,
+        idx: ShapeLabelIdx,
+    },
+    /// The node is assumed to conform to the shape because it is currently being checked (recursive reference)
+    ShapeRefAssumed {
+        node: Node,
+        idx: ShapeLabelIdx,
+    },
+    /// The node doesn't conform to the shape, as expected by a negative atom
+    NegAtomPassed {
+        node: Node,
+        idx: ShapeLabelIdx,
+        errors_evidences: ValidatorErrors,
+    },
 }
 
 impl Display for Reason {
@@ -22,9 +61,219 @@ impl Display for Reason {
             Reason::ShapeAndPassed { node, se } => {
                 write!(f, "AND passed for node {node}: {se}")
             }
-            Reason::ShapePassed { node, shape } => {
-                write!(f, "Shape passed for node {node}: {shape}")
+            Reason::ShapeOrPassed {
+                node,
+                shape_expr,
+                reasons,
+            } => {
+                write!(f, "OR passed for node {node} with {shape_expr}: {reasons}")
+            }
+            Reason::ShapeNotPassed {
+                node,
+                shape_expr,
+                errors_evidences,
+            } => {
+                write!(
+                    f,
+                    "NOT passed for node {node}: {shape_expr} because it failed with: {errors_evidences}"
+                )
+            }
+            Reason::ShapePassed {
+                node,
+                shape,
+                matches,
+            } => {
+                write!(f, "Shape passed for node {node}: {shape}")?;
+                for m in matches {
+                    write!(f, "\n    {m}")?;
+                }
+                Ok(())
+            }
+            Reason::ShapeRefPassed { node, idx } => {
+                write!(f, "Shape reference @{idx} passed for node {node}")
+            }
+            Reason::ShapeRefAssumed { node, idx } => {
+                write!(
+                    f,
+                    "Shape reference @{idx} assumed for node {node} because it is being checked"
+                )
+            }
+            Reason::NegAtomPassed {
+                node,
+                idx,
+                errors_evidences,
+            } => {
+                write!(
+                    f,
+                    "Node {node} doesn't conform to @{idx} as expected: {errors_evidences}"
+                )
+            }
+        }
+    }
+}
+
+impl Reason {
+    /// Triples matched by the triple constraints of a shape
+    pub fn triple_matches(&self) -> &[TripleMatch] {
+        match self {
+            Reason::ShapePassed { matches, .. } => matches,
+            _ => &[],
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Reason::NodeConstraintPassed { .. } => "NodeConstraintPassed",
+            Reason::ShapeAndPassed { .. } => "ShapeAndPassed",
+            Reason::ShapeOrPassed { .. } => "ShapeOrPassed",
+            Reason::ShapeNotPassed { .. } => "ShapeNotPassed",
+            Reason::ShapePassed { .. } => "ShapePassed",
+            Reason::ShapeRefPassed { .. } => "ShapeRefPassed",
+            Reason::ShapeRefAssumed { .. } => "ShapeRefAssumed",
+            Reason::NegAtomPassed { .. } => "NegAtomPassed",
+        }
+    }
+}
+
+impl Serialize for Reason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", self.name())?;
+        match self {
+            Reason::NodeConstraintPassed { node, nc } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("node_constraint", &nc.to_string())?;
+            }
+            Reason::ShapeAndPassed { node, se } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape_expr", &se.to_string())?;
+            }
+            Reason::ShapeOrPassed {
+                node,
+                shape_expr,
+                reasons,
+            } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape_expr", &shape_expr.to_string())?;
+                map.serialize_entry("reasons", reasons)?;
+            }
+            Reason::ShapeNotPassed {
+                node,
+                shape_expr,
+                errors_evidences,
+            } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape_expr", &shape_expr.to_string())?;
+                map.serialize_entry("errors", errors_evidences)?;
+            }
+            Reason::ShapePassed { node, matches, .. } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("matches", matches)?;
+            }
+            Reason::ShapeRefPassed { node, idx } | Reason::ShapeRefAssumed { node, idx } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape", &idx.to_string())?;
             }
+            Reason::NegAtomPassed {
+                node,
+                idx,
+                errors_evidences,
+            } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape", &idx.to_string())?;
+                map.serialize_entry("errors", errors_evidences)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// A triple of the neighbourhood of a node and the triple constraint that it matched
+#[derive(Debug, Clone, PartialEq)]
+pub struct TripleMatch {
+    predicate: Pred,
+    object: Node,
+    constraint: String,
+}
+
+impl TripleMatch {
+    pub fn new(predicate: Pred, object: Node, constraint: String) -> TripleMatch {
+        TripleMatch {
+            predicate,
+            object,
+            constraint,
+        }
+    }
+
+    pub fn predicate(&self) -> &Pred {
+        &self.predicate
+    }
+
+    pub fn object(&self) -> &Node {
+        &self.object
+    }
+
+    /// Description of the condition of the triple constraint that was matched
+    pub fn constraint(&self) -> &str {
+        &self.constraint
+    }
+}
+
+impl Display for TripleMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} matched {}",
+            self.predicate, self.object, self.constraint
+        )
+    }
+}
+
+impl Serialize for TripleMatch {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("predicate", &self.predicate.to_string())?;
+        map.serialize_entry("object", &self.object.to_string())?;
+        map.serialize_entry("constraint", &self.constraint)?;
+        map.end()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reasons {
+    reasons: Vec<Reason>,
+}
+
+impl Reasons {
+    pub fn new(reasons: Vec<Reason>) -> Reasons {
+        Reasons { reasons }
+    }
+}
+
+impl Display for Reasons {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for reason in self.reasons.iter() {
+            writeln!(f, "  {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Reasons {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.reasons.len()))?;
+        for reason in &self.reasons {
+            seq.serialize_element(reason)?;
         }
+        seq.end()
     }
 }
@@ -1,23 +1,26 @@
 use colored::*;
+use indexmap::map::Entry;
+use indexmap::{IndexMap, IndexSet};
 use prefixmap::PrefixMap;
+use serde::Serialize;
+use shapemap::{ResultAssociation, ResultShapeMap, ValidationStatus};
 use shex_ast::compiled::shape_label::ShapeLabel;
-use shex_ast::Node;
+use shex_ast::{Node, ShapeExprLabel};
 use srdf::Object;
-use std::collections::hash_map::Entry;
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::{Debug, Display, Formatter},
-};
+use std::fmt::{Debug, Display, Formatter};
 
 use crate::{Reason, ResultValue, ValidatorError};
 
+/// Results of validating a shape map.
+///
+/// Nodes and shapes are kept in the order in which they were added, so the same validation always shows the same result map
 #[derive(Debug, Default)]
 pub struct ResultMap {
     nodes_prefixmap: PrefixMap,
     schema_prefixmap: PrefixMap,
-    ok_map: HashMap<Node, HashMap<ShapeLabel, Vec<Reason>>>,
-    fail_map: HashMap<Node, HashMap<ShapeLabel, Vec<ValidatorError>>>,
-    pending: HashMap<Node, HashSet<ShapeLabel>>,
+    ok_map: IndexMap<Node, IndexMap<ShapeLabel, Vec<Reason>>>,
+    fail_map: IndexMap<Node, IndexMap<ShapeLabel, Vec<ValidatorError>>>,
+    pending: IndexMap<Node, IndexSet<ShapeLabel>>,
 }
 
 impl ResultMap {
@@ -34,7 +37,7 @@ impl ResultMap {
                 }
             },
             Entry::Vacant(vacant) => {
-                vacant.insert(HashMap::from([(s, reasons)]));
+                vacant.insert(IndexMap::from([(s, reasons)]));
             }
         }
     }
@@ -48,7 +51,7 @@ impl ResultMap {
                 }
             },
             Entry::Vacant(vacant) => {
-                vacant.insert(HashMap::from([(s, errs)]));
+                vacant.insert(IndexMap::from([(s, errs)]));
             }
         }
     }
@@ -69,7 +72,7 @@ impl ResultMap {
                 v.get_mut().insert(s);
             }
             Entry::Vacant(vacant) => {
-                vacant.insert(HashSet::from([s]));
+                vacant.insert(IndexSet::from([s]));
             }
         }
     }
@@ -102,6 +105,49 @@ impl ResultMap {
         }
     }
 
+    /// Obtains the fixed shape map with the status of each node/shape pair,
+    /// which can be stored or compared with the results of other validations.
+    ///
+    /// It is serialized as a [result shape map](https://shexspec.github.io/shape-map/#results) in JSON,
+    /// where the reasons or errors that justify each result are included in its `appinfo`
+    pub fn result_shape_map(&self) -> ResultShapeMap {
+        let mut result = ResultShapeMap::new()
+            .with_nodes_prefixmap(&self.nodes_prefixmap)
+            .with_shapes_prefixmap(&self.schema_prefixmap);
+        for (node, shapes) in &self.ok_map {
+            for (shape, reasons) in shapes {
+                result.add_association(result_association(
+                    node,
+                    shape,
+                    ValidationStatus::Conformant,
+                    "reasons",
+                    reasons,
+                ))
+            }
+        }
+        for (node, shapes) in &self.fail_map {
+            for (shape, errors) in shapes {
+                result.add_association(result_association(
+                    node,
+                    shape,
+                    ValidationStatus::NonConformant,
+                    "errors",
+                    errors,
+                ))
+            }
+        }
+        for (node, shapes) in &self.pending {
+            for shape in shapes {
+                result.add_association(ResultAssociation::new(
+                    node.as_object().clone(),
+                    shape_expr_label(shape),
+                    ValidationStatus::Pending,
+                ))
+            }
+        }
+        result
+    }
+
     pub fn is_pending(&self, node: &Node, shape: &ShapeLabel) -> bool {
         if let Some(hs) = self.pending.get(node) {
             hs.contains(shape)
@@ -111,6 +157,37 @@ impl ResultMap {
     }
 }
 
+fn shape_expr_label(label: &ShapeLabel) -> ShapeExprLabel {
+    match label {
+        ShapeLabel::Iri(iri) => ShapeExprLabel::iri(iri.clone()),
+        ShapeLabel::BNode(bnode) => ShapeExprLabel::bnode(bnode.clone()),
+        ShapeLabel::Start => ShapeExprLabel::Start,
+    }
+}
+
+fn result_association<T>(
+    node: &Node,
+    shape: &ShapeLabel,
+    status: ValidationStatus,
+    name: &str,
+    values: &[T],
+) -> ResultAssociation
+where
+    T: Display + Serialize,
+{
+    let reason = values
+        .iter()
+        .map(|v| v.to_string().trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let app_info = serde_json::to_value(values).ok().map(|values| {
+        serde_json::Value::Object(serde_json::Map::from_iter([(name.to_string(), values)]))
+    });
+    ResultAssociation::new(node.as_object().clone(), shape_expr_label(shape), status)
+        .with_reason(Some(reason))
+        .with_app_info(app_info)
+}
+
 fn show_node(node: &Node, prefixmap: &PrefixMap) -> String {
     match node.as_object() {
         Object::Iri(iri) => prefixmap.qualify(iri),
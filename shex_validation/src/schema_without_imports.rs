@@ -1,10 +1,9 @@
 use iri_s::IriS;
 use prefixmap::IriRef;
 use serde_derive::{Deserialize, Serialize};
-use shex_ast::{IriOrStr, Schema, SchemaJsonError, Shape, ShapeDecl, ShapeExpr, ShapeExprLabel};
+use shex_ast::{IriOrStr, Schema, Shape, ShapeDecl, ShapeExpr, ShapeExprLabel};
 use shex_compact::ShExParser;
 use std::collections::{hash_map::Entry, HashMap};
-use tracing::debug;
 use url::Url;
 
 use crate::{ResolveMethod, SchemaWithoutImportsError, ShExFormat};
@@ -205,14 +204,16 @@ pub fn resolve_iri_or_str(
     }
 }
 
-pub fn local_folder_as_iri() -> Result<IriS, SchemaJsonError> {
-    let current_dir = std::env::current_dir().map_err(|e| SchemaJsonError::CurrentDir {
-        error: format!("{e}"),
-    })?;
-    debug!("Current dir: {current_dir:?}");
+#[cfg(all(feature = "fs", not(target_arch = "wasm32")))]
+pub fn local_folder_as_iri() -> Result<IriS, shex_ast::SchemaJsonError> {
+    let current_dir =
+        std::env::current_dir().map_err(|e| shex_ast::SchemaJsonError::CurrentDir {
+            error: format!("{e}"),
+        })?;
+    tracing::debug!("Current dir: {current_dir:?}");
     let url = Url::from_file_path(&current_dir)
-        .map_err(|_e| SchemaJsonError::LocalFolderIriError { path: current_dir })?;
-    debug!("url: {url}");
+        .map_err(|_e| shex_ast::SchemaJsonError::LocalFolderIriError { path: current_dir })?;
+    tracing::debug!("url: {url}");
     Ok(IriS::new_unchecked(url.as_str()))
 }
 
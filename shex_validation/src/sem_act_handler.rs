@@ -0,0 +1,211 @@
+use std::any::Any;
+use std::fmt::Debug;
+
+use iri_s::IriS;
+use shex_ast::{Node, Pred};
+use tracing::debug;
+
+/// IRI of the [Test extension](http://shex.io/extensions/Test/) employed by the ShEx test suite
+pub const TEST_EXTENSION: &str = "http://shex.io/extensions/Test/";
+
+/// Default IRI of the value extraction extension implemented by [`ExtractSemActHandler`]
+pub const EXTRACT_EXTENSION: &str = "http://www.weso.es/shex/extensions/Extract/";
+
+/// Handlers execute the code of the semantic actions of some extension.
+///
+/// Handlers are registered in the validator by the IRI of the extension that they implement.
+pub trait SemActHandler: Debug + Send {
+    /// Executes the `code` of a semantic action. Returns an error message if the action fails
+    fn run(&mut self, code: Option<&str>, context: &SemActContext) -> Result<(), String>;
+
+    /// Creates an empty handler of the same kind, used by each worker in parallel validation
+    fn fork(&self) -> Box<dyn SemActHandler>;
+
+    /// Collects the results of a handler obtained by `fork` once its worker has finished
+    fn merge(&mut self, other: Box<dyn SemActHandler>);
+
+    /// Allows to recover the concrete handler after validation, for example, to obtain the values it collected
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// Values available to a semantic action when it is executed
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SemActContext {
+    focus: Option<Node>,
+    predicate: Option<Pred>,
+    object: Option<Node>,
+}
+
+impl SemActContext {
+    /// Context of the start actions of a schema
+    pub fn start() -> SemActContext {
+        SemActContext::default()
+    }
+
+    /// Context of the actions of a shape that has been matched by `focus`
+    pub fn shape(focus: &Node) -> SemActContext {
+        SemActContext {
+            focus: Some(focus.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Context of the actions of a triple constraint that has been matched by a triple
+    pub fn triple(focus: &Node, predicate: &Pred, object: &Node) -> SemActContext {
+        SemActContext {
+            focus: Some(focus.clone()),
+            predicate: Some(predicate.clone()),
+            object: Some(object.clone()),
+        }
+    }
+
+    pub fn focus(&self) -> Option<&Node> {
+        self.focus.as_ref()
+    }
+
+    pub fn predicate(&self) -> Option<&Pred> {
+        self.predicate.as_ref()
+    }
+
+    pub fn object(&self) -> Option<&Node> {
+        self.object.as_ref()
+    }
+}
+
+/// Handler of the Test extension.
+///
+/// It accepts the code `print(x)`, which records `x`, and `fail(x)`, which fails with `x`,
+/// where `x` can be `s`, `p`, `o` or a quoted string.
+#[derive(Debug, Default)]
+pub struct TestSemActHandler {
+    messages: Vec<String>,
+}
+
+impl TestSemActHandler {
+    pub fn new() -> TestSemActHandler {
+        TestSemActHandler::default()
+    }
+
+    pub fn iri() -> IriS {
+        IriS::new_unchecked(TEST_EXTENSION)
+    }
+
+    /// Messages recorded by `print` actions
+    pub fn messages(&self) -> &Vec<String> {
+        &self.messages
+    }
+
+    fn argument(arg: &str, context: &SemActContext) -> Result<String, String> {
+        let value = match arg {
+            "s" => context.focus().map(|s| s.to_string()),
+            "p" => context.predicate().map(|p| p.to_string()),
+            "o" => context.object().map(|o| o.to_string()),
+            str => str
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .map(|s| s.to_string()),
+        };
+        value.ok_or(format!("No value for argument {arg} in Test extension"))
+    }
+}
+
+impl SemActHandler for TestSemActHandler {
+    fn run(&mut self, code: Option<&str>, context: &SemActContext) -> Result<(), String> {
+        let code = code.unwrap_or_default().trim();
+        let (function, arg) = code
+            .strip_suffix(')')
+            .and_then(|call| call.split_once('('))
+            .ok_or(format!("Unknown code for Test extension: {code}"))?;
+        let value = Self::argument(arg.trim(), context)?;
+        match function.trim() {
+            "print" => {
+                debug!("Test extension print: {value}");
+                self.messages.push(value);
+                Ok(())
+            }
+            "fail" => Err(value),
+            _ => Err(format!("Unknown code for Test extension: {code}")),
+        }
+    }
+
+    fn fork(&self) -> Box<dyn SemActHandler> {
+        Box::new(TestSemActHandler::new())
+    }
+
+    fn merge(&mut self, other: Box<dyn SemActHandler>) {
+        if let Some(other) = other.as_any().downcast_ref::<TestSemActHandler>() {
+            self.messages.extend(other.messages.iter().cloned())
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Handler that collects values during validation.
+///
+/// The code of the action is the name under which the value is collected.
+/// In a triple constraint the value is the object of the matched triple
+/// and in a shape it is the focus node.
+#[derive(Debug, Default)]
+pub struct ExtractSemActHandler {
+    values: Vec<(String, Node)>,
+}
+
+impl ExtractSemActHandler {
+    pub fn new() -> ExtractSemActHandler {
+        ExtractSemActHandler::default()
+    }
+
+    pub fn iri() -> IriS {
+        IriS::new_unchecked(EXTRACT_EXTENSION)
+    }
+
+    /// Pairs of names and values that have been collected, in the order in which they were found
+    pub fn values(&self) -> &Vec<(String, Node)> {
+        &self.values
+    }
+
+    /// Values that have been collected with some name
+    pub fn values_of(&self, name: &str) -> Vec<&Node> {
+        self.values
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v)
+            .collect()
+    }
+}
+
+impl SemActHandler for ExtractSemActHandler {
+    fn run(&mut self, code: Option<&str>, context: &SemActContext) -> Result<(), String> {
+        let name = code.unwrap_or_default().trim();
+        match context.object().or(context.focus()) {
+            Some(value) => {
+                if !self.values.contains(&(name.to_string(), value.clone())) {
+                    self.values.push((name.to_string(), value.clone()));
+                }
+                Ok(())
+            }
+            None => Err(format!("No value to extract for {name}")),
+        }
+    }
+
+    fn fork(&self) -> Box<dyn SemActHandler> {
+        Box::new(ExtractSemActHandler::new())
+    }
+
+    fn merge(&mut self, other: Box<dyn SemActHandler>) {
+        if let Some(other) = other.as_any().downcast_ref::<ExtractSemActHandler>() {
+            for value in other.values() {
+                if !self.values.contains(value) {
+                    self.values.push(value.clone())
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
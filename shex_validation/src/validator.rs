@@ -5,33 +5,38 @@ use crate::validator_runner::Engine;
 use crate::PosAtom;
 use crate::Reason;
 use crate::ResultValue;
+use crate::SemActContext;
+use crate::SemActHandler;
 use crate::ValidatorConfig;
 use either::Either;
-use prefixmap::IriRef;
+use iri_s::IriS;
 use prefixmap::PrefixMap;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
 use shapemap::query_shape_map::QueryShapeMap;
 use shex_ast::compiled::compiled_schema::CompiledSchema;
-use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::compiled::shape_label::ShapeLabel;
-use shex_ast::object_value::ObjectValue;
 use shex_ast::Node;
 use shex_ast::ShapeExprLabel;
 use shex_ast::ShapeLabelIdx;
-use srdf::SRDF;
+use srdf::{FetchableSRDF, PrefetchedSRDF, QuerySRDF2, SRDF};
+use std::sync::Arc;
 use tracing::debug;
 
 type Result<T> = std::result::Result<T, ValidatorError>;
 type Atom = atom::Atom<(Node, ShapeLabelIdx)>;
 
 pub struct Validator {
-    schema: CompiledSchema,
+    schema: Arc<CompiledSchema>,
     runner: Engine,
 }
 
 impl Validator {
-    pub fn new(schema: CompiledSchema, config: &ValidatorConfig) -> Validator {
+    /// Creates a validator for a schema. The schema can be given in an `Arc`
+    /// to share it between validators without cloning it
+    pub fn new(schema: impl Into<Arc<CompiledSchema>>, config: &ValidatorConfig) -> Validator {
         Validator {
-            schema,
+            schema: schema.into(),
             runner: Engine::new(config),
         }
     }
@@ -42,12 +47,42 @@ impl Validator {
         S: SRDF,
     {
         let idx = self.get_idx(shape)?;
+        self.check_start_acts()?;
         self.runner.add_pending(node.clone(), idx);
         debug!("Before while loop: ${}@{}", node, idx);
         self.loop_validating(rdf)?;
         Ok(())
     }
 
+    /// Registers the handler of the semantic actions of the extension `name`.
+    ///
+    /// A handler for the Test extension is registered by default
+    pub fn add_sem_act_handler(&mut self, name: IriS, handler: Box<dyn SemActHandler>) {
+        self.runner.add_sem_act_handler(name, handler)
+    }
+
+    pub fn with_sem_act_handler(mut self, name: IriS, handler: Box<dyn SemActHandler>) -> Self {
+        self.add_sem_act_handler(name, handler);
+        self
+    }
+
+    /// Obtains the handler registered for the extension `name` if it has type `H`
+    pub fn sem_act_handler<H: SemActHandler + 'static>(&self, name: &IriS) -> Option<&H> {
+        self.runner
+            .sem_act_handler(name)
+            .and_then(|handler| handler.as_any().downcast_ref::<H>())
+    }
+
+    fn check_start_acts(&mut self) -> Result<()> {
+        match self
+            .runner
+            .check_sem_acts(self.schema.start_acts(), &SemActContext::start())
+        {
+            None => Ok(()),
+            Some(error) => Err(error),
+        }
+    }
+
     fn get_shape_expr_label(&mut self, label: &ShapeExprLabel) -> Result<ShapeLabelIdx> {
         self.schema
             .find_ref(label)
@@ -57,61 +92,178 @@ impl Validator {
             })
     }
 
+    /// Validates the atoms of a shape map one after another
     pub fn validate_shapemap<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
     where
-        S: SRDF,
+        S: SRDF + QuerySRDF2,
     {
+        self.check_start_acts()?;
         self.fill_pending(shapemap, rdf)?;
         self.loop_validating(rdf)?;
         Ok(())
     }
 
+    /// Validates a shape map concurrently when the configuration sets `parallel`, and one atom after another otherwise
+    pub fn validate_shapemap_parallel<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
+    where
+        S: SRDF + QuerySRDF2 + Sync,
+    {
+        if !self.runner.config().is_parallel() {
+            return self.validate_shapemap(shapemap, rdf);
+        }
+        self.check_start_acts()?;
+        self.fill_pending(shapemap, rdf)?;
+        let result = self.loop_validating_parallel(rdf);
+        self.runner.clear_neighs_cache();
+        result
+    }
+
     fn fill_pending<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
     where
-        S: SRDF,
+        S: SRDF + QuerySRDF2,
     {
-        for (node_value, label) in shapemap.iter_node_shape(rdf) {
+        let pairs =
+            shapemap
+                .fixed_shape_map(rdf)
+                .map_err(|e| ValidatorError::NodeSelectorError {
+                    error: e.to_string(),
+                })?;
+        for (term, label) in pairs {
             let idx = self.get_shape_expr_label(label)?;
-            let node = self.node_from_object_value(node_value, rdf)?;
-            self.runner.add_pending(node.clone(), idx);
+            let node = Node::from(S::term_as_object(&term));
+            self.runner.add_pending(node, idx);
         }
         Ok(())
     }
 
-    fn node_from_object_value<S>(&mut self, value: &ObjectValue, rdf: &S) -> Result<Node>
+    fn loop_validating<S>(&mut self, rdf: &S) -> Result<()>
     where
         S: SRDF,
     {
-        match value {
-            ObjectValue::IriRef(IriRef::Iri(iri)) => Ok(Node::iri(iri.clone())),
-            ObjectValue::IriRef(IriRef::Prefixed { prefix, local }) => {
-                let iri = rdf.resolve_prefix_local(prefix, local)?;
-                Ok(Node::iri(iri.clone()))
+        let mut result = Ok(());
+        while result.is_ok() && self.runner.no_end_steps() && self.runner.more_pending() {
+            self.runner.new_step();
+            let atom = self.runner.pop_pending().unwrap();
+            debug!("Processing atom: ${atom:?}");
+            // The result is recorded by the runner as a checked atom
+            result = self.check_node_atom(&atom, rdf).map(|_| ());
+        }
+        // The arcs retrieved are only reused during a validation
+        self.runner.clear_neighs_cache();
+        result
+    }
+
+    /// Checks the pending atoms concurrently.
+    ///
+    /// The atoms are split in chunks and each chunk is checked by a forked engine, which handles
+    /// recursive references and hypothetical typings on its own. The engines are merged back in
+    /// the order of the chunks, so the results do not depend on the scheduling of the threads.
+    fn loop_validating_parallel<S>(&mut self, rdf: &S) -> Result<()>
+    where
+        S: SRDF + Sync,
+    {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(self.runner.config().num_threads().unwrap_or_default())
+            .build()
+            .map_err(|e| ValidatorError::ThreadPoolError {
+                error: e.to_string(),
+            })?;
+        while self.runner.no_end_steps() && self.runner.more_pending() {
+            let mut atoms = Vec::new();
+            while atoms.len() < self.runner.max_steps() - self.runner.steps() {
+                match self.runner.pop_pending() {
+                    Some(atom) => atoms.push(atom),
+                    None => break,
+                }
+            }
+            debug!("Processing {} atoms in parallel", atoms.len());
+            let chunk_size = atoms.len().div_ceil(pool.current_num_threads() * 4).max(1);
+            let workers: Vec<(Engine, &[Atom])> = atoms
+                .chunks(chunk_size)
+                .map(|chunk| (self.runner.fork(), chunk))
+                .collect();
+            let schema = &self.schema;
+            let results: Vec<Result<Engine>> = pool.install(|| {
+                workers
+                    .into_par_iter()
+                    .map(|(mut engine, chunk)| {
+                        for atom in chunk {
+                            engine.new_step();
+                            engine.check_atom(atom, schema, rdf)?;
+                        }
+                        Ok(engine)
+                    })
+                    .collect()
+            });
+            for result in results {
+                self.runner.merge(result?);
             }
-            ObjectValue::Literal(_lit) => todo!(),
         }
+        Ok(())
     }
 
-    fn loop_validating<S>(&mut self, rdf: &S) -> Result<()>
+    /// Validates a shape map against data that is fetched asynchronously, for example from a SPARQL endpoint.
+    ///
+    /// Each atom is checked by a forked engine, as in parallel validation. The atoms whose check needed
+    /// data that had not been fetched yet are checked again once all the data missed by the other atoms
+    /// has been fetched, so the queries are sent in batches and the number of rounds depends on the depth
+    /// of the shape references instead of on the number of nodes
+    pub async fn validate_shapemap_async<S>(
+        &mut self,
+        shapemap: &QueryShapeMap,
+        rdf: &PrefetchedSRDF<S>,
+    ) -> Result<()>
     where
-        S: SRDF,
+        S: FetchableSRDF,
     {
+        self.check_start_acts()?;
+        loop {
+            let missed = rdf.missed_lookups();
+            let result = shapemap.fixed_shape_map(rdf);
+            if rdf.missed_lookups() == missed {
+                result.map_err(|e| ValidatorError::NodeSelectorError {
+                    error: e.to_string(),
+                })?;
+                break;
+            }
+            rdf.fetch_misses().await?;
+        }
+        self.fill_pending(shapemap, rdf)?;
         while self.runner.no_end_steps() && self.runner.more_pending() {
-            self.runner.new_step();
-            let atom = self.runner.pop_pending().unwrap();
-            debug!("Processing atom: ${atom:?}");
-            self.runner.add_processing(&atom);
-            let passed = self.check_node_atom(&atom, rdf)?;
-            self.runner.remove_processing(&atom);
-            match passed {
-                Either::Right(reasons) => {
-                    self.runner.add_checked_pos(atom, reasons);
+            let mut atoms = Vec::new();
+            while atoms.len() < self.runner.max_steps() - self.runner.steps() {
+                match self.runner.pop_pending() {
+                    Some(atom) => atoms.push(atom),
+                    None => break,
+                }
+            }
+            let mut engines: Vec<Option<Engine>> = atoms.iter().map(|_| None).collect();
+            let mut unchecked: Vec<usize> = (0..atoms.len()).collect();
+            while !unchecked.is_empty() {
+                debug!("Checking {} atoms with fetched data", unchecked.len());
+                let mut incomplete = Vec::new();
+                for index in unchecked {
+                    let missed = rdf.missed_lookups();
+                    let mut engine = self.runner.fork();
+                    engine.new_step();
+                    let result = engine.check_atom(&atoms[index], &self.schema, rdf);
+                    if rdf.missed_lookups() > missed {
+                        incomplete.push(index)
+                    } else {
+                        result?;
+                        engines[index] = Some(engine);
+                    }
                 }
-                Either::Left(errors) => {
-                    self.runner.add_checked_neg(atom.negated(), errors);
+                if !incomplete.is_empty() {
+                    rdf.fetch_misses().await?;
                 }
+                unchecked = incomplete;
+            }
+            for engine in engines.into_iter().flatten() {
+                self.runner.merge(engine);
             }
         }
+        self.runner.clear_neighs_cache();
         Ok(())
     }
 
@@ -123,15 +275,7 @@ impl Validator {
     where
         S: SRDF,
     {
-        let (node, idx) = atom.get_value();
-        let se = find_shape_idx(idx, &self.schema);
-        match atom {
-            Atom::Pos { .. } => self.runner.check_node_shape_expr(node, se, rdf),
-            Atom::Neg { .. } => {
-                // Check if a node doesn't conform to a shape expr
-                todo!()
-            }
-        }
+        self.runner.check_atom(atom, &self.schema, rdf)
     }
 
     pub fn get_result(&self, node: &Node, shape: &ShapeLabel) -> Result<ResultValue> {
@@ -194,10 +338,713 @@ impl Validator {
     }
 }
 
-fn find_shape_idx<'a>(idx: &'a ShapeLabelIdx, schema: &'a CompiledSchema) -> &'a ShapeExpr {
-    let (_label, se) = schema.find_shape_idx(idx).unwrap();
-    se
-}
-
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::{ExtractSemActHandler, NeighsMode, TestSemActHandler, UnknownSemActs};
+    use iri_s::IriS;
+    use shapemap::{ResultShapeMap, ValidationStatus};
+    use shex_ast::ShapeExprLabel;
+    use shex_compact::{ShExParser, ShapeMapParser};
+    use sparql_service::{LocalEndpoint, RdfData};
+    use srdf::{FetchConfig, Object, RDFFormat, ReaderMode, SRDFGraph, SRDFSparql};
+
+    fn validate(schema: &str, data: &str, node: &str, shape: &str) -> Result<ResultValue> {
+        let validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        validate_with(validator, data, node, shape).map(|(result, _)| result)
+    }
+
+    fn compile(schema: &str) -> CompiledSchema {
+        let schema = ShExParser::parse(schema, None).unwrap();
+        let mut compiled_schema = CompiledSchema::new();
+        compiled_schema.from_schema_json(&schema).unwrap();
+        compiled_schema
+    }
+
+    fn validate_with(
+        mut validator: Validator,
+        data: &str,
+        node: &str,
+        shape: &str,
+    ) -> Result<(ResultValue, Validator)> {
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let node = Node::iri(IriS::new_unchecked(node));
+        let shape = ShapeLabel::iri(IriS::new_unchecked(shape));
+        validator.validate_node_shape(&node, &shape, &graph)?;
+        let result = validator.get_result(&node, &shape)?;
+        Ok((result, validator))
+    }
+
+    #[test]
+    fn test_shape_or() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+            :S xsd:string OR xsd:integer"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p 1 ."#;
+        let result = validate(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(result.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_shape_not() {
+        let schema = r#"prefix : <http://example.org/>
+            :S NOT { :p . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :q 1 .
+            :y :p 1 ."#;
+        let ok = validate(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(ok.unwrap(), ResultValue::Ok);
+        let failed = validate(schema, data, "http://example.org/y", "http://example.org/S");
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_shape_ref_recursive() {
+        let schema = r#"prefix : <http://example.org/>
+            :Person { :knows @:Person * }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :knows :bob .
+            :bob :knows :alice ."#;
+        let result = validate(
+            schema,
+            data,
+            "http://example.org/alice",
+            "http://example.org/Person",
+        );
+        assert_eq!(result.unwrap(), ResultValue::Ok);
+    }
+
+    #[test]
+    fn test_shape_ref_failed() {
+        let schema = r#"prefix : <http://example.org/>
+            :Person { :knows @:Person }
+            :Other { :name . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :knows :bob .
+            :bob :name "Bob" ."#;
+        let result = validate(
+            schema,
+            data,
+            "http://example.org/alice",
+            "http://example.org/Person",
+        );
+        assert_eq!(result.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_or_with_refs() {
+        let schema = r#"prefix : <http://example.org/>
+            :S @:A OR @:B
+            :A { :a . }
+            :B { :b . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :b 1 .
+            :y :a 1 .
+            :z :c 1 ."#;
+        let result = validate(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(result.unwrap(), ResultValue::Ok);
+
+        // the failed references of the other branches don't make the node nonconformant
+        let mut validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        for node in ["x", "y", "z"] {
+            let node = format!("http://example.org/{node}");
+            validator = validate_with(validator, data, &node, "http://example.org/S")
+                .unwrap()
+                .1;
+        }
+        let result_map = validator.result_map(None).unwrap().result_shape_map();
+        let s = ShapeExprLabel::iri_unchecked("http://example.org/S");
+        let status = |node: &str| {
+            let node = Object::iri(IriS::new_unchecked(&format!("http://example.org/{node}")));
+            result_map.get_status(&node, &s).cloned()
+        };
+        assert_eq!(status("x"), Some(ValidationStatus::Conformant));
+        assert_eq!(status("y"), Some(ValidationStatus::Conformant));
+        assert_eq!(status("z"), Some(ValidationStatus::NonConformant));
+    }
+
+    #[test]
+    fn test_extra() {
+        let schema = r#"prefix : <http://example.org/>
+            :S EXTRA :p { :p [:a] ; :q [:a] ? }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p :a, :b .
+            :y :p :b .
+            :z :p :a ; :q :a, :b ."#;
+        for (node, expected) in [
+            ("x", ResultValue::Ok),
+            ("y", ResultValue::Failed),
+            ("z", ResultValue::Failed),
+        ] {
+            let result = validate(
+                schema,
+                data,
+                &format!("http://example.org/{node}"),
+                "http://example.org/S",
+            );
+            assert_eq!(result.unwrap(), expected, "{node}");
+        }
+    }
+
+    #[test]
+    fn test_extends() {
+        let schema = r#"prefix : <http://example.org/>
+            :Person { :name . }
+            :Employee extends @:Person { :salary . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :salary 100 .
+            :bob :name "Bob" ."#;
+        let ok = validate(
+            schema,
+            data,
+            "http://example.org/alice",
+            "http://example.org/Employee",
+        );
+        assert_eq!(ok.unwrap(), ResultValue::Ok);
+        let failed = validate(
+            schema,
+            data,
+            "http://example.org/bob",
+            "http://example.org/Employee",
+        );
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+        let person = validate(
+            schema,
+            data,
+            "http://example.org/bob",
+            "http://example.org/Person",
+        );
+        assert_eq!(person.unwrap(), ResultValue::Ok);
+    }
+
+    #[test]
+    fn test_extends_partition() {
+        let schema = r#"prefix : <http://example.org/>
+            :S { :p . }
+            :T extends @:S { :p . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p 1, 2 .
+            :y :p 1 ."#;
+        let ok = validate(schema, data, "http://example.org/x", "http://example.org/T");
+        assert_eq!(ok.unwrap(), ResultValue::Ok);
+        let failed = validate(schema, data, "http://example.org/y", "http://example.org/T");
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_extends_many_triples_and_parents() {
+        let schema = r#"prefix : <http://example.org/>
+            :A { :p [:a] }
+            :B { :p [:b] }
+            :C { :p [:c] }
+            :T extends @:A extends @:B extends @:C { :p . * }"#;
+        let mut values: Vec<String> = vec![":c".to_string(), ":b".to_string(), ":a".to_string()];
+        values.extend((4..=20).map(|n| format!(":v{n}")));
+        let data = format!(
+            "prefix : <http://example.org/>\n:x :p {} .\n:y :p {} .",
+            values.join(", "),
+            values[..2].join(", ")
+        );
+        let ok = validate(
+            schema,
+            &data,
+            "http://example.org/x",
+            "http://example.org/T",
+        );
+        assert_eq!(ok.unwrap(), ResultValue::Ok);
+        let failed = validate(
+            schema,
+            &data,
+            "http://example.org/y",
+            "http://example.org/T",
+        );
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_abstract() {
+        let schema = r#"prefix : <http://example.org/>
+            ABSTRACT :Person { :name . }
+            :Employee extends @:Person { :salary . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :salary 100 .
+            :bob :name "Bob" ."#;
+        let ok = validate(
+            schema,
+            data,
+            "http://example.org/alice",
+            "http://example.org/Person",
+        );
+        assert_eq!(ok.unwrap(), ResultValue::Ok);
+        let failed = validate(
+            schema,
+            data,
+            "http://example.org/bob",
+            "http://example.org/Person",
+        );
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_sem_act_test_extension() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix Test: <http://shex.io/extensions/Test/>
+            :S { :p . %Test:{ print(o) %} }
+            :T { :p . %Test:{ fail("unexpected") %} }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p "a" ."#;
+        let validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        let (ok, validator) = validate_with(
+            validator,
+            data,
+            "http://example.org/x",
+            "http://example.org/S",
+        )
+        .unwrap();
+        assert_eq!(ok, ResultValue::Ok);
+        let handler = validator
+            .sem_act_handler::<TestSemActHandler>(&TestSemActHandler::iri())
+            .unwrap();
+        assert_eq!(handler.messages().len(), 1);
+        let failed = validate(schema, data, "http://example.org/x", "http://example.org/T");
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+    }
+
+    fn test_messages(
+        schema: &str,
+        data: &str,
+        node: &str,
+        shape: &str,
+    ) -> (ResultValue, Vec<String>) {
+        let validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        let (result, validator) = validate_with(validator, data, node, shape).unwrap();
+        let handler = validator
+            .sem_act_handler::<TestSemActHandler>(&TestSemActHandler::iri())
+            .unwrap();
+        (result, handler.messages().clone())
+    }
+
+    #[test]
+    fn test_sem_acts_follow_the_chosen_assignment() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix Test: <http://shex.io/extensions/Test/>
+            :S { :p . %Test:{ print(o) %} ; :p [:b] }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p :a, :b ."#;
+        let (result, messages) =
+            test_messages(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(result, ResultValue::Ok);
+        assert_eq!(messages, vec!["http://example.org/a".to_string()]);
+    }
+
+    #[test]
+    fn test_sem_acts_not_run_for_refuted_assumptions() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix Test: <http://shex.io/extensions/Test/>
+            :S { :p @:T } AND { :r . }
+            :T { :p @:S %Test:{ print(o) %} }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p :y .
+            :y :p :x ."#;
+        let (result, messages) =
+            test_messages(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(result, ResultValue::Failed);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn test_failed_sem_act_fails_referencing_shapes() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix Test: <http://shex.io/extensions/Test/>
+            :S { :p @:T %Test:{ print(o) %} }
+            :T { :q . %Test:{ fail("unexpected") %} }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p :y .
+            :y :q 1 ."#;
+        let (result, messages) =
+            test_messages(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(result, ResultValue::Failed);
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn test_sem_act_extract() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix Extract: <http://www.weso.es/shex/extensions/Extract/>
+            :S { :name . %Extract:{ name %} ; :knows . * }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :name "Alice" ; :knows :y ."#;
+        let validator = Validator::new(compile(schema), &ValidatorConfig::default())
+            .with_sem_act_handler(
+                ExtractSemActHandler::iri(),
+                Box::new(ExtractSemActHandler::new()),
+            );
+        let (ok, validator) = validate_with(
+            validator,
+            data,
+            "http://example.org/x",
+            "http://example.org/S",
+        )
+        .unwrap();
+        assert_eq!(ok, ResultValue::Ok);
+        let handler = validator
+            .sem_act_handler::<ExtractSemActHandler>(&ExtractSemActHandler::iri())
+            .unwrap();
+        assert_eq!(handler.values().len(), 1);
+        assert_eq!(handler.values_of("name").len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_sem_act() {
+        let schema = r#"prefix : <http://example.org/>
+            :S { :p . %<http://example.org/Unknown>{ code %} }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p 1 ."#;
+        let ignored = validate(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(ignored.unwrap(), ResultValue::Ok);
+        let config = ValidatorConfig {
+            unknown_sem_acts: Some(UnknownSemActs::Fail),
+            ..Default::default()
+        };
+        let validator = Validator::new(compile(schema), &config);
+        let (failed, _) = validate_with(
+            validator,
+            data,
+            "http://example.org/x",
+            "http://example.org/S",
+        )
+        .unwrap();
+        assert_eq!(failed, ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_blank_and_literal_nodes() {
+        let schema = r#"prefix : <http://example.org/>
+            :S { :p @:T ; :r @:U }
+            :T { :q . }
+            :U { :q . ? }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p [ :q 1 ] ; :r 2 .
+            :y :p [ :other 1 ] ; :r 2 ."#;
+        let ok = validate(schema, data, "http://example.org/x", "http://example.org/S");
+        assert_eq!(ok.unwrap(), ResultValue::Ok);
+        let failed = validate(schema, data, "http://example.org/y", "http://example.org/S");
+        assert_eq!(failed.unwrap(), ResultValue::Failed);
+    }
+
+    #[test]
+    fn test_neighs_mode_shape_preds() {
+        let schema = r#"prefix : <http://example.org/>
+            :S CLOSED { :p . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p 1 .
+            :y :p 1 ; :q 2 ."#;
+        for neighs_mode in [NeighsMode::AllArcs, NeighsMode::ShapePreds] {
+            let config = ValidatorConfig {
+                neighs_mode: Some(neighs_mode),
+                ..Default::default()
+            };
+            let validator = Validator::new(compile(schema), &config);
+            let (ok, _) = validate_with(
+                validator,
+                data,
+                "http://example.org/x",
+                "http://example.org/S",
+            )
+            .unwrap();
+            assert_eq!(ok, ResultValue::Ok);
+            let validator = Validator::new(compile(schema), &config);
+            let (failed, _) = validate_with(
+                validator,
+                data,
+                "http://example.org/y",
+                "http://example.org/S",
+            )
+            .unwrap();
+            assert_eq!(failed, ResultValue::Failed);
+        }
+    }
+
+    #[test]
+    fn test_negation_cycle() {
+        let schema = r#"prefix : <http://example.org/>
+            :S NOT { :p @:S }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p :x ."#;
+        let result = validate(schema, data, "http://example.org/x", "http://example.org/S");
+        assert!(matches!(
+            result,
+            Err(ValidatorError::NegationCycleError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parallel_validation() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+            :Person { :name xsd:string ; :knows @:Person * }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob, :carol .
+            :bob   :name "Bob" ; :knows :alice .
+            :carol :name "Carol" ; :knows :dave .
+            :dave  :name "Dave" ; :knows :carol, :eve .
+            :eve   :knows :alice .
+            :frank :name "Frank" ; :knows :frank .
+            :grace :name "Grace" ."#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let nodes = ["alice", "bob", "carol", "dave", "eve", "frank", "grace"];
+        let shape = ShapeLabel::iri(IriS::new_unchecked("http://example.org/Person"));
+        let shapemap = nodes
+            .map(|name| format!("<http://example.org/{name}>@<http://example.org/Person>"))
+            .join(",");
+        let shapemap = ShapeMapParser::parse(&shapemap, &None, &None).unwrap();
+        let rdf = RdfData::from_graph(graph).unwrap();
+        let run = |parallel: bool, num_threads: Option<usize>| {
+            let config = ValidatorConfig {
+                parallel: Some(parallel),
+                num_threads,
+                ..Default::default()
+            };
+            let mut validator = Validator::new(compile(schema), &config);
+            validator
+                .validate_shapemap_parallel(&shapemap, &rdf)
+                .unwrap();
+            validator
+        };
+        let sequential = run(false, None);
+        let parallel = run(true, Some(4));
+        for name in nodes {
+            let node = Node::iri(IriS::new_unchecked(&format!("http://example.org/{name}")));
+            assert_eq!(
+                parallel.get_result(&node, &shape).unwrap(),
+                sequential.get_result(&node, &shape).unwrap(),
+                "Result of {name}"
+            );
+        }
+        let alice = Node::iri(IriS::new_unchecked("http://example.org/alice"));
+        let frank = Node::iri(IriS::new_unchecked("http://example.org/frank"));
+        assert_eq!(
+            parallel.get_result(&alice, &shape).unwrap(),
+            ResultValue::Failed
+        );
+        assert_eq!(
+            parallel.get_result(&frank, &shape).unwrap(),
+            ResultValue::Ok
+        );
+        let result_map = parallel.result_map(None).unwrap().to_string();
+        assert_eq!(
+            run(true, Some(1)).result_map(None).unwrap().to_string(),
+            result_map
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_validation_with_endpoint() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+            :Person { :name xsd:string ; :knows @:Person * }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob, :carol .
+            :bob   :name "Bob" ; :knows :alice .
+            :carol :name "Carol" ; :knows :dave .
+            :dave  :name "Dave" ; :knows :carol, :eve .
+            :eve   :knows :alice .
+            :frank :name "Frank" ; :knows :frank .
+            :grace :name "Grace" ."#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let nodes = ["alice", "bob", "carol", "frank", "grace"];
+        let shapemap = nodes
+            .map(|name| format!("<http://example.org/{name}>@<http://example.org/Person>"))
+            .join(",");
+        let shapemap = ShapeMapParser::parse(&shapemap, &None, &None).unwrap();
+        let mut sequential = Validator::new(compile(schema), &ValidatorConfig::default());
+        let local = RdfData::from_graph(graph.clone()).unwrap();
+        sequential.validate_shapemap(&shapemap, &local).unwrap();
+
+        let endpoint = LocalEndpoint::new(&graph).unwrap();
+        // The first request fails and is retried
+        endpoint.fail_next(1);
+        let config = FetchConfig::new()
+            .with_batch_size(3)
+            .with_parallelism(2)
+            .with_backoff_ms(10);
+        let rdf = PrefetchedSRDF::new(SRDFSparql::new(endpoint.iri()).unwrap(), &config);
+        let mut validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        validator
+            .validate_shapemap_async(&shapemap, &rdf)
+            .await
+            .unwrap();
+        let shape = ShapeLabel::iri(IriS::new_unchecked("http://example.org/Person"));
+        for name in nodes {
+            let node = Node::iri(IriS::new_unchecked(&format!("http://example.org/{name}")));
+            assert_eq!(
+                validator.get_result(&node, &shape).unwrap(),
+                sequential.get_result(&node, &shape).unwrap(),
+                "Result of {name}"
+            );
+        }
+        // The 5 nodes of the shape map in batches of 3 and the retry, then dave, which is
+        // referenced by carol, and eve, which is referenced by dave
+        assert_eq!(endpoint.requests(), 5);
+    }
+
+    #[test]
+    fn test_validation_with_sparql_endpoint() {
+        let schema = r#"prefix : <http://example.org/>
+            :Person { :name . ; :knows @:Person * }
+            :Named CLOSED { :name . }"#;
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob, _:anon .
+            :bob   :name "Bob" .
+            :carol :name "Carol" ; :age 30 .
+            _:anon :name "Anon" ."#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let endpoint = LocalEndpoint::new(&graph).unwrap();
+        let shapemap = ShapeMapParser::parse(
+            r#"<http://example.org/alice>@<http://example.org/Person>,
+               <http://example.org/bob>@<http://example.org/Person>,
+               <http://example.org/bob>@<http://example.org/Named>,
+               <http://example.org/carol>@<http://example.org/Named>"#,
+            &None,
+            &None,
+        )
+        .unwrap();
+        let expected = [
+            ("alice", "Person", ResultValue::Failed),
+            ("bob", "Person", ResultValue::Ok),
+            ("bob", "Named", ResultValue::Ok),
+            ("carol", "Named", ResultValue::Failed),
+        ];
+        let check = |validator: &Validator| {
+            for (node, shape, result) in &expected {
+                let node = Node::iri(IriS::new_unchecked(&format!("http://example.org/{node}")));
+                let shape =
+                    ShapeLabel::iri(IriS::new_unchecked(&format!("http://example.org/{shape}")));
+                assert_eq!(validator.get_result(&node, &shape).unwrap(), *result);
+            }
+        };
+
+        // Blank nodes returned by an endpoint can't be queried, so alice fails without aborting the validation
+        let config = ValidatorConfig {
+            neighs_mode: Some(NeighsMode::ShapePreds),
+            ..ValidatorConfig::default()
+        };
+        let mut validator = Validator::new(compile(schema), &config);
+        let rdf = SRDFSparql::new(endpoint.iri()).unwrap();
+        validator.validate_shapemap(&shapemap, &rdf).unwrap();
+        check(&validator);
+        // One query for each node and shape, as the remainder of open shapes is not retrieved
+        assert_eq!(endpoint.requests(), 4);
+
+        let mut validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        let rdf = RdfData::from_endpoint(SRDFSparql::new(endpoint.iri()).unwrap());
+        validator.validate_shapemap(&shapemap, &rdf).unwrap();
+        check(&validator);
+        // All the arcs of each node are retrieved once
+        assert_eq!(endpoint.requests(), 4 + 3);
+    }
+
+    #[test]
+    fn test_result_map_json_blank_nodes() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+            :S { :p @:T }
+            :T { :q xsd:string }"#;
+        let data = r#"prefix : <http://example.org/>
+            :x :p [ :q 1 ] ."#;
+        let validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        let (result, validator) = validate_with(
+            validator,
+            data,
+            "http://example.org/x",
+            "http://example.org/S",
+        )
+        .unwrap();
+        assert_eq!(result, ResultValue::Failed);
+        let json =
+            serde_json::to_value(validator.result_map(None).unwrap().result_shape_map()).unwrap();
+        let entries = json.as_array().unwrap();
+        let bnode = entries
+            .iter()
+            .find(|e| e["shape"] == "http://example.org/T")
+            .unwrap()["node"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(bnode.starts_with("_:") && !bnode.starts_with("_:_:"));
+        let x = entries
+            .iter()
+            .find(|e| e["node"] == "http://example.org/x")
+            .unwrap();
+        let error = &x["appinfo"]["errors"][0];
+        assert_eq!(error["type"], "TripleConstraintError");
+        assert_eq!(error["object"], bnode);
+        assert!(x["reason"].as_str().unwrap().contains(&bnode));
+    }
+
+    #[test]
+    fn test_result_map_json() {
+        let schema = r#"prefix : <http://example.org/>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+            :S { :p xsd:integer ; :q . + }"#;
+        let data = r#"prefix : <http://example.org/>
+            :ok :p 1 ; :q 2 .
+            :value :p "one" ; :q 2 .
+            :card :p 1 ."#;
+        let mut validator = Validator::new(compile(schema), &ValidatorConfig::default());
+        for node in ["ok", "value", "card"] {
+            let node = format!("http://example.org/{node}");
+            (_, validator) = validate_with(validator, data, &node, "http://example.org/S").unwrap();
+        }
+        let json =
+            serde_json::to_value(validator.result_map(None).unwrap().result_shape_map()).unwrap();
+        let entry = |node: &str| {
+            json.as_array()
+                .unwrap()
+                .iter()
+                .find(|e| e["node"] == format!("http://example.org/{node}"))
+                .unwrap()
+                .clone()
+        };
+
+        let ok = entry("ok");
+        assert_eq!(ok["status"], "conformant");
+        assert_eq!(ok["shape"], "http://example.org/S");
+        let matches = &ok["appinfo"]["reasons"][0]["matches"];
+        assert_eq!(matches[0]["predicate"], "http://example.org/p");
+        assert_eq!(matches[1]["predicate"], "http://example.org/q");
+
+        let value = entry("value");
+        assert_eq!(value["status"], "nonconformant");
+        let error = &value["appinfo"]["errors"][0];
+        assert_eq!(error["type"], "TripleConstraintError");
+        assert_eq!(error["predicate"], "http://example.org/p");
+
+        let card = entry("card");
+        let error = &card["appinfo"]["errors"][0];
+        assert_eq!(error["type"], "CardinalityError");
+        let cards = error["cardinalities"].as_array().unwrap();
+        let q = cards
+            .iter()
+            .find(|c| c["predicate"] == "http://example.org/q")
+            .unwrap();
+        assert_eq!(q["found"], 0);
+        assert_eq!(q["expected"], "+");
+
+        // The JSON result map can be read back as a result shape map
+        let result_map = validator.result_map(None).unwrap().result_shape_map();
+        let parsed = ResultShapeMap::from_json_str(&json.to_string()).unwrap();
+        assert!(parsed.iter().eq(result_map.iter()));
+        let card = Object::iri(IriS::new_unchecked("http://example.org/card"));
+        let s = ShapeExprLabel::iri_unchecked("http://example.org/S");
+        assert_eq!(
+            parsed.get_status(&card, &s),
+            Some(&ValidationStatus::NonConformant)
+        );
+    }
+}
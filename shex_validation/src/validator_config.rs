@@ -17,6 +17,40 @@ pub struct ValidatorConfig {
 
     /// Configuration of ShEx schemas
     pub shex_config: Option<ShExConfig>,
+
+    /// Behaviour with semantic actions whose extension has no registered handler
+    pub unknown_sem_acts: Option<UnknownSemActs>,
+
+    /// Strategy to retrieve the neighbourhood of the nodes from the RDF data
+    pub neighs_mode: Option<NeighsMode>,
+
+    /// Validate the atoms of a shape map concurrently in `Validator::validate_shapemap_parallel` (`false` by default)
+    pub parallel: Option<bool>,
+
+    /// Number of threads employed in parallel validation. By default, the number of available CPUs
+    pub num_threads: Option<usize>,
+}
+
+/// Strategy to retrieve the neighbourhood of the nodes that are validated
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub enum NeighsMode {
+    /// All the outgoing arcs of a node are retrieved at once and reused for every shape that it is checked against
+    #[default]
+    AllArcs,
+
+    /// Only the values of the predicates mentioned in the shape are retrieved each time a node is checked
+    ShapePreds,
+}
+
+/// Behaviour of the validator when it finds a semantic action of an extension without handler
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub enum UnknownSemActs {
+    /// The semantic action is skipped
+    #[default]
+    Ignore,
+
+    /// The semantic action fails
+    Fail,
 }
 
 impl Default for ValidatorConfig {
@@ -25,6 +59,10 @@ impl Default for ValidatorConfig {
             max_steps: MAX_STEPS,
             data_config: Some(RdfDataConfig::default()),
             shex_config: Some(ShExConfig::default()),
+            unknown_sem_acts: Some(UnknownSemActs::default()),
+            neighs_mode: Some(NeighsMode::default()),
+            parallel: Some(false),
+            num_threads: None,
         }
     }
 }
@@ -55,6 +93,26 @@ impl ValidatorConfig {
         self.max_steps
     }
 
+    pub fn unknown_sem_acts(&self) -> UnknownSemActs {
+        self.unknown_sem_acts.clone().unwrap_or_default()
+    }
+
+    pub fn neighs_mode(&self) -> NeighsMode {
+        self.neighs_mode.clone().unwrap_or_default()
+    }
+
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = Some(parallel);
+    }
+
+    pub fn is_parallel(&self) -> bool {
+        self.parallel.unwrap_or(false)
+    }
+
+    pub fn num_threads(&self) -> Option<usize> {
+        self.num_threads
+    }
+
     pub fn shex_config(&self) -> ShExConfig {
         match &self.shex_config {
             None => ShExConfig::default(),
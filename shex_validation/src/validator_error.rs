@@ -1,21 +1,32 @@
 use std::fmt::Display;
 
+use iri_s::IriS;
 use prefixmap::PrefixMapError;
-use rbe::RbeError;
+use rbe::{CardinalityCounts, RbeError};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
 use shex_ast::compiled::preds::Preds;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::{
     compiled::shape_label::ShapeLabel, CompiledSchemaError, Node, Pred, ShapeExprLabel,
     ShapeLabelIdx,
 };
-use srdf::Object;
+use srdf::{FetchError, Object};
 use thiserror::Error;
 
+use crate::Reasons;
+
 #[derive(Error, Debug, Clone)]
 pub enum ValidatorError {
     #[error("SRDF Error: {error}")]
     SRDFError { error: String },
 
+    #[error("Error obtaining the neighbourhood of {node}: {error}")]
+    NeighsError { node: Node, error: String },
+
+    #[error(transparent)]
+    FetchError(#[from] FetchError),
+
     #[error("Not found shape label {shape}")]
     NotFoundShapeLabel { shape: ShapeLabel },
 
@@ -32,7 +43,7 @@ pub enum ValidatorError {
     ClosedShapeWithRemainderPreds { remainder: Preds, declared: Preds },
 
     #[error(transparent)]
-    RbeError(#[from] RbeError<Pred, Node, ShapeLabelIdx>),
+    RbeError(Box<RbeError<Pred, Node, ShapeLabelIdx>>),
 
     #[error(transparent)]
     PrefixMapError(#[from] PrefixMapError),
@@ -45,16 +56,96 @@ pub enum ValidatorError {
 
     #[error("And error: shape expression {shape_expr} failed for node {node}: {errors}")]
     ShapeAndError {
-        shape_expr: ShapeExpr,
+        shape_expr: Box<ShapeExpr>,
+        node: Node,
+        errors: ValidatorErrors,
+    },
+
+    #[error(
+        "Or error: none of the shape expressions in {shape_expr} passed for node {node}: {errors}"
+    )]
+    ShapeOrError {
+        shape_expr: Box<ShapeExpr>,
+        node: Node,
+        errors: ValidatorErrors,
+    },
+
+    #[error(
+        "Not error: node {node} conforms to the negated shape expression {shape_expr}: {reasons}"
+    )]
+    ShapeNotError {
+        shape_expr: Box<ShapeExpr>,
         node: Node,
+        reasons: Reasons,
+    },
+
+    #[error("Shape reference @{idx} failed for node {node}: {errors}")]
+    ShapeRefFailed {
+        node: Node,
+        idx: ShapeLabelIdx,
+        errors: ValidatorErrors,
+    },
+
+    #[error("Node {node} conforms to shape @{idx} which was expected to fail: {reasons}")]
+    NegAtomFailed {
+        node: Node,
+        idx: ShapeLabelIdx,
+        reasons: Reasons,
+    },
+
+    #[error(
+        "Node {node} can only conform to abstract shape @{idx} through a shape that extends it"
+    )]
+    AbstractShapeError { node: Node, idx: ShapeLabelIdx },
+
+    #[error("Triple {node} {predicate} {object} doesn't match the triple constraint {constraint}: {errors}")]
+    TripleConstraintError {
+        node: Box<Node>,
+        predicate: Pred,
+        object: Box<Node>,
+        constraint: String,
         errors: ValidatorErrors,
     },
 
+    #[error("The triples of node {node} don't match the cardinalities of the shape: {counts}")]
+    CardinalityError {
+        node: Node,
+        counts: CardinalityCounts<Pred>,
+    },
+
+    #[error("No partition of the neighbourhood of {node} satisfies the extended shapes: {errors}")]
+    ShapeExtendsError { node: Node, errors: ValidatorErrors },
+
+    #[error("Semantic action {name} failed: {error}")]
+    SemActFailed { name: IriS, error: String },
+
+    #[error("No handler registered for the extension {name} of a semantic action")]
+    UnknownSemAct { name: IriS },
+
+    #[error("Shape label with index {idx} not found in schema")]
+    ShapeLabelIdxNotFound { idx: ShapeLabelIdx },
+
+    #[error("Negation cycle: reference to @{idx} for node {node} depends on itself through a NOT")]
+    NegationCycleError { node: Node, idx: ShapeLabelIdx },
+
+    #[error("Error selecting nodes from shape map: {error}")]
+    NodeSelectorError { error: String },
+
     #[error("Error reading config file from path {path}: {error}")]
     ValidatorConfigFromPathError { path: String, error: String },
 
     #[error("Error reading config file from path {path}: {error}")]
     ValidatorConfigYamlError { path: String, error: String },
+
+    #[error("Error creating the thread pool for parallel validation: {error}")]
+    ThreadPoolError { error: String },
+}
+
+// the errors of the regular bag expressions are boxed, as they are much larger than the others
+impl From<RbeError<Pred, Node, ShapeLabelIdx>> for ValidatorError {
+    fn from(error: RbeError<Pred, Node, ShapeLabelIdx>) -> Self {
+        ValidatorError::RbeError(Box::new(error))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -76,3 +167,113 @@ impl Display for ValidatorErrors {
         Ok(())
     }
 }
+
+impl Serialize for ValidatorErrors {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.errs.len()))?;
+        for err in &self.errs {
+            seq.serialize_element(err)?;
+        }
+        seq.end()
+    }
+}
+
+impl ValidatorError {
+    fn name(&self) -> &'static str {
+        match self {
+            ValidatorError::ClosedShapeWithRemainderPreds { .. } => "ClosedShapeError",
+            ValidatorError::RbeError(_) => "RbeError",
+            ValidatorError::ShapeAndError { .. } => "ShapeAndError",
+            ValidatorError::ShapeOrError { .. } => "ShapeOrError",
+            ValidatorError::ShapeNotError { .. } => "ShapeNotError",
+            ValidatorError::ShapeRefFailed { .. } => "ShapeRefFailed",
+            ValidatorError::NegAtomFailed { .. } => "NegAtomFailed",
+            ValidatorError::AbstractShapeError { .. } => "AbstractShapeError",
+            ValidatorError::TripleConstraintError { .. } => "TripleConstraintError",
+            ValidatorError::CardinalityError { .. } => "CardinalityError",
+            ValidatorError::ShapeExtendsError { .. } => "ShapeExtendsError",
+            ValidatorError::SemActFailed { .. } => "SemActFailed",
+            ValidatorError::UnknownSemAct { .. } => "UnknownSemAct",
+            ValidatorError::NegationCycleError { .. } => "NegationCycleError",
+            _ => "ValidatorError",
+        }
+    }
+}
+
+/// Errors are serialized with their type, a message and, when available,
+/// the triples, cardinalities and nested errors that explain them
+impl Serialize for ValidatorError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", self.name())?;
+        match self {
+            ValidatorError::ClosedShapeWithRemainderPreds {
+                remainder,
+                declared,
+            } => {
+                let remainder: Vec<_> = remainder.preds().iter().map(|p| p.to_string()).collect();
+                let declared: Vec<_> = declared.preds().iter().map(|p| p.to_string()).collect();
+                map.serialize_entry("remainder", &remainder)?;
+                map.serialize_entry("declared", &declared)?;
+            }
+            ValidatorError::ShapeAndError { node, errors, .. }
+            | ValidatorError::ShapeOrError { node, errors, .. }
+            | ValidatorError::ShapeExtendsError { node, errors } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("errors", errors)?;
+            }
+            ValidatorError::ShapeRefFailed { node, idx, errors } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape", &idx.to_string())?;
+                map.serialize_entry("errors", errors)?;
+            }
+            ValidatorError::ShapeNotError { node, reasons, .. } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("reasons", reasons)?;
+            }
+            ValidatorError::NegAtomFailed { node, idx, reasons } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("shape", &idx.to_string())?;
+                map.serialize_entry("reasons", reasons)?;
+            }
+            ValidatorError::TripleConstraintError {
+                node,
+                predicate,
+                object,
+                constraint,
+                errors,
+            } => {
+                map.serialize_entry("node", &node.to_string())?;
+                map.serialize_entry("predicate", &predicate.to_string())?;
+                map.serialize_entry("object", &object.to_string())?;
+                map.serialize_entry("constraint", constraint)?;
+                map.serialize_entry("errors", errors)?;
+            }
+            ValidatorError::CardinalityError { node, counts } => {
+                map.serialize_entry("node", &node.to_string())?;
+                let counts: Vec<_> = counts
+                    .iter()
+                    .map(|count| {
+                        serde_json::json!({
+                            "predicate": count.key.to_string(),
+                            "constraint": count.cond,
+                            "expected": count.expected.to_string(),
+                            "found": count.found,
+                        })
+                    })
+                    .collect();
+                map.serialize_entry("cardinalities", &counts)?;
+            }
+            _ => {
+                map.serialize_entry("message", &self.to_string())?;
+            }
+        }
+        map.end()
+    }
+}
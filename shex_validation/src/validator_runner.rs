@@ -1,21 +1,32 @@
 use crate::atom;
 use crate::validator_error::*;
+use crate::NeighsMode;
 use crate::Reason;
+use crate::Reasons;
 use crate::ResultValue;
+use crate::SemActContext;
+use crate::SemActHandler;
+use crate::TestSemActHandler;
+use crate::TripleMatch;
+use crate::UnknownSemActs;
 use crate::ValidatorConfig;
 use either::Either;
 use indexmap::IndexSet;
 use iri_s::IriS;
-use rbe::MatchTableIter;
+use rbe::{Component, RbeError};
+use shex_ast::compiled::compiled_schema::CompiledSchema;
 use shex_ast::compiled::preds::Preds;
+use shex_ast::compiled::sem_act::SemAct;
 use shex_ast::compiled::shape::Shape;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::Node;
 use shex_ast::Pred;
 use shex_ast::ShapeLabelIdx;
-use srdf::{Object, SRDF};
+use srdf::SRDF;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
 use tracing::debug;
 
 type Result<T> = std::result::Result<T, ValidatorError>;
@@ -24,6 +35,7 @@ type NegAtom = atom::NegAtom<(Node, ShapeLabelIdx)>;
 type PosAtom = atom::PosAtom<(Node, ShapeLabelIdx)>;
 // type Rule = rule::Rule<(Node, ShapeLabelIdx)>;
 type Neighs = (Vec<(Pred, Node)>, Vec<Pred>);
+type TableMatch = rbe::TableMatch<Pred, Node, ShapeLabelIdx>;
 
 #[derive(Debug)]
 pub struct Engine {
@@ -31,12 +43,74 @@ pub struct Engine {
     processing: IndexSet<Atom>,
     pending: IndexSet<Atom>,
     //rules: Vec<Rule>,
-    alternative_match_iterators: Vec<MatchTableIter<Pred, Node, ShapeLabelIdx>>,
+    assumed: IndexSet<Atom>,
+    negation_marks: Vec<usize>,
     // alternatives: Vec<ResultMap<Node, ShapeLabelIdx>>,
     config: ValidatorConfig,
     step_counter: usize,
     reasons: HashMap<PosAtom, Vec<Reason>>,
     errors: HashMap<NegAtom, Vec<ValidatorError>>,
+    sem_act_handlers: HashMap<IriS, Box<dyn SemActHandler>>,
+    // Outgoing arcs of the nodes that have already been retrieved
+    neighs_cache: HashMap<Node, Vec<(Pred, Node)>>,
+    // Semantic actions of the shapes matched while checking the current atom, which are executed once its result is final
+    sem_act_queue: Vec<QueuedSemActs>,
+    // Atoms checked before forking the engine, which are read by the forked engines without copying them
+    shared: Arc<CheckedAtoms>,
+}
+
+/// Checked atoms with their reasons and errors
+#[derive(Debug, Default, Clone)]
+struct CheckedAtoms {
+    checked: IndexSet<Atom>,
+    reasons: HashMap<PosAtom, Vec<Reason>>,
+    errors: HashMap<NegAtom, Vec<ValidatorError>>,
+}
+
+/// Semantic actions of a shape matched while checking `atom`, with the context in which each list of actions is executed
+#[derive(Debug)]
+struct QueuedSemActs {
+    atom: Atom,
+    sem_acts: Vec<(Vec<SemAct>, SemActContext)>,
+}
+
+/// Advances `chosen` to the next subset of `0..n` ordered by size and then lexicographically,
+/// returning false when there are no more subsets
+fn next_subset(chosen: &mut Vec<usize>, n: usize) -> bool {
+    let size = chosen.len();
+    match (0..size).rev().find(|i| chosen[*i] < n - size + i) {
+        Some(i) => {
+            chosen[i] += 1;
+            for j in i + 1..size {
+                chosen[j] = chosen[j - 1] + 1;
+            }
+            true
+        }
+        None if size < n => {
+            *chosen = (0..size + 1).collect();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Position of the state of an engine to roll back to it
+struct CheckedMark {
+    checked: usize,
+    assumed: IndexSet<Atom>,
+    sem_acts: usize,
+}
+
+/// State of the search of a partition of the triples of a node between the parts of a shape with `EXTENDS`
+struct PartitionSearch {
+    values: Vec<(Pred, Node)>,
+    // Parts to which each triple can be assigned
+    candidates: Vec<Vec<usize>>,
+    // Shape expressions of the extended shapes followed by the shape itself
+    parts: Vec<ShapeExpr>,
+    // Triples that have already failed to satisfy a part
+    failed: HashSet<(usize, Vec<usize>)>,
+    errors: Vec<ValidatorError>,
 }
 
 impl Default for Engine {
@@ -47,25 +121,246 @@ impl Default for Engine {
 
 impl Engine {
     pub fn new(config: &ValidatorConfig) -> Engine {
+        let mut sem_act_handlers: HashMap<IriS, Box<dyn SemActHandler>> = HashMap::new();
+        sem_act_handlers.insert(TestSemActHandler::iri(), Box::new(TestSemActHandler::new()));
         Engine {
             checked: IndexSet::new(),
             processing: IndexSet::new(),
             pending: IndexSet::new(),
             //rules: Vec::new(),
-            alternative_match_iterators: Vec::new(),
+            assumed: IndexSet::new(),
+            negation_marks: Vec::new(),
             config: config.clone(),
             step_counter: 0,
             reasons: HashMap::new(),
             errors: HashMap::new(),
+            sem_act_handlers,
+            neighs_cache: HashMap::new(),
+            sem_act_queue: Vec::new(),
+            shared: Arc::default(),
+        }
+    }
+
+    /// Creates an engine for a worker of parallel validation.
+    ///
+    /// The new engine shares the configuration and the atoms already checked, and has empty
+    /// copies of the semantic action handlers, so it can check atoms independently of this one
+    pub(crate) fn fork(&mut self) -> Engine {
+        self.share_checked();
+        Engine {
+            shared: self.shared.clone(),
+            sem_act_handlers: self
+                .sem_act_handlers
+                .iter()
+                .map(|(name, handler)| (name.clone(), handler.fork()))
+                .collect(),
+            ..Engine::new(&self.config)
+        }
+    }
+
+    /// Moves the atoms checked by this engine to the ones shared with its forks.
+    ///
+    /// The shared atoms are only copied if some fork of a previous round is still alive
+    fn share_checked(&mut self) {
+        if self.checked.is_empty() {
+            return;
+        }
+        let mut shared =
+            Arc::try_unwrap(std::mem::take(&mut self.shared)).unwrap_or_else(|s| (*s).clone());
+        shared.checked.extend(std::mem::take(&mut self.checked));
+        shared.reasons.extend(std::mem::take(&mut self.reasons));
+        shared.errors.extend(std::mem::take(&mut self.errors));
+        self.shared = Arc::new(shared);
+    }
+
+    /// Checks if an atom has already been decided by this engine or before forking it
+    fn is_checked(&self, atom: &Atom) -> bool {
+        self.checked.contains(atom) || self.shared.checked.contains(atom)
+    }
+
+    /// Collects the results of an engine obtained by `fork`.
+    ///
+    /// Atoms which had already been decided are kept, so merging the workers in a fixed order gives deterministic results
+    pub(crate) fn merge(&mut self, other: Engine) {
+        let Engine {
+            checked,
+            mut reasons,
+            mut errors,
+            sem_act_handlers,
+            step_counter,
+            neighs_cache,
+            ..
+        } = other;
+        for atom in checked {
+            if self.is_checked(&atom) || self.is_checked(&atom.negated()) {
+                continue;
+            }
+            match &atom {
+                Atom::Pos(pa) => {
+                    let rs = reasons.remove(pa).unwrap_or_default();
+                    self.add_checked_pos(atom, rs)
+                }
+                Atom::Neg(na) => {
+                    let es = errors.remove(na).unwrap_or_default();
+                    self.add_checked_neg(atom, es)
+                }
+            }
+        }
+        for (name, handler) in sem_act_handlers {
+            if let Some(current) = self.sem_act_handlers.get_mut(&name) {
+                current.merge(handler)
+            }
+        }
+        self.step_counter += step_counter;
+        for (node, neighs) in neighs_cache {
+            self.neighs_cache.entry(node).or_insert(neighs);
         }
     }
 
+    /// Checks a pending atom. The result is recorded as a checked atom
+    pub(crate) fn check_atom<S>(
+        &mut self,
+        atom: &Atom,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        let (node, idx) = atom.get_value();
+        let result = loop {
+            let mark = self.checked_mark();
+            let result = self.check_node_ref(node, idx, schema, rdf)?;
+            match self.run_sem_act_queue() {
+                None => break result,
+                Some((failed, error)) => {
+                    // The atom whose actions failed doesn't conform, so the atoms checked after it are checked again
+                    debug!("Semantic actions of {failed:?} failed: {error}");
+                    self.rollback(mark);
+                    self.add_checked_neg(failed.negated(), vec![error]);
+                }
+            }
+        };
+        match atom {
+            Atom::Pos { .. } => Ok(result),
+            Atom::Neg { .. } => match result {
+                // Check if a node doesn't conform to a shape expr
+                Either::Left(errors) => Ok(Either::Right(vec![Reason::NegAtomPassed {
+                    node: node.clone(),
+                    idx: *idx,
+                    errors_evidences: ValidatorErrors::new(errors),
+                }])),
+                Either::Right(reasons) => Ok(Either::Left(vec![ValidatorError::NegAtomFailed {
+                    node: node.clone(),
+                    idx: *idx,
+                    reasons: Reasons::new(reasons),
+                }])),
+            },
+        }
+    }
+
+    /// Registers the handler of the semantic actions of the extension `name`, replacing any previous one
+    pub fn add_sem_act_handler(&mut self, name: IriS, handler: Box<dyn SemActHandler>) {
+        self.sem_act_handlers.insert(name, handler);
+    }
+
+    pub fn sem_act_handler(&self, name: &IriS) -> Option<&dyn SemActHandler> {
+        self.sem_act_handlers
+            .get(name)
+            .map(|handler| handler.as_ref())
+    }
+
+    /// Executes a list of semantic actions, returning the error of the first one that fails
+    pub(crate) fn check_sem_acts(
+        &mut self,
+        sem_acts: &[SemAct],
+        context: &SemActContext,
+    ) -> Option<ValidatorError> {
+        for sem_act in sem_acts {
+            match self.sem_act_handlers.get_mut(sem_act.name()) {
+                Some(handler) => {
+                    if let Err(error) = handler.run(sem_act.code(), context) {
+                        return Some(ValidatorError::SemActFailed {
+                            name: sem_act.name().clone(),
+                            error,
+                        });
+                    }
+                }
+                None => match self.config.unknown_sem_acts() {
+                    UnknownSemActs::Ignore => {
+                        debug!("Ignoring semantic action {}", sem_act.name())
+                    }
+                    UnknownSemActs::Fail => {
+                        return Some(ValidatorError::UnknownSemAct {
+                            name: sem_act.name().clone(),
+                        })
+                    }
+                },
+            }
+        }
+        None
+    }
+
+    /// Queues the semantic actions of a shape for a match of the neighbourhood of `node`.
+    ///
+    /// The actions of each triple constraint are executed for the triples assigned to it in the match,
+    /// followed by the actions of the shape. They are executed by [`Engine::run_sem_act_queue`] when
+    /// the result of the atom being checked is final
+    fn queue_shape_sem_acts(&mut self, node: &Node, shape: &Shape, table_match: &TableMatch) {
+        let mut sem_acts = Vec::new();
+        for (pred, object, component) in table_match.assignment() {
+            for triple_sem_acts in shape
+                .triple_sem_acts()
+                .iter()
+                .filter(|triple_sem_acts| triple_sem_acts.component() == component)
+            {
+                sem_acts.push((
+                    triple_sem_acts.sem_acts().clone(),
+                    SemActContext::triple(node, pred, object),
+                ));
+            }
+        }
+        if !shape.sem_acts().is_empty() {
+            sem_acts.push((shape.sem_acts().clone(), SemActContext::shape(node)));
+        }
+        if let (false, Some(atom)) = (sem_acts.is_empty(), self.processing.last()) {
+            self.sem_act_queue.push(QueuedSemActs {
+                atom: atom.clone(),
+                sem_acts,
+            })
+        }
+    }
+
+    /// Discards the semantic actions queued from position `mark` while checking `atom`,
+    /// because the shape expression that matched them didn't finally pass
+    fn discard_sem_acts(&mut self, atom: &Atom, mark: usize) {
+        let mut position = 0;
+        self.sem_act_queue.retain(|queued| {
+            position += 1;
+            position <= mark || queued.atom != *atom
+        })
+    }
+
+    /// Executes the queued semantic actions, returning the atom and the error of the first one that fails.
+    ///
+    /// The actions queued after a failed one are not executed
+    fn run_sem_act_queue(&mut self) -> Option<(Atom, ValidatorError)> {
+        for queued in std::mem::take(&mut self.sem_act_queue) {
+            for (sem_acts, context) in queued.sem_acts {
+                if let Some(error) = self.check_sem_acts(&sem_acts, &context) {
+                    return Some((queued.atom, error));
+                }
+            }
+        }
+        None
+    }
+
     pub(crate) fn add_processing(&mut self, atom: &Atom) {
         self.processing.insert((*atom).clone());
     }
 
     pub(crate) fn remove_processing(&mut self, atom: &Atom) {
-        self.processing.swap_remove(atom);
+        self.processing.shift_remove(atom);
     }
 
     pub(crate) fn add_checked_pos(&mut self, atom: Atom, reasons: Vec<Reason>) {
@@ -95,7 +390,12 @@ impl Engine {
     }
 
     pub(crate) fn checked(&self) -> IndexSet<Atom> {
-        self.checked.clone()
+        self.shared
+            .checked
+            .iter()
+            .chain(self.checked.iter())
+            .cloned()
+            .collect()
     }
 
     fn add_reasons(&mut self, pa: PosAtom, rs: Vec<Reason>) {
@@ -120,18 +420,18 @@ impl Engine {
         self.pending.clone()
     }
 
-    pub fn set_max_steps(&mut self, max_steps: usize) {
-        self.config.set_max_steps(max_steps);
+    pub(crate) fn config(&self) -> &ValidatorConfig {
+        &self.config
     }
 
-    pub(crate) fn is_processing(&self, atom: &Atom) -> bool {
-        self.processing.contains(atom)
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.config.set_max_steps(max_steps);
     }
 
     pub(crate) fn get_result(&self, atom: &Atom) -> ResultValue {
-        if self.checked.contains(atom) {
+        if self.is_checked(atom) {
             ResultValue::Ok
-        } else if self.checked.contains(&atom.negated()) {
+        } else if self.is_checked(&atom.negated()) {
             ResultValue::Failed
         } else if self.pending.contains(atom) {
             ResultValue::Pending
@@ -190,23 +490,159 @@ impl Engine {
     }
 
     pub(crate) fn find_errors(&self, na: &NegAtom) -> Vec<ValidatorError> {
-        match self.errors.get(na) {
+        match self.errors.get(na).or_else(|| self.shared.errors.get(na)) {
             Some(vs) => vs.to_vec(),
             None => Vec::new(),
         }
     }
 
     pub(crate) fn find_reasons(&self, pa: &PosAtom) -> Vec<Reason> {
-        match self.reasons.get(pa) {
+        match self.reasons.get(pa).or_else(|| self.shared.reasons.get(pa)) {
             Some(vs) => vs.to_vec(),
             None => Vec::new(),
         }
     }
 
+    /// Checks if a node conforms to the shape expression identified by `idx`.
+    ///
+    /// Results are memoized in the checked atoms. Recursive references to atoms which are being
+    /// processed are assumed to pass (hypothetical typing). If that assumption is later refuted,
+    /// the results obtained while it was active are discarded.
+    pub(crate) fn check_node_ref<S>(
+        &mut self,
+        node: &Node,
+        idx: &ShapeLabelIdx,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        let pos_atom = PosAtom::new((node.clone(), *idx));
+        let atom = Atom::pos(&pos_atom);
+        if self.is_checked(&atom) {
+            return Ok(Either::Right(vec![Reason::ShapeRefPassed {
+                node: node.clone(),
+                idx: *idx,
+            }]));
+        }
+        if self.is_checked(&atom.negated()) {
+            let errors = self.find_errors(&NegAtom::new((node.clone(), *idx)));
+            return Ok(Either::Left(vec![ValidatorError::ShapeRefFailed {
+                node: node.clone(),
+                idx: *idx,
+                errors: ValidatorErrors::new(errors),
+            }]));
+        }
+        if let Some(position) = self.processing.get_index_of(&atom) {
+            if self.negation_marks.iter().any(|mark| position < *mark) {
+                return Err(ValidatorError::NegationCycleError {
+                    node: node.clone(),
+                    idx: *idx,
+                });
+            }
+            debug!("Assuming {node}@{idx} because it is being processed");
+            self.assumed.insert(atom);
+            return Ok(Either::Right(vec![Reason::ShapeRefAssumed {
+                node: node.clone(),
+                idx: *idx,
+            }]));
+        }
+        let (_label, se) = schema
+            .find_shape_idx(idx)
+            .ok_or(ValidatorError::ShapeLabelIdxNotFound { idx: *idx })?;
+        let checked_mark = self.checked.len();
+        let sem_acts_mark = self.sem_act_queue.len();
+        self.add_processing(&atom);
+        let result = self.check_node_decl(node, idx, se, schema, rdf);
+        self.remove_processing(&atom);
+        let was_assumed = self.assumed.swap_remove(&atom);
+        match result? {
+            Either::Right(reasons) => {
+                self.add_checked_pos(atom, reasons);
+                Ok(Either::Right(vec![Reason::ShapeRefPassed {
+                    node: node.clone(),
+                    idx: *idx,
+                }]))
+            }
+            Either::Left(errors) => {
+                if was_assumed {
+                    self.discard_checked_from(checked_mark);
+                    self.sem_act_queue.truncate(sem_acts_mark);
+                } else {
+                    self.discard_sem_acts(&atom, sem_acts_mark);
+                }
+                self.add_checked_neg(atom.negated(), errors.clone());
+                Ok(Either::Left(vec![ValidatorError::ShapeRefFailed {
+                    node: node.clone(),
+                    idx: *idx,
+                    errors: ValidatorErrors::new(errors),
+                }]))
+            }
+        }
+    }
+
+    /// Checks if a node conforms to the shape declaration `idx` whose shape expression is `se`.
+    ///
+    /// A node conforms to a shape declaration if it conforms to its shape expression, or to some
+    /// non-abstract shape that extends it. Abstract shapes are only satisfied through their descendants.
+    fn check_node_decl<S>(
+        &mut self,
+        node: &Node,
+        idx: &ShapeLabelIdx,
+        se: &ShapeExpr,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        let mut errors = Vec::new();
+        if schema.is_abstract(idx) {
+            errors.push(ValidatorError::AbstractShapeError {
+                node: node.clone(),
+                idx: *idx,
+            });
+        } else {
+            match self.check_node_shape_expr(node, se, schema, rdf)? {
+                Either::Right(reasons) => return Ok(Either::Right(reasons)),
+                Either::Left(errs) => errors.extend(errs),
+            }
+        }
+        for descendant in schema.descendants(idx) {
+            if schema.is_abstract(descendant) {
+                continue;
+            }
+            match self.check_node_ref(node, descendant, schema, rdf)? {
+                Either::Right(reasons) => return Ok(Either::Right(reasons)),
+                Either::Left(errs) => errors.extend(errs),
+            }
+        }
+        Ok(Either::Left(errors))
+    }
+
+    /// Removes the atoms that were checked after position `mark` because they could depend on an assumption that has been refuted
+    fn discard_checked_from(&mut self, mark: usize) {
+        while self.checked.len() > mark {
+            if let Some(atom) = self.checked.pop() {
+                debug!("Discarding {atom:?} because it depended on a refuted assumption");
+                match atom {
+                    Atom::Pos(pa) => {
+                        self.reasons.remove(&pa);
+                    }
+                    Atom::Neg(na) => {
+                        self.errors.remove(&na);
+                    }
+                }
+            }
+        }
+    }
+
     pub(crate) fn check_node_shape_expr<S>(
         &mut self,
         node: &Node,
         se: &ShapeExpr,
+        schema: &CompiledSchema,
         rdf: &S,
     ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
     where
@@ -226,17 +662,15 @@ impl Engine {
                         nc: nc.clone(),
                     }]))
                 }
-                Err(err) => Ok(Either::Left(vec![ValidatorError::RbeError(err)])),
+                Err(err) => Ok(Either::Left(vec![ValidatorError::from(err)])),
             },
-            ShapeExpr::Ref { .. } => {
-                todo!()
-            }
+            ShapeExpr::Ref { idx } => self.check_node_ref(node, idx, schema, rdf),
             ShapeExpr::ShapeAnd { exprs, .. } => {
                 for e in exprs {
-                    let result = self.check_node_shape_expr(node, e, rdf)?;
+                    let result = self.check_node_shape_expr(node, e, schema, rdf)?;
                     if let Some(errors) = result.left() {
                         return Ok(Either::Left(vec![ValidatorError::ShapeAndError {
-                            shape_expr: e.clone(),
+                            shape_expr: Box::new(e.clone()),
                             node: node.clone(),
                             errors: ValidatorErrors::new(errors),
                         }]));
@@ -248,27 +682,57 @@ impl Engine {
                 }]))
             }
             ShapeExpr::ShapeNot { expr, .. } => {
-                let result = self.check_node_shape_expr(node, expr, rdf)?;
-                match result {
-                    Either::Left(_errors) => {
-                        todo!()
-                    }
-                    Either::Right(_errors) => {
-                        todo!()
+                // Recursive references reached from here to atoms which are already being processed form a negation cycle
+                self.negation_marks.push(self.processing.len());
+                let sem_acts_mark = self.sem_act_queue.len();
+                let result = self.check_node_shape_expr(node, expr, schema, rdf);
+                self.negation_marks.pop();
+                // The actions of the negated expression are not executed whatever its result
+                if let Some(atom) = self.processing.last().cloned() {
+                    self.discard_sem_acts(&atom, sem_acts_mark);
+                }
+                match result? {
+                    Either::Left(errors) => Ok(Either::Right(vec![Reason::ShapeNotPassed {
+                        node: node.clone(),
+                        shape_expr: se.clone(),
+                        errors_evidences: ValidatorErrors::new(errors),
+                    }])),
+                    Either::Right(reasons) => {
+                        Ok(Either::Left(vec![ValidatorError::ShapeNotError {
+                            shape_expr: Box::new(se.clone()),
+                            node: node.clone(),
+                            reasons: Reasons::new(reasons),
+                        }]))
                     }
                 }
             }
-            ShapeExpr::ShapeOr { .. } => {
-                todo!()
-                /*for e in exprs {
-                    let result = self.check_node_shape_expr(node, e, rdf)?;
-                    if result {
-                        return Ok(true);
+            ShapeExpr::ShapeOr { exprs, .. } => {
+                let mut errors_collection = Vec::new();
+                for e in exprs {
+                    let sem_acts_mark = self.sem_act_queue.len();
+                    match self.check_node_shape_expr(node, e, schema, rdf)? {
+                        Either::Right(reasons) => {
+                            return Ok(Either::Right(vec![Reason::ShapeOrPassed {
+                                node: node.clone(),
+                                shape_expr: e.clone(),
+                                reasons: Reasons::new(reasons),
+                            }]))
+                        }
+                        Either::Left(errors) => {
+                            if let Some(atom) = self.processing.last().cloned() {
+                                self.discard_sem_acts(&atom, sem_acts_mark);
+                            }
+                            errors_collection.extend(errors)
+                        }
                     }
                 }
-                Ok(false)*/
+                Ok(Either::Left(vec![ValidatorError::ShapeOrError {
+                    shape_expr: Box::new(se.clone()),
+                    node: node.clone(),
+                    errors: ValidatorErrors::new(errors_collection),
+                }]))
             }
-            ShapeExpr::Shape(shape) => self.check_node_shape(node, shape, rdf),
+            ShapeExpr::Shape(shape) => self.check_node_shape(node, shape, schema, rdf),
             ShapeExpr::Empty => Ok(Either::Right(Vec::new())),
             ShapeExpr::External {} => Ok(Either::Right(Vec::new())),
         }
@@ -278,84 +742,450 @@ impl Engine {
         &mut self,
         node: &Node,
         shape: &Shape,
+        schema: &CompiledSchema,
         rdf: &S,
     ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
     where
         S: SRDF,
     {
-        let (values, remainder) = self.neighs(node, shape.preds(), rdf)?;
+        let preds = if shape.extends().is_empty() {
+            shape.preds()
+        } else {
+            self.extended_preds(shape, schema)
+        };
+        let (values, remainder) = match self.neighs(node, preds.clone(), shape.is_closed(), rdf) {
+            Ok(neighs) => neighs,
+            // The node fails, but the validation of the other nodes goes on
+            Err(error) => return Ok(Either::Left(vec![error])),
+        };
         if shape.is_closed() && !remainder.is_empty() {
             let errs = vec![ValidatorError::ClosedShapeWithRemainderPreds {
                 remainder: Preds::new(remainder),
-                declared: Preds::new(
-                    shape
-                        .preds()
-                        .iter()
-                        .map(|iri| {
-                            let new_iri = iri.clone();
-                            Pred::from(new_iri)
-                        })
-                        .collect(),
-                ),
+                declared: Preds::new(preds.into_iter().map(Pred::from).collect()),
             }];
             return Ok(Either::Left(errs));
         };
         debug!("Neighs of {node}: {values:?}");
-        let mut result_iter = shape.rbe_table().matches(values)?;
-        let mut current_err = None;
+        self.check_shape_neighs(node, shape, values, schema, rdf)
+    }
+
+    /// Checks if the triples in `values` conform to a shape, without checking whether it is closed
+    fn check_shape_neighs<S>(
+        &mut self,
+        node: &Node,
+        shape: &Shape,
+        values: Vec<(Pred, Node)>,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        if !shape.extends().is_empty() {
+            return self.check_extended_shape(node, shape, values, schema, rdf);
+        }
+        let extra = Self::to_preds(shape.extra().clone());
+        let extra_values: Vec<usize> = (0..values.len())
+            .filter(|value| extra.contains(&values[*value].0))
+            .collect();
+        if extra_values.is_empty() {
+            return self.match_shape_neighs(node, shape, values, schema, rdf);
+        }
+        // The triples of EXTRA predicates can be left out of the match, the subsets of them
+        // to leave out are tried from the smallest ones
+        let mut left_out = Vec::new();
+        let mut first_errs = None;
+        loop {
+            let kept = values
+                .iter()
+                .enumerate()
+                .filter(|(value, _)| !left_out.iter().any(|l| extra_values[*l] == *value))
+                .map(|(_, value)| value.clone())
+                .collect();
+            let mark = self.checked_mark();
+            match self.match_shape_neighs(node, shape, kept, schema, rdf)? {
+                Either::Right(reasons) => return Ok(Either::Right(reasons)),
+                Either::Left(errs) => {
+                    self.rollback(mark);
+                    first_errs.get_or_insert(errs);
+                }
+            }
+            if !next_subset(&mut left_out, extra_values.len()) {
+                return Ok(Either::Left(first_errs.unwrap_or_default()));
+            }
+        }
+    }
+
+    /// Checks if all the triples in `values` match the triple expression of a shape without `EXTENDS`
+    fn match_shape_neighs<S>(
+        &mut self,
+        node: &Node,
+        shape: &Shape,
+        values: Vec<(Pred, Node)>,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        let mut result_iter = shape.rbe_table().matches(values.clone())?;
+        let mut current_errs = Vec::new();
         let counter = self.step_counter;
-        let mut found = false;
         let mut iter_count = 0;
 
-        // Search for the first result which is not an err
-        while let Some(next_result) = result_iter.next() {
+        // Search for the first result whose pending references also pass
+        while let Some(next_result) = result_iter.next_match() {
             iter_count += 1;
             match next_result {
-                Ok(pending_values) => {
+                Ok(table_match) => {
                     debug!("Found result, iteration {iter_count}");
-                    for (p, v) in pending_values.iter() {
-                        debug!("Step {counter}: Value in pending: {p}/{v}");
-                        let pos_atom = PosAtom::new(((*p).clone(), *v));
-                        let atom = Atom::pos(&pos_atom);
-                        if self.is_processing(&atom) {
-                            let pred = p.clone();
-                            debug!(
-                                "Step {counter} Adding ok: {}/{v} because it was already processed",
-                                &pred
-                            );
-                            self.add_ok(pred, *v);
-                        } else {
-                            self.insert_pending(&atom);
+                    let mut failed_refs = Vec::new();
+                    for (p, v) in table_match.pending().iter() {
+                        debug!("Step {counter}: Checking pending value: {p}/{v}");
+                        if let Either::Left(errs) = self.check_node_ref(p, v, schema, rdf)? {
+                            failed_refs.extend(Self::triple_error(
+                                node,
+                                shape,
+                                &table_match,
+                                p,
+                                errs,
+                            ));
+                            break;
                         }
                     }
-                    // We keep alternative match iterators which will be recovered in case of failure
-                    self.alternative_match_iterators.push(result_iter);
-                    found = true;
-                    break;
+                    if failed_refs.is_empty() {
+                        self.queue_shape_sem_acts(node, shape, &table_match);
+                        return Ok(Either::Right(vec![Reason::ShapePassed {
+                            node: node.clone(),
+                            shape: shape.clone(),
+                            matches: Self::triple_matches(shape, &table_match),
+                        }]));
+                    }
+                    debug!("Alternative {iter_count} failed: {failed_refs:?}");
+                    current_errs = failed_refs;
+                }
+                Err(RbeError::ValueFail {
+                    key,
+                    value,
+                    cond,
+                    error,
+                }) => {
+                    debug!("Value {value} of {key} failed at iteration {iter_count}: {error}");
+                    current_errs = vec![ValidatorError::TripleConstraintError {
+                        node: Box::new(node.clone()),
+                        predicate: key,
+                        object: Box::new(value),
+                        constraint: cond,
+                        errors: ValidatorErrors::new(vec![ValidatorError::from(
+                            RbeError::MsgError { msg: error },
+                        )]),
+                    }];
+                }
+                Err(RbeError::CardinalityMismatch { counts }) => {
+                    debug!("Cardinalities failed at iteration {iter_count}: {counts}");
+                    current_errs = vec![ValidatorError::CardinalityError {
+                        node: node.clone(),
+                        counts,
+                    }];
                 }
                 Err(err) => {
                     debug!("Result with error {err} at iteration {iter_count}");
-                    current_err = Some(err);
+                    current_errs = vec![ValidatorError::from(err)];
                 }
             }
         }
-        if !found {
-            let errs = match current_err {
-                Some(rbe_err) => vec![ValidatorError::RbeError(rbe_err)],
-                None => {
-                    debug!("No value found for node/shape where node = {node}, shape = {shape:?}. Current_err = empty");
-                    Vec::new()
-                }
-            };
-            Ok(Either::Left(errs))
-        } else {
-            Ok(Either::Right(vec![Reason::ShapePassed {
+        if current_errs.is_empty() {
+            debug!("No value found for node/shape where node = {node}, shape = {shape:?}. Current_err = empty");
+        }
+        Ok(Either::Left(current_errs))
+    }
+
+    /// Triples matched by each triple constraint of a shape in a match of its neighbourhood
+    fn triple_matches(shape: &Shape, table_match: &TableMatch) -> Vec<TripleMatch> {
+        table_match
+            .assignment()
+            .iter()
+            .map(|(pred, object, component)| {
+                TripleMatch::new(
+                    pred.clone(),
+                    object.clone(),
+                    Self::constraint_name(shape, component),
+                )
+            })
+            .collect()
+    }
+
+    /// Error of the triple whose object `object` is referenced by a triple constraint and doesn't conform to the referenced shape
+    fn triple_error(
+        node: &Node,
+        shape: &Shape,
+        table_match: &TableMatch,
+        object: &Node,
+        errors: Vec<ValidatorError>,
+    ) -> Vec<ValidatorError> {
+        match table_match
+            .assignment()
+            .iter()
+            .find(|(_, value, _)| value == object)
+        {
+            Some((pred, value, component)) => vec![ValidatorError::TripleConstraintError {
+                node: Box::new(node.clone()),
+                predicate: pred.clone(),
+                object: Box::new(value.clone()),
+                constraint: Self::constraint_name(shape, component),
+                errors: ValidatorErrors::new(errors),
+            }],
+            None => errors,
+        }
+    }
+
+    fn constraint_name(shape: &Shape, component: &Component) -> String {
+        shape
+            .rbe_table()
+            .component_cond(component)
+            .map(|cond| cond.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Checks the triples in `values` against a shape with `EXTENDS` following ShEx 2.2 semantics.
+    ///
+    /// The triples are partitioned between the extended shapes and the shape's own triple expression.
+    /// The node conforms if there is some partition where each extended shape is satisfied by its
+    /// part and the remaining triples satisfy the triple expression of the shape.
+    fn check_extended_shape<S>(
+        &mut self,
+        node: &Node,
+        shape: &Shape,
+        values: Vec<(Pred, Node)>,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        // One part for each extended shape followed by the part of the shape itself
+        let mut parts = Vec::new();
+        for parent in shape.extends() {
+            let (_label, se) = schema
+                .find_shape_idx(parent)
+                .ok_or(ValidatorError::ShapeLabelIdxNotFound { idx: *parent })?;
+            parts.push(se.clone());
+        }
+        parts.push(ShapeExpr::Shape(shape.clone().with_extends(Vec::new())));
+        let parts_preds: Vec<Vec<Pred>> = parts
+            .iter()
+            .map(|se| Self::to_preds(self.shape_expr_preds(se, schema)))
+            .collect();
+        let candidates = values
+            .iter()
+            .map(|(pred, _)| {
+                (0..parts.len())
+                    .filter(|part| parts_preds[*part].contains(pred))
+                    .collect()
+            })
+            .collect();
+        let mut search = PartitionSearch {
+            values,
+            candidates,
+            parts,
+            failed: HashSet::new(),
+            errors: Vec::new(),
+        };
+        let remaining = (0..search.values.len()).collect();
+        match self.search_partition(node, &mut search, 0, remaining, schema, rdf)? {
+            Some(matches) => Ok(Either::Right(vec![Reason::ShapePassed {
                 node: node.clone(),
                 shape: shape.clone(),
-            }]))
+                matches,
+            }])),
+            None => Ok(Either::Left(vec![ValidatorError::ShapeExtendsError {
+                node: node.clone(),
+                errors: ValidatorErrors::new(search.errors),
+            }])),
+        }
+    }
+
+    /// Searches by backtracking a partition of the `remaining` triples between the parts from
+    /// `part` onwards where each part is satisfied, returning the triples matched.
+    ///
+    /// Each part is checked as soon as its triples are chosen, so a part that fails prunes all the
+    /// partitions of the triples that remain. The triples that can't go to a later part are always
+    /// assigned to the current one. The checked atoms and assumptions of a failed attempt are rolled back
+    fn search_partition<S>(
+        &mut self,
+        node: &Node,
+        search: &mut PartitionSearch,
+        part: usize,
+        remaining: Vec<usize>,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Option<Vec<TripleMatch>>>
+    where
+        S: SRDF,
+    {
+        let last = part + 1 == search.parts.len();
+        let mut forced = Vec::new();
+        let mut optional = Vec::new();
+        let mut later = Vec::new();
+        for value in remaining {
+            let candidates = &search.candidates[value];
+            match (
+                candidates.contains(&part),
+                candidates.iter().any(|c| *c > part),
+            ) {
+                (true, false) => forced.push(value),
+                (true, true) => optional.push(value),
+                (false, true) => later.push(value),
+                // it could only go to a previous part
+                (false, false) => return Ok(None),
+            }
+        }
+        if last && !later.is_empty() {
+            return Ok(None);
+        }
+        // subsets of the optional triples are tried from the smallest ones
+        let mut chosen = Vec::new();
+        loop {
+            let mut selected = forced.clone();
+            let mut rest = later.clone();
+            for (position, value) in optional.iter().enumerate() {
+                if chosen.contains(&position) {
+                    selected.push(*value)
+                } else {
+                    rest.push(*value)
+                }
+            }
+            selected.sort();
+            if !search.failed.contains(&(part, selected.clone())) {
+                let mark = self.checked_mark();
+                let part_values = selected
+                    .iter()
+                    .map(|value| search.values[*value].clone())
+                    .collect();
+                let se = search.parts[part].clone();
+                match self.check_shape_expr_neighs(node, &se, part_values, schema, rdf)? {
+                    Either::Right(reasons) => {
+                        let mut matches: Vec<TripleMatch> = reasons
+                            .iter()
+                            .flat_map(|r| r.triple_matches().to_vec())
+                            .collect();
+                        if last {
+                            return Ok(Some(matches));
+                        }
+                        rest.sort();
+                        if let Some(rest_matches) =
+                            self.search_partition(node, search, part + 1, rest, schema, rdf)?
+                        {
+                            matches.extend(rest_matches);
+                            return Ok(Some(matches));
+                        }
+                        self.rollback(mark);
+                    }
+                    Either::Left(errs) => {
+                        self.rollback(mark);
+                        search.failed.insert((part, selected));
+                        search.errors.extend(errs);
+                    }
+                }
+            }
+            if !next_subset(&mut chosen, optional.len()) {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Position of the checked atoms and the current assumptions, to roll back to them with [`Engine::rollback`]
+    fn checked_mark(&self) -> CheckedMark {
+        CheckedMark {
+            checked: self.checked.len(),
+            assumed: self.assumed.clone(),
+            sem_acts: self.sem_act_queue.len(),
         }
     }
 
+    fn rollback(&mut self, mark: CheckedMark) {
+        self.discard_checked_from(mark.checked);
+        self.assumed = mark.assumed;
+        self.sem_act_queue.truncate(mark.sem_acts);
+    }
+
+    /// Checks the triples in `values` against the shape expression of an extended shape
+    fn check_shape_expr_neighs<S>(
+        &mut self,
+        node: &Node,
+        se: &ShapeExpr,
+        values: Vec<(Pred, Node)>,
+        schema: &CompiledSchema,
+        rdf: &S,
+    ) -> Result<Either<Vec<ValidatorError>, Vec<Reason>>>
+    where
+        S: SRDF,
+    {
+        match se {
+            ShapeExpr::Shape(shape) => self.check_shape_neighs(node, shape, values, schema, rdf),
+            ShapeExpr::ShapeAnd { exprs, .. } => {
+                for e in exprs {
+                    let result =
+                        self.check_shape_expr_neighs(node, e, values.clone(), schema, rdf)?;
+                    if let Some(errors) = result.left() {
+                        return Ok(Either::Left(vec![ValidatorError::ShapeAndError {
+                            shape_expr: Box::new(e.clone()),
+                            node: node.clone(),
+                            errors: ValidatorErrors::new(errors),
+                        }]));
+                    }
+                }
+                Ok(Either::Right(vec![Reason::ShapeAndPassed {
+                    node: node.clone(),
+                    se: se.clone(),
+                }]))
+            }
+            ShapeExpr::Ref { idx } => {
+                let (_label, se) = schema
+                    .find_shape_idx(idx)
+                    .ok_or(ValidatorError::ShapeLabelIdxNotFound { idx: *idx })?;
+                self.check_shape_expr_neighs(node, se, values, schema, rdf)
+            }
+            _ => self.check_node_shape_expr(node, se, schema, rdf),
+        }
+    }
+
+    /// Predicates mentioned by a shape and the shapes that it extends
+    fn extended_preds(&self, shape: &Shape, schema: &CompiledSchema) -> Vec<IriS> {
+        let mut preds = shape.preds();
+        for parent in shape.extends() {
+            if let Some((_label, se)) = schema.find_shape_idx(parent) {
+                preds.extend(self.shape_expr_preds(se, schema))
+            }
+        }
+        let mut unique = Vec::new();
+        for pred in preds {
+            if !unique.contains(&pred) {
+                unique.push(pred)
+            }
+        }
+        unique
+    }
+
+    fn shape_expr_preds(&self, se: &ShapeExpr, schema: &CompiledSchema) -> Vec<IriS> {
+        match se {
+            ShapeExpr::Shape(shape) => self.extended_preds(shape, schema),
+            ShapeExpr::ShapeAnd { exprs, .. } => exprs
+                .iter()
+                .flat_map(|e| self.shape_expr_preds(e, schema))
+                .collect(),
+            ShapeExpr::Ref { idx } => match schema.find_shape_idx(idx) {
+                Some((_label, se)) => self.shape_expr_preds(se, schema),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn to_preds(iris: Vec<IriS>) -> Vec<Pred> {
+        iris.into_iter().map(Pred::from).collect()
+    }
+
     fn cnv_iri<S>(&self, iri: S::IRI) -> Pred
     where
         S: SRDF,
@@ -372,58 +1202,101 @@ impl Engine {
         Node::from(object)
     }
 
-    fn neighs<S>(&self, node: &Node, preds: Vec<IriS>, rdf: &S) -> Result<Neighs>
+    /// Obtains the values of the arcs of `node` whose predicates are in `preds` and the
+    /// predicates of the other arcs of `node`
+    fn neighs<S>(&mut self, node: &Node, preds: Vec<IriS>, closed: bool, rdf: &S) -> Result<Neighs>
     where
         S: SRDF,
     {
-        let node = self.get_rdf_node(node, rdf);
-        let list: Vec<_> = preds.iter().map(|pred| S::iri_s2iri(pred)).collect();
-        if let Some(subject) = S::term_as_subject(&node) {
-            let (outgoing_arcs, remainder) = rdf
-                .outgoing_arcs_from_list(&subject, &list)
-                .map_err(|e| self.cnv_err::<S>(e))?;
-            let mut result = Vec::new();
-            for (pred, values) in outgoing_arcs.into_iter() {
-                for obj in values.into_iter() {
-                    let iri = self.cnv_iri::<S>(pred.clone());
-                    let object = self.cnv_object::<S>(&obj);
-                    result.push((iri.clone(), object))
+        let term = self.get_rdf_node(node, rdf);
+        let subject = match S::term_as_subject(&term) {
+            Some(subject) => subject,
+            // Literals have no outgoing arcs
+            None => return Ok((Vec::new(), Vec::new())),
+        };
+        match self.config.neighs_mode() {
+            NeighsMode::ShapePreds => {
+                let list: Vec<_> = preds.iter().map(|pred| S::iri_s2iri(pred)).collect();
+                // The other predicates of the node are only needed to check closed shapes
+                let (outgoing_arcs, remainder) = if closed {
+                    rdf.outgoing_arcs_from_list(&subject, &list)
+                } else {
+                    rdf.outgoing_arcs_of_preds(&subject, &list)
+                        .map(|arcs| (arcs, Vec::new()))
+                }
+                .map_err(|e| self.cnv_err::<S>(node, e))?;
+                let mut result = Vec::new();
+                for (pred, values) in outgoing_arcs.into_iter() {
+                    for obj in values.into_iter() {
+                        let iri = self.cnv_iri::<S>(pred.clone());
+                        let object = self.cnv_object::<S>(&obj);
+                        result.push((iri.clone(), object))
+                    }
                 }
+                let mut remainder_preds = Vec::new();
+                for r in remainder {
+                    let iri_r = self.cnv_iri::<S>(r.clone());
+                    remainder_preds.push(iri_r)
+                }
+                Self::sort_arcs(&mut result);
+                Ok((result, remainder_preds))
             }
-            let mut remainder_preds = Vec::new();
-            for r in remainder {
-                let iri_r = self.cnv_iri::<S>(r.clone());
-                remainder_preds.push(iri_r)
+            NeighsMode::AllArcs => {
+                if !self.neighs_cache.contains_key(node) {
+                    let outgoing_arcs = rdf
+                        .outgoing_arcs(&subject)
+                        .map_err(|e| self.cnv_err::<S>(node, e))?;
+                    let mut arcs = Vec::new();
+                    for (pred, values) in outgoing_arcs.into_iter() {
+                        let iri = self.cnv_iri::<S>(pred);
+                        for obj in values.into_iter() {
+                            arcs.push((iri.clone(), self.cnv_object::<S>(&obj)))
+                        }
+                    }
+                    Self::sort_arcs(&mut arcs);
+                    self.neighs_cache.insert(node.clone(), arcs);
+                }
+                let preds: Vec<Pred> = preds.into_iter().map(Pred::from).collect();
+                let mut result = Vec::new();
+                let mut remainder_preds = Vec::new();
+                for (pred, object) in self.neighs_cache.get(node).into_iter().flatten() {
+                    if preds.contains(pred) {
+                        result.push((pred.clone(), object.clone()))
+                    } else if !remainder_preds.contains(pred) {
+                        remainder_preds.push(pred.clone())
+                    }
+                }
+                Ok((result, remainder_preds))
             }
-            Ok((result, remainder_preds))
-        } else {
-            todo!()
         }
     }
 
-    fn cnv_err<S>(&self, _err: S::Err) -> ValidatorError
+    /// The RDF data returns the arcs in no particular order. Sorting them makes the order in which
+    /// referenced nodes are checked, and so the results and their explanations, reproducible
+    fn sort_arcs(arcs: &mut [(Pred, Node)]) {
+        arcs.sort_by_cached_key(|(pred, object)| (pred.to_string(), object.to_string()))
+    }
+
+    fn cnv_err<S>(&self, node: &Node, err: S::Err) -> ValidatorError
     where
         S: SRDF,
     {
-        todo!()
+        ValidatorError::NeighsError {
+            node: node.clone(),
+            error: err.to_string(),
+        }
+    }
+
+    /// Forgets the arcs retrieved during a validation, so they are not kept after it ends
+    pub(crate) fn clear_neighs_cache(&mut self) {
+        self.neighs_cache.clear()
     }
 
     fn get_rdf_node<S>(&self, node: &Node, _rdf: &S) -> S::Term
     where
         S: SRDF,
     {
-        match node.as_object() {
-            Object::Iri(iri) => {
-                let i = S::iri_s2iri(iri);
-                S::iri_as_term(i)
-            }
-            Object::BlankNode(_id) => {
-                todo!()
-            }
-            Object::Literal(_lit) => {
-                todo!()
-            }
-        }
+        S::object_as_term(node.as_object())
     }
 
     pub fn insert_pending(&mut self, atom: &Atom) {
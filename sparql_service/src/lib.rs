@@ -1,5 +1,7 @@
 //! SPARQL Service
 //!
+#[cfg(feature = "local-endpoint")]
+pub mod local_endpoint;
 pub mod query_config;
 pub mod query_processor;
 pub mod service_config;
@@ -9,6 +11,8 @@ pub mod service_description_parser;
 pub mod service_description_vocab;
 pub mod srdf_data;
 
+#[cfg(feature = "local-endpoint")]
+pub use crate::local_endpoint::*;
 pub use crate::query_config::*;
 pub use crate::query_processor::*;
 pub use crate::service_config::*;
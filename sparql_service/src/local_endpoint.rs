@@ -0,0 +1,270 @@
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use iri_s::IriS;
+use oxigraph::sparql::results::QueryResultsFormat;
+use oxigraph::sparql::{Query, QueryResults};
+use oxigraph::store::Store;
+use oxrdfio::RdfFormat;
+use srdf::SRDFGraph;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::RdfDataError;
+
+/// SPARQL endpoint that serves an in-memory graph on a local port.
+///
+/// It implements the GET and URL-encoded POST operations of the SPARQL protocol, returning the results of SELECT and ASK
+/// queries in JSON and the results of CONSTRUCT and DESCRIBE queries in N-Triples, which is enough to
+/// run the clients of SPARQL endpoints without network access. The server stops when it is dropped
+pub struct LocalEndpoint {
+    iri: IriS,
+    server: Arc<Server>,
+    requests: Arc<AtomicUsize>,
+    posts: Arc<AtomicUsize>,
+    failures: Arc<AtomicUsize>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl LocalEndpoint {
+    /// Serves the graph on a free port of `127.0.0.1`
+    pub fn new(graph: &SRDFGraph) -> Result<LocalEndpoint, RdfDataError> {
+        let store = Store::new()?;
+        store.bulk_loader().load_quads(graph.quads())?;
+        let server = Server::http("127.0.0.1:0").map_err(|e| io::Error::other(e.to_string()))?;
+        let port = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.port())
+            .ok_or_else(|| io::Error::other("Local endpoint without IP address"))?;
+        let server = Arc::new(server);
+        let requests = Arc::new(AtomicUsize::new(0));
+        let posts = Arc::new(AtomicUsize::new(0));
+        let failures = Arc::new(AtomicUsize::new(0));
+        let handle = {
+            let server = server.clone();
+            let requests = requests.clone();
+            let posts = posts.clone();
+            let failures = failures.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    if *request.method() == Method::Post {
+                        posts.fetch_add(1, Ordering::SeqCst);
+                    }
+                    let failed = failures
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                        .is_ok();
+                    if failed {
+                        let _ = request.respond(Response::from_string("").with_status_code(503));
+                    } else {
+                        respond(&store, request)
+                    }
+                }
+            })
+        };
+        Ok(LocalEndpoint {
+            iri: IriS::new_unchecked(format!("http://127.0.0.1:{port}/sparql").as_str()),
+            server,
+            requests,
+            posts,
+            failures,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn iri(&self) -> &IriS {
+        &self.iri
+    }
+
+    /// Number of requests received so far
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    /// Number of requests received so far that used POST
+    pub fn post_requests(&self) -> usize {
+        self.posts.load(Ordering::SeqCst)
+    }
+
+    /// Makes the next `n` requests fail with status 503
+    pub fn fail_next(&self, n: usize) {
+        self.failures.store(n, Ordering::SeqCst)
+    }
+}
+
+impl Drop for LocalEndpoint {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn respond(store: &Store, mut request: Request) {
+    let query = if *request.method() == Method::Post {
+        let mut body = Vec::new();
+        request
+            .as_reader()
+            .read_to_end(&mut body)
+            .ok()
+            .and_then(|_| query_param(url::form_urlencoded::parse(&body)))
+    } else {
+        url::Url::parse(&format!("http://localhost{}", request.url()))
+            .ok()
+            .and_then(|url| query_param(url.query_pairs()))
+    };
+    let response = match query {
+        None => Err((400, "Missing query parameter".to_string())),
+        Some(query) => evaluate(store, &query).map_err(|error| (400, error)),
+    };
+    let _ = match response {
+        Ok((content_type, body)) => request.respond(
+            Response::from_data(body)
+                .with_header(Header::from_bytes("Content-Type", content_type).unwrap()),
+        ),
+        Err((status, error)) => {
+            request.respond(Response::from_string(error).with_status_code(status))
+        }
+    };
+}
+
+fn query_param(mut pairs: url::form_urlencoded::Parse<'_>) -> Option<String> {
+    pairs
+        .find(|(name, _)| name == "query")
+        .map(|(_, value)| value.to_string())
+}
+
+fn evaluate(store: &Store, query: &str) -> Result<(&'static str, Vec<u8>), String> {
+    let query = Query::parse(query, None).map_err(|e| e.to_string())?;
+    match store.query(query).map_err(|e| e.to_string())? {
+        results @ QueryResults::Graph(_) => results
+            .write_graph(Vec::new(), RdfFormat::NTriples)
+            .map(|body| ("application/n-triples", body))
+            .map_err(|e| e.to_string()),
+        results => results
+            .write(Vec::new(), QueryResultsFormat::Json)
+            .map(|body| ("application/sparql-results+json", body))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxrdf::{BlankNode, NamedNode, Subject};
+    use srdf::{
+        AsyncQuerySRDF, AsyncSRDF, FetchConfig, FetchError, PrefetchedSRDF, RDFFormat, ReaderMode,
+        SRDFSparql, SRDFSparqlError, SRDF,
+    };
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice :name "Alice" ; :knows :bob .
+:bob :name "Bob" .
+:carol :knows :bob ."#;
+
+    fn endpoint() -> LocalEndpoint {
+        let graph =
+            SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::default()).unwrap();
+        LocalEndpoint::new(&graph).unwrap()
+    }
+
+    fn ex(local: &str) -> NamedNode {
+        NamedNode::new_unchecked(format!("http://example.org/{local}"))
+    }
+
+    #[tokio::test]
+    async fn outgoing_arcs_of_several_subjects_in_one_request() {
+        let endpoint = endpoint();
+        let sparql = SRDFSparql::new(endpoint.iri()).unwrap();
+        let subjects = vec![
+            Subject::NamedNode(ex("alice")),
+            Subject::NamedNode(ex("bob")),
+            Subject::NamedNode(ex("dave")),
+        ];
+        let arcs = sparql.get_outgoing_arcs(&subjects).await.unwrap();
+        assert_eq!(endpoint.requests(), 1);
+        assert_eq!(endpoint.post_requests(), 1);
+        assert_eq!(arcs[&subjects[0]].len(), 2);
+        assert_eq!(arcs[&subjects[1]].len(), 1);
+        assert!(!arcs.contains_key(&subjects[2]));
+    }
+
+    #[tokio::test]
+    async fn incoming_arcs_and_queries() {
+        let endpoint = endpoint();
+        let sparql = SRDFSparql::new(endpoint.iri()).unwrap();
+        let bob = ex("bob").into();
+        let arcs = sparql.get_incoming_arcs(&[bob]).await.unwrap();
+        assert_eq!(arcs.values().next().unwrap()[&ex("knows")].len(), 2);
+        let (variables, rows) = sparql
+            .get_query_select("select ?name where { ?p <http://example.org/name> ?name }")
+            .await
+            .unwrap();
+        assert_eq!(variables.len(), 1);
+        assert_eq!(rows.len(), 2);
+        assert!(sparql
+            .get_query_ask("ask { <http://example.org/carol> ?p ?o }")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn blank_nodes_are_not_sent_in_queries() {
+        let endpoint = endpoint();
+        let sparql = SRDFSparql::new(endpoint.iri()).unwrap();
+        let bnode = BlankNode::new_unchecked("b0");
+        let subjects = vec![Subject::NamedNode(ex("alice")), bnode.clone().into()];
+        assert!(matches!(
+            sparql.get_outgoing_arcs(&subjects).await,
+            Err(SRDFSparqlError::BlankNodeSubject { .. })
+        ));
+        assert!(matches!(
+            sparql.get_incoming_arcs(&[bnode.into()]).await,
+            Err(SRDFSparqlError::BlankNodeObject { .. })
+        ));
+        assert_eq!(endpoint.requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn prefetched_blank_nodes_are_errors() {
+        let endpoint = endpoint();
+        let prefetched = PrefetchedSRDF::new(
+            SRDFSparql::new(endpoint.iri()).unwrap(),
+            &FetchConfig::default(),
+        );
+        let bnode = BlankNode::new_unchecked("b0");
+        assert!(matches!(
+            prefetched.outgoing_arcs(&bnode.clone().into()),
+            Err(FetchError::BlankNode { .. })
+        ));
+        assert!(matches!(
+            prefetched.incoming_arcs(&bnode.into()),
+            Err(FetchError::BlankNode { .. })
+        ));
+        assert!(!prefetched.has_misses());
+        prefetched.outgoing_arcs(&ex("alice").into()).unwrap();
+        prefetched.fetch_misses().await.unwrap();
+        assert_eq!(
+            prefetched.outgoing_arcs(&ex("alice").into()).unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn error_statuses_are_errors() {
+        let endpoint = endpoint();
+        let sparql = SRDFSparql::new(endpoint.iri()).unwrap();
+        endpoint.fail_next(2);
+        assert!(matches!(
+            sparql.get_query_ask("ask { ?s ?p ?o }").await,
+            Err(SRDFSparqlError::HTTPRequestError { .. })
+        ));
+        assert!(matches!(
+            sparql.get_incoming_arcs(&[ex("bob").into()]).await,
+            Err(SRDFSparqlError::HTTPRequestError { .. })
+        ));
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use iri_s::IriS;
+use oxigraph::sparql::{Query, QueryResults, QuerySolutionIter, ServiceHandler};
+use oxrdf::{NamedNode as OxNamedNode, Term as OxTerm, Variable};
+use srdf::{QuerySRDF2, SRDFSparql};
+
+use super::RdfDataError;
+
+/// Evaluates the `SERVICE` clauses of the queries over the local store.
+///
+/// The services are resolved to the configured endpoints by their IRI, so their prefix maps and
+/// clients are reused. Other services are rejected, so that a query can't make the process
+/// send requests to arbitrary IRIs.
+pub(crate) struct EndpointServiceHandler {
+    endpoints: Vec<SRDFSparql>,
+}
+
+impl EndpointServiceHandler {
+    pub(crate) fn new(endpoints: &[SRDFSparql]) -> EndpointServiceHandler {
+        EndpointServiceHandler {
+            endpoints: endpoints.to_vec(),
+        }
+    }
+}
+
+impl ServiceHandler for EndpointServiceHandler {
+    type Error = RdfDataError;
+
+    fn handle(&self, service_name: OxNamedNode, query: Query) -> Result<QueryResults, Self::Error> {
+        let iri = IriS::from_named_node(&service_name);
+        let Some(endpoint) = self.endpoints.iter().find(|e| *e.iri() == iri) else {
+            return Err(RdfDataError::UnknownService {
+                service: iri.to_string(),
+            });
+        };
+        let solutions = endpoint.query_select(&query.to_string())?;
+        let variables: Arc<[Variable]> = solutions
+            .variables()
+            .map(|v| Variable::new_unchecked(v.as_str()))
+            .collect();
+        let values: Vec<Vec<Option<OxTerm>>> = solutions
+            .iter()
+            .map(|solution| {
+                variables
+                    .iter()
+                    .map(|v| solution.find_solution(v.as_str()).cloned())
+                    .collect()
+            })
+            .collect();
+        Ok(QueryResults::Solutions(QuerySolutionIter::new(
+            variables,
+            values.into_iter().map(Ok),
+        )))
+    }
+}
@@ -1,5 +1,10 @@
+mod endpoint_service_handler;
 mod rdf_data;
 mod rdf_data_error;
+#[cfg(feature = "rocksdb")]
+mod rdf_store;
 
 pub use rdf_data::*;
 pub use rdf_data_error::*;
+#[cfg(feature = "rocksdb")]
+pub use rdf_store::*;
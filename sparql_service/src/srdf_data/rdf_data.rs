@@ -1,11 +1,13 @@
 use colored::*;
 use iri_s::IriS;
 use oxigraph::sparql::Query;
+use oxigraph::sparql::QueryOptions;
 use oxigraph::sparql::QueryResults;
 use oxigraph::store::Store;
+use oxiri::Iri;
 use oxrdf::{
-    BlankNode as OxBlankNode, Literal as OxLiteral, NamedNode as OxNamedNode, Subject as OxSubject,
-    Term as OxTerm,
+    BlankNode as OxBlankNode, GraphNameRef, Literal as OxLiteral, NamedNode as OxNamedNode,
+    QuadRef, Subject as OxSubject, Term as OxTerm,
 };
 use oxrdfio::RdfFormat;
 use prefixmap::IriRef;
@@ -21,20 +23,24 @@ use srdf::QuerySRDF2;
 use srdf::QuerySolution2;
 use srdf::QuerySolutions;
 use srdf::RDFFormat;
+use srdf::ReaderMode;
 use srdf::SRDFBasic;
 use srdf::SRDFBuilder;
 use srdf::SRDFGraph;
 use srdf::SRDFSparql;
+use srdf::Triple;
 use srdf::VarName2;
 use srdf::RDF_TYPE_STR;
 use srdf::SRDF;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
+use std::io::BufRead;
 use std::rc::Rc;
 // use sparesults::QuerySolution as SparQuerySolution;
 use std::str::FromStr;
 
+use super::endpoint_service_handler::EndpointServiceHandler;
 use super::RdfDataError;
 
 /// Generic abstraction that represents RDF Data which can be either behind SPARQL endpoints or an in-memory graph
@@ -78,10 +84,89 @@ impl RdfData {
         }
     }
 
+    /// Adds a SPARQL endpoint whose data is merged with the rest of the data.
+    ///
+    /// The prefixes declared by the endpoint are added unless their aliases are already declared
+    pub fn add_endpoint(&mut self, endpoint: SRDFSparql) -> Result<(), RdfDataError> {
+        if let Some(prefixmap) = endpoint.prefixmap() {
+            for (alias, iri) in prefixmap.iter() {
+                if self.prefixmap.find(alias).is_none() {
+                    self.prefixmap.insert(alias, iri)?
+                }
+            }
+        }
+        self.endpoints.push(endpoint);
+        Ok(())
+    }
+
+    /// Parses RDF data and adds its triples and prefixes to the in-memory graph and to the store,
+    /// which are created if there is no local data yet.
+    ///
+    /// The data is parsed before anything is added, so nothing changes if it can't be parsed
+    pub fn merge_from_reader<R: BufRead>(
+        &mut self,
+        read: R,
+        format: &RDFFormat,
+        base: Option<Iri<String>>,
+        reader_mode: &ReaderMode,
+    ) -> Result<(), RdfDataError> {
+        let mut new_graph = SRDFGraph::new();
+        new_graph.merge_from_reader(read, format, base, reader_mode)?;
+        let store = match &self.store {
+            Some(store) => store,
+            None => self.store.insert(Store::new()?),
+        };
+        store.bulk_loader().load_quads(new_graph.quads())?;
+        self.graph
+            .get_or_insert_with(SRDFGraph::empty)
+            .merge_graph(&new_graph)?;
+        self.prefixmap.merge(new_graph.prefixmap())?;
+        Ok(())
+    }
+
+    /// Builder version of [`RdfData::add_endpoint`]
+    pub fn with_endpoint(mut self, endpoint: SRDFSparql) -> Result<RdfData, RdfDataError> {
+        self.add_endpoint(endpoint)?;
+        Ok(self)
+    }
+
+    pub fn endpoints(&self) -> &[SRDFSparql] {
+        &self.endpoints
+    }
+
     pub fn prefixmap(&self) -> PrefixMap {
         self.prefixmap.clone()
     }
 
+    /// In-memory graph from which the data has been loaded, if any
+    pub fn graph(&self) -> Option<&SRDFGraph> {
+        self.graph.as_ref()
+    }
+
+    /// Endpoints that can be queried about a subject.
+    /// Blank nodes are local to the graph, so endpoints are not queried about them when there is a graph
+    fn endpoints_for(&self, subject: &OxSubject) -> &[SRDFSparql] {
+        match subject {
+            OxSubject::BlankNode(_) if self.graph.is_some() => &[],
+            _ => &self.endpoints,
+        }
+    }
+
+    /// Endpoints that can be queried about a term, see [`RdfData::endpoints_for`]
+    fn endpoints_for_term(&self, term: &OxTerm) -> &[SRDFSparql] {
+        match term {
+            OxTerm::BlankNode(_) if self.graph.is_some() => &[],
+            _ => &self.endpoints,
+        }
+    }
+
+    /// Evaluates a query over the local store, whose `SERVICE` clauses are delegated to the endpoints
+    fn query_store(&self, store: &Store, query: Query) -> Result<QueryResults, RdfDataError> {
+        let options = QueryOptions::default()
+            .with_service_handler(EndpointServiceHandler::new(&self.endpoints));
+        Ok(store.query_opt(query, options)?)
+    }
+
     pub fn show_blanknode(&self, bn: &OxBlankNode) -> String {
         let str: String = format!("{}", bn);
         format!("{}", str.green())
@@ -188,7 +273,7 @@ impl SRDFBasic for RdfData {
                     Literal::StringLiteral { lexical_form, lang } => match lang {
                         Some(lang) => OxLiteral::new_language_tagged_literal_unchecked(
                             lexical_form,
-                            lang.to_string(),
+                            lang.value(),
                         ),
                         None => OxLiteral::new_simple_literal(lexical_form),
                     },
@@ -201,10 +286,7 @@ impl SRDFBasic for RdfData {
                             let n: i128 = *n as i128;
                             OxLiteral::from(n)
                         }
-                        NumericLiteral::Decimal(d) => {
-                            let decimal = cnv_decimal(d);
-                            OxLiteral::from(decimal)
-                        }
+                        NumericLiteral::Decimal(d) => cnv_decimal(d),
                         NumericLiteral::Double(d) => OxLiteral::from(*d),
                     },
                     Literal::BooleanLiteral(b) => OxLiteral::from(*b),
@@ -333,39 +415,113 @@ impl QuerySRDF2 for RdfData {
         let mut sols: QuerySolutions<RdfData> = QuerySolutions::empty();
         let query = Query::parse(query_str, None)?;
         if let Some(store) = &self.store {
-            let new_sol = store.query(query)?;
-            let sol = cnv_query_results(new_sol)?;
-            sols.extend(sol)
+            let new_sol = self.query_store(store, query)?;
+            return cnv_query_results(new_sol);
         }
         for endpoint in &self.endpoints {
             let new_sols = endpoint.query_select(query_str)?;
+            let variables = new_sols.variables().cloned().collect();
             let new_sols_converted: Vec<QuerySolution2<RdfData>> =
                 new_sols.iter().map(cnv_sol).collect();
-            sols.extend(new_sols_converted)
+            sols.extend(QuerySolutions::new(variables, new_sols_converted))
         }
         Ok(sols)
     }
 
-    fn query_ask(&self, _query: &str) -> Result<bool, Self::Err> {
-        todo!()
+    fn query_ask(&self, query_str: &str) -> Result<bool, Self::Err> {
+        let query = Query::parse(query_str, None)?;
+        if let Some(store) = &self.store {
+            let result = self.query_store(store, query)?;
+            return Ok(matches!(result, QueryResults::Boolean(true)));
+        }
+        for endpoint in &self.endpoints {
+            if endpoint.query_ask(query_str)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn query_construct(&self, query_str: &str) -> Result<Vec<Triple<RdfData>>, RdfDataError> {
+        let query = Query::parse(query_str, None)?;
+        if let Some(store) = &self.store {
+            return graph_triples(self.query_store(store, query)?, "CONSTRUCT");
+        }
+        let mut triples = Vec::new();
+        for endpoint in &self.endpoints {
+            triples.extend(
+                endpoint
+                    .query_construct(query_str)?
+                    .into_iter()
+                    .map(cnv_triple),
+            )
+        }
+        Ok(triples)
+    }
+
+    fn query_describe(&self, query_str: &str) -> Result<Vec<Triple<RdfData>>, RdfDataError> {
+        let query = Query::parse(query_str, None)?;
+        if let Some(store) = &self.store {
+            return graph_triples(self.query_store(store, query)?, "DESCRIBE");
+        }
+        let mut triples = Vec::new();
+        for endpoint in &self.endpoints {
+            triples.extend(
+                endpoint
+                    .query_describe(query_str)?
+                    .into_iter()
+                    .map(cnv_triple),
+            )
+        }
+        Ok(triples)
+    }
+}
+
+fn cnv_triple(triple: Triple<SRDFSparql>) -> Triple<RdfData> {
+    Triple::new(triple.subj(), triple.pred(), triple.obj())
+}
+
+/// Triples of the results of a CONSTRUCT or DESCRIBE query, which fails if the query is of another form
+pub(crate) fn graph_triples<S>(
+    results: QueryResults,
+    form: &str,
+) -> Result<Vec<Triple<S>>, RdfDataError>
+where
+    S: SRDFBasic<Subject = OxSubject, IRI = OxNamedNode, Term = OxTerm>,
+{
+    let QueryResults::Graph(graph) = results else {
+        return Err(RdfDataError::ExpectedGraphQuery {
+            form: form.to_string(),
+        });
+    };
+    let mut triples = Vec::new();
+    for triple in graph {
+        let triple = triple?;
+        triples.push(Triple::new(triple.subject, triple.predicate, triple.object))
     }
+    Ok(triples)
 }
 
 fn cnv_sol(sol: &QuerySolution2<SRDFSparql>) -> QuerySolution2<RdfData> {
     sol.convert(|t| t.clone())
 }
 
-fn cnv_query_results(
-    query_results: QueryResults,
-) -> Result<Vec<QuerySolution2<RdfData>>, RdfDataError> {
+fn cnv_query_results(query_results: QueryResults) -> Result<QuerySolutions<RdfData>, RdfDataError> {
+    let mut variables = Vec::new();
     let mut results = Vec::new();
     if let QueryResults::Solutions(solutions) = query_results {
+        variables.extend(
+            solutions
+                .variables()
+                .iter()
+                .map(|v| VarName2::new(v.as_str())),
+        );
         for solution in solutions {
             let result = cnv_query_solution(solution?);
             results.push(result)
         }
     }
-    Ok(results)
+    Ok(QuerySolutions::new(variables, results))
 }
 
 fn cnv_query_solution(qs: QuerySolution) -> QuerySolution2<RdfData> {
@@ -393,7 +549,7 @@ fn _cnv_rdf_format(rdf_format: RDFFormat) -> RdfFormat {
     }
 }
 
-fn _rdf_type() -> OxNamedNode {
+fn rdf_type() -> OxNamedNode {
     OxNamedNode::new_unchecked(RDF_TYPE_STR)
 }
 
@@ -401,53 +557,111 @@ fn cnv_iri_ref(iri_ref: &IriRef) -> OxNamedNode {
     OxNamedNode::new_unchecked(iri_ref.to_string())
 }
 
-fn cnv_decimal(_d: &Decimal) -> oxsdatatypes::Decimal {
-    todo!()
+/// Converts a decimal to an `xsd:decimal` literal. Decimals that don't fit
+/// in the decimals of oxrdf, which have at most 18 fractional digits, are
+/// kept with their lexical form
+fn cnv_decimal(d: &Decimal) -> OxLiteral {
+    match oxsdatatypes::Decimal::from_str(&d.to_string()) {
+        Ok(decimal) => OxLiteral::from(decimal),
+        Err(_) => OxLiteral::new_typed_literal(d.to_string(), oxrdf::vocab::xsd::DECIMAL),
+    }
 }
 
 impl SRDF for RdfData {
     fn predicates_for_subject(
         &self,
-        _subject: &Self::Subject,
+        subject: &Self::Subject,
     ) -> Result<std::collections::HashSet<Self::IRI>, Self::Err> {
-        todo!()
+        let mut result = HashSet::new();
+        if let Some(graph) = &self.graph {
+            result.extend(graph.predicates_for_subject(subject)?);
+        }
+        for endpoint in self.endpoints_for(subject) {
+            result.extend(endpoint.predicates_for_subject(subject)?);
+        }
+        Ok(result)
     }
 
     fn objects_for_subject_predicate(
         &self,
-        _subject: &Self::Subject,
-        _pred: &Self::IRI,
+        subject: &Self::Subject,
+        pred: &Self::IRI,
     ) -> Result<std::collections::HashSet<Self::Term>, Self::Err> {
-        todo!()
+        let mut result = HashSet::new();
+        if let Some(graph) = &self.graph {
+            result.extend(graph.objects_for_subject_predicate(subject, pred)?);
+        }
+        for endpoint in self.endpoints_for(subject) {
+            result.extend(endpoint.objects_for_subject_predicate(subject, pred)?);
+        }
+        Ok(result)
     }
 
     fn subjects_with_predicate_object(
         &self,
-        _pred: &Self::IRI,
-        _object: &Self::Term,
+        pred: &Self::IRI,
+        object: &Self::Term,
     ) -> Result<std::collections::HashSet<Self::Subject>, Self::Err> {
-        todo!()
+        let mut result = HashSet::new();
+        if let Some(graph) = &self.graph {
+            result.extend(graph.subjects_with_predicate_object(pred, object)?);
+        }
+        for endpoint in self.endpoints_for_term(object) {
+            result.extend(endpoint.subjects_with_predicate_object(pred, object)?);
+        }
+        Ok(result)
     }
 
     fn triples_with_predicate(
         &self,
-        _pred: &Self::IRI,
+        pred: &Self::IRI,
     ) -> Result<Vec<srdf::Triple<Self>>, Self::Err> {
-        todo!()
+        let mut result = Vec::new();
+        if let Some(graph) = &self.graph {
+            result.extend(
+                graph
+                    .triples_with_predicate(pred)?
+                    .iter()
+                    .map(|t| srdf::Triple::new(t.subj(), t.pred(), t.obj())),
+            );
+        }
+        for endpoint in &self.endpoints {
+            result.extend(
+                endpoint
+                    .triples_with_predicate(pred)?
+                    .iter()
+                    .map(|t| srdf::Triple::new(t.subj(), t.pred(), t.obj())),
+            );
+        }
+        Ok(result)
     }
 
     fn outgoing_arcs(
         &self,
-        _subject: &Self::Subject,
+        subject: &Self::Subject,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
-        todo!()
+        let mut result = HashMap::new();
+        if let Some(graph) = &self.graph {
+            merge_arcs(&mut result, graph.outgoing_arcs(subject)?);
+        }
+        for endpoint in self.endpoints_for(subject) {
+            merge_arcs(&mut result, endpoint.outgoing_arcs(subject)?);
+        }
+        Ok(result)
     }
 
     fn incoming_arcs(
         &self,
-        _object: &Self::Term,
+        object: &Self::Term,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>, Self::Err> {
-        todo!()
+        let mut result = HashMap::new();
+        if let Some(graph) = &self.graph {
+            merge_arcs(&mut result, graph.incoming_arcs(object)?);
+        }
+        for endpoint in self.endpoints_for_term(object) {
+            merge_arcs(&mut result, endpoint.incoming_arcs(object)?);
+        }
+        Ok(result)
     }
 
     fn outgoing_arcs_from_list(
@@ -459,13 +673,31 @@ impl SRDF for RdfData {
         if let Some(graph) = &self.graph {
             merge_outgoing_arcs(&mut result, graph.outgoing_arcs_from_list(subject, preds)?);
         }
-        for endpoint in &self.endpoints {
+        for endpoint in self.endpoints_for(subject) {
             let next = endpoint.outgoing_arcs_from_list(subject, preds)?;
             merge_outgoing_arcs(&mut result, next)
         }
         Ok(result)
     }
 
+    fn outgoing_arcs_of_preds(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
+        let mut result = HashMap::new();
+        if let Some(graph) = &self.graph {
+            merge_arcs(&mut result, graph.outgoing_arcs_of_preds(subject, preds)?);
+        }
+        for endpoint in self.endpoints_for(subject) {
+            merge_arcs(
+                &mut result,
+                endpoint.outgoing_arcs_of_preds(subject, preds)?,
+            );
+        }
+        Ok(result)
+    }
+
     fn neighs(
         &self,
         node: &Self::Term,
@@ -487,41 +719,96 @@ impl SRDF for RdfData {
 
 impl SRDFBuilder for RdfData {
     fn empty() -> Self {
-        todo!()
+        RdfData {
+            endpoints: Vec::new(),
+            graph: Some(SRDFGraph::empty()),
+            prefixmap: PrefixMap::new(),
+            store: Store::new().ok(),
+        }
     }
 
-    fn add_base(&mut self, _base: &Option<IriS>) -> Result<(), Self::Err> {
-        todo!()
+    fn add_base(&mut self, base: &Option<IriS>) -> Result<(), Self::Err> {
+        if let Some(graph) = &mut self.graph {
+            graph.add_base(base)?;
+        }
+        Ok(())
     }
 
-    fn add_prefix(&mut self, _alias: &str, _iri: &IriS) -> Result<(), Self::Err> {
-        todo!()
+    fn add_prefix(&mut self, alias: &str, iri: &IriS) -> Result<(), Self::Err> {
+        let graph = self.graph.get_or_insert_with(SRDFGraph::empty);
+        graph.add_prefix(alias, iri)?;
+        self.prefixmap = graph.prefixmap();
+        Ok(())
     }
 
-    fn add_prefix_map(&mut self, _prefix_map: PrefixMap) -> Result<(), Self::Err> {
-        todo!()
+    fn add_prefix_map(&mut self, prefix_map: PrefixMap) -> Result<(), Self::Err> {
+        if let Some(graph) = &mut self.graph {
+            graph.add_prefix_map(prefix_map.clone())?;
+        }
+        self.prefixmap = prefix_map;
+        Ok(())
     }
 
+    /// Prefixed names are resolved with the prefixes of the data
+    fn resolve_iri_ref(&self, iri: &IriRef) -> Result<IriS, Self::Err> {
+        match iri {
+            IriRef::Iri(iri) => Ok(iri.clone()),
+            IriRef::Prefixed { prefix, local } => Ok(self.resolve_prefix_local(prefix, local)?),
+        }
+    }
+
+    /// Triples are added to the in-memory graph and to the store that is used
+    /// to answer SPARQL queries, so both of them remain in sync
     fn add_triple(
         &mut self,
-        _subj: &Self::Subject,
-        _pred: &Self::IRI,
-        _obj: &Self::Term,
+        subj: &Self::Subject,
+        pred: &Self::IRI,
+        obj: &Self::Term,
     ) -> Result<(), Self::Err> {
-        todo!()
+        self.graph
+            .get_or_insert_with(SRDFGraph::empty)
+            .add_triple(subj, pred, obj)?;
+        if let Some(store) = &self.store {
+            store.insert(QuadRef::new(subj, pred, obj, GraphNameRef::DefaultGraph))?;
+        }
+        Ok(())
+    }
+
+    fn add_node_triple(
+        &mut self,
+        node: &srdf::RDFNode,
+        pred: &Self::IRI,
+        obj: &Self::Term,
+    ) -> Result<(), Self::Err> {
+        match Self::object_as_subject(node) {
+            Some(subj) => self.add_triple(&subj, pred, obj),
+            None => Err(RdfDataError::ExpectedSubject {
+                node: node.to_string(),
+            }),
+        }
     }
 
     fn remove_triple(
         &mut self,
-        _subj: &Self::Subject,
-        _pred: &Self::IRI,
-        _obj: &Self::Term,
+        subj: &Self::Subject,
+        pred: &Self::IRI,
+        obj: &Self::Term,
     ) -> Result<(), Self::Err> {
-        todo!()
+        if let Some(graph) = &mut self.graph {
+            graph.remove_triple(subj, pred, obj)?;
+        }
+        if let Some(store) = &self.store {
+            store.remove(QuadRef::new(subj, pred, obj, GraphNameRef::DefaultGraph))?;
+        }
+        Ok(())
     }
 
-    fn add_type(&mut self, _node: &srdf::RDFNode, _type_: Self::Term) -> Result<(), Self::Err> {
-        todo!()
+    fn add_type(&mut self, node: &srdf::RDFNode, type_: Self::Term) -> Result<(), Self::Err> {
+        self.add_node_triple(node, &rdf_type(), &type_)
+    }
+
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err> {
+        Ok(OxBlankNode::default())
     }
 
     fn serialize<W: std::io::Write>(
@@ -554,12 +841,189 @@ fn merge_outgoing_arcs<I, T>(
     for v in next_vs {
         current_vs.push(v)
     }
-    for (key, values) in next_map {
-        current_map
-            .entry(key)
-            .and_modify(|current_values| {
-                let _ = current_values.union(&values);
-            })
-            .or_insert(values);
+    merge_arcs(current_map, next_map)
+}
+
+fn merge_arcs<I, T>(current: &mut HashMap<I, HashSet<T>>, next: HashMap<I, HashSet<T>>)
+where
+    I: Eq + Hash,
+    T: Eq + Hash,
+{
+    for (key, values) in next {
+        current.entry(key).or_default().extend(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use srdf::ReaderMode;
+
+    use super::*;
+
+    #[test]
+    fn query_select_keeps_variables_without_solutions() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ."#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let rdf_data = RdfData::from_graph(graph).unwrap();
+        let query = "prefix : <http://example.org/> select ?x ?y where { ?x :knows ?y }";
+        let solutions = rdf_data.query_select(query).unwrap();
+        assert!(solutions.is_empty());
+        let variables: Vec<&str> = solutions.variables().map(|v| v.as_str()).collect();
+        assert_eq!(variables, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn add_endpoint_keeps_declared_prefixes() {
+        let data = r#"prefix ex: <http://example.org/>
+            ex:alice ex:name "Alice" ."#;
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let mut endpoint_pm = PrefixMap::new();
+        endpoint_pm
+            .insert("ex", &IriS::new_unchecked("http://other.example.org/"))
+            .unwrap();
+        endpoint_pm
+            .insert(
+                "wd",
+                &IriS::new_unchecked("http://www.wikidata.org/entity/"),
+            )
+            .unwrap();
+        let endpoint = SRDFSparql::new(&IriS::new_unchecked("http://localhost/sparql"))
+            .unwrap()
+            .with_prefixmap(endpoint_pm);
+        let rdf_data = RdfData::from_graph(graph)
+            .unwrap()
+            .with_endpoint(endpoint)
+            .unwrap();
+        let pm = rdf_data.prefixmap();
+        assert_eq!(
+            pm.find("ex"),
+            Some(&IriS::new_unchecked("http://example.org/"))
+        );
+        assert_eq!(
+            pm.find("wd"),
+            Some(&IriS::new_unchecked("http://www.wikidata.org/entity/"))
+        );
+        assert_eq!(rdf_data.endpoints().len(), 1);
+    }
+
+    #[test]
+    fn merge_from_reader_adds_to_the_loaded_data() {
+        let endpoint = SRDFSparql::new(&IriS::new_unchecked("http://localhost/sparql")).unwrap();
+        let mut rdf_data = RdfData::from_endpoint(endpoint);
+        let first = r#"prefix : <http://example.org/>
+            :alice :knows :bob ."#;
+        let second = r#"prefix ex: <http://example.org/>
+            ex:bob ex:knows ex:carol ."#;
+        for data in [first, second] {
+            rdf_data
+                .merge_from_reader(
+                    data.as_bytes(),
+                    &RDFFormat::Turtle,
+                    None,
+                    &ReaderMode::Strict,
+                )
+                .unwrap();
+        }
+        assert!(rdf_data
+            .merge_from_reader(
+                "not turtle".as_bytes(),
+                &RDFFormat::Turtle,
+                None,
+                &ReaderMode::Strict
+            )
+            .is_err());
+        assert_eq!(rdf_data.graph().unwrap().len(), 2);
+        let query = "select ?x where { ?x <http://example.org/knows> ?y }";
+        assert_eq!(rdf_data.query_select(query).unwrap().len(), 2);
+        assert!(rdf_data.prefixmap().find("").is_some());
+        assert!(rdf_data.prefixmap().find("ex").is_some());
+        assert_eq!(rdf_data.endpoints().len(), 1);
+    }
+
+    #[cfg(feature = "local-endpoint")]
+    #[test]
+    fn construct_and_describe_return_the_results_of_the_store() {
+        use crate::LocalEndpoint;
+
+        let local = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob ."#;
+        let remote = r#"prefix : <http://example.org/>
+            :alice :name "Remote Alice" ."#;
+        let graph =
+            SRDFGraph::from_str(local, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let remote =
+            SRDFGraph::from_str(remote, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let endpoint = LocalEndpoint::new(&remote).unwrap();
+        let rdf_data = RdfData::from_graph(graph)
+            .unwrap()
+            .with_endpoint(SRDFSparql::new(endpoint.iri()).unwrap())
+            .unwrap();
+        let triples = rdf_data
+            .query_construct("construct { ?s ?p ?o } where { ?s ?p ?o }")
+            .unwrap();
+        assert_eq!(triples.len(), 2);
+        let triples = rdf_data
+            .query_describe("describe <http://example.org/alice>")
+            .unwrap();
+        assert_eq!(triples.len(), 2);
+        assert_eq!(endpoint.requests(), 0);
+        assert!(matches!(
+            rdf_data.query_describe("select * where { ?s ?p ?o }"),
+            Err(RdfDataError::ExpectedGraphQuery { .. })
+        ));
+    }
+
+    #[cfg(feature = "local-endpoint")]
+    #[test]
+    fn describe_without_store_is_delegated_to_the_endpoints() {
+        use crate::LocalEndpoint;
+
+        let remote = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :knows :bob ."#;
+        let remote =
+            SRDFGraph::from_str(remote, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let endpoint = LocalEndpoint::new(&remote).unwrap();
+        let rdf_data = RdfData::from_endpoint(SRDFSparql::new(endpoint.iri()).unwrap());
+        let triples = rdf_data
+            .query_describe("describe <http://example.org/alice>")
+            .unwrap();
+        assert_eq!(triples.len(), 2);
+        assert_eq!(endpoint.requests(), 1);
+    }
+
+    #[cfg(feature = "local-endpoint")]
+    #[test]
+    fn service_clauses_are_only_delegated_to_configured_endpoints() {
+        use crate::LocalEndpoint;
+
+        let local = r#"prefix : <http://example.org/>
+            :alice :knows :bob ."#;
+        let remote = r#"prefix : <http://example.org/>
+            :bob :name "Bob" ."#;
+        let graph =
+            SRDFGraph::from_str(local, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let remote =
+            SRDFGraph::from_str(remote, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let configured = LocalEndpoint::new(&remote).unwrap();
+        let other = LocalEndpoint::new(&remote).unwrap();
+        let rdf_data = RdfData::from_graph(graph)
+            .unwrap()
+            .with_endpoint(SRDFSparql::new(configured.iri()).unwrap())
+            .unwrap();
+        let query = |endpoint: &LocalEndpoint| {
+            format!(
+                "prefix : <http://example.org/>
+                select ?name where {{ ?x :knows ?y . SERVICE <{}> {{ ?y :name ?name }} }}",
+                endpoint.iri().as_str()
+            )
+        };
+        let solutions = rdf_data.query_select(&query(&configured)).unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(configured.requests(), 1);
+        assert!(rdf_data.query_select(&query(&other)).is_err());
+        assert_eq!(other.requests(), 0);
     }
 }
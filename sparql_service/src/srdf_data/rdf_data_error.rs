@@ -4,12 +4,22 @@ use oxigraph::{
     sparql::{EvaluationError, SparqlSyntaxError},
     store::StorageError,
 };
+use prefixmap::PrefixMapError;
 use thiserror::Error;
 
 use srdf::{SRDFGraphError, SRDFSparqlError};
 
 #[derive(Debug, Error)]
 pub enum RdfDataError {
+    #[error("Node {node} cannot be the subject of a triple")]
+    ExpectedSubject { node: String },
+
+    #[error("Expected a {form} query, whose results are the triples of a graph")]
+    ExpectedGraphQuery { form: String },
+
+    #[error("SERVICE {service} is not one of the configured endpoints")]
+    UnknownService { service: String },
+
     #[error(transparent)]
     SRDFSparqlError {
         #[from]
@@ -45,4 +55,10 @@ pub enum RdfDataError {
         #[from]
         err: EvaluationError,
     },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
 }
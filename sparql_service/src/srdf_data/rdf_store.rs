@@ -0,0 +1,766 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use colored::*;
+use iri_s::IriS;
+use oxigraph::sparql::{Query, QueryResults};
+use oxigraph::store::Store;
+use oxiri::Iri;
+use oxrdf::{
+    BlankNode as OxBlankNode, GraphNameRef, Literal as OxLiteral, NamedNode as OxNamedNode, Quad,
+    QuadRef, Subject as OxSubject, Term as OxTerm, TripleRef,
+};
+use oxrdfio::{RdfFormat, RdfParser, RdfSerializer};
+use prefixmap::{IriRef, PrefixMap};
+use srdf::{
+    QuerySRDF2, QuerySolution2, QuerySolutions, RDFFormat, ReaderMode, SRDFBasic, SRDFBuilder,
+    SRDFGraph, Triple, VarName2, RDF_TYPE_STR, SRDF,
+};
+use tracing::debug;
+
+use super::rdf_data::graph_triples;
+use super::{RdfData, RdfDataError};
+
+/// Name of the file of the store directory in which the prefix declarations of the loaded data are kept
+const PREFIXES_FILE: &str = "rudof_prefixes.json";
+
+/// RDF data kept in a persistent oxigraph store backed by RocksDB.
+///
+/// The data is loaded once in a directory with [`RdfStore::load_reader`] and later runs open that
+/// directory instead of parsing the data again, which is the way to handle datasets which are too big
+/// to be loaded in memory each time. The triples of all the graphs of the store are visible, so quads in
+/// named graphs behave as if they were merged in the default graph
+pub struct RdfStore {
+    store: Option<Store>,
+    path: Option<PathBuf>,
+    prefixmap: PrefixMap,
+    base: Option<IriS>,
+}
+
+impl RdfStore {
+    /// Opens the store of a directory, creating it if it doesn't exist
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<RdfStore, RdfDataError> {
+        let store = Store::open(path.as_ref())?;
+        Self::with_store(store, path.as_ref())
+    }
+
+    /// Opens the store of a directory without locking it, so several processes can read it at the same time
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<RdfStore, RdfDataError> {
+        let store = Store::open_read_only(path.as_ref())?;
+        Self::with_store(store, path.as_ref())
+    }
+
+    fn with_store(store: Store, path: &Path) -> Result<RdfStore, RdfDataError> {
+        let prefixes = path.join(PREFIXES_FILE);
+        let prefixmap = if prefixes.exists() {
+            let str = fs::read_to_string(&prefixes)?;
+            serde_json::from_str(&str).map_err(io::Error::other)?
+        } else {
+            PrefixMap::new()
+        };
+        Ok(RdfStore {
+            store: Some(store),
+            path: Some(path.to_path_buf()),
+            prefixmap,
+            base: None,
+        })
+    }
+
+    /// The store in which the data is written, the in-memory store of an empty `RdfStore` is created the
+    /// first time it is needed
+    fn store(&mut self) -> Result<&Store, RdfDataError> {
+        match self.store {
+            Some(ref store) => Ok(store),
+            None => Ok(self.store.insert(Store::new()?)),
+        }
+    }
+
+    /// Adds the RDF data of a reader with the bulk loader of the store.
+    ///
+    /// Blank nodes are renamed so the ones of different loads are not merged. The prefixes declared in the
+    /// data are added to the prefixes of the store. Relative IRIs are resolved with `base`, or with the
+    /// base of the store if there is none.
+    ///
+    /// The quads are streamed to the bulk loader, which is not transactional, so the quads loaded before
+    /// an error reading the data, or a syntax error in strict mode, are kept in the store. The prefixes are
+    /// only added when the whole data has been loaded
+    pub fn load_reader<R: BufRead>(
+        &mut self,
+        reader: R,
+        format: &RDFFormat,
+        base: Option<&str>,
+        reader_mode: &ReaderMode,
+    ) -> Result<(), RdfDataError> {
+        let default_base = self.base.clone();
+        let base = base.or(default_base.as_ref().map(|b| b.as_str()));
+        if let RDFFormat::N3 = format {
+            // N3 statements which are not facts are filtered by the graph
+            let base = base
+                .map(|b| Iri::parse(b.to_string()))
+                .transpose()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+            let graph = SRDFGraph::from_reader(reader, format, base, reader_mode)?;
+            self.store()?.bulk_loader().load_quads(graph.quads())?;
+            return self.add_prefix_map(graph.prefixmap());
+        }
+        let mut parser = RdfParser::from_format(cnv_rdf_format(format)).rename_blank_nodes();
+        if let Some(base) = base {
+            parser = parser
+                .with_base_iri(base)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        }
+        let mut quads = parser.for_reader(reader);
+        let strict = reader_mode.is_strict();
+        self.store()?
+            .bulk_loader()
+            .load_ok_quads::<RdfDataError, RdfDataError>(quads.by_ref().filter_map(
+                |quad| match quad {
+                    Ok(quad) => Some(Ok(quad)),
+                    Err(e) if strict => Some(Err(io::Error::other(e).into())),
+                    Err(e) => {
+                        debug!("Error captured: {e:?}");
+                        None
+                    }
+                },
+            ))?;
+        let prefixes: HashMap<&str, &str> = quads.prefixes().collect();
+        let prefixmap = PrefixMap::from_hashmap(&prefixes)?;
+        self.add_prefix_map(prefixmap)
+    }
+
+    /// Number of quads of the store
+    pub fn len(&self) -> Result<usize, RdfDataError> {
+        match &self.store {
+            Some(store) => Ok(store.len()?),
+            None => Ok(0),
+        }
+    }
+
+    pub fn is_empty(&self) -> Result<bool, RdfDataError> {
+        match &self.store {
+            Some(store) => Ok(store.is_empty()?),
+            None => Ok(true),
+        }
+    }
+
+    /// Directory of the store, `None` if it is kept in memory
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn prefixmap(&self) -> PrefixMap {
+        self.prefixmap.clone()
+    }
+
+    /// Merges the prefixes and saves them with the store, the aliases which are already declared are kept
+    fn add_prefix_map(&mut self, prefixmap: PrefixMap) -> Result<(), RdfDataError> {
+        for (alias, iri) in prefixmap.iter() {
+            if self.prefixmap.find(alias).is_none() {
+                self.prefixmap.insert(alias, iri)?
+            }
+        }
+        self.save_prefixes()
+    }
+
+    fn save_prefixes(&self) -> Result<(), RdfDataError> {
+        if let Some(path) = &self.path {
+            let str = serde_json::to_string_pretty(&self.prefixmap).map_err(io::Error::other)?;
+            fs::write(path.join(PREFIXES_FILE), str)?;
+        }
+        Ok(())
+    }
+
+    fn quads_for_pattern(
+        &self,
+        subject: Option<&OxSubject>,
+        predicate: Option<&OxNamedNode>,
+        object: Option<&OxTerm>,
+    ) -> impl Iterator<Item = Result<Quad, RdfDataError>> {
+        let quads = self.store.as_ref().map(|store| {
+            store.quads_for_pattern(
+                subject.map(|s| s.into()),
+                predicate.map(|p| p.into()),
+                object.map(|o| o.into()),
+                None,
+            )
+        });
+        quads.into_iter().flatten().map(|quad| Ok(quad?))
+    }
+
+    /// Evaluates a query resolving its relative IRIs with the base of the store. A store which has not
+    /// been created yet is queried as an empty one
+    fn query(&self, query_str: &str) -> Result<QueryResults, RdfDataError> {
+        let mut query = Query::parse(query_str, self.base.as_ref().map(|b| b.as_str()))?;
+        query.dataset_mut().set_default_graph_as_union();
+        let store = match &self.store {
+            Some(store) => store.clone(),
+            None => Store::new()?,
+        };
+        Ok(store.query(query)?)
+    }
+}
+
+impl SRDFBasic for RdfStore {
+    type IRI = OxNamedNode;
+    type BNode = OxBlankNode;
+    type Literal = OxLiteral;
+    type Subject = OxSubject;
+    type Term = OxTerm;
+    type Err = RdfDataError;
+
+    fn subject_as_iri(subject: &Self::Subject) -> Option<Self::IRI> {
+        RdfData::subject_as_iri(subject)
+    }
+
+    fn subject_as_bnode(subject: &Self::Subject) -> Option<Self::BNode> {
+        RdfData::subject_as_bnode(subject)
+    }
+
+    fn subject_is_iri(subject: &Self::Subject) -> bool {
+        RdfData::subject_is_iri(subject)
+    }
+
+    fn subject_is_bnode(subject: &Self::Subject) -> bool {
+        RdfData::subject_is_bnode(subject)
+    }
+
+    fn term_as_iri(object: &Self::Term) -> Option<Self::IRI> {
+        RdfData::term_as_iri(object)
+    }
+
+    fn term_as_bnode(object: &Self::Term) -> Option<Self::BNode> {
+        RdfData::term_as_bnode(object)
+    }
+
+    fn term_as_literal(object: &Self::Term) -> Option<Self::Literal> {
+        RdfData::term_as_literal(object)
+    }
+
+    fn term_as_object(term: &Self::Term) -> srdf::Object {
+        RdfData::term_as_object(term)
+    }
+
+    fn object_as_term(obj: &srdf::Object) -> Self::Term {
+        RdfData::object_as_term(obj)
+    }
+
+    fn term_is_iri(object: &Self::Term) -> bool {
+        RdfData::term_is_iri(object)
+    }
+
+    fn term_is_bnode(object: &Self::Term) -> bool {
+        RdfData::term_is_bnode(object)
+    }
+
+    fn term_is_literal(object: &Self::Term) -> bool {
+        RdfData::term_is_literal(object)
+    }
+
+    fn term_as_subject(object: &Self::Term) -> Option<Self::Subject> {
+        RdfData::term_as_subject(object)
+    }
+
+    fn subject_as_term(subject: &Self::Subject) -> Self::Term {
+        RdfData::subject_as_term(subject)
+    }
+
+    fn lexical_form(literal: &Self::Literal) -> &str {
+        RdfData::lexical_form(literal)
+    }
+
+    fn lang(literal: &Self::Literal) -> Option<String> {
+        RdfData::lang(literal)
+    }
+
+    fn datatype(literal: &Self::Literal) -> Self::IRI {
+        RdfData::datatype(literal)
+    }
+
+    fn iri_s2iri(iri_s: &IriS) -> Self::IRI {
+        RdfData::iri_s2iri(iri_s)
+    }
+
+    fn term_s2term(term: &OxTerm) -> Self::Term {
+        RdfData::term_s2term(term)
+    }
+
+    fn bnode_id2bnode(id: &str) -> Self::BNode {
+        RdfData::bnode_id2bnode(id)
+    }
+
+    fn iri_as_term(iri: Self::IRI) -> Self::Term {
+        RdfData::iri_as_term(iri)
+    }
+
+    fn iri_as_subject(iri: Self::IRI) -> Self::Subject {
+        RdfData::iri_as_subject(iri)
+    }
+
+    fn bnode_as_term(bnode: Self::BNode) -> Self::Term {
+        RdfData::bnode_as_term(bnode)
+    }
+
+    fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
+        RdfData::bnode_as_subject(bnode)
+    }
+
+    fn iri2iri_s(iri: &Self::IRI) -> IriS {
+        RdfData::iri2iri_s(iri)
+    }
+
+    fn qualify_iri(&self, node: &Self::IRI) -> String {
+        self.prefixmap.qualify(&IriS::from_named_node(node))
+    }
+
+    fn qualify_subject(&self, subj: &Self::Subject) -> String {
+        match subj {
+            OxSubject::NamedNode(n) => self.qualify_iri(n),
+            _ => subj.to_string().green().to_string(),
+        }
+    }
+
+    fn qualify_term(&self, term: &Self::Term) -> String {
+        match term {
+            OxTerm::NamedNode(n) => self.qualify_iri(n),
+            OxTerm::Literal(lit) => lit.to_string().red().to_string(),
+            _ => term.to_string().green().to_string(),
+        }
+    }
+
+    fn prefixmap(&self) -> Option<PrefixMap> {
+        Some(self.prefixmap.clone())
+    }
+
+    fn resolve_prefix_local(
+        &self,
+        prefix: &str,
+        local: &str,
+    ) -> Result<IriS, prefixmap::PrefixMapError> {
+        let iri = self.prefixmap.resolve_prefix_local(prefix, local)?;
+        Ok(iri.clone())
+    }
+}
+
+impl SRDF for RdfStore {
+    fn predicates_for_subject(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HashSet<Self::IRI>, Self::Err> {
+        self.quads_for_pattern(Some(subject), None, None)
+            .map(|quad| Ok(quad?.predicate))
+            .collect()
+    }
+
+    fn objects_for_subject_predicate(
+        &self,
+        subject: &Self::Subject,
+        pred: &Self::IRI,
+    ) -> Result<HashSet<Self::Term>, Self::Err> {
+        self.quads_for_pattern(Some(subject), Some(pred), None)
+            .map(|quad| Ok(quad?.object))
+            .collect()
+    }
+
+    fn subjects_with_predicate_object(
+        &self,
+        pred: &Self::IRI,
+        object: &Self::Term,
+    ) -> Result<HashSet<Self::Subject>, Self::Err> {
+        self.quads_for_pattern(None, Some(pred), Some(object))
+            .map(|quad| Ok(quad?.subject))
+            .collect()
+    }
+
+    fn triples_with_predicate(&self, pred: &Self::IRI) -> Result<Vec<Triple<Self>>, Self::Err> {
+        // The same triple can be in several graphs
+        let pairs: HashSet<(OxSubject, OxTerm)> = self
+            .quads_for_pattern(None, Some(pred), None)
+            .map(|quad| quad.map(|q| (q.subject, q.object)))
+            .collect::<Result<_, _>>()?;
+        Ok(pairs
+            .into_iter()
+            .map(|(subj, obj)| Triple::new(subj, pred.clone(), obj))
+            .collect())
+    }
+
+    fn outgoing_arcs(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
+        let mut result: HashMap<_, HashSet<_>> = HashMap::new();
+        for quad in self.quads_for_pattern(Some(subject), None, None) {
+            let quad = quad?;
+            result
+                .entry(quad.predicate)
+                .or_default()
+                .insert(quad.object);
+        }
+        Ok(result)
+    }
+
+    fn incoming_arcs(
+        &self,
+        object: &Self::Term,
+    ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>, Self::Err> {
+        let mut result: HashMap<_, HashSet<_>> = HashMap::new();
+        for quad in self.quads_for_pattern(None, None, Some(object)) {
+            let quad = quad?;
+            result
+                .entry(quad.predicate)
+                .or_default()
+                .insert(quad.subject);
+        }
+        Ok(result)
+    }
+
+    fn outgoing_arcs_from_list(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<(HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>), Self::Err> {
+        let mut result: HashMap<_, HashSet<_>> = HashMap::new();
+        let mut remainder = Vec::new();
+        for quad in self.quads_for_pattern(Some(subject), None, None) {
+            let quad = quad?;
+            if preds.contains(&quad.predicate) {
+                result
+                    .entry(quad.predicate)
+                    .or_default()
+                    .insert(quad.object);
+            } else {
+                remainder.push(quad.predicate)
+            }
+        }
+        Ok((result, remainder))
+    }
+}
+
+impl QuerySRDF2 for RdfStore {
+    fn query_select(&self, query_str: &str) -> Result<QuerySolutions<RdfStore>, RdfDataError> {
+        let mut variables = Vec::new();
+        let mut results = Vec::new();
+        if let QueryResults::Solutions(solutions) = self.query(query_str)? {
+            variables.extend(
+                solutions
+                    .variables()
+                    .iter()
+                    .map(|v| VarName2::new(v.as_str())),
+            );
+            let shared = Rc::new(variables.clone());
+            for solution in solutions {
+                let values = solution?.values().to_vec();
+                results.push(QuerySolution2::new(shared.clone(), values))
+            }
+        }
+        Ok(QuerySolutions::new(variables, results))
+    }
+
+    fn query_ask(&self, query_str: &str) -> Result<bool, RdfDataError> {
+        Ok(matches!(
+            self.query(query_str)?,
+            QueryResults::Boolean(true)
+        ))
+    }
+
+    fn query_construct(&self, query_str: &str) -> Result<Vec<Triple<RdfStore>>, RdfDataError> {
+        graph_triples(self.query(query_str)?, "CONSTRUCT")
+    }
+
+    fn query_describe(&self, query_str: &str) -> Result<Vec<Triple<RdfStore>>, RdfDataError> {
+        graph_triples(self.query(query_str)?, "DESCRIBE")
+    }
+}
+
+impl SRDFBuilder for RdfStore {
+    /// Store kept in memory, which is not persisted
+    fn empty() -> Self {
+        RdfStore {
+            store: Store::new().ok(),
+            path: None,
+            prefixmap: PrefixMap::new(),
+            base: None,
+        }
+    }
+
+    /// The base is used for the loads without base and for the queries, it is not saved with the store
+    fn add_base(&mut self, base: &Option<IriS>) -> Result<(), Self::Err> {
+        self.base.clone_from(base);
+        Ok(())
+    }
+
+    fn add_prefix(&mut self, alias: &str, iri: &IriS) -> Result<(), Self::Err> {
+        self.prefixmap.insert(alias, iri)?;
+        self.save_prefixes()
+    }
+
+    fn add_prefix_map(&mut self, prefix_map: PrefixMap) -> Result<(), Self::Err> {
+        RdfStore::add_prefix_map(self, prefix_map)
+    }
+
+    fn resolve_iri_ref(&self, iri: &IriRef) -> Result<IriS, Self::Err> {
+        match iri {
+            IriRef::Iri(iri) => Ok(iri.clone()),
+            IriRef::Prefixed { prefix, local } => Ok(self.resolve_prefix_local(prefix, local)?),
+        }
+    }
+
+    fn add_triple(
+        &mut self,
+        subj: &Self::Subject,
+        pred: &Self::IRI,
+        obj: &Self::Term,
+    ) -> Result<(), Self::Err> {
+        self.store()?
+            .insert(QuadRef::new(subj, pred, obj, GraphNameRef::DefaultGraph))?;
+        Ok(())
+    }
+
+    fn add_node_triple(
+        &mut self,
+        node: &srdf::RDFNode,
+        pred: &Self::IRI,
+        obj: &Self::Term,
+    ) -> Result<(), Self::Err> {
+        match Self::object_as_subject(node) {
+            Some(subj) => self.add_triple(&subj, pred, obj),
+            None => Err(RdfDataError::ExpectedSubject {
+                node: node.to_string(),
+            }),
+        }
+    }
+
+    fn remove_triple(
+        &mut self,
+        subj: &Self::Subject,
+        pred: &Self::IRI,
+        obj: &Self::Term,
+    ) -> Result<(), Self::Err> {
+        if let Some(store) = &self.store {
+            store.remove(QuadRef::new(subj, pred, obj, GraphNameRef::DefaultGraph))?;
+        }
+        Ok(())
+    }
+
+    fn add_type(&mut self, node: &srdf::RDFNode, type_: Self::Term) -> Result<(), Self::Err> {
+        self.add_node_triple(node, &OxNamedNode::new_unchecked(RDF_TYPE_STR), &type_)
+    }
+
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err> {
+        Ok(OxBlankNode::default())
+    }
+
+    /// The triples of triple formats are written as they are retrieved from the store, without sorting them
+    fn serialize<W: std::io::Write>(
+        &self,
+        format: RDFFormat,
+        writer: &mut W,
+    ) -> Result<(), Self::Err> {
+        let rdf_format = cnv_rdf_format(&format);
+        let mut serializer = RdfSerializer::from_format(rdf_format);
+        for (alias, iri) in self.prefixmap.iter() {
+            if let Ok(with_prefix) = serializer.clone().with_prefix(alias, iri.as_str()) {
+                serializer = with_prefix
+            }
+        }
+        let mut serializer = serializer.for_writer(writer);
+        for quad in self.store.iter().flat_map(|store| store.iter()) {
+            let quad = quad?;
+            if rdf_format.supports_datasets() {
+                serializer.serialize_quad(&quad)?
+            } else {
+                serializer.serialize_triple(TripleRef::from(quad.as_ref()))?
+            }
+        }
+        serializer.finish()?;
+        Ok(())
+    }
+}
+
+fn cnv_rdf_format(rdf_format: &RDFFormat) -> RdfFormat {
+    match rdf_format {
+        RDFFormat::NTriples => RdfFormat::NTriples,
+        RDFFormat::Turtle => RdfFormat::Turtle,
+        RDFFormat::RDFXML => RdfFormat::RdfXml,
+        RDFFormat::TriG => RdfFormat::TriG,
+        RDFFormat::N3 => RdfFormat::N3,
+        RDFFormat::NQuads => RdfFormat::NQuads,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice :name "Alice" ; :knows :bob, _:x .
+:bob :name "Bob" .
+_:x :name "Unknown" ."#;
+
+    fn ex(local: &str) -> OxNamedNode {
+        OxNamedNode::new_unchecked(format!("http://example.org/{local}"))
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rudof_store_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn loaded_data_is_kept_between_openings() {
+        let dir = temp_dir("reopen");
+        {
+            let mut store = RdfStore::open(&dir).unwrap();
+            store
+                .load_reader(
+                    DATA.as_bytes(),
+                    &RDFFormat::Turtle,
+                    None,
+                    &ReaderMode::Strict,
+                )
+                .unwrap();
+            assert_eq!(store.len().unwrap(), 5);
+        }
+        let store = RdfStore::open_read_only(&dir).unwrap();
+        let alice = OxSubject::NamedNode(ex("alice"));
+        assert_eq!(store.outgoing_arcs(&alice).unwrap()[&ex("knows")].len(), 2);
+        assert_eq!(store.qualify_iri(&ex("knows")), ":knows");
+        let names = store
+            .query_select("select ?n where { ?p <http://example.org/name> ?n }")
+            .unwrap();
+        assert_eq!(names.iter().count(), 3);
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn blank_nodes_of_different_loads_are_not_merged() {
+        let dir = temp_dir("bnodes");
+        let mut store = RdfStore::open(&dir).unwrap();
+        for _ in 0..2 {
+            store
+                .load_reader(
+                    DATA.as_bytes(),
+                    &RDFFormat::Turtle,
+                    None,
+                    &ReaderMode::Strict,
+                )
+                .unwrap();
+        }
+        assert_eq!(store.len().unwrap(), 7);
+        let bob = OxTerm::NamedNode(ex("bob"));
+        assert_eq!(store.incoming_arcs(&bob).unwrap()[&ex("knows")].len(), 1);
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn named_graphs_are_part_of_the_data() {
+        let data = r#"prefix : <http://example.org/>
+:g { :alice :knows :bob . }"#;
+        let mut store = RdfStore::empty();
+        store
+            .load_reader(data.as_bytes(), &RDFFormat::TriG, None, &ReaderMode::Strict)
+            .unwrap();
+        let alice = OxSubject::NamedNode(ex("alice"));
+        assert_eq!(store.predicates_for_subject(&alice).unwrap().len(), 1);
+        assert!(store
+            .query_ask("ask { <http://example.org/alice> ?p ?o }")
+            .unwrap());
+    }
+
+    #[test]
+    fn strict_mode_stops_at_syntax_errors() {
+        let data = "<http://example.org/a> <http://example.org/p> .\n\
+                    <http://example.org/a> <http://example.org/p> <http://example.org/b> .";
+        let mut store = RdfStore::empty();
+        assert!(store
+            .load_reader(
+                data.as_bytes(),
+                &RDFFormat::NTriples,
+                None,
+                &ReaderMode::Strict
+            )
+            .is_err());
+        store
+            .load_reader(
+                data.as_bytes(),
+                &RDFFormat::NTriples,
+                None,
+                &ReaderMode::Lax,
+            )
+            .unwrap();
+        assert_eq!(store.len().unwrap(), 1);
+    }
+
+    #[test]
+    fn failed_loads_leave_the_prefixes_unchanged() {
+        let dir = temp_dir("failed");
+        let mut store = RdfStore::open(&dir).unwrap();
+        let data = "prefix : <http://example.org/>\n\
+                    :alice :knows :bob .\n\
+                    :bob :knows .";
+        assert!(store
+            .load_reader(
+                data.as_bytes(),
+                &RDFFormat::Turtle,
+                None,
+                &ReaderMode::Strict
+            )
+            .is_err());
+        assert!(store.prefixmap().find("").is_none());
+        assert!(!dir.join(PREFIXES_FILE).exists());
+        drop(store);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prefix_maps_are_merged() {
+        let mut store = RdfStore::empty();
+        store
+            .add_prefix("ex", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        let mut prefixmap = PrefixMap::new();
+        prefixmap
+            .insert("ex", &IriS::new_unchecked("http://other.org/"))
+            .unwrap();
+        prefixmap
+            .insert("foaf", &IriS::new_unchecked("http://xmlns.com/foaf/0.1/"))
+            .unwrap();
+        SRDFBuilder::add_prefix_map(&mut store, prefixmap).unwrap();
+        assert_eq!(store.qualify_iri(&ex("alice")), "ex:alice");
+        assert!(store.prefixmap().find("foaf").is_some());
+    }
+
+    #[test]
+    fn relative_iris_are_resolved_with_the_base() {
+        let mut store = RdfStore::empty();
+        store
+            .add_base(&Some(IriS::new_unchecked("http://example.org/")))
+            .unwrap();
+        store
+            .load_reader(
+                "<alice> <knows> <bob> .".as_bytes(),
+                &RDFFormat::Turtle,
+                None,
+                &ReaderMode::Strict,
+            )
+            .unwrap();
+        assert!(store.query_ask("ask { <alice> <knows> <bob> }").unwrap());
+        let alice = OxSubject::NamedNode(ex("alice"));
+        assert_eq!(store.predicates_for_subject(&alice).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn add_type_of_a_literal_is_an_error() {
+        let mut store = RdfStore::empty();
+        let literal = srdf::Object::literal(srdf::literal::Literal::str("Alice"));
+        assert!(matches!(
+            store.add_type(&literal, ex("Person").into()),
+            Err(RdfDataError::ExpectedSubject { .. })
+        ));
+    }
+}
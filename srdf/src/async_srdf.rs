@@ -1,15 +1,24 @@
-use std::{collections::HashSet, fmt::Display};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
 use async_trait::async_trait;
 use std::hash::Hash;
 
+use crate::VarName2;
+
+/// Outgoing or incoming arcs of several nodes, indexed by node and predicate
+pub type ArcsByNode<N, I, T> = HashMap<N, HashMap<I, HashSet<T>>>;
+
+/// Variables and rows of values of the solutions of a SELECT query
+pub type AsyncSolutions<T> = (Vec<VarName2>, Vec<Vec<Option<T>>>);
+
 #[async_trait]
 pub trait AsyncSRDF {
-    type Subject: Display + Sync + Send;
-    type IRI: Display + Hash + Eq + Sync + Send;
+    type Subject: Display + Hash + Eq + Clone + Sync + Send;
+    type IRI: Display + Hash + Eq + Clone + Sync + Send;
     type BNode: Display + Sync + Send;
     type Literal: Display + Sync + Send;
-    type Term: Display + Sync + Send;
+    type Term: Display + Hash + Eq + Clone + Sync + Send;
     type Err: Display;
 
     async fn get_predicates_subject(
@@ -28,6 +37,35 @@ pub trait AsyncSRDF {
         object: &Self::Term,
         pred: &Self::IRI,
     ) -> Result<HashSet<Self::Subject>, Self::Err>;
+
+    /// Outgoing arcs of several subjects retrieved at once.
+    ///
+    /// Subjects without arcs may be absent from the result
+    async fn get_outgoing_arcs(
+        &self,
+        subjects: &[Self::Subject],
+    ) -> Result<ArcsByNode<Self::Subject, Self::IRI, Self::Term>, Self::Err>;
+
+    /// Incoming arcs of several objects retrieved at once.
+    ///
+    /// Objects without arcs may be absent from the result
+    async fn get_incoming_arcs(
+        &self,
+        objects: &[Self::Term],
+    ) -> Result<ArcsByNode<Self::Term, Self::IRI, Self::Subject>, Self::Err>;
+
+    async fn get_triples_with_predicate(
+        &self,
+        pred: &Self::IRI,
+    ) -> Result<Vec<(Self::Subject, Self::Term)>, Self::Err>;
+}
+
+/// RDF data that can answer SPARQL queries asynchronously
+#[async_trait]
+pub trait AsyncQuerySRDF: AsyncSRDF {
+    async fn get_query_select(&self, query: &str) -> Result<AsyncSolutions<Self::Term>, Self::Err>;
+
+    async fn get_query_ask(&self, query: &str) -> Result<bool, Self::Err>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use serde_derive::{Deserialize, Serialize};
+
+/// This struct can be used to configure how RDF data is fetched asynchronously from remote sources
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct FetchConfig {
+    /// Maximum number of nodes whose neighbourhood is retrieved by a single query (100 by default)
+    pub batch_size: Option<usize>,
+
+    /// Maximum number of queries that are sent at the same time (4 by default)
+    pub parallelism: Option<usize>,
+
+    /// Milliseconds to wait for the response of a query before it is cancelled (30 seconds by default)
+    pub timeout_ms: Option<u64>,
+
+    /// Number of times a failed or cancelled query is sent again (2 by default)
+    pub retries: Option<usize>,
+
+    /// Milliseconds to wait before the first retry, doubled for each new retry (200 by default)
+    pub backoff_ms: Option<u64>,
+}
+
+impl FetchConfig {
+    pub fn new() -> FetchConfig {
+        Self::default()
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = Some(parallelism);
+        self
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    pub fn with_retries(mut self, retries: usize) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+
+    pub fn with_backoff_ms(mut self, backoff_ms: u64) -> Self {
+        self.backoff_ms = Some(backoff_ms);
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(100).max(1)
+    }
+
+    pub fn parallelism(&self) -> usize {
+        self.parallelism.unwrap_or(4).max(1)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.unwrap_or(30_000))
+    }
+
+    pub fn retries(&self) -> usize {
+        self.retries.unwrap_or(2)
+    }
+
+    /// Time to wait before the retry number `attempt`, starting at 1
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(16);
+        Duration::from_millis(self.backoff_ms.unwrap_or(200).saturating_mul(factor))
+    }
+}
@@ -7,11 +7,13 @@
 //! - [`RDFNodeParse`]: RDF graphs that can be parsed
 pub mod async_srdf;
 pub mod bnode;
+pub mod fetch_config;
 pub mod lang;
 pub mod literal;
 pub mod neighs;
 pub mod numeric_literal;
 pub mod object;
+pub mod prefetched_srdf;
 pub mod query_srdf;
 pub mod query_srdf2;
 pub mod rdf;
@@ -29,7 +31,9 @@ pub mod triple;
 pub mod vocab;
 
 pub use crate::async_srdf::*;
+pub use crate::fetch_config::*;
 pub use crate::neighs::*;
+pub use crate::prefetched_srdf::*;
 pub use crate::query_srdf::*;
 pub use crate::query_srdf2::*;
 pub use crate::rdf_data_config::*;
@@ -0,0 +1,585 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::rc::Rc;
+use std::sync::{Mutex, RwLock};
+
+use futures::stream::{self, StreamExt};
+use iri_s::IriS;
+use oxrdf::Term as OxTerm;
+use prefixmap::{PrefixMap, PrefixMapError};
+use thiserror::Error;
+use tracing::debug;
+
+use crate::{
+    AsyncQuerySRDF, AsyncSolutions, FetchConfig, Object, QuerySRDF2, QuerySolution2,
+    QuerySolutions, SRDFBasic, Triple, SRDF,
+};
+
+type Subject<S> = <S as SRDFBasic>::Subject;
+type Iri<S> = <S as SRDFBasic>::IRI;
+type Term<S> = <S as SRDFBasic>::Term;
+type Arcs<I, T> = HashMap<I, HashSet<T>>;
+type Pairs<S> = Vec<(Subject<S>, Term<S>)>;
+
+/// RDF data that can be fetched asynchronously and whose nodes are the same as the ones of its
+/// synchronous interface, such as SPARQL endpoints
+pub trait FetchableSRDF:
+    SRDFBasic
+    + AsyncQuerySRDF<
+        Subject = <Self as SRDFBasic>::Subject,
+        IRI = <Self as SRDFBasic>::IRI,
+        Term = <Self as SRDFBasic>::Term,
+    > + Sync
+{
+}
+
+impl<S> FetchableSRDF for S where
+    S: SRDFBasic
+        + AsyncQuerySRDF<
+            Subject = <S as SRDFBasic>::Subject,
+            IRI = <S as SRDFBasic>::IRI,
+            Term = <S as SRDFBasic>::Term,
+        > + Sync
+{
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum FetchError {
+    #[error("Timeout after {timeout_ms}ms fetching {fetch}")]
+    Timeout { fetch: String, timeout_ms: u128 },
+
+    #[error("Error fetching {fetch}: {error}")]
+    Request { fetch: String, error: String },
+
+    #[error("CONSTRUCT queries are not supported on prefetched RDF data")]
+    ConstructNotSupported,
+
+    #[error("DESCRIBE queries are not supported on prefetched RDF data")]
+    DescribeNotSupported,
+
+    #[error(
+        "The arcs of blank node {node} can't be fetched, as blank nodes can't be used in queries"
+    )]
+    BlankNode { node: String },
+}
+
+/// Synchronous view of RDF data which is fetched asynchronously in batches.
+///
+/// The methods of [`SRDF`] and [`QuerySRDF2`] only look at the data fetched so far. When some data
+/// has not been fetched yet they answer as if it was empty and record the lookup as a miss.
+/// A computation is repeated after calling [`PrefetchedSRDF::fetch_misses`] until it has no misses,
+/// so every round retrieves at once all the data that the previous round needed.
+///
+/// Blank nodes can't be fetched, because their labels are only valid in the results that contain
+/// them, so looking up the arcs of a blank node returns [`FetchError::BlankNode`].
+pub struct PrefetchedSRDF<S: FetchableSRDF> {
+    rdf: S,
+    config: FetchConfig,
+    cache: RwLock<Cache<S>>,
+    misses: Mutex<Misses<S>>,
+}
+
+struct Cache<S: SRDFBasic> {
+    outgoing: HashMap<Subject<S>, Arcs<Iri<S>, Term<S>>>,
+    incoming: HashMap<Term<S>, Arcs<Iri<S>, Subject<S>>>,
+    by_predicate: HashMap<Iri<S>, Pairs<S>>,
+    selects: HashMap<String, AsyncSolutions<Term<S>>>,
+    asks: HashMap<String, bool>,
+}
+
+impl<S: SRDFBasic> Default for Cache<S> {
+    fn default() -> Self {
+        Cache {
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+            by_predicate: HashMap::new(),
+            selects: HashMap::new(),
+            asks: HashMap::new(),
+        }
+    }
+}
+
+struct Misses<S: SRDFBasic> {
+    subjects: HashSet<Subject<S>>,
+    objects: HashSet<Term<S>>,
+    predicates: HashSet<Iri<S>>,
+    selects: HashSet<String>,
+    asks: HashSet<String>,
+    // Number of lookups that have missed, including repeated ones
+    count: usize,
+}
+
+impl<S: SRDFBasic> Default for Misses<S> {
+    fn default() -> Self {
+        Misses {
+            subjects: HashSet::new(),
+            objects: HashSet::new(),
+            predicates: HashSet::new(),
+            selects: HashSet::new(),
+            asks: HashSet::new(),
+            count: 0,
+        }
+    }
+}
+
+impl<S: SRDFBasic> Misses<S> {
+    fn is_empty(&self) -> bool {
+        self.subjects.is_empty()
+            && self.objects.is_empty()
+            && self.predicates.is_empty()
+            && self.selects.is_empty()
+            && self.asks.is_empty()
+    }
+}
+
+/// A query sent to the underlying data
+enum Fetch<S: SRDFBasic> {
+    Outgoing(Vec<Subject<S>>),
+    Incoming(Vec<Term<S>>),
+    Predicate(Iri<S>),
+    Select(String),
+    Ask(String),
+}
+
+impl<S: SRDFBasic> Display for Fetch<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fetch::Outgoing(subjects) => write!(f, "outgoing arcs of {} nodes", subjects.len()),
+            Fetch::Incoming(objects) => write!(f, "incoming arcs of {} nodes", objects.len()),
+            Fetch::Predicate(pred) => write!(f, "triples with predicate {pred}"),
+            Fetch::Select(query) => write!(f, "SELECT query {query}"),
+            Fetch::Ask(query) => write!(f, "ASK query {query}"),
+        }
+    }
+}
+
+enum Fetched<S: SRDFBasic> {
+    Outgoing(HashMap<Subject<S>, Arcs<Iri<S>, Term<S>>>),
+    Incoming(HashMap<Term<S>, Arcs<Iri<S>, Subject<S>>>),
+    Predicate(Iri<S>, Pairs<S>),
+    Select(String, AsyncSolutions<Term<S>>),
+    Ask(String, bool),
+}
+
+impl<S: FetchableSRDF> PrefetchedSRDF<S> {
+    pub fn new(rdf: S, config: &FetchConfig) -> PrefetchedSRDF<S> {
+        PrefetchedSRDF {
+            rdf,
+            config: config.clone(),
+            cache: RwLock::new(Cache::default()),
+            misses: Mutex::new(Misses::default()),
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.rdf
+    }
+
+    pub fn config(&self) -> &FetchConfig {
+        &self.config
+    }
+
+    /// Number of lookups that have missed since the data was created.
+    ///
+    /// It can be compared before and after a computation to know if it has been done with incomplete data
+    pub fn missed_lookups(&self) -> usize {
+        self.misses.lock().unwrap().count
+    }
+
+    /// Checks if there are misses that have not been fetched yet
+    pub fn has_misses(&self) -> bool {
+        !self.misses.lock().unwrap().is_empty()
+    }
+
+    /// Fetches the data of the lookups that have missed.
+    ///
+    /// The neighbourhoods are retrieved in batches of nodes and the queries are sent concurrently,
+    /// each one with a timeout and retried when it fails. After this, the lookups that missed
+    /// find their data, which is empty if the underlying data has nothing for them
+    pub async fn fetch_misses(&self) -> Result<(), FetchError> {
+        let misses = std::mem::take(&mut *self.misses.lock().unwrap());
+        let Misses {
+            subjects,
+            objects,
+            predicates,
+            selects,
+            asks,
+            count,
+        } = misses;
+        self.misses.lock().unwrap().count = count;
+        let subjects: Vec<_> = subjects.into_iter().collect();
+        let objects: Vec<_> = objects.into_iter().collect();
+        let predicates: Vec<_> = predicates.into_iter().collect();
+        let batch_size = self.config.batch_size();
+        let mut fetches: Vec<Fetch<S>> = Vec::new();
+        fetches.extend(
+            subjects
+                .chunks(batch_size)
+                .map(|c| Fetch::Outgoing(c.to_vec())),
+        );
+        fetches.extend(
+            objects
+                .chunks(batch_size)
+                .map(|c| Fetch::Incoming(c.to_vec())),
+        );
+        fetches.extend(predicates.iter().cloned().map(Fetch::Predicate));
+        fetches.extend(selects.into_iter().map(Fetch::Select));
+        fetches.extend(asks.into_iter().map(Fetch::Ask));
+        debug!("Fetching {} queries", fetches.len());
+        let results: Vec<Result<Fetched<S>, FetchError>> = stream::iter(fetches.iter())
+            .map(|fetch| self.fetch_with_retries(fetch))
+            .buffer_unordered(self.config.parallelism())
+            .collect()
+            .await;
+        let mut cache = self.cache.write().unwrap();
+        for result in results {
+            match result? {
+                Fetched::Outgoing(arcs) => cache.outgoing.extend(arcs),
+                Fetched::Incoming(arcs) => cache.incoming.extend(arcs),
+                Fetched::Predicate(pred, triples) => {
+                    cache.by_predicate.insert(pred, triples);
+                }
+                Fetched::Select(query, solutions) => {
+                    cache.selects.insert(query, solutions);
+                }
+                Fetched::Ask(query, value) => {
+                    cache.asks.insert(query, value);
+                }
+            }
+        }
+        // Nodes without arcs are not returned by the queries
+        for subject in subjects {
+            cache.outgoing.entry(subject).or_default();
+        }
+        for object in objects {
+            cache.incoming.entry(object).or_default();
+        }
+        Ok(())
+    }
+
+    async fn fetch_with_retries(&self, fetch: &Fetch<S>) -> Result<Fetched<S>, FetchError> {
+        let mut attempt = 0;
+        loop {
+            let error = match tokio::time::timeout(self.config.timeout(), self.fetch(fetch)).await {
+                Ok(Ok(fetched)) => return Ok(fetched),
+                Ok(Err(error)) => error,
+                Err(_) => FetchError::Timeout {
+                    fetch: fetch.to_string(),
+                    timeout_ms: self.config.timeout().as_millis(),
+                },
+            };
+            if attempt >= self.config.retries() {
+                return Err(error);
+            }
+            attempt += 1;
+            debug!("Retrying {fetch} ({attempt}) after error: {error}");
+            tokio::time::sleep(self.config.backoff(attempt)).await;
+        }
+    }
+
+    async fn fetch(&self, fetch: &Fetch<S>) -> Result<Fetched<S>, FetchError> {
+        let request_error = |error: <S as crate::AsyncSRDF>::Err| FetchError::Request {
+            fetch: fetch.to_string(),
+            error: error.to_string(),
+        };
+        match fetch {
+            Fetch::Outgoing(subjects) => self
+                .rdf
+                .get_outgoing_arcs(subjects)
+                .await
+                .map(Fetched::Outgoing)
+                .map_err(request_error),
+            Fetch::Incoming(objects) => self
+                .rdf
+                .get_incoming_arcs(objects)
+                .await
+                .map(Fetched::Incoming)
+                .map_err(request_error),
+            Fetch::Predicate(pred) => self
+                .rdf
+                .get_triples_with_predicate(pred)
+                .await
+                .map(|triples| Fetched::Predicate(pred.clone(), triples))
+                .map_err(request_error),
+            Fetch::Select(query) => self
+                .rdf
+                .get_query_select(query)
+                .await
+                .map(|solutions| Fetched::Select(query.clone(), solutions))
+                .map_err(request_error),
+            Fetch::Ask(query) => self
+                .rdf
+                .get_query_ask(query)
+                .await
+                .map(|value| Fetched::Ask(query.clone(), value))
+                .map_err(request_error),
+        }
+    }
+
+    fn record_miss(&self, record: impl FnOnce(&mut Misses<S>)) {
+        let mut misses = self.misses.lock().unwrap();
+        misses.count += 1;
+        record(&mut misses)
+    }
+
+    fn outgoing(&self, subject: &Subject<S>) -> Result<Arcs<Iri<S>, Term<S>>, FetchError> {
+        if let Some(arcs) = self.cache.read().unwrap().outgoing.get(subject) {
+            return Ok(arcs.clone());
+        }
+        if S::subject_is_bnode(subject) {
+            return Err(FetchError::BlankNode {
+                node: subject.to_string(),
+            });
+        }
+        self.record_miss(|misses| {
+            misses.subjects.insert(subject.clone());
+        });
+        Ok(HashMap::new())
+    }
+
+    fn incoming(&self, object: &Term<S>) -> Result<Arcs<Iri<S>, Subject<S>>, FetchError> {
+        if let Some(arcs) = self.cache.read().unwrap().incoming.get(object) {
+            return Ok(arcs.clone());
+        }
+        if S::term_is_bnode(object) {
+            return Err(FetchError::BlankNode {
+                node: object.to_string(),
+            });
+        }
+        self.record_miss(|misses| {
+            misses.objects.insert(object.clone());
+        });
+        Ok(HashMap::new())
+    }
+}
+
+impl<S: FetchableSRDF> SRDFBasic for PrefetchedSRDF<S> {
+    type Subject = Subject<S>;
+    type IRI = Iri<S>;
+    type BNode = <S as SRDFBasic>::BNode;
+    type Literal = <S as SRDFBasic>::Literal;
+    type Term = Term<S>;
+    type Err = FetchError;
+
+    fn subject_as_iri(subject: &Self::Subject) -> Option<Self::IRI> {
+        S::subject_as_iri(subject)
+    }
+
+    fn subject_as_bnode(subject: &Self::Subject) -> Option<Self::BNode> {
+        S::subject_as_bnode(subject)
+    }
+
+    fn subject_is_iri(subject: &Self::Subject) -> bool {
+        S::subject_is_iri(subject)
+    }
+
+    fn subject_is_bnode(subject: &Self::Subject) -> bool {
+        S::subject_is_bnode(subject)
+    }
+
+    fn term_as_iri(object: &Self::Term) -> Option<Self::IRI> {
+        S::term_as_iri(object)
+    }
+
+    fn term_as_bnode(object: &Self::Term) -> Option<Self::BNode> {
+        S::term_as_bnode(object)
+    }
+
+    fn term_as_literal(object: &Self::Term) -> Option<Self::Literal> {
+        S::term_as_literal(object)
+    }
+
+    fn term_as_object(term: &Self::Term) -> Object {
+        S::term_as_object(term)
+    }
+
+    fn object_as_term(obj: &Object) -> Self::Term {
+        S::object_as_term(obj)
+    }
+
+    fn term_is_iri(object: &Self::Term) -> bool {
+        S::term_is_iri(object)
+    }
+
+    fn term_is_bnode(object: &Self::Term) -> bool {
+        S::term_is_bnode(object)
+    }
+
+    fn term_is_literal(object: &Self::Term) -> bool {
+        S::term_is_literal(object)
+    }
+
+    fn term_as_subject(object: &Self::Term) -> Option<Self::Subject> {
+        S::term_as_subject(object)
+    }
+
+    fn subject_as_term(subject: &Self::Subject) -> Self::Term {
+        S::subject_as_term(subject)
+    }
+
+    fn lexical_form(literal: &Self::Literal) -> &str {
+        S::lexical_form(literal)
+    }
+
+    fn lang(literal: &Self::Literal) -> Option<String> {
+        S::lang(literal)
+    }
+
+    fn datatype(literal: &Self::Literal) -> Self::IRI {
+        S::datatype(literal)
+    }
+
+    fn iri_s2iri(iri_s: &IriS) -> Self::IRI {
+        S::iri_s2iri(iri_s)
+    }
+
+    fn term_s2term(term: &OxTerm) -> Self::Term {
+        S::term_s2term(term)
+    }
+
+    fn bnode_id2bnode(id: &str) -> Self::BNode {
+        S::bnode_id2bnode(id)
+    }
+
+    fn iri_as_term(iri: Self::IRI) -> Self::Term {
+        S::iri_as_term(iri)
+    }
+
+    fn iri_as_subject(iri: Self::IRI) -> Self::Subject {
+        S::iri_as_subject(iri)
+    }
+
+    fn bnode_as_term(bnode: Self::BNode) -> Self::Term {
+        S::bnode_as_term(bnode)
+    }
+
+    fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
+        S::bnode_as_subject(bnode)
+    }
+
+    fn iri2iri_s(iri: &Self::IRI) -> IriS {
+        S::iri2iri_s(iri)
+    }
+
+    fn qualify_iri(&self, iri: &Self::IRI) -> String {
+        self.rdf.qualify_iri(iri)
+    }
+
+    fn qualify_subject(&self, subj: &Self::Subject) -> String {
+        self.rdf.qualify_subject(subj)
+    }
+
+    fn qualify_term(&self, term: &Self::Term) -> String {
+        self.rdf.qualify_term(term)
+    }
+
+    fn prefixmap(&self) -> Option<PrefixMap> {
+        self.rdf.prefixmap()
+    }
+
+    fn resolve_prefix_local(&self, prefix: &str, local: &str) -> Result<IriS, PrefixMapError> {
+        self.rdf.resolve_prefix_local(prefix, local)
+    }
+}
+
+impl<S: FetchableSRDF> SRDF for PrefetchedSRDF<S> {
+    fn predicates_for_subject(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<HashSet<Self::IRI>, Self::Err> {
+        Ok(self.outgoing(subject)?.into_keys().collect())
+    }
+
+    fn objects_for_subject_predicate(
+        &self,
+        subject: &Self::Subject,
+        pred: &Self::IRI,
+    ) -> Result<HashSet<Self::Term>, Self::Err> {
+        Ok(self.outgoing(subject)?.remove(pred).unwrap_or_default())
+    }
+
+    fn subjects_with_predicate_object(
+        &self,
+        pred: &Self::IRI,
+        object: &Self::Term,
+    ) -> Result<HashSet<Self::Subject>, Self::Err> {
+        Ok(self.incoming(object)?.remove(pred).unwrap_or_default())
+    }
+
+    fn triples_with_predicate(&self, pred: &Self::IRI) -> Result<Vec<Triple<Self>>, Self::Err> {
+        if let Some(pairs) = self.cache.read().unwrap().by_predicate.get(pred) {
+            return Ok(pairs
+                .iter()
+                .map(|(subj, obj)| Triple::new(subj.clone(), pred.clone(), obj.clone()))
+                .collect());
+        }
+        self.record_miss(|misses| {
+            misses.predicates.insert(pred.clone());
+        });
+        Ok(Vec::new())
+    }
+
+    fn outgoing_arcs(
+        &self,
+        subject: &Self::Subject,
+    ) -> Result<Arcs<Self::IRI, Self::Term>, Self::Err> {
+        self.outgoing(subject)
+    }
+
+    fn incoming_arcs(
+        &self,
+        object: &Self::Term,
+    ) -> Result<Arcs<Self::IRI, Self::Subject>, Self::Err> {
+        self.incoming(object)
+    }
+
+    fn outgoing_arcs_from_list(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<(Arcs<Self::IRI, Self::Term>, Vec<Self::IRI>), Self::Err> {
+        let mut arcs = self.outgoing(subject)?;
+        let mut result = HashMap::new();
+        for pred in preds {
+            if let Some(values) = arcs.remove(pred) {
+                result.insert(pred.clone(), values);
+            }
+        }
+        Ok((result, arcs.into_keys().collect()))
+    }
+}
+
+impl<S: FetchableSRDF> QuerySRDF2 for PrefetchedSRDF<S> {
+    fn query_select(&self, query: &str) -> Result<QuerySolutions<Self>, Self::Err> {
+        if let Some((variables, rows)) = self.cache.read().unwrap().selects.get(query) {
+            let shared = Rc::new(variables.clone());
+            let solutions = rows
+                .iter()
+                .map(|row| QuerySolution2::new(shared.clone(), row.clone()))
+                .collect();
+            return Ok(QuerySolutions::new(variables.clone(), solutions));
+        }
+        self.record_miss(|misses| {
+            misses.selects.insert(query.to_string());
+        });
+        Ok(QuerySolutions::empty())
+    }
+
+    fn query_ask(&self, query: &str) -> Result<bool, Self::Err> {
+        if let Some(value) = self.cache.read().unwrap().asks.get(query) {
+            return Ok(*value);
+        }
+        self.record_miss(|misses| {
+            misses.asks.insert(query.to_string());
+        });
+        Ok(false)
+    }
+
+    fn query_construct(&self, _query: &str) -> Result<Vec<Triple<Self>>, Self::Err> {
+        Err(FetchError::ConstructNotSupported)
+    }
+
+    fn query_describe(&self, _query: &str) -> Result<Vec<Triple<Self>>, Self::Err> {
+        Err(FetchError::DescribeNotSupported)
+    }
+}
@@ -1,6 +1,7 @@
 use std::{fmt::Display, rc::Rc};
 
 use crate::SRDFBasic;
+use crate::Triple;
 
 /// Alternative QuerySRDF trait
 pub trait QuerySRDF2: SRDFBasic {
@@ -9,6 +10,16 @@ pub trait QuerySRDF2: SRDFBasic {
         Self: Sized;
 
     fn query_ask(&self, query: &str) -> Result<bool, Self::Err>;
+
+    /// Returns the triples generated by a CONSTRUCT query
+    fn query_construct(&self, query: &str) -> Result<Vec<Triple<Self>>, Self::Err>
+    where
+        Self: Sized;
+
+    /// Returns the triples that describe the resources of a DESCRIBE query
+    fn query_describe(&self, query: &str) -> Result<Vec<Triple<Self>>, Self::Err>
+    where
+        Self: Sized;
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
@@ -28,6 +39,11 @@ impl VarName2 {
             str: str.to_string(),
         }
     }
+
+    /// Name of the variable without the `?`
+    pub fn as_str(&self) -> &str {
+        &self.str
+    }
 }
 
 impl From<String> for VarName2 {
@@ -110,25 +126,49 @@ impl<S: SRDFBasic, V: Into<Rc<Vec<VarName2>>>, T: Into<Vec<Option<S::Term>>>> Fr
 }
 
 pub struct QuerySolutions<S: SRDFBasic> {
+    variables: Vec<VarName2>,
     solutions: Vec<QuerySolution2<S>>,
 }
 
 impl<S: SRDFBasic> QuerySolutions<S> {
     pub fn empty() -> QuerySolutions<S> {
         QuerySolutions {
+            variables: Vec::new(),
             solutions: Vec::new(),
         }
     }
 
-    pub fn new(solutions: Vec<QuerySolution2<S>>) -> QuerySolutions<S> {
-        QuerySolutions { solutions }
+    pub fn new(variables: Vec<VarName2>, solutions: Vec<QuerySolution2<S>>) -> QuerySolutions<S> {
+        QuerySolutions {
+            variables,
+            solutions,
+        }
     }
 
-    pub fn extend(&mut self, solutions: Vec<QuerySolution2<S>>) {
-        self.solutions.extend(solutions)
+    /// Adds the solutions and the variables of `other` which are not already declared
+    pub fn extend(&mut self, other: QuerySolutions<S>) {
+        for var in other.variables {
+            if !self.variables.contains(&var) {
+                self.variables.push(var)
+            }
+        }
+        self.solutions.extend(other.solutions)
+    }
+
+    /// Variables of the query, even if there are no solutions
+    pub fn variables(&self) -> impl Iterator<Item = &VarName2> {
+        self.variables.iter()
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &QuerySolution2<S>> {
         self.solutions.iter()
     }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
 }
@@ -5,20 +5,38 @@ use thiserror::Error;
 use iri_s::IriS;
 use serde_derive::{Deserialize, Serialize};
 
+use crate::NamedGraphMode;
+
 /// This struct can be used to define configuration of RDF data readers
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct RdfDataConfig {
     /// Default base to resolve relative IRIs, if it is `None` relative IRIs will be marked as errors`
     pub base: Option<IriS>,
+
+    /// How the named graphs of TriG and N-Quads files are loaded, by default they are merged into the default graph
+    pub named_graphs: Option<NamedGraphMode>,
+
+    /// SPARQL endpoints whose data is merged with the data being processed
+    pub endpoints: Option<Vec<IriS>>,
 }
 
 impl RdfDataConfig {
     pub fn new() -> RdfDataConfig {
         Self {
             base: Some(IriS::new_unchecked("base://")),
+            named_graphs: None,
+            endpoints: None,
         }
     }
 
+    pub fn named_graph_mode(&self) -> NamedGraphMode {
+        self.named_graphs.clone().unwrap_or_default()
+    }
+
+    pub fn endpoints(&self) -> Vec<IriS> {
+        self.endpoints.clone().unwrap_or_default()
+    }
+
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<RdfDataConfig, RdfDataConfigError> {
         let path_name = path.as_ref().display().to_string();
         let f = std::fs::File::open(path).map_err(|e| RdfDataConfigError::ReadingConfigError {
@@ -20,22 +20,65 @@ impl SHACLPath {
     pub fn iri(pred: IriS) -> Self {
         SHACLPath::Predicate { pred }
     }
+
+    /// Returns the representation of this path as a [SPARQL property path](https://www.w3.org/TR/sparql11-query/#propertypaths)
+    pub fn sparql_path(&self) -> String {
+        match self {
+            SHACLPath::Predicate { pred } => format!("<{}>", pred.as_str()),
+            SHACLPath::Alternative { paths } => format!("({})", join_sparql_paths(paths, " | ")),
+            SHACLPath::Sequence { paths } => format!("({})", join_sparql_paths(paths, " / ")),
+            SHACLPath::Inverse { path } => format!("^({})", path.sparql_path()),
+            SHACLPath::ZeroOrMore { path } => format!("({})*", path.sparql_path()),
+            SHACLPath::OneOrMore { path } => format!("({})+", path.sparql_path()),
+            SHACLPath::ZeroOrOne { path } => format!("({})?", path.sparql_path()),
+        }
+    }
+
+    /// Checks if the path traverses some triple in the inverse direction
+    pub fn has_inverse(&self) -> bool {
+        match self {
+            SHACLPath::Predicate { .. } => false,
+            SHACLPath::Alternative { paths } | SHACLPath::Sequence { paths } => {
+                paths.iter().any(|path| path.has_inverse())
+            }
+            SHACLPath::Inverse { .. } => true,
+            SHACLPath::ZeroOrMore { path }
+            | SHACLPath::OneOrMore { path }
+            | SHACLPath::ZeroOrOne { path } => path.has_inverse(),
+        }
+    }
+}
+
+fn join_sparql_paths(paths: &[SHACLPath], separator: &str) -> String {
+    paths
+        .iter()
+        .map(|path| path.sparql_path())
+        .collect::<Vec<_>>()
+        .join(separator)
 }
 
 impl Display for SHACLPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SHACLPath::Predicate { pred } => write!(f, "{pred}"),
-            SHACLPath::Alternative { .. } => todo!(),
-            SHACLPath::Sequence { .. } => todo!(),
-            SHACLPath::Inverse { .. } => todo!(),
-            SHACLPath::ZeroOrMore { .. } => todo!(),
-            SHACLPath::OneOrMore { .. } => todo!(),
-            SHACLPath::ZeroOrOne { .. } => todo!(),
+            SHACLPath::Alternative { paths } => write!(f, "({})", join_paths(paths, " | ")),
+            SHACLPath::Sequence { paths } => write!(f, "({})", join_paths(paths, " / ")),
+            SHACLPath::Inverse { path } => write!(f, "^{path}"),
+            SHACLPath::ZeroOrMore { path } => write!(f, "{path}*"),
+            SHACLPath::OneOrMore { path } => write!(f, "{path}+"),
+            SHACLPath::ZeroOrOne { path } => write!(f, "{path}?"),
         }
     }
 }
 
+fn join_paths(paths: &[SHACLPath], separator: &str) -> String {
+    paths
+        .iter()
+        .map(|path| path.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
 impl From<SHACLPath> for &str {
     fn from(value: SHACLPath) -> Self {
         match value {
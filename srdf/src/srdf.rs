@@ -78,4 +78,15 @@ pub trait SRDF: SRDFBasic {
         subject: &Self::Subject,
         preds: &[Self::IRI],
     ) -> Result<OutgoingArcs<Self::IRI, Self::Term>, Self::Err>;
+
+    /// get outgoing arcs from a `node` taking into account only a controlled list of `preds`.
+    /// Unlike `outgoing_arcs_from_list`, the predicates that are not in the list are not retrieved
+    fn outgoing_arcs_of_preds(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<HasMapOfIriAndItem<Self::IRI, Self::Term>, Self::Err> {
+        self.outgoing_arcs_from_list(subject, preds)
+            .map(|(arcs, _remainder)| arcs)
+    }
 }
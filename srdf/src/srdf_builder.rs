@@ -1,9 +1,10 @@
 use std::io::Write;
 
 use iri_s::IriS;
+use prefixmap::IriRef;
 use prefixmap::PrefixMap;
 
-use crate::{RDFFormat, RDFNode, SRDF};
+use crate::{RDFFormat, RDFNode, RDF_FIRST, RDF_NIL, RDF_REST, SRDF};
 
 /// Types that implement this trait can build RDF data
 pub trait SRDFBuilder: SRDF {
@@ -19,6 +20,10 @@ pub trait SRDFBuilder: SRDF {
     /// Adds a prefix map declaration to the current RDF graph
     fn add_prefix_map(&mut self, prefix_map: PrefixMap) -> Result<(), Self::Err>;
 
+    /// Obtains the IRI of an IRI reference, resolving prefixed names with the
+    /// prefixes of the current RDF graph
+    fn resolve_iri_ref(&self, iri: &IriRef) -> Result<IriS, Self::Err>;
+
     /// Adds an RDF triple to the current RDF graph
     fn add_triple(
         &mut self,
@@ -27,6 +32,15 @@ pub trait SRDFBuilder: SRDF {
         obj: &Self::Term,
     ) -> Result<(), Self::Err>;
 
+    /// Adds an RDF triple whose subject is given as a node, which fails if the
+    /// node cannot be the subject of a triple, like literals
+    fn add_node_triple(
+        &mut self,
+        node: &RDFNode,
+        pred: &Self::IRI,
+        obj: &Self::Term,
+    ) -> Result<(), Self::Err>;
+
     /// Removes an RDf triple to the current RDF graph
     fn remove_triple(
         &mut self,
@@ -38,6 +52,22 @@ pub trait SRDFBuilder: SRDF {
     /// Adds an `rdf:type` declaration to the current RDF graph
     fn add_type(&mut self, node: &RDFNode, type_: Self::Term) -> Result<(), Self::Err>;
 
+    /// Creates a blank node that is different from the ones in the current RDF graph
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err>;
+
+    /// Adds an RDF list with the given values to the current RDF graph and
+    /// returns its head, which is `rdf:nil` for an empty list
+    fn add_list(&mut self, values: &[Self::Term]) -> Result<Self::Term, Self::Err> {
+        let mut rest = Self::iri_s2term(&RDF_NIL);
+        for value in values.iter().rev() {
+            let node = Self::bnode_as_subject(self.add_bnode()?);
+            self.add_triple(&node, &Self::iri_s2iri(&RDF_FIRST), value)?;
+            self.add_triple(&node, &Self::iri_s2iri(&RDF_REST), &rest)?;
+            rest = Self::subject_as_term(&node);
+        }
+        Ok(rest)
+    }
+
     /// Serialize the current graph to a Write implementation
     fn serialize<W: Write>(&self, format: RDFFormat, writer: &mut W) -> Result<(), Self::Err>;
 }
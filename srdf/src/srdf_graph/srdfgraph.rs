@@ -3,7 +3,7 @@ use colored::*;
 use iri_s::IriS;
 use tracing::debug;
 // use log::debug;
-use crate::async_srdf::AsyncSRDF;
+use crate::async_srdf::{ArcsByNode, AsyncSRDF};
 use crate::literal::Literal;
 use crate::numeric_literal::NumericLiteral;
 use crate::{FocusRDF, RDFFormat, SRDFBasic, SRDFBuilder, Triple as STriple, RDF_TYPE_STR, SRDF};
@@ -22,12 +22,15 @@ use crate::lang::Lang;
 use crate::srdfgraph_error::SRDFGraphError;
 use crate::Object;
 use oxrdf::{
-    BlankNode as OxBlankNode, Graph, GraphName, Literal as OxLiteral, NamedNode as OxNamedNode,
-    Quad, Subject as OxSubject, Term as OxTerm, Triple as OxTriple, TripleRef,
+    BlankNode as OxBlankNode, Dataset, Graph, GraphName, GraphNameRef, Literal as OxLiteral,
+    NamedNode as OxNamedNode, Quad, QuadRef, Subject as OxSubject, Term as OxTerm,
+    Triple as OxTriple, TripleRef,
 };
 use oxsdatatypes::Decimal as OxDecimal;
-use oxttl::{NQuadsParser, NTriplesParser, TurtleParser};
+use oxttl::n3::{N3Quad, N3Term};
+use oxttl::{N3Parser, NQuadsParser, NTriplesParser, TriGParser, TurtleParser};
 use prefixmap::{prefixmap::*, IriRef, PrefixMapError};
+use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone)]
 pub struct SRDFGraph {
@@ -35,6 +38,8 @@ pub struct SRDFGraph {
     graph: Graph,
     pm: PrefixMap,
     base: Option<IriS>,
+    named_graphs: Dataset,
+    named_graph_mode: NamedGraphMode,
 }
 
 impl SRDFGraph {
@@ -42,6 +47,17 @@ impl SRDFGraph {
         Self::default()
     }
 
+    /// Sets how the named graphs are loaded when reading TriG or N-Quads
+    pub fn with_named_graph_mode(mut self, named_graph_mode: NamedGraphMode) -> Self {
+        self.named_graph_mode = named_graph_mode;
+        self
+    }
+
+    /// Quads of the named graphs that have been kept apart from the default graph
+    pub fn named_graphs(&self) -> &Dataset {
+        &self.named_graphs
+    }
+
     pub fn len(&self) -> usize {
         self.graph.len()
     }
@@ -51,12 +67,56 @@ impl SRDFGraph {
         self.graph
             .iter()
             .map(move |t| triple_to_quad(t, graph_name.clone()))
+            .chain(self.named_graphs.iter().map(|q| q.into_owned()))
     }
 
     pub fn is_empty(&self) -> bool {
         self.graph.is_empty()
     }
 
+    /// Subjects and objects of the triples that are in only one of `self` and `other`,
+    /// i.e. the nodes whose neighbourhood is different in both graphs
+    pub fn changed_nodes(&self, other: &SRDFGraph) -> HashSet<OxTerm> {
+        let mut changed = HashSet::new();
+        let only_in = |g1: &Graph, g2: &Graph, changed: &mut HashSet<OxTerm>| {
+            for triple in g1.iter().filter(|t| !g2.contains(*t)) {
+                changed.insert(OxTerm::from(triple.subject.into_owned()));
+                changed.insert(triple.object.into_owned());
+            }
+        };
+        only_in(&self.graph, &other.graph, &mut changed);
+        only_in(&other.graph, &self.graph, &mut changed);
+        changed
+    }
+
+    /// Extends `nodes` with the nodes that reach some of them following the triples of the graph.
+    ///
+    /// If `inverse` is true, the nodes reachable from them are also added
+    pub fn dependent_nodes(&self, nodes: &HashSet<OxTerm>, inverse: bool) -> HashSet<OxTerm> {
+        let mut neighs: HashMap<OxTerm, Vec<OxTerm>> = HashMap::new();
+        for triple in self.graph.iter() {
+            let subject = OxTerm::from(triple.subject.into_owned());
+            let object = triple.object.into_owned();
+            if inverse {
+                neighs
+                    .entry(subject.clone())
+                    .or_default()
+                    .push(object.clone());
+            }
+            neighs.entry(object).or_default().push(subject);
+        }
+        let mut result = nodes.clone();
+        let mut pending: Vec<OxTerm> = nodes.iter().cloned().collect();
+        while let Some(node) = pending.pop() {
+            for neigh in neighs.get(&node).into_iter().flatten() {
+                if result.insert(neigh.clone()) {
+                    pending.push(neigh.clone())
+                }
+            }
+        }
+        result
+    }
+
     pub fn merge_from_reader<R: BufRead>(
         &mut self,
         read: R,
@@ -76,13 +136,7 @@ impl SRDFGraph {
                     self.graph.insert(triple_result?.as_ref());
                 }
                 let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
-                self.base = match (&self.base, base) {
-                    (None, None) => None,
-                    (Some(b), None) => Some(b.clone()),
-                    (_, Some(b)) => Some(IriS::new_unchecked(b.as_str())),
-                };
-                let pm = PrefixMap::from_hashmap(&prefixes)?;
-                self.merge_prefixes(pm)?;
+                self.merge_base_prefixes(base, &prefixes)?;
             }
             RDFFormat::NTriples => {
                 let parser = NTriplesParser::new();
@@ -119,8 +173,79 @@ impl SRDFGraph {
                     }
                 }
             }
-            RDFFormat::TriG => todo!(),
-            RDFFormat::N3 => todo!(),
+            RDFFormat::TriG => {
+                let parser = match base {
+                    None => TriGParser::new(),
+                    Some(ref iri) => TriGParser::new().with_base_iri(iri.as_str())?,
+                };
+                let mut reader = parser.for_reader(read);
+                for quad_result in reader.by_ref() {
+                    match quad_result {
+                        Err(e) => {
+                            if reader_mode.is_strict() {
+                                return Err(SRDFGraphError::TurtleError {
+                                    data: "Reading TriG".to_string(),
+                                    turtle_error: e,
+                                });
+                            } else {
+                                debug!("Error captured: {e:?}")
+                            }
+                        }
+                        Ok(q) => self.add_quad(q.as_ref()),
+                    }
+                }
+                let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
+                self.merge_base_prefixes(base, &prefixes)?;
+            }
+            RDFFormat::N3 => {
+                let parser = match base {
+                    None => N3Parser::new(),
+                    Some(ref iri) => N3Parser::new().with_base_iri(iri.as_str())?,
+                };
+                let mut reader = parser.for_reader(read);
+                let mut quads = Vec::new();
+                for quad_result in reader.by_ref() {
+                    match quad_result {
+                        Err(e) => {
+                            if reader_mode.is_strict() {
+                                return Err(SRDFGraphError::TurtleError {
+                                    data: "Reading N3".to_string(),
+                                    turtle_error: e,
+                                });
+                            } else {
+                                debug!("Error captured: {e:?}")
+                            }
+                        }
+                        Ok(q) => quads.push(q),
+                    }
+                }
+                // The statements of a formula are in a graph named by the blank node of the formula
+                let formulas: HashSet<OxBlankNode> = quads
+                    .iter()
+                    .filter_map(|q| match &q.graph_name {
+                        GraphName::BlankNode(b) => Some(b.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                for quad in quads {
+                    match n3_fact(&quad, &formulas) {
+                        Some(triple) => {
+                            self.graph.insert(&triple);
+                        }
+                        None => {
+                            if reader_mode.is_strict() {
+                                return Err(SRDFGraphError::N3NotFact {
+                                    statement: quad.to_string(),
+                                });
+                            } else {
+                                debug!("Ignoring N3 statement which is not a fact: {quad}")
+                            }
+                        }
+                    }
+                }
+                let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
+                self.merge_base_prefixes(base, &prefixes)?;
+            }
             RDFFormat::NQuads => {
                 let parser = NQuadsParser::new();
                 let mut reader = parser.for_reader(read);
@@ -129,9 +254,7 @@ impl SRDFGraph {
                         Err(e) => {
                             debug!("Error captured: {e:?}")
                         }
-                        Ok(t) => {
-                            self.graph.insert(t.as_ref());
-                        }
+                        Ok(q) => self.add_quad(q.as_ref()),
                     }
                 }
             }
@@ -139,11 +262,52 @@ impl SRDFGraph {
         Ok(())
     }
 
+    /// Adds the triples, the named graphs and the prefixes of another graph
+    pub fn merge_graph(&mut self, other: &SRDFGraph) -> Result<(), SRDFGraphError> {
+        for triple in other.graph.iter() {
+            self.graph.insert(triple);
+        }
+        for quad in other.named_graphs.iter() {
+            self.named_graphs.insert(quad);
+        }
+        if other.base.is_some() {
+            self.base.clone_from(&other.base)
+        }
+        self.merge_prefixes(other.pm.clone())
+    }
+
     pub fn merge_prefixes(&mut self, prefixmap: PrefixMap) -> Result<(), SRDFGraphError> {
         self.pm.merge(prefixmap)?;
         Ok(())
     }
 
+    fn merge_base_prefixes(
+        &mut self,
+        base: Option<Iri<String>>,
+        prefixes: &HashMap<&str, &str>,
+    ) -> Result<(), SRDFGraphError> {
+        self.base = match (&self.base, base) {
+            (None, None) => None,
+            (Some(b), None) => Some(b.clone()),
+            (_, Some(b)) => Some(IriS::new_unchecked(b.as_str())),
+        };
+        let pm = PrefixMap::from_hashmap(prefixes)?;
+        self.merge_prefixes(pm)
+    }
+
+    /// Quads in named graphs are merged into the default graph or kept apart
+    /// depending on the named graph mode
+    fn add_quad(&mut self, quad: QuadRef) {
+        match (quad.graph_name, &self.named_graph_mode) {
+            (GraphNameRef::DefaultGraph, _) | (_, NamedGraphMode::Merge) => {
+                self.graph.insert(quad);
+            }
+            (_, NamedGraphMode::Keep) => {
+                self.named_graphs.insert(quad);
+            }
+        }
+    }
+
     pub fn from_reader<R: BufRead>(
         read: R,
         format: &RDFFormat,
@@ -439,7 +603,7 @@ impl SRDFBasic for SRDFGraph {
                     Literal::StringLiteral { lexical_form, lang } => match lang {
                         Some(lang) => OxLiteral::new_language_tagged_literal_unchecked(
                             lexical_form,
-                            lang.to_string(),
+                            lang.value(),
                         ),
                         None => OxLiteral::new_simple_literal(lexical_form),
                     },
@@ -452,10 +616,7 @@ impl SRDFBasic for SRDFGraph {
                             let n: i128 = *n as i128;
                             OxLiteral::from(n)
                         }
-                        NumericLiteral::Decimal(d) => {
-                            let decimal = cnv_decimal(d);
-                            OxLiteral::from(decimal)
-                        }
+                        NumericLiteral::Decimal(d) => cnv_decimal(d),
                         NumericLiteral::Double(d) => OxLiteral::from(*d),
                     },
                     Literal::BooleanLiteral(b) => OxLiteral::from(*b),
@@ -474,8 +635,14 @@ fn cnv_iri_ref(iri_ref: &IriRef) -> OxNamedNode {
     OxNamedNode::new_unchecked(iri_ref.to_string())
 }
 
-fn cnv_decimal(_d: &Decimal) -> OxDecimal {
-    todo!()
+/// Converts a decimal to an `xsd:decimal` literal. Decimals that don't fit
+/// in the decimals of oxrdf, which have at most 18 fractional digits, are
+/// kept with their lexical form
+fn cnv_decimal(d: &Decimal) -> OxLiteral {
+    match OxDecimal::from_str(&d.to_string()) {
+        Ok(decimal) => OxLiteral::from(decimal),
+        Err(_) => OxLiteral::new_typed_literal(d.to_string(), oxrdf::vocab::xsd::DECIMAL),
+    }
 }
 
 impl SRDF for SRDFGraph {
@@ -652,6 +819,39 @@ impl AsyncSRDF for SRDFGraph {
         }
         Ok(results)
     }
+
+    async fn get_outgoing_arcs(
+        &self,
+        subjects: &[OxSubject],
+    ) -> Result<ArcsByNode<OxSubject, OxNamedNode, OxTerm>, SRDFGraphError> {
+        let mut results = HashMap::new();
+        for subject in subjects {
+            results.insert(subject.clone(), self.outgoing_arcs(subject)?);
+        }
+        Ok(results)
+    }
+
+    async fn get_incoming_arcs(
+        &self,
+        objects: &[OxTerm],
+    ) -> Result<ArcsByNode<OxTerm, OxNamedNode, OxSubject>, SRDFGraphError> {
+        let mut results = HashMap::new();
+        for object in objects {
+            results.insert(object.clone(), self.incoming_arcs(object)?);
+        }
+        Ok(results)
+    }
+
+    async fn get_triples_with_predicate(
+        &self,
+        pred: &OxNamedNode,
+    ) -> Result<Vec<(OxSubject, OxTerm)>, SRDFGraphError> {
+        Ok(self
+            .graph
+            .triples_for_predicate(pred)
+            .map(|triple| (triple.subject.into_owned(), triple.object.into_owned()))
+            .collect())
+    }
 }
 
 impl FocusRDF for SRDFGraph {
@@ -680,6 +880,13 @@ impl SRDFBuilder for SRDFGraph {
         Ok(())
     }
 
+    fn resolve_iri_ref(&self, iri: &IriRef) -> Result<IriS, Self::Err> {
+        match iri {
+            IriRef::Iri(iri) => Ok(iri.clone()),
+            IriRef::Prefixed { prefix, local } => Ok(self.resolve_prefix_local(prefix, local)?),
+        }
+    }
+
     fn add_triple(
         &mut self,
         subj: &Self::Subject,
@@ -691,6 +898,20 @@ impl SRDFBuilder for SRDFGraph {
         Ok(())
     }
 
+    fn add_node_triple(
+        &mut self,
+        node: &crate::RDFNode,
+        pred: &Self::IRI,
+        obj: &Self::Term,
+    ) -> Result<(), Self::Err> {
+        match Self::object_as_subject(node) {
+            Some(subj) => self.add_triple(&subj, pred, obj),
+            None => Err(SRDFGraphError::ExpectedSubject {
+                node: node.to_string(),
+            }),
+        }
+    }
+
     fn remove_triple(
         &mut self,
         subj: &Self::Subject,
@@ -703,16 +924,11 @@ impl SRDFBuilder for SRDFGraph {
     }
 
     fn add_type(&mut self, node: &crate::RDFNode, r#type: Self::Term) -> Result<(), Self::Err> {
-        match Self::object_as_subject(node) {
-            Some(subj) => {
-                let triple = OxTriple::new(subj, rdf_type(), r#type.clone());
-                self.graph.insert(&triple);
-                Ok(())
-            }
-            None => {
-                panic!("Error adding type to {node} because it can't be converted to a subject")
-            }
-        }
+        self.add_node_triple(node, &rdf_type(), &r#type)
+    }
+
+    fn add_bnode(&mut self) -> Result<Self::BNode, Self::Err> {
+        Ok(OxBlankNode::default())
     }
 
     fn empty() -> Self {
@@ -721,11 +937,21 @@ impl SRDFBuilder for SRDFGraph {
             graph: Graph::new(),
             pm: PrefixMap::new(),
             base: None,
+            named_graphs: Dataset::new(),
+            named_graph_mode: NamedGraphMode::default(),
         }
     }
 
+    /// Fails if there are named graphs and the format only supports triples
     fn serialize<W: Write>(&self, format: RDFFormat, write: &mut W) -> Result<(), Self::Err> {
-        let mut serializer = RdfSerializer::from_format(cnv_rdf_format(format));
+        let rdf_format = cnv_rdf_format(format);
+        if !self.named_graphs.is_empty() && !rdf_format.supports_datasets() {
+            return Err(SRDFGraphError::NamedGraphsNotSupported {
+                format: rdf_format.name().to_string(),
+                count: self.named_graphs.len(),
+            });
+        }
+        let mut serializer = RdfSerializer::from_format(rdf_format);
 
         for (prefix, iri) in &self.pm.map {
             serializer = serializer.with_prefix(prefix, iri.as_str()).unwrap();
@@ -735,6 +961,9 @@ impl SRDFBuilder for SRDFGraph {
         for triple in self.graph.iter() {
             writer.serialize_triple(triple)?;
         }
+        for quad in self.named_graphs.iter() {
+            writer.serialize_quad(quad)?;
+        }
         writer.finish()?;
         Ok(())
     }
@@ -758,8 +987,39 @@ fn rdf_type() -> OxNamedNode {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{int, srdf, SRDFGraph, SRDF};
+    use crate::{int, srdf, RDFNode, SRDFGraph, SRDF};
     use iri_s::iri;
+    use oxrdf::dataset::CanonicalizationAlgorithm;
+
+    #[test]
+    fn test_changed_and_dependent_nodes() {
+        let before = r#"prefix : <http://example.org/>
+            :alice :knows :bob .
+            :bob :name "Bob" .
+            :carol :name "Carol" ."#;
+        let after = r#"prefix : <http://example.org/>
+            :alice :knows :bob .
+            :bob :name "Robert" .
+            :carol :name "Carol" ."#;
+        let before =
+            SRDFGraph::from_str(before, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let after =
+            SRDFGraph::from_str(after, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let node = |name: &str| {
+            OxTerm::NamedNode(OxNamedNode::new_unchecked(format!(
+                "http://example.org/{name}"
+            )))
+        };
+        let lit = |str: &str| OxTerm::Literal(OxLiteral::new_simple_literal(str));
+        let changed = before.changed_nodes(&after);
+        assert_eq!(
+            changed,
+            HashSet::from([node("bob"), lit("Bob"), lit("Robert")])
+        );
+        let dependent = after.dependent_nodes(&changed, false);
+        assert!(dependent.contains(&node("alice")));
+        assert!(!dependent.contains(&node("carol")));
+    }
 
     #[tokio::test]
     async fn parse_get_predicates() {
@@ -825,6 +1085,21 @@ mod tests {
         assert_eq!(outgoing.get(&p), Some(&HashSet::from([one])))
     }
 
+    #[test]
+    fn test_lang_literal_roundtrip() {
+        let s = r#"prefix : <http://example.org/>
+        :x :p "hi"@en .
+        "#;
+
+        let graph = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let x = <SRDFGraph as SRDFBasic>::iri_s2subject(&iri!("http://example.org/x"));
+        let p = <SRDFGraph as SRDFBasic>::iri_s2iri(&iri!("http://example.org/p"));
+        let terms = srdf::SRDF::objects_for_subject_predicate(&graph, &x, &p).unwrap();
+        let term = terms.iter().next().unwrap().clone();
+        let object = <SRDFGraph as SRDFBasic>::term_as_object(&term);
+        assert_eq!(<SRDFGraph as SRDFBasic>::object_as_term(&object), term)
+    }
+
     #[test]
     fn test_parser() {
         use crate::{ok, rdf_parser, RDFNodeParse};
@@ -1032,6 +1307,131 @@ mod tests {
         let result = parser.parse(&x, graph);
         assert!(result.is_ok())
     }
+
+    #[test]
+    fn test_trig_named_graphs() {
+        let s = r#"prefix : <http://example.org/>
+                   :x :p 1 .
+                   :g { :y :p 2 . }
+        "#;
+        let merged = SRDFGraph::from_str(s, &RDFFormat::TriG, None, &ReaderMode::Strict).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert!(merged.named_graphs().is_empty());
+
+        let mut kept = SRDFGraph::new().with_named_graph_mode(NamedGraphMode::Keep);
+        kept.merge_from_reader(s.as_bytes(), &RDFFormat::TriG, None, &ReaderMode::Strict)
+            .unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept.named_graphs().len(), 1);
+        assert_eq!(kept.quads().count(), 2);
+
+        let mut buffer = Vec::new();
+        kept.serialize(RDFFormat::NQuads, &mut buffer).unwrap();
+        let mut parsed = SRDFGraph::new().with_named_graph_mode(NamedGraphMode::Keep);
+        parsed
+            .merge_from_reader(
+                buffer.as_slice(),
+                &RDFFormat::NQuads,
+                None,
+                &ReaderMode::Strict,
+            )
+            .unwrap();
+        assert_eq!(parsed.named_graphs(), kept.named_graphs());
+
+        let mut buffer = Vec::new();
+        assert!(matches!(
+            kept.serialize(RDFFormat::Turtle, &mut buffer),
+            Err(SRDFGraphError::NamedGraphsNotSupported { count: 1, .. })
+        ));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_n3_facts() {
+        let s = r#"prefix : <http://example.org/>
+                   :x :p 1 .
+                   { ?x :p 1 } => { ?x a :Q } .
+        "#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::N3, None, &ReaderMode::Lax).unwrap();
+        assert_eq!(graph.len(), 1);
+        assert!(SRDFGraph::from_str(s, &RDFFormat::N3, None, &ReaderMode::Strict).is_err());
+    }
+
+    #[test]
+    fn test_add_type_to_literal_fails() {
+        let mut graph = SRDFGraph::empty();
+        let person = SRDFGraph::iri_s2term(&iri!("http://example.org/Person"));
+        let alice = RDFNode::iri(iri!("http://example.org/alice"));
+        assert!(graph.add_type(&alice, person.clone()).is_ok());
+        assert!(graph.add_type(&RDFNode::literal(int!(1)), person).is_err());
+        assert_eq!(graph.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_iri_ref() {
+        let mut graph = SRDFGraph::empty();
+        graph.add_prefix("", &iri!("http://example.org/")).unwrap();
+        assert_eq!(
+            graph.resolve_iri_ref(&IriRef::prefixed("", "x")).unwrap(),
+            iri!("http://example.org/x")
+        );
+        assert!(graph.resolve_iri_ref(&IriRef::prefixed("ex", "x")).is_err());
+    }
+
+    #[test]
+    fn test_decimals_to_terms() {
+        let decimal = |str: &str| {
+            let decimal = Decimal::from_str(str).unwrap();
+            let object =
+                RDFNode::literal(Literal::NumericLiteral(NumericLiteral::Decimal(decimal)));
+            SRDFGraph::object_as_term(&object)
+        };
+        assert_eq!(
+            decimal("1.5"),
+            OxTerm::Literal(OxLiteral::from(OxDecimal::from_str("1.5").unwrap()))
+        );
+        // more fractional digits than the decimals of oxrdf
+        assert_eq!(
+            decimal("0.12345678901234567890"),
+            OxTerm::Literal(OxLiteral::new_typed_literal(
+                "0.12345678901234567890",
+                oxrdf::vocab::xsd::DECIMAL
+            ))
+        );
+    }
+
+    #[test]
+    fn test_formats_round_trip() {
+        let s = r#"prefix : <http://example.org/>
+                   :x :p 1, "hi"@en ;
+                      :q [ :r :y ] .
+        "#;
+        let canonical = |graph: &SRDFGraph| {
+            let mut dataset = Dataset::from_iter(graph.quads());
+            dataset.canonicalize(CanonicalizationAlgorithm::Unstable);
+            dataset
+        };
+        let graph = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        for format in [
+            RDFFormat::Turtle,
+            RDFFormat::NTriples,
+            RDFFormat::RDFXML,
+            RDFFormat::TriG,
+            RDFFormat::N3,
+            RDFFormat::NQuads,
+        ] {
+            let mut buffer = Vec::new();
+            graph.serialize(format, &mut buffer).unwrap();
+            let parsed =
+                SRDFGraph::from_reader(buffer.as_slice(), &format, None, &ReaderMode::Strict)
+                    .unwrap();
+            assert_eq!(
+                canonical(&parsed),
+                canonical(&graph),
+                "Round trip through {format:?}"
+            );
+        }
+    }
 }
 
 #[test]
@@ -1057,6 +1457,30 @@ fn test_rdf_list() {
     )
 }
 
+/// Converts an N3 statement to an RDF triple when it is a fact, i.e. it is
+/// asserted in the default graph and contains neither variables nor formulas
+fn n3_fact(quad: &N3Quad, formulas: &HashSet<OxBlankNode>) -> Option<OxTriple> {
+    if quad.graph_name != GraphName::DefaultGraph {
+        return None;
+    }
+    let subject = match &quad.subject {
+        N3Term::NamedNode(n) => OxSubject::NamedNode(n.clone()),
+        N3Term::BlankNode(b) if !formulas.contains(b) => OxSubject::BlankNode(b.clone()),
+        _ => return None,
+    };
+    let predicate = match &quad.predicate {
+        N3Term::NamedNode(n) => n.clone(),
+        _ => return None,
+    };
+    let object = match &quad.object {
+        N3Term::NamedNode(n) => OxTerm::NamedNode(n.clone()),
+        N3Term::BlankNode(b) if !formulas.contains(b) => OxTerm::BlankNode(b.clone()),
+        N3Term::Literal(l) => OxTerm::Literal(l.clone()),
+        _ => return None,
+    };
+    Some(OxTriple::new(subject, predicate, object))
+}
+
 fn triple_to_quad(t: TripleRef, graph_name: GraphName) -> Quad {
     let subj: oxrdf::Subject = t.subject.into();
     let pred: oxrdf::NamedNode = t.predicate.into();
@@ -1075,6 +1499,18 @@ pub enum ReaderMode {
     Lax,
 }
 
+/// How the named graphs of quad formats like TriG and N-Quads are loaded
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NamedGraphMode {
+    /// The triples of the named graphs are added to the default graph
+    #[default]
+    Merge,
+
+    /// The named graphs are kept as quads, apart from the default graph
+    Keep,
+}
+
 impl ReaderMode {
     pub fn is_strict(&self) -> bool {
         matches!(self, ReaderMode::Strict)
@@ -29,6 +29,15 @@ pub enum SRDFGraphError {
         turtle_error: TurtleParseError,
     },
 
+    #[error("Node {node} cannot be the subject of a triple")]
+    ExpectedSubject { node: String },
+
+    #[error("The data has {count} quads in named graphs, which can't be serialized as {format}, use a format that supports named graphs, like TriG or N-Quads")]
+    NamedGraphsNotSupported { format: String, count: usize },
+
+    #[error("N3 statement {statement} is not a fact, only facts are supported")]
+    N3NotFact { statement: String },
+
     #[error(transparent)]
     IriParseError {
         #[from]
@@ -1811,11 +1811,16 @@ where
     type Output = Vec<A>;
 
     fn parse_impl(&mut self, rdf: &mut RDF) -> PResult<Self::Output> {
+        // the focus node is restored so that the following parsers are not affected
+        let focus = rdf.get_focus().clone();
         let mut results = Vec::new();
         for node in self.nodes.iter() {
             rdf.set_focus(node);
-            let value = self.parser.parse_impl(rdf)?;
-            results.push(value)
+            let value = self.parser.parse_impl(rdf);
+            if let Some(focus) = &focus {
+                rdf.set_focus(focus);
+            }
+            results.push(value?)
         }
         Ok(results)
     }
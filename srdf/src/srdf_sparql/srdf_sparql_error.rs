@@ -8,9 +8,13 @@ use crate::SparqlVars;
 
 #[derive(Error, Debug)]
 pub enum SRDFSparqlError {
+    #[cfg(all(feature = "http", not(target_arch = "wasm32")))]
     #[error("HTTP Request error: {e:?}")]
     HTTPRequestError { e: reqwest::Error },
 
+    #[error("Can't query {url}: queries to SPARQL endpoints need the http feature and a non-wasm target")]
+    HTTPNotSupported { url: String },
+
     #[error("URL parser error: {e:?}")]
     URLParseError { e: url::ParseError },
 
@@ -25,6 +29,12 @@ pub enum SRDFSparqlError {
         err: IriParseError,
     },
 
+    #[error("Blank node {bnode} can't be used as the subject of a query to an endpoint")]
+    BlankNodeSubject { bnode: String },
+
+    #[error("Blank node {bnode} can't be used as the object of a query to an endpoint")]
+    BlankNodeObject { bnode: String },
+
     #[error("Unknown name for endpoint: {name}")]
     UnknownEndpontName { name: String },
 
@@ -53,6 +63,7 @@ pub enum SRDFSparqlError {
     },
 }
 
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 impl From<reqwest::Error> for SRDFSparqlError {
     fn from(e: reqwest::Error) -> SRDFSparqlError {
         SRDFSparqlError::HTTPRequestError { e }
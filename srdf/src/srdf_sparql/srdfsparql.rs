@@ -1,50 +1,59 @@
 use crate::{lang::Lang, literal::Literal, Object, SRDFSparqlError};
 use crate::{
-    AsyncSRDF, QuerySRDF, QuerySRDF2, QuerySolution2, QuerySolutionIter, QuerySolutions, SRDFBasic,
-    VarName2, SRDF,
+    ArcsByNode, AsyncQuerySRDF, AsyncSRDF, AsyncSolutions, QuerySRDF, QuerySRDF2, QuerySolution2,
+    QuerySolutionIter, QuerySolutions, SRDFBasic, SRDFGraph, Triple, VarName2, SRDF,
 };
 use async_trait::async_trait;
 use colored::*;
 use iri_s::IriS;
 use oxrdf::{
     BlankNode as OxBlankNode, Literal as OxLiteral, NamedNode as OxNamedNode, Subject as OxSubject,
-    Term as OxTerm,
+    Term as OxTerm, Variable as OxVariable,
 };
+use oxrdfio::{RdfFormat, RdfParser};
 use prefixmap::{IriRef, PrefixMap};
 use regex::Regex;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
 use reqwest::{
     blocking::Client,
     header::{self, ACCEPT, USER_AGENT},
-    Url,
+    Client as AsyncClient,
 };
 use sparesults::{
     QueryResultsFormat, QueryResultsParser, QuerySolution as OxQuerySolution,
     ReaderQueryResultsParserOutput,
 };
 use std::rc::Rc;
+use std::sync::OnceLock;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     fmt::Display,
     str::FromStr,
 };
+use url::Url;
 
 type Result<A> = std::result::Result<A, SRDFSparqlError>;
 
 /// Implements SRDF interface as a SPARQL endpoint
+///
+/// The blocking methods of [`SRDF`] and [`QuerySRDF2`] and the asynchronous methods of [`AsyncSRDF`]
+/// and [`AsyncQuerySRDF`] use different HTTP clients
 #[derive(Debug, Clone)]
 pub struct SRDFSparql {
     endpoint_iri: IriS,
     prefixmap: PrefixMap,
-    client: Client,
+    client: OnceLock<Client>,
+    async_client: AsyncClient,
 }
 
 impl SRDFSparql {
     pub fn new(iri: &IriS) -> Result<SRDFSparql> {
-        let client = sparql_client()?;
+        let async_client = sparql_async_client()?;
         Ok(SRDFSparql {
             endpoint_iri: iri.clone(),
             prefixmap: PrefixMap::new(),
-            client,
+            client: OnceLock::new(),
+            async_client,
         })
     }
 
@@ -71,6 +80,40 @@ impl SRDFSparql {
         let str: String = format!("{}", lit);
         format!("{}", str.red())
     }
+
+    /// The blocking client is created when it is first needed, because blocking clients can't be
+    /// created or dropped inside an asynchronous runtime
+    fn client(&self) -> Result<&Client> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+        let client = sparql_client()?;
+        Ok(self.client.get_or_init(|| client))
+    }
+
+    async fn select_async(&self, query: &str) -> Result<Vec<OxQuerySolution>> {
+        let (_, solutions) = self.select_with_variables_async(query).await?;
+        Ok(solutions)
+    }
+
+    async fn select_with_variables_async(
+        &self,
+        query: &str,
+    ) -> Result<(Vec<OxVariable>, Vec<OxQuerySolution>)> {
+        let url = Url::parse_with_params(self.endpoint_iri.as_str(), &[("query", query)])?;
+        tracing::debug!("SPARQL query: {}", url);
+        let body = get_body_async(&self.async_client, url).await?;
+        parse_solutions(body)
+    }
+
+    /// Sends a SELECT query in the body of a POST request
+    async fn select_post_async(&self, query: &str) -> Result<Vec<OxQuerySolution>> {
+        let url = Url::parse(self.endpoint_iri.as_str())?;
+        tracing::debug!("SPARQL query (POST to {url}): {query}");
+        let body = post_body_async(&self.async_client, url, query).await?;
+        let (_, solutions) = parse_solutions(body)?;
+        Ok(solutions)
+    }
 }
 
 impl FromStr for SRDFSparql {
@@ -80,12 +123,7 @@ impl FromStr for SRDFSparql {
         let re_iri = Regex::new(r"<(.*)>").unwrap();
         if let Some(iri_str) = re_iri.captures(s) {
             let iri_s = IriS::from_str(&iri_str[1])?;
-            let client = sparql_client()?;
-            Ok(SRDFSparql {
-                endpoint_iri: iri_s,
-                prefixmap: PrefixMap::new(),
-                client,
-            })
+            SRDFSparql::new(&iri_s)
         } else {
             match s.to_lowercase().as_str() {
                 "wikidata" => SRDFSparql::wikidata(),
@@ -187,7 +225,7 @@ impl SRDFBasic for SRDFSparql {
 
     fn term_as_object(term: &Self::Term) -> Object {
         match term {
-            Self::Term::BlankNode(bn) => Object::BlankNode(bn.to_string()),
+            Self::Term::BlankNode(bn) => Object::BlankNode(bn.as_str().to_string()),
             Self::Term::Literal(lit) => match lit.to_owned().destruct() {
                 (s, None, None) => Object::Literal(Literal::StringLiteral {
                     lexical_form: s,
@@ -264,12 +302,13 @@ impl SRDFBasic for SRDFSparql {
         OxTerm::BlankNode(bnode)
     }
 
-    fn object_as_term(_obj: &Object) -> Self::Term {
-        todo!()
+    fn object_as_term(obj: &Object) -> Self::Term {
+        // Both implementations are based on oxrdf terms
+        SRDFGraph::object_as_term(obj)
     }
 
-    fn bnode_as_subject(_bnode: Self::BNode) -> Self::Subject {
-        todo!()
+    fn bnode_as_subject(bnode: Self::BNode) -> Self::Subject {
+        OxSubject::BlankNode(bnode)
     }
 }
 
@@ -284,29 +323,140 @@ impl AsyncSRDF for SRDFSparql {
 
     async fn get_predicates_subject(&self, subject: &OxSubject) -> Result<HashSet<OxNamedNode>> {
         let query = format!(r#"select ?pred where {{ {} ?pred ?obj . }}"#, subject);
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
         let mut results = HashSet::new();
-        for solution in solutions {
-            let n = get_iri_solution(solution, "pred")?;
-            results.insert(n.clone());
+        for solution in self.select_async(query.as_str()).await? {
+            results.insert(get_iri_solution(&solution, "pred")?);
         }
         Ok(results)
     }
 
     async fn get_objects_for_subject_predicate(
         &self,
-        _subject: &OxSubject,
-        _pred: &OxNamedNode,
+        subject: &OxSubject,
+        pred: &OxNamedNode,
     ) -> Result<HashSet<OxTerm>> {
-        todo!();
+        let query = format!(r#"select ?obj where {{ {} {} ?obj . }}"#, subject, pred);
+        let mut results = HashSet::new();
+        for solution in self.select_async(query.as_str()).await? {
+            results.insert(get_object_solution(&solution, "obj")?);
+        }
+        Ok(results)
     }
 
     async fn get_subjects_for_object_predicate(
         &self,
-        _object: &OxTerm,
-        _pred: &OxNamedNode,
+        object: &OxTerm,
+        pred: &OxNamedNode,
     ) -> Result<HashSet<OxSubject>> {
-        todo!();
+        let query = format!(r#"select ?subj where {{ ?subj {} {} . }}"#, pred, object);
+        let mut results = HashSet::new();
+        for solution in self.select_async(query.as_str()).await? {
+            results.insert(get_subject_solution(&solution, "subj")?);
+        }
+        Ok(results)
+    }
+
+    /// Retrieves the arcs of all the subjects with a single query, which is
+    /// sent with POST as it can be too long for a URL.
+    ///
+    /// Blank nodes can't be used in queries, so they return an error
+    async fn get_outgoing_arcs(
+        &self,
+        subjects: &[OxSubject],
+    ) -> Result<ArcsByNode<OxSubject, OxNamedNode, OxTerm>> {
+        let mut results: ArcsByNode<OxSubject, OxNamedNode, OxTerm> = HashMap::new();
+        if subjects.is_empty() {
+            return Ok(results);
+        }
+        for subject in subjects {
+            check_not_bnode(subject)?;
+        }
+        let values = sparql_values(subjects.iter());
+        let query = format!(
+            "select ?subj ?pred ?obj where {{ VALUES ?subj {{ {values} }} ?subj ?pred ?obj }}"
+        );
+        for solution in self.select_post_async(query.as_str()).await? {
+            let subj = get_subject_solution(&solution, "subj")?;
+            let pred = get_iri_solution(&solution, "pred")?;
+            let obj = get_object_solution(&solution, "obj")?;
+            results
+                .entry(subj)
+                .or_default()
+                .entry(pred)
+                .or_default()
+                .insert(obj);
+        }
+        Ok(results)
+    }
+
+    /// Retrieves the arcs of all the objects with a single query, which is
+    /// sent with POST as it can be too long for a URL.
+    ///
+    /// Blank nodes can't be used in queries, so they return an error
+    async fn get_incoming_arcs(
+        &self,
+        objects: &[OxTerm],
+    ) -> Result<ArcsByNode<OxTerm, OxNamedNode, OxSubject>> {
+        let mut results: ArcsByNode<OxTerm, OxNamedNode, OxSubject> = HashMap::new();
+        if objects.is_empty() {
+            return Ok(results);
+        }
+        for object in objects {
+            check_object_not_bnode(object)?;
+        }
+        let values = sparql_values(objects.iter());
+        let query = format!(
+            "select ?subj ?pred ?obj where {{ VALUES ?obj {{ {values} }} ?subj ?pred ?obj }}"
+        );
+        for solution in self.select_post_async(query.as_str()).await? {
+            let subj = get_subject_solution(&solution, "subj")?;
+            let pred = get_iri_solution(&solution, "pred")?;
+            let obj = get_object_solution(&solution, "obj")?;
+            results
+                .entry(obj)
+                .or_default()
+                .entry(pred)
+                .or_default()
+                .insert(subj);
+        }
+        Ok(results)
+    }
+
+    async fn get_triples_with_predicate(
+        &self,
+        pred: &OxNamedNode,
+    ) -> Result<Vec<(OxSubject, OxTerm)>> {
+        let query = format!(r#"select ?subj ?obj where {{ ?subj {} ?obj . }}"#, pred);
+        let mut results = Vec::new();
+        for solution in self.select_async(query.as_str()).await? {
+            let subj = get_subject_solution(&solution, "subj")?;
+            let obj = get_object_solution(&solution, "obj")?;
+            results.push((subj, obj));
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl AsyncQuerySRDF for SRDFSparql {
+    async fn get_query_select(&self, query: &str) -> Result<AsyncSolutions<OxTerm>> {
+        let (variables, solutions) = self.select_with_variables_async(query).await?;
+        let variables = variables
+            .iter()
+            .map(|v| VarName2::new(v.as_str()))
+            .collect();
+        let rows = solutions
+            .iter()
+            .map(|solution| solution.values().to_vec())
+            .collect();
+        Ok((variables, rows))
+    }
+
+    async fn get_query_ask(&self, query: &str) -> Result<bool> {
+        let url = Url::parse_with_params(self.endpoint_iri.as_str(), &[("query", query)])?;
+        tracing::debug!("SPARQL ASK query: {}", url);
+        let body = get_body_async(&self.async_client, url).await?;
+        parse_boolean(body)
     }
 }
 
@@ -317,10 +467,10 @@ impl SRDF for SRDFSparql {
             "SPARQL query (get predicates for subject {subject}): {}",
             query
         );
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        let solutions = make_sparql_query(query.as_str(), self.client()?, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_iri_solution(solution, "pred")?;
+            let n = get_iri_solution(&solution, "pred")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -332,10 +482,10 @@ impl SRDF for SRDFSparql {
         pred: &OxNamedNode,
     ) -> Result<HashSet<OxTerm>> {
         let query = format!(r#"select ?obj where {{ {} {} ?obj . }}"#, subject, pred);
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        let solutions = make_sparql_query(query.as_str(), self.client()?, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_object_solution(solution, "obj")?;
+            let n = get_object_solution(&solution, "obj")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -347,10 +497,10 @@ impl SRDF for SRDFSparql {
         object: &OxTerm,
     ) -> Result<HashSet<OxSubject>> {
         let query = format!(r#"select ?subj where {{ ?subj {} {} . }}"#, pred, object);
-        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        let solutions = make_sparql_query(query.as_str(), self.client()?, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_subject_solution(solution, "subj")?;
+            let n = get_subject_solution(&solution, "subj")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -360,9 +510,10 @@ impl SRDF for SRDFSparql {
         &self,
         subject: &Self::Subject,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>> {
+        check_not_bnode(subject)?;
         outgoing_neighs(
             subject.to_string().as_str(),
-            &self.client,
+            self.client()?,
             &self.endpoint_iri,
         )
     }
@@ -373,7 +524,7 @@ impl SRDF for SRDFSparql {
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>> {
         incoming_neighs(
             object.to_string().as_str(),
-            &self.client,
+            self.client()?,
             &self.endpoint_iri,
         )
     }
@@ -386,20 +537,39 @@ impl SRDF for SRDFSparql {
         (HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>),
         Self::Err,
     > {
-        outgoing_neighs_from_list(subject, preds, &self.client, &self.endpoint_iri)
+        check_not_bnode(subject)?;
+        outgoing_neighs_from_list(subject, preds, true, self.client()?, &self.endpoint_iri)
+    }
+
+    fn outgoing_arcs_of_preds(
+        &self,
+        subject: &Self::Subject,
+        preds: &[Self::IRI],
+    ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>> {
+        check_not_bnode(subject)?;
+        outgoing_neighs_from_list(subject, preds, false, self.client()?, &self.endpoint_iri)
+            .map(|(arcs, _remainder)| arcs)
     }
 
     fn triples_with_predicate(
         &self,
-        _pred: &Self::IRI,
+        pred: &Self::IRI,
     ) -> std::prelude::v1::Result<Vec<crate::Triple<Self>>, Self::Err> {
-        todo!()
+        let query = format!(r#"select ?subj ?obj where {{ ?subj {} ?obj . }}"#, pred);
+        let solutions = make_sparql_query(query.as_str(), self.client()?, &self.endpoint_iri)?;
+        let mut results = Vec::new();
+        for solution in solutions {
+            let obj = get_object_solution(&solution, "obj")?;
+            let subj = get_subject_solution(&solution, "subj")?;
+            results.push(crate::Triple::new(subj, pred.clone(), obj));
+        }
+        Ok(results)
     }
 }
 
 impl QuerySRDF for SRDFSparql {
     fn query_select(&self, query: &str) -> Result<QuerySolutionIter<SRDFSparql>> {
-        let solutions = make_sparql_query(query, &self.client, &self.endpoint_iri)?;
+        let solutions = make_sparql_query(query, self.client()?, &self.endpoint_iri)?;
         let mut variables = Vec::new();
         let mut values = Vec::new();
         for solution in solutions {
@@ -415,36 +585,33 @@ impl QuerySRDF for SRDFSparql {
     }
 
     fn query_ask(&self, query: &str) -> Result<bool> {
-        make_sparql_query(query, &self.client, &self.endpoint_iri)?
-            .first()
-            .and_then(|query_solution| query_solution.get(0))
-            .and_then(|term| match term {
-                OxTerm::Literal(literal) => Some(literal.value()),
-                _ => None,
-            })
-            .and_then(|value| value.parse().ok())
-            .ok_or_else(|| todo!())
+        make_sparql_query_ask(query, self.client()?, &self.endpoint_iri)
     }
 }
 
 impl QuerySRDF2 for SRDFSparql {
     fn query_select(&self, query: &str) -> Result<QuerySolutions<Self>> {
-        let solutions = make_sparql_query(query, &self.client, &self.endpoint_iri)?;
+        let (variables, solutions) =
+            make_sparql_query_with_variables(query, self.client()?, &self.endpoint_iri)?;
+        let variables = variables
+            .iter()
+            .map(|v| VarName2::new(v.as_str()))
+            .collect();
         let qs: Vec<QuerySolution2<SRDFSparql>> =
             solutions.iter().map(cnv_query_solution).collect();
-        Ok(QuerySolutions::new(qs))
+        Ok(QuerySolutions::new(variables, qs))
     }
 
     fn query_ask(&self, query: &str) -> Result<bool> {
-        make_sparql_query(query, &self.client, &self.endpoint_iri)?
-            .first()
-            .and_then(|query_solution| query_solution.get(0))
-            .and_then(|term| match term {
-                OxTerm::Literal(literal) => Some(literal.value()),
-                _ => None,
-            })
-            .and_then(|value| value.parse().ok())
-            .ok_or_else(|| todo!())
+        make_sparql_query_ask(query, self.client()?, &self.endpoint_iri)
+    }
+
+    fn query_construct(&self, query: &str) -> Result<Vec<Triple<Self>>> {
+        make_sparql_query_graph(query, "CONSTRUCT", self.client()?, &self.endpoint_iri)
+    }
+
+    fn query_describe(&self, query: &str) -> Result<Vec<Triple<Self>>> {
+        make_sparql_query_graph(query, "DESCRIBE", self.client()?, &self.endpoint_iri)
     }
 }
 
@@ -462,42 +629,192 @@ fn cnv_query_solution(qs: &OxQuerySolution) -> QuerySolution2<SRDFSparql> {
     QuerySolution2::new(Rc::new(variables), values)
 }
 
-fn sparql_client() -> Result<Client> {
+/// Without the `http` feature endpoints can be declared but queries to them fail
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+struct Client;
+
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+struct AsyncClient;
+
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+fn sparql_headers() -> header::HeaderMap {
     let mut headers = header::HeaderMap::new();
     headers.insert(
         ACCEPT,
         header::HeaderValue::from_static("application/sparql-results+json"),
     );
     headers.insert(USER_AGENT, header::HeaderValue::from_static("rudof"));
+    headers
+}
+
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+fn sparql_client() -> Result<Client> {
     let client = reqwest::blocking::Client::builder()
-        .default_headers(headers)
+        .default_headers(sparql_headers())
         .build()?;
     Ok(client)
 }
 
-fn make_sparql_query(
-    query: &str,
-    client: &Client,
-    endpoint_iri: &IriS,
-) -> Result<Vec<OxQuerySolution>> {
-    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
-    tracing::debug!("SPARQL query: {}", url);
-    let body = client.get(url).send()?.text()?;
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+fn sparql_async_client() -> Result<AsyncClient> {
+    let client = AsyncClient::builder()
+        .default_headers(sparql_headers())
+        .build()?;
+    Ok(client)
+}
+
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+fn sparql_client() -> Result<Client> {
+    Ok(Client)
+}
+
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+fn sparql_async_client() -> Result<AsyncClient> {
+    Ok(AsyncClient)
+}
+
+/// Sends a GET request and returns the body of the response, overriding the default `Accept` header if `accept` is given
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+fn get_body(client: &Client, url: Url, accept: Option<&str>) -> Result<String> {
+    let mut request = client.get(url);
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept)
+    }
+    Ok(request.send()?.error_for_status()?.text()?)
+}
+
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+fn get_body(_client: &Client, url: Url, _accept: Option<&str>) -> Result<String> {
+    Err(SRDFSparqlError::HTTPNotSupported {
+        url: url.to_string(),
+    })
+}
+
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+async fn get_body_async(client: &AsyncClient, url: Url) -> Result<String> {
+    Ok(client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+async fn get_body_async(_client: &AsyncClient, url: Url) -> Result<String> {
+    Err(SRDFSparqlError::HTTPNotSupported {
+        url: url.to_string(),
+    })
+}
+
+/// Sends a query in a URL-encoded POST request, as defined by the SPARQL protocol
+#[cfg(all(feature = "http", not(target_arch = "wasm32")))]
+async fn post_body_async(client: &AsyncClient, url: Url, query: &str) -> Result<String> {
+    Ok(client
+        .post(url)
+        .form(&[("query", query)])
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+#[cfg(any(not(feature = "http"), target_arch = "wasm32"))]
+async fn post_body_async(_client: &AsyncClient, url: Url, _query: &str) -> Result<String> {
+    Err(SRDFSparqlError::HTTPNotSupported {
+        url: url.to_string(),
+    })
+}
+
+/// Parses the solutions of a SELECT query in the SPARQL results JSON format
+fn parse_solutions(body: String) -> Result<(Vec<OxVariable>, Vec<OxQuerySolution>)> {
     let mut results = Vec::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
     if let ReaderQueryResultsParserOutput::Solutions(solutions) =
         json_parser.for_reader(body.as_bytes())?
     {
+        let variables = solutions.variables().to_vec();
         for solution in solutions {
             let sol = solution?;
             results.push(sol)
         }
-        Ok(results)
+        Ok((variables, results))
     } else {
         Err(SRDFSparqlError::ParsingBody { body })
     }
 }
 
+/// Parses the result of an ASK query in the SPARQL results JSON format
+fn parse_boolean(body: String) -> Result<bool> {
+    let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
+    if let ReaderQueryResultsParserOutput::Boolean(value) =
+        json_parser.for_reader(body.as_bytes())?
+    {
+        Ok(value)
+    } else {
+        Err(SRDFSparqlError::ParsingBody { body })
+    }
+}
+
+/// Nodes separated by spaces to be used in a `VALUES` clause
+fn sparql_values<T: Display>(nodes: impl Iterator<Item = T>) -> String {
+    nodes
+        .map(|node| node.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn make_sparql_query(
+    query: &str,
+    client: &Client,
+    endpoint_iri: &IriS,
+) -> Result<Vec<OxQuerySolution>> {
+    let (_, solutions) = make_sparql_query_with_variables(query, client, endpoint_iri)?;
+    Ok(solutions)
+}
+
+/// Runs a SELECT query returning the variables declared in the results and the solutions
+fn make_sparql_query_with_variables(
+    query: &str,
+    client: &Client,
+    endpoint_iri: &IriS,
+) -> Result<(Vec<OxVariable>, Vec<OxQuerySolution>)> {
+    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
+    tracing::debug!("SPARQL query: {}", url);
+    let body = get_body(client, url, None)?;
+    parse_solutions(body)
+}
+
+fn make_sparql_query_ask(query: &str, client: &Client, endpoint_iri: &IriS) -> Result<bool> {
+    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
+    tracing::debug!("SPARQL ASK query: {}", url);
+    let body = get_body(client, url, None)?;
+    parse_boolean(body)
+}
+
+/// Runs a CONSTRUCT or DESCRIBE query, whose results are the triples of a graph
+fn make_sparql_query_graph(
+    query: &str,
+    form: &str,
+    client: &Client,
+    endpoint_iri: &IriS,
+) -> Result<Vec<Triple<SRDFSparql>>> {
+    let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
+    tracing::debug!("SPARQL {form} query: {}", url);
+    let body = get_body(client, url, Some("application/n-triples"))?;
+    RdfParser::from_format(RdfFormat::NTriples)
+        .for_reader(body.as_bytes())
+        .map(|quad| match quad {
+            Ok(quad) => Ok(Triple::new(quad.subject, quad.predicate, quad.object)),
+            Err(_) => Err(SRDFSparqlError::ParsingBody { body: body.clone() }),
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub struct SparqlVars {
     values: Vec<String>,
@@ -524,7 +841,7 @@ fn outgoing_neighs(
     let obj = "obj";
     let query = format!("select ?{pred} ?{obj} where {{ {subject} ?{pred} ?{obj} }}");
     let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
-    let body = client.get(url).send()?.text()?;
+    let body = get_body(client, url, None)?;
     let mut results: HashMap<OxNamedNode, HashSet<OxTerm>> = HashMap::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
     if let ReaderQueryResultsParserOutput::Solutions(solutions) =
@@ -574,27 +891,73 @@ fn outgoing_neighs(
 
 type OutputNodes = HashMap<OxNamedNode, HashSet<OxTerm>>;
 
+/// Blank node labels are local to each result set so they can't be used to query an endpoint
+fn check_not_bnode(subject: &OxSubject) -> Result<()> {
+    match subject {
+        OxSubject::BlankNode(bnode) => Err(SRDFSparqlError::BlankNodeSubject {
+            bnode: bnode.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn check_object_not_bnode(object: &OxTerm) -> Result<()> {
+    match object {
+        OxTerm::BlankNode(bnode) => Err(SRDFSparqlError::BlankNodeObject {
+            bnode: bnode.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Obtains the values of the predicates in `preds` and, if `with_remainder` is true, the other
+/// predicates of `subject` without their values, in a single query
 fn outgoing_neighs_from_list(
     subject: &OxSubject,
     preds: &[OxNamedNode],
+    with_remainder: bool,
     client: &Client,
     endpoint_iri: &IriS,
 ) -> Result<(OutputNodes, Vec<OxNamedNode>)> {
-    // This is not an efficient way to obtain the neighbours related with a set of predicates
-    // At this moment, it obtains all neighbours and them removes the ones that are not in the list
+    let mut results: OutputNodes = HashMap::new();
     let mut remainder = Vec::new();
-    let mut all_results = outgoing_neighs(subject.to_string().as_str(), client, endpoint_iri)?;
-    let mut remove_keys = Vec::new();
-    for key in all_results.keys() {
-        if !preds.contains(key) {
-            remainder.push(key.clone());
-            remove_keys.push(key.clone());
-        }
+    if preds.is_empty() && !with_remainder {
+        return Ok((results, remainder));
     }
-    for key in remove_keys {
-        all_results.remove(&key);
+    let preds_str: Vec<String> = preds.iter().map(|p| p.to_string()).collect();
+    let values = format!(
+        "{{ VALUES ?pred {{ {} }} {subject} ?pred ?obj }}",
+        preds_str.join(" ")
+    );
+    let others = if preds.is_empty() {
+        format!("{{ select distinct ?pred where {{ {subject} ?pred ?o }} }}")
+    } else {
+        format!(
+            "{{ select distinct ?pred where {{ {subject} ?pred ?o . FILTER (?pred NOT IN ({})) }} }}",
+            preds_str.join(", ")
+        )
+    };
+    let query = match (preds.is_empty(), with_remainder) {
+        (false, false) => format!("select ?pred ?obj where {values}"),
+        (true, _) => format!("select ?pred ?obj where {others}"),
+        (false, true) => format!("select ?pred ?obj where {{ {values} UNION {others} }}"),
+    };
+    for solution in make_sparql_query(query.as_str(), client, endpoint_iri)? {
+        let pred = get_iri_solution(&solution, "pred")?;
+        // The predicates of the remainder have no value
+        match solution.get("obj") {
+            Some(obj) => match results.entry(pred) {
+                Entry::Occupied(mut vs) => {
+                    vs.get_mut().insert(obj.clone());
+                }
+                Entry::Vacant(vacant) => {
+                    vacant.insert(HashSet::from([obj.clone()]));
+                }
+            },
+            None => remainder.push(pred),
+        }
     }
-    Ok((all_results, remainder))
+    Ok((results, remainder))
 }
 
 fn incoming_neighs(
@@ -606,7 +969,7 @@ fn incoming_neighs(
     let subj = "subj";
     let query = format!("select ?{pred} ?{subj} where {{ ?{subj} ?{pred} {object} }}");
     let url = Url::parse_with_params(endpoint_iri.as_str(), &[("query", query)])?;
-    let body = client.get(url).send()?.text()?;
+    let body = get_body(client, url, None)?;
     let mut results: HashMap<OxNamedNode, HashSet<OxSubject>> = HashMap::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
     if let ReaderQueryResultsParserOutput::Solutions(solutions) =
@@ -657,7 +1020,7 @@ fn incoming_neighs(
     }
 }
 
-fn get_iri_solution(solution: OxQuerySolution, name: &str) -> Result<OxNamedNode> {
+fn get_iri_solution(solution: &OxQuerySolution, name: &str) -> Result<OxNamedNode> {
     match solution.get(name) {
         Some(v) => match v {
             OxTerm::NamedNode(n) => Ok(n.clone()),
@@ -670,7 +1033,7 @@ fn get_iri_solution(solution: OxQuerySolution, name: &str) -> Result<OxNamedNode
     }
 }
 
-fn get_object_solution(solution: OxQuerySolution, name: &str) -> Result<OxTerm> {
+fn get_object_solution(solution: &OxQuerySolution, name: &str) -> Result<OxTerm> {
     match solution.get(name) {
         Some(v) => Ok(v.clone()),
         None => Err(SRDFSparqlError::NotFoundInSolution {
@@ -680,7 +1043,7 @@ fn get_object_solution(solution: OxQuerySolution, name: &str) -> Result<OxTerm>
     }
 }
 
-fn get_subject_solution(solution: OxQuerySolution, name: &str) -> Result<OxSubject> {
+fn get_subject_solution(solution: &OxQuerySolution, name: &str) -> Result<OxSubject> {
     match solution.get(name) {
         Some(v) => match term_as_subject(v) {
             Some(s) => Ok(s),